@@ -0,0 +1,605 @@
+mod bookmarks_settings;
+mod persistence;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use bookmarks_settings::BookmarksPanelSettings;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use editor::{scroll::Autoscroll, Bias, Editor};
+use gpui::{
+    actions, uniform_list, Action, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, KeyContext, Pixels, Subscription, Task, UniformListScrollHandle, View,
+    ViewContext, VisualContext, WeakView, WindowContext,
+};
+use language::Point;
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
+use persistence::DB;
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, ListItem, Tooltip};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    panels::{register_panel, PanelPlacement},
+    Workspace, WorkspaceId,
+};
+
+const BOOKMARKS_PANEL_KEY: &'static str = "BookmarksPanel";
+
+actions!(bookmarks, [ToggleFocus, Toggle, Next, Previous]);
+
+pub fn init(cx: &mut AppContext) {
+    BookmarksPanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), BookmarksPanel::load, cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, cx| {
+                workspace.toggle_panel_focus::<BookmarksPanel>(cx);
+            })
+            .register_action(toggle)
+            .register_action(|workspace, _: &Next, cx| cycle(workspace, true, cx))
+            .register_action(|workspace, _: &Previous, cx| cycle(workspace, false, cx));
+    })
+    .detach();
+}
+
+/// A bookmarked location, labeled with the text of its line.
+#[derive(Clone, Debug, PartialEq)]
+struct Bookmark {
+    abs_path: Arc<Path>,
+    position: Point,
+    label: SharedString,
+}
+
+impl Bookmark {
+    fn key(&self) -> (&Path, u32) {
+        (&self.abs_path, self.position.row)
+    }
+}
+
+/// A dock panel listing the workspace's bookmarks. The bookmarks are kept in
+/// the panel, and persisted along with the workspace.
+pub struct BookmarksPanel {
+    workspace: WeakView<Workspace>,
+    workspace_id: WorkspaceId,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    bookmarks: Vec<Bookmark>,
+    selected_index: Option<usize>,
+    list: UniformListScrollHandle,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    pending_bookmarks_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBookmarksPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl BookmarksPanel {
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            workspace: workspace.weak_handle(),
+            workspace_id: workspace.database_id(),
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            bookmarks: Vec::new(),
+            selected_index: None,
+            list: UniformListScrollHandle::new(),
+            width: None,
+            height: None,
+            pending_bookmarks_serialization: Task::ready(None),
+            _subscriptions: vec![cx.observe_global::<SettingsStore>(|_, cx| cx.notify())],
+        }
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let workspace_id = workspace.update(&mut cx, |workspace, _| workspace.database_id())?;
+            let (serialized_panel, bookmarks) = cx
+                .background_executor()
+                .spawn(async move {
+                    (
+                        KEY_VALUE_STORE.read_kvp(BOOKMARKS_PANEL_KEY),
+                        DB.get_bookmarks(workspace_id),
+                    )
+                })
+                .await;
+            let serialized_panel = if let Some(panel) = serialized_panel.log_err().flatten() {
+                Some(serde_json::from_str::<SerializedBookmarksPanel>(&panel)?)
+            } else {
+                None
+            };
+            let mut bookmarks = bookmarks
+                .log_err()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(abs_path, row, column, label)| Bookmark {
+                    abs_path: abs_path.into(),
+                    position: Point::new(row, column),
+                    label: label.into(),
+                })
+                .collect::<Vec<_>>();
+            bookmarks.sort_by(|a, b| a.key().cmp(&b.key()));
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = cx.new_view(|cx| Self::new(workspace, cx));
+                panel.update(cx, |panel, cx| {
+                    panel.bookmarks = bookmarks;
+                    if let Some(serialized_panel) = serialized_panel {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                    }
+                    cx.notify();
+                });
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedBookmarksPanel {
+            width: self.width,
+            height: self.height,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(BOOKMARKS_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    fn serialize_bookmarks(&mut self, cx: &mut ViewContext<Self>) {
+        let workspace_id = self.workspace_id;
+        let bookmarks = self
+            .bookmarks
+            .iter()
+            .map(|bookmark| {
+                (
+                    bookmark.abs_path.to_path_buf(),
+                    bookmark.position.row,
+                    bookmark.position.column,
+                    bookmark.label.to_string(),
+                )
+            })
+            .collect();
+        self.pending_bookmarks_serialization = cx
+            .background_executor()
+            .spawn(async move { DB.save_bookmarks(workspace_id, bookmarks).await }.log_err());
+    }
+
+    /// Removes the bookmark on the line of the given location, or adds one there.
+    fn toggle_bookmark(&mut self, bookmark: Bookmark, cx: &mut ViewContext<Self>) {
+        match self
+            .bookmarks
+            .binary_search_by(|probe| probe.key().cmp(&bookmark.key()))
+        {
+            Ok(ix) => {
+                self.bookmarks.remove(ix);
+            }
+            Err(ix) => self.bookmarks.insert(ix, bookmark),
+        }
+        self.selected_index = self.selected_index.filter(|ix| *ix < self.bookmarks.len());
+        self.serialize_bookmarks(cx);
+        cx.notify();
+    }
+
+    fn remove_bookmark(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix < self.bookmarks.len() {
+            self.bookmarks.remove(ix);
+            self.selected_index = self.selected_index.filter(|ix| *ix < self.bookmarks.len());
+            self.serialize_bookmarks(cx);
+            cx.notify();
+        }
+    }
+
+    /// The bookmark following (or preceding) the given location, wrapping around
+    /// at the end (or start) of the list.
+    fn adjacent_bookmark(&self, location: Option<&Bookmark>, forward: bool) -> Option<&Bookmark> {
+        let Some(location) = location else {
+            return if forward {
+                self.bookmarks.first()
+            } else {
+                self.bookmarks.last()
+            };
+        };
+        if forward {
+            self.bookmarks
+                .iter()
+                .find(|bookmark| bookmark.key() > location.key())
+                .or_else(|| self.bookmarks.first())
+        } else {
+            self.bookmarks
+                .iter()
+                .rev()
+                .find(|bookmark| bookmark.key() < location.key())
+                .or_else(|| self.bookmarks.last())
+        }
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix < self.bookmarks.len() {
+            self.selected_index = Some(ix);
+            self.list.scroll_to_item(ix);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, cx: &mut ViewContext<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, cx: &mut ViewContext<Self>) {
+        self.select_index(self.bookmarks.len().saturating_sub(1), cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_bookmark(ix, cx);
+        }
+    }
+
+    fn open_bookmark(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(bookmark) = self.bookmarks.get(ix).cloned() else {
+            return;
+        };
+        if let Some(workspace) = self.workspace.upgrade() {
+            workspace.update(cx, |workspace, cx| open_bookmark(workspace, bookmark, cx));
+        }
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::default();
+        dispatch_context.add("BookmarksPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_bookmark(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let bookmark = &self.bookmarks[ix];
+        let file_name = bookmark
+            .abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| bookmark.abs_path.to_string_lossy().to_string());
+
+        ListItem::new(ix)
+            .inset(true)
+            .selected(self.selected_index == Some(ix))
+            .child(
+                v_flex().child(Label::new(bookmark.label.clone())).child(
+                    Label::new(format!("{}:{}", file_name, bookmark.position.row + 1))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+            .end_hover_slot(
+                IconButton::new(("remove-bookmark", ix), IconName::Close)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Remove Bookmark", cx))
+                    .on_click(cx.listener(move |this, _, cx| this.remove_bookmark(ix, cx))),
+            )
+            .on_click(cx.listener(move |this, _, cx| {
+                this.selected_index = Some(ix);
+                this.open_bookmark(ix, cx);
+                cx.notify();
+            }))
+    }
+}
+
+/// The location of the newest cursor of the active editor.
+fn cursor_location(workspace: &Workspace, cx: &WindowContext) -> Option<Bookmark> {
+    let editor = workspace.active_item_as::<Editor>(cx)?;
+    let editor = editor.read(cx);
+    let cursor = editor.selections.newest::<usize>(cx).head();
+    let (buffer, offset, _) = editor
+        .buffer()
+        .read(cx)
+        .point_to_buffer_offset(cursor, cx)?;
+    let buffer = buffer.read(cx);
+    let abs_path = buffer.file()?.as_local()?.abs_path(cx);
+    let position = buffer.offset_to_point(offset);
+    let line_start = Point::new(position.row, 0);
+    let line_end = Point::new(position.row, buffer.line_len(position.row));
+    let label = buffer
+        .text_for_range(line_start..line_end)
+        .collect::<String>()
+        .trim()
+        .to_string();
+    Some(Bookmark {
+        abs_path: abs_path.into(),
+        position,
+        label: label.into(),
+    })
+}
+
+fn toggle(workspace: &mut Workspace, _: &Toggle, cx: &mut ViewContext<Workspace>) {
+    let Some(panel) = workspace.panel::<BookmarksPanel>(cx) else {
+        return;
+    };
+    if let Some(location) = cursor_location(workspace, cx) {
+        panel.update(cx, |panel, cx| panel.toggle_bookmark(location, cx));
+    }
+}
+
+fn cycle(workspace: &mut Workspace, forward: bool, cx: &mut ViewContext<Workspace>) {
+    let Some(panel) = workspace.panel::<BookmarksPanel>(cx) else {
+        return;
+    };
+    let location = cursor_location(workspace, cx);
+    let bookmark = panel
+        .read(cx)
+        .adjacent_bookmark(location.as_ref(), forward)
+        .cloned();
+    if let Some(bookmark) = bookmark {
+        open_bookmark(workspace, bookmark, cx);
+    }
+}
+
+fn open_bookmark(workspace: &mut Workspace, bookmark: Bookmark, cx: &mut ViewContext<Workspace>) {
+    let open_task = workspace.open_abs_path(PathBuf::from(bookmark.abs_path.as_ref()), true, cx);
+    cx.spawn(|_, mut cx| async move {
+        let item = open_task.await?;
+        if let Some(editor) = item.downcast::<Editor>() {
+            editor.update(&mut cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let point = snapshot.clip_point(bookmark.position, Bias::Left);
+                editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+                    s.select_ranges([point..point])
+                });
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("bookmarks-panel")
+            .size_full()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .child(if self.bookmarks.is_empty() {
+                h_flex()
+                    .flex_1()
+                    .justify_center()
+                    .child(Label::new("No bookmarks").color(Color::Muted))
+                    .into_any_element()
+            } else {
+                uniform_list(
+                    cx.view().clone(),
+                    "bookmarks-panel-entries",
+                    self.bookmarks.len(),
+                    |this, range, cx| {
+                        range
+                            .map(|ix| this.render_bookmark(ix, cx).into_any_element())
+                            .collect()
+                    },
+                )
+                .flex_1()
+                .track_scroll(self.list.clone())
+                .into_any_element()
+            })
+    }
+}
+
+impl FocusableView for BookmarksPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "BookmarksPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        BookmarksPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<BookmarksPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = BookmarksPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        BookmarksPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::Link)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Bookmarks Panel")
+    }
+
+    fn icon_label(&self, _: &WindowContext) -> Option<String> {
+        let count = self.bookmarks.len();
+        (count > 0).then(|| count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+
+    #[gpui::test]
+    async fn test_toggling_and_cycling_bookmarks(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "a.txt": "one\n  two\nthree\n",
+                "b.txt": "four\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let window = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let workspace = window.root(cx).unwrap();
+        workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| BookmarksPanel::new(workspace, cx));
+            workspace.add_panel(panel, cx);
+        });
+        let panel = workspace.update(cx, |workspace, cx| {
+            workspace.panel::<BookmarksPanel>(cx).unwrap()
+        });
+
+        let bookmarks = |panel: &View<BookmarksPanel>, cx: &mut VisualTestContext| {
+            panel.update(cx, |panel, _| {
+                panel
+                    .bookmarks
+                    .iter()
+                    .map(|bookmark| {
+                        (
+                            bookmark.abs_path.to_string_lossy().to_string(),
+                            bookmark.position.row,
+                            bookmark.label.to_string(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        move_cursor(&workspace, "/root/b.txt", 0, cx).await;
+        workspace.update(cx, |workspace, cx| toggle(workspace, &Toggle, cx));
+        move_cursor(&workspace, "/root/a.txt", 1, cx).await;
+        workspace.update(cx, |workspace, cx| toggle(workspace, &Toggle, cx));
+        assert_eq!(
+            bookmarks(&panel, cx),
+            [
+                ("/root/a.txt".into(), 1, "two".into()),
+                ("/root/b.txt".into(), 0, "four".into()),
+            ]
+        );
+
+        // Cycling wraps around at either end of the list.
+        let active_location = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                let location = cursor_location(workspace, cx).unwrap();
+                (
+                    location.abs_path.to_string_lossy().to_string(),
+                    location.position.row,
+                )
+            })
+        };
+        move_cursor(&workspace, "/root/a.txt", 2, cx).await;
+        workspace.update(cx, |workspace, cx| cycle(workspace, true, cx));
+        cx.run_until_parked();
+        assert_eq!(active_location(cx), ("/root/b.txt".into(), 0));
+        workspace.update(cx, |workspace, cx| cycle(workspace, true, cx));
+        cx.run_until_parked();
+        assert_eq!(active_location(cx), ("/root/a.txt".into(), 1));
+        workspace.update(cx, |workspace, cx| cycle(workspace, false, cx));
+        cx.run_until_parked();
+        assert_eq!(active_location(cx), ("/root/b.txt".into(), 0));
+
+        // Toggling on a bookmarked line removes the bookmark.
+        workspace.update(cx, |workspace, cx| toggle(workspace, &Toggle, cx));
+        assert_eq!(
+            bookmarks(&panel, cx),
+            [("/root/a.txt".into(), 1, "two".into())]
+        );
+    }
+
+    async fn move_cursor(
+        workspace: &View<Workspace>,
+        path: &str,
+        row: u32,
+        cx: &mut VisualTestContext,
+    ) {
+        let item = workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_abs_path(PathBuf::from(path), true, cx)
+            })
+            .await
+            .unwrap();
+        let editor = item.downcast::<Editor>().unwrap();
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |s| {
+                s.select_ranges([Point::new(row, 0)..Point::new(row, 0)])
+            })
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            client::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+            editor::init(cx);
+        });
+    }
+}
@@ -0,0 +1,49 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug)]
+pub struct BookmarksPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+}
+
+/// Bookmarks panel configuration.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct BookmarksPanelSettingsContent {
+    /// Whether to show the bookmarks panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the bookmarks panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the bookmarks panel in pixels, when docked to the left or right.
+    ///
+    /// Default: 240
+    pub default_width: Option<f32>,
+    /// Default height of the bookmarks panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+}
+
+impl settings::Settings for BookmarksPanelSettings {
+    const KEY: Option<&'static str> = Some("bookmarks_panel");
+    type FileContent = BookmarksPanelSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _cx: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
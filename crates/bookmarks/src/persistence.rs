@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use db::{define_connection, query, sqlez_macros::sql};
+use workspace::{WorkspaceDb, WorkspaceId};
+
+define_connection! {
+    // Current schema shape using pseudo-rust syntax:
+    // bookmarks(
+    //   workspace_id: usize,
+    //   path: PathBuf,
+    //   row_number: u32,
+    //   column_number: u32,
+    //   label: String,
+    // )
+    pub static ref DB: BookmarksDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path BLOB NOT NULL,
+                row_number INTEGER NOT NULL,
+                column_number INTEGER NOT NULL,
+                label TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, path, row_number),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl BookmarksDb {
+    query! {
+        pub fn get_bookmarks(workspace_id: WorkspaceId) -> Result<Vec<(PathBuf, u32, u32, String)>> {
+            SELECT path, row_number, column_number, label
+            FROM bookmarks
+            WHERE workspace_id = ?
+            ORDER BY path, row_number
+        }
+    }
+
+    /// Replaces the bookmarks of the workspace.
+    pub async fn save_bookmarks(
+        &self,
+        workspace_id: WorkspaceId,
+        bookmarks: Vec<(PathBuf, u32, u32, String)>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("save_bookmarks", || {
+                conn.exec_bound(sql!(DELETE FROM bookmarks WHERE workspace_id = ?))?(workspace_id)?;
+                for (path, row, column, label) in bookmarks {
+                    conn.exec_bound(sql!(
+                        INSERT INTO bookmarks(workspace_id, path, row_number, column_number, label)
+                        VALUES (?, ?, ?, ?, ?)
+                    ))?((workspace_id, path, row, column, label))?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+}
@@ -1,14 +1,17 @@
-use editor::Editor;
+use editor::{scroll::Autoscroll, Anchor, Editor, ToOffset};
 use gpui::{
-    Element, EventEmitter, IntoElement, ParentElement, Render, StyledText, Subscription,
-    ViewContext,
+    AppContext, Element, EventEmitter, IntoElement, Model, ParentElement, Render, StyledText,
+    Subscription, View, ViewContext, WeakView,
 };
 use itertools::Itertools;
+use language::OutlineItem;
+use project::{Project, ProjectPath, WorktreeId};
+use std::{path::Path, sync::Arc};
 use theme::ActiveTheme;
-use ui::{prelude::*, ButtonLike, ButtonStyle, Label, Tooltip};
+use ui::{popover_menu, prelude::*, ButtonLike, ButtonStyle, ContextMenu, Label, Tooltip};
 use workspace::{
     item::{ItemEvent, ItemHandle},
-    ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
+    ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace,
 };
 
 pub struct Breadcrumbs {
@@ -35,6 +38,11 @@ impl Render for Breadcrumbs {
         let Some(active_item) = self.active_item.as_ref() else {
             return element;
         };
+        if let Some(editor) = active_item.downcast::<Editor>() {
+            if let Some(breadcrumbs) = render_editor_breadcrumbs(&editor, cx) {
+                return element.child(breadcrumbs);
+            }
+        }
         let Some(segments) = active_item.breadcrumbs(cx.theme(), cx) else {
             return element;
         };
@@ -75,6 +83,218 @@ impl Render for Breadcrumbs {
     }
 }
 
+/// Renders the breadcrumbs of an editor for a project file as a row of dropdowns.
+/// Path segments list the files of their directory, and symbol segments list the
+/// symbols declared alongside them.
+fn render_editor_breadcrumbs(
+    editor: &View<Editor>,
+    cx: &mut ViewContext<Breadcrumbs>,
+) -> Option<Div> {
+    let editor_ref = editor.read(cx);
+    let workspace = editor_ref.workspace()?;
+    let project = workspace.read(cx).project().clone();
+    let buffer = editor_ref.buffer().read(cx).as_singleton()?;
+    let file = buffer.read(cx).file()?.clone();
+    let worktree_id = WorktreeId::from_usize(file.worktree_id());
+    let worktree = project.read(cx).worktree_for_id(worktree_id, cx)?;
+    let show_root = project.read(cx).visible_worktrees(cx).count() > 1;
+
+    let path = file.path().clone();
+    let file_dir: Arc<Path> = Arc::from(path.parent().unwrap_or(Path::new("")));
+    let mut path_segments = Vec::new();
+    if show_root {
+        path_segments.push((
+            SharedString::from(worktree.read(cx).root_name().to_string()),
+            Arc::from(Path::new("")),
+        ));
+    }
+    for ancestor in file_dir.ancestors().collect::<Vec<_>>().into_iter().rev() {
+        if let Some(name) = ancestor.file_name() {
+            path_segments.push((name.to_string_lossy().to_string().into(), ancestor.into()));
+        }
+    }
+    path_segments.push((
+        file.file_name(cx).to_string_lossy().to_string().into(),
+        file_dir,
+    ));
+
+    let snapshot = editor_ref.buffer().read(cx).snapshot(cx);
+    let cursor = editor_ref.selections.newest_anchor().head();
+    let symbols = snapshot
+        .symbols_containing(cursor, Some(&cx.theme().syntax()))
+        .map(|(_, symbols)| symbols)
+        .unwrap_or_default();
+
+    let workspace = workspace.downgrade();
+    let path_segments = path_segments
+        .into_iter()
+        .enumerate()
+        .map(|(ix, (label, dir))| {
+            let project = project.clone();
+            let workspace = workspace.clone();
+            popover_menu(("breadcrumb_path", ix))
+                .trigger(
+                    ButtonLike::new(("breadcrumb_path_trigger", ix))
+                        .style(ButtonStyle::Subtle)
+                        .child(Label::new(label).color(Color::Muted)),
+                )
+                .menu(move |cx| {
+                    Some(sibling_files_menu(
+                        &project,
+                        &workspace,
+                        worktree_id,
+                        &dir,
+                        cx,
+                    ))
+                })
+                .into_any_element()
+        });
+
+    let editor = editor.downgrade();
+    let symbol_segments = symbols.into_iter().enumerate().map(|(ix, symbol)| {
+        let mut text_style = cx.text_style();
+        text_style.color = Color::Muted.color(cx);
+        let label = StyledText::new(symbol.text.clone())
+            .with_highlights(&text_style, symbol.highlight_ranges.clone());
+        let editor = editor.clone();
+        popover_menu(("breadcrumb_symbol", ix))
+            .trigger(
+                ButtonLike::new(("breadcrumb_symbol_trigger", ix))
+                    .style(ButtonStyle::Subtle)
+                    .child(label),
+            )
+            .menu(move |cx| Some(sibling_symbols_menu(&editor, &symbol, cx)))
+            .into_any_element()
+    });
+
+    let breadcrumbs = Itertools::intersperse_with(path_segments.chain(symbol_segments), || {
+        Label::new("›").color(Color::Muted).into_any_element()
+    });
+    Some(h_flex().gap_0p5().children(breadcrumbs))
+}
+
+/// Lists the files in `dir`, opening the one that gets picked.
+fn sibling_files_menu(
+    project: &Model<Project>,
+    workspace: &WeakView<Workspace>,
+    worktree_id: WorktreeId,
+    dir: &Path,
+    cx: &mut WindowContext,
+) -> View<ContextMenu> {
+    let files = sibling_files(project, worktree_id, dir, cx);
+    ContextMenu::build(cx, |mut menu, _| {
+        if files.is_empty() {
+            return menu.header("No Files");
+        }
+        for path in files {
+            let label = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let workspace = workspace.clone();
+            menu = menu.entry(label, None, move |cx| {
+                let project_path = ProjectPath {
+                    worktree_id,
+                    path: path.clone(),
+                };
+                workspace
+                    .update(cx, |workspace, cx| {
+                        workspace
+                            .open_path(project_path, None, true, cx)
+                            .detach_and_log_err(cx)
+                    })
+                    .ok();
+            });
+        }
+        menu
+    })
+}
+
+/// The paths of the files directly in `dir`, leaving out its subdirectories.
+fn sibling_files(
+    project: &Model<Project>,
+    worktree_id: WorktreeId,
+    dir: &Path,
+    cx: &AppContext,
+) -> Vec<Arc<Path>> {
+    project
+        .read(cx)
+        .worktree_for_id(worktree_id, cx)
+        .map(|worktree| {
+            worktree
+                .read(cx)
+                .child_entries(dir)
+                .filter(|entry| entry.is_file())
+                .map(|entry| entry.path.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Lists the outline items that share `symbol`'s parent, moving the cursor to
+/// the one that gets picked.
+fn sibling_symbols_menu(
+    editor: &WeakView<Editor>,
+    symbol: &OutlineItem<Anchor>,
+    cx: &mut WindowContext,
+) -> View<ContextMenu> {
+    let siblings = editor
+        .upgrade()
+        .and_then(|editor| {
+            let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+            let outline = snapshot.outline(None)?;
+            let symbol_start = symbol.range.start.to_offset(&snapshot);
+            let ix = outline.items.iter().position(|item| {
+                item.depth == symbol.depth && item.range.start.to_offset(&snapshot) == symbol_start
+            })?;
+            Some(
+                sibling_items(&outline.items, ix)
+                    .map(|item| (item.text.clone(), item.range.start))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .unwrap_or_default();
+
+    ContextMenu::build(cx, |mut menu, _| {
+        for (label, position) in siblings {
+            let editor = editor.clone();
+            menu = menu.entry(label, None, move |cx| {
+                if let Some(editor) = editor.upgrade() {
+                    editor.update(cx, |editor, cx| {
+                        editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+                            s.select_ranges([position..position])
+                        });
+                    });
+                    cx.focus_view(&editor);
+                }
+            });
+        }
+        let editor = editor.clone();
+        menu.separator().entry(
+            "Show Symbol Outline",
+            Some(Box::new(outline::Toggle)),
+            move |cx| {
+                if let Some(editor) = editor.upgrade() {
+                    outline::toggle(editor, &outline::Toggle, cx)
+                }
+            },
+        )
+    })
+}
+
+/// The items at the same depth as `items[ix]` that are nested in the same parent.
+fn sibling_items<T>(items: &[OutlineItem<T>], ix: usize) -> impl Iterator<Item = &OutlineItem<T>> {
+    let depth = items[ix].depth;
+    let start = items[..ix]
+        .iter()
+        .rposition(|item| item.depth < depth)
+        .map_or(0, |parent_ix| parent_ix + 1);
+    items[start..]
+        .iter()
+        .take_while(move |item| item.depth >= depth)
+        .filter(move |item| item.depth == depth)
+}
+
 impl ToolbarItemView for Breadcrumbs {
     fn set_active_pane_item(
         &mut self,
@@ -112,3 +332,133 @@ impl ToolbarItemView for Breadcrumbs {
         self.pane_focused = pane_focused;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+
+    #[test]
+    fn test_sibling_items() {
+        let items = [
+            outline_item(0, "struct A"),
+            outline_item(1, "a1"),
+            outline_item(1, "a2"),
+            outline_item(0, "impl A"),
+            outline_item(1, "fn new"),
+            outline_item(2, "let x"),
+            outline_item(1, "fn get"),
+            outline_item(0, "fn main"),
+        ];
+
+        // The first and last top-level items are siblings of every top-level item.
+        assert_eq!(sibling_texts(&items, 0), ["struct A", "impl A", "fn main"]);
+        assert_eq!(sibling_texts(&items, 7), ["struct A", "impl A", "fn main"]);
+
+        // Nested items only have the items sharing their parent as siblings, and
+        // skip over the items nested in those.
+        assert_eq!(sibling_texts(&items, 1), ["a1", "a2"]);
+        assert_eq!(sibling_texts(&items, 2), ["a1", "a2"]);
+        assert_eq!(sibling_texts(&items, 6), ["fn new", "fn get"]);
+        assert_eq!(sibling_texts(&items, 5), ["let x"]);
+    }
+
+    #[gpui::test]
+    async fn test_sibling_files_menu(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "a.rs": "",
+                "b.rs": "",
+                "src": {
+                    "lib.rs": "",
+                    "main.rs": "",
+                    "nested": { "mod.rs": "" },
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees().next().unwrap().read(cx).id()
+        });
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+
+        // Only the files directly in a directory are listed.
+        cx.update(|cx| {
+            assert_eq!(
+                sibling_files(&project, worktree_id, Path::new(""), cx),
+                [Arc::from(Path::new("a.rs")), Arc::from(Path::new("b.rs"))]
+            );
+            assert_eq!(
+                sibling_files(&project, worktree_id, Path::new("src"), cx),
+                [
+                    Arc::from(Path::new("src/lib.rs")),
+                    Arc::from(Path::new("src/main.rs"))
+                ]
+            );
+        });
+
+        // Picking one of them opens it.
+        let menu = cx.update(|cx| {
+            sibling_files_menu(
+                &project,
+                &workspace.downgrade(),
+                worktree_id,
+                Path::new("src"),
+                cx,
+            )
+        });
+        menu.update(cx, |menu, cx| {
+            menu.select_last();
+            menu.confirm(&menu::Confirm, cx);
+        });
+        cx.run_until_parked();
+        let active_path = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_item(cx)
+                .and_then(|item| item.project_path(cx))
+        });
+        assert_eq!(
+            active_path,
+            Some(ProjectPath {
+                worktree_id,
+                path: Arc::from(Path::new("src/main.rs")),
+            })
+        );
+    }
+
+    fn outline_item(depth: usize, text: &str) -> OutlineItem<usize> {
+        OutlineItem {
+            depth,
+            range: 0..0,
+            text: text.to_string(),
+            highlight_ranges: Vec::new(),
+            name_ranges: Vec::new(),
+        }
+    }
+
+    fn sibling_texts(items: &[OutlineItem<usize>], ix: usize) -> Vec<&str> {
+        sibling_items(items, ix)
+            .map(|item| item.text.as_str())
+            .collect()
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            client::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            editor::init(cx);
+        });
+    }
+}
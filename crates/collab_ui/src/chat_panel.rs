@@ -4,28 +4,32 @@ use call::{room, ActiveCall};
 use channel::{ChannelChat, ChannelChatEvent, ChannelMessageId, ChannelStore};
 use client::Client;
 use collections::HashMap;
-use db::kvp::KEY_VALUE_STORE;
-use editor::Editor;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use editor::{scroll::Autoscroll, Editor};
 use gpui::{
     actions, div, list, prelude::*, px, Action, AppContext, AsyncWindowContext, DismissEvent,
-    ElementId, EventEmitter, Fill, FocusHandle, FocusableView, FontWeight, ListOffset,
-    ListScrollEvent, ListState, Model, Render, Subscription, Task, View, ViewContext,
-    VisualContext, WeakView,
+    ElementId, EventEmitter, Fill, FocusHandle, FocusableView, FontWeight, HighlightStyle,
+    ListOffset, ListScrollEvent, ListState, Model, Render, Subscription, Task, UnderlineStyle,
+    View, ViewContext, VisualContext, WeakView,
 };
-use language::LanguageRegistry;
+use language::{LanguageRegistry, Point};
 use menu::Confirm;
 use message_editor::MessageEditor;
-use project::Fs;
-use rich_text::RichText;
+use project::{Fs, Project, ProjectPath};
+use rich_text::{Highlight, RichText};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
-use std::{sync::Arc, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 use time::{OffsetDateTime, UtcOffset};
 use ui::{
     popover_menu, prelude::*, Avatar, Button, ContextMenu, IconButton, IconName, KeyBinding, Label,
     TabBar,
 };
-use util::{ResultExt, TryFutureExt};
+use util::{paths::PathLikeWithPosition, ResultExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
     Workspace,
@@ -46,6 +50,7 @@ pub fn init(cx: &mut AppContext) {
 }
 
 pub struct ChatPanel {
+    workspace: WeakView<Workspace>,
     client: Arc<Client>,
     channel_store: Model<ChannelStore>,
     languages: Arc<LanguageRegistry>,
@@ -56,12 +61,12 @@ pub struct ChatPanel {
     fs: Arc<dyn Fs>,
     width: Option<Pixels>,
     active: bool,
-    pending_serialization: Task<Option<()>>,
     subscriptions: Vec<gpui::Subscription>,
     is_scrolled_to_bottom: bool,
     markdown_data: HashMap<ChannelMessageId, RichText>,
     focus_handle: FocusHandle,
     open_context_menu: Option<(u64, Subscription)>,
+    unread_message_count: usize,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -108,13 +113,13 @@ impl ChatPanel {
             }));
 
             let mut this = Self {
+                workspace: workspace.weak_handle(),
                 fs,
                 client,
                 channel_store,
                 languages,
                 message_list,
                 active_chat: Default::default(),
-                pending_serialization: Task::ready(None),
                 message_editor: input_editor,
                 local_timezone: cx.local_timezone(),
                 subscriptions: Vec::new(),
@@ -124,6 +129,7 @@ impl ChatPanel {
                 markdown_data: Default::default(),
                 focus_handle: cx.focus_handle(),
                 open_context_menu: None,
+                unread_message_count: 0,
             };
 
             if let Some(channel_id) = ActiveCall::global(cx)
@@ -210,19 +216,11 @@ impl ChatPanel {
     }
 
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
-        let width = self.width;
-        self.pending_serialization = cx.background_executor().spawn(
-            async move {
-                KEY_VALUE_STORE
-                    .write_kvp(
-                        CHAT_PANEL_KEY.into(),
-                        serde_json::to_string(&SerializedChatPanel { width })?,
-                    )
-                    .await?;
-                anyhow::Ok(())
-            }
-            .log_err(),
-        );
+        if let Some(serialized) =
+            serde_json::to_string(&SerializedChatPanel { width: self.width }).log_err()
+        {
+            write_kvp_debounced(CHAT_PANEL_KEY, serialized, cx);
+        }
     }
 
     fn set_active_chat(&mut self, chat: Model<ChannelChat>, cx: &mut ViewContext<Self>) {
@@ -240,6 +238,7 @@ impl ChatPanel {
             };
             let subscription = cx.subscribe(&chat, Self::channel_did_change);
             self.active_chat = Some((chat, subscription));
+            self.unread_message_count = 0;
             self.acknowledge_last_message(cx);
             cx.notify();
         }
@@ -266,6 +265,7 @@ impl ChatPanel {
                 message_id,
             } => {
                 if !self.active {
+                    self.unread_message_count += 1;
                     self.channel_store.update(cx, |store, cx| {
                         store.update_latest_message_id(*channel_id, *message_id, cx)
                     })
@@ -317,8 +317,17 @@ impl ChatPanel {
             });
 
         let _is_pending = message.is_pending();
+        let project = self
+            .workspace
+            .upgrade()
+            .map(|workspace| workspace.read(cx).project().clone());
         let text = self.markdown_data.entry(message.id).or_insert_with(|| {
-            Self::render_markdown_with_mentions(&self.languages, self.client.id(), &message)
+            let mut text =
+                Self::render_markdown_with_mentions(&self.languages, self.client.id(), &message);
+            if let Some(project) = project {
+                add_file_links(&mut text, project.read(cx), cx);
+            }
+            text
         });
 
         let belongs_to_user = Some(message.sender.id) == self.client.user_id();
@@ -384,7 +393,10 @@ impl ChatPanel {
                         .text_ui_sm()
                         .id(element_id)
                         .group("")
-                        .child(text.element("body".into(), cx))
+                        .child(text.element_with_link_handler("body".into(), cx, {
+                            let workspace = self.workspace.clone();
+                            move |url, cx| open_link(&workspace, url, cx)
+                        }))
                         .child(
                             div()
                                 .absolute()
@@ -652,7 +664,9 @@ impl Panel for ChatPanel {
     fn set_active(&mut self, active: bool, cx: &mut ViewContext<Self>) {
         self.active = active;
         if active {
+            self.unread_message_count = 0;
             self.acknowledge_last_message(cx);
+            cx.notify();
         }
     }
 
@@ -668,6 +682,14 @@ impl Panel for ChatPanel {
         Some("Chat Panel")
     }
 
+    fn icon_label(&self, _: &WindowContext) -> Option<String> {
+        if self.unread_message_count == 0 {
+            None
+        } else {
+            Some(self.unread_message_count.to_string())
+        }
+    }
+
     fn toggle_action(&self) -> Box<dyn gpui::Action> {
         Box::new(ToggleFocus)
     }
@@ -682,6 +704,132 @@ impl Panel for ChatPanel {
 
 impl EventEmitter<PanelEvent> for ChatPanel {}
 
+/// Turns the references to files of the project in a message, such as
+/// `src/main.rs:12`, into links that open them.
+fn add_file_links(text: &mut RichText, project: &Project, cx: &AppContext) {
+    let body = text.text.clone();
+    let mut links = text
+        .link_ranges
+        .iter()
+        .cloned()
+        .zip(text.link_urls.iter().cloned())
+        .collect::<Vec<_>>();
+    let link_count = links.len();
+
+    for word in body.split_whitespace() {
+        let reference = word.trim_matches(|c: char| {
+            matches!(
+                c,
+                '(' | ')' | '[' | ']' | '`' | '"' | '\'' | ',' | ';' | '.'
+            )
+        });
+        if reference.is_empty() {
+            continue;
+        }
+        let start = reference.as_ptr() as usize - body.as_ptr() as usize;
+        let range = start..start + reference.len();
+        if links
+            .iter()
+            .any(|(link_range, _)| link_range.start < range.end && range.start < link_range.end)
+        {
+            continue;
+        }
+        if resolve_file_reference(reference, project, cx).is_none() {
+            continue;
+        }
+        // Highlights can't overlap, so styled references are linked as they are.
+        if !text.highlights.iter().any(|(highlight_range, _)| {
+            highlight_range.start < range.end && range.start < highlight_range.end
+        }) {
+            text.highlights.push((
+                range.clone(),
+                Highlight::Highlight(HighlightStyle {
+                    underline: Some(UnderlineStyle {
+                        thickness: px(1.),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            ));
+        }
+        links.push((range, reference.to_string()));
+    }
+
+    if links.len() > link_count {
+        links.sort_by_key(|(range, _)| range.start);
+        text.highlights.sort_by_key(|(range, _)| range.start);
+        let (link_ranges, link_urls): (Vec<_>, Vec<_>) = links.into_iter().unzip();
+        text.link_ranges = link_ranges;
+        text.link_urls = link_urls.into();
+    }
+}
+
+/// Finds the file of the project a reference like `src/main.rs:12:5` points to,
+/// along with the position within it. The path may be prefixed with the name of
+/// its worktree.
+fn resolve_file_reference(
+    reference: &str,
+    project: &Project,
+    cx: &AppContext,
+) -> Option<(ProjectPath, Option<u32>, Option<u32>)> {
+    let reference =
+        PathLikeWithPosition::parse_str(reference, |path| anyhow::Ok(PathBuf::from(path))).ok()?;
+    if reference.path_like.is_absolute() {
+        return None;
+    }
+    project.visible_worktrees(cx).find_map(|worktree| {
+        let worktree = worktree.read(cx);
+        let path = reference.path_like.as_path();
+        [Some(path), path.strip_prefix(worktree.root_name()).ok()]
+            .into_iter()
+            .flatten()
+            .filter(|path| *path != Path::new(""))
+            .find_map(|path| {
+                let entry = worktree.entry_for_path(path)?;
+                entry.is_file().then(|| {
+                    (
+                        ProjectPath {
+                            worktree_id: worktree.id(),
+                            path: entry.path.clone(),
+                        },
+                        reference.row,
+                        reference.column,
+                    )
+                })
+            })
+    })
+}
+
+fn open_link(workspace: &WeakView<Workspace>, url: &str, cx: &mut WindowContext) {
+    let Some(workspace) = workspace.upgrade() else {
+        cx.open_url(url);
+        return;
+    };
+    let Some((project_path, row, column)) =
+        resolve_file_reference(url, workspace.read(cx).project().read(cx), cx)
+    else {
+        cx.open_url(url);
+        return;
+    };
+
+    let open_task = workspace.update(cx, |workspace, cx| {
+        workspace.open_path(project_path, None, true, cx)
+    });
+    cx.spawn(|mut cx| async move {
+        let item = open_task.await?;
+        if let (Some(row), Some(editor)) = (row, item.downcast::<Editor>()) {
+            let point = Point::new(row.saturating_sub(1), column.unwrap_or(1).saturating_sub(1));
+            editor.update(&mut cx, |editor, cx| {
+                editor.change_selections(Some(Autoscroll::center()), cx, |selections| {
+                    selections.select_ranges([point..point])
+                });
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
 fn format_timestamp(
     reference: OffsetDateTime,
     timestamp: OffsetDateTime,
@@ -10,7 +10,7 @@ use call::ActiveCall;
 use channel::{Channel, ChannelEvent, ChannelId, ChannelStore};
 use client::{Client, Contact, User, UserStore};
 use contact_finder::ContactFinder;
-use db::kvp::KEY_VALUE_STORE;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
 use editor::{Editor, EditorElement, EditorStyle};
 use fuzzy::{match_strings, StringMatchCandidate};
 use gpui::{
@@ -36,7 +36,7 @@ use ui::{
     prelude::*, tooltip_container, Avatar, AvatarAvailabilityIndicator, Button, Color, ContextMenu,
     Icon, IconButton, IconName, IconSize, Label, ListHeader, ListItem, Tooltip,
 };
-use util::{maybe, ResultExt, TryFutureExt};
+use util::{maybe, ResultExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
     notifications::{DetachAndPromptErr, NotifyResultExt, NotifyTaskExt},
@@ -96,10 +96,10 @@ impl ChannelEditingState {
 
 pub struct CollabPanel {
     width: Option<Pixels>,
+    height: Option<Pixels>,
     fs: Arc<dyn Fs>,
     focus_handle: FocusHandle,
     channel_clipboard: Option<ChannelMoveClipboard>,
-    pending_serialization: Task<Option<()>>,
     context_menu: Option<(View<ContextMenu>, Point<Pixels>, Subscription)>,
     list_state: ListState,
     filter_editor: View<Editor>,
@@ -121,6 +121,7 @@ pub struct CollabPanel {
 #[derive(Serialize, Deserialize)]
 struct SerializedCollabPanel {
     width: Option<Pixels>,
+    height: Option<Pixels>,
     collapsed_channels: Option<Vec<u64>>,
 }
 
@@ -232,10 +233,10 @@ impl CollabPanel {
 
             let mut this = Self {
                 width: None,
+                height: None,
                 focus_handle: cx.focus_handle(),
                 channel_clipboard: None,
                 fs: workspace.app_state().fs.clone(),
-                pending_serialization: Task::ready(None),
                 context_menu: None,
                 list_state,
                 channel_name_editor,
@@ -311,6 +312,7 @@ impl CollabPanel {
             if let Some(serialized_panel) = serialized_panel {
                 panel.update(cx, |panel, cx| {
                     panel.width = serialized_panel.width;
+                    panel.height = serialized_panel.height;
                     panel.collapsed_channels = serialized_panel
                         .collapsed_channels
                         .unwrap_or_else(|| Vec::new());
@@ -322,23 +324,16 @@ impl CollabPanel {
     }
 
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
-        let width = self.width;
         let collapsed_channels = self.collapsed_channels.clone();
-        self.pending_serialization = cx.background_executor().spawn(
-            async move {
-                KEY_VALUE_STORE
-                    .write_kvp(
-                        COLLABORATION_PANEL_KEY.into(),
-                        serde_json::to_string(&SerializedCollabPanel {
-                            width,
-                            collapsed_channels: Some(collapsed_channels),
-                        })?,
-                    )
-                    .await?;
-                anyhow::Ok(())
-            }
-            .log_err(),
-        );
+        if let Some(serialized) = serde_json::to_string(&SerializedCollabPanel {
+            width: self.width,
+            height: self.height,
+            collapsed_channels: Some(collapsed_channels),
+        })
+        .log_err()
+        {
+            write_kvp_debounced(COLLABORATION_PANEL_KEY, serialized, cx);
+        }
     }
 
     fn scroll_to_item(&mut self, ix: usize) {
@@ -2650,8 +2645,8 @@ impl Panel for CollabPanel {
         CollaborationPanelSettings::get_global(cx).dock
     }
 
-    fn position_is_valid(&self, position: DockPosition) -> bool {
-        matches!(position, DockPosition::Left | DockPosition::Right)
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
     }
 
     fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
@@ -2663,12 +2658,20 @@ impl Panel for CollabPanel {
     }
 
     fn size(&self, cx: &gpui::WindowContext) -> Pixels {
-        self.width
-            .unwrap_or_else(|| CollaborationPanelSettings::get_global(cx).default_width)
+        let settings = CollaborationPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
     }
 
     fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
-        self.width = size;
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
         self.serialize(cx);
         cx.notify();
     }
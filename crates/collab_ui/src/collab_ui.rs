@@ -6,6 +6,7 @@ mod face_pile;
 pub mod notification_panel;
 pub mod notifications;
 mod panel_settings;
+mod sharing_indicator;
 
 use std::{rc::Rc, sync::Arc};
 
@@ -20,6 +21,7 @@ pub use panel_settings::{
     ChatPanelSettings, CollaborationPanelSettings, NotificationPanelSettings,
 };
 use settings::Settings;
+pub use sharing_indicator::ProjectSharingIndicator;
 use workspace::{notifications::DetachAndPromptErr, AppState};
 
 actions!(
@@ -118,5 +120,6 @@ fn notification_window_options(
         kind: WindowKind::PopUp,
         is_movable: false,
         display_id: Some(screen.id()),
+        tabbing_identifier: None,
     }
 }
@@ -3,7 +3,7 @@ use anyhow::Result;
 use channel::ChannelStore;
 use client::{Client, Notification, User, UserStore};
 use collections::HashMap;
-use db::kvp::KEY_VALUE_STORE;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
 use futures::StreamExt;
 use gpui::{
     actions, div, img, list, px, AnyElement, AppContext, AsyncWindowContext, CursorStyle,
@@ -23,6 +23,7 @@ use ui::{h_flex, prelude::*, v_flex, Avatar, Button, Icon, IconButton, IconName,
 use util::{ResultExt, TryFutureExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
+    notifications::{log_notification, NotificationSeverity},
     Workspace,
 };
 
@@ -40,7 +41,6 @@ pub struct NotificationPanel {
     width: Option<Pixels>,
     active: bool,
     notification_list: ListState,
-    pending_serialization: Task<Option<()>>,
     subscriptions: Vec<gpui::Subscription>,
     workspace: WeakView<Workspace>,
     current_notification_toast: Option<(u64, Task<()>)>,
@@ -134,7 +134,6 @@ impl NotificationPanel {
                 channel_store: ChannelStore::global(cx),
                 notification_store: NotificationStore::global(cx),
                 notification_list,
-                pending_serialization: Task::ready(None),
                 workspace: workspace_handle,
                 focus_handle: cx.focus_handle(),
                 current_notification_toast: None,
@@ -193,19 +192,11 @@ impl NotificationPanel {
     }
 
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
-        let width = self.width;
-        self.pending_serialization = cx.background_executor().spawn(
-            async move {
-                KEY_VALUE_STORE
-                    .write_kvp(
-                        NOTIFICATION_PANEL_KEY.into(),
-                        serde_json::to_string(&SerializedNotificationPanel { width })?,
-                    )
-                    .await?;
-                anyhow::Ok(())
-            }
-            .log_err(),
-        );
+        if let Some(serialized) =
+            serde_json::to_string(&SerializedNotificationPanel { width: self.width }).log_err()
+        {
+            write_kvp_debounced(NOTIFICATION_PANEL_KEY, serialized, cx);
+        }
     }
 
     fn render_notification(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
@@ -501,6 +492,7 @@ impl NotificationPanel {
         else {
             return;
         };
+        log_notification(NotificationSeverity::Info, text.clone(), cx);
 
         let notification_id = entry.id;
         self.current_notification_toast = Some((
@@ -10,6 +10,7 @@ pub struct CollaborationPanelSettings {
     pub button: bool,
     pub dock: DockPosition,
     pub default_width: Pixels,
+    pub default_height: Pixels,
 }
 
 #[derive(Deserialize, Debug)]
@@ -42,9 +43,30 @@ pub struct PanelSettingsContent {
     pub default_width: Option<f32>,
 }
 
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct CollaborationPanelSettingsContent {
+    /// Whether to show the panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the panel. Unlike the other collaboration panels, it can
+    /// also be docked at the bottom.
+    ///
+    /// Default: left
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels, when docked on either side.
+    ///
+    /// Default: 240
+    pub default_width: Option<f32>,
+    /// Default height of the panel in pixels, when docked at the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+}
+
 impl Settings for CollaborationPanelSettings {
     const KEY: Option<&'static str> = Some("collaboration_panel");
-    type FileContent = PanelSettingsContent;
+    type FileContent = CollaborationPanelSettingsContent;
     fn load(
         default_value: &Self::FileContent,
         user_values: &[&Self::FileContent],
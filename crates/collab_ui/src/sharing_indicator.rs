@@ -0,0 +1,98 @@
+use call::ActiveCall;
+use gpui::{Model, Render, Subscription, ViewContext};
+use project::Project;
+use ui::{prelude::*, IconButton, IconName, Tooltip};
+use util::ResultExt;
+use workspace::{item::ItemHandle, StatusItemView, Workspace};
+
+/// Shows whether the project is shared with the participants of the current
+/// call, and shares or unshares it when clicked.
+pub struct ProjectSharingIndicator {
+    project: Model<Project>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl ProjectSharingIndicator {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        Self {
+            _subscriptions: [
+                cx.observe(&ActiveCall::global(cx), |_, _, cx| cx.notify()),
+                cx.observe(&project, |_, _, cx| cx.notify()),
+            ],
+            project,
+        }
+    }
+
+    fn toggle_sharing(&mut self, cx: &mut ViewContext<Self>) {
+        let active_call = ActiveCall::global(cx);
+        let project = self.project.clone();
+        if project.read(cx).is_shared() {
+            active_call
+                .update(cx, |call, cx| call.unshare_project(project, cx))
+                .log_err();
+        } else {
+            active_call
+                .update(cx, |call, cx| call.share_project(project, cx))
+                .detach_and_log_err(cx);
+        }
+    }
+
+    /// The GitHub logins of the collaborators the project is shared with.
+    fn guests(&self, cx: &AppContext) -> Vec<SharedString> {
+        let project = self.project.read(cx);
+        let user_store = project.user_store().read(cx);
+        project
+            .collaborators()
+            .values()
+            .filter_map(|collaborator| {
+                let user = user_store.get_cached_user(collaborator.user_id)?;
+                Some(user.github_login.clone().into())
+            })
+            .collect()
+    }
+}
+
+impl Render for ProjectSharingIndicator {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(room) = ActiveCall::global(cx).read(cx).room() else {
+            return div().into_any_element();
+        };
+        let project = self.project.read(cx);
+        if !project.is_local() || room.read(cx).read_only() {
+            return div().into_any_element();
+        }
+
+        let is_shared = project.is_shared();
+        let guests = self.guests(cx);
+        IconButton::new("toggle-project-sharing", IconName::Public)
+            .style(ButtonStyle::Subtle)
+            .icon_size(IconSize::Small)
+            .icon_color(if is_shared {
+                Color::Accent
+            } else {
+                Color::Muted
+            })
+            .selected(is_shared)
+            .tooltip(move |cx| {
+                if !is_shared {
+                    Tooltip::text("Share Project with Call Participants", cx)
+                } else if guests.is_empty() {
+                    Tooltip::with_meta("Stop Sharing Project", None, "No guests yet", cx)
+                } else {
+                    Tooltip::with_meta(
+                        "Stop Sharing Project",
+                        None,
+                        format!("Shared with {}", guests.join(", ")),
+                        cx,
+                    )
+                }
+            })
+            .on_click(cx.listener(|this, _, cx| this.toggle_sharing(cx)))
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for ProjectSharingIndicator {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+}
@@ -16,7 +16,7 @@ use picker::{Picker, PickerDelegate};
 use release_channel::{parse_zed_link, ReleaseChannel};
 use ui::{h_flex, prelude::*, v_flex, HighlightedLabel, KeyBinding, ListItem, ListItemSpacing};
 use util::ResultExt;
-use workspace::{ModalView, Workspace};
+use workspace::{action_registry::humanize_action_name, ModalView, Workspace};
 use zed_actions::OpenZedUrl;
 
 actions!(command_palette, [Toggle]);
@@ -66,10 +66,13 @@ impl CommandPalette {
                     return None;
                 }
 
-                Some(Command {
-                    name: humanize_action_name(&name),
-                    action,
-                })
+                let mut name = humanize_action_name(&name);
+                if let Some(variant_name) = cx.action_variant_name(&*action) {
+                    name.push(' ');
+                    name.push_str(variant_name);
+                }
+
+                Some(Command { name, action })
             })
             .collect();
 
@@ -354,30 +357,6 @@ impl PickerDelegate for CommandPaletteDelegate {
     }
 }
 
-fn humanize_action_name(name: &str) -> String {
-    let capacity = name.len() + name.chars().filter(|c| c.is_uppercase()).count();
-    let mut result = String::with_capacity(capacity);
-    for char in name.chars() {
-        if char == ':' {
-            if result.ends_with(':') {
-                result.push(' ');
-            } else {
-                result.push(':');
-            }
-        } else if char == '_' {
-            result.push(' ');
-        } else if char.is_uppercase() {
-            if !result.ends_with(' ') {
-                result.push(' ');
-            }
-            result.extend(char.to_lowercase());
-        } else {
-            result.push(char);
-        }
-    }
-    result
-}
-
 impl std::fmt::Debug for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Command")
@@ -399,22 +378,6 @@ mod tests {
     use settings::KeymapFile;
     use workspace::{AppState, Workspace};
 
-    #[test]
-    fn test_humanize_action_name() {
-        assert_eq!(
-            humanize_action_name("editor::GoToDefinition"),
-            "editor: go to definition"
-        );
-        assert_eq!(
-            humanize_action_name("editor::Backspace"),
-            "editor: backspace"
-        );
-        assert_eq!(
-            humanize_action_name("go_to_line::Deploy"),
-            "go to line: deploy"
-        );
-    }
-
     #[gpui::test]
     async fn test_command_palette(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
@@ -487,6 +450,38 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_command_palette_action_variants(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+
+        cx.simulate_keystrokes("cmd-n");
+        cx.simulate_keystrokes("cmd-shift-p");
+
+        let palette = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<CommandPalette>(cx)
+                .unwrap()
+                .read(cx)
+                .picker
+                .clone()
+        });
+
+        palette.update(cx, |palette, _| {
+            let names = palette
+                .delegate
+                .commands
+                .iter()
+                .map(|command| command.name.as_str())
+                .collect::<Vec<_>>();
+            for direction in ["up", "down", "left", "right"] {
+                let name = format!("workspace: activate pane in direction {direction}");
+                assert!(names.contains(&name.as_str()), "missing {name:?}");
+            }
+        });
+    }
+
     #[gpui::test]
     async fn test_go_to_line(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
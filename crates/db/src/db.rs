@@ -20,6 +20,7 @@ use sqlez_macros::sql;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use util::{async_maybe, ResultExt};
 
 const CONNECTION_INITIALIZE_QUERY: &'static str = sql!(
@@ -37,6 +38,11 @@ const FALLBACK_DB_NAME: &'static str = "FALLBACK_MEMORY_DB";
 
 const DB_FILE_NAME: &'static str = "db.sqlite";
 
+/// How long to wait before persisting state that changes continuously, such as panel
+/// sizes and pane split ratios while they are being dragged. Callers replace their
+/// pending write on every change, so only the last value gets written.
+pub const WRITE_DEBOUNCE: Duration = Duration::from_millis(100);
+
 lazy_static::lazy_static! {
     pub static ref ZED_STATELESS: bool = std::env::var("ZED_STATELESS").map_or(false, |v| !v.is_empty());
     pub static ref ALL_FILE_DB_FAILED: AtomicBool = AtomicBool::new(false);
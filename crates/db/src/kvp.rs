@@ -1,6 +1,12 @@
+use std::{future::Future, mem, sync::Arc};
+
+use collections::HashMap;
+use gpui::{AppContext, Global, Subscription, Task};
+use parking_lot::Mutex;
 use sqlez_macros::sql;
+use util::ResultExt;
 
-use crate::{define_connection, query};
+use crate::{define_connection, query, WRITE_DEBOUNCE};
 
 define_connection!(pub static ref KEY_VALUE_STORE: KeyValueStore<()> =
     &[sql!(
@@ -31,9 +37,70 @@ impl KeyValueStore {
     }
 }
 
+/// Values passed to [`write_kvp_debounced`] that haven't been written yet, by key,
+/// along with the timers that will write them.
+struct PendingKvpWrites {
+    values: Arc<Mutex<HashMap<String, String>>>,
+    timers: HashMap<String, Task<()>>,
+    _quit_subscription: Subscription,
+}
+
+impl Global for PendingKvpWrites {}
+
+/// Writes `value` under `key` in the [`KEY_VALUE_STORE`] once [`WRITE_DEBOUNCE`] has
+/// passed without another write to the same key, so that state which changes
+/// continuously, like a panel being resized, is only written once it settles.
+///
+/// Writes that are still pending when the app quits are flushed before it exits.
+pub fn write_kvp_debounced(key: impl Into<String>, value: String, cx: &mut AppContext) {
+    if !cx.has_global::<PendingKvpWrites>() {
+        let quit_subscription = cx.on_app_quit(flush_pending_kvp_writes);
+        cx.set_global(PendingKvpWrites {
+            values: Arc::default(),
+            timers: HashMap::default(),
+            _quit_subscription: quit_subscription,
+        });
+    }
+
+    let key = key.into();
+    let executor = cx.background_executor().clone();
+    let pending_writes = cx.global_mut::<PendingKvpWrites>();
+    pending_writes.values.lock().insert(key.clone(), value);
+    let values = pending_writes.values.clone();
+    let timer = executor.spawn({
+        let executor = executor.clone();
+        let key = key.clone();
+        async move {
+            executor.timer(WRITE_DEBOUNCE).await;
+            let value = values.lock().remove(&key);
+            if let Some(value) = value {
+                KEY_VALUE_STORE.write_kvp(key, value).await.log_err();
+            }
+        }
+    });
+    pending_writes.timers.insert(key, timer);
+}
+
+fn flush_pending_kvp_writes(cx: &mut AppContext) -> impl Future<Output = ()> {
+    let values = cx
+        .try_global::<PendingKvpWrites>()
+        .map(|pending_writes| mem::take(&mut *pending_writes.values.lock()))
+        .unwrap_or_default();
+    async move {
+        for (key, value) in values {
+            KEY_VALUE_STORE.write_kvp(key, value).await.log_err();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::kvp::KeyValueStore;
+    use gpui::TestAppContext;
+
+    use crate::{
+        kvp::{write_kvp_debounced, KeyValueStore, KEY_VALUE_STORE},
+        WRITE_DEBOUNCE,
+    };
 
     #[gpui::test]
     async fn test_kvp() {
@@ -59,4 +126,32 @@ mod tests {
         db.delete_kvp("key-1".to_string()).await.unwrap();
         assert_eq!(db.read_kvp("key-1").unwrap(), None);
     }
+
+    #[gpui::test]
+    async fn test_debounced_kvp_writes(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            write_kvp_debounced("debounced-1", "one".to_string(), cx);
+            write_kvp_debounced("debounced-1", "one-2".to_string(), cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(KEY_VALUE_STORE.read_kvp("debounced-1").unwrap(), None);
+
+        // Only the last value written within the debounce interval is stored.
+        cx.executor().advance_clock(WRITE_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            KEY_VALUE_STORE.read_kvp("debounced-1").unwrap(),
+            Some("one-2".to_string())
+        );
+
+        // Writes that are still pending are flushed when the app quits.
+        cx.update(|cx| write_kvp_debounced("debounced-2", "two".to_string(), cx));
+        cx.run_until_parked();
+        assert_eq!(KEY_VALUE_STORE.read_kvp("debounced-2").unwrap(), None);
+        cx.update(|cx| cx.shutdown());
+        assert_eq!(
+            KEY_VALUE_STORE.read_kvp("debounced-2").unwrap(),
+            Some("two".to_string())
+        );
+    }
 }
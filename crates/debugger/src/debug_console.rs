@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+use editor::Editor;
+use gpui::{
+    actions, uniform_list, Action, AppContext, EventEmitter, FocusHandle, FocusableView, Model,
+    Pixels, Subscription, UniformListScrollHandle, View, ViewContext, VisualContext, WindowContext,
+};
+use project::Fs;
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, Tooltip};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+use crate::{
+    debugger_settings::DebuggerSettings,
+    session::{DebugSession, OutputKind, SessionStatus},
+    Continue, Pause, StepInto, StepOut, StepOver, Stop,
+};
+
+actions!(debug_console, [ToggleFocus]);
+
+/// A panel showing the output of the active debug session, with the controls
+/// to run and step the debuggee and a prompt evaluating expressions.
+pub struct DebugConsolePanel {
+    fs: Arc<dyn Fs>,
+    session: Option<Model<DebugSession>>,
+    input: View<Editor>,
+    list: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    _session_subscription: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DebugConsolePanel {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let input = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Evaluate an expression…", cx);
+            editor
+        });
+        let focus_handle = cx.focus_handle();
+        let _subscriptions = vec![
+            cx.on_focus(&focus_handle, |this, cx| cx.focus_view(&this.input)),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+        Self {
+            fs: workspace.app_state().fs.clone(),
+            session: None,
+            input,
+            list: UniformListScrollHandle::new(),
+            focus_handle,
+            width: None,
+            height: None,
+            _session_subscription: None,
+            _subscriptions,
+        }
+    }
+
+    pub fn session(&self) -> Option<&Model<DebugSession>> {
+        self.session.as_ref()
+    }
+
+    pub fn set_session(&mut self, session: Model<DebugSession>, cx: &mut ViewContext<Self>) {
+        if let Some(previous_session) = self.session.take() {
+            previous_session.update(cx, |session, cx| session.stop(cx));
+        }
+        self._session_subscription = Some(cx.observe(&session, |this, session, cx| {
+            let line_count = session.read(cx).output().len();
+            if line_count > 0 {
+                this.list.scroll_to_item(line_count - 1);
+            }
+            cx.notify();
+        }));
+        self.session = Some(session);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let expression = self.input.read(cx).text(cx);
+        if expression.trim().is_empty() {
+            return;
+        }
+        self.input.update(cx, |input, cx| input.clear(cx));
+        session.update(cx, |session, cx| session.evaluate(expression, cx));
+    }
+
+    fn render_controls(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let status = self
+            .session
+            .as_ref()
+            .map(|session| session.read(cx).status().clone());
+        let is_stopped = matches!(status, Some(SessionStatus::Stopped { .. }));
+        let is_running = matches!(status, Some(SessionStatus::Running));
+        let is_active = is_stopped || is_running || status == Some(SessionStatus::Starting);
+
+        let control = |id: &'static str, label: &'static str, action: Box<dyn Action>, enabled| {
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .disabled(!enabled)
+                .tooltip({
+                    let action = action.boxed_clone();
+                    move |cx| Tooltip::for_action(label, &*action, cx)
+                })
+                .on_click(move |_, cx| cx.dispatch_action(action.boxed_clone()))
+        };
+
+        let status_label = match (&self.session, &status) {
+            (Some(session), Some(status)) => {
+                let label = session.read(cx).label();
+                match status {
+                    SessionStatus::Starting => format!("{label}: starting"),
+                    SessionStatus::Running => format!("{label}: running"),
+                    SessionStatus::Stopped { reason } => format!("{label}: paused ({reason})"),
+                    SessionStatus::Terminated => format!("{label}: terminated"),
+                }
+            }
+            _ => "No debug session".to_string(),
+        };
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(control(
+                "continue",
+                "Continue",
+                Box::new(Continue),
+                is_stopped,
+            ))
+            .child(control("pause", "Pause", Box::new(Pause), is_running))
+            .child(control(
+                "step-over",
+                "Step Over",
+                Box::new(StepOver),
+                is_stopped,
+            ))
+            .child(control(
+                "step-into",
+                "Step Into",
+                Box::new(StepInto),
+                is_stopped,
+            ))
+            .child(control(
+                "step-out",
+                "Step Out",
+                Box::new(StepOut),
+                is_stopped,
+            ))
+            .child(control("stop", "Stop", Box::new(Stop), is_active))
+            .child(
+                div().ml_2().child(
+                    Label::new(status_label)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+    }
+}
+
+impl EventEmitter<PanelEvent> for DebugConsolePanel {}
+
+impl Render for DebugConsolePanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let line_count = self
+            .session
+            .as_ref()
+            .map_or(0, |session| session.read(cx).output().len());
+
+        v_flex()
+            .key_context("DebugConsole")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::confirm))
+            .child(self.render_controls(cx))
+            .child(
+                uniform_list(
+                    cx.view().clone(),
+                    "debug-console-output",
+                    line_count,
+                    |this, range, cx| {
+                        let Some(session) = this.session.as_ref() else {
+                            return Vec::new();
+                        };
+                        let output = session.read(cx).output();
+                        range
+                            .filter_map(|ix| output.get(ix))
+                            .map(|line| {
+                                let color = match line.kind {
+                                    OutputKind::Output => Color::Default,
+                                    OutputKind::Input => Color::Muted,
+                                    OutputKind::Error => Color::Error,
+                                };
+                                div()
+                                    .px_2()
+                                    .child(Label::new(line.text.clone()).color(color))
+                                    .into_any_element()
+                            })
+                            .collect()
+                    },
+                )
+                .flex_1()
+                .track_scroll(self.list.clone()),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(self.input.clone()),
+            )
+    }
+}
+
+impl FocusableView for DebugConsolePanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for DebugConsolePanel {
+    fn persistent_name() -> &'static str {
+        "DebugConsolePanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        DebuggerSettings::get_global(cx).console_dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<DebuggerSettings>(self.fs.clone(), cx, move |settings| {
+            settings.console_dock = Some(position)
+        });
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width.unwrap_or_else(|| px(480.)),
+            DockPosition::Bottom => self.height.unwrap_or_else(|| px(280.)),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        cx.notify();
+    }
+
+    fn icon(&self, _: &WindowContext) -> Option<IconName> {
+        self.session.as_ref().map(|_| IconName::Screen)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Debug Console")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
@@ -0,0 +1,133 @@
+mod debug_console;
+mod debugger_settings;
+mod session;
+mod transport;
+mod variables_panel;
+
+use anyhow::anyhow;
+use gpui::{
+    actions, AppContext, AsyncWindowContext, Context, ModelContext, Task, View, ViewContext,
+    VisualContext, WeakView,
+};
+use settings::Settings;
+use transport::DebugAdapterClient;
+use workspace::{
+    notifications::NotifyResultExt,
+    panels::{register_panel, Panel, PanelPlacement},
+    Workspace,
+};
+
+pub use debug_console::DebugConsolePanel;
+pub use debugger_settings::{AdapterCommand, DebugConfiguration, DebugRequest, DebuggerSettings};
+pub use session::DebugSession;
+pub use variables_panel::VariablesPanel;
+
+actions!(
+    debugger,
+    [Start, Continue, Pause, StepOver, StepInto, StepOut, Stop]
+);
+
+pub fn init(cx: &mut AppContext) {
+    DebuggerSettings::register(cx);
+    register_panel(
+        PanelPlacement::default(),
+        |workspace, cx| build_panel(workspace, cx, DebugConsolePanel::new),
+        cx,
+    );
+    register_panel(
+        PanelPlacement::default(),
+        |workspace, cx| build_panel(workspace, cx, VariablesPanel::new),
+        cx,
+    );
+
+    cx.observe_new_views(
+        |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
+            workspace.register_action(start);
+            workspace.register_action(|workspace, _: &Continue, cx| {
+                update_session(workspace, cx, |session, cx| session.continue_(cx));
+            });
+            workspace.register_action(|workspace, _: &Pause, cx| {
+                update_session(workspace, cx, |session, cx| session.pause(cx));
+            });
+            workspace.register_action(|workspace, _: &StepOver, cx| {
+                update_session(workspace, cx, |session, cx| session.step_over(cx));
+            });
+            workspace.register_action(|workspace, _: &StepInto, cx| {
+                update_session(workspace, cx, |session, cx| session.step_into(cx));
+            });
+            workspace.register_action(|workspace, _: &StepOut, cx| {
+                update_session(workspace, cx, |session, cx| session.step_out(cx));
+            });
+            workspace.register_action(|workspace, _: &Stop, cx| {
+                update_session(workspace, cx, |session, cx| session.stop(cx));
+            });
+            workspace.register_action(|workspace, _: &debug_console::ToggleFocus, cx| {
+                workspace.toggle_panel_focus::<DebugConsolePanel>(cx);
+            });
+            workspace.register_action(|workspace, _: &variables_panel::ToggleFocus, cx| {
+                workspace.toggle_panel_focus::<VariablesPanel>(cx);
+            });
+        },
+    )
+    .detach();
+}
+
+/// The debugger panels don't persist any state, so they're built right away.
+fn build_panel<T: Panel>(
+    workspace: WeakView<Workspace>,
+    mut cx: AsyncWindowContext,
+    build: fn(&Workspace, &mut ViewContext<T>) -> T,
+) -> Task<anyhow::Result<View<T>>> {
+    Task::ready(workspace.update(&mut cx, |workspace, cx| {
+        cx.new_view(|cx| build(workspace, cx))
+    }))
+}
+
+/// Starts the first debug configuration of the project, in the root of the
+/// first worktree, replacing any running session.
+fn start(workspace: &mut Workspace, _: &Start, cx: &mut ViewContext<Workspace>) {
+    let Some(configuration) = DebuggerSettings::get_global(cx)
+        .configurations
+        .first()
+        .cloned()
+        .ok_or_else(|| anyhow!("no debug configurations are defined in the settings"))
+        .notify_err(workspace, cx)
+    else {
+        return;
+    };
+    let cwd = workspace
+        .visible_worktrees(cx)
+        .next()
+        .and_then(|worktree| Some(worktree.read(cx).as_local()?.abs_path().to_path_buf()));
+
+    let Some((client, events)) = DebugAdapterClient::start(
+        &configuration.adapter,
+        cwd.as_deref(),
+        cx.background_executor().clone(),
+    )
+    .notify_err(workspace, cx) else {
+        return;
+    };
+    let session = cx.new_model(|cx| DebugSession::new(configuration, client, events, cx));
+
+    if let Some(console) = workspace.panel::<DebugConsolePanel>(cx) {
+        console.update(cx, |console, cx| console.set_session(session.clone(), cx));
+    }
+    if let Some(variables) = workspace.panel::<VariablesPanel>(cx) {
+        variables.update(cx, |variables, cx| variables.set_session(session, cx));
+    }
+    workspace.focus_panel::<DebugConsolePanel>(cx);
+}
+
+fn update_session(
+    workspace: &mut Workspace,
+    cx: &mut ViewContext<Workspace>,
+    update: impl FnOnce(&mut DebugSession, &mut ModelContext<DebugSession>),
+) {
+    let session = workspace
+        .panel::<DebugConsolePanel>(cx)
+        .and_then(|console| console.read(cx).session().cloned());
+    if let Some(session) = session {
+        session.update(cx, update);
+    }
+}
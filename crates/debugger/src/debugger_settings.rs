@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use settings::Settings;
+use workspace::dock::DockPosition;
+
+/// How to start the debug adapter process.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AdapterCommand {
+    /// The adapter executable.
+    pub path: PathBuf,
+    /// Arguments passed to the adapter executable.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Whether a configuration starts a new debuggee or attaches to a running one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DebugRequest {
+    #[default]
+    Launch,
+    Attach,
+}
+
+impl DebugRequest {
+    pub fn command(&self) -> &'static str {
+        match self {
+            DebugRequest::Launch => "launch",
+            DebugRequest::Attach => "attach",
+        }
+    }
+}
+
+/// A way of debugging the project, started with `debugger::Start`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DebugConfiguration {
+    /// The name shown for this configuration.
+    pub label: String,
+    /// The debug adapter to talk to.
+    pub adapter: AdapterCommand,
+    /// Whether to launch or attach to the debuggee.
+    #[serde(default)]
+    pub request: DebugRequest,
+    /// Adapter-specific arguments of the `launch` or `attach` request, such as
+    /// the program to run.
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DebuggerSettings {
+    pub configurations: Vec<DebugConfiguration>,
+    pub console_dock: DockPosition,
+    pub variables_dock: DockPosition,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct DebuggerSettingsContent {
+    /// The debug configurations of the project.
+    ///
+    /// Default: []
+    pub configurations: Option<Vec<DebugConfiguration>>,
+    /// Where to dock the debug console.
+    ///
+    /// Default: bottom
+    pub console_dock: Option<DockPosition>,
+    /// Where to dock the variables and watches panel.
+    ///
+    /// Default: right
+    pub variables_dock: Option<DockPosition>,
+}
+
+impl Settings for DebuggerSettings {
+    const KEY: Option<&'static str> = Some("debugger");
+    type FileContent = DebuggerSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
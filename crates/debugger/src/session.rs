@@ -0,0 +1,457 @@
+use std::{collections::VecDeque, path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use futures::{channel::mpsc, StreamExt};
+use gpui::{ModelContext, SharedString, Task};
+use serde_json::{json, Value};
+use util::ResultExt;
+
+use crate::{
+    debugger_settings::DebugConfiguration,
+    transport::{DapEvent, DebugAdapterClient},
+};
+
+const MAX_OUTPUT_LINES: usize = 5000;
+const MAX_STACK_FRAMES: u64 = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SessionStatus {
+    Starting,
+    Running,
+    Stopped { reason: SharedString },
+    Terminated,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputKind {
+    /// Output of the debuggee or the adapter.
+    Output,
+    /// An expression typed into the console.
+    Input,
+    /// A message about the session itself, such as a failed request.
+    Error,
+}
+
+#[derive(Clone, Debug)]
+pub struct OutputLine {
+    pub kind: OutputKind,
+    pub text: SharedString,
+}
+
+#[derive(Clone, Debug)]
+pub struct StackFrame {
+    pub id: u64,
+    pub name: SharedString,
+    pub path: Option<PathBuf>,
+    pub line: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Variable {
+    pub name: SharedString,
+    pub value: SharedString,
+}
+
+#[derive(Clone, Debug)]
+pub struct Scope {
+    pub name: SharedString,
+    pub variables: Vec<Variable>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Watch {
+    pub expression: SharedString,
+    /// The value of the expression in the current frame, or why it couldn't be
+    /// evaluated. `None` while the debuggee is running.
+    pub value: Option<Result<SharedString, SharedString>>,
+}
+
+/// A running debug adapter and the state of the program it debugs.
+pub struct DebugSession {
+    configuration: DebugConfiguration,
+    client: Arc<DebugAdapterClient>,
+    status: SessionStatus,
+    thread_id: Option<u64>,
+    output: VecDeque<OutputLine>,
+    stack_frames: Vec<StackFrame>,
+    scopes: Vec<Scope>,
+    watches: Vec<Watch>,
+    _event_loop: Task<()>,
+}
+
+impl DebugSession {
+    pub fn new(
+        configuration: DebugConfiguration,
+        client: DebugAdapterClient,
+        mut events: mpsc::UnboundedReceiver<DapEvent>,
+        cx: &mut ModelContext<Self>,
+    ) -> Self {
+        let client = Arc::new(client);
+
+        let _event_loop = cx.spawn(|this, mut cx| async move {
+            while let Some(event) = events.next().await {
+                if this
+                    .update(&mut cx, |this, cx| this.handle_event(event, cx))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            this.update(&mut cx, |this, cx| {
+                this.status = SessionStatus::Terminated;
+                cx.notify();
+            })
+            .ok();
+        });
+
+        let initialize = client.request(
+            "initialize",
+            json!({
+                "clientID": "zed",
+                "clientName": "Zed",
+                "adapterID": configuration.label,
+                "pathFormat": "path",
+                "linesStartAt1": true,
+                "columnsStartAt1": true,
+                "supportsVariableType": false,
+            }),
+        );
+        let client_handle = client.clone();
+        let request = configuration.request;
+        let arguments = configuration.arguments.clone();
+        cx.spawn(|this, mut cx| async move {
+            let result = async {
+                initialize.await?;
+                // Adapters may only answer `launch` once configuration is done, which
+                // happens in response to the `initialized` event.
+                client_handle.request(request.command(), arguments).await
+            }
+            .await;
+            if let Err(error) = result {
+                this.update(&mut cx, |this, cx| {
+                    this.push_output(OutputKind::Error, error.to_string(), cx);
+                    this.status = SessionStatus::Terminated;
+                    cx.notify();
+                })
+                .ok();
+            }
+        })
+        .detach();
+
+        Self {
+            configuration,
+            client,
+            status: SessionStatus::Starting,
+            thread_id: None,
+            output: VecDeque::new(),
+            stack_frames: Vec::new(),
+            scopes: Vec::new(),
+            watches: Vec::new(),
+            _event_loop,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.configuration.label
+    }
+
+    pub fn status(&self) -> &SessionStatus {
+        &self.status
+    }
+
+    pub fn output(&self) -> &VecDeque<OutputLine> {
+        &self.output
+    }
+
+    pub fn stack_frames(&self) -> &[StackFrame] {
+        &self.stack_frames
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn watches(&self) -> &[Watch] {
+        &self.watches
+    }
+
+    fn handle_event(&mut self, event: DapEvent, cx: &mut ModelContext<Self>) {
+        match event.event.as_str() {
+            "initialized" => {
+                let configuration_done = self.client.request("configurationDone", json!({}));
+                cx.background_executor()
+                    .spawn(async move { configuration_done.await.log_err() })
+                    .detach();
+                self.status = SessionStatus::Running;
+            }
+            "output" => {
+                if let Some(output) = event.body["output"].as_str() {
+                    for line in output.lines() {
+                        self.push_output(OutputKind::Output, line.to_string(), cx);
+                    }
+                }
+            }
+            "thread" => {
+                if event.body["reason"] == "started" && self.thread_id.is_none() {
+                    self.thread_id = event.body["threadId"].as_u64();
+                }
+            }
+            "stopped" => {
+                if let Some(thread_id) = event.body["threadId"].as_u64() {
+                    self.thread_id = Some(thread_id);
+                }
+                let reason = event.body["reason"].as_str().unwrap_or("paused");
+                self.status = SessionStatus::Stopped {
+                    reason: reason.to_string().into(),
+                };
+                self.refresh_stopped_state(cx);
+            }
+            "continued" => self.set_running(),
+            "exited" => {
+                if let Some(exit_code) = event.body["exitCode"].as_i64() {
+                    self.push_output(
+                        OutputKind::Output,
+                        format!("Process exited with code {exit_code}"),
+                        cx,
+                    );
+                }
+            }
+            "terminated" => {
+                self.set_running();
+                self.status = SessionStatus::Terminated;
+            }
+            _ => {}
+        }
+        cx.notify();
+    }
+
+    fn set_running(&mut self) {
+        self.status = SessionStatus::Running;
+        self.stack_frames.clear();
+        self.scopes.clear();
+        for watch in &mut self.watches {
+            watch.value = None;
+        }
+    }
+
+    fn push_output(&mut self, kind: OutputKind, text: String, cx: &mut ModelContext<Self>) {
+        if self.output.len() == MAX_OUTPUT_LINES {
+            self.output.pop_front();
+        }
+        self.output.push_back(OutputLine {
+            kind,
+            text: text.into(),
+        });
+        cx.notify();
+    }
+
+    /// Fetches the stack of the stopped thread, the variables of its top frame
+    /// and the values of the watched expressions.
+    fn refresh_stopped_state(&mut self, cx: &mut ModelContext<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+        let client = self.client.clone();
+        let expressions = self
+            .watches
+            .iter()
+            .map(|watch| watch.expression.to_string())
+            .collect::<Vec<_>>();
+        cx.spawn(|this, mut cx| async move {
+            let stack_trace = client
+                .request(
+                    "stackTrace",
+                    json!({ "threadId": thread_id, "levels": MAX_STACK_FRAMES }),
+                )
+                .await?;
+            let stack_frames = stack_trace["stackFrames"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|frame| {
+                    Some(StackFrame {
+                        id: frame["id"].as_u64()?,
+                        name: frame["name"].as_str()?.to_string().into(),
+                        path: frame["source"]["path"].as_str().map(PathBuf::from),
+                        line: frame["line"].as_u64().unwrap_or(0) as u32,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut scopes = Vec::new();
+            let mut watch_values = Vec::new();
+            if let Some(frame_id) = stack_frames.first().map(|frame| frame.id) {
+                let response = client
+                    .request("scopes", json!({ "frameId": frame_id }))
+                    .await?;
+                for scope in response["scopes"].as_array().into_iter().flatten() {
+                    let Some(reference) = scope["variablesReference"].as_u64() else {
+                        continue;
+                    };
+                    let variables = client
+                        .request("variables", json!({ "variablesReference": reference }))
+                        .await?;
+                    scopes.push(Scope {
+                        name: scope["name"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string()
+                            .into(),
+                        variables: variables["variables"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|variable| {
+                                Some(Variable {
+                                    name: variable["name"].as_str()?.to_string().into(),
+                                    value: variable["value"]
+                                        .as_str()
+                                        .unwrap_or_default()
+                                        .to_string()
+                                        .into(),
+                                })
+                            })
+                            .collect(),
+                    });
+                }
+
+                for expression in expressions {
+                    let value = evaluate(&client, &expression, Some(frame_id), "watch").await;
+                    watch_values.push((expression, value));
+                }
+            }
+
+            this.update(&mut cx, |this, cx| {
+                this.stack_frames = stack_frames;
+                this.scopes = scopes;
+                for (expression, value) in watch_values {
+                    if let Some(watch) = this
+                        .watches
+                        .iter_mut()
+                        .find(|watch| watch.expression.as_ref() == expression)
+                    {
+                        watch.value = Some(value);
+                    }
+                }
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn thread_request(&mut self, command: &str, cx: &mut ModelContext<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+        let request = self
+            .client
+            .request(command, json!({ "threadId": thread_id }));
+        if command != "pause" {
+            self.set_running();
+        }
+        cx.spawn(|this, mut cx| async move {
+            if let Err(error) = request.await {
+                this.update(&mut cx, |this, cx| {
+                    this.push_output(OutputKind::Error, error.to_string(), cx)
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+        cx.notify();
+    }
+
+    pub fn continue_(&mut self, cx: &mut ModelContext<Self>) {
+        self.thread_request("continue", cx);
+    }
+
+    pub fn pause(&mut self, cx: &mut ModelContext<Self>) {
+        self.thread_request("pause", cx);
+    }
+
+    pub fn step_over(&mut self, cx: &mut ModelContext<Self>) {
+        self.thread_request("next", cx);
+    }
+
+    pub fn step_into(&mut self, cx: &mut ModelContext<Self>) {
+        self.thread_request("stepIn", cx);
+    }
+
+    pub fn step_out(&mut self, cx: &mut ModelContext<Self>) {
+        self.thread_request("stepOut", cx);
+    }
+
+    pub fn stop(&mut self, cx: &mut ModelContext<Self>) {
+        let disconnect = self
+            .client
+            .request("disconnect", json!({ "terminateDebuggee": true }));
+        cx.background_executor()
+            .spawn(async move { disconnect.await.log_err() })
+            .detach();
+        self.set_running();
+        self.status = SessionStatus::Terminated;
+        cx.notify();
+    }
+
+    /// Evaluates an expression typed into the debug console, in the top frame
+    /// when the debuggee is stopped.
+    pub fn evaluate(&mut self, expression: String, cx: &mut ModelContext<Self>) {
+        self.push_output(OutputKind::Input, format!("> {expression}"), cx);
+        let client = self.client.clone();
+        let frame_id = self.stack_frames.first().map(|frame| frame.id);
+        cx.spawn(|this, mut cx| async move {
+            let result = evaluate(&client, &expression, frame_id, "repl").await;
+            this.update(&mut cx, |this, cx| match result {
+                Ok(value) => this.push_output(OutputKind::Output, value.to_string(), cx),
+                Err(error) => this.push_output(OutputKind::Error, error.to_string(), cx),
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn add_watch(&mut self, expression: String, cx: &mut ModelContext<Self>) {
+        if expression.is_empty()
+            || self
+                .watches
+                .iter()
+                .any(|watch| watch.expression.as_ref() == expression)
+        {
+            return;
+        }
+        self.watches.push(Watch {
+            expression: expression.into(),
+            value: None,
+        });
+        if matches!(self.status, SessionStatus::Stopped { .. }) {
+            self.refresh_stopped_state(cx);
+        }
+        cx.notify();
+    }
+
+    pub fn remove_watch(&mut self, ix: usize, cx: &mut ModelContext<Self>) {
+        if ix < self.watches.len() {
+            self.watches.remove(ix);
+            cx.notify();
+        }
+    }
+}
+
+async fn evaluate(
+    client: &DebugAdapterClient,
+    expression: &str,
+    frame_id: Option<u64>,
+    context: &str,
+) -> Result<SharedString, SharedString> {
+    let mut arguments = json!({ "expression": expression, "context": context });
+    if let Some(frame_id) = frame_id {
+        arguments["frameId"] = Value::from(frame_id);
+    }
+    match client.request("evaluate", arguments).await {
+        Ok(response) => Ok(response["result"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+            .into()),
+        Err(error) => Err(error.to_string().into()),
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    io::Write,
+    path::Path,
+    process::Stdio,
+    sync::{
+        atomic::{AtomicU64, Ordering::SeqCst},
+        Arc,
+    },
+};
+
+use anyhow::{anyhow, Context, Result};
+use collections::HashMap;
+use futures::{
+    channel::{mpsc, oneshot},
+    AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, Future, StreamExt,
+};
+use gpui::{BackgroundExecutor, Task};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use smol::io::BufReader;
+use util::ResultExt;
+
+use crate::debugger_settings::AdapterCommand;
+
+const CONTENT_LENGTH_HEADER: &str = "Content-Length:";
+
+type PendingRequests = Arc<Mutex<Option<HashMap<u64, oneshot::Sender<Result<Value>>>>>>;
+
+/// An event sent by the debug adapter, such as `stopped` or `output`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DapEvent {
+    pub event: String,
+    pub body: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum IncomingMessage {
+    Response {
+        request_seq: u64,
+        success: bool,
+        #[serde(default)]
+        message: Option<String>,
+        #[serde(default)]
+        body: Option<Value>,
+    },
+    Event {
+        event: String,
+        #[serde(default)]
+        body: Option<Value>,
+    },
+    Request {
+        seq: u64,
+        command: String,
+    },
+}
+
+/// A connection to a debug adapter, speaking the Debug Adapter Protocol over
+/// the adapter's stdio.
+pub struct DebugAdapterClient {
+    next_seq: Arc<AtomicU64>,
+    outbound_tx: mpsc::UnboundedSender<String>,
+    pending_requests: PendingRequests,
+    _process: Option<Mutex<smol::process::Child>>,
+    _io_tasks: [Task<Option<()>>; 2],
+}
+
+impl DebugAdapterClient {
+    /// Spawns the adapter process and connects to it.
+    pub fn start(
+        command: &AdapterCommand,
+        cwd: Option<&Path>,
+        executor: BackgroundExecutor,
+    ) -> Result<(Self, mpsc::UnboundedReceiver<DapEvent>)> {
+        let mut process = smol::process::Command::new(&command.path);
+        process
+            .args(&command.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true);
+        if let Some(cwd) = cwd {
+            process.current_dir(cwd);
+        }
+        let mut child = process
+            .spawn()
+            .with_context(|| format!("failed to spawn debug adapter {:?}", command.path))?;
+        let stdin = child.stdin.take().context("no stdin")?;
+        let stdout = child.stdout.take().context("no stdout")?;
+
+        let (mut client, events) = Self::new(stdin, stdout, executor);
+        client._process = Some(Mutex::new(child));
+        Ok((client, events))
+    }
+
+    pub fn new<Stdin, Stdout>(
+        stdin: Stdin,
+        stdout: Stdout,
+        executor: BackgroundExecutor,
+    ) -> (Self, mpsc::UnboundedReceiver<DapEvent>)
+    where
+        Stdin: AsyncWrite + Unpin + Send + 'static,
+        Stdout: AsyncRead + Unpin + Send + 'static,
+    {
+        let next_seq = Arc::new(AtomicU64::new(1));
+        let pending_requests = PendingRequests::new(Mutex::new(Some(HashMap::default())));
+        let (outbound_tx, outbound_rx) = mpsc::unbounded();
+        let (events_tx, events_rx) = mpsc::unbounded();
+
+        let input_task = executor.spawn(
+            Self::handle_input(
+                stdout,
+                pending_requests.clone(),
+                events_tx,
+                outbound_tx.clone(),
+                next_seq.clone(),
+            )
+            .log_err(),
+        );
+        let output_task = executor.spawn(Self::handle_output(stdin, outbound_rx).log_err());
+
+        (
+            Self {
+                next_seq,
+                outbound_tx,
+                pending_requests,
+                _process: None,
+                _io_tasks: [input_task, output_task],
+            },
+            events_rx,
+        )
+    }
+
+    /// Sends a request to the adapter, resolving to the body of its response.
+    pub fn request(&self, command: &str, arguments: Value) -> impl Future<Output = Result<Value>> {
+        let seq = self.next_seq.fetch_add(1, SeqCst);
+        let (tx, rx) = oneshot::channel();
+        let registered = self
+            .pending_requests
+            .lock()
+            .as_mut()
+            .map(|pending| pending.insert(seq, tx))
+            .is_some();
+        let message = json!({
+            "seq": seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        });
+        let sent = registered && self.outbound_tx.unbounded_send(message.to_string()).is_ok();
+        let command = command.to_string();
+        async move {
+            if !sent {
+                return Err(anyhow!("debug adapter is not running"));
+            }
+            rx.await
+                .map_err(|_| anyhow!("debug adapter exited before answering {command:?}"))?
+        }
+    }
+
+    async fn handle_input<Stdout>(
+        stdout: Stdout,
+        pending_requests: PendingRequests,
+        events_tx: mpsc::UnboundedSender<DapEvent>,
+        outbound_tx: mpsc::UnboundedSender<String>,
+        next_seq: Arc<AtomicU64>,
+    ) -> Result<()>
+    where
+        Stdout: AsyncRead + Unpin + Send + 'static,
+    {
+        let _clear_pending_requests = util::defer({
+            let pending_requests = pending_requests.clone();
+            move || {
+                pending_requests.lock().take();
+            }
+        });
+        let mut stdout = BufReader::new(stdout);
+        let mut buffer = Vec::new();
+        loop {
+            let mut content_length = None;
+            loop {
+                buffer.clear();
+                if stdout.read_until(b'\n', &mut buffer).await? == 0 {
+                    return Ok(());
+                }
+                let line = std::str::from_utf8(&buffer)?.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(length) = line.strip_prefix(CONTENT_LENGTH_HEADER) {
+                    content_length = Some(length.trim().parse::<usize>()?);
+                }
+            }
+            let content_length = content_length
+                .ok_or_else(|| anyhow!("debug adapter message without a content length"))?;
+            buffer.resize(content_length, 0);
+            stdout.read_exact(&mut buffer).await?;
+            log::trace!(
+                "incoming debug adapter message: {}",
+                String::from_utf8_lossy(&buffer)
+            );
+
+            match serde_json::from_slice::<IncomingMessage>(&buffer) {
+                Ok(IncomingMessage::Response {
+                    request_seq,
+                    success,
+                    message,
+                    body,
+                }) => {
+                    let handler = pending_requests
+                        .lock()
+                        .as_mut()
+                        .and_then(|pending| pending.remove(&request_seq));
+                    if let Some(handler) = handler {
+                        let result = if success {
+                            Ok(body.unwrap_or(Value::Null))
+                        } else {
+                            Err(anyhow!(message.unwrap_or_else(|| "request failed".into())))
+                        };
+                        handler.send(result).ok();
+                    }
+                }
+                Ok(IncomingMessage::Event { event, body }) => {
+                    events_tx
+                        .unbounded_send(DapEvent {
+                            event,
+                            body: body.unwrap_or(Value::Null),
+                        })
+                        .ok();
+                }
+                Ok(IncomingMessage::Request { seq, command }) => {
+                    // Reverse requests such as `runInTerminal` aren't supported yet.
+                    let response = json!({
+                        "seq": next_seq.fetch_add(1, SeqCst),
+                        "type": "response",
+                        "request_seq": seq,
+                        "command": command,
+                        "success": false,
+                        "message": "not supported",
+                    });
+                    outbound_tx.unbounded_send(response.to_string()).ok();
+                }
+                Err(error) => {
+                    log::error!("invalid debug adapter message: {error}");
+                }
+            }
+        }
+    }
+
+    async fn handle_output<Stdin>(
+        stdin: Stdin,
+        mut outbound_rx: mpsc::UnboundedReceiver<String>,
+    ) -> Result<()>
+    where
+        Stdin: AsyncWrite + Unpin + Send + 'static,
+    {
+        let mut stdin = stdin;
+        let mut header = Vec::new();
+        while let Some(message) = outbound_rx.next().await {
+            log::trace!("outgoing debug adapter message: {}", message);
+            header.clear();
+            write!(header, "{CONTENT_LENGTH_HEADER} {}\r\n\r\n", message.len()).unwrap();
+            stdin.write_all(&header).await?;
+            stdin.write_all(message.as_bytes()).await?;
+            stdin.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    async fn read_message(reader: &mut BufReader<async_pipe::PipeReader>) -> Value {
+        let mut header = String::new();
+        reader.read_line(&mut header).await.unwrap();
+        let length = header
+            .strip_prefix(CONTENT_LENGTH_HEADER)
+            .unwrap()
+            .trim()
+            .parse::<usize>()
+            .unwrap();
+        let mut blank_line = String::new();
+        reader.read_line(&mut blank_line).await.unwrap();
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn write_message(writer: &mut async_pipe::PipeWriter, message: Value) {
+        let message = message.to_string();
+        writer
+            .write_all(
+                format!("{CONTENT_LENGTH_HEADER} {}\r\n\r\n{message}", message.len()).as_bytes(),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_requests_and_events(cx: &mut TestAppContext) {
+        let (stdin_writer, stdin_reader) = async_pipe::pipe();
+        let (mut stdout_writer, stdout_reader) = async_pipe::pipe();
+        let (client, mut events) =
+            DebugAdapterClient::new(stdin_writer, stdout_reader, cx.executor());
+        let mut adapter_input = BufReader::new(stdin_reader);
+
+        let response = cx.executor().spawn(client.request("threads", json!({})));
+        let request = read_message(&mut adapter_input).await;
+        assert_eq!(request["type"], "request");
+        assert_eq!(request["command"], "threads");
+
+        write_message(
+            &mut stdout_writer,
+            json!({
+                "seq": 1,
+                "type": "event",
+                "event": "stopped",
+                "body": { "threadId": 7 },
+            }),
+        )
+        .await;
+        write_message(
+            &mut stdout_writer,
+            json!({
+                "seq": 2,
+                "type": "response",
+                "request_seq": request["seq"],
+                "command": "threads",
+                "success": true,
+                "body": { "threads": [] },
+            }),
+        )
+        .await;
+
+        assert_eq!(
+            events.next().await.unwrap(),
+            DapEvent {
+                event: "stopped".into(),
+                body: json!({ "threadId": 7 }),
+            }
+        );
+        assert_eq!(response.await.unwrap(), json!({ "threads": [] }));
+
+        let failing = cx
+            .executor()
+            .spawn(client.request("evaluate", json!({ "expression": "x" })));
+        let request = read_message(&mut adapter_input).await;
+        write_message(
+            &mut stdout_writer,
+            json!({
+                "seq": 3,
+                "type": "response",
+                "request_seq": request["seq"],
+                "command": "evaluate",
+                "success": false,
+                "message": "not available",
+            }),
+        )
+        .await;
+        assert_eq!(failing.await.unwrap_err().to_string(), "not available");
+    }
+}
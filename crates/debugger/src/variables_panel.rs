@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use editor::Editor;
+use gpui::{
+    actions, Action, AppContext, EventEmitter, FocusHandle, FocusableView, Model, Pixels,
+    SharedString, Subscription, View, ViewContext, VisualContext, WindowContext,
+};
+use project::Fs;
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, Tooltip};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+use crate::{debugger_settings::DebuggerSettings, session::DebugSession};
+
+actions!(debug_variables, [ToggleFocus]);
+
+/// A panel showing the call stack and the variables of the paused debuggee,
+/// along with a list of watched expressions.
+pub struct VariablesPanel {
+    fs: Arc<dyn Fs>,
+    session: Option<Model<DebugSession>>,
+    watch_input: View<Editor>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    _session_subscription: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl VariablesPanel {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let watch_input = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Add a watch expression…", cx);
+            editor
+        });
+        let focus_handle = cx.focus_handle();
+        let _subscriptions = vec![
+            cx.on_focus(&focus_handle, |this, cx| cx.focus_view(&this.watch_input)),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+        Self {
+            fs: workspace.app_state().fs.clone(),
+            session: None,
+            watch_input,
+            focus_handle,
+            width: None,
+            height: None,
+            _session_subscription: None,
+            _subscriptions,
+        }
+    }
+
+    pub fn set_session(&mut self, session: Model<DebugSession>, cx: &mut ViewContext<Self>) {
+        self._session_subscription = Some(cx.observe(&session, |_, _, cx| cx.notify()));
+        self.session = Some(session);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let expression = self.watch_input.read(cx).text(cx).trim().to_string();
+        self.watch_input.update(cx, |input, cx| input.clear(cx));
+        session.update(cx, |session, cx| session.add_watch(expression, cx));
+    }
+
+    fn render_section_header(&self, title: &'static str, cx: &ViewContext<Self>) -> Div {
+        div()
+            .px_2()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+    }
+
+    fn render_row(name: SharedString, value: SharedString, color: Color) -> Div {
+        h_flex()
+            .px_2()
+            .gap_2()
+            .child(Label::new(name))
+            .child(Label::new(value).color(color))
+    }
+}
+
+impl EventEmitter<PanelEvent> for VariablesPanel {}
+
+impl Render for VariablesPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let mut content = v_flex().id("debug-variables").flex_1().overflow_y_scroll();
+
+        if let Some(session) = self.session.clone() {
+            let session = session.read(cx);
+
+            content = content.child(self.render_section_header("Call Stack", cx));
+            content = content.children(session.stack_frames().iter().map(|frame| {
+                let location = match &frame.path {
+                    Some(path) => format!("{}:{}", path.display(), frame.line),
+                    None => String::new(),
+                };
+                Self::render_row(frame.name.clone(), location.into(), Color::Muted)
+            }));
+
+            for scope in session.scopes() {
+                content = content.child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .border_b_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(
+                            Label::new(scope.name.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                );
+                content = content.children(scope.variables.iter().map(|variable| {
+                    Self::render_row(variable.name.clone(), variable.value.clone(), Color::Muted)
+                }));
+            }
+
+            content = content.child(self.render_section_header("Watch", cx));
+            content = content.children(session.watches().iter().enumerate().map(|(ix, watch)| {
+                let (value, color) = match &watch.value {
+                    Some(Ok(value)) => (value.clone(), Color::Muted),
+                    Some(Err(error)) => (error.clone(), Color::Error),
+                    None => ("not available".into(), Color::Disabled),
+                };
+                Self::render_row(watch.expression.clone(), value, color).child(
+                    div().ml_auto().child(
+                        IconButton::new(("remove-watch", ix), IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| Tooltip::text("Remove Watch", cx))
+                            .on_click(cx.listener(move |this, _, cx| {
+                                if let Some(session) = this.session.clone() {
+                                    session.update(cx, |session, cx| session.remove_watch(ix, cx));
+                                }
+                            })),
+                    ),
+                )
+            }));
+        } else {
+            content = content.child(
+                div()
+                    .p_2()
+                    .child(Label::new("No debug session").color(Color::Muted)),
+            );
+        }
+
+        v_flex()
+            .key_context("DebugVariables")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::confirm))
+            .child(content)
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(self.watch_input.clone()),
+            )
+    }
+}
+
+impl FocusableView for VariablesPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for VariablesPanel {
+    fn persistent_name() -> &'static str {
+        "DebugVariablesPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        DebuggerSettings::get_global(cx).variables_dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<DebuggerSettings>(self.fs.clone(), cx, move |settings| {
+            settings.variables_dock = Some(position)
+        });
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width.unwrap_or_else(|| px(320.)),
+            DockPosition::Bottom => self.height.unwrap_or_else(|| px(280.)),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        cx.notify();
+    }
+
+    fn icon(&self, _: &WindowContext) -> Option<IconName> {
+        self.session.as_ref().map(|_| IconName::Hash)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Debug Variables")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
@@ -1,3 +1,5 @@
+pub mod diagnostics_panel;
+mod diagnostics_panel_settings;
 pub mod items;
 mod project_diagnostics_settings;
 mod toolbar_controls;
@@ -39,7 +41,7 @@ pub use toolbar_controls::ToolbarControls;
 use ui::{h_flex, prelude::*, Icon, IconName, Label};
 use util::TryFutureExt;
 use workspace::{
-    item::{BreadcrumbText, Item, ItemEvent, ItemHandle},
+    item::{BreadcrumbText, Item, ItemEvent, ItemHandle, SerializableItem},
     ItemNavHistory, Pane, ToolbarItemLocation, Workspace,
 };
 
@@ -49,6 +51,8 @@ const CONTEXT_LINE_COUNT: u32 = 1;
 
 pub fn init(cx: &mut AppContext) {
     ProjectDiagnosticsSettings::register(cx);
+    diagnostics_panel::init(cx);
+    workspace::register_serializable_item::<ProjectDiagnosticsEditor>(cx);
     cx.observe_new_views(ProjectDiagnosticsEditor::register)
         .detach();
 }
@@ -631,6 +635,11 @@ impl Item for ProjectDiagnosticsEditor {
         Editor::to_item_events(event, f)
     }
 
+    fn set_read_only(&mut self, read_only: bool, cx: &mut ViewContext<Self>) {
+        self.editor
+            .update(cx, |editor, cx| Item::set_read_only(editor, read_only, cx));
+    }
+
     fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
         self.editor.update(cx, |editor, cx| editor.deactivated(cx));
     }
@@ -779,9 +788,11 @@ impl Item for ProjectDiagnosticsEditor {
         self.editor
             .update(cx, |editor, cx| editor.added_to_workspace(workspace, cx));
     }
+}
 
-    fn serialized_item_kind() -> Option<&'static str> {
-        Some("diagnostics")
+impl SerializableItem for ProjectDiagnosticsEditor {
+    fn serialized_item_kind() -> &'static str {
+        "diagnostics"
     }
 
     fn deserialize(
@@ -789,6 +800,7 @@ impl Item for ProjectDiagnosticsEditor {
         workspace: WeakView<Workspace>,
         _workspace_id: workspace::WorkspaceId,
         _item_id: workspace::ItemId,
+        _state: Option<String>,
         cx: &mut ViewContext<Pane>,
     ) -> Task<Result<View<Self>>> {
         Task::ready(Ok(cx.new_view(|cx| Self::new(project, workspace, cx))))
@@ -0,0 +1,640 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use collections::HashSet;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use editor::{scroll::Autoscroll, Editor};
+use gpui::{
+    actions, uniform_list, Action, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, InteractiveElement, IntoElement, Model, ParentElement, Pixels, Render,
+    SharedString, Styled, Subscription, Task, UniformListScrollHandle, View, ViewContext,
+    VisualContext, WeakView, WindowContext,
+};
+use language::{DiagnosticSeverity, Point};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
+use project::{Fs, Project, ProjectPath};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use theme::ActiveTheme;
+use ui::{prelude::*, Icon, IconName, Label, ListItem, Tooltip};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    panels::{register_panel, PanelPlacement},
+    Workspace,
+};
+
+use crate::diagnostics_panel_settings::DiagnosticsPanelSettings;
+
+const DIAGNOSTICS_PANEL_KEY: &'static str = "DiagnosticsPanel";
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(50);
+
+actions!(diagnostics_panel, [ToggleFocus, SortBySeverity, SortByFile]);
+
+pub fn init(cx: &mut AppContext) {
+    DiagnosticsPanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), DiagnosticsPanel::load, cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<DiagnosticsPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum SortOrder {
+    #[default]
+    Severity,
+    File,
+}
+
+/// A primary diagnostic, as listed in the panel.
+struct PanelEntry {
+    path: ProjectPath,
+    position: Point,
+    severity: DiagnosticSeverity,
+    message: SharedString,
+    source: Option<SharedString>,
+}
+
+/// A dock panel listing the errors and warnings of the whole project, kept up to
+/// date as language servers publish diagnostics.
+pub struct DiagnosticsPanel {
+    project: Model<Project>,
+    workspace: WeakView<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    entries: Vec<PanelEntry>,
+    selected_index: Option<usize>,
+    sort_order: SortOrder,
+    list: UniformListScrollHandle,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    refresh_task: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedDiagnosticsPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    #[serde(default)]
+    sort_order: SortOrder,
+}
+
+impl DiagnosticsPanel {
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let _subscriptions = vec![
+            cx.subscribe(&project, |this, _, event, cx| match event {
+                project::Event::DiagnosticsUpdated { .. }
+                | project::Event::DiskBasedDiagnosticsFinished { .. } => this.refresh(cx),
+                _ => {}
+            }),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+
+        let mut this = Self {
+            project,
+            workspace: workspace.weak_handle(),
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            entries: Vec::new(),
+            selected_index: None,
+            sort_order: SortOrder::default(),
+            list: UniformListScrollHandle::new(),
+            width: None,
+            height: None,
+            refresh_task: Task::ready(None),
+            _subscriptions,
+        };
+        this.refresh(cx);
+        this
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(DIAGNOSTICS_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedDiagnosticsPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = cx.new_view(|cx| Self::new(workspace, cx));
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                        panel.set_sort_order(serialized_panel.sort_order, cx);
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedDiagnosticsPanel {
+            width: self.width,
+            height: self.height,
+            sort_order: self.sort_order,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(DIAGNOSTICS_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    /// Re-reads the diagnostics of every path that has any, once the language
+    /// servers have stopped publishing for a moment.
+    fn refresh(&mut self, cx: &mut ViewContext<Self>) {
+        let project = self.project.clone();
+        let mut paths = project
+            .read(cx)
+            .diagnostic_summaries(false, cx)
+            .map(|(path, _, _)| path)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        paths.sort_by(|a, b| a.path.cmp(&b.path));
+
+        self.refresh_task = cx.spawn(|this, mut cx| {
+            async move {
+                cx.background_executor().timer(REFRESH_DEBOUNCE).await;
+
+                let mut entries = Vec::new();
+                for path in paths {
+                    let open_buffer = project
+                        .update(&mut cx, |project, cx| project.open_buffer(path.clone(), cx))?;
+                    let Some(buffer) = open_buffer.await.log_err() else {
+                        continue;
+                    };
+                    let snapshot = buffer.update(&mut cx, |buffer, _| buffer.snapshot())?;
+                    entries.extend(
+                        snapshot
+                            .diagnostics_in_range::<_, Point>(0..snapshot.len(), false)
+                            .filter(|entry| {
+                                entry.diagnostic.is_primary
+                                    && entry.diagnostic.severity <= DiagnosticSeverity::WARNING
+                            })
+                            .map(|entry| PanelEntry {
+                                path: path.clone(),
+                                position: entry.range.start,
+                                severity: entry.diagnostic.severity,
+                                message: entry
+                                    .diagnostic
+                                    .message
+                                    .lines()
+                                    .next()
+                                    .unwrap_or_default()
+                                    .to_string()
+                                    .into(),
+                                source: entry.diagnostic.source.map(Into::into),
+                            }),
+                    );
+                }
+
+                this.update(&mut cx, |this, cx| {
+                    this.entries = entries;
+                    this.sort_entries();
+                    this.selected_index = this
+                        .selected_index
+                        .map(|ix| ix.min(this.entries.len().saturating_sub(1)))
+                        .filter(|_| !this.entries.is_empty());
+                    cx.notify();
+                })
+            }
+            .log_err()
+        });
+    }
+
+    fn sort_entries(&mut self) {
+        match self.sort_order {
+            SortOrder::Severity => self.entries.sort_by(|a, b| {
+                a.severity
+                    .cmp(&b.severity)
+                    .then_with(|| a.path.path.cmp(&b.path.path))
+                    .then_with(|| a.position.cmp(&b.position))
+            }),
+            SortOrder::File => self.entries.sort_by(|a, b| {
+                a.path
+                    .path
+                    .cmp(&b.path.path)
+                    .then_with(|| a.position.cmp(&b.position))
+            }),
+        }
+    }
+
+    fn set_sort_order(&mut self, sort_order: SortOrder, cx: &mut ViewContext<Self>) {
+        if self.sort_order != sort_order {
+            self.sort_order = sort_order;
+            self.sort_entries();
+            self.selected_index = None;
+            self.serialize(cx);
+            cx.notify();
+        }
+    }
+
+    fn sort_by_severity(&mut self, _: &SortBySeverity, cx: &mut ViewContext<Self>) {
+        self.set_sort_order(SortOrder::Severity, cx);
+    }
+
+    fn sort_by_file(&mut self, _: &SortByFile, cx: &mut ViewContext<Self>) {
+        self.set_sort_order(SortOrder::File, cx);
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix < self.entries.len() {
+            self.selected_index = Some(ix);
+            self.list.scroll_to_item(ix);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, cx: &mut ViewContext<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, cx: &mut ViewContext<Self>) {
+        self.select_index(self.entries.len().saturating_sub(1), cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_entry(ix, cx);
+        }
+    }
+
+    /// Opens the diagnostic's file in the center pane, with the cursor at the
+    /// start of the diagnostic.
+    fn open_entry(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let path = entry.path.clone();
+        let position = entry.position;
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let open_path = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(path, None, true, cx)
+        });
+        cx.spawn(|_, mut cx| async move {
+            let item = open_path.await?;
+            if let Some(editor) = item.downcast::<Editor>() {
+                editor.update(&mut cx, |editor, cx| {
+                    editor.change_selections(Some(Autoscroll::center()), cx, |selections| {
+                        selections.select_ranges([position..position])
+                    });
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let (icon, color) = if entry.severity == DiagnosticSeverity::ERROR {
+            (IconName::XCircle, Color::Error)
+        } else {
+            (IconName::ExclamationTriangle, Color::Warning)
+        };
+        let location = format!(
+            "{}:{}:{}",
+            entry.path.path.display(),
+            entry.position.row + 1,
+            entry.position.column + 1
+        );
+
+        ListItem::new(ix)
+            .inset(true)
+            .selected(self.selected_index == Some(ix))
+            .start_slot(Icon::new(icon).size(IconSize::Small).color(color))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(entry.message.clone()))
+                    .children(entry.source.clone().map(|source| {
+                        Label::new(source)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    }))
+                    .child(
+                        Label::new(location)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, cx| {
+                this.selected_index = Some(ix);
+                this.open_entry(ix, cx);
+                cx.notify();
+            }))
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let error_count = self
+            .entries
+            .iter()
+            .filter(|entry| entry.severity == DiagnosticSeverity::ERROR)
+            .count();
+        let warning_count = self.entries.len() - error_count;
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Label::new(format!("{error_count} errors, {warning_count} warnings"))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("sort-by-severity", "Severity")
+                            .label_size(LabelSize::Small)
+                            .selected(self.sort_order == SortOrder::Severity)
+                            .tooltip(|cx| {
+                                Tooltip::for_action("Sort by Severity", &SortBySeverity, cx)
+                            })
+                            .on_click(|_, cx| cx.dispatch_action(SortBySeverity.boxed_clone())),
+                    )
+                    .child(
+                        Button::new("sort-by-file", "File")
+                            .label_size(LabelSize::Small)
+                            .selected(self.sort_order == SortOrder::File)
+                            .tooltip(|cx| Tooltip::for_action("Sort by File", &SortByFile, cx))
+                            .on_click(|_, cx| cx.dispatch_action(SortByFile.boxed_clone())),
+                    ),
+            )
+    }
+}
+
+impl EventEmitter<PanelEvent> for DiagnosticsPanel {}
+
+impl Render for DiagnosticsPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("diagnostics-panel")
+            .size_full()
+            .key_context("DiagnosticsPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::sort_by_severity))
+            .on_action(cx.listener(Self::sort_by_file))
+            .child(self.render_header(cx))
+            .child(if self.entries.is_empty() {
+                h_flex()
+                    .flex_1()
+                    .justify_center()
+                    .child(Label::new("No problems in the project").color(Color::Muted))
+                    .into_any_element()
+            } else {
+                uniform_list(
+                    cx.view().clone(),
+                    "diagnostics",
+                    self.entries.len(),
+                    |this, range, cx| {
+                        range
+                            .map(|ix| this.render_entry(ix, cx).into_any_element())
+                            .collect()
+                    },
+                )
+                .flex_1()
+                .track_scroll(self.list.clone())
+                .into_any_element()
+            })
+    }
+}
+
+impl FocusableView for DiagnosticsPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for DiagnosticsPanel {
+    fn persistent_name() -> &'static str {
+        "DiagnosticsPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        DiagnosticsPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<DiagnosticsPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = DiagnosticsPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        DiagnosticsPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::ExclamationTriangle)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Diagnostics Panel")
+    }
+
+    fn icon_label(&self, _: &WindowContext) -> Option<String> {
+        let count = self.entries.len();
+        (count > 0).then(|| count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::{Diagnostic, DiagnosticEntry, PointUtf16, Unclipped};
+    use lsp::LanguageServerId;
+    use project::FakeFs;
+    use serde_json::json;
+    use std::path::{Path, PathBuf};
+
+    #[gpui::test]
+    async fn test_diagnostics_panel_sorting(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/test",
+            json!({
+                "a.rs": "let a = 1;\nlet b = 2;\n",
+                "b.rs": "let c = 3;\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/test".as_ref()], cx).await;
+        let window = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let workspace = window.root(cx).unwrap();
+
+        let entry = |row, severity| DiagnosticEntry {
+            range: Unclipped(PointUtf16::new(row, 4))..Unclipped(PointUtf16::new(row, 5)),
+            diagnostic: Diagnostic {
+                message: "message".to_string(),
+                severity,
+                is_primary: true,
+                group_id: row as usize,
+                ..Default::default()
+            },
+        };
+        project.update(cx, |project, cx| {
+            project
+                .update_diagnostic_entries(
+                    LanguageServerId(0),
+                    PathBuf::from("/test/a.rs"),
+                    None,
+                    vec![
+                        entry(0, DiagnosticSeverity::WARNING),
+                        entry(1, DiagnosticSeverity::ERROR),
+                    ],
+                    cx,
+                )
+                .unwrap();
+            project
+                .update_diagnostic_entries(
+                    LanguageServerId(0),
+                    PathBuf::from("/test/b.rs"),
+                    None,
+                    vec![entry(0, DiagnosticSeverity::ERROR)],
+                    cx,
+                )
+                .unwrap();
+        });
+
+        let panel = workspace.update(cx, |workspace, cx| {
+            cx.new_view(|cx| DiagnosticsPanel::new(workspace, cx))
+        });
+        cx.executor().advance_clock(REFRESH_DEBOUNCE);
+        cx.run_until_parked();
+
+        let locations = |panel: &View<DiagnosticsPanel>, cx: &mut VisualTestContext| {
+            panel.update(cx, |panel, _| {
+                panel
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.path.path.to_path_buf(), entry.position.row))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            locations(&panel, cx),
+            [
+                (Path::new("a.rs").to_path_buf(), 1),
+                (Path::new("b.rs").to_path_buf(), 0),
+                (Path::new("a.rs").to_path_buf(), 0),
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.set_sort_order(SortOrder::File, cx));
+        assert_eq!(
+            locations(&panel, cx),
+            [
+                (Path::new("a.rs").to_path_buf(), 0),
+                (Path::new("a.rs").to_path_buf(), 1),
+                (Path::new("b.rs").to_path_buf(), 0),
+            ]
+        );
+
+        // Diagnostics published later are picked up without reopening the panel.
+        project.update(cx, |project, cx| {
+            project
+                .update_diagnostic_entries(
+                    LanguageServerId(0),
+                    PathBuf::from("/test/b.rs"),
+                    None,
+                    Vec::new(),
+                    cx,
+                )
+                .unwrap();
+        });
+        cx.executor().advance_clock(REFRESH_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            locations(&panel, cx),
+            [
+                (Path::new("a.rs").to_path_buf(), 0),
+                (Path::new("a.rs").to_path_buf(), 1),
+            ]
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            client::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+            editor::init(cx);
+        });
+    }
+}
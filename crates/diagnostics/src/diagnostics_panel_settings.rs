@@ -0,0 +1,49 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug)]
+pub struct DiagnosticsPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+}
+
+/// Diagnostics panel configuration.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct DiagnosticsPanelSettingsContent {
+    /// Whether to show the diagnostics panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the diagnostics panel.
+    ///
+    /// Default: bottom
+    pub dock: Option<DockPosition>,
+    /// Default width of the diagnostics panel in pixels, when docked to the left or right.
+    ///
+    /// Default: 480
+    pub default_width: Option<f32>,
+    /// Default height of the diagnostics panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 240
+    pub default_height: Option<f32>,
+}
+
+impl settings::Settings for DiagnosticsPanelSettings {
+    const KEY: Option<&'static str> = Some("diagnostics_panel");
+    type FileContent = DiagnosticsPanelSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _cx: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
@@ -0,0 +1,658 @@
+//! Side-by-side diffs: an item showing two buffers next to each other, the old one
+//! on the left and the new one on the right, with their changes highlighted and their
+//! scroll positions kept in sync.
+
+use std::ops::Range;
+
+use anyhow::{Context as _, Result};
+use git::diff::BufferDiff;
+use gpui::{
+    AnyElement, AppContext, EntityId, EventEmitter, FocusHandle, FocusableView, HighlightStyle,
+    Hsla, Model, Render, SharedString, Subscription, Task, View, ViewContext, VisualContext,
+    WindowContext,
+};
+use language::{Buffer, Capability, Point};
+use project::{Project, ProjectPath};
+use text::{Anchor, BufferId};
+use theme::color_alpha;
+use ui::{prelude::*, Tooltip};
+use util::ResultExt;
+use workspace::{
+    item::{Item, ItemEvent},
+    notifications::NotifyTaskExt,
+    DiffActiveFile, Workspace,
+};
+
+use crate::{
+    actions::{GoToHunk, GoToPrevHunk},
+    scroll::Autoscroll,
+    Direction, Editor, EditorEvent,
+};
+
+const CHANGE_HIGHLIGHT_ALPHA: f32 = 0.2;
+
+enum DiffViewDeletions {}
+enum DiffViewInsertions {}
+
+/// A region that differs between the two sides of a [`DiffView`], as the rows it
+/// spans in each buffer. One of the ranges is empty when lines were only added or
+/// only removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffViewHunk {
+    pub old_rows: Range<u32>,
+    pub new_rows: Range<u32>,
+}
+
+/// Shows two buffers side by side. The left one is usually a read-only copy of a
+/// file's contents in git HEAD, and the right one the file itself, which can be edited
+/// from the view. The diff is recomputed whenever either buffer changes.
+pub struct DiffView {
+    old_editor: View<Editor>,
+    new_editor: View<Editor>,
+    old_buffer: Model<Buffer>,
+    new_buffer: Model<Buffer>,
+    old_title: SharedString,
+    new_title: SharedString,
+    /// The file compared with git HEAD, for views opened with [`DiffView::open_head_diff`].
+    head_diff_path: Option<ProjectPath>,
+    hunks: Vec<DiffViewHunk>,
+    active_hunk: Option<usize>,
+    /// The editor whose scroll position was just set to follow the other one, so that
+    /// the resulting event isn't synced back.
+    scroll_follower: Option<EntityId>,
+    diff_task: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DiffView {
+    pub fn new(
+        old_buffer: Model<Buffer>,
+        new_buffer: Model<Buffer>,
+        old_title: impl Into<SharedString>,
+        new_title: impl Into<SharedString>,
+        project: Option<Model<Project>>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let old_editor = cx.new_view(|cx| {
+            let mut editor = Editor::for_buffer(old_buffer.clone(), project.clone(), cx);
+            editor.set_read_only(true);
+            editor
+        });
+        let new_editor = cx.new_view(|cx| Editor::for_buffer(new_buffer.clone(), project, cx));
+        let _subscriptions = vec![
+            cx.subscribe(&old_buffer, Self::handle_buffer_event),
+            cx.subscribe(&new_buffer, Self::handle_buffer_event),
+            cx.subscribe(&old_editor, Self::handle_editor_event),
+            cx.subscribe(&new_editor, Self::handle_editor_event),
+        ];
+
+        let mut this = Self {
+            old_editor,
+            new_editor,
+            old_buffer,
+            new_buffer,
+            old_title: old_title.into(),
+            new_title: new_title.into(),
+            head_diff_path: None,
+            hunks: Vec::new(),
+            active_hunk: None,
+            scroll_follower: None,
+            diff_task: Task::ready(None),
+            _subscriptions,
+        };
+        this.update_diff(cx);
+        this
+    }
+
+    /// Opens a view comparing the file at `project_path` with its contents in git
+    /// HEAD, or activates the one already open. Files that aren't in HEAD yet are
+    /// compared with an empty buffer.
+    pub fn open_head_diff(
+        workspace: &mut Workspace,
+        project_path: ProjectPath,
+        cx: &mut ViewContext<Workspace>,
+    ) -> Task<Result<View<Self>>> {
+        let existing = workspace
+            .items_of_type::<Self>(cx)
+            .find(|view| view.read(cx).head_diff_path.as_ref() == Some(&project_path));
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, cx);
+            return Task::ready(Ok(existing));
+        }
+
+        let project = workspace.project().clone();
+        let open_buffer = project.update(cx, |project, cx| {
+            project.open_buffer(project_path.clone(), cx)
+        });
+        cx.spawn(|workspace, mut cx| async move {
+            let buffer = open_buffer.await?;
+            let (repo_path, repository) = buffer
+                .update(&mut cx, |buffer, cx| {
+                    let file = project::File::from_dyn(buffer.file())?;
+                    file.worktree
+                        .read(cx)
+                        .as_local()?
+                        .local_git_repository_for_path(&file.path)
+                })?
+                .with_context(|| {
+                    format!("{} isn't in a git repository", project_path.path.display())
+                })?;
+            let head_text = cx
+                .background_executor()
+                .spawn(async move { repository.lock().load_head_text(&repo_path) })
+                .await
+                .unwrap_or_default();
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let language = buffer.read(cx).language().cloned();
+                let head_buffer = cx.new_model(|cx| {
+                    let mut head_buffer = Buffer::new(
+                        0,
+                        BufferId::new(cx.entity_id().as_u64()).unwrap(),
+                        head_text,
+                    );
+                    head_buffer.set_language(language, cx);
+                    head_buffer.set_capability(Capability::ReadOnly, cx);
+                    head_buffer
+                });
+                let title = project_path.path.to_string_lossy().to_string();
+                let view = cx.new_view(|cx| {
+                    let mut view = Self::new(
+                        head_buffer,
+                        buffer,
+                        format!("{title} (HEAD)"),
+                        title,
+                        Some(project),
+                        cx,
+                    );
+                    view.head_diff_path = Some(project_path);
+                    view
+                });
+                workspace.add_item(Box::new(view.clone()), cx);
+                view
+            })
+        })
+    }
+
+    /// The regions that differ between the two buffers, as of the last time the diff
+    /// was computed.
+    pub fn hunks(&self) -> &[DiffViewHunk] {
+        &self.hunks
+    }
+
+    /// Selects the start of the given hunk on both sides, scrolling it into view.
+    pub fn activate_hunk(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(hunk) = self.hunks.get(ix).cloned() else {
+            return;
+        };
+        self.active_hunk = Some(ix);
+        for (editor, row) in [
+            (&self.old_editor, hunk.old_rows.start),
+            (&self.new_editor, hunk.new_rows.start),
+        ] {
+            editor.update(cx, |editor, cx| {
+                let point = Point::new(row, 0);
+                editor.change_selections(Some(Autoscroll::center()), cx, |selections| {
+                    selections.select_ranges([point..point])
+                });
+            });
+        }
+        cx.notify();
+    }
+
+    fn go_to_hunk(&mut self, _: &GoToHunk, cx: &mut ViewContext<Self>) {
+        self.activate_adjacent_hunk(Direction::Next, cx);
+        cx.stop_propagation();
+    }
+
+    fn go_to_prev_hunk(&mut self, _: &GoToPrevHunk, cx: &mut ViewContext<Self>) {
+        self.activate_adjacent_hunk(Direction::Prev, cx);
+        cx.stop_propagation();
+    }
+
+    /// Activates the hunk after or before the cursor of the focused side, wrapping
+    /// around at either end.
+    fn activate_adjacent_hunk(&mut self, direction: Direction, cx: &mut ViewContext<Self>) {
+        if self.hunks.is_empty() {
+            return;
+        }
+        let old_focused = self.old_editor.focus_handle(cx).contains_focused(cx);
+        let editor = if old_focused {
+            &self.old_editor
+        } else {
+            &self.new_editor
+        };
+        let cursor_row = editor.update(cx, |editor, cx| {
+            editor.selections.newest::<Point>(cx).head().row
+        });
+        let start_row = |hunk: &DiffViewHunk| {
+            if old_focused {
+                hunk.old_rows.start
+            } else {
+                hunk.new_rows.start
+            }
+        };
+
+        let ix = match direction {
+            Direction::Next => self
+                .hunks
+                .iter()
+                .position(|hunk| start_row(hunk) > cursor_row)
+                .unwrap_or(0),
+            Direction::Prev => self
+                .hunks
+                .iter()
+                .rposition(|hunk| start_row(hunk) < cursor_row)
+                .unwrap_or(self.hunks.len() - 1),
+        };
+        self.activate_hunk(ix, cx);
+    }
+
+    fn handle_buffer_event(
+        &mut self,
+        _: Model<Buffer>,
+        event: &language::Event,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let language::Event::Edited | language::Event::Reloaded = event {
+            self.update_diff(cx);
+        }
+    }
+
+    /// Scrolls the other side along with the one the user scrolled, keeping the
+    /// corresponding rows level.
+    fn handle_editor_event(
+        &mut self,
+        editor: View<Editor>,
+        event: &EditorEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let EditorEvent::ScrollPositionChanged {
+            local: true,
+            autoscroll: false,
+        } = event
+        else {
+            return;
+        };
+        if self.scroll_follower == Some(editor.entity_id()) {
+            return;
+        }
+
+        let from_old = editor == self.old_editor;
+        let follower = if from_old {
+            self.new_editor.clone()
+        } else {
+            self.old_editor.clone()
+        };
+        let hunks = self.hunks.iter().map(|hunk| {
+            if from_old {
+                (hunk.old_rows.clone(), hunk.new_rows.clone())
+            } else {
+                (hunk.new_rows.clone(), hunk.old_rows.clone())
+            }
+        });
+        let mut position = editor.update(cx, |editor, cx| editor.scroll_position(cx));
+        let top_row = position.y.max(0.).floor();
+        position.y = map_row(top_row as u32, hunks) as f32 + (position.y - top_row);
+
+        self.scroll_follower = Some(follower.entity_id());
+        follower.update(cx, |follower, cx| {
+            follower.set_scroll_position(position, cx)
+        });
+        // The follower's scroll event, if it emitted one, is delivered before this runs.
+        cx.defer(|this, _| this.scroll_follower = None);
+    }
+
+    fn update_diff(&mut self, cx: &mut ViewContext<Self>) {
+        let old_snapshot = self.old_buffer.read(cx).text_snapshot();
+        let new_snapshot = self.new_buffer.read(cx).text_snapshot();
+        self.diff_task = cx.spawn(|this, mut cx| async move {
+            let hunks = cx
+                .background_executor()
+                .spawn(async move {
+                    let mut diff = BufferDiff::new();
+                    diff.update(&old_snapshot.text(), &new_snapshot).await;
+                    diff.hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &new_snapshot)
+                        .map(|hunk| {
+                            let start =
+                                old_snapshot.offset_to_point(hunk.diff_base_byte_range.start);
+                            let end = old_snapshot.offset_to_point(hunk.diff_base_byte_range.end);
+                            let end_row = if end.column > 0 { end.row + 1 } else { end.row };
+                            DiffViewHunk {
+                                old_rows: start.row..end_row,
+                                new_rows: hunk.buffer_range,
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
+
+            this.update(&mut cx, |this, cx| {
+                this.hunks = hunks;
+                this.active_hunk = this.active_hunk.filter(|ix| *ix < this.hunks.len());
+                this.highlight_changes(cx);
+                cx.notify();
+            })
+            .log_err()
+        });
+    }
+
+    fn highlight_changes(&mut self, cx: &mut ViewContext<Self>) {
+        let status = cx.theme().status();
+        let deleted = color_alpha(status.deleted, CHANGE_HIGHLIGHT_ALPHA);
+        let created = color_alpha(status.created, CHANGE_HIGHLIGHT_ALPHA);
+        let old_rows = self
+            .hunks
+            .iter()
+            .map(|hunk| hunk.old_rows.clone())
+            .collect();
+        let new_rows = self
+            .hunks
+            .iter()
+            .map(|hunk| hunk.new_rows.clone())
+            .collect();
+        highlight_rows::<DiffViewDeletions>(&self.old_editor, old_rows, deleted, cx);
+        highlight_rows::<DiffViewInsertions>(&self.new_editor, new_rows, created, cx);
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let changes = match (self.hunks.len(), self.active_hunk) {
+            (0, _) => "No changes".to_string(),
+            (1, None) => "1 change".to_string(),
+            (count, None) => format!("{count} changes"),
+            (count, Some(ix)) => format!("Change {} of {count}", ix + 1),
+        };
+        let side_header = |title: &SharedString| {
+            h_flex()
+                .flex_1()
+                .px_2()
+                .child(Label::new(title.clone()).size(LabelSize::Small))
+        };
+
+        h_flex()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(side_header(&self.old_title))
+            .child(side_header(&self.new_title))
+            .child(
+                h_flex()
+                    .px_2()
+                    .gap_1()
+                    .child(
+                        Label::new(changes)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        IconButton::new("diff-prev-hunk", IconName::ArrowUp)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.hunks.is_empty())
+                            .tooltip(|cx| Tooltip::for_action("Previous Change", &GoToPrevHunk, cx))
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.activate_adjacent_hunk(Direction::Prev, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("diff-next-hunk", IconName::ArrowDown)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.hunks.is_empty())
+                            .tooltip(|cx| Tooltip::for_action("Next Change", &GoToHunk, cx))
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.activate_adjacent_hunk(Direction::Next, cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+fn highlight_rows<T: 'static>(
+    editor: &View<Editor>,
+    rows: Vec<Range<u32>>,
+    color: Hsla,
+    cx: &mut WindowContext,
+) {
+    editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let max_point = snapshot.max_point();
+        let ranges = rows
+            .into_iter()
+            .filter(|rows| !rows.is_empty())
+            .map(|rows| {
+                snapshot.anchor_before(Point::new(rows.start, 0).min(max_point))
+                    ..snapshot.anchor_before(Point::new(rows.end, 0).min(max_point))
+            })
+            .collect();
+        editor.highlight_text::<T>(
+            ranges,
+            HighlightStyle {
+                background_color: Some(color),
+                ..Default::default()
+            },
+            cx,
+        );
+    });
+}
+
+/// Maps a row on one side of a diff to the row level with it on the other side,
+/// given the hunks as the ranges of rows they span on each side, in order. Rows
+/// within a hunk map to the corresponding row of the other side's part of the hunk,
+/// or to its last row when that part is shorter.
+fn map_row(row: u32, hunks: impl Iterator<Item = (Range<u32>, Range<u32>)>) -> u32 {
+    let mut from_end = 0;
+    let mut to_end = 0;
+    for (from, to) in hunks {
+        if row < from.start {
+            break;
+        }
+        if row < from.end {
+            let last_to_row = to.end.saturating_sub(1).max(to.start);
+            return (to.start + row - from.start).min(last_to_row);
+        }
+        from_end = from.end;
+        to_end = to.end;
+    }
+    to_end + row - from_end
+}
+
+pub(crate) fn diff_active_file(
+    workspace: &mut Workspace,
+    _: &DiffActiveFile,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let Some(project_path) = workspace
+        .active_item_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+        .and_then(|buffer| buffer.read(cx).project_path(cx))
+    else {
+        return;
+    };
+    DiffView::open_head_diff(workspace, project_path, cx).detach_and_notify_err(cx);
+}
+
+impl Render for DiffView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("DiffView")
+            .size_full()
+            .capture_action(cx.listener(Self::go_to_hunk))
+            .capture_action(cx.listener(Self::go_to_prev_hunk))
+            .child(self.render_header(cx))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .child(
+                        div()
+                            .flex_1()
+                            .h_full()
+                            .border_r_1()
+                            .border_color(cx.theme().colors().border)
+                            .child(self.old_editor.clone()),
+                    )
+                    .child(div().flex_1().h_full().child(self.new_editor.clone())),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for DiffView {}
+
+impl FocusableView for DiffView {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.new_editor.focus_handle(cx)
+    }
+}
+
+impl Item for DiffView {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        let title = match &self.head_diff_path {
+            Some(project_path) => match project_path.path.file_name() {
+                Some(file_name) => format!("{} (Diff)", file_name.to_string_lossy()),
+                None => self.new_title.to_string(),
+            },
+            None => format!("{} ↔ {}", self.old_title, self.new_title),
+        };
+        Label::new(title)
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("diff view")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor_tests::init_test;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use std::path::Path;
+
+    #[test]
+    fn test_map_row() {
+        // Rows 2..4 were replaced by a single row, and a row was added after row 6.
+        let hunks = [(2..4, 2..3), (7..7, 6..7)];
+        let mapped = (0..10)
+            .map(|row| map_row(row, hunks.iter().cloned()))
+            .collect::<Vec<_>>();
+        assert_eq!(mapped, [0, 1, 2, 2, 3, 4, 5, 7, 8, 9]);
+    }
+
+    #[gpui::test]
+    async fn test_diff_view(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                ".git": {},
+                "a.txt": "one\nTWO\nthree\nfour\nFIVE\nsix\n",
+            }),
+        )
+        .await;
+        fs.set_head_for_repo(
+            Path::new("/root/.git"),
+            &[(
+                Path::new("a.txt"),
+                "one\ntwo\nthree\nfour\nfive\nsix\n".into(),
+            )],
+        );
+
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let project_path = project.update(cx, |project, cx| ProjectPath {
+            worktree_id: project.worktrees().next().unwrap().read(cx).id(),
+            path: Path::new("a.txt").into(),
+        });
+
+        let view = workspace
+            .update(cx, |workspace, cx| {
+                DiffView::open_head_diff(workspace, project_path.clone(), cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        view.update(cx, |view, cx| {
+            assert_eq!(
+                view.hunks(),
+                [
+                    DiffViewHunk {
+                        old_rows: 1..2,
+                        new_rows: 1..2,
+                    },
+                    DiffViewHunk {
+                        old_rows: 4..5,
+                        new_rows: 4..5,
+                    },
+                ]
+            );
+
+            view.activate_adjacent_hunk(Direction::Next, cx);
+            assert_eq!(view.active_hunk, Some(0));
+            view.activate_adjacent_hunk(Direction::Next, cx);
+            assert_eq!(view.active_hunk, Some(1));
+            view.activate_adjacent_hunk(Direction::Next, cx);
+            assert_eq!(view.active_hunk, Some(0));
+            view.activate_adjacent_hunk(Direction::Prev, cx);
+            assert_eq!(view.active_hunk, Some(1));
+        });
+
+        // Scrolling either side scrolls the other one along.
+        view.update(cx, |view, cx| {
+            view.old_editor.update(cx, |editor, cx| {
+                editor.set_scroll_position(gpui::Point::new(0., 2.), cx)
+            })
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, cx| {
+            view.new_editor.update(cx, |editor, cx| {
+                assert_eq!(editor.scroll_position(cx).y, 2.);
+                editor.set_scroll_position(gpui::Point::new(0., 3.), cx)
+            })
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, cx| {
+            assert_eq!(view.scroll_follower, None);
+            view.old_editor.update(cx, |editor, cx| {
+                assert_eq!(editor.scroll_position(cx).y, 3.)
+            });
+        });
+
+        // Editing the file updates the diff.
+        view.update(cx, |view, cx| {
+            view.new_editor.update(cx, |editor, cx| {
+                editor.set_text("one\ntwo\nthree\nfour\nfive\nsix\n", cx)
+            })
+        });
+        cx.run_until_parked();
+        view.update(cx, |view, _| {
+            assert!(view.hunks().is_empty());
+            assert_eq!(view.active_hunk, None);
+        });
+
+        // Opening the diff again activates the existing view.
+        let reopened = workspace
+            .update(cx, |workspace, cx| {
+                DiffView::open_head_diff(workspace, project_path, cx)
+            })
+            .await
+            .unwrap();
+        assert!(reopened == view);
+    }
+}
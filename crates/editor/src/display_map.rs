@@ -312,11 +312,10 @@ impl DisplayMap {
     }
 
     fn tab_size(buffer: &Model<MultiBuffer>, cx: &mut ModelContext<Self>) -> NonZeroU32 {
-        let language = buffer
-            .read(cx)
-            .as_singleton()
-            .and_then(|buffer| buffer.read(cx).language());
-        language_settings(language.as_deref(), None, cx).tab_size
+        let buffer = buffer.read(cx).as_singleton().map(|buffer| buffer.read(cx));
+        let language = buffer.and_then(|buffer| buffer.language());
+        let file = buffer.and_then(|buffer| buffer.file());
+        language_settings(language, file, cx).tab_size
     }
 
     #[cfg(test)]
@@ -1001,14 +1000,17 @@ pub mod tests {
     };
     use gpui::{div, font, observe, px, AppContext, Context, Element, Hsla};
     use language::{
-        language_settings::{AllLanguageSettings, AllLanguageSettingsContent},
+        language_settings::{
+            AllLanguageSettings, AllLanguageSettingsContent, LanguageSettingsContent,
+        },
         Buffer, Language, LanguageConfig, SelectionGoal,
     };
-    use project::Project;
+    use project::{FakeFs, Project};
     use rand::{prelude::*, Rng};
+    use serde_json::json;
     use settings::SettingsStore;
     use smol::stream::StreamExt;
-    use std::{env, sync::Arc};
+    use std::{env, path::Path, sync::Arc};
     use text::BufferId;
     use theme::{LoadThemes, SyntaxTheme};
     use util::test::{marked_text_ranges, sample_text};
@@ -1812,6 +1814,73 @@ pub mod tests {
         )
     }
 
+    #[gpui::test]
+    async fn test_language_specific_tab_size(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            init_test(cx, |s| {
+                s.defaults.tab_size = NonZeroU32::new(4);
+                s.languages.insert(
+                    "Rust".into(),
+                    LanguageSettingsContent {
+                        tab_size: NonZeroU32::new(2),
+                        ..Default::default()
+                    },
+                );
+            })
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "a.rs": "\ta",
+                "a.txt": "\ta",
+                "local": {
+                    ".zed": {
+                        "settings.json": r#"{ "languages": { "Rust": { "tab_size": 8 } } }"#
+                    },
+                    "b.rs": "\tb",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        project.update(cx, |project, _| {
+            project.languages().add(Arc::new(Language::new(
+                LanguageConfig {
+                    name: "Rust".into(),
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                None,
+            )))
+        });
+
+        // Files without a language use the default tab size, others that of their language,
+        // unless it's overridden by the settings of the directory they're in.
+        for (path, expected_text) in [
+            ("a.txt", "    a"),
+            ("a.rs", "  a"),
+            ("local/b.rs", "        b"),
+        ] {
+            let buffer = project
+                .update(cx, |project, cx| {
+                    project.open_local_buffer(Path::new("/root").join(path), cx)
+                })
+                .await
+                .unwrap();
+            cx.run_until_parked();
+            let text = cx.update(|cx| {
+                let buffer = cx.new_model(|cx| MultiBuffer::singleton(buffer, cx));
+                let map = cx.new_model(|cx| {
+                    DisplayMap::new(buffer, font("Helvetica"), px(14.0), None, 1, 1, cx)
+                });
+                map.update(cx, |map, cx| map.snapshot(cx)).text()
+            });
+            assert_eq!(text, expected_text, "unexpected tab size for {path}");
+        }
+    }
+
     fn syntax_chunks<'a>(
         rows: Range<u32>,
         map: &Model<DisplayMap>,
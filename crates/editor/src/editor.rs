@@ -20,6 +20,7 @@ mod element;
 mod inlay_hint_cache;
 
 mod debounced_delay;
+mod diff_view;
 mod git;
 mod highlight_matching_bracket;
 mod hover_popover;
@@ -29,6 +30,7 @@ mod mouse_context_menu;
 pub mod movement;
 mod persistence;
 mod rust_analyzer_ext;
+mod scratch;
 pub mod scroll;
 mod selections_collection;
 
@@ -47,6 +49,7 @@ use collections::{BTreeMap, Bound, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
 use copilot::Copilot;
 use debounced_delay::DebouncedDelay;
+pub use diff_view::{DiffView, DiffViewHunk};
 pub use display_map::DisplayPoint;
 use display_map::*;
 pub use editor_settings::EditorSettings;
@@ -232,11 +235,15 @@ pub fn init(cx: &mut AppContext) {
 
     workspace::register_project_item::<Editor>(cx);
     workspace::register_followable_item::<Editor>(cx);
-    workspace::register_deserializable_item::<Editor>(cx);
+    workspace::register_serializable_item::<Editor>(cx);
+    scratch::init(cx);
     cx.observe_new_views(
         |workspace: &mut Workspace, _cx: &mut ViewContext<Workspace>| {
             workspace.register_action(Editor::new_file);
             workspace.register_action(Editor::new_file_in_direction);
+            workspace.register_action(scratch::new_scratch);
+            workspace.register_action(scratch::open_scratch);
+            workspace.register_action(diff_view::diff_active_file);
         },
     )
     .detach();
@@ -369,6 +376,8 @@ pub struct Editor {
     project: Option<Model<Project>>,
     completion_provider: Option<Box<dyn CompletionProvider>>,
     collaboration_hub: Option<Box<dyn CollaborationHub>>,
+    /// The collaborators with a cursor in the editor's buffers, shown on its tab.
+    present_collaborators: Vec<PeerId>,
     blink_manager: Model<BlinkManager>,
     show_cursor_names: bool,
     hovered_cursors: HashMap<HoveredCursor, Task<()>>,
@@ -398,6 +407,8 @@ pub struct Editor {
     keymap_context_layers: BTreeMap<TypeId, KeyContext>,
     input_enabled: bool,
     read_only: bool,
+    workspace_read_only: bool,
+    scratch_name: Option<SharedString>,
     leader_peer_id: Option<PeerId>,
     remote_id: Option<ViewId>,
     hover_state: HoverState,
@@ -413,6 +424,7 @@ pub struct Editor {
     editor_actions: Vec<Box<dyn Fn(&mut ViewContext<Self>)>>,
     show_copilot_suggestions: bool,
     use_autoclose: bool,
+    pending_saves: usize,
 }
 
 pub struct EditorSnapshot {
@@ -1364,6 +1376,7 @@ impl Editor {
         clone.selections.clone_state(&self.selections);
         clone.scroll_manager.clone_state(&self.scroll_manager);
         clone.searchable = self.searchable;
+        clone.scratch_name = self.scratch_name.clone();
         clone
     }
 
@@ -1432,6 +1445,7 @@ impl Editor {
             soft_wrap_mode_override,
             completion_provider: project.clone().map(|project| Box::new(project) as _),
             collaboration_hub: project.clone().map(|project| Box::new(project) as _),
+            present_collaborators: Vec::new(),
             project,
             blink_manager: blink_manager.clone(),
             show_local_selections: true,
@@ -1461,6 +1475,8 @@ impl Editor {
             keymap_context_layers: Default::default(),
             input_enabled: true,
             read_only: false,
+            workspace_read_only: false,
+            scratch_name: None,
             use_autoclose: true,
             leader_peer_id: None,
             remote_id: None,
@@ -1476,6 +1492,7 @@ impl Editor {
             hovered_cursors: Default::default(),
             editor_actions: Default::default(),
             show_copilot_suggestions: mode == EditorMode::Full,
+            pending_saves: 0,
             _subscriptions: vec![
                 cx.observe(&buffer, Self::on_buffer_changed),
                 cx.subscribe(&buffer, Self::on_buffer_event),
@@ -1736,7 +1753,7 @@ impl Editor {
     }
 
     pub fn read_only(&self, cx: &AppContext) -> bool {
-        self.read_only || self.buffer.read(cx).read_only()
+        self.read_only || self.workspace_read_only || self.buffer.read(cx).read_only()
     }
 
     pub fn set_read_only(&mut self, read_only: bool) {
@@ -8564,9 +8581,39 @@ impl Editor {
     }
 
     fn on_buffer_changed(&mut self, _: Model<MultiBuffer>, cx: &mut ViewContext<Self>) {
+        self.refresh_present_collaborators(cx);
         cx.notify();
     }
 
+    pub fn present_collaborators(&self) -> &[PeerId] {
+        &self.present_collaborators
+    }
+
+    fn refresh_present_collaborators(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(collaboration_hub) = self.collaboration_hub.as_deref() else {
+            return;
+        };
+        let collaborators = collaboration_hub.collaborators(cx);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let range = Anchor::min()..Anchor::max();
+        let mut peer_ids = Vec::new();
+        for (replica_id, _, _, _) in snapshot.remote_selections_in_range(&range) {
+            if let Some(collaborator) = collaborators
+                .values()
+                .find(|collaborator| collaborator.replica_id == replica_id)
+            {
+                if !peer_ids.contains(&collaborator.peer_id) {
+                    peer_ids.push(collaborator.peer_id);
+                }
+            }
+        }
+
+        if peer_ids != self.present_collaborators {
+            self.present_collaborators = peer_ids;
+            cx.emit(EditorEvent::PresentCollaboratorsChanged);
+        }
+    }
+
     fn on_buffer_event(
         &mut self,
         multibuffer: Model<MultiBuffer>,
@@ -9320,9 +9367,11 @@ pub enum EditorEvent {
     Focused,
     Blurred,
     DirtyChanged,
+    SavingChanged,
     Saved,
     TitleChanged,
     DiffBaseChanged,
+    PresentCollaboratorsChanged,
     SelectionsChanged {
         local: bool,
     },
@@ -1,7 +1,8 @@
 use crate::{
     editor_settings::SeedQuerySetting, link_go_to_definition::hide_link_definition,
-    persistence::DB, scroll::ScrollAnchor, Anchor, Autoscroll, Editor, EditorEvent, EditorSettings,
-    ExcerptId, ExcerptRange, MultiBuffer, MultiBufferSnapshot, NavigationData, ToPoint as _,
+    persistence::DB, scratch::build_scratch_editor, scroll::ScrollAnchor, Anchor, Autoscroll,
+    Editor, EditorEvent, EditorSettings, ExcerptId, ExcerptRange, MultiBuffer, MultiBufferSnapshot,
+    NavigationData, ToPoint as _,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::HashSet;
@@ -35,7 +36,10 @@ use theme::Theme;
 use ui::{h_flex, prelude::*, Label};
 use util::{paths::PathExt, paths::FILE_ROW_COLUMN_DELIMITER, ResultExt, TryFutureExt};
 use workspace::{
-    item::{BreadcrumbText, FollowEvent, FollowableItemHandle},
+    item::{
+        text_tab_preview, BreadcrumbText, FollowEvent, FollowableItemHandle, SerializableItem,
+        TAB_PREVIEW_MAX_LINES,
+    },
     StatusItemView,
 };
 use workspace::{
@@ -540,6 +544,23 @@ impl Item for Editor {
     type Event = EditorEvent;
 
     fn navigate(&mut self, data: Box<dyn std::any::Any>, cx: &mut ViewContext<Self>) -> bool {
+        let data = match data.downcast::<Point>() {
+            Ok(position) => {
+                let position = self
+                    .buffer
+                    .read(cx)
+                    .read(cx)
+                    .clip_point(*position, Bias::Left);
+                let nav_history = self.nav_history.take();
+                self.change_selections(Some(Autoscroll::center()), cx, |s| {
+                    s.select_ranges([position..position])
+                });
+                self.nav_history = nav_history;
+                return true;
+            }
+            Err(data) => data,
+        };
+
         if let Ok(data) = data.downcast::<NavigationData>() {
             let newest_selection = self.selections.newest::<Point>(cx);
             let buffer = self.buffer.read(cx).read(cx);
@@ -574,6 +595,13 @@ impl Item for Editor {
         }
     }
 
+    fn restorable_position(&self, cx: &AppContext) -> Option<Point> {
+        if !self.buffer.read(cx).is_singleton() {
+            return None;
+        }
+        Some(self.selections.newest::<Point>(cx).head())
+    }
+
     fn tab_tooltip_text(&self, cx: &AppContext) -> Option<SharedString> {
         let file_path = self
             .buffer()
@@ -647,6 +675,26 @@ impl Item for Editor {
             .into_any_element()
     }
 
+    fn tab_preview(&self, cx: &WindowContext) -> Option<AnyElement> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let end = snapshot.clip_point(Point::new(TAB_PREVIEW_MAX_LINES as u32, 0), Bias::Left);
+        let text = snapshot
+            .text_for_range(Point::zero()..end)
+            .collect::<String>();
+        if text.trim().is_empty() {
+            return None;
+        }
+        Some(text_tab_preview(&text, cx))
+    }
+
+    fn tab_icon(&self, _: &WindowContext) -> Option<Icon> {
+        (self.pending_saves > 0).then(|| {
+            Icon::new(IconName::ArrowCircle)
+                .size(IconSize::XSmall)
+                .color(Color::Muted)
+        })
+    }
+
     fn for_each_project_item(
         &self,
         cx: &AppContext,
@@ -687,6 +735,11 @@ impl Item for Editor {
     }
 
     fn is_dirty(&self, cx: &AppContext) -> bool {
+        // Scratch buffers are written to the database as they're edited, and
+        // flushed when they're closed or the app quits.
+        if self.scratch_name.is_some() {
+            return false;
+        }
         self.buffer().read(cx).read(cx).is_dirty()
     }
 
@@ -694,6 +747,10 @@ impl Item for Editor {
         self.buffer().read(cx).read(cx).has_conflict()
     }
 
+    fn present_collaborators(&self, _: &AppContext) -> Vec<PeerId> {
+        self.present_collaborators.clone()
+    }
+
     fn can_save(&self, cx: &AppContext) -> bool {
         let buffer = &self.buffer().read(cx);
         if let Some(buffer) = buffer.as_singleton() {
@@ -707,7 +764,9 @@ impl Item for Editor {
         self.report_editor_event("save", None, cx);
         let format = self.perform_format(project.clone(), FormatTrigger::Save, cx);
         let buffers = self.buffer().clone().read(cx).all_buffers();
-        cx.spawn(|_, mut cx| async move {
+        self.pending_saves += 1;
+        cx.emit(EditorEvent::SavingChanged);
+        let save = cx.spawn(|_, mut cx| async move {
             format.await?;
 
             if buffers.len() == 1 {
@@ -744,6 +803,15 @@ impl Item for Editor {
             }
 
             Ok(())
+        });
+        cx.spawn(|this, mut cx| async move {
+            let result = save.await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_saves -= 1;
+                cx.emit(EditorEvent::SavingChanged);
+            })
+            .ok();
+            result
         })
     }
 
@@ -835,6 +903,11 @@ impl Item for Editor {
         Some(breadcrumbs)
     }
 
+    fn set_read_only(&mut self, read_only: bool, cx: &mut ViewContext<Self>) {
+        self.workspace_read_only = read_only;
+        cx.notify();
+    }
+
     fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
         let workspace_id = workspace.database_id();
         let item_id = cx.view().item_id().as_u64() as ItemId;
@@ -878,10 +951,6 @@ impl Item for Editor {
         }
     }
 
-    fn serialized_item_kind() -> Option<&'static str> {
-        Some("Editor")
-    }
-
     fn to_item_events(event: &EditorEvent, mut f: impl FnMut(ItemEvent)) {
         match event {
             EditorEvent::Closed => f(ItemEvent::CloseItem),
@@ -899,10 +968,14 @@ impl Item for Editor {
                 f(ItemEvent::UpdateBreadcrumbs);
             }
 
-            EditorEvent::DirtyChanged => {
+            EditorEvent::DirtyChanged | EditorEvent::PresentCollaboratorsChanged => {
                 f(ItemEvent::UpdateTab);
             }
 
+            EditorEvent::SavingChanged => {
+                f(ItemEvent::UpdateTabIcon);
+            }
+
             EditorEvent::BufferEdited => {
                 f(ItemEvent::Edit);
                 f(ItemEvent::UpdateBreadcrumbs);
@@ -915,14 +988,30 @@ impl Item for Editor {
             _ => {}
         }
     }
+}
+
+impl SerializableItem for Editor {
+    fn serialized_item_kind() -> &'static str {
+        "Editor"
+    }
+
+    /// Scratch buffers are restored by name, other editors by the path stored for them.
+    fn serialize(&self, _cx: &AppContext) -> Option<String> {
+        self.scratch_name().map(ToString::to_string)
+    }
 
     fn deserialize(
         project: Model<Project>,
         _workspace: WeakView<Workspace>,
         workspace_id: workspace::WorkspaceId,
         item_id: ItemId,
+        state: Option<String>,
         cx: &mut ViewContext<Pane>,
     ) -> Task<Result<View<Self>>> {
+        if let Some(scratch_name) = state {
+            return Task::ready(build_scratch_editor(scratch_name.into(), project, cx));
+        }
+
         let project_item: Result<_> = project.update(cx, |project, cx| {
             // Look up the path with this key associated, create a self with that path
             let path = DB
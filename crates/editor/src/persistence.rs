@@ -15,6 +15,11 @@ define_connection!(
     //   scroll_vertical_offset: f32,
     //   scroll_horizontal_offset: f32,
     // )
+    //
+    // scratch_buffers(
+    //   name: String,
+    //   contents: String,
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> =
         &[sql! (
             CREATE TABLE editors(
@@ -31,6 +36,12 @@ define_connection!(
             ALTER TABLE editors ADD COLUMN scroll_top_row INTEGER NOT NULL DEFAULT 0;
             ALTER TABLE editors ADD COLUMN scroll_horizontal_offset REAL NOT NULL DEFAULT 0;
             ALTER TABLE editors ADD COLUMN scroll_vertical_offset REAL NOT NULL DEFAULT 0;
+        ),
+        sql! (
+            CREATE TABLE scratch_buffers(
+                name TEXT NOT NULL PRIMARY KEY,
+                contents TEXT NOT NULL
+            ) STRICT;
         )];
 );
 
@@ -80,4 +91,29 @@ impl EditorDb {
             WHERE item_id = ?1 AND workspace_id = ?2
         }
     }
+
+    query! {
+        pub fn get_scratch_contents(name: String) -> Result<Option<String>> {
+            SELECT contents FROM scratch_buffers
+            WHERE name = ?
+        }
+    }
+
+    query! {
+        pub fn scratch_names() -> Result<Vec<String>> {
+            SELECT name FROM scratch_buffers
+            ORDER BY name
+        }
+    }
+
+    query! {
+        pub async fn save_scratch_contents(name: String, contents: String) -> Result<()> {
+            INSERT INTO scratch_buffers
+                (name, contents)
+            VALUES
+                (?1, ?2)
+            ON CONFLICT DO UPDATE SET
+                contents = ?2
+        }
+    }
 }
@@ -0,0 +1,327 @@
+//! Named scratch buffers: untitled buffers whose contents are kept in the database
+//! instead of a file, so that they survive restarts without ever being saved.
+
+use std::{future::Future, time::Duration};
+
+use anyhow::Result;
+use collections::HashMap;
+use gpui::{
+    AppContext, Context as _, Global, Model, SharedString, Subscription, View, ViewContext,
+    VisualContext, WeakModel, WindowContext,
+};
+use language::Buffer;
+use multi_buffer::MultiBuffer;
+use project::Project;
+use util::ResultExt;
+use workspace::{NewScratch, OpenScratch, Workspace};
+
+use crate::{persistence::DB, Editor};
+
+const SCRATCH_SAVE_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// The scratch buffers that are open, by name. Every editor showing a scratch buffer
+/// shares its buffer, so that a name is never backed by two buffers overwriting
+/// each other's contents.
+struct ScratchBuffers {
+    buffers: HashMap<SharedString, ScratchBuffer>,
+    _quit_subscription: Subscription,
+}
+
+impl Global for ScratchBuffers {}
+
+struct ScratchBuffer {
+    buffer: WeakModel<Buffer>,
+    _subscriptions: [Subscription; 2],
+}
+
+pub(crate) fn init(cx: &mut AppContext) {
+    let quit_subscription = cx.on_app_quit(flush_scratch_buffers);
+    cx.set_global(ScratchBuffers {
+        buffers: HashMap::default(),
+        _quit_subscription: quit_subscription,
+    });
+}
+
+impl Editor {
+    /// Creates an editor for the scratch buffer `name`, showing `buffer`, which should
+    /// be the one other editors for that name share.
+    pub fn for_scratch(
+        name: SharedString,
+        buffer: Model<Buffer>,
+        project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let multi_buffer =
+            cx.new_model(|cx| MultiBuffer::singleton(buffer, cx).with_title(name.to_string()));
+        let mut editor = Self::for_multibuffer(multi_buffer, Some(project), cx);
+        editor.scratch_name = Some(name);
+        editor
+    }
+
+    /// The name of the scratch buffer this editor shows, if any.
+    pub fn scratch_name(&self) -> Option<&SharedString> {
+        self.scratch_name.as_ref()
+    }
+}
+
+/// Returns the open buffer for the scratch buffer `name`, or creates one from its
+/// stored contents, recording it as empty if it doesn't exist yet.
+///
+/// Edits are written to the database shortly after they're made, and the contents
+/// are flushed when the buffer is released or the app quits.
+fn scratch_buffer(
+    name: SharedString,
+    project: &Model<Project>,
+    cx: &mut AppContext,
+) -> Result<Model<Buffer>> {
+    if let Some(buffer) = cx
+        .global::<ScratchBuffers>()
+        .buffers
+        .get(&name)
+        .and_then(|scratch| scratch.buffer.upgrade())
+    {
+        return Ok(buffer);
+    }
+
+    let stored_contents = DB.get_scratch_contents(name.to_string())?;
+    let contents = stored_contents.clone().unwrap_or_default();
+    let buffer = project.update(cx, |project, cx| project.create_buffer(&contents, None, cx))?;
+    if stored_contents.is_none() {
+        let name = name.to_string();
+        cx.background_executor()
+            .spawn(async move {
+                DB.save_scratch_contents(name, String::new())
+                    .await
+                    .log_err()
+            })
+            .detach();
+    }
+
+    let edit_subscription = cx.subscribe(&buffer, {
+        let name = name.clone();
+        move |buffer, event: &language::Event, cx| {
+            if let language::Event::Edited = event {
+                save_scratch_contents(name.clone(), buffer, cx);
+            }
+        }
+    });
+    let buffer_id = buffer.entity_id();
+    let release_subscription = cx.observe_release(&buffer, {
+        let name = name.clone();
+        move |buffer: &mut Buffer, cx| {
+            let contents = buffer.text();
+            let db_name = name.to_string();
+            cx.background_executor()
+                .spawn(async move { DB.save_scratch_contents(db_name, contents).await.log_err() })
+                .detach();
+
+            let buffers = &mut cx.global_mut::<ScratchBuffers>().buffers;
+            if buffers
+                .get(&name)
+                .map_or(false, |scratch| scratch.buffer.entity_id() == buffer_id)
+            {
+                buffers.remove(&name);
+            }
+        }
+    });
+    cx.global_mut::<ScratchBuffers>().buffers.insert(
+        name,
+        ScratchBuffer {
+            buffer: buffer.downgrade(),
+            _subscriptions: [edit_subscription, release_subscription],
+        },
+    );
+    Ok(buffer)
+}
+
+/// Writes the contents of a scratch buffer once it hasn't been edited for a while.
+fn save_scratch_contents(name: SharedString, buffer: Model<Buffer>, cx: &mut AppContext) {
+    let version = buffer.read(cx).version();
+    let buffer = buffer.downgrade();
+    cx.spawn(|cx| async move {
+        cx.background_executor().timer(SCRATCH_SAVE_DEBOUNCE).await;
+        // A buffer released in the meantime had its contents flushed then.
+        let contents = buffer
+            .read_with(&cx, |buffer, _| {
+                (!buffer.version().changed_since(&version)).then(|| buffer.text())
+            })
+            .ok()
+            .flatten();
+        if let Some(contents) = contents {
+            DB.save_scratch_contents(name.to_string(), contents).await?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Writes the contents of every open scratch buffer, so that edits still waiting
+/// for [`SCRATCH_SAVE_DEBOUNCE`] aren't lost on quit.
+fn flush_scratch_buffers(cx: &mut AppContext) -> impl Future<Output = ()> {
+    let scratch_buffers = cx
+        .global::<ScratchBuffers>()
+        .buffers
+        .iter()
+        .filter_map(|(name, scratch)| {
+            let buffer = scratch.buffer.upgrade()?;
+            Some((name.to_string(), buffer.read(cx).text()))
+        })
+        .collect::<Vec<_>>();
+    async move {
+        for (name, contents) in scratch_buffers {
+            DB.save_scratch_contents(name, contents).await.log_err();
+        }
+    }
+}
+
+/// Builds an editor for the scratch buffer `name`, sharing its buffer with the other
+/// editors showing it.
+pub(crate) fn build_scratch_editor(
+    name: SharedString,
+    project: Model<Project>,
+    cx: &mut WindowContext,
+) -> Result<View<Editor>> {
+    let buffer = scratch_buffer(name.clone(), &project, cx)?;
+    Ok(cx.new_view(|cx| Editor::for_scratch(name, buffer, project, cx)))
+}
+
+/// The first `scratch N` name that isn't taken by a stored or open scratch buffer.
+fn next_scratch_name(taken: &[SharedString]) -> SharedString {
+    (1..)
+        .map(|ix| SharedString::from(format!("scratch {ix}")))
+        .find(|name| !taken.contains(name))
+        .unwrap()
+}
+
+pub(crate) fn new_scratch(
+    workspace: &mut Workspace,
+    _: &NewScratch,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let mut taken = DB
+        .scratch_names()
+        .log_err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(SharedString::from)
+        .collect::<Vec<_>>();
+    taken.extend(cx.global::<ScratchBuffers>().buffers.keys().cloned());
+    let name = next_scratch_name(&taken);
+    open_scratch_buffer(workspace, name, false, cx);
+}
+
+pub(crate) fn open_scratch(
+    workspace: &mut Workspace,
+    action: &OpenScratch,
+    cx: &mut ViewContext<Workspace>,
+) {
+    open_scratch_buffer(workspace, action.name.clone().into(), action.in_dock, cx);
+}
+
+/// Activates the scratch buffer `name` in the active pane, or in a dock pane when
+/// `in_dock` is set, opening it there if that pane doesn't show it yet. Editors
+/// opened elsewhere for the same name share its buffer.
+fn open_scratch_buffer(
+    workspace: &mut Workspace,
+    name: SharedString,
+    in_dock: bool,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let pane = in_dock
+        .then(|| workspace.reveal_dock_pane(cx))
+        .flatten()
+        .unwrap_or_else(|| workspace.active_pane().clone());
+    let existing = pane
+        .read(cx)
+        .items_of_type::<Editor>()
+        .find(|editor| editor.read(cx).scratch_name() == Some(&name));
+    if let Some(editor) = existing {
+        pane.update(cx, |pane, cx| {
+            if let Some(ix) = pane.index_for_item(&editor) {
+                pane.activate_item(ix, true, true, cx);
+            }
+        });
+        return;
+    }
+
+    let project = workspace.project().clone();
+    if let Some(editor) = build_scratch_editor(name, project, cx).log_err() {
+        pane.update(cx, |pane, cx| {
+            pane.add_item(Box::new(editor), true, true, None, cx)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor_tests::init_test;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+
+    #[test]
+    fn test_next_scratch_name() {
+        assert_eq!(next_scratch_name(&[]), "scratch 1");
+        let taken = ["scratch 1".into(), "scratch 3".into(), "notes".into()];
+        assert_eq!(next_scratch_name(&taken), "scratch 2");
+    }
+
+    #[gpui::test]
+    async fn test_scratch_contents_round_trip(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let name = SharedString::from("round trip");
+
+        let editor = workspace
+            .update(cx, |_, cx| {
+                build_scratch_editor(name.clone(), project.clone(), cx)
+            })
+            .unwrap();
+        editor.update(cx, |editor, cx| editor.insert("one", cx));
+        cx.executor().advance_clock(SCRATCH_SAVE_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            DB.get_scratch_contents(name.to_string())
+                .unwrap()
+                .as_deref(),
+            Some("one")
+        );
+
+        // A second editor for the same name shares the buffer instead of loading
+        // its own copy.
+        let other_editor = workspace
+            .update(cx, |_, cx| {
+                build_scratch_editor(name.clone(), project.clone(), cx)
+            })
+            .unwrap();
+        let buffers = [&editor, &other_editor].map(|editor| {
+            editor.update(cx, |editor, cx| {
+                editor.buffer().read(cx).as_singleton().unwrap()
+            })
+        });
+        assert_eq!(buffers[0], buffers[1]);
+
+        // Edits that are still waiting for the debounce are flushed once the
+        // buffer's editors are gone.
+        other_editor.update(cx, |editor, cx| editor.insert(" two", cx));
+        drop((editor, other_editor, buffers));
+        cx.update(|_| {});
+        cx.run_until_parked();
+        assert_eq!(
+            DB.get_scratch_contents(name.to_string())
+                .unwrap()
+                .as_deref(),
+            Some("one two")
+        );
+
+        // Reopening the scratch buffer loads the stored contents.
+        let editor = workspace
+            .update(cx, |_, cx| {
+                build_scratch_editor(name.clone(), project.clone(), cx)
+            })
+            .unwrap();
+        editor.update(cx, |editor, cx| assert_eq!(editor.text(cx), "one two"));
+    }
+}
@@ -144,6 +144,14 @@ pub struct FileFinderDelegate {
     history_items: Vec<FoundPath>,
 }
 
+/// The pane that the file finder opens the selected path in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OpenTarget {
+    Center,
+    Split,
+    Dock,
+}
+
 /// Use a custom ordering for file finder: the regular one
 /// defines max element with the highest score and the latest alphanumerical path (in case of a tie on other params), e.g:
 /// `[{score: 0.5, path = "c/d" }, { score: 0.5, path = "/a/b" }]`
@@ -630,96 +638,17 @@ impl FileFinderDelegate {
                 .log_err();
         })
     }
-}
-
-impl PickerDelegate for FileFinderDelegate {
-    type ListItem = ListItem;
-
-    fn placeholder_text(&self) -> Arc<str> {
-        "Search project files...".into()
-    }
-
-    fn match_count(&self) -> usize {
-        self.matches.len()
-    }
 
-    fn selected_index(&self) -> usize {
-        self.selected_index.unwrap_or(0)
-    }
-
-    fn set_selected_index(&mut self, ix: usize, cx: &mut ViewContext<Picker<Self>>) {
-        self.selected_index = Some(ix);
-        cx.notify();
-    }
-
-    fn separators_after_indices(&self) -> Vec<usize> {
-        let history_items = self.matches.history.len();
-        if history_items == 0 || self.matches.search.is_empty() {
-            Vec::new()
-        } else {
-            vec![history_items - 1]
-        }
-    }
-
-    fn update_matches(
-        &mut self,
-        raw_query: String,
-        cx: &mut ViewContext<Picker<Self>>,
-    ) -> Task<()> {
-        let raw_query = raw_query.replace(" ", "");
-        let raw_query = raw_query.trim();
-        if raw_query.is_empty() {
-            let project = self.project.read(cx);
-            self.latest_search_id = post_inc(&mut self.search_count);
-            self.selected_index.take();
-            self.matches = Matches {
-                history: self
-                    .history_items
-                    .iter()
-                    .filter(|history_item| {
-                        project
-                            .worktree_for_id(history_item.project.worktree_id, cx)
-                            .is_some()
-                            || (project.is_local() && history_item.absolute.is_some())
-                    })
-                    .cloned()
-                    .map(|p| (p, None))
-                    .collect(),
-                search: Vec::new(),
-            };
-            cx.notify();
-            Task::ready(())
-        } else {
-            let query = PathLikeWithPosition::parse_str(raw_query, |path_like_str| {
-                Ok::<_, std::convert::Infallible>(FileSearchQuery {
-                    raw_query: raw_query.to_owned(),
-                    file_query_end: if path_like_str == raw_query {
-                        None
-                    } else {
-                        Some(path_like_str.len())
-                    },
-                })
-            })
-            .expect("infallible");
-
-            if Path::new(query.path_like.path_query()).is_absolute() {
-                self.lookup_absolute_path(query, cx)
-            } else {
-                self.spawn_search(query, cx)
-            }
-        }
-    }
-
-    fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<FileFinderDelegate>>) {
+    /// Opens the selected path, with `target` choosing the pane it opens in. Paths outside of
+    /// the project's worktrees aren't project items, so they never open into a dock.
+    fn open_selected(&mut self, target: OpenTarget, cx: &mut ViewContext<Picker<Self>>) {
         if let Some(m) = self.matches.get(self.selected_index()) {
             if let Some(workspace) = self.workspace.upgrade() {
                 let open_task = workspace.update(cx, move |workspace, cx| {
-                    let split_or_open = |workspace: &mut Workspace, project_path, cx| {
-                        if secondary {
-                            workspace.split_path(project_path, cx)
-                        } else {
-                            workspace.open_path(project_path, None, true, cx)
-                        }
+                    let split_or_open = |workspace: &mut Workspace, project_path, cx| match target {
+                        OpenTarget::Split => workspace.split_path(project_path, cx),
+                        OpenTarget::Center => workspace.open_path(project_path, None, true, cx),
+                        OpenTarget::Dock => workspace.open_path_in_dock(project_path, cx),
                     };
                     match m {
                         Match::History(history_match, _) => {
@@ -741,7 +670,7 @@ impl PickerDelegate for FileFinderDelegate {
                             } else {
                                 match history_match.absolute.as_ref() {
                                     Some(abs_path) => {
-                                        if secondary {
+                                        if target == OpenTarget::Split {
                                             workspace.split_abs_path(
                                                 abs_path.to_path_buf(),
                                                 false,
@@ -816,6 +745,98 @@ impl PickerDelegate for FileFinderDelegate {
             }
         }
     }
+}
+
+impl PickerDelegate for FileFinderDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self) -> Arc<str> {
+        "Search project files...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index.unwrap_or(0)
+    }
+
+    fn set_selected_index(&mut self, ix: usize, cx: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = Some(ix);
+        cx.notify();
+    }
+
+    fn separators_after_indices(&self) -> Vec<usize> {
+        let history_items = self.matches.history.len();
+        if history_items == 0 || self.matches.search.is_empty() {
+            Vec::new()
+        } else {
+            vec![history_items - 1]
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        raw_query: String,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) -> Task<()> {
+        let raw_query = raw_query.replace(" ", "");
+        let raw_query = raw_query.trim();
+        if raw_query.is_empty() {
+            let project = self.project.read(cx);
+            self.latest_search_id = post_inc(&mut self.search_count);
+            self.selected_index.take();
+            self.matches = Matches {
+                history: self
+                    .history_items
+                    .iter()
+                    .filter(|history_item| {
+                        project
+                            .worktree_for_id(history_item.project.worktree_id, cx)
+                            .is_some()
+                            || (project.is_local() && history_item.absolute.is_some())
+                    })
+                    .cloned()
+                    .map(|p| (p, None))
+                    .collect(),
+                search: Vec::new(),
+            };
+            cx.notify();
+            Task::ready(())
+        } else {
+            let query = PathLikeWithPosition::parse_str(raw_query, |path_like_str| {
+                Ok::<_, std::convert::Infallible>(FileSearchQuery {
+                    raw_query: raw_query.to_owned(),
+                    file_query_end: if path_like_str == raw_query {
+                        None
+                    } else {
+                        Some(path_like_str.len())
+                    },
+                })
+            })
+            .expect("infallible");
+
+            if Path::new(query.path_like.path_query()).is_absolute() {
+                self.lookup_absolute_path(query, cx)
+            } else {
+                self.spawn_search(query, cx)
+            }
+        }
+    }
+
+    fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<FileFinderDelegate>>) {
+        let target = if secondary {
+            OpenTarget::Split
+        } else {
+            OpenTarget::Center
+        };
+        self.open_selected(target, cx);
+    }
+
+    fn confirm_in_dock(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.open_selected(OpenTarget::Dock, cx);
+    }
 
     fn dismissed(&mut self, cx: &mut ViewContext<Picker<FileFinderDelegate>>) {
         self.file_finder
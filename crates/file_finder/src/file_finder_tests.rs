@@ -5,7 +5,10 @@ use editor::Editor;
 use gpui::{Entity, TestAppContext, VisualTestContext};
 use menu::{Confirm, SelectNext};
 use serde_json::json;
-use workspace::{AppState, Workspace};
+use workspace::{
+    dock::{test::TestPanel, DockPosition},
+    AppState, Pane, Workspace,
+};
 
 #[ctor::ctor]
 fn init_logger() {
@@ -1119,6 +1122,71 @@ async fn test_history_items_vs_very_good_external_match(cx: &mut gpui::TestAppCo
         });
 }
 
+#[gpui::test]
+async fn test_confirm_in_dock(cx: &mut gpui::TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/src",
+            json!({
+                "test": {
+                    "first.rs": "// First Rust file",
+                    "second.rs": "// Second Rust file",
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/src".as_ref()], cx).await;
+    let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+    // Without a dock panel hosting panes, the selection opens in the center.
+    open_file_picker(&workspace, cx);
+    cx.simulate_input("fir");
+    cx.dispatch_action(picker::ConfirmInDock);
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "first.rs");
+    });
+
+    let dock_pane = workspace.update(cx, |workspace, cx| {
+        let pane = cx.new_view(|cx| {
+            Pane::new(
+                workspace.weak_handle(),
+                workspace.project().clone(),
+                Default::default(),
+                None,
+                cx,
+            )
+        });
+        let panel = cx.new_view(|cx| TestPanel {
+            panes: vec![pane.clone()],
+            ..TestPanel::new(DockPosition::Bottom, cx)
+        });
+        workspace.add_panel(panel, cx);
+        assert!(!workspace.bottom_dock().read(cx).is_open());
+        pane
+    });
+
+    // With one, the selection opens in its pane, revealing the dock.
+    open_file_picker(&workspace, cx);
+    cx.simulate_input("sec");
+    cx.dispatch_action(picker::ConfirmInDock);
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.bottom_dock().read(cx).is_open());
+        let center_pane = &workspace.panes()[0];
+        assert_eq!(center_pane.read(cx).items_len(), 1);
+    });
+    dock_pane.update(cx, |pane, cx| {
+        let editor = pane.active_item().unwrap().downcast::<Editor>().unwrap();
+        assert_eq!(editor.read(cx).title(cx), "second.rs");
+    });
+}
+
 #[gpui::test]
 async fn test_nonexistent_history_items_not_shown(cx: &mut gpui::TestAppContext) {
     let app_state = init_test(cx);
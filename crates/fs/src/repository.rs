@@ -1,5 +1,5 @@
-use anyhow::Result;
-use collections::HashMap;
+use anyhow::{Context, Result};
+use collections::{HashMap, HashSet};
 use git2::{BranchType, StatusShow};
 use parking_lot::Mutex;
 use serde_derive::{Deserialize, Serialize};
@@ -8,6 +8,7 @@ use std::{
     ffi::OsStr,
     os::unix::prelude::OsStrExt,
     path::{Component, Path, PathBuf},
+    process::Command,
     sync::Arc,
     time::SystemTime,
 };
@@ -23,10 +24,24 @@ pub struct Branch {
     pub unix_timestamp: Option<i64>,
 }
 
+/// A file with changes in the index or in the working directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub repo_path: RepoPath,
+    /// The status of the file in the index, with respect to the HEAD commit.
+    pub staged: Option<GitFileStatus>,
+    /// The status of the file in the working directory, with respect to the index.
+    pub unstaged: Option<GitFileStatus>,
+}
+
 pub trait GitRepository: Send {
     fn reload_index(&self);
     fn load_index_text(&self, relative_file_path: &Path) -> Option<String>;
 
+    /// Returns the contents of the file at the given path in the HEAD commit, or
+    /// `None` if it isn't part of that commit.
+    fn load_head_text(&self, relative_file_path: &Path) -> Option<String>;
+
     /// Returns the URL of the remote with the given name.
     fn remote_url(&self, name: &str) -> Option<String>;
     fn branch_name(&self) -> Option<String>;
@@ -55,6 +70,27 @@ pub trait GitRepository: Send {
     fn branches(&self) -> Result<Vec<Branch>>;
     fn change_branch(&self, _: &str) -> Result<()>;
     fn create_branch(&self, _: &str) -> Result<()>;
+
+    /// Returns every file that differs between HEAD, the index and the working
+    /// directory, excluding ignored files.
+    fn changed_files(&self) -> Result<Vec<ChangedFile>>;
+
+    /// Adds the working directory contents of the given paths to the index.
+    fn stage_paths(&self, paths: &[RepoPath]) -> Result<()>;
+
+    /// Resets the index entries of the given paths to their contents in HEAD.
+    fn unstage_paths(&self, paths: &[RepoPath]) -> Result<()>;
+
+    /// Replaces the contents of a file in the index without touching the
+    /// working directory, which is how individual hunks get staged.
+    fn set_index_text(&self, path: &RepoPath, content: &str) -> Result<()>;
+
+    /// Commits the contents of the index on top of HEAD.
+    fn commit(&self, message: &str) -> Result<()>;
+
+    /// The directory git commands for this repository run in, or `None` for a
+    /// bare repository.
+    fn workdir(&self) -> Option<PathBuf>;
 }
 
 impl std::fmt::Debug for dyn GitRepository {
@@ -94,6 +130,31 @@ impl GitRepository for LibGitRepository {
         None
     }
 
+    fn load_head_text(&self, relative_file_path: &Path) -> Option<String> {
+        fn logic(repo: &LibGitRepository, relative_file_path: &Path) -> Result<Option<String>> {
+            check_path_to_repo_path_errors(relative_file_path)?;
+
+            let Ok(head) = repo.head() else {
+                return Ok(None);
+            };
+            let tree = head.peel_to_tree()?;
+            let entry = match tree.get_path(relative_file_path) {
+                Ok(entry) => entry,
+                Err(err) if err.code() == git2::ErrorCode::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+
+            let content = repo.find_blob(entry.id())?.content().to_owned();
+            Ok(Some(String::from_utf8(content)?))
+        }
+
+        match logic(self, relative_file_path) {
+            Ok(value) => return value,
+            Err(err) => log::error!("Error loading HEAD text: {:?}", err),
+        }
+        None
+    }
+
     fn remote_url(&self, name: &str) -> Option<String> {
         let remote = self.find_remote(name).ok()?;
         remote.url().map(|url| url.to_string())
@@ -211,6 +272,147 @@ impl GitRepository for LibGitRepository {
 
         Ok(())
     }
+
+    fn changed_files(&self) -> Result<Vec<ChangedFile>> {
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true);
+        options.recurse_untracked_dirs(true);
+        options.include_ignored(false);
+
+        let statuses = self.statuses(Some(&mut options))?;
+        Ok(statuses
+            .iter()
+            .filter_map(|entry| {
+                let status = entry.status();
+                let staged = read_index_status(status);
+                let unstaged = read_workdir_status(status);
+                if staged.is_none() && unstaged.is_none() {
+                    return None;
+                }
+                Some(ChangedFile {
+                    repo_path: RepoPath(PathBuf::from(OsStr::from_bytes(entry.path_bytes()))),
+                    staged,
+                    unstaged,
+                })
+            })
+            .collect())
+    }
+
+    fn stage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        let workdir = self
+            .workdir()
+            .context("cannot stage in a bare repository")?;
+        let mut index = self.index()?;
+        for path in paths {
+            check_path_to_repo_path_errors(path)?;
+            if workdir.join(path).exists() {
+                index.add_path(path)?;
+            } else {
+                index.remove_path(path)?;
+            }
+        }
+        index.write()?;
+        Ok(())
+    }
+
+    fn unstage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        match self.head().and_then(|head| head.peel_to_commit()) {
+            Ok(head) => self.reset_default(
+                Some(head.as_object()),
+                paths.iter().map(|path| path.as_path()),
+            )?,
+            // Before the first commit, unstaging a file removes it from the index.
+            Err(_) => {
+                let mut index = self.index()?;
+                for path in paths {
+                    index.remove_path(path)?;
+                }
+                index.write()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_index_text(&self, path: &RepoPath, content: &str) -> Result<()> {
+        const STAGE_NORMAL: i32 = 0;
+        const FILE_MODE: u32 = 0o100644;
+
+        check_path_to_repo_path_errors(path)?;
+        let mut index = self.index()?;
+        let mut entry = match index.get_path(path, STAGE_NORMAL) {
+            Some(entry) => entry,
+            None => git2::IndexEntry {
+                ctime: git2::IndexTime::new(0, 0),
+                mtime: git2::IndexTime::new(0, 0),
+                dev: 0,
+                ino: 0,
+                mode: FILE_MODE,
+                uid: 0,
+                gid: 0,
+                file_size: 0,
+                id: git2::Oid::zero(),
+                flags: 0,
+                flags_extended: 0,
+                path: path.as_os_str().as_bytes().to_vec(),
+            },
+        };
+        entry.file_size = content.len() as u32;
+        index.add_frombuffer(&entry, content.as_bytes())?;
+        index.write()?;
+        Ok(())
+    }
+
+    fn commit(&self, message: &str) -> Result<()> {
+        let signature = self.signature()?;
+        let tree = self.find_tree(self.index()?.write_tree()?)?;
+        let parent = self.head().and_then(|head| head.peel_to_commit()).ok();
+        let parents = parent.iter().collect::<Vec<_>>();
+        LibGitRepository::commit(
+            self,
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?;
+        Ok(())
+    }
+
+    fn workdir(&self) -> Option<PathBuf> {
+        LibGitRepository::workdir(self).map(Path::to_path_buf)
+    }
+}
+
+// Pushing and pulling go through the git executable, so that the user's credential
+// helpers and ssh configuration are respected. They talk to the network, so they
+// take a [`GitRepository::workdir`] rather than the repository, which mustn't stay
+// locked meanwhile.
+
+/// Pushes the current branch of the repository checked out in `workdir`.
+pub fn push(workdir: &Path) -> Result<()> {
+    run_git_command(workdir, &["push"])
+}
+
+/// Fast-forwards the current branch of the repository checked out in `workdir`.
+pub fn pull(workdir: &Path) -> Result<()> {
+    run_git_command(workdir, &["pull", "--ff-only"])
+}
+
+fn run_git_command(workdir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(workdir)
+        .output()
+        .with_context(|| format!("failed to run git {args:?}"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
 }
 
 fn matches_index(repo: &LibGitRepository, path: &RepoPath, mtime: SystemTime) -> bool {
@@ -228,6 +430,40 @@ fn matches_index(repo: &LibGitRepository, path: &RepoPath, mtime: SystemTime) ->
     false
 }
 
+fn read_index_status(status: git2::Status) -> Option<GitFileStatus> {
+    if status.contains(git2::Status::CONFLICTED) {
+        Some(GitFileStatus::Conflict)
+    } else if status.intersects(
+        git2::Status::INDEX_MODIFIED
+            | git2::Status::INDEX_RENAMED
+            | git2::Status::INDEX_DELETED
+            | git2::Status::INDEX_TYPECHANGE,
+    ) {
+        Some(GitFileStatus::Modified)
+    } else if status.contains(git2::Status::INDEX_NEW) {
+        Some(GitFileStatus::Added)
+    } else {
+        None
+    }
+}
+
+fn read_workdir_status(status: git2::Status) -> Option<GitFileStatus> {
+    if status.contains(git2::Status::CONFLICTED) {
+        Some(GitFileStatus::Conflict)
+    } else if status.intersects(
+        git2::Status::WT_MODIFIED
+            | git2::Status::WT_RENAMED
+            | git2::Status::WT_DELETED
+            | git2::Status::WT_TYPECHANGE,
+    ) {
+        Some(GitFileStatus::Modified)
+    } else if status.contains(git2::Status::WT_NEW) {
+        Some(GitFileStatus::Added)
+    } else {
+        None
+    }
+}
+
 fn read_status(status: git2::Status) -> Option<GitFileStatus> {
     if status.contains(git2::Status::CONFLICTED) {
         Some(GitFileStatus::Conflict)
@@ -253,8 +489,11 @@ pub struct FakeGitRepository {
 #[derive(Debug, Clone, Default)]
 pub struct FakeGitRepositoryState {
     pub index_contents: HashMap<PathBuf, String>,
+    pub head_contents: HashMap<PathBuf, String>,
     pub worktree_statuses: HashMap<RepoPath, GitFileStatus>,
     pub branch_name: Option<String>,
+    pub staged_paths: HashSet<RepoPath>,
+    pub commit_messages: Vec<String>,
 }
 
 impl FakeGitRepository {
@@ -271,6 +510,11 @@ impl GitRepository for FakeGitRepository {
         state.index_contents.get(path).cloned()
     }
 
+    fn load_head_text(&self, path: &Path) -> Option<String> {
+        let state = self.state.lock();
+        state.head_contents.get(path).cloned()
+    }
+
     fn remote_url(&self, _name: &str) -> Option<String> {
         None
     }
@@ -319,6 +563,62 @@ impl GitRepository for FakeGitRepository {
         state.branch_name = Some(name.to_owned());
         Ok(())
     }
+
+    fn changed_files(&self) -> Result<Vec<ChangedFile>> {
+        let state = self.state.lock();
+        let mut files = state
+            .worktree_statuses
+            .iter()
+            .map(|(repo_path, status)| {
+                let is_staged = state.staged_paths.contains(repo_path);
+                ChangedFile {
+                    repo_path: repo_path.clone(),
+                    staged: is_staged.then_some(*status),
+                    unstaged: (!is_staged).then_some(*status),
+                }
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.repo_path.cmp(&b.repo_path));
+        Ok(files)
+    }
+
+    fn stage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        let mut state = self.state.lock();
+        state.staged_paths.extend(paths.iter().cloned());
+        Ok(())
+    }
+
+    fn unstage_paths(&self, paths: &[RepoPath]) -> Result<()> {
+        let mut state = self.state.lock();
+        for path in paths {
+            state.staged_paths.remove(path);
+        }
+        Ok(())
+    }
+
+    fn set_index_text(&self, path: &RepoPath, content: &str) -> Result<()> {
+        let mut state = self.state.lock();
+        state
+            .index_contents
+            .insert(path.to_path_buf(), content.to_owned());
+        Ok(())
+    }
+
+    fn commit(&self, message: &str) -> Result<()> {
+        let mut state = self.state.lock();
+        if state.staged_paths.is_empty() {
+            anyhow::bail!("nothing to commit");
+        }
+        for path in std::mem::take(&mut state.staged_paths) {
+            state.worktree_statuses.remove(&path);
+        }
+        state.commit_messages.push(message.to_owned());
+        Ok(())
+    }
+
+    fn workdir(&self) -> Option<PathBuf> {
+        None
+    }
 }
 
 fn check_path_to_repo_path_errors(relative_file_path: &Path) -> Result<()> {
@@ -0,0 +1,969 @@
+mod git_panel_settings;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use editor::{DiffView, Editor};
+use git_panel_settings::GitPanelSettings;
+use gpui::{
+    actions, uniform_list, Action, AppContext, AsyncWindowContext, Context, EventEmitter,
+    FocusHandle, FocusableView, KeyContext, Model, Pixels, Subscription, Task,
+    UniformListScrollHandle, View, ViewContext, VisualContext, WeakView, WindowContext,
+};
+use language::{Anchor, Capability, Point};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
+use multi_buffer::MultiBuffer;
+use parking_lot::Mutex;
+use project::{
+    repository::{self, GitFileStatus, GitRepository, RepoPath},
+    Fs, Project, ProjectPath, WorktreeId,
+};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, Checkbox, ListItem, Tooltip};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::NotifyTaskExt,
+    panels::{register_panel, PanelPlacement},
+    Workspace,
+};
+
+const GIT_PANEL_KEY: &'static str = "GitPanel";
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(50);
+const DIFF_CONTEXT_LINES: u32 = 3;
+
+actions!(
+    git_panel,
+    [
+        ToggleFocus,
+        ToggleStaged,
+        StageAll,
+        UnstageAll,
+        Commit,
+        Push,
+        Pull,
+        StageHunk
+    ]
+);
+
+pub fn init(cx: &mut AppContext) {
+    GitPanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), GitPanel::load, cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<GitPanel>(cx);
+        });
+        workspace.register_action(stage_hunk);
+    })
+    .detach();
+}
+
+/// A git repository found in one of the project's local worktrees.
+struct Repository {
+    git: Arc<Mutex<dyn GitRepository>>,
+    branch: Option<SharedString>,
+}
+
+/// A file with changes, as listed in the panel.
+struct GitPanelEntry {
+    repository_ix: usize,
+    repo_path: RepoPath,
+    project_path: ProjectPath,
+    staged: Option<GitFileStatus>,
+    unstaged: Option<GitFileStatus>,
+}
+
+impl GitPanelEntry {
+    fn selection(&self) -> Selection {
+        match (self.staged.is_some(), self.unstaged.is_some()) {
+            (true, false) => Selection::Selected,
+            (true, true) => Selection::Indeterminate,
+            _ => Selection::Unselected,
+        }
+    }
+
+    fn status(&self) -> Option<GitFileStatus> {
+        GitFileStatus::merge(self.staged, self.unstaged, false)
+    }
+}
+
+/// A dock panel listing the changed files of the project's git repositories, from
+/// which changes can be staged, committed and pushed.
+pub struct GitPanel {
+    project: Model<Project>,
+    workspace: WeakView<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    commit_editor: View<Editor>,
+    repositories: Vec<Repository>,
+    entries: Vec<GitPanelEntry>,
+    selected_index: Option<usize>,
+    list: UniformListScrollHandle,
+    pending_operation: bool,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    refresh_task: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedGitPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl GitPanel {
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let commit_editor = cx.new_view(|cx| {
+            let mut editor = Editor::auto_height(6, cx);
+            editor.set_placeholder_text("Commit message", cx);
+            editor
+        });
+        let _subscriptions = vec![
+            cx.subscribe(&project, |this, _, event, cx| match event {
+                project::Event::WorktreeAdded
+                | project::Event::WorktreeRemoved(_)
+                | project::Event::WorktreeUpdatedEntries(..)
+                | project::Event::WorktreeUpdatedGitRepositories => this.refresh(cx),
+                _ => {}
+            }),
+            cx.observe(&commit_editor, |_, _, cx| cx.notify()),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+
+        let mut this = Self {
+            project,
+            workspace: workspace.weak_handle(),
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            commit_editor,
+            repositories: Vec::new(),
+            entries: Vec::new(),
+            selected_index: None,
+            list: UniformListScrollHandle::new(),
+            pending_operation: false,
+            width: None,
+            height: None,
+            refresh_task: Task::ready(None),
+            _subscriptions,
+        };
+        this.refresh(cx);
+        this
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(GIT_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedGitPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = cx.new_view(|cx| Self::new(workspace, cx));
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedGitPanel {
+            width: self.width,
+            height: self.height,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(GIT_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    /// Re-reads the status of every repository in the project's local worktrees.
+    fn refresh(&mut self, cx: &mut ViewContext<Self>) {
+        let repositories = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                let repositories = worktree
+                    .as_local()?
+                    .local_git_repositories()
+                    .map(|(work_directory, git)| (work_directory.as_ref().to_path_buf(), git))
+                    .collect::<Vec<_>>();
+                Some((worktree.id(), repositories))
+            })
+            .flat_map(|(worktree_id, repositories)| {
+                repositories
+                    .into_iter()
+                    .map(move |(work_directory, git)| (worktree_id, work_directory, git))
+            })
+            .collect::<Vec<_>>();
+
+        self.refresh_task = cx.spawn(|this, mut cx| {
+            async move {
+                cx.background_executor().timer(REFRESH_DEBOUNCE).await;
+
+                let (repositories, mut entries) = cx
+                    .background_executor()
+                    .spawn(async move { read_repositories(repositories) })
+                    .await;
+                entries.sort_by(|a, b| {
+                    a.project_path
+                        .worktree_id
+                        .cmp(&b.project_path.worktree_id)
+                        .then_with(|| a.project_path.path.cmp(&b.project_path.path))
+                });
+
+                this.update(&mut cx, |this, cx| {
+                    this.repositories = repositories;
+                    this.entries = entries;
+                    this.selected_index = this
+                        .selected_index
+                        .map(|ix| ix.min(this.entries.len().saturating_sub(1)))
+                        .filter(|_| !this.entries.is_empty());
+                    cx.notify();
+                })
+            }
+            .log_err()
+        });
+    }
+
+    /// Runs a git operation in the background, refreshing the panel once it
+    /// completes and surfacing any failure as a workspace notification.
+    fn run_operation(
+        &mut self,
+        operation: impl 'static + Send + FnOnce() -> Result<()>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if self.pending_operation {
+            return;
+        }
+        self.pending_operation = true;
+        cx.notify();
+
+        let task = cx.spawn(|this, mut cx| async move {
+            let result = cx
+                .background_executor()
+                .spawn(async move { operation() })
+                .await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_operation = false;
+                this.refresh(cx);
+            })?;
+            result
+        });
+        task.detach_and_notify_err(cx);
+    }
+
+    fn set_staged(&mut self, ixs: Vec<usize>, stage: bool, cx: &mut ViewContext<Self>) {
+        let mut paths_by_repository = Vec::<(Arc<Mutex<dyn GitRepository>>, Vec<RepoPath>)>::new();
+        for ix in ixs {
+            let Some(entry) = self.entries.get(ix) else {
+                continue;
+            };
+            let git = &self.repositories[entry.repository_ix].git;
+            match paths_by_repository
+                .iter_mut()
+                .find(|(repository, _)| Arc::ptr_eq(repository, git))
+            {
+                Some((_, paths)) => paths.push(entry.repo_path.clone()),
+                None => paths_by_repository.push((git.clone(), vec![entry.repo_path.clone()])),
+            }
+        }
+        if paths_by_repository.is_empty() {
+            return;
+        }
+
+        self.run_operation(
+            move || {
+                for (repository, paths) in paths_by_repository {
+                    let repository = repository.lock();
+                    if stage {
+                        repository.stage_paths(&paths)?;
+                    } else {
+                        repository.unstage_paths(&paths)?;
+                    }
+                }
+                Ok(())
+            },
+            cx,
+        );
+    }
+
+    fn toggle_entry(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(entry) = self.entries.get(ix) {
+            let stage = entry.unstaged.is_some();
+            self.set_staged(vec![ix], stage, cx);
+        }
+    }
+
+    fn toggle_staged(&mut self, _: &ToggleStaged, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.toggle_entry(ix, cx);
+        }
+    }
+
+    fn stage_all(&mut self, _: &StageAll, cx: &mut ViewContext<Self>) {
+        let ixs = (0..self.entries.len())
+            .filter(|ix| self.entries[*ix].unstaged.is_some())
+            .collect();
+        self.set_staged(ixs, true, cx);
+    }
+
+    fn unstage_all(&mut self, _: &UnstageAll, cx: &mut ViewContext<Self>) {
+        let ixs = (0..self.entries.len())
+            .filter(|ix| self.entries[*ix].staged.is_some())
+            .collect();
+        self.set_staged(ixs, false, cx);
+    }
+
+    fn commit(&mut self, _: &Commit, cx: &mut ViewContext<Self>) {
+        let message = self.commit_editor.read(cx).text(cx).trim().to_string();
+        if message.is_empty() {
+            return;
+        }
+        let mut repositories = Vec::<Arc<Mutex<dyn GitRepository>>>::new();
+        for entry in &self.entries {
+            let git = &self.repositories[entry.repository_ix].git;
+            if entry.staged.is_some() && !repositories.iter().any(|r| Arc::ptr_eq(r, git)) {
+                repositories.push(git.clone());
+            }
+        }
+        if repositories.is_empty() {
+            return;
+        }
+
+        self.commit_editor
+            .update(cx, |editor, cx| editor.set_text("", cx));
+        self.run_operation(
+            move || {
+                for repository in repositories {
+                    repository.lock().commit(&message)?;
+                }
+                Ok(())
+            },
+            cx,
+        );
+    }
+
+    /// The working directory of the repository that pushes and pulls act upon:
+    /// the one of the first visible worktree.
+    fn primary_workdir(&self) -> Result<PathBuf> {
+        let repository = self
+            .repositories
+            .first()
+            .ok_or_else(|| anyhow!("the project is not in a git repository"))?;
+        let workdir = repository.git.lock().workdir();
+        workdir.ok_or_else(|| anyhow!("cannot push or pull a bare repository"))
+    }
+
+    fn push(&mut self, _: &Push, cx: &mut ViewContext<Self>) {
+        if let Some(workdir) = self.primary_workdir().log_err() {
+            self.run_operation(move || repository::push(&workdir), cx);
+        }
+    }
+
+    fn pull(&mut self, _: &Pull, cx: &mut ViewContext<Self>) {
+        if let Some(workdir) = self.primary_workdir().log_err() {
+            self.run_operation(move || repository::pull(&workdir), cx);
+        }
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix < self.entries.len() {
+            self.selected_index = Some(ix);
+            self.list.scroll_to_item(ix);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, cx: &mut ViewContext<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, cx: &mut ViewContext<Self>) {
+        self.select_index(self.entries.len().saturating_sub(1), cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_entry(ix, cx);
+        }
+    }
+
+    fn open_entry(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let project_path = entry.project_path.clone();
+        if let Some(workspace) = self.workspace.upgrade() {
+            workspace.update(cx, |workspace, cx| {
+                open_changes(workspace, project_path, cx)
+            });
+        }
+    }
+
+    fn open_diff(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let project_path = entry.project_path.clone();
+        if let Some(workspace) = self.workspace.upgrade() {
+            workspace.update(cx, |workspace, cx| {
+                DiffView::open_head_diff(workspace, project_path, cx).detach_and_notify_err(cx)
+            });
+        }
+    }
+
+    fn dispatch_context(&self, cx: &ViewContext<Self>) -> KeyContext {
+        let mut dispatch_context = KeyContext::default();
+        dispatch_context.add("GitPanel");
+        dispatch_context.add("menu");
+
+        let identifier = if self.commit_editor.focus_handle(cx).is_focused(cx) {
+            "editing"
+        } else {
+            "not_editing"
+        };
+
+        dispatch_context.add(identifier);
+        dispatch_context
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let branch = self
+            .repositories
+            .first()
+            .and_then(|repository| repository.branch.clone())
+            .unwrap_or_else(|| "No repository".into());
+        let has_repository = !self.repositories.is_empty();
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Label::new(branch)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("git-pull", IconName::ArrowDown)
+                            .icon_size(IconSize::Small)
+                            .disabled(!has_repository || self.pending_operation)
+                            .tooltip(|cx| Tooltip::for_action("Pull", &Pull, cx))
+                            .on_click(|_, cx| cx.dispatch_action(Pull.boxed_clone())),
+                    )
+                    .child(
+                        IconButton::new("git-push", IconName::ArrowUp)
+                            .icon_size(IconSize::Small)
+                            .disabled(!has_repository || self.pending_operation)
+                            .tooltip(|cx| Tooltip::for_action("Push", &Push, cx))
+                            .on_click(|_, cx| cx.dispatch_action(Push.boxed_clone())),
+                    ),
+            )
+    }
+
+    fn render_changes_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let all_staged = self.entries.iter().all(|entry| entry.unstaged.is_none());
+        let button = if all_staged {
+            Button::new("unstage-all", "Unstage All")
+                .tooltip(|cx| Tooltip::for_action("Unstage All", &UnstageAll, cx))
+                .on_click(|_, cx| cx.dispatch_action(UnstageAll.boxed_clone()))
+        } else {
+            Button::new("stage-all", "Stage All")
+                .tooltip(|cx| Tooltip::for_action("Stage All", &StageAll, cx))
+                .on_click(|_, cx| cx.dispatch_action(StageAll.boxed_clone()))
+        };
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(format!("Changes ({})", self.entries.len()))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                button
+                    .label_size(LabelSize::Small)
+                    .disabled(self.entries.is_empty() || self.pending_operation),
+            )
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let color = match entry.status() {
+            Some(GitFileStatus::Added) => Color::Created,
+            Some(GitFileStatus::Modified) => Color::Modified,
+            Some(GitFileStatus::Conflict) => Color::Conflict,
+            None => Color::Default,
+        };
+        let path = &entry.project_path.path;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        let directory = path
+            .parent()
+            .filter(|parent| *parent != Path::new(""))
+            .map(|parent| parent.to_string_lossy().to_string());
+
+        ListItem::new(ix)
+            .inset(true)
+            .selected(self.selected_index == Some(ix))
+            .start_slot(
+                Checkbox::new(("git-stage", ix), entry.selection())
+                    .disabled(self.pending_operation)
+                    .on_click(cx.listener(move |this, _, cx| this.toggle_entry(ix, cx))),
+            )
+            .end_slot(
+                IconButton::new(("git-diff", ix), IconName::Split)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Open Side-by-Side Diff", cx))
+                    .on_click(cx.listener(move |this, _, cx| this.open_diff(ix, cx))),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(file_name).color(color))
+                    .children(directory.map(|directory| {
+                        Label::new(directory)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    })),
+            )
+            .on_click(cx.listener(move |this, _, cx| {
+                this.selected_index = Some(ix);
+                this.open_entry(ix, cx);
+                cx.notify();
+            }))
+    }
+
+    fn render_commit_box(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let can_commit = !self.pending_operation
+            && self.entries.iter().any(|entry| entry.staged.is_some())
+            && !self.commit_editor.read(cx).text(cx).trim().is_empty();
+
+        v_flex()
+            .p_2()
+            .gap_1()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                div()
+                    .px_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.commit_editor.clone()),
+            )
+            .child(
+                h_flex().justify_end().child(
+                    Button::new("git-commit", "Commit")
+                        .label_size(LabelSize::Small)
+                        .disabled(!can_commit)
+                        .tooltip(|cx| Tooltip::for_action("Commit Staged Changes", &Commit, cx))
+                        .on_click(|_, cx| cx.dispatch_action(Commit.boxed_clone())),
+                ),
+            )
+    }
+}
+
+fn read_repositories(
+    repositories: Vec<(
+        WorktreeId,
+        std::path::PathBuf,
+        Arc<Mutex<dyn GitRepository>>,
+    )>,
+) -> (Vec<Repository>, Vec<GitPanelEntry>) {
+    let mut entries = Vec::new();
+    let repositories = repositories
+        .into_iter()
+        .enumerate()
+        .map(|(repository_ix, (worktree_id, work_directory, git))| {
+            let (branch, changed_files) = {
+                let repository = git.lock();
+                (
+                    repository.branch_name().map(SharedString::from),
+                    repository.changed_files().log_err().unwrap_or_default(),
+                )
+            };
+            entries.extend(changed_files.into_iter().map(|file| GitPanelEntry {
+                repository_ix,
+                project_path: ProjectPath {
+                    worktree_id,
+                    path: work_directory.join(&file.repo_path).into(),
+                },
+                repo_path: file.repo_path,
+                staged: file.staged,
+                unstaged: file.unstaged,
+            }));
+            Repository { git, branch }
+        })
+        .collect();
+    (repositories, entries)
+}
+
+/// Opens the changed regions of a file in a multibuffer in the center pane, or the
+/// whole file when it has no hunks, as is the case for untracked files.
+fn open_changes(
+    workspace: &mut Workspace,
+    project_path: ProjectPath,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let project = workspace.project().clone();
+    let open_buffer = project.update(cx, |project, cx| {
+        project.open_buffer(project_path.clone(), cx)
+    });
+    cx.spawn(|workspace, mut cx| async move {
+        let buffer = open_buffer.await?;
+        let recalculate_diff = buffer.update(&mut cx, |buffer, cx| buffer.git_diff_recalc(cx))?;
+        if let Some(recalculate_diff) = recalculate_diff {
+            recalculate_diff.await;
+        }
+        let hunk_ranges = buffer.update(&mut cx, |buffer, _| {
+            let snapshot = buffer.snapshot();
+            snapshot
+                .git_diff_hunks_intersecting_range(Anchor::MIN..Anchor::MAX)
+                .map(|hunk| {
+                    let start = Point::new(hunk.buffer_range.start, 0);
+                    let end = Point::new(hunk.buffer_range.end, 0).min(snapshot.max_point());
+                    start..end
+                })
+                .collect::<Vec<_>>()
+        })?;
+
+        workspace.update(&mut cx, |workspace, cx| {
+            if hunk_ranges.is_empty() {
+                workspace
+                    .open_path(project_path, None, true, cx)
+                    .detach_and_log_err(cx);
+                return;
+            }
+
+            let title = format!("Changes in {}", project_path.path.display());
+            let replica_id = project.read(cx).replica_id();
+            let multibuffer = cx.new_model(|cx| {
+                let mut multibuffer =
+                    MultiBuffer::new(replica_id, Capability::ReadWrite).with_title(title);
+                multibuffer.push_excerpts_with_context_lines(
+                    buffer,
+                    hunk_ranges,
+                    DIFF_CONTEXT_LINES,
+                    cx,
+                );
+                multibuffer
+            });
+            let editor =
+                cx.new_view(|cx| Editor::for_multibuffer(multibuffer, Some(project.clone()), cx));
+            workspace.add_item(Box::new(editor), cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Stages the hunk under the newest cursor of the active editor, by writing the
+/// index text with just that hunk applied.
+fn stage_hunk(workspace: &mut Workspace, _: &StageHunk, cx: &mut ViewContext<Workspace>) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let editor = editor.read(cx);
+    let cursor = editor.selections.newest::<usize>(cx).head();
+    let Some((buffer, offset, _)) = editor.buffer().read(cx).point_to_buffer_offset(cursor, cx)
+    else {
+        return;
+    };
+    let buffer = buffer.read(cx);
+    let Some(diff_base) = buffer.diff_base() else {
+        return;
+    };
+    let Some(file) = project::File::from_dyn(buffer.file()) else {
+        return;
+    };
+    let Some((repo_path, repository)) = file
+        .worktree
+        .read(cx)
+        .as_local()
+        .and_then(|worktree| worktree.local_git_repository_for_path(&file.path))
+    else {
+        return;
+    };
+
+    let snapshot = buffer.snapshot();
+    let row = snapshot.offset_to_point(offset).row;
+    let row_range = snapshot.anchor_before(Point::new(row, 0))
+        ..snapshot.anchor_after(Point::new(row, snapshot.line_len(row)));
+    let Some(hunk) = snapshot.git_diff_hunks_intersecting_range(row_range).next() else {
+        return;
+    };
+    let start = Point::new(hunk.buffer_range.start, 0);
+    let end = Point::new(hunk.buffer_range.end, 0).min(snapshot.max_point());
+    let mut index_text = diff_base.to_string();
+    index_text.replace_range(
+        hunk.diff_base_byte_range.clone(),
+        &snapshot.text_for_range(start..end).collect::<String>(),
+    );
+
+    cx.background_executor()
+        .spawn(async move { repository.lock().set_index_text(&repo_path, &index_text) })
+        .detach_and_notify_err(cx);
+}
+
+impl EventEmitter<PanelEvent> for GitPanel {}
+
+impl Render for GitPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("git-panel")
+            .size_full()
+            .key_context(self.dispatch_context(cx))
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::toggle_staged))
+            .on_action(cx.listener(Self::stage_all))
+            .on_action(cx.listener(Self::unstage_all))
+            .on_action(cx.listener(Self::commit))
+            .on_action(cx.listener(Self::push))
+            .on_action(cx.listener(Self::pull))
+            .child(self.render_header(cx))
+            .child(self.render_changes_header(cx))
+            .child(if self.entries.is_empty() {
+                h_flex()
+                    .flex_1()
+                    .justify_center()
+                    .child(Label::new("No changes").color(Color::Muted))
+                    .into_any_element()
+            } else {
+                uniform_list(
+                    cx.view().clone(),
+                    "git-panel-entries",
+                    self.entries.len(),
+                    |this, range, cx| {
+                        range
+                            .map(|ix| this.render_entry(ix, cx).into_any_element())
+                            .collect()
+                    },
+                )
+                .flex_1()
+                .track_scroll(self.list.clone())
+                .into_any_element()
+            })
+            .child(self.render_commit_box(cx))
+    }
+}
+
+impl FocusableView for GitPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for GitPanel {
+    fn persistent_name() -> &'static str {
+        "GitPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        GitPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<GitPanelSettings>(self.fs.clone(), cx, move |settings| {
+            settings.dock = Some(position)
+        });
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = GitPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        GitPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::FileGit)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Git Panel")
+    }
+
+    fn icon_label(&self, _: &WindowContext) -> Option<String> {
+        let count = self.entries.len();
+        (count > 0).then(|| count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+
+    #[gpui::test]
+    async fn test_staging_and_committing(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                ".git": {},
+                "a.txt": "a",
+                "src": {
+                    "b.txt": "b",
+                },
+            }),
+        )
+        .await;
+        fs.set_status_for_repo_via_git_operation(
+            Path::new("/root/.git"),
+            &[
+                (Path::new("a.txt"), GitFileStatus::Modified),
+                (Path::new("src/b.txt"), GitFileStatus::Added),
+            ],
+        );
+
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let window = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let workspace = window.root(cx).unwrap();
+        let panel = workspace.update(cx, |workspace, cx| {
+            cx.new_view(|cx| GitPanel::new(workspace, cx))
+        });
+        cx.executor().advance_clock(REFRESH_DEBOUNCE);
+        cx.run_until_parked();
+
+        let entries = |panel: &View<GitPanel>, cx: &mut VisualTestContext| {
+            panel.update(cx, |panel, _| {
+                panel
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.project_path.path.to_path_buf(), entry.selection()))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            entries(&panel, cx),
+            [
+                (Path::new("a.txt").to_path_buf(), Selection::Unselected),
+                (Path::new("src/b.txt").to_path_buf(), Selection::Unselected),
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.toggle_entry(1, cx));
+        cx.executor().advance_clock(REFRESH_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            entries(&panel, cx),
+            [
+                (Path::new("a.txt").to_path_buf(), Selection::Unselected),
+                (Path::new("src/b.txt").to_path_buf(), Selection::Selected),
+            ]
+        );
+
+        // Only the staged file is committed, and the message box is cleared.
+        panel.update(cx, |panel, cx| {
+            panel
+                .commit_editor
+                .update(cx, |editor, cx| editor.set_text("Add b", cx));
+            panel.commit(&Commit, cx);
+            assert_eq!(panel.commit_editor.read(cx).text(cx), "");
+        });
+        cx.executor().advance_clock(REFRESH_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            entries(&panel, cx),
+            [(Path::new("a.txt").to_path_buf(), Selection::Unselected)]
+        );
+        let mut commit_messages = Vec::new();
+        fs.with_git_state(Path::new("/root/.git"), false, |state| {
+            commit_messages = state.commit_messages.clone();
+        });
+        assert_eq!(commit_messages, ["Add b"]);
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            client::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+            editor::init(cx);
+        });
+    }
+}
@@ -1,7 +1,7 @@
 use crate::SharedString;
 use anyhow::{anyhow, Context, Result};
 use collections::HashMap;
-pub use no_action::NoAction;
+pub use no_action::{NoAction, Unbind};
 use serde_json::json;
 use std::any::{Any, TypeId};
 
@@ -64,6 +64,29 @@ pub trait Action: 'static {
         Self: Sized;
 }
 
+/// Actions with parameters can't be built without knowing those parameters, so they
+/// aren't reported by [`crate::WindowContext::available_actions`] and don't show up in
+/// UI like the command palette. Implement this trait and register it with
+/// `register_action_variants!` to list each meaningful parameterization instead.
+/// ```ignore
+/// #[derive(Clone, PartialEq, serde_derive::Deserialize)]
+/// pub struct ActivatePaneInDirection(pub SplitDirection);
+/// impl_actions!(workspace, [ActivatePaneInDirection]);
+///
+/// impl ActionVariants for ActivatePaneInDirection {
+///     fn variants() -> Vec<(&'static str, Self)> {
+///         vec![("left", Self(SplitDirection::Left)), ("right", Self(SplitDirection::Right))]
+///     }
+/// }
+/// register_action_variants!(ActivatePaneInDirection);
+/// ```
+pub trait ActionVariants: Action {
+    /// Each variant of the action, along with a short name distinguishing it from the others.
+    fn variants() -> Vec<(&'static str, Self)>
+    where
+        Self: Sized;
+}
+
 impl std::fmt::Debug for dyn Action {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("dyn Action")
@@ -80,10 +103,12 @@ impl dyn Action {
 }
 
 type ActionBuilder = fn(json: serde_json::Value) -> anyhow::Result<Box<dyn Action>>;
+type ActionVariantsBuilder = fn() -> Vec<(&'static str, Box<dyn Action>)>;
 
 pub(crate) struct ActionRegistry {
     builders_by_name: HashMap<SharedString, ActionBuilder>,
     names_by_type_id: HashMap<TypeId, SharedString>,
+    variants_by_type_id: HashMap<TypeId, Vec<(SharedString, Box<dyn Action>)>>,
     all_names: Vec<SharedString>, // So we can return a static slice.
 }
 
@@ -92,6 +117,7 @@ impl Default for ActionRegistry {
         let mut this = ActionRegistry {
             builders_by_name: Default::default(),
             names_by_type_id: Default::default(),
+            variants_by_type_id: Default::default(),
             all_names: Default::default(),
         };
 
@@ -121,6 +147,35 @@ pub struct ActionData {
 #[linkme::distributed_slice]
 pub static __GPUI_ACTIONS: [MacroActionBuilder];
 
+/// This type must be public so that our macros can build it in other crates.
+/// But this is an implementation detail and should not be used directly.
+#[doc(hidden)]
+pub type MacroActionVariantsBuilder = fn() -> ActionVariantsData;
+
+/// This type must be public so that our macros can build it in other crates.
+/// But this is an implementation detail and should not be used directly.
+#[doc(hidden)]
+pub struct ActionVariantsData {
+    pub type_id: TypeId,
+    pub variants: ActionVariantsBuilder,
+}
+
+/// This constant must be public to be accessible from other crates.
+/// But its existence is an implementation detail and should not be used directly.
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static __GPUI_ACTION_VARIANTS: [MacroActionVariantsBuilder];
+
+/// This function must be public so that our macros can reference it from other crates.
+/// But this is an implementation detail and should not be used directly.
+#[doc(hidden)]
+pub fn __boxed_action_variants<A: ActionVariants>() -> Vec<(&'static str, Box<dyn Action>)> {
+    A::variants()
+        .into_iter()
+        .map(|(name, action)| (name, action.boxed_clone()))
+        .collect()
+}
+
 impl ActionRegistry {
     /// Load all registered actions into the registry.
     pub(crate) fn load_actions(&mut self) {
@@ -128,6 +183,14 @@ impl ActionRegistry {
             let action = builder();
             self.insert_action(action);
         }
+        for builder in __GPUI_ACTION_VARIANTS {
+            let ActionVariantsData { type_id, variants } = builder();
+            let variants = (variants)()
+                .into_iter()
+                .map(|(name, action)| (name.into(), action))
+                .collect();
+            self.variants_by_type_id.insert(type_id, variants);
+        }
     }
 
     #[cfg(test)]
@@ -174,6 +237,21 @@ impl ActionRegistry {
     pub fn all_action_names(&self) -> &[SharedString] {
         self.all_names.as_slice()
     }
+
+    /// Returns the variants registered for the given action type, if any.
+    pub fn action_variants(&self, type_id: &TypeId) -> Option<&[(SharedString, Box<dyn Action>)]> {
+        self.variants_by_type_id
+            .get(type_id)
+            .map(|variants| variants.as_slice())
+    }
+
+    /// Returns the name distinguishing the given action from the other variants of its type.
+    pub fn variant_name(&self, action: &dyn Action) -> Option<&SharedString> {
+        self.action_variants(&action.type_id())?
+            .iter()
+            .find(|(_, variant)| variant.partial_eq(action))
+            .map(|(name, _)| name)
+    }
 }
 
 /// Defines unit structs that can be used as actions.
@@ -261,6 +339,16 @@ macro_rules! __impl_action {
 
 mod no_action {
     use crate as gpui;
+    use crate::SharedString;
+    use serde::Deserialize;
 
     actions!(zed, [NoAction]);
+
+    /// Disables the binding of the named action to the same keystrokes in the same context,
+    /// leaving any other actions bound to them in place, unlike [`NoAction`]. In a keymap
+    /// file, it's written as `"cmd-k": ["zed::Unbind", "editor::Foo"]`.
+    #[derive(Clone, PartialEq, Deserialize)]
+    pub struct Unbind(pub SharedString);
+
+    impl_actions!(zed, [Unbind]);
 }
@@ -1006,6 +1006,11 @@ impl AppContext {
         self.pending_effects.push_back(Effect::Refresh);
     }
 
+    /// The keymap holding the key bindings of the app, which are shared by all windows.
+    pub fn key_bindings(&self) -> Rc<RefCell<Keymap>> {
+        self.keymap.clone()
+    }
+
     /// Clear all key bindings in the app.
     pub fn clear_key_bindings(&mut self) {
         self.keymap.borrow_mut().clear();
@@ -1058,6 +1063,12 @@ impl AppContext {
         self.actions.all_action_names()
     }
 
+    /// Get the name distinguishing the given action from the other variants of its
+    /// type, if variants were registered for it via `register_action_variants!`.
+    pub fn action_variant_name(&self, action: &dyn Action) -> Option<&SharedString> {
+        self.actions.variant_name(action)
+    }
+
     /// Register a callback to be invoked when the application is about to quit.
     /// It is not possible to cancel the quit event at this point.
     pub fn on_app_quit<Fut>(
@@ -1175,6 +1186,11 @@ impl AppContext {
     pub fn has_active_drag(&self) -> bool {
         self.active_drag.is_some()
     }
+
+    /// The value being dragged, if there is a drag in progress and its value is a `T`.
+    pub fn active_drag_value<T: 'static>(&self) -> Option<&T> {
+        self.active_drag.as_ref()?.value.downcast_ref()
+    }
 }
 
 impl Context for AppContext {
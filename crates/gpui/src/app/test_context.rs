@@ -116,6 +116,11 @@ impl TestAppContext {
         self.test_platform.did_prompt_for_new_path()
     }
 
+    /// Checks whether the app has asked the platform to quit.
+    pub fn did_quit(&self) -> bool {
+        self.test_platform.did_quit()
+    }
+
     /// returns a new `TestAppContext` re-using the same executors to interleave tasks.
     pub fn new_app(&self) -> TestAppContext {
         Self::new(self.dispatcher.clone(), self.fn_name)
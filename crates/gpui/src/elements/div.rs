@@ -20,8 +20,8 @@ use crate::{
     ClickEvent, DispatchPhase, Element, ElementContext, ElementId, FocusHandle, Global,
     IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId, MouseButton,
     MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point, Render,
-    ScrollWheelEvent, SharedString, Size, StackingOrder, Style, StyleRefinement, Styled, Task,
-    View, Visibility, WindowContext,
+    ScrollWheelEvent, SharedString, Size, StackingOrder, Style, StyleRefinement, Styled,
+    SwipeEvent, Task, View, Visibility, WindowContext,
 };
 
 use collections::HashMap;
@@ -283,6 +283,19 @@ impl Interactivity {
             }));
     }
 
+    /// Bind the given callback to swipe gestures during the bubble phase
+    /// The imperative API equivalent to [`InteractiveElement::on_swipe`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    pub fn on_swipe(&mut self, listener: impl Fn(&SwipeEvent, &mut WindowContext) + 'static) {
+        self.swipe_listeners
+            .push(Box::new(move |event, bounds, phase, cx| {
+                if phase == DispatchPhase::Bubble && bounds.visibly_contains(&event.position, cx) {
+                    (listener)(event, cx);
+                }
+            }));
+    }
+
     /// Bind the given callback to an action dispatch during the capture phase
     /// The imperative API equivalent to [`InteractiveElement::capture_action`]
     ///
@@ -485,6 +498,13 @@ impl Interactivity {
     pub fn block_mouse(&mut self) {
         self.block_mouse = true;
     }
+
+    /// Ignore key bindings while this element or any of its children is focused, so
+    /// that key down events go straight to the key listeners.
+    /// The imperative API equivalent to [`InteractiveElement::block_key_bindings`]
+    pub fn block_key_bindings(&mut self) {
+        self.block_key_bindings = true;
+    }
 }
 
 /// A trait for elements that want to use the standard GPUI event handlers that don't
@@ -700,6 +720,15 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Bind the given callback to swipe gestures during the bubble phase
+    /// The fluent API equivalent to [`Interactivity::on_swipe`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    fn on_swipe(mut self, listener: impl Fn(&SwipeEvent, &mut WindowContext) + 'static) -> Self {
+        self.interactivity().on_swipe(listener);
+        self
+    }
+
     /// Capture the given action, before normal action dispatch can fire
     /// The fluent API equivalent to [`Interactivity::on_scroll_wheel`]
     ///
@@ -840,6 +869,14 @@ pub trait InteractiveElement: Sized {
         self.interactivity().block_mouse();
         self
     }
+
+    /// Ignore key bindings while this element or any of its children is focused, so
+    /// that key down events go straight to the key listeners.
+    /// The fluent API equivalent to [`Interactivity::block_key_bindings`]
+    fn block_key_bindings(mut self) -> Self {
+        self.interactivity().block_key_bindings();
+        self
+    }
 }
 
 /// A trait for elements that want to use the standard GPUI interactivity features
@@ -989,6 +1026,9 @@ pub(crate) type MouseMoveListener =
 pub(crate) type ScrollWheelListener =
     Box<dyn Fn(&ScrollWheelEvent, &InteractiveBounds, DispatchPhase, &mut WindowContext) + 'static>;
 
+pub(crate) type SwipeListener =
+    Box<dyn Fn(&SwipeEvent, &InteractiveBounds, DispatchPhase, &mut WindowContext) + 'static>;
+
 pub(crate) type ClickListener = Box<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>;
 
 pub(crate) type DragListener = Box<dyn Fn(&dyn Any, &mut WindowContext) -> AnyView + 'static>;
@@ -1193,6 +1233,7 @@ pub struct Interactivity {
     pub(crate) mouse_up_listeners: Vec<MouseUpListener>,
     pub(crate) mouse_move_listeners: Vec<MouseMoveListener>,
     pub(crate) scroll_wheel_listeners: Vec<ScrollWheelListener>,
+    pub(crate) swipe_listeners: Vec<SwipeListener>,
     pub(crate) key_down_listeners: Vec<KeyDownListener>,
     pub(crate) key_up_listeners: Vec<KeyUpListener>,
     pub(crate) action_listeners: Vec<(TypeId, ActionListener)>,
@@ -1203,6 +1244,7 @@ pub struct Interactivity {
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut WindowContext)>>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) block_mouse: bool,
+    pub(crate) block_key_bindings: bool,
 
     #[cfg(debug_assertions)]
     pub(crate) location: Option<core::panic::Location<'static>>,
@@ -1506,6 +1548,13 @@ impl Interactivity {
                             })
                         }
 
+                        for listener in self.swipe_listeners.drain(..) {
+                            let interactive_bounds = interactive_bounds.clone();
+                            cx.on_mouse_event(move |event: &SwipeEvent, phase, cx| {
+                                listener(event, &interactive_bounds, phase, cx);
+                            })
+                        }
+
                         paint_hover_group_handler(cx);
 
                         if self.hover_style.is_some()
@@ -1890,10 +1939,15 @@ impl Interactivity {
                         let key_down_listeners = mem::take(&mut self.key_down_listeners);
                         let key_up_listeners = mem::take(&mut self.key_up_listeners);
                         let action_listeners = mem::take(&mut self.action_listeners);
+                        let block_key_bindings = self.block_key_bindings;
                         cx.with_key_dispatch(
                             self.key_context.clone(),
                             element_state.focus_handle.clone(),
                             |_, cx| {
+                                if block_key_bindings {
+                                    cx.block_key_bindings();
+                                }
+
                                 for listener in key_down_listeners {
                                     cx.on_key_event(move |event: &KeyDownEvent, phase, cx| {
                                         listener(event, phase, cx);
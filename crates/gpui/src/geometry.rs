@@ -13,7 +13,7 @@ use std::{
 };
 
 /// An axis along which a measurement can be made.
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Axis {
     /// The y axis, or up and down
     Vertical,
@@ -122,7 +122,7 @@ pub use element::*;
 pub use elements::*;
 pub use executor::*;
 pub use geometry::*;
-pub use gpui_macros::{register_action, test, IntoElement, Render};
+pub use gpui_macros::{register_action, register_action_variants, test, IntoElement, Render};
 use image_cache::*;
 pub use input::*;
 pub use interactive::*;
@@ -1,5 +1,6 @@
 use crate::{
-    point, seal::Sealed, IntoElement, Keystroke, Modifiers, Pixels, Point, Render, ViewContext,
+    point, seal::Sealed, IntoElement, Keystroke, Modifiers, Pixels, Point, Render, SharedString,
+    ViewContext,
 };
 use smallvec::SmallVec;
 use std::{any::Any, fmt::Debug, ops::Deref, path::PathBuf};
@@ -254,6 +255,41 @@ impl Deref for ScrollWheelEvent {
     }
 }
 
+/// A swipe gesture from the platform, such as a three-finger swipe on a trackpad.
+#[derive(Clone, Debug, Default)]
+pub struct SwipeEvent {
+    /// The position of the mouse on the window.
+    pub position: Point<Pixels>,
+
+    /// The direction the fingers moved in.
+    pub direction: SwipeDirection,
+
+    /// The modifiers that were held down during the swipe.
+    pub modifiers: Modifiers,
+}
+
+impl Sealed for SwipeEvent {}
+impl InputEvent for SwipeEvent {
+    fn to_platform_input(self) -> PlatformInput {
+        PlatformInput::Swipe(self)
+    }
+}
+impl MouseEvent for SwipeEvent {}
+
+/// The direction of a swipe gesture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    /// The fingers moved to the left.
+    #[default]
+    Left,
+    /// The fingers moved to the right.
+    Right,
+    /// The fingers moved up.
+    Up,
+    /// The fingers moved down.
+    Down,
+}
+
 /// The scroll delta for a scroll wheel event.
 #[derive(Clone, Copy, Debug)]
 pub enum ScrollDelta {
@@ -335,6 +371,11 @@ impl Deref for MouseExitEvent {
 pub struct ExternalPaths(pub(crate) SmallVec<[PathBuf; 2]>);
 
 impl ExternalPaths {
+    /// Create a collection of paths, as the platform does for files dragged onto a window.
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self(paths.into_iter().collect())
+    }
+
     /// Convert this collection of paths into a slice.
     pub fn paths(&self) -> &[PathBuf] {
         &self.0
@@ -347,7 +388,29 @@ impl Render for ExternalPaths {
     }
 }
 
-/// A file drop event from the platform, generated when files are dragged and dropped onto the window.
+/// Text from the platform, such as a selection dragged out of another application.
+#[derive(Debug, Clone, Default)]
+pub struct ExternalText(pub(crate) SharedString);
+
+impl ExternalText {
+    /// Create dragged text, as the platform does for text dragged onto a window.
+    pub fn new(text: impl Into<SharedString>) -> Self {
+        Self(text.into())
+    }
+
+    /// The text being dragged.
+    pub fn text(&self) -> &SharedString {
+        &self.0
+    }
+}
+
+impl Render for ExternalText {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        // Intentionally left empty because the platform will render a preview of the dragged text
+    }
+}
+
+/// A file drop event from the platform, generated when files or text are dragged and dropped onto the window.
 #[derive(Debug, Clone)]
 pub enum FileDropEvent {
     /// The files have entered the window.
@@ -357,6 +420,13 @@ pub enum FileDropEvent {
         /// The paths of the files that are being dragged.
         paths: ExternalPaths,
     },
+    /// Text has entered the window.
+    TextEntered {
+        /// The position of the mouse relative to the window.
+        position: Point<Pixels>,
+        /// The text that is being dragged.
+        text: ExternalText,
+    },
     /// The files are being dragged over the window
     Pending {
         /// The position of the mouse relative to the window.
@@ -398,6 +468,8 @@ pub enum PlatformInput {
     MouseExited(MouseExitEvent),
     /// The scroll wheel was used.
     ScrollWheel(ScrollWheelEvent),
+    /// A swipe gesture was made.
+    Swipe(SwipeEvent),
     /// Files were dragged and dropped onto the window.
     FileDrop(FileDropEvent),
 }
@@ -413,6 +485,7 @@ impl PlatformInput {
             PlatformInput::MouseMove(event) => Some(event),
             PlatformInput::MouseExited(event) => Some(event),
             PlatformInput::ScrollWheel(event) => Some(event),
+            PlatformInput::Swipe(event) => Some(event),
             PlatformInput::FileDrop(event) => Some(event),
         }
     }
@@ -427,6 +500,7 @@ impl PlatformInput {
             PlatformInput::MouseMove(_) => None,
             PlatformInput::MouseExited(_) => None,
             PlatformInput::ScrollWheel(_) => None,
+            PlatformInput::Swipe(_) => None,
             PlatformInput::FileDrop(_) => None,
         }
     }
@@ -92,6 +92,9 @@ pub(crate) struct DispatchNode {
     pub key_listeners: Vec<KeyListener>,
     pub action_listeners: Vec<DispatchActionListener>,
     pub context: Option<KeyContext>,
+    /// Whether key bindings are ignored while focus is within this node, see
+    /// [`crate::ElementContext::block_key_bindings`].
+    pub blocks_key_bindings: bool,
     focus_id: Option<FocusId>,
     view_id: Option<EntityId>,
     parent: Option<DispatchNodeId>,
@@ -173,6 +176,18 @@ impl DispatchTree {
         let target = self.active_node();
         target.key_listeners = mem::take(&mut source.key_listeners);
         target.action_listeners = mem::take(&mut source.action_listeners);
+        target.blocks_key_bindings = source.blocks_key_bindings;
+    }
+
+    pub fn block_key_bindings(&mut self) {
+        self.active_node().blocks_key_bindings = true;
+    }
+
+    /// Whether any node on the given dispatch path blocks key bindings.
+    pub fn blocks_key_bindings(&self, dispatch_path: &[DispatchNodeId]) -> bool {
+        dispatch_path
+            .iter()
+            .any(|node_id| self.node(*node_id).blocks_key_bindings)
     }
 
     pub fn reuse_view(&mut self, view_id: EntityId, source: &mut Self) -> SmallVec<[EntityId; 8]> {
@@ -282,21 +297,27 @@ impl DispatchTree {
     }
 
     pub fn available_actions(&self, target: DispatchNodeId) -> Vec<Box<dyn Action>> {
-        let mut actions = Vec::<Box<dyn Action>>::new();
+        let mut action_types = Vec::<TypeId>::new();
         for node_id in self.dispatch_path(target) {
             let node = &self.nodes[node_id.0];
             for DispatchActionListener { action_type, .. } in &node.action_listeners {
-                if let Err(ix) = actions.binary_search_by_key(action_type, |a| a.as_any().type_id())
-                {
-                    // Intentionally silence these errors without logging.
-                    // If an action cannot be built by default, it's not available.
-                    let action = self.action_registry.build_action_type(action_type).ok();
-                    if let Some(action) = action {
-                        actions.insert(ix, action);
-                    }
+                if let Err(ix) = action_types.binary_search(action_type) {
+                    action_types.insert(ix, *action_type);
                 }
             }
         }
+
+        let mut actions = Vec::<Box<dyn Action>>::new();
+        for action_type in action_types {
+            if let Some(variants) = self.action_registry.action_variants(&action_type) {
+                actions.extend(variants.iter().map(|(_, action)| action.boxed_clone()));
+            } else {
+                // Intentionally silence these errors without logging.
+                // If an action cannot be built by default, it's not available.
+                let action = self.action_registry.build_action_type(&action_type).ok();
+                actions.extend(action);
+            }
+        }
         actions
     }
 
@@ -6,7 +6,7 @@ pub use binding::*;
 pub use context::*;
 pub(crate) use matcher::*;
 
-use crate::{Action, Keystroke, NoAction};
+use crate::{Action, Keystroke, NoAction, SharedString, Unbind};
 use collections::HashSet;
 use smallvec::SmallVec;
 use std::{
@@ -26,6 +26,12 @@ pub struct Keymap {
     binding_indices_by_action_id: HashMap<TypeId, SmallVec<[usize; 3]>>,
     disabled_keystrokes:
         HashMap<SmallVec<[Keystroke; 2]>, HashSet<Option<KeyBindingContextPredicate>>>,
+    /// The actions unbound from each keystroke sequence with [`Unbind`], in the contexts
+    /// they're unbound in.
+    unbound_actions: HashMap<
+        SmallVec<[Keystroke; 2]>,
+        HashSet<(Option<KeyBindingContextPredicate>, SharedString)>,
+    >,
     version: KeymapVersion,
 }
 
@@ -53,6 +59,12 @@ impl Keymap {
                     .entry(binding.keystrokes)
                     .or_default()
                     .insert(binding.context_predicate);
+            } else if let Some(Unbind(action_name)) = binding.action().as_any().downcast_ref() {
+                let action_name = action_name.clone();
+                self.unbound_actions
+                    .entry(binding.keystrokes)
+                    .or_default()
+                    .insert((binding.context_predicate, action_name));
             } else {
                 self.binding_indices_by_action_id
                     .entry(action_id)
@@ -70,6 +82,7 @@ impl Keymap {
         self.bindings.clear();
         self.binding_indices_by_action_id.clear();
         self.disabled_keystrokes.clear();
+        self.unbound_actions.clear();
         self.version.0 += 1;
     }
 
@@ -117,6 +130,19 @@ impl Keymap {
             }
         }
 
+        if let Some(unbound_actions) = self.unbound_actions.get(&binding.keystrokes) {
+            let action_name = binding.action().name();
+            for (predicate, unbound_action) in unbound_actions {
+                if unbound_action.as_ref() == action_name
+                    && predicate
+                        .as_ref()
+                        .map_or(true, |predicate| predicate.eval(context))
+                {
+                    return false;
+                }
+            }
+        }
+
         true
     }
 }
@@ -183,4 +209,29 @@ mod tests {
         // binding is globally disabled
         assert!(!keymap.binding_enabled(&bindings[1], &[KeyContext::parse("barf").unwrap()]));
     }
+
+    #[test]
+    fn test_keymap_unbind() {
+        let bindings = [
+            KeyBinding::new("ctrl-a", ActionAlpha {}, Some("editor")),
+            KeyBinding::new("ctrl-a", ActionBeta {}, Some("editor")),
+            KeyBinding::new("ctrl-b", ActionAlpha {}, None),
+            KeyBinding::new(
+                "ctrl-a",
+                Unbind("keymap_test::ActionAlpha".into()),
+                Some("editor"),
+            ),
+            KeyBinding::new("ctrl-b", Unbind("keymap_test::ActionAlpha".into()), None),
+        ];
+
+        let mut keymap = Keymap::default();
+        keymap.add_bindings(bindings.clone());
+        assert_eq!(keymap.bindings().count(), 3);
+
+        // only the named action is unbound from the keystrokes
+        let editor = [KeyContext::parse("editor").unwrap()];
+        assert!(!keymap.binding_enabled(&bindings[0], &editor));
+        assert!(keymap.binding_enabled(&bindings[1], &editor));
+        assert!(!keymap.binding_enabled(&bindings[2], &[KeyContext::parse("barf").unwrap()]));
+    }
 }
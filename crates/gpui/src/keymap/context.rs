@@ -323,6 +323,41 @@ impl KeyBindingContextPredicate {
     }
 }
 
+impl fmt::Display for KeyBindingContextPredicate {
+    /// Formats the predicate in the syntax accepted by [`KeyBindingContextPredicate::parse`],
+    /// parenthesizing every operand that isn't an identifier or a negation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn operand(predicate: &KeyBindingContextPredicate, f: &mut fmt::Formatter) -> fmt::Result {
+            match predicate {
+                KeyBindingContextPredicate::Identifier(_) | KeyBindingContextPredicate::Not(_) => {
+                    write!(f, "{predicate}")
+                }
+                _ => write!(f, "({predicate})"),
+            }
+        }
+
+        match self {
+            Self::Identifier(name) => write!(f, "{name}"),
+            Self::Equal(left, right) => write!(f, "{left} == {right}"),
+            Self::NotEqual(left, right) => write!(f, "{left} != {right}"),
+            Self::Not(predicate) => {
+                write!(f, "!")?;
+                operand(predicate, f)
+            }
+            Self::Child(left, right) | Self::And(left, right) | Self::Or(left, right) => {
+                let operator = match self {
+                    Self::Child(_, _) => ">",
+                    Self::And(_, _) => "&&",
+                    _ => "||",
+                };
+                operand(left, f)?;
+                write!(f, " {operator} ")?;
+                operand(right, f)
+            }
+        }
+    }
+}
+
 const PRECEDENCE_CHILD: u32 = 1;
 const PRECEDENCE_OR: u32 = 2;
 const PRECEDENCE_AND: u32 = 3;
@@ -505,4 +540,26 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_display_round_trips() {
+        for source in [
+            "Editor",
+            "mode == full",
+            "!(a || b) && c != d",
+            "Workspace > (Pane && !(extension == md))",
+        ] {
+            let predicate = KeyBindingContextPredicate::parse(source).unwrap();
+            assert_eq!(
+                KeyBindingContextPredicate::parse(&predicate.to_string()).unwrap(),
+                predicate
+            );
+        }
+        assert_eq!(
+            KeyBindingContextPredicate::parse("a && b || !c")
+                .unwrap()
+                .to_string(),
+            "(a && b) || !c"
+        );
+    }
 }
@@ -501,6 +501,10 @@ pub struct WindowOptions {
 
     /// The display to create the window on
     pub display_id: Option<DisplayId>,
+
+    /// Windows sharing an identifier can be merged into the tabs of a single native
+    /// window, on platforms that support it. `None` keeps the window out of tabs.
+    pub tabbing_identifier: Option<SharedString>,
 }
 
 impl Default for WindowOptions {
@@ -518,6 +522,7 @@ impl Default for WindowOptions {
             kind: WindowKind::Normal,
             is_movable: true,
             display_id: None,
+            tabbing_identifier: None,
         }
     }
 }
@@ -544,6 +549,10 @@ pub enum WindowKind {
     /// A window that appears above all other windows, usually used for alerts or popups
     /// use sparingly!
     PopUp,
+
+    /// A window that floats above the normal windows of the application, even when
+    /// they're fullscreen, such as a tool window
+    Floating,
 }
 
 /// Which bounds algorithm to use for the initial size a window
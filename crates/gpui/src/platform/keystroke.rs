@@ -108,6 +108,25 @@ impl Keystroke {
             ime_key,
         })
     }
+
+    /// Produces the keystroke in the syntax accepted by [`Keystroke::parse`], as used
+    /// in keymap files, without the `ime_key`.
+    pub fn unparse(&self) -> String {
+        let mut result = String::new();
+        for (pressed, modifier) in [
+            (self.modifiers.control, "ctrl-"),
+            (self.modifiers.alt, "alt-"),
+            (self.modifiers.shift, "shift-"),
+            (self.modifiers.command, "cmd-"),
+            (self.modifiers.function, "fn-"),
+        ] {
+            if pressed {
+                result.push_str(modifier);
+            }
+        }
+        result.push_str(&self.key);
+        result
+    }
 }
 
 impl std::fmt::Display for Keystroke {
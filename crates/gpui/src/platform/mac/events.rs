@@ -1,7 +1,7 @@
 use crate::{
     point, px, KeyDownEvent, KeyUpEvent, Keystroke, Modifiers, ModifiersChangedEvent, MouseButton,
     MouseDownEvent, MouseExitEvent, MouseMoveEvent, MouseUpEvent, NavigationDirection, Pixels,
-    PlatformInput, ScrollDelta, ScrollWheelEvent, TouchPhase,
+    PlatformInput, ScrollDelta, ScrollWheelEvent, SwipeDirection, SwipeEvent, TouchPhase,
 };
 use cocoa::{
     appkit::{NSEvent, NSEventModifierFlags, NSEventPhase, NSEventType},
@@ -189,6 +189,25 @@ impl PlatformInput {
                     modifiers: read_modifiers(native_event),
                 })
             }),
+            NSEventType::NSEventTypeSwipe => window_height.and_then(|window_height| {
+                // AppKit reports a delta of 1.0 for swipes to the left or up, and -1.0
+                // for swipes to the right or down.
+                let direction = match (native_event.deltaX(), native_event.deltaY()) {
+                    (dx, _) if dx > 0. => SwipeDirection::Left,
+                    (dx, _) if dx < 0. => SwipeDirection::Right,
+                    (_, dy) if dy > 0. => SwipeDirection::Up,
+                    (_, dy) if dy < 0. => SwipeDirection::Down,
+                    _ => return None,
+                };
+                Some(Self::Swipe(SwipeEvent {
+                    position: point(
+                        px(native_event.locationInWindow().x as f32),
+                        window_height - px(native_event.locationInWindow().y as f32),
+                    ),
+                    direction,
+                    modifiers: read_modifiers(native_event),
+                }))
+            }),
             NSEventType::NSLeftMouseDragged
             | NSEventType::NSRightMouseDragged
             | NSEventType::NSOtherMouseDragged => {
@@ -1,18 +1,19 @@
 use super::{global_bounds_from_ns_rect, ns_string, MacDisplay, MetalRenderer, NSRange};
 use crate::{
     global_bounds_to_ns_rect, platform::PlatformInputHandler, point, px, size, AnyWindowHandle,
-    Bounds, ExternalPaths, FileDropEvent, ForegroundExecutor, GlobalPixels, KeyDownEvent,
-    Keystroke, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformWindow, Point,
-    PromptLevel, Size, Timer, WindowAppearance, WindowBounds, WindowKind, WindowOptions,
+    Bounds, ExternalPaths, ExternalText, FileDropEvent, ForegroundExecutor, GlobalPixels,
+    KeyDownEvent, Keystroke, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformWindow, Point, PromptLevel, Size, Timer, WindowAppearance, WindowBounds, WindowKind,
+    WindowOptions,
 };
 use block::ConcreteBlock;
 use cocoa::{
     appkit::{
         CGPoint, NSApplication, NSBackingStoreBuffered, NSEventModifierFlags,
-        NSFilenamesPboardType, NSPasteboard, NSScreen, NSView, NSViewHeightSizable,
-        NSViewWidthSizable, NSWindow, NSWindowButton, NSWindowCollectionBehavior,
-        NSWindowStyleMask, NSWindowTitleVisibility,
+        NSFilenamesPboardType, NSPasteboard, NSPasteboardTypeString, NSScreen, NSView,
+        NSViewHeightSizable, NSViewWidthSizable, NSWindow, NSWindowButton,
+        NSWindowCollectionBehavior, NSWindowStyleMask, NSWindowTitleVisibility,
     },
     base::{id, nil},
     foundation::{
@@ -63,6 +64,8 @@ const NSWindowStyleMaskNonactivatingPanel: NSWindowStyleMask =
 #[allow(non_upper_case_globals)]
 const NSNormalWindowLevel: NSInteger = 0;
 #[allow(non_upper_case_globals)]
+const NSFloatingWindowLevel: NSInteger = 3;
+#[allow(non_upper_case_globals)]
 const NSPopUpWindowLevel: NSInteger = 101;
 #[allow(non_upper_case_globals)]
 const NSTrackingMouseEnteredAndExited: NSUInteger = 0x01;
@@ -76,6 +79,11 @@ const NSTrackingInVisibleRect: NSUInteger = 0x200;
 const NSWindowAnimationBehaviorUtilityWindow: NSInteger = 4;
 #[allow(non_upper_case_globals)]
 const NSViewLayerContentsRedrawDuringViewResize: NSInteger = 2;
+// https://developer.apple.com/documentation/appkit/nswindowtabbingmode
+#[allow(non_upper_case_globals)]
+const NSWindowTabbingModeAutomatic: NSInteger = 0;
+#[allow(non_upper_case_globals)]
+const NSWindowTabbingModeDisallowed: NSInteger = 2;
 // https://developer.apple.com/documentation/appkit/nsdragoperation
 type NSDragOperation = NSUInteger;
 #[allow(non_upper_case_globals)]
@@ -141,6 +149,10 @@ unsafe fn build_classes() {
             sel!(scrollWheel:),
             handle_view_event as extern "C" fn(&Object, Sel, id),
         );
+        decl.add_method(
+            sel!(swipeWithEvent:),
+            handle_view_event as extern "C" fn(&Object, Sel, id),
+        );
         decl.add_method(
             sel!(flagsChanged:),
             handle_view_event as extern "C" fn(&Object, Sel, id),
@@ -478,7 +490,7 @@ impl MacWindow {
             }
 
             let native_window: id = match options.kind {
-                WindowKind::Normal => msg_send![WINDOW_CLASS, alloc],
+                WindowKind::Normal | WindowKind::Floating => msg_send![WINDOW_CLASS, alloc],
                 WindowKind::PopUp => {
                     style_mask |= NSWindowStyleMaskNonactivatingPanel;
                     msg_send![PANEL_CLASS, alloc]
@@ -515,8 +527,10 @@ impl MacWindow {
             assert!(!native_window.is_null());
             let () = msg_send![
                 native_window,
-                registerForDraggedTypes:
-                    NSArray::arrayWithObject(nil, NSFilenamesPboardType)
+                registerForDraggedTypes: NSArray::arrayWithObjects(
+                    nil,
+                    &[NSFilenamesPboardType, NSPasteboardTypeString]
+                )
             ];
 
             let native_view: id = msg_send![VIEW_CLASS, alloc];
@@ -604,6 +618,19 @@ impl MacWindow {
                 native_window.center();
             }
 
+            match &options.tabbing_identifier {
+                Some(tabbing_identifier) if options.kind == WindowKind::Normal => {
+                    let tabbing_identifier = NSString::alloc(nil).init_str(tabbing_identifier);
+                    let _: () = msg_send![native_window, setTabbingIdentifier: tabbing_identifier];
+                    let _: () =
+                        msg_send![native_window, setTabbingMode: NSWindowTabbingModeAutomatic];
+                }
+                _ => {
+                    let _: () =
+                        msg_send![native_window, setTabbingMode: NSWindowTabbingModeDisallowed];
+                }
+            }
+
             match options.kind {
                 WindowKind::Normal => {
                     native_window.setLevel_(NSNormalWindowLevel);
@@ -634,6 +661,14 @@ impl MacWindow {
                         NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary
                     );
                 }
+                WindowKind::Floating => {
+                    native_window.setLevel_(NSFloatingWindowLevel);
+                    native_window.setAcceptsMouseMovedEvents_(YES);
+                    native_window.setCollectionBehavior_(
+                        NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces |
+                        NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary
+                    );
+                }
             }
             if options.focus {
                 native_window.makeKeyAndOrderFront_(nil);
@@ -1689,7 +1724,7 @@ extern "C" fn accepts_first_mouse(this: &Object, _: Sel, _: id) -> BOOL {
     unsafe {
         let state = get_window_state(this);
         let lock = state.as_ref().lock();
-        if lock.kind == WindowKind::PopUp {
+        if matches!(lock.kind, WindowKind::PopUp | WindowKind::Floating) {
             YES
         } else {
             NO
@@ -1702,7 +1737,12 @@ extern "C" fn dragging_entered(this: &Object, _: Sel, dragging_info: id) -> NSDr
     if send_new_event(&window_state, {
         let position = drag_event_position(&window_state, dragging_info);
         let paths = external_paths_from_event(dragging_info);
-        PlatformInput::FileDrop(FileDropEvent::Entered { position, paths })
+        match external_text_from_event(dragging_info) {
+            Some(text) if paths.paths().is_empty() => {
+                PlatformInput::FileDrop(FileDropEvent::TextEntered { position, text })
+            }
+            _ => PlatformInput::FileDrop(FileDropEvent::Entered { position, paths }),
+        }
     }) {
         window_state.lock().external_files_dragged = true;
         NSDragOperationCopy
@@ -1750,6 +1790,9 @@ fn external_paths_from_event(dragging_info: *mut Object) -> ExternalPaths {
     let mut paths = SmallVec::new();
     let pasteboard: id = unsafe { msg_send![dragging_info, draggingPasteboard] };
     let filenames = unsafe { NSPasteboard::propertyListForType(pasteboard, NSFilenamesPboardType) };
+    if filenames == nil {
+        return ExternalPaths(paths);
+    }
     for file in unsafe { filenames.iter() } {
         let path = unsafe {
             let f = NSString::UTF8String(file);
@@ -1760,6 +1803,16 @@ fn external_paths_from_event(dragging_info: *mut Object) -> ExternalPaths {
     ExternalPaths(paths)
 }
 
+fn external_text_from_event(dragging_info: *mut Object) -> Option<ExternalText> {
+    let pasteboard: id = unsafe { msg_send![dragging_info, draggingPasteboard] };
+    let text = unsafe { NSPasteboard::stringForType(pasteboard, NSPasteboardTypeString) };
+    if text == nil {
+        return None;
+    }
+    let text = unsafe { CStr::from_ptr(NSString::UTF8String(text)).to_string_lossy() };
+    Some(ExternalText(text.into_owned().into()))
+}
+
 extern "C" fn conclude_drag_operation(this: &Object, _: Sel, _: id) {
     let window_state = unsafe { get_window_state(this) };
     send_new_event(
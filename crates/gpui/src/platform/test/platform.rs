@@ -8,7 +8,7 @@ use collections::VecDeque;
 use futures::channel::oneshot;
 use parking_lot::Mutex;
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     path::PathBuf,
     rc::{Rc, Weak},
     sync::Arc,
@@ -25,6 +25,7 @@ pub(crate) struct TestPlatform {
     active_cursor: Mutex<CursorStyle>,
     current_clipboard_item: Mutex<Option<ClipboardItem>>,
     pub(crate) prompts: RefCell<TestPrompts>,
+    did_quit: Cell<bool>,
     weak: Weak<Self>,
 }
 
@@ -40,6 +41,7 @@ impl TestPlatform {
             background_executor: executor,
             foreground_executor,
             prompts: Default::default(),
+            did_quit: Default::default(),
             active_cursor: Default::default(),
             active_display: Rc::new(TestDisplay::new()),
             active_window: Default::default(),
@@ -106,6 +108,10 @@ impl TestPlatform {
     pub(crate) fn did_prompt_for_new_path(&self) -> bool {
         self.prompts.borrow().new_path.len() > 0
     }
+
+    pub(crate) fn did_quit(&self) -> bool {
+        self.did_quit.get()
+    }
 }
 
 impl Platform for TestPlatform {
@@ -125,7 +131,9 @@ impl Platform for TestPlatform {
         unimplemented!()
     }
 
-    fn quit(&self) {}
+    fn quit(&self) {
+        self.did_quit.set(true);
+    }
 
     fn restart(&self) {
         unimplemented!()
@@ -276,6 +276,7 @@ pub struct Window {
     pub(crate) focus: Option<FocusId>,
     focus_enabled: bool,
     pending_input: Option<PendingInput>,
+    pending_input_observers: SubscriberSet<(), AnyObserver>,
 
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) focus_invalidated: bool,
@@ -418,6 +419,7 @@ impl Window {
             focus: None,
             focus_enabled: true,
             pending_input: None,
+            pending_input_observers: SubscriberSet::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             focus_invalidated: false,
@@ -1116,8 +1118,13 @@ impl<'a> WindowContext<'a> {
                 self.window.modifiers = scroll_wheel.modifiers;
                 PlatformInput::ScrollWheel(scroll_wheel)
             }
-            // Translate dragging and dropping of external files from the operating system
-            // to internal drag and drop events.
+            PlatformInput::Swipe(swipe) => {
+                self.window.mouse_position = swipe.position;
+                self.window.modifiers = swipe.modifiers;
+                PlatformInput::Swipe(swipe)
+            }
+            // Translate dragging and dropping of external files and text from the operating
+            // system to internal drag and drop events.
             PlatformInput::FileDrop(file_drop) => match file_drop {
                 FileDropEvent::Entered { position, paths } => {
                     self.window.mouse_position = position;
@@ -1134,6 +1141,21 @@ impl<'a> WindowContext<'a> {
                         modifiers: Modifiers::default(),
                     })
                 }
+                FileDropEvent::TextEntered { position, text } => {
+                    self.window.mouse_position = position;
+                    if self.active_drag.is_none() {
+                        self.active_drag = Some(AnyDrag {
+                            value: Box::new(text.clone()),
+                            view: self.new_view(|_| text).into(),
+                            cursor_offset: position,
+                        });
+                    }
+                    PlatformInput::MouseMove(MouseMoveEvent {
+                        position,
+                        pressed_button: Some(MouseButton::Left),
+                        modifiers: Modifiers::default(),
+                    })
+                }
                 FileDropEvent::Pending { position } => {
                     self.window.mouse_position = position;
                     PlatformInput::MouseMove(MouseMoveEvent {
@@ -1242,7 +1264,15 @@ impl<'a> WindowContext<'a> {
             .dispatch_tree
             .dispatch_path(node_id);
 
-        if let Some(key_down_event) = event.downcast_ref::<KeyDownEvent>() {
+        let key_bindings_blocked = self
+            .window
+            .rendered_frame
+            .dispatch_tree
+            .blocks_key_bindings(&dispatch_path);
+        if let Some(key_down_event) = event
+            .downcast_ref::<KeyDownEvent>()
+            .filter(|_| !key_bindings_blocked)
+        {
             let KeymatchResult {
                 bindings,
                 mut pending,
@@ -1281,6 +1311,7 @@ impl<'a> WindowContext<'a> {
                             let Some(currently_pending) = cx.window.pending_input.take() else {
                                 return;
                             };
+                            cx.pending_input_changed();
                             cx.replay_pending_input(currently_pending)
                         })
                         .log_err();
@@ -1289,10 +1320,12 @@ impl<'a> WindowContext<'a> {
                     currently_pending.timer = None;
                 }
                 self.window.pending_input = Some(currently_pending);
+                self.pending_input_changed();
 
                 self.propagate_event = false;
                 return;
             } else if let Some(currently_pending) = self.window.pending_input.take() {
+                self.pending_input_changed();
                 if bindings
                     .iter()
                     .all(|binding| !currently_pending.used_by_binding(binding))
@@ -1354,6 +1387,21 @@ impl<'a> WindowContext<'a> {
             .has_pending_keystrokes()
     }
 
+    /// Returns the keystrokes typed so far towards a multi-stroke key binding, if any.
+    pub fn pending_input_keystrokes(&self) -> Option<&[Keystroke]> {
+        self.window
+            .pending_input
+            .as_ref()
+            .map(|pending_input| pending_input.keystrokes.as_slice())
+    }
+
+    fn pending_input_changed(&mut self) {
+        self.window
+            .pending_input_observers
+            .clone()
+            .retain(&(), |callback| callback(self));
+    }
+
     fn replay_pending_input(&mut self, currently_pending: PendingInput) {
         let node_id = self
             .window
@@ -2044,6 +2092,21 @@ impl<'a, V: 'static> ViewContext<'a, V> {
         subscription
     }
 
+    /// Register a callback to be invoked when keystrokes are added to or cleared from
+    /// a pending multi-stroke key binding.
+    pub fn observe_pending_input(
+        &mut self,
+        mut callback: impl FnMut(&mut V, &mut ViewContext<V>) + 'static,
+    ) -> Subscription {
+        let view = self.view.downgrade();
+        let (subscription, activate) = self.window.pending_input_observers.insert(
+            (),
+            Box::new(move |cx| view.update(cx, |view, cx| callback(view, cx)).is_ok()),
+        );
+        activate();
+        subscription
+    }
+
     /// Register a callback to be invoked when the window is activated or deactivated.
     pub fn observe_window_activation(
         &mut self,
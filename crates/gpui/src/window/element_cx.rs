@@ -1177,6 +1177,13 @@ impl<'a> ElementContext<'a> {
             ))
     }
 
+    /// Prevents key bindings from matching while focus is within the current dispatch node,
+    /// so that key down events go straight to the key listeners, for example to record the
+    /// keystrokes of a new binding.
+    pub fn block_key_bindings(&mut self) {
+        self.window.next_frame.dispatch_tree.block_key_bindings();
+    }
+
     /// Register a key event listener on the window for the next frame. The type of event
     /// is determined by the first parameter of the given listener. When the next frame is rendered
     /// the listener will be cleared.
@@ -14,6 +14,14 @@ pub fn register_action(ident: TokenStream) -> TokenStream {
     register_action::register_action_macro(ident)
 }
 
+#[proc_macro]
+/// register_action_variants! lists the variants of a parameterized action with the
+/// GPUI runtime, so that it can be offered in UI like the command palette.
+/// The type must implement `gpui::ActionVariants`.
+pub fn register_action_variants(ident: TokenStream) -> TokenStream {
+    register_action::register_action_variants_macro(ident)
+}
+
 #[proc_macro_derive(IntoElement)]
 // #[derive(IntoElement)] is used to create a Component out of anything that implements
 // the `RenderOnce` trait.
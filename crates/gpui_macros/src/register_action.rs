@@ -12,6 +12,15 @@ pub fn register_action_macro(ident: TokenStream) -> TokenStream {
     })
 }
 
+pub fn register_action_variants_macro(ident: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(ident as Ident);
+    let registration = register_action_variants(&name);
+
+    TokenStream::from(quote! {
+        #registration
+    })
+}
+
 pub(crate) fn register_action(type_name: &Ident) -> proc_macro2::TokenStream {
     let static_slice_name =
         format_ident!("__GPUI_ACTIONS_{}", type_name.to_string().to_uppercase());
@@ -38,3 +47,31 @@ pub(crate) fn register_action(type_name: &Ident) -> proc_macro2::TokenStream {
         }
     }
 }
+
+pub(crate) fn register_action_variants(type_name: &Ident) -> proc_macro2::TokenStream {
+    let static_slice_name = format_ident!(
+        "__GPUI_ACTION_VARIANTS_{}",
+        type_name.to_string().to_uppercase()
+    );
+
+    let variants_builder_fn_name = format_ident!(
+        "__gpui_action_variants_builder_{}",
+        type_name.to_string().to_lowercase()
+    );
+
+    quote! {
+        #[doc(hidden)]
+        #[gpui::private::linkme::distributed_slice(gpui::__GPUI_ACTION_VARIANTS)]
+        #[linkme(crate = gpui::private::linkme)]
+        static #static_slice_name: gpui::MacroActionVariantsBuilder = #variants_builder_fn_name;
+
+        /// This is an auto generated function, do not use.
+        #[doc(hidden)]
+        fn #variants_builder_fn_name() -> gpui::ActionVariantsData {
+            gpui::ActionVariantsData {
+                type_id: ::std::any::TypeId::of::<#type_name>(),
+                variants: gpui::__boxed_action_variants::<#type_name>,
+            }
+        }
+    }
+}
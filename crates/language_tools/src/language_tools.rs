@@ -1,4 +1,5 @@
 mod lsp_log;
+pub mod lsp_log_panel;
 mod syntax_tree_view;
 
 #[cfg(test)]
@@ -11,5 +12,6 @@ pub use syntax_tree_view::{SyntaxTreeToolbarItemView, SyntaxTreeView};
 
 pub fn init(cx: &mut AppContext) {
     lsp_log::init(cx);
+    lsp_log_panel::init(cx);
     syntax_tree_view::init(cx);
 }
@@ -2,9 +2,9 @@ use collections::{HashMap, VecDeque};
 use editor::{actions::MoveToEnd, Editor, EditorEvent};
 use futures::{channel::mpsc, StreamExt};
 use gpui::{
-    actions, div, AnchorCorner, AnyElement, AppContext, Context, EventEmitter, FocusHandle,
-    FocusableView, IntoElement, Model, ModelContext, ParentElement, Render, Styled, Subscription,
-    View, ViewContext, VisualContext, WeakModel, WindowContext,
+    actions, div, AnchorCorner, AnyElement, AppContext, ClipboardItem, Context, EventEmitter,
+    FocusHandle, FocusableView, Global, IntoElement, Model, ModelContext, ParentElement, Render,
+    Styled, Subscription, View, ViewContext, VisualContext, WeakModel, WindowContext,
 };
 use language::{LanguageServerId, LanguageServerName};
 use lsp::IoKind;
@@ -34,6 +34,7 @@ struct ProjectState {
 
 struct LanguageServerState {
     log_messages: VecDeque<String>,
+    stderr_messages: VecDeque<String>,
     rpc_state: Option<LanguageServerRpcState>,
     _io_logs_subscription: Option<lsp::Subscription>,
     _lsp_logs_subscription: Option<lsp::Subscription>,
@@ -49,7 +50,7 @@ pub struct LspLogView {
     editor_subscriptions: Vec<Subscription>,
     log_store: Model<LogStore>,
     current_server_id: Option<LanguageServerId>,
-    is_showing_rpc_trace: bool,
+    active_log_kind: LogKind,
     project: Model<Project>,
     focus_handle: FocusHandle,
     _log_store_subscriptions: Vec<Subscription>,
@@ -60,6 +61,17 @@ pub struct LspLogToolbarItemView {
     _log_view_subscription: Option<Subscription>,
 }
 
+/// The output channels kept for each language server.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogKind {
+    /// Messages the server sent with `window/logMessage`.
+    Logs,
+    /// The JSON-RPC traffic between Zed and the server, when tracing is enabled.
+    Rpc,
+    /// Whatever the server process wrote to its standard error.
+    Stderr,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum MessageKind {
     Send,
@@ -74,12 +86,19 @@ pub(crate) struct LogMenuItem {
     pub rpc_trace_enabled: bool,
     pub rpc_trace_selected: bool,
     pub logs_selected: bool,
+    pub stderr_selected: bool,
 }
 
-actions!(debug, [OpenLanguageServerLogs]);
+actions!(debug, [OpenLanguageServerLogs, CopyLanguageServerLogs]);
+
+/// The log store shared by every workspace, for views created outside of [`init`].
+pub(crate) struct GlobalLogStore(pub Model<LogStore>);
+
+impl Global for GlobalLogStore {}
 
 pub fn init(cx: &mut AppContext) {
     let log_store = cx.new_model(|cx| LogStore::new(cx));
+    cx.set_global(GlobalLogStore(log_store.clone()));
 
     cx.observe_new_views(move |workspace: &mut Workspace, cx| {
         let project = workspace.project();
@@ -165,6 +184,7 @@ impl LogStore {
             LanguageServerState {
                 rpc_state: None,
                 log_messages: VecDeque::with_capacity(MAX_STORED_LOG_ENTRIES),
+                stderr_messages: VecDeque::with_capacity(MAX_STORED_LOG_ENTRIES),
                 _io_logs_subscription: None,
                 _lsp_logs_subscription: None,
             }
@@ -211,6 +231,27 @@ impl LogStore {
         id: LanguageServerId,
         message: &str,
         cx: &mut ModelContext<Self>,
+    ) -> Option<()> {
+        self.push_log_line(project, id, LogKind::Logs, message, cx)
+    }
+
+    fn add_language_server_stderr(
+        &mut self,
+        project: &Model<Project>,
+        id: LanguageServerId,
+        message: &str,
+        cx: &mut ModelContext<Self>,
+    ) -> Option<()> {
+        self.push_log_line(project, id, LogKind::Stderr, message, cx)
+    }
+
+    fn push_log_line(
+        &mut self,
+        project: &Model<Project>,
+        id: LanguageServerId,
+        kind: LogKind,
+        message: &str,
+        cx: &mut ModelContext<Self>,
     ) -> Option<()> {
         let language_server_state = match self
             .projects
@@ -222,7 +263,11 @@ impl LogStore {
             None => self.add_language_server(&project, id, cx)?,
         };
 
-        let log_lines = &mut language_server_state.log_messages;
+        let log_lines = match kind {
+            LogKind::Logs => &mut language_server_state.log_messages,
+            LogKind::Stderr => &mut language_server_state.stderr_messages,
+            LogKind::Rpc => &mut language_server_state.rpc_state.as_mut()?.rpc_messages,
+        };
         while log_lines.len() >= MAX_STORED_LOG_ENTRIES {
             log_lines.pop_front();
         }
@@ -231,7 +276,7 @@ impl LogStore {
         cx.emit(Event::NewServerLogEntry {
             id,
             entry: message.to_string(),
-            is_rpc: false,
+            kind,
         });
         cx.notify();
         Some(())
@@ -260,6 +305,17 @@ impl LogStore {
         Some(&server_state.log_messages)
     }
 
+    fn server_stderr(
+        &self,
+        project: &Model<Project>,
+        server_id: LanguageServerId,
+    ) -> Option<&VecDeque<String>> {
+        let weak_project = project.downgrade();
+        let project_state = self.projects.get(&weak_project)?;
+        let server_state = project_state.servers.get(&server_id)?;
+        Some(&server_state.stderr_messages)
+    }
+
     fn enable_rpc_trace_for_language_server(
         &mut self,
         project: &Model<Project>,
@@ -290,7 +346,7 @@ impl LogStore {
         Some(())
     }
 
-    fn on_io(
+    pub(crate) fn on_io(
         &mut self,
         project: WeakModel<Project>,
         language_server_id: LanguageServerId,
@@ -303,8 +359,7 @@ impl LogStore {
             IoKind::StdIn => false,
             IoKind::StdErr => {
                 let project = project.upgrade()?;
-                let message = format!("stderr: {}", message.trim());
-                self.add_language_server_log(&project, language_server_id, &message, cx);
+                self.add_language_server_stderr(&project, language_server_id, message, cx);
                 return Some(());
             }
         };
@@ -332,7 +387,7 @@ impl LogStore {
             cx.emit(Event::NewServerLogEntry {
                 id: language_server_id,
                 entry: line_before_message.to_string(),
-                is_rpc: true,
+                kind: LogKind::Rpc,
             });
         }
 
@@ -344,7 +399,7 @@ impl LogStore {
         cx.emit(Event::NewServerLogEntry {
             id: language_server_id,
             entry: message.to_string(),
-            is_rpc: true,
+            kind: LogKind::Rpc,
         });
         cx.notify();
         Some(())
@@ -368,11 +423,7 @@ impl LspLogView {
                 if let Some(current_lsp) = this.current_server_id {
                     if !project_state.servers.contains_key(&current_lsp) {
                         if let Some(server) = project_state.servers.iter().next() {
-                            if this.is_showing_rpc_trace {
-                                this.show_rpc_trace_for_server(*server.0, cx)
-                            } else {
-                                this.show_logs_for_server(*server.0, cx)
-                            }
+                            this.show_log_kind_for_server(*server.0, this.active_log_kind, cx)
                         } else {
                             this.current_server_id = None;
                             this.editor.update(cx, |editor, cx| {
@@ -385,11 +436,7 @@ impl LspLogView {
                     }
                 } else {
                     if let Some(server) = project_state.servers.iter().next() {
-                        if this.is_showing_rpc_trace {
-                            this.show_rpc_trace_for_server(*server.0, cx)
-                        } else {
-                            this.show_logs_for_server(*server.0, cx)
-                        }
+                        this.show_log_kind_for_server(*server.0, this.active_log_kind, cx)
                     }
                 }
 
@@ -399,11 +446,9 @@ impl LspLogView {
             cx.notify();
         });
         let events_subscriptions = cx.subscribe(&log_store, |log_view, _, e, cx| match e {
-            Event::NewServerLogEntry { id, entry, is_rpc } => {
+            Event::NewServerLogEntry { id, entry, kind } => {
                 if log_view.current_server_id == Some(*id) {
-                    if (*is_rpc && log_view.is_showing_rpc_trace)
-                        || (!*is_rpc && !log_view.is_showing_rpc_trace)
-                    {
+                    if *kind == log_view.active_log_kind {
                         log_view.editor.update(cx, |editor, cx| {
                             editor.set_read_only(false);
                             let last_point = editor.buffer().read(cx).len(cx);
@@ -434,7 +479,7 @@ impl LspLogView {
             project,
             log_store,
             current_server_id: None,
-            is_showing_rpc_trace: false,
+            active_log_kind: LogKind::Logs,
             _log_store_subscriptions: vec![
                 model_changes_subscription,
                 events_subscriptions,
@@ -489,10 +534,9 @@ impl LspLogView {
                     server_name: language_server_name,
                     worktree_root_name: worktree.read(cx).root_name().to_string(),
                     rpc_trace_enabled: state.rpc_state.is_some(),
-                    rpc_trace_selected: self.is_showing_rpc_trace
-                        && self.current_server_id == Some(server_id),
-                    logs_selected: !self.is_showing_rpc_trace
-                        && self.current_server_id == Some(server_id),
+                    rpc_trace_selected: self.is_showing(server_id, LogKind::Rpc),
+                    logs_selected: self.is_showing(server_id, LogKind::Logs),
+                    stderr_selected: self.is_showing(server_id, LogKind::Stderr),
                 })
             })
             .chain(
@@ -506,10 +550,9 @@ impl LspLogView {
                             server_name: name.clone(),
                             worktree_root_name: "supplementary".to_string(),
                             rpc_trace_enabled: state.rpc_state.is_some(),
-                            rpc_trace_selected: self.is_showing_rpc_trace
-                                && self.current_server_id == Some(server_id),
-                            logs_selected: !self.is_showing_rpc_trace
-                                && self.current_server_id == Some(server_id),
+                            rpc_trace_selected: self.is_showing(server_id, LogKind::Rpc),
+                            logs_selected: self.is_showing(server_id, LogKind::Logs),
+                            stderr_selected: self.is_showing(server_id, LogKind::Stderr),
                         })
                     }),
             )
@@ -519,6 +562,23 @@ impl LspLogView {
         Some(rows)
     }
 
+    fn is_showing(&self, server_id: LanguageServerId, kind: LogKind) -> bool {
+        self.current_server_id == Some(server_id) && self.active_log_kind == kind
+    }
+
+    pub(crate) fn show_log_kind_for_server(
+        &mut self,
+        server_id: LanguageServerId,
+        kind: LogKind,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match kind {
+            LogKind::Logs => self.show_logs_for_server(server_id, cx),
+            LogKind::Rpc => self.show_rpc_trace_for_server(server_id, cx),
+            LogKind::Stderr => self.show_stderr_for_server(server_id, cx),
+        }
+    }
+
     fn show_logs_for_server(&mut self, server_id: LanguageServerId, cx: &mut ViewContext<Self>) {
         let log_contents = self
             .log_store
@@ -527,7 +587,7 @@ impl LspLogView {
             .map(log_contents);
         if let Some(log_contents) = log_contents {
             self.current_server_id = Some(server_id);
-            self.is_showing_rpc_trace = false;
+            self.active_log_kind = LogKind::Logs;
             let (editor, editor_subscriptions) = Self::editor_for_logs(log_contents, cx);
             self.editor = editor;
             self.editor_subscriptions = editor_subscriptions;
@@ -536,6 +596,23 @@ impl LspLogView {
         cx.focus(&self.focus_handle);
     }
 
+    fn show_stderr_for_server(&mut self, server_id: LanguageServerId, cx: &mut ViewContext<Self>) {
+        let stderr_contents = self
+            .log_store
+            .read(cx)
+            .server_stderr(&self.project, server_id)
+            .map(log_contents);
+        if let Some(stderr_contents) = stderr_contents {
+            self.current_server_id = Some(server_id);
+            self.active_log_kind = LogKind::Stderr;
+            let (editor, editor_subscriptions) = Self::editor_for_logs(stderr_contents, cx);
+            self.editor = editor;
+            self.editor_subscriptions = editor_subscriptions;
+            cx.notify();
+        }
+        cx.focus(&self.focus_handle);
+    }
+
     fn show_rpc_trace_for_server(
         &mut self,
         server_id: LanguageServerId,
@@ -548,7 +625,7 @@ impl LspLogView {
         });
         if let Some(rpc_log) = rpc_log {
             self.current_server_id = Some(server_id);
-            self.is_showing_rpc_trace = true;
+            self.active_log_kind = LogKind::Rpc;
             let (editor, editor_subscriptions) = Self::editor_for_logs(rpc_log, cx);
             let language = self.project.read(cx).languages().language_for_name("JSON");
             editor
@@ -596,6 +673,11 @@ impl LspLogView {
             cx.notify();
         }
     }
+
+    fn copy_all(&mut self, _: &CopyLanguageServerLogs, cx: &mut ViewContext<Self>) {
+        let text = self.editor.read(cx).text(cx);
+        cx.write_to_clipboard(ClipboardItem::new(text));
+    }
 }
 
 fn log_contents(lines: &VecDeque<String>) -> String {
@@ -610,8 +692,10 @@ fn log_contents(lines: &VecDeque<String>) -> String {
 
 impl Render for LspLogView {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        self.editor
-            .update(cx, |editor, cx| editor.render(cx).into_any_element())
+        div()
+            .size_full()
+            .on_action(cx.listener(Self::copy_all))
+            .child(self.editor.clone())
     }
 }
 
@@ -763,6 +847,8 @@ impl Render for LspLogToolbarItemView {
                             row.worktree_root_name,
                             if row.rpc_trace_selected {
                                 RPC_MESSAGES
+                            } else if row.stderr_selected {
+                                SERVER_STDERR
                             } else {
                                 SERVER_LOGS
                             },
@@ -792,7 +878,7 @@ impl Render for LspLogToolbarItemView {
                         if server_selected && row.logs_selected {
                             let selected_ix = menu.select_last();
                             debug_assert_eq!(
-                                Some(ix * 3 + 1),
+                                Some(ix * 4 + 1),
                                 selected_ix,
                                 "Could not scroll to a just added LSP menu item"
                             );
@@ -843,7 +929,23 @@ impl Render for LspLogToolbarItemView {
                         if server_selected && row.rpc_trace_selected {
                             let selected_ix = menu.select_last();
                             debug_assert_eq!(
-                                Some(ix * 3 + 2),
+                                Some(ix * 4 + 2),
+                                selected_ix,
+                                "Could not scroll to a just added LSP menu item"
+                            );
+                        }
+
+                        menu = menu.entry(
+                            SERVER_STDERR,
+                            None,
+                            cx.handler_for(&log_view, move |view, cx| {
+                                view.show_stderr_for_server(row.server_id, cx);
+                            }),
+                        );
+                        if server_selected && row.stderr_selected {
+                            let selected_ix = menu.select_last();
+                            debug_assert_eq!(
+                                Some(ix * 4 + 3),
                                 selected_ix,
                                 "Could not scroll to a just added LSP menu item"
                             );
@@ -854,30 +956,49 @@ impl Render for LspLogToolbarItemView {
                 .into()
             });
 
-        h_flex().size_full().child(lsp_menu).child(
-            div()
-                .child(
-                    Button::new("clear_log_button", "Clear").on_click(cx.listener(
-                        |this, _, cx| {
-                            if let Some(log_view) = this.log_view.as_ref() {
-                                log_view.update(cx, |log_view, cx| {
-                                    log_view.editor.update(cx, |editor, cx| {
-                                        editor.set_read_only(false);
-                                        editor.clear(cx);
-                                        editor.set_read_only(true);
-                                    });
-                                })
-                            }
-                        },
-                    )),
-                )
-                .ml_2(),
-        )
+        h_flex()
+            .size_full()
+            .child(lsp_menu)
+            .child(
+                div()
+                    .child(
+                        Button::new("clear_log_button", "Clear").on_click(cx.listener(
+                            |this, _, cx| {
+                                if let Some(log_view) = this.log_view.as_ref() {
+                                    log_view.update(cx, |log_view, cx| {
+                                        log_view.editor.update(cx, |editor, cx| {
+                                            editor.set_read_only(false);
+                                            editor.clear(cx);
+                                            editor.set_read_only(true);
+                                        });
+                                    })
+                                }
+                            },
+                        )),
+                    )
+                    .ml_2(),
+            )
+            .child(
+                div()
+                    .child(
+                        Button::new("copy_log_button", "Copy All").on_click(cx.listener(
+                            |this, _, cx| {
+                                if let Some(log_view) = this.log_view.as_ref() {
+                                    log_view.update(cx, |log_view, cx| {
+                                        log_view.copy_all(&CopyLanguageServerLogs, cx)
+                                    })
+                                }
+                            },
+                        )),
+                    )
+                    .ml_2(),
+            )
     }
 }
 
 const RPC_MESSAGES: &str = "RPC Messages";
 const SERVER_LOGS: &str = "Server Logs";
+const SERVER_STDERR: &str = "Server Stderr";
 
 impl LspLogToolbarItemView {
     pub fn new() -> Self {
@@ -911,7 +1032,7 @@ pub enum Event {
     NewServerLogEntry {
         id: LanguageServerId,
         entry: String,
-        is_rpc: bool,
+        kind: LogKind,
     },
 }
 
@@ -0,0 +1,303 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use gpui::{
+    actions, Action, AppContext, AsyncWindowContext, EventEmitter, FocusHandle, FocusableView,
+    Model, Pixels, Subscription, Task, View, ViewContext, VisualContext, WeakView, WindowContext,
+};
+use project::{Fs, Project};
+use schemars::JsonSchema;
+use search::{buffer_search::DivRegistrar, BufferSearchBar};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, IconName};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    pane,
+    panels::{register_panel, PanelPlacement},
+    Pane, Workspace,
+};
+
+use crate::{lsp_log::GlobalLogStore, LspLogToolbarItemView, LspLogView};
+
+const LSP_LOG_PANEL_KEY: &'static str = "LspLogPanel";
+
+actions!(lsp_log_panel, [ToggleFocus]);
+
+pub fn init(cx: &mut AppContext) {
+    LspLogPanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), LspLogPanel::load, cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<LspLogPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LspLogPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct LspLogPanelSettingsContent {
+    /// Whether to show the language server logs panel button in the status bar.
+    ///
+    /// Default: false
+    pub button: Option<bool>,
+    /// Where to dock the language server logs panel.
+    ///
+    /// Default: bottom
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels, when docked to the left or right.
+    ///
+    /// Default: 640
+    pub default_width: Option<f32>,
+    /// Default height of the panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+}
+
+impl Settings for LspLogPanelSettings {
+    const KEY: Option<&'static str> = Some("lsp_log_panel");
+    type FileContent = LspLogPanelSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _: &mut AppContext,
+    ) -> Result<Self> {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
+
+/// A dock panel showing the language server logs of the project, so they can be
+/// followed while editing.
+pub struct LspLogPanel {
+    pane: View<Pane>,
+    project: Model<Project>,
+    fs: Arc<dyn Fs>,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedLspLogPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl LspLogPanel {
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let pane = cx.new_view(|cx| {
+            let mut pane = Pane::new(
+                workspace.weak_handle(),
+                project.clone(),
+                Default::default(),
+                None,
+                cx,
+            );
+            pane.set_can_split(false, cx);
+            pane.set_can_navigate(false, cx);
+            pane.display_nav_history_buttons(false);
+            pane.toolbar().update(cx, |toolbar, cx| {
+                let lsp_log_item = cx.new_view(|_| LspLogToolbarItemView::new());
+                toolbar.add_item(lsp_log_item, cx);
+                let buffer_search_bar = cx.new_view(BufferSearchBar::new);
+                toolbar.add_item(buffer_search_bar, cx);
+            });
+            pane
+        });
+        let _subscriptions = vec![
+            cx.observe(&pane, |_, _, cx| cx.notify()),
+            cx.subscribe(&pane, Self::handle_pane_event),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+
+        let mut this = Self {
+            pane,
+            project,
+            fs: workspace.app_state().fs.clone(),
+            width: None,
+            height: None,
+            _subscriptions,
+        };
+        this.add_log_view(cx);
+        this
+    }
+
+    fn add_log_view(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(log_store) = cx
+            .try_global::<GlobalLogStore>()
+            .map(|store| store.0.clone())
+        else {
+            return;
+        };
+        let project = self.project.clone();
+        let log_view = cx.new_view(|cx| LspLogView::new(project, log_store, cx));
+        self.pane.update(cx, |pane, cx| {
+            pane.add_item(Box::new(log_view), true, false, None, cx);
+        });
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(LSP_LOG_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedLspLogPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = cx.new_view(|cx| Self::new(workspace, cx));
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedLspLogPanel {
+            width: self.width,
+            height: self.height,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(LSP_LOG_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    fn handle_pane_event(
+        &mut self,
+        _: View<Pane>,
+        event: &pane::Event,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            pane::Event::Remove => cx.emit(PanelEvent::Close),
+            pane::Event::ZoomIn => cx.emit(PanelEvent::ZoomIn),
+            pane::Event::ZoomOut => cx.emit(PanelEvent::ZoomOut),
+            _ => {}
+        }
+    }
+}
+
+impl EventEmitter<PanelEvent> for LspLogPanel {}
+
+impl Render for LspLogPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let mut registrar = DivRegistrar::new(
+            |panel, cx| {
+                panel
+                    .pane
+                    .read(cx)
+                    .toolbar()
+                    .read(cx)
+                    .item_of_type::<BufferSearchBar>()
+            },
+            cx,
+        );
+        BufferSearchBar::register(&mut registrar);
+        registrar.into_div().size_full().child(self.pane.clone())
+    }
+}
+
+impl FocusableView for LspLogPanel {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.pane.focus_handle(cx)
+    }
+}
+
+impl Panel for LspLogPanel {
+    fn persistent_name() -> &'static str {
+        "LspLogPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        LspLogPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<LspLogPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = LspLogPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn is_zoomed(&self, cx: &WindowContext) -> bool {
+        self.pane.read(cx).is_zoomed()
+    }
+
+    fn set_zoomed(&mut self, zoomed: bool, cx: &mut ViewContext<Self>) {
+        self.pane.update(cx, |pane, cx| pane.set_zoomed(zoomed, cx));
+    }
+
+    fn set_active(&mut self, active: bool, cx: &mut ViewContext<Self>) {
+        if active && self.pane.read(cx).items_len() == 0 {
+            self.add_log_view(cx);
+        }
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        LspLogPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::FileDoc)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Language Server Logs")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
-use crate::lsp_log::LogMenuItem;
+use crate::lsp_log::{CopyLanguageServerLogs, LogKind, LogMenuItem};
 
 use super::*;
 use futures::StreamExt;
 use gpui::{Context, TestAppContext, VisualTestContext};
 use language::{tree_sitter_rust, FakeLspAdapter, Language, LanguageConfig, LanguageServerName};
+use lsp::IoKind;
 use project::{FakeFs, Project};
 use serde_json::json;
 use settings::SettingsStore;
@@ -89,10 +90,42 @@ async fn test_lsp_logs(cx: &mut TestAppContext) {
                 rpc_trace_enabled: false,
                 rpc_trace_selected: false,
                 logs_selected: true,
+                stderr_selected: false,
             }]
         );
         assert_eq!(view.editor.read(cx).text(cx), "hello from the server\n");
     });
+
+    // What the server writes to stderr is kept apart from its log messages.
+    let server_id = language_server.server.server_id();
+    log_store.update(&mut cx, |store, cx| {
+        store.on_io(
+            project.downgrade(),
+            server_id,
+            IoKind::StdErr,
+            "warning: something went wrong\n",
+            cx,
+        );
+    });
+    log_view.update(&mut cx, |view, cx| {
+        assert_eq!(view.editor.read(cx).text(cx), "hello from the server\n");
+        view.show_log_kind_for_server(server_id, LogKind::Stderr, cx);
+    });
+    log_view.update(&mut cx, |view, cx| {
+        let menu_items = view.menu_items(cx).unwrap();
+        assert!(menu_items[0].stderr_selected);
+        assert!(!menu_items[0].logs_selected);
+        assert_eq!(
+            view.editor.read(cx).text(cx),
+            "warning: something went wrong"
+        );
+    });
+
+    cx.dispatch_action(CopyLanguageServerLogs);
+    assert_eq!(
+        cx.read_from_clipboard().map(|item| item.text().clone()),
+        Some("warning: something went wrong".to_string())
+    );
 }
 
 fn init_test(cx: &mut gpui::TestAppContext) {
@@ -0,0 +1,348 @@
+mod notification_center_settings;
+
+use anyhow::Result;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use gpui::{
+    actions, AnyElement, AppContext, AsyncWindowContext, ClipboardItem, EventEmitter, FocusHandle,
+    FocusableView, Subscription, Task, View, WeakView,
+};
+use notification_center_settings::NotificationCenterSettings;
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use std::sync::Arc;
+use time::UtcOffset;
+use ui::{prelude::*, Tooltip};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::{LoggedNotification, NotificationLog, NotificationSeverity},
+    panels::{register_panel, PanelPlacement},
+    Workspace,
+};
+
+const NOTIFICATION_CENTER_KEY: &'static str = "NotificationCenter";
+
+actions!(notification_center, [ToggleFocus, ClearNotifications]);
+
+pub fn init(cx: &mut AppContext) {
+    NotificationCenterSettings::register(cx);
+    register_panel(PanelPlacement::default(), NotificationCenter::load, cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<NotificationCenter>(cx);
+        });
+    })
+    .detach();
+}
+
+/// A panel listing the notifications shown so far, including the ones that have
+/// already been dismissed.
+pub struct NotificationCenter {
+    fs: Arc<dyn Fs>,
+    width: Option<Pixels>,
+    severity_filter: Option<NotificationSeverity>,
+    local_timezone: UtcOffset,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedNotificationCenter {
+    width: Option<Pixels>,
+}
+
+impl NotificationCenter {
+    pub fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        cx.new_view(|cx: &mut ViewContext<Self>| {
+            let _subscriptions = vec![
+                cx.observe_global::<NotificationLog>(|_, cx| cx.notify()),
+                cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+            ];
+
+            Self {
+                fs,
+                width: None,
+                severity_filter: None,
+                local_timezone: cx.local_timezone(),
+                focus_handle: cx.focus_handle(),
+                _subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(NOTIFICATION_CENTER_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedNotificationCenter>(
+                    &panel,
+                )?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) =
+            serde_json::to_string(&SerializedNotificationCenter { width: self.width }).log_err()
+        {
+            write_kvp_debounced(NOTIFICATION_CENTER_KEY, serialized, cx);
+        }
+    }
+
+    fn clear_notifications(&mut self, _: &ClearNotifications, cx: &mut ViewContext<Self>) {
+        cx.update_global(|log: &mut NotificationLog, _| log.clear());
+    }
+
+    fn set_severity_filter(
+        &mut self,
+        severity_filter: Option<NotificationSeverity>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.severity_filter = severity_filter;
+        cx.notify();
+    }
+
+    fn render_filter_button(
+        &self,
+        label: &'static str,
+        severity_filter: Option<NotificationSeverity>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        Button::new(label, label)
+            .style(ButtonStyle::Subtle)
+            .selected(self.severity_filter == severity_filter)
+            .on_click(cx.listener(move |this, _, cx| {
+                this.set_severity_filter(severity_filter, cx);
+            }))
+    }
+
+    fn render_notification(
+        &self,
+        notification: &LoggedNotification,
+        cx: &mut ViewContext<Self>,
+    ) -> AnyElement {
+        let (icon, color) = match notification.severity {
+            NotificationSeverity::Info => (IconName::Bell, Color::Muted),
+            NotificationSeverity::Warning => (IconName::ExclamationTriangle, Color::Warning),
+            NotificationSeverity::Error => (IconName::XCircle, Color::Error),
+        };
+        let timestamp = notification.timestamp.to_offset(self.local_timezone);
+        let id = notification.id;
+        let message = notification.message.clone();
+
+        h_flex()
+            .id(("notification", id))
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .items_start()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Icon::new(icon).size(IconSize::Small).color(color))
+            .child(
+                v_flex()
+                    .flex_1()
+                    .gap_1()
+                    .overflow_hidden()
+                    .child(Label::new(message.clone()))
+                    .child(
+                        Label::new(format!(
+                            "{:02}:{:02}:{:02}",
+                            timestamp.hour(),
+                            timestamp.minute(),
+                            timestamp.second()
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .child(
+                IconButton::new(("copy", id), IconName::Copy)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Copy Message", cx))
+                    .on_click(move |_, cx| {
+                        cx.write_to_clipboard(ClipboardItem::new(message.to_string()))
+                    }),
+            )
+            .child(
+                IconButton::new(("remove", id), IconName::Close)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Remove", cx))
+                    .on_click(move |_, cx| {
+                        cx.update_global(|log: &mut NotificationLog, _| log.remove(id))
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+impl Render for NotificationCenter {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let notifications = cx
+            .global::<NotificationLog>()
+            .entries()
+            .rev()
+            .filter(|notification| {
+                self.severity_filter
+                    .map_or(true, |severity| notification.severity == severity)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("NotificationCenter")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::clear_notifications))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(rems(ui::Tab::CONTAINER_HEIGHT_IN_REMS))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(self.render_filter_button("All", None, cx))
+                            .child(self.render_filter_button(
+                                "Info",
+                                Some(NotificationSeverity::Info),
+                                cx,
+                            ))
+                            .child(self.render_filter_button(
+                                "Warnings",
+                                Some(NotificationSeverity::Warning),
+                                cx,
+                            ))
+                            .child(self.render_filter_button(
+                                "Errors",
+                                Some(NotificationSeverity::Error),
+                                cx,
+                            )),
+                    )
+                    .child(
+                        IconButton::new("clear_notifications", IconName::Delete)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| Tooltip::for_action("Clear All", &ClearNotifications, cx))
+                            .on_click(|_, cx| cx.dispatch_action(ClearNotifications.boxed_clone())),
+                    ),
+            )
+            .map(|this| {
+                if notifications.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            div().flex().w_full().items_center().child(
+                                Label::new("There are no notifications.")
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small),
+                            ),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        v_flex()
+                            .id("notifications")
+                            .size_full()
+                            .overflow_y_scroll()
+                            .children(
+                                notifications
+                                    .iter()
+                                    .map(|notification| self.render_notification(notification, cx)),
+                            ),
+                    )
+                }
+            })
+    }
+}
+
+impl FocusableView for NotificationCenter {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for NotificationCenter {}
+
+impl Panel for NotificationCenter {
+    fn persistent_name() -> &'static str {
+        "NotificationCenter"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        NotificationCenterSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<NotificationCenterSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| NotificationCenterSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        NotificationCenterSettings::get_global(cx)
+            .button
+            .then(|| IconName::BellRing)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Notification Center")
+    }
+
+    fn icon_label(&self, cx: &WindowContext) -> Option<String> {
+        let error_count = cx
+            .global::<NotificationLog>()
+            .entries()
+            .filter(|notification| notification.severity == NotificationSeverity::Error)
+            .count();
+        (error_count > 0).then(|| error_count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
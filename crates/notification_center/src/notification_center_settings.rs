@@ -0,0 +1,42 @@
+use anyhow;
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::Settings;
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug)]
+pub struct NotificationCenterSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct NotificationCenterSettingsContent {
+    /// Whether to show the notification center button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the notification center.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the notification center in pixels.
+    ///
+    /// Default: 380
+    pub default_width: Option<f32>,
+}
+
+impl Settings for NotificationCenterSettings {
+    const KEY: Option<&'static str> = Some("notification_center");
+    type FileContent = NotificationCenterSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
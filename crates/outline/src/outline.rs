@@ -22,10 +22,13 @@ use ui::{prelude::*, ListItem, ListItemSpacing};
 use util::ResultExt;
 use workspace::ModalView;
 
+pub mod outline_panel;
+
 actions!(outline, [Toggle]);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(OutlineView::register).detach();
+    outline_panel::init(cx);
 }
 
 pub fn toggle(editor: View<Editor>, _: &Toggle, cx: &mut WindowContext) {
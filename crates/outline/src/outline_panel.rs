@@ -0,0 +1,660 @@
+use std::{cmp::Reverse, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use editor::{scroll::Autoscroll, Anchor, Editor, EditorEvent};
+use fuzzy::StringMatch;
+use gpui::{
+    actions, uniform_list, Action, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, KeyContext, Pixels, Subscription, Task, UniformListScrollHandle, View,
+    ViewContext, VisualContext, WeakView, WindowContext,
+};
+use language::Outline;
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
+use project::Fs;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, HighlightedLabel, ListItem};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    panels::{register_panel, PanelPlacement},
+    Workspace,
+};
+
+const OUTLINE_PANEL_KEY: &'static str = "OutlinePanel";
+const UPDATE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+actions!(outline_panel, [ToggleFocus]);
+
+pub fn init(cx: &mut AppContext) {
+    OutlinePanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), OutlinePanel::load, cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<OutlinePanel>(cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OutlinePanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct OutlinePanelSettingsContent {
+    /// Whether to show the outline panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the outline panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels, when docked to the left or right.
+    ///
+    /// Default: 240
+    pub default_width: Option<f32>,
+    /// Default height of the panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+}
+
+impl Settings for OutlinePanelSettings {
+    const KEY: Option<&'static str> = Some("outline_panel");
+    type FileContent = OutlinePanelSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _: &mut AppContext,
+    ) -> Result<Self> {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
+
+struct ActiveEditor {
+    editor: View<Editor>,
+    _subscription: Subscription,
+}
+
+/// A dock panel showing the symbols of the active editor, as in the outline modal,
+/// that follows the cursor and can be filtered.
+pub struct OutlinePanel {
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    filter_editor: View<Editor>,
+    active_editor: Option<ActiveEditor>,
+    outline: Option<Arc<Outline<Anchor>>>,
+    matches: Vec<StringMatch>,
+    selected_index: Option<usize>,
+    list: UniformListScrollHandle,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    update_outline_task: Task<()>,
+    update_matches_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedOutlinePanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl OutlinePanel {
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let filter_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Filter symbols…", cx);
+            editor
+        });
+        let mut _subscriptions = vec![
+            cx.subscribe(&filter_editor, |this, _, event, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_matches(cx);
+                }
+            }),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+        if let Some(workspace_handle) = workspace.weak_handle().upgrade() {
+            _subscriptions.push(
+                cx.subscribe(&workspace_handle, |this, workspace, event, cx| {
+                    if let workspace::Event::ActiveItemChanged = event {
+                        let active_editor = workspace.read(cx).active_item_as::<Editor>(cx);
+                        this.set_active_editor(active_editor, cx);
+                    }
+                }),
+            );
+        }
+
+        let mut this = Self {
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            filter_editor,
+            active_editor: None,
+            outline: None,
+            matches: Vec::new(),
+            selected_index: None,
+            list: UniformListScrollHandle::new(),
+            width: None,
+            height: None,
+            update_outline_task: Task::ready(()),
+            update_matches_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.set_active_editor(workspace.active_item_as::<Editor>(cx), cx);
+        this
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(OUTLINE_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedOutlinePanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = cx.new_view(|cx| Self::new(workspace, cx));
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedOutlinePanel {
+            width: self.width,
+            height: self.height,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(OUTLINE_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    fn set_active_editor(&mut self, editor: Option<View<Editor>>, cx: &mut ViewContext<Self>) {
+        let editor = editor.filter(|editor| editor.read(cx).mode() == editor::EditorMode::Full);
+        if self.active_editor.as_ref().map(|active| &active.editor) == editor.as_ref() {
+            return;
+        }
+
+        self.active_editor = editor.map(|editor| ActiveEditor {
+            _subscription: cx.subscribe(&editor, |this, _, event, cx| match event {
+                EditorEvent::BufferEdited | EditorEvent::Reparsed => this.update_outline(true, cx),
+                EditorEvent::SelectionsChanged { .. } => this.select_cursor_item(cx),
+                _ => {}
+            }),
+            editor,
+        });
+        self.update_outline(false, cx);
+    }
+
+    /// Recomputes the outline of the active editor, after a short delay when the
+    /// buffer is being edited.
+    fn update_outline(&mut self, debounce: bool, cx: &mut ViewContext<Self>) {
+        self.update_outline_task = cx.spawn(|this, mut cx| async move {
+            if debounce {
+                cx.background_executor().timer(UPDATE_DEBOUNCE).await;
+            }
+            this.update(&mut cx, |this, cx| {
+                this.outline = this.active_editor.as_ref().and_then(|active| {
+                    active
+                        .editor
+                        .read(cx)
+                        .buffer()
+                        .read(cx)
+                        .snapshot(cx)
+                        .outline(Some(&cx.theme().syntax()))
+                        .map(Arc::new)
+                });
+                this.update_matches(cx);
+            })
+            .ok();
+        });
+    }
+
+    fn update_matches(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(outline) = self.outline.clone() else {
+            self.matches.clear();
+            self.selected_index = None;
+            cx.notify();
+            return;
+        };
+
+        let query = self.filter_editor.read(cx).text(cx);
+        if query.trim().is_empty() {
+            self.matches = (0..outline.items.len())
+                .map(|candidate_id| StringMatch {
+                    candidate_id,
+                    score: Default::default(),
+                    positions: Default::default(),
+                    string: Default::default(),
+                })
+                .collect();
+            self.select_cursor_item(cx);
+            return;
+        }
+
+        let executor = cx.background_executor().clone();
+        self.update_matches_task = cx.spawn(|this, mut cx| async move {
+            let matches = outline.search(&query, executor).await;
+            this.update(&mut cx, |this, cx| {
+                this.selected_index = (!matches.is_empty()).then_some(0);
+                this.matches = matches;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// Selects the innermost symbol containing the newest cursor of the editor.
+    fn select_cursor_item(&mut self, cx: &mut ViewContext<Self>) {
+        let (Some(active), Some(outline)) = (&self.active_editor, &self.outline) else {
+            return;
+        };
+        let editor = active.editor.read(cx);
+        let cursor = editor.selections.newest_anchor().head();
+        let buffer = editor.buffer().read(cx).snapshot(cx);
+        let cursor_item = outline
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                item.range.start.cmp(&cursor, &buffer).is_le()
+                    && item.range.end.cmp(&cursor, &buffer).is_ge()
+            })
+            .max_by_key(|(ix, item)| (item.depth, Reverse(*ix)))
+            .map(|(ix, _)| ix);
+
+        let selected_index = cursor_item.and_then(|item_ix| {
+            self.matches
+                .iter()
+                .position(|mat| mat.candidate_id == item_ix)
+        });
+        if let Some(ix) = selected_index {
+            self.list.scroll_to_item(ix);
+        }
+        self.selected_index = selected_index;
+        cx.notify();
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix < self.matches.len() {
+            self.selected_index = Some(ix);
+            self.list.scroll_to_item(ix);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, cx: &mut ViewContext<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, cx: &mut ViewContext<Self>) {
+        self.select_index(self.matches.len().saturating_sub(1), cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.navigate_to(ix, cx);
+        }
+    }
+
+    /// Moves the cursor of the active editor to the start of the symbol, and
+    /// focuses the editor.
+    fn navigate_to(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let (Some(active), Some(outline)) = (&self.active_editor, &self.outline) else {
+            return;
+        };
+        let Some(item) = self
+            .matches
+            .get(ix)
+            .and_then(|mat| outline.items.get(mat.candidate_id))
+        else {
+            return;
+        };
+        let position = item.range.start;
+        active.editor.update(cx, |editor, cx| {
+            editor.change_selections(Some(Autoscroll::center()), cx, |selections| {
+                selections.select_ranges([position..position])
+            });
+            editor.focus(cx);
+        });
+    }
+
+    fn dispatch_context(&self, cx: &ViewContext<Self>) -> KeyContext {
+        let mut dispatch_context = KeyContext::default();
+        dispatch_context.add("OutlinePanel");
+        dispatch_context.add("menu");
+
+        let identifier = if self.filter_editor.focus_handle(cx).is_focused(cx) {
+            "editing"
+        } else {
+            "not_editing"
+        };
+
+        dispatch_context.add(identifier);
+        dispatch_context
+    }
+
+    fn render_match(&self, ix: usize, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        let mat = self.matches.get(ix)?;
+        let item = self.outline.as_ref()?.items.get(mat.candidate_id)?;
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .indent_level(item.depth)
+                .selected(self.selected_index == Some(ix))
+                .child(HighlightedLabel::new(
+                    item.text.clone(),
+                    mat.positions.clone(),
+                ))
+                .on_click(cx.listener(move |this, _, cx| {
+                    this.selected_index = Some(ix);
+                    this.navigate_to(ix, cx);
+                    cx.notify();
+                })),
+        )
+    }
+}
+
+impl EventEmitter<PanelEvent> for OutlinePanel {}
+
+impl Render for OutlinePanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let placeholder = if self.active_editor.is_none() {
+            Some("No active editor")
+        } else if self.matches.is_empty() {
+            Some("No symbols")
+        } else {
+            None
+        };
+
+        v_flex()
+            .id("outline-panel")
+            .size_full()
+            .key_context(self.dispatch_context(cx))
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(self.filter_editor.clone()),
+            )
+            .child(match placeholder {
+                Some(placeholder) => h_flex()
+                    .flex_1()
+                    .justify_center()
+                    .child(Label::new(placeholder).color(Color::Muted))
+                    .into_any_element(),
+                None => uniform_list(
+                    cx.view().clone(),
+                    "outline-panel-entries",
+                    self.matches.len(),
+                    |this, range, cx| {
+                        range
+                            .filter_map(|ix| Some(this.render_match(ix, cx)?.into_any_element()))
+                            .collect()
+                    },
+                )
+                .flex_1()
+                .track_scroll(self.list.clone())
+                .into_any_element(),
+            })
+    }
+}
+
+impl FocusableView for OutlinePanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for OutlinePanel {
+    fn persistent_name() -> &'static str {
+        "OutlinePanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        OutlinePanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<OutlinePanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = OutlinePanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        OutlinePanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::FileTree)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Outline Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::{tree_sitter_rust, Language, LanguageConfig, Point};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+
+    #[gpui::test]
+    async fn test_outline_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "main.rs": "struct Point {\n    x: i32,\n}\n\nfn origin() -> Point {\n    Point { x: 0 }\n}\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+        project.update(cx, |project, _| {
+            project.languages().add(Arc::new(rust_lang()));
+        });
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| OutlinePanel::new(workspace, cx));
+            workspace.add_panel(panel.clone(), cx);
+            panel
+        });
+        assert_eq!(displayed_items(&panel, cx), Vec::<String>::new());
+
+        // The panel follows the active editor, selecting the symbol under its cursor.
+        let editor = workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_abs_path("/dir/main.rs".into(), true, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        cx.run_until_parked();
+        cx.executor().advance_clock(UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            displayed_items(&panel, cx),
+            ["struct Point", "x", "fn origin"]
+        );
+        assert_eq!(selected_item(&panel, cx).as_deref(), Some("struct Point"));
+
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |s| {
+                s.select_ranges([Point::new(5, 4)..Point::new(5, 4)])
+            })
+        });
+        assert_eq!(selected_item(&panel, cx).as_deref(), Some("fn origin"));
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |s| {
+                s.select_ranges([Point::new(1, 4)..Point::new(1, 4)])
+            })
+        });
+        assert_eq!(selected_item(&panel, cx).as_deref(), Some("x"));
+
+        // Filtering the symbols and confirming one moves the cursor to it.
+        panel.update(cx, |panel, cx| {
+            panel
+                .filter_editor
+                .update(cx, |filter_editor, cx| filter_editor.set_text("orig", cx))
+        });
+        cx.run_until_parked();
+        assert_eq!(displayed_items(&panel, cx), ["fn origin"]);
+        assert_eq!(selected_item(&panel, cx).as_deref(), Some("fn origin"));
+        panel.update(cx, |panel, cx| panel.confirm(&Confirm, cx));
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor.selections.newest::<Point>(cx).head(),
+                Point::new(4, 0)
+            );
+            assert!(editor.focus_handle(cx).is_focused(cx));
+        });
+
+        // Closing the editor clears the panel.
+        workspace.update(cx, |workspace, cx| {
+            workspace.active_pane().update(cx, |pane, cx| {
+                pane.close_active_item(&Default::default(), cx)
+                    .unwrap()
+                    .detach()
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(displayed_items(&panel, cx), Vec::<String>::new());
+        panel.update(cx, |panel, _| assert!(panel.active_editor.is_none()));
+    }
+
+    fn displayed_items(panel: &View<OutlinePanel>, cx: &mut VisualTestContext) -> Vec<String> {
+        panel.update(cx, |panel, _| {
+            let Some(outline) = panel.outline.as_ref() else {
+                return Vec::new();
+            };
+            panel
+                .matches
+                .iter()
+                .map(|mat| outline.items[mat.candidate_id].text.clone())
+                .collect()
+        })
+    }
+
+    fn selected_item(panel: &View<OutlinePanel>, cx: &mut VisualTestContext) -> Option<String> {
+        panel.update(cx, |panel, _| {
+            let mat = panel.matches.get(panel.selected_index?)?;
+            Some(panel.outline.as_ref()?.items[mat.candidate_id].text.clone())
+        })
+    }
+
+    fn rust_lang() -> Language {
+        Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                path_suffixes: vec!["rs".to_string()],
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::language()),
+        )
+        .with_outline_query(
+            r#"
+            (struct_item
+                "struct" @context
+                name: (_) @name) @item
+            (field_declaration
+                name: (_) @name) @item
+            (function_item
+                "fn" @context
+                name: (_) @name) @item
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            OutlinePanelSettings::register(cx);
+        });
+    }
+}
@@ -1,19 +1,28 @@
 use editor::Editor;
 use gpui::{
-    div, prelude::*, uniform_list, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle,
-    FocusableView, Length, MouseButton, MouseDownEvent, Render, Task, UniformListScrollHandle,
-    View, ViewContext, WindowContext,
+    actions, div, prelude::*, uniform_list, AnyElement, AppContext, DismissEvent, EventEmitter,
+    FocusHandle, FocusableView, Length, MouseButton, MouseDownEvent, Render, Task,
+    UniformListScrollHandle, View, ViewContext, WindowContext,
 };
 use std::sync::Arc;
 use ui::{prelude::*, v_flex, Color, Divider, Label, ListItem, ListItemSpacing};
 use workspace::ModalView;
 
+actions!(picker, [ConfirmInDock]);
+
+/// How to confirm the selection once the pending matches have been updated.
+enum PendingConfirm {
+    Primary,
+    Secondary,
+    InDock,
+}
+
 pub struct Picker<D: PickerDelegate> {
     pub delegate: D,
     scroll_handle: UniformListScrollHandle,
     editor: View<Editor>,
     pending_update_matches: Option<Task<()>>,
-    confirm_on_update: Option<bool>,
+    confirm_on_update: Option<PendingConfirm>,
     width: Option<Length>,
     max_height: Option<Length>,
 
@@ -36,6 +45,11 @@ pub trait PickerDelegate: Sized + 'static {
     fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()>;
 
     fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<Self>>);
+    /// Confirms the selection, opening it in a pane of a dock rather than in the
+    /// center. Pickers whose selections aren't opened as items just confirm it.
+    fn confirm_in_dock(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.confirm(false, cx);
+    }
     fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>);
 
     fn render_match(
@@ -155,7 +169,7 @@ impl<D: PickerDelegate> Picker<D> {
 
     fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
         if self.pending_update_matches.is_some() {
-            self.confirm_on_update = Some(false)
+            self.confirm_on_update = Some(PendingConfirm::Primary)
         } else {
             self.delegate.confirm(false, cx);
         }
@@ -163,12 +177,20 @@ impl<D: PickerDelegate> Picker<D> {
 
     fn secondary_confirm(&mut self, _: &menu::SecondaryConfirm, cx: &mut ViewContext<Self>) {
         if self.pending_update_matches.is_some() {
-            self.confirm_on_update = Some(true)
+            self.confirm_on_update = Some(PendingConfirm::Secondary)
         } else {
             self.delegate.confirm(true, cx);
         }
     }
 
+    fn confirm_in_dock(&mut self, _: &ConfirmInDock, cx: &mut ViewContext<Self>) {
+        if self.pending_update_matches.is_some() {
+            self.confirm_on_update = Some(PendingConfirm::InDock)
+        } else {
+            self.delegate.confirm_in_dock(cx);
+        }
+    }
+
     fn handle_click(&mut self, ix: usize, secondary: bool, cx: &mut ViewContext<Self>) {
         cx.stop_propagation();
         cx.prevent_default();
@@ -215,8 +237,11 @@ impl<D: PickerDelegate> Picker<D> {
         let index = self.delegate.selected_index();
         self.scroll_handle.scroll_to_item(index);
         self.pending_update_matches = None;
-        if let Some(secondary) = self.confirm_on_update.take() {
-            self.delegate.confirm(secondary, cx);
+        match self.confirm_on_update.take() {
+            Some(PendingConfirm::Primary) => self.delegate.confirm(false, cx),
+            Some(PendingConfirm::Secondary) => self.delegate.confirm(true, cx),
+            Some(PendingConfirm::InDock) => self.delegate.confirm_in_dock(cx),
+            None => {}
         }
         cx.notify();
     }
@@ -260,6 +285,7 @@ impl<D: PickerDelegate> Render for Picker<D> {
             .on_action(cx.listener(Self::cancel))
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::secondary_confirm))
+            .on_action(cx.listener(Self::confirm_in_dock))
             .child(picker_editor)
             .child(Divider::horizontal())
             .when(self.delegate.match_count() > 0, |el| {
@@ -224,6 +224,7 @@ pub enum Event {
     WorktreeAdded,
     WorktreeRemoved(WorktreeId),
     WorktreeUpdatedEntries(WorktreeId, UpdatedEntriesSet),
+    WorktreeUpdatedGitRepositories,
     DiskBasedDiagnosticsStarted {
         language_server_id: LanguageServerId,
     },
@@ -6385,7 +6386,8 @@ impl Project {
                     ));
                 }
                 worktree::Event::UpdatedGitRepositories(updated_repos) => {
-                    this.update_local_worktree_buffers_git_repos(worktree, updated_repos, cx)
+                    this.update_local_worktree_buffers_git_repos(worktree, updated_repos, cx);
+                    cx.emit(Event::WorktreeUpdatedGitRepositories);
                 }
             })
             .detach();
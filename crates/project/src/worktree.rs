@@ -2068,6 +2068,31 @@ impl LocalSnapshot {
         Some((path, self.git_repositories.get(&repo.work_directory_id())?))
     }
 
+    /// Returns the git repositories of this worktree, along with the paths of
+    /// their work directories.
+    pub fn local_git_repositories(
+        &self,
+    ) -> impl Iterator<Item = (RepositoryWorkDirectory, Arc<Mutex<dyn GitRepository>>)> + '_ {
+        self.repositories().filter_map(|(work_directory, repo)| {
+            let repo = self.get_local_repo(repo)?;
+            Some((
+                RepositoryWorkDirectory(work_directory.clone()),
+                repo.repo_ptr.clone(),
+            ))
+        })
+    }
+
+    /// Returns the git repository containing the given path, along with the
+    /// path relative to the repository's work directory.
+    pub fn local_git_repository_for_path(
+        &self,
+        path: &Path,
+    ) -> Option<(RepoPath, Arc<Mutex<dyn GitRepository>>)> {
+        let (work_directory, repo) = self.local_repo_for_path(path)?;
+        let repo_path = path.strip_prefix(&work_directory).ok()?;
+        Some((repo_path.into(), repo.repo_ptr.clone()))
+    }
+
     fn build_update(
         &self,
         project_id: u64,
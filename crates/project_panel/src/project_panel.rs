@@ -3,7 +3,7 @@ mod project_panel_settings;
 use client::{ErrorCode, ErrorExt};
 use settings::Settings;
 
-use db::kvp::KEY_VALUE_STORE;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
 use editor::{actions::Cancel, scroll::Autoscroll, Editor};
 use file_associations::FileAssociations;
 
@@ -33,7 +33,7 @@ use std::{
 use theme::ThemeSettings;
 use ui::{prelude::*, v_flex, ContextMenu, Icon, KeyBinding, Label, ListItem};
 use unicase::UniCase;
-use util::{maybe, ResultExt, TryFutureExt};
+use util::{maybe, ResultExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
     notifications::DetachAndPromptErr,
@@ -58,8 +58,8 @@ pub struct ProjectPanel {
     clipboard_entry: Option<ClipboardEntry>,
     _dragged_entry_destination: Option<Arc<Path>>,
     workspace: WeakView<Workspace>,
+    workspace_read_only: bool,
     width: Option<Pixels>,
-    pending_serialization: Task<Option<()>>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -126,6 +126,7 @@ actions!(
         Open,
         ToggleFocus,
         NewSearchInDirectory,
+        RevealActiveFile,
     ]
 );
 
@@ -141,10 +142,27 @@ pub fn init(assets: impl AssetSource, cx: &mut AppContext) {
         workspace.register_action(|workspace, _: &ToggleFocus, cx| {
             workspace.toggle_panel_focus::<ProjectPanel>(cx);
         });
+        workspace.register_action(reveal_active_file);
     })
     .detach();
 }
 
+/// Selects the file of the active item in the project panel, for when the
+/// `auto_reveal_entries` setting is off. Unlike automatic reveals, this also
+/// reveals gitignored files.
+fn reveal_active_file(
+    workspace: &mut Workspace,
+    _: &RevealActiveFile,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let project = workspace.project().clone();
+    if let Some(entry_id) = project.read(cx).active_entry() {
+        project.update(cx, |_, cx| {
+            cx.emit(project::Event::RevealInProjectPanel(entry_id))
+        });
+    }
+}
+
 #[derive(Debug)]
 pub enum Event {
     OpenedEntry {
@@ -171,7 +189,19 @@ struct DraggedProjectEntryView {
 impl ProjectPanel {
     fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
         let project = workspace.project().clone();
+        let workspace_handle = cx.view().clone();
         let project_panel = cx.new_view(|cx: &mut ViewContext<Self>| {
+            cx.subscribe(&workspace_handle, |this, _, event, cx| {
+                if let workspace::Event::ReadOnlyChanged(read_only) = event {
+                    this.workspace_read_only = *read_only;
+                    if *read_only {
+                        this.edit_state = None;
+                        this.update_visible_entries(None, cx);
+                    }
+                    cx.notify();
+                }
+            })
+            .detach();
             cx.observe(&project, |this, _, cx| {
                 this.update_visible_entries(None, cx);
                 cx.notify();
@@ -244,8 +274,8 @@ impl ProjectPanel {
                 clipboard_entry: None,
                 _dragged_entry_destination: None,
                 workspace: workspace.weak_handle(),
+                workspace_read_only: workspace.is_read_only(),
                 width: None,
-                pending_serialization: Task::ready(None),
             };
             this.update_visible_entries(None, cx);
 
@@ -342,19 +372,11 @@ impl ProjectPanel {
     }
 
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
-        let width = self.width;
-        self.pending_serialization = cx.background_executor().spawn(
-            async move {
-                KEY_VALUE_STORE
-                    .write_kvp(
-                        PROJECT_PANEL_KEY.into(),
-                        serde_json::to_string(&SerializedProjectPanel { width })?,
-                    )
-                    .await?;
-                anyhow::Ok(())
-            }
-            .log_err(),
-        );
+        if let Some(serialized) =
+            serde_json::to_string(&SerializedProjectPanel { width: self.width }).log_err()
+        {
+            write_kvp_debounced(PROJECT_PANEL_KEY, serialized, cx);
+        }
     }
 
     fn focus_in(&mut self, cx: &mut ViewContext<Self>) {
@@ -388,7 +410,7 @@ impl ProjectPanel {
             let is_dir = entry.is_dir();
             let worktree_id = worktree.id();
             let is_local = project.is_local();
-            let is_read_only = project.is_read_only();
+            let is_read_only = self.workspace_read_only || project.is_read_only();
 
             let context_menu = ContextMenu::build(cx, |menu, cx| {
                 menu.context(self.focus_handle.clone()).when_else(
@@ -1006,6 +1028,9 @@ impl ProjectPanel {
         destination_is_file: bool,
         cx: &mut ViewContext<Self>,
     ) {
+        if self.workspace_read_only {
+            return;
+        }
         let destination_worktree = self.project.update(cx, |project, cx| {
             let entry_path = project.path_for_entry(entry_to_move, cx)?;
             let destination_entry_path = project.path_for_entry(destination, cx)?.path.clone();
@@ -1498,7 +1523,7 @@ impl Render for ProjectPanel {
                 .on_action(cx.listener(Self::copy_path))
                 .on_action(cx.listener(Self::copy_relative_path))
                 .on_action(cx.listener(Self::new_search_in_directory))
-                .when(!project.is_read_only(), |el| {
+                .when(!self.workspace_read_only && !project.is_read_only(), |el| {
                     el.on_action(cx.listener(Self::new_file))
                         .on_action(cx.listener(Self::new_directory))
                         .on_action(cx.listener(Self::rename))
@@ -3438,6 +3463,72 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_reveal_active_file(cx: &mut gpui::TestAppContext) {
+        init_test_with_editor(cx);
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<ProjectPanelSettings>(cx, |project_panel_settings| {
+                    project_panel_settings.auto_reveal_entries = Some(false)
+                });
+            })
+        });
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/project_root",
+            json!({
+                "dir_1": {
+                    "file_1.py": "# File 1_1 contents",
+                },
+                "dir_2": {
+                    "file_1.py": "# File 2_1 contents",
+                }
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/project_root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| ProjectPanel::new(workspace, cx))
+            .unwrap();
+
+        project.update(cx, |project, cx| {
+            let worktree_id = project.worktrees().next().unwrap().read(cx).id();
+            project.set_active_path(
+                Some(ProjectPath {
+                    worktree_id,
+                    path: Path::new("dir_2/file_1.py").into(),
+                }),
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..20, cx),
+            &["v project_root", "    > dir_1", "    > dir_2"],
+            "With no auto reveal, the active file should not be revealed"
+        );
+
+        workspace
+            .update(cx, |workspace, cx| {
+                reveal_active_file(workspace, &RevealActiveFile, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..20, cx),
+            &[
+                "v project_root",
+                "    > dir_1",
+                "    v dir_2",
+                "          file_1.py  <== selected",
+            ],
+        );
+    }
+
     fn toggle_expand_dir(
         panel: &View<ProjectPanel>,
         path: impl AsRef<Path>,
@@ -51,6 +51,7 @@ pub struct ProjectPanelSettingsContent {
     /// Whether to reveal it in the project panel automatically,
     /// when a corresponding project entry becomes active.
     /// Gitignored entries are never auto revealed.
+    /// `project_panel::RevealActiveFile` reveals the active entry on demand.
     ///
     /// Default: true
     pub auto_reveal_entries: Option<bool>,
@@ -102,15 +102,8 @@ impl ProjectSymbolsDelegate {
         self.matches = matches;
         self.set_selected_index(0, cx);
     }
-}
-
-impl PickerDelegate for ProjectSymbolsDelegate {
-    type ListItem = ListItem;
-    fn placeholder_text(&self) -> Arc<str> {
-        "Search project symbols...".into()
-    }
 
-    fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<Self>>) {
+    fn open_selected(&mut self, target: OpenTarget, cx: &mut ViewContext<Picker<Self>>) {
         if let Some(symbol) = self
             .matches
             .get(self.selected_match_index)
@@ -128,10 +121,12 @@ impl PickerDelegate for ProjectSymbolsDelegate {
                         .read(cx)
                         .clip_point_utf16(symbol.range.start, Bias::Left);
 
-                    let editor = if secondary {
-                        workspace.split_project_item::<Editor>(buffer, cx)
-                    } else {
-                        workspace.open_project_item::<Editor>(buffer, cx)
+                    let editor = match target {
+                        OpenTarget::Center => workspace.open_project_item::<Editor>(buffer, cx),
+                        OpenTarget::Split => workspace.split_project_item::<Editor>(buffer, cx),
+                        OpenTarget::Dock => {
+                            workspace.open_project_item_in_dock::<Editor>(buffer, cx)
+                        }
                     };
 
                     editor.update(cx, |editor, cx| {
@@ -146,6 +141,34 @@ impl PickerDelegate for ProjectSymbolsDelegate {
             cx.emit(DismissEvent);
         }
     }
+}
+
+/// The pane that the selected symbol is opened in.
+#[derive(Clone, Copy)]
+enum OpenTarget {
+    Center,
+    Split,
+    Dock,
+}
+
+impl PickerDelegate for ProjectSymbolsDelegate {
+    type ListItem = ListItem;
+    fn placeholder_text(&self) -> Arc<str> {
+        "Search project symbols...".into()
+    }
+
+    fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let target = if secondary {
+            OpenTarget::Split
+        } else {
+            OpenTarget::Center
+        };
+        self.open_selected(target, cx);
+    }
+
+    fn confirm_in_dock(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.open_selected(OpenTarget::Dock, cx);
+    }
 
     fn dismissed(&mut self, _cx: &mut ViewContext<Picker<Self>>) {}
 
@@ -265,6 +288,10 @@ mod tests {
     use serde_json::json;
     use settings::SettingsStore;
     use std::{path::Path, sync::Arc};
+    use workspace::{
+        dock::{test::TestPanel, DockPosition},
+        Pane,
+    };
 
     #[gpui::test]
     async fn test_project_symbols(cx: &mut TestAppContext) {
@@ -375,6 +402,35 @@ mod tests {
             assert_eq!(delegate.matches[1].string, "one");
         });
 
+        // Confirming in the dock opens the selected symbol in a pane hosted by a dock panel.
+        let dock_pane = workspace.update(cx, |workspace, cx| {
+            let pane = cx.new_view(|cx| {
+                Pane::new(
+                    workspace.weak_handle(),
+                    project.clone(),
+                    Default::default(),
+                    None,
+                    cx,
+                )
+            });
+            let panel = cx.new_view(|cx| TestPanel {
+                panes: vec![pane.clone()],
+                ..TestPanel::new(DockPosition::Bottom, cx)
+            });
+            workspace.add_panel(panel, cx);
+            pane
+        });
+        symbols.update(cx, |symbols, cx| symbols.delegate.confirm_in_dock(cx));
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.bottom_dock().read(cx).is_open());
+            assert_eq!(workspace.panes()[0].read(cx).items_len(), 0);
+        });
+        dock_pane.update(cx, |pane, cx| {
+            let editor = pane.active_item().unwrap().downcast::<Editor>().unwrap();
+            assert_eq!(editor.read(cx).title(cx), "test.rs");
+        });
+
         // Spawn more updates such that in the end, there are again no matches.
         symbols.update(cx, |p, cx| {
             p.update_matches("o".to_string(), cx);
@@ -48,6 +48,17 @@ pub struct Mention {
 
 impl RichText {
     pub fn element(&self, id: ElementId, cx: &mut WindowContext) -> AnyElement {
+        self.element_with_link_handler(id, cx, |url, cx| cx.open_url(url))
+    }
+
+    /// Renders the text, calling `on_link` with the URL of any link that gets
+    /// clicked instead of opening it in the browser.
+    pub fn element_with_link_handler(
+        &self,
+        id: ElementId,
+        cx: &mut WindowContext,
+        on_link: impl Fn(&str, &mut WindowContext) + 'static,
+    ) -> AnyElement {
         let theme = cx.theme();
         let code_background = theme.colors().surface_background;
 
@@ -83,7 +94,7 @@ impl RichText {
         )
         .on_click(self.link_ranges.clone(), {
             let link_urls = self.link_urls.clone();
-            move |ix, cx| cx.open_url(&link_urls[ix])
+            move |ix, cx| on_link(&link_urls[ix], cx)
         })
         .tooltip({
             let link_ranges = self.link_ranges.clone();
@@ -24,6 +24,7 @@ use project::{
     Entry, Project,
 };
 use semantic_index::{SemanticIndex, SemanticIndexStatus};
+use serde::{Deserialize, Serialize};
 
 use settings::Settings;
 use smol::stream::StreamExt;
@@ -44,7 +45,7 @@ use ui::{
 };
 use util::{paths::PathMatcher, ResultExt as _};
 use workspace::{
-    item::{BreadcrumbText, Item, ItemEvent, ItemHandle},
+    item::{BreadcrumbText, Item, ItemEvent, ItemHandle, SerializableItem},
     searchable::{Direction, SearchableItem, SearchableItemHandle},
     ItemNavHistory, Pane, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace,
     WorkspaceId,
@@ -127,6 +128,7 @@ pub fn init(cx: &mut AppContext) {
         });
     })
     .detach();
+    workspace::register_serializable_item::<ProjectSearchView>(cx);
 }
 
 struct ProjectSearch {
@@ -333,6 +335,8 @@ pub enum ViewEvent {
     Activate,
     EditorEvent(editor::EditorEvent),
     Dismiss,
+    /// The query, its options or its file filters changed.
+    QueryChanged,
 }
 
 impl EventEmitter<ViewEvent> for ProjectSearchView {}
@@ -469,6 +473,11 @@ impl Item for ProjectSearchView {
         }
     }
 
+    fn set_read_only(&mut self, read_only: bool, cx: &mut ViewContext<Self>) {
+        self.results_editor
+            .update(cx, |editor, cx| Item::set_read_only(editor, read_only, cx));
+    }
+
     fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
         self.results_editor
             .update(cx, |editor, cx| editor.deactivated(cx));
@@ -597,6 +606,7 @@ impl Item for ProjectSearchView {
                 Editor::to_item_events(editor_event, f);
             }
             ViewEvent::Dismiss => f(ItemEvent::CloseItem),
+            ViewEvent::QueryChanged => f(ItemEvent::Serialize),
             _ => {}
         }
     }
@@ -612,19 +622,88 @@ impl Item for ProjectSearchView {
     fn breadcrumbs(&self, theme: &theme::Theme, cx: &AppContext) -> Option<Vec<BreadcrumbText>> {
         self.results_editor.breadcrumbs(theme, cx)
     }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedProjectSearch {
+    query: String,
+    search_options: u8,
+    mode: SerializedSearchMode,
+    filters_enabled: bool,
+    included_files: String,
+    excluded_files: String,
+}
+
+#[derive(Serialize, Deserialize)]
+enum SerializedSearchMode {
+    Text,
+    Semantic,
+    Regex,
+}
+
+impl SerializableItem for ProjectSearchView {
+    fn serialized_item_kind() -> &'static str {
+        "ProjectSearchView"
+    }
 
-    fn serialized_item_kind() -> Option<&'static str> {
-        None
+    fn serialize(&self, cx: &AppContext) -> Option<String> {
+        let mode = match self.current_mode {
+            SearchMode::Text => SerializedSearchMode::Text,
+            SearchMode::Semantic => SerializedSearchMode::Semantic,
+            SearchMode::Regex => SerializedSearchMode::Regex,
+        };
+        serde_json::to_string(&SerializedProjectSearch {
+            query: self.query_editor.read(cx).text(cx),
+            search_options: self.search_options.bits(),
+            mode,
+            filters_enabled: self.filters_enabled,
+            included_files: self.included_files_editor.read(cx).text(cx),
+            excluded_files: self.excluded_files_editor.read(cx).text(cx),
+        })
+        .log_err()
     }
 
     fn deserialize(
-        _project: Model<Project>,
+        project: Model<Project>,
         _workspace: WeakView<Workspace>,
         _workspace_id: workspace::WorkspaceId,
         _item_id: workspace::ItemId,
-        _cx: &mut ViewContext<Pane>,
+        state: Option<String>,
+        cx: &mut ViewContext<Pane>,
     ) -> Task<anyhow::Result<View<Self>>> {
-        unimplemented!()
+        let state = match state
+            .map(|state| serde_json::from_str::<SerializedProjectSearch>(&state))
+            .transpose()
+        {
+            Ok(state) => state,
+            Err(error) => return Task::ready(Err(error.into())),
+        };
+
+        let settings = state.as_ref().map(|state| ProjectSearchSettings {
+            search_options: SearchOptions::from_bits_truncate(state.search_options),
+            filters_enabled: state.filters_enabled,
+            current_mode: match state.mode {
+                SerializedSearchMode::Text => SearchMode::Text,
+                SerializedSearchMode::Semantic => SearchMode::Semantic,
+                SerializedSearchMode::Regex => SearchMode::Regex,
+            },
+        });
+        let model = cx.new_model(|cx| ProjectSearch::new(project, cx));
+        let view = cx.new_view(|cx| {
+            let mut view = ProjectSearchView::new(model, cx, settings);
+            if let Some(state) = state {
+                view.set_query(&state.query, cx);
+                view.included_files_editor
+                    .update(cx, |editor, cx| editor.set_text(state.included_files, cx));
+                view.excluded_files_editor
+                    .update(cx, |editor, cx| editor.set_text(state.excluded_files, cx));
+                if !state.query.is_empty() {
+                    view.search(cx);
+                }
+            }
+            view
+        });
+        Task::ready(Ok(view))
     }
 }
 
@@ -637,6 +716,7 @@ impl ProjectSearchView {
                 self.current_settings(),
             );
         });
+        cx.emit(ViewEvent::QueryChanged);
     }
 
     fn current_settings(&self) -> ProjectSearchSettings {
@@ -654,6 +734,7 @@ impl ProjectSearchView {
                 self.current_settings(),
             );
         });
+        cx.emit(ViewEvent::QueryChanged);
     }
 
     fn index_project(&mut self, cx: &mut ViewContext<Self>) {
@@ -729,6 +810,7 @@ impl ProjectSearchView {
         self.clear_search(cx);
         self.current_mode = mode;
         self.active_match_index = None;
+        cx.emit(ViewEvent::QueryChanged);
 
         match mode {
             SearchMode::Semantic => {
@@ -836,6 +918,18 @@ impl ProjectSearchView {
         }
     }
 
+    /// Creates a search view with a fresh search over `project`, using the
+    /// options last used for that project.
+    pub(crate) fn new_empty(project: Model<Project>, cx: &mut ViewContext<Self>) -> Self {
+        let settings = cx
+            .global::<ActiveSettings>()
+            .0
+            .get(&project.downgrade())
+            .cloned();
+        let model = cx.new_model(|cx| ProjectSearch::new(project, cx));
+        Self::new(model, cx, settings)
+    }
+
     fn new(
         model: Model<ProjectSearch>,
         cx: &mut ViewContext<Self>,
@@ -879,6 +973,9 @@ impl ProjectSearchView {
         // Subscribe to query_editor in order to reraise editor events for workspace item activation purposes
         subscriptions.push(
             cx.subscribe(&query_editor, |_, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    cx.emit(ViewEvent::QueryChanged);
+                }
                 cx.emit(ViewEvent::EditorEvent(event.clone()))
             }),
         );
@@ -914,11 +1011,15 @@ impl ProjectSearchView {
             editor
         });
         // Subscribe to include_files_editor in order to reraise editor events for workspace item activation purposes
-        subscriptions.push(
-            cx.subscribe(&included_files_editor, |_, _, event: &EditorEvent, cx| {
+        subscriptions.push(cx.subscribe(
+            &included_files_editor,
+            |_, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    cx.emit(ViewEvent::QueryChanged);
+                }
                 cx.emit(ViewEvent::EditorEvent(event.clone()))
-            }),
-        );
+            },
+        ));
 
         let excluded_files_editor = cx.new_view(|cx| {
             let mut editor = Editor::single_line(cx);
@@ -927,11 +1028,15 @@ impl ProjectSearchView {
             editor
         });
         // Subscribe to excluded_files_editor in order to reraise editor events for workspace item activation purposes
-        subscriptions.push(
-            cx.subscribe(&excluded_files_editor, |_, _, event: &EditorEvent, cx| {
+        subscriptions.push(cx.subscribe(
+            &excluded_files_editor,
+            |_, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    cx.emit(ViewEvent::QueryChanged);
+                }
                 cx.emit(ViewEvent::EditorEvent(event.clone()))
-            }),
-        );
+            },
+        ));
 
         let focus_handle = cx.focus_handle();
         subscriptions.push(cx.on_focus_in(&focus_handle, |this, cx| {
@@ -1257,7 +1362,7 @@ impl ProjectSearchView {
         }
     }
 
-    fn focus_query_editor(&mut self, cx: &mut ViewContext<Self>) {
+    pub(crate) fn focus_query_editor(&mut self, cx: &mut ViewContext<Self>) {
         self.query_editor.update(cx, |query_editor, cx| {
             query_editor.select_all(&SelectAll, cx);
         });
@@ -2100,6 +2205,7 @@ pub mod tests {
     use semantic_index::semantic_index_settings::SemanticIndexSettings;
     use serde_json::json;
     use settings::{Settings, SettingsStore};
+    use std::{cell::Cell, rc::Rc};
     use workspace::DeploySearch;
 
     #[gpui::test]
@@ -3378,6 +3484,94 @@ pub mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_project_search_serialization(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+        let search = cx.new_model(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|cx| ProjectSearchView::new(search.clone(), cx, None));
+
+        let query_changes = Rc::new(Cell::new(0));
+        cx.update(|cx| {
+            let query_changes = query_changes.clone();
+            cx.subscribe(
+                &search_view.root_view(cx).unwrap(),
+                move |_, event: &ViewEvent, _| {
+                    if let ViewEvent::QueryChanged = event {
+                        query_changes.set(query_changes.get() + 1);
+                    }
+                },
+            )
+            .detach();
+        });
+
+        let state = search_view
+            .update(cx, |search_view, cx| {
+                search_view
+                    .query_editor
+                    .update(cx, |query_editor, cx| query_editor.set_text("ONE", cx));
+                search_view.toggle_filters(cx);
+                search_view
+                    .included_files_editor
+                    .update(cx, |editor, cx| editor.set_text("*.rs", cx));
+                search_view.toggle_search_option(SearchOptions::CASE_SENSITIVE, cx);
+                search_view.activate_search_mode(SearchMode::Regex, cx);
+                search_view.serialize(cx).unwrap()
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        assert!(
+            query_changes.get() >= 5,
+            "every query, filter and option change should ask for serialization"
+        );
+
+        let window = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let restored = window
+            .update(cx, |workspace, cx| {
+                let weak_workspace = cx.view().downgrade();
+                workspace.active_pane().update(cx, |_, cx| {
+                    ProjectSearchView::deserialize(
+                        project.clone(),
+                        weak_workspace,
+                        0,
+                        0,
+                        Some(state.clone()),
+                        cx,
+                    )
+                })
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.background_executor.run_until_parked();
+
+        window
+            .update(cx, |_, cx| {
+                restored.update(cx, |restored, cx| {
+                    assert_eq!(restored.query_editor.read(cx).text(cx), "ONE");
+                    assert_eq!(restored.included_files_editor.read(cx).text(cx), "*.rs");
+                    assert_eq!(restored.excluded_files_editor.read(cx).text(cx), "");
+                    assert!(restored.filters_enabled);
+                    assert!(restored
+                        .search_options
+                        .contains(SearchOptions::CASE_SENSITIVE));
+                    assert_eq!(restored.current_mode, SearchMode::Regex);
+                    assert_eq!(restored.serialize(cx), Some(state.clone()));
+                });
+            })
+            .unwrap();
+    }
+
     pub fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
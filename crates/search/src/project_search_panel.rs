@@ -0,0 +1,378 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use gpui::{
+    actions, AppContext, AsyncWindowContext, EventEmitter, FocusHandle, FocusableView, Pixels,
+    Subscription, Task, View, ViewContext, VisualContext, WeakView, WindowContext,
+};
+use project::Fs;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, IconName};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    pane,
+    panels::{register_panel, PanelPlacement},
+    Pane, Workspace,
+};
+
+use crate::{project_search::ProjectSearchBar, ProjectSearchView};
+
+const PROJECT_SEARCH_PANEL_KEY: &'static str = "ProjectSearchPanel";
+
+actions!(project_search_panel, [ToggleFocus]);
+
+pub fn init(cx: &mut AppContext) {
+    ProjectSearchPanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), ProjectSearchPanel::load, cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<ProjectSearchPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProjectSearchPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct ProjectSearchPanelSettingsContent {
+    /// Whether to show the project search panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the project search panel.
+    ///
+    /// Default: bottom
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels, when docked to the left or right.
+    ///
+    /// Default: 480
+    pub default_width: Option<f32>,
+    /// Default height of the panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+}
+
+impl Settings for ProjectSearchPanelSettings {
+    const KEY: Option<&'static str> = Some("project_search_panel");
+    type FileContent = ProjectSearchPanelSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _: &mut AppContext,
+    ) -> Result<Self> {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
+
+/// A dock panel hosting project searches. Results open in the center panes, so the
+/// search stays visible while stepping through its matches.
+pub struct ProjectSearchPanel {
+    pane: View<Pane>,
+    fs: Arc<dyn Fs>,
+    workspace: WeakView<Workspace>,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedProjectSearchPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl ProjectSearchPanel {
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let pane = cx.new_view(|cx| {
+            let mut pane = Pane::new(
+                workspace.weak_handle(),
+                workspace.project().clone(),
+                Default::default(),
+                None,
+                cx,
+            );
+            pane.set_can_split(false, cx);
+            pane.set_can_navigate(false, cx);
+            pane.display_nav_history_buttons(false);
+            let project_search_bar = cx.new_view(|_| ProjectSearchBar::new());
+            pane.toolbar()
+                .update(cx, |toolbar, cx| toolbar.add_item(project_search_bar, cx));
+            pane
+        });
+        let _subscriptions = vec![
+            cx.observe(&pane, |_, _, cx| cx.notify()),
+            cx.subscribe(&pane, Self::handle_pane_event),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+
+        Self {
+            pane,
+            fs: workspace.app_state().fs.clone(),
+            workspace: workspace.weak_handle(),
+            width: None,
+            height: None,
+            _subscriptions,
+        }
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(PROJECT_SEARCH_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedProjectSearchPanel>(
+                    &panel,
+                )?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = cx.new_view(|cx| Self::new(workspace, cx));
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedProjectSearchPanel {
+            width: self.width,
+            height: self.height,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(PROJECT_SEARCH_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    fn handle_pane_event(
+        &mut self,
+        pane: View<Pane>,
+        event: &pane::Event,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            pane::Event::Remove => cx.emit(PanelEvent::Close),
+            pane::Event::ZoomIn => cx.emit(PanelEvent::ZoomIn),
+            pane::Event::ZoomOut => cx.emit(PanelEvent::ZoomOut),
+            pane::Event::AddItem { item } => {
+                if let Some(workspace) = self.workspace.upgrade() {
+                    workspace.update(cx, |workspace, cx| item.added_to_pane(workspace, pane, cx))
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_search(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+        let search = cx.new_view(|cx| ProjectSearchView::new_empty(project, cx));
+        self.pane.update(cx, |pane, cx| {
+            pane.add_item(Box::new(search.clone()), true, true, None, cx);
+        });
+        search.update(cx, |search, cx| search.focus_query_editor(cx));
+    }
+}
+
+impl EventEmitter<PanelEvent> for ProjectSearchPanel {}
+
+impl Render for ProjectSearchPanel {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        div().size_full().child(self.pane.clone())
+    }
+}
+
+impl FocusableView for ProjectSearchPanel {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.pane.focus_handle(cx)
+    }
+}
+
+impl Panel for ProjectSearchPanel {
+    fn persistent_name() -> &'static str {
+        "ProjectSearchPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        ProjectSearchPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<ProjectSearchPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = ProjectSearchPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn is_zoomed(&self, cx: &WindowContext) -> bool {
+        self.pane.read(cx).is_zoomed()
+    }
+
+    fn set_zoomed(&mut self, zoomed: bool, cx: &mut ViewContext<Self>) {
+        self.pane.update(cx, |pane, cx| pane.set_zoomed(zoomed, cx));
+    }
+
+    fn set_active(&mut self, active: bool, cx: &mut ViewContext<Self>) {
+        if active && self.pane.read(cx).items_len() == 0 {
+            self.add_search(cx);
+        }
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        ProjectSearchPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::MagnifyingGlass)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Project Search Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn panes(&self) -> Vec<View<Pane>> {
+        vec![self.pane.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_search::{self, tests::init_test};
+    use editor::{actions::OpenExcerpts, Editor};
+    use gpui::TestAppContext;
+    use menu::Confirm;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+
+    #[gpui::test]
+    async fn test_searching_from_the_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| ProjectSearchPanelSettings::register(cx));
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| ProjectSearchPanel::new(workspace, cx));
+            workspace.add_panel(panel.clone(), cx);
+            panel
+        });
+        let center_pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        // Opening the panel starts a new search, ready for a query to be typed.
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_panel_focus::<ProjectSearchPanel>(cx)
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            let pane = panel.pane.read(cx);
+            assert_eq!(pane.items_len(), 1);
+            assert!(pane
+                .active_item()
+                .and_then(|item| item.downcast::<ProjectSearchView>())
+                .is_some());
+        });
+        cx.simulate_input("ONE");
+        cx.dispatch_action(Confirm);
+        cx.run_until_parked();
+
+        // Moving to the results only succeeds once there are matches, and opening
+        // one shows it in the center, leaving the search in the panel.
+        cx.dispatch_action(project_search::ToggleFocus);
+        cx.dispatch_action(OpenExcerpts);
+        cx.run_until_parked();
+        center_pane.update(cx, |pane, cx| {
+            let editor = pane
+                .active_item()
+                .and_then(|item| item.downcast::<Editor>())
+                .expect("expected the result to be opened in the center");
+            assert!(editor.read(cx).buffer().read(cx).is_singleton());
+        });
+        panel.update(cx, |panel, cx| {
+            assert_eq!(panel.pane.read(cx).items_len(), 1);
+            assert!(panel
+                .pane
+                .read(cx)
+                .active_item()
+                .unwrap()
+                .downcast::<ProjectSearchView>()
+                .is_some());
+        });
+
+        // Reopening the panel keeps the existing search rather than starting another.
+        workspace.update(cx, |workspace, cx| {
+            workspace.close_all_docks(cx);
+            workspace.toggle_panel_focus::<ProjectSearchPanel>(cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(panel.pane.read(cx).items_len(), 1);
+        });
+    }
+}
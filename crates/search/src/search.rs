@@ -11,12 +11,14 @@ pub mod buffer_search;
 mod history;
 mod mode;
 pub mod project_search;
+pub mod project_search_panel;
 pub(crate) mod search_bar;
 
 pub fn init(cx: &mut AppContext) {
     menu::init();
     buffer_search::init(cx);
     project_search::init(cx);
+    project_search_panel::init(cx);
 }
 
 actions!(
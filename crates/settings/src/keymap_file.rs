@@ -1,7 +1,9 @@
 use crate::{settings_store::parse_json_with_comments, SettingsAssets};
 use anyhow::{anyhow, Context, Result};
-use collections::BTreeMap;
-use gpui::{Action, AppContext, KeyBinding, SharedString};
+use collections::{BTreeMap, HashMap};
+use gpui::{
+    Action, AppContext, Global, KeyBinding, KeyBindingContextPredicate, Keystroke, SharedString,
+};
 use schemars::{
     gen::{SchemaGenerator, SchemaSettings},
     schema::{InstanceType, Schema, SchemaObject, SingleOrVec, SubschemaValidation},
@@ -55,47 +57,120 @@ impl KeymapFile {
             let bindings = bindings
                 .into_iter()
                 .filter_map(|(keystroke, action)| {
-                    let action = action.0;
-
-                    // This is a workaround for a limitation in serde: serde-rs/json#497
-                    // We want to deserialize the action data as a `RawValue` so that we can
-                    // deserialize the action itself dynamically directly from the JSON
-                    // string. But `RawValue` currently does not work inside of an untagged enum.
-                    match action {
-                        Value::Array(items) => {
-                            let Ok([name, data]): Result<[serde_json::Value; 2], _> =
-                                items.try_into()
-                            else {
-                                return Some(Err(anyhow!("Expected array of length 2")));
-                            };
-                            let serde_json::Value::String(name) = name else {
-                                return Some(Err(anyhow!(
-                                    "Expected first item in array to be a string."
-                                )));
-                            };
-                            cx.build_action(&name, Some(data))
-                        }
-                        Value::String(name) => cx.build_action(&name, None),
-                        Value::Null => Ok(no_action()),
-                        _ => {
-                            return Some(Err(anyhow!("Expected two-element array, got {action:?}")))
-                        }
-                    }
-                    .with_context(|| {
-                        format!(
-                            "invalid binding value for keystroke {keystroke}, context {context:?}"
-                        )
-                    })
-                    .log_err()
-                    .map(|action| KeyBinding::load(&keystroke, action, context.as_deref()))
+                    let action_value = action.0;
+                    build_action(&action_value, cx)
+                        .with_context(|| {
+                            format!(
+                                "invalid binding value for keystroke {keystroke}, context {context:?}"
+                            )
+                        })
+                        .log_err()
+                        .map(|action| {
+                            KeyBinding::load(&keystroke, action, context.as_deref())
+                                .map(|binding| (binding, action_value))
+                        })
                 })
                 .collect::<Result<Vec<_>>>()?;
 
-            cx.bind_keys(bindings);
+            let action_values = cx.default_global::<BindingActionValues>();
+            for (binding, action_value) in &bindings {
+                let values = action_values
+                    .0
+                    .entry((
+                        binding.keystrokes().to_vec(),
+                        binding.context_predicate().cloned(),
+                    ))
+                    .or_default();
+                if !values.contains(action_value) {
+                    values.push(action_value.clone());
+                }
+            }
+            cx.bind_keys(bindings.into_iter().map(|(binding, _)| binding));
         }
         Ok(())
     }
 
+    /// The JSON that the action of `binding` was written as in the keymap file it was
+    /// loaded from, including the action's data, so that the binding can be written
+    /// back to a keymap file. Returns `None` for bindings that weren't loaded from one.
+    pub fn action_value(binding: &KeyBinding, cx: &AppContext) -> Option<Value> {
+        cx.try_global::<BindingActionValues>()?
+            .0
+            .get(&(
+                binding.keystrokes().to_vec(),
+                binding.context_predicate().cloned(),
+            ))?
+            .iter()
+            .rev()
+            .find(|value| {
+                build_action(value, cx).map_or(false, |action| action.partial_eq(binding.action()))
+            })
+            .cloned()
+    }
+
+    /// Appends a block of `bindings` for `context` to the end of the keymap file `text`,
+    /// keeping its existing contents and comments as they are. Since later blocks take
+    /// precedence, the new bindings override any earlier ones for the same keystrokes.
+    /// A binding to [`Value::Null`] unbinds its keystrokes.
+    pub fn append_block_to_text(
+        text: &str,
+        context: Option<&str>,
+        bindings: &[(String, Value)],
+    ) -> Result<String> {
+        let text = if text.trim().is_empty() { "[]" } else { text };
+        let block_count = parse_json_with_comments::<Vec<Value>>(text)
+            .context("the keymap file isn't a valid JSON array")?
+            .len();
+        let end = text
+            .rfind(']')
+            .ok_or_else(|| anyhow!("the keymap file doesn't end with a ']'"))?;
+
+        let mut block = serde_json::Map::new();
+        if let Some(context) = context {
+            block.insert("context".into(), context.into());
+        }
+        block.insert(
+            "bindings".into(),
+            Value::Object(bindings.iter().cloned().collect()),
+        );
+        let block = serde_json::to_string_pretty(&Value::Object(block))?;
+
+        // The separating comma goes right after the last block, before any comment
+        // that follows it on the same line.
+        let (before, after) = text.split_at(end);
+        let before = before.trim_end();
+        let mut last_value_end = 0;
+        let mut line_start = 0;
+        for line in before.split_inclusive('\n') {
+            let code = line.split("//").next().unwrap_or_default().trim_end();
+            if !code.trim_start().is_empty() {
+                last_value_end = line_start + code.len();
+            }
+            line_start += line.len();
+        }
+        let (before_comma, after_comma) = before.split_at(last_value_end);
+
+        let mut result = String::from(before_comma);
+        if block_count > 0 && !before_comma.ends_with(',') {
+            result.push(',');
+        }
+        result.push_str(after_comma);
+        for line in block.lines() {
+            result.push_str("\n  ");
+            result.push_str(line);
+        }
+        result.push('\n');
+        result.push_str(after);
+
+        let new_block_count = parse_json_with_comments::<Vec<Value>>(&result)
+            .ok()
+            .map(|blocks| blocks.len());
+        if new_block_count != Some(block_count + 1) {
+            return Err(anyhow!("failed to append the bindings to the keymap file"));
+        }
+        Ok(result)
+    }
+
     pub fn generate_json_schema(action_names: &[SharedString]) -> serde_json::Value {
         let mut root_schema = SchemaSettings::draft07()
             .with(|settings| settings.option_add_null_type = false)
@@ -137,6 +212,36 @@ impl KeymapFile {
     }
 }
 
+/// The JSON values of the actions bound to each keystroke sequence in each context, as
+/// they were written in the keymap files.
+#[derive(Default)]
+struct BindingActionValues(
+    HashMap<(Vec<Keystroke>, Option<KeyBindingContextPredicate>), Vec<Value>>,
+);
+
+impl Global for BindingActionValues {}
+
+fn build_action(action: &Value, cx: &AppContext) -> Result<Box<dyn Action>> {
+    // This is a workaround for a limitation in serde: serde-rs/json#497
+    // We want to deserialize the action data as a `RawValue` so that we can
+    // deserialize the action itself dynamically directly from the JSON
+    // string. But `RawValue` currently does not work inside of an untagged enum.
+    match action {
+        Value::Array(items) => {
+            let [name, data] = items.as_slice() else {
+                return Err(anyhow!("Expected array of length 2"));
+            };
+            let Value::String(name) = name else {
+                return Err(anyhow!("Expected first item in array to be a string."));
+            };
+            cx.build_action(name, Some(data.clone()))
+        }
+        Value::String(name) => cx.build_action(name, None),
+        Value::Null => Ok(no_action()),
+        _ => Err(anyhow!("Expected two-element array, got {action:?}")),
+    }
+}
+
 fn no_action() -> Box<dyn gpui::Action> {
     gpui::NoAction.boxed_clone()
 }
@@ -160,4 +265,53 @@ mod tests {
         };
         KeymapFile::parse(json).unwrap();
     }
+
+    #[test]
+    fn test_append_block_to_text() {
+        let bindings = [
+            ("cmd-k t".to_string(), Value::from("workspace::NewTerminal")),
+            ("ctrl-`".to_string(), Value::Null),
+        ];
+
+        let text = KeymapFile::append_block_to_text("", Some("Workspace"), &bindings).unwrap();
+        assert_eq!(
+            text,
+            indoc::indoc! {r#"
+                [
+                  {
+                    "context": "Workspace",
+                    "bindings": {
+                      "cmd-k t": "workspace::NewTerminal",
+                      "ctrl-`": null
+                    }
+                  }
+                ]"#}
+        );
+
+        let text = KeymapFile::append_block_to_text(
+            indoc::indoc! {r#"
+                [
+                  // Existing bindings
+                  { "bindings": { "up": "menu::SelectPrev" } } // no trailing comma
+                ]
+            "#},
+            None,
+            &bindings[..1],
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            indoc::indoc! {r#"
+                [
+                  // Existing bindings
+                  { "bindings": { "up": "menu::SelectPrev" } }, // no trailing comma
+                  {
+                    "bindings": {
+                      "cmd-k t": "workspace::NewTerminal"
+                    }
+                  }
+                ]
+            "#}
+        );
+    }
 }
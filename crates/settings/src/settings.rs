@@ -2,13 +2,30 @@ mod keymap_file;
 mod settings_file;
 mod settings_store;
 
+use gpui::{actions, impl_actions};
 use rust_embed::RustEmbed;
+use serde_derive::Deserialize;
 use std::{borrow::Cow, str};
 use util::asset_str;
 
 pub use keymap_file::KeymapFile;
 pub use settings_file::*;
-pub use settings_store::{Settings, SettingsJsonSchemaParams, SettingsStore};
+pub use settings_store::{
+    parse_json_with_comments, Settings, SettingsJsonSchemaParams, SettingsStore,
+};
+
+/// Activates one of the profiles defined in the `profiles` section of the user
+/// settings, or goes back to the plain user settings when no profile is given.
+#[derive(Clone, Default, Deserialize, PartialEq)]
+pub struct SwitchProfile {
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+impl_actions!(settings, [SwitchProfile]);
+
+// `SelectProfile` opens a picker listing the settings profiles.
+actions!(settings, [SelectProfile]);
 
 #[derive(RustEmbed)]
 #[folder = "../../assets"]
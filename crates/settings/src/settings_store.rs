@@ -126,12 +126,17 @@ pub struct SettingsJsonSchemaParams<'a> {
     pub font_names: &'a [String],
 }
 
+/// The key of the user settings object holding named sets of overrides,
+/// which can be layered on top of the user settings by activating them.
+const PROFILES_KEY: &str = "profiles";
+
 /// A set of strongly-typed setting values defined via multiple JSON files.
 pub struct SettingsStore {
     setting_values: HashMap<TypeId, Box<dyn AnySettingValue>>,
     raw_default_settings: serde_json::Value,
     raw_user_settings: serde_json::Value,
     raw_local_settings: BTreeMap<(usize, Arc<Path>), serde_json::Value>,
+    active_profile: Option<String>,
     tab_size_callback: Option<(
         TypeId,
         Box<dyn Fn(&dyn Any) -> Option<usize> + Send + Sync + 'static>,
@@ -147,6 +152,7 @@ impl Default for SettingsStore {
             raw_default_settings: serde_json::json!({}),
             raw_user_settings: serde_json::json!({}),
             raw_local_settings: Default::default(),
+            active_profile: None,
             tab_size_callback: Default::default(),
         }
     }
@@ -220,6 +226,17 @@ impl SettingsStore {
                 }
             }
 
+            if let Some(profile_settings) =
+                profile_settings(&self.raw_user_settings, self.active_profile.as_deref())
+            {
+                if let Some(profile_settings) = setting_value
+                    .deserialize_setting(profile_settings)
+                    .log_err()
+                {
+                    user_values_stack.push(profile_settings);
+                }
+            }
+
             if let Some(setting) = setting_value
                 .load_setting(&default_settings, &user_values_stack, cx)
                 .context("A default setting must be added to the `default.json` file")
@@ -261,6 +278,36 @@ impl SettingsStore {
         &self.raw_user_settings
     }
 
+    /// The names of the settings profiles defined in the user settings.
+    pub fn profile_names(&self) -> impl '_ + Iterator<Item = &str> {
+        self.raw_user_settings
+            .get(PROFILES_KEY)
+            .and_then(|profiles| profiles.as_object())
+            .into_iter()
+            .flat_map(|profiles| profiles.keys().map(String::as_str))
+    }
+
+    /// The profile whose overrides are currently layered on top of the user settings.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_deref()
+    }
+
+    /// Layers the overrides of the given profile on top of the user settings,
+    /// or goes back to the plain user settings when `None` is given.
+    pub fn set_active_profile(
+        &mut self,
+        profile: Option<String>,
+        cx: &mut AppContext,
+    ) -> Result<()> {
+        if let Some(profile) = &profile {
+            if profile_settings(&self.raw_user_settings, Some(profile)).is_none() {
+                return Err(anyhow!("no settings profile named {profile:?}"));
+            }
+        }
+        self.active_profile = profile;
+        self.recompute_values(None, cx)
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn test(cx: &mut AppContext) -> Self {
         let mut this = Self::default();
@@ -395,6 +442,10 @@ impl SettingsStore {
         let settings: serde_json::Value = parse_json_with_comments(user_settings_content)?;
         if settings.is_object() {
             self.raw_user_settings = settings;
+            // Fall back to the plain user settings when the active profile was removed.
+            if profile_settings(&self.raw_user_settings, self.active_profile.as_deref()).is_none() {
+                self.active_profile = None;
+            }
             self.recompute_values(None, cx)?;
             Ok(())
         } else {
@@ -440,7 +491,7 @@ impl SettingsStore {
     ) -> serde_json::Value {
         use schemars::{
             gen::SchemaSettings,
-            schema::{Schema, SchemaObject},
+            schema::{InstanceType, ObjectValidation, Schema, SchemaObject},
         };
 
         let settings = SchemaSettings::draft07().with(|settings| {
@@ -507,6 +558,20 @@ impl SettingsStore {
             }
         }
 
+        let profile_schema = SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                additional_properties: Some(Box::new(combined_schema.schema.clone().into())),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        combined_schema
+            .schema
+            .object()
+            .properties
+            .insert(PROFILES_KEY.to_string(), profile_schema.into());
+
         for release_stage in ["dev", "nightly", "stable", "preview"] {
             let schema = combined_schema.schema.clone();
             combined_schema
@@ -554,6 +619,18 @@ impl SettingsStore {
                 }
             }
 
+            if let Some(profile_settings) =
+                profile_settings(&self.raw_user_settings, self.active_profile.as_deref())
+            {
+                if let Some(profile_settings) = setting_value
+                    .deserialize_setting(profile_settings)
+                    .log_err()
+                {
+                    user_settings_stack.push(profile_settings);
+                    paths_stack.push(None);
+                }
+            }
+
             // If the global settings file changed, reload the global value for the field.
             if changed_local_path.is_none() {
                 if let Some(value) = setting_value
@@ -605,6 +682,13 @@ impl SettingsStore {
     }
 }
 
+fn profile_settings<'a>(
+    raw_user_settings: &'a serde_json::Value,
+    profile: Option<&str>,
+) -> Option<&'a serde_json::Value> {
+    raw_user_settings.get(PROFILES_KEY)?.get(profile?)
+}
+
 impl Debug for SettingsStore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SettingsStore")
@@ -619,6 +703,7 @@ impl Debug for SettingsStore {
             .field("default_settings", &self.raw_default_settings)
             .field("user_settings", &self.raw_user_settings)
             .field("local_settings", &self.raw_local_settings)
+            .field("active_profile", &self.active_profile)
             .finish_non_exhaustive()
     }
 }
@@ -1085,6 +1170,94 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_settings_profiles(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store.register_setting::<UserSettings>(cx);
+        store.register_setting::<TurboSetting>(cx);
+        store
+            .set_default_settings(
+                r#"{
+                    "turbo": false,
+                    "user": {
+                        "name": "John Doe",
+                        "age": 30,
+                        "staff": false
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        store
+            .set_user_settings(
+                r#"{
+                    "user": { "age": 31 },
+                    "profiles": {
+                        "streaming": {
+                            "turbo": true,
+                            "user": { "name": "Anonymous" }
+                        },
+                        "work": {}
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+
+        assert_eq!(
+            store.profile_names().collect::<Vec<_>>(),
+            vec!["streaming", "work"]
+        );
+        assert_eq!(store.active_profile(), None);
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(false));
+
+        store
+            .set_active_profile(Some("streaming".into()), cx)
+            .unwrap();
+        assert_eq!(store.active_profile(), Some("streaming"));
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(true));
+        assert_eq!(
+            store.get::<UserSettings>(None),
+            &UserSettings {
+                name: "Anonymous".to_string(),
+                age: 31,
+                staff: false,
+            }
+        );
+
+        // Unknown profiles are rejected, leaving the active profile in place.
+        assert!(store.set_active_profile(Some("gaming".into()), cx).is_err());
+        assert_eq!(store.active_profile(), Some("streaming"));
+
+        store.set_active_profile(None, cx).unwrap();
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(false));
+        assert_eq!(
+            store.get::<UserSettings>(None),
+            &UserSettings {
+                name: "John Doe".to_string(),
+                age: 31,
+                staff: false,
+            }
+        );
+
+        // Removing the active profile from the settings file goes back to the defaults.
+        store
+            .set_active_profile(Some("streaming".into()), cx)
+            .unwrap();
+        store
+            .set_user_settings(r#"{ "profiles": { "work": {} } }"#, cx)
+            .unwrap();
+        assert_eq!(store.active_profile(), None);
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(false));
+
+        // Other edits leave the active profile in place.
+        store.set_active_profile(Some("work".into()), cx).unwrap();
+        store
+            .set_user_settings(r#"{ "turbo": true, "profiles": { "work": {} } }"#, cx)
+            .unwrap();
+        assert_eq!(store.active_profile(), Some("work"));
+    }
+
     #[gpui::test]
     fn test_setting_store_update(cx: &mut AppContext) {
         let mut store = SettingsStore::default();
@@ -0,0 +1,205 @@
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, Task, View,
+    ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use settings::{SelectProfile, SettingsStore};
+use std::{iter, sync::Arc};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+/// How the plain user settings, without any profile layered on top, are listed.
+const DEFAULT_SETTINGS_LABEL: &str = "Default Settings";
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(
+        |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
+            workspace.register_action(|workspace, _: &SelectProfile, cx| {
+                workspace.toggle_modal(cx, SettingsProfileSelector::new);
+            });
+        },
+    )
+    .detach();
+}
+
+pub struct SettingsProfileSelector {
+    picker: View<Picker<SettingsProfileSelectorDelegate>>,
+}
+
+impl SettingsProfileSelector {
+    fn new(cx: &mut ViewContext<Self>) -> Self {
+        let delegate = SettingsProfileSelectorDelegate::new(cx.view().downgrade(), cx);
+        let picker = cx.new_view(|cx| Picker::new(delegate, cx));
+        Self { picker }
+    }
+}
+
+impl Render for SettingsProfileSelector {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl FocusableView for SettingsProfileSelector {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for SettingsProfileSelector {}
+impl ModalView for SettingsProfileSelector {}
+
+pub struct SettingsProfileSelectorDelegate {
+    view: WeakView<SettingsProfileSelector>,
+    /// The profiles to pick from, starting with `None` for the plain user settings.
+    profiles: Vec<Option<String>>,
+    active_profile: Option<String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl SettingsProfileSelectorDelegate {
+    fn new(view: WeakView<SettingsProfileSelector>, cx: &AppContext) -> Self {
+        let store = cx.global::<SettingsStore>();
+        let profiles = iter::once(None)
+            .chain(store.profile_names().map(|name| Some(name.to_string())))
+            .collect::<Vec<_>>();
+        let active_profile = store.active_profile().map(ToString::to_string);
+        let matches = profiles
+            .iter()
+            .enumerate()
+            .map(|(ix, profile)| StringMatch {
+                candidate_id: ix,
+                score: 0.0,
+                positions: Vec::new(),
+                string: profile_label(profile).to_string(),
+            })
+            .collect();
+        let selected_index = profiles
+            .iter()
+            .position(|profile| *profile == active_profile)
+            .unwrap_or(0);
+        Self {
+            view,
+            profiles,
+            active_profile,
+            matches,
+            selected_index,
+        }
+    }
+}
+
+fn profile_label(profile: &Option<String>) -> &str {
+    profile.as_deref().unwrap_or(DEFAULT_SETTINGS_LABEL)
+}
+
+impl PickerDelegate for SettingsProfileSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self) -> Arc<str> {
+        "Select settings profile...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(id, profile)| StringMatchCandidate {
+                id,
+                char_bag: profile_label(profile).into(),
+                string: profile_label(profile).to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = this
+                    .delegate
+                    .selected_index
+                    .min(this.delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        if let Some(profile_match) = self.matches.get(self.selected_index) {
+            let profile = self.profiles[profile_match.candidate_id].clone();
+            cx.update_global(|store: &mut SettingsStore, cx| store.set_active_profile(profile, cx))
+                .log_err();
+        }
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.view
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let profile_match = &self.matches[ix];
+        let is_active = self.profiles[profile_match.candidate_id] == self.active_profile;
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(HighlightedLabel::new(
+                    profile_match.string.clone(),
+                    profile_match.positions.clone(),
+                ))
+                .end_slot::<Icon>(is_active.then(|| {
+                    Icon::new(IconName::Check)
+                        .size(IconSize::Small)
+                        .color(Color::Muted)
+                })),
+        )
+    }
+}
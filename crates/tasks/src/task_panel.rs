@@ -0,0 +1,748 @@
+use std::{collections::VecDeque, path::PathBuf, process::Stdio, sync::Arc};
+
+use anyhow::{Context, Result};
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt};
+use gpui::{
+    actions, AppContext, AsyncWindowContext, EventEmitter, FocusHandle, FocusableView,
+    SharedString, Subscription, Task, View, WeakView,
+};
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use smol::io::{AsyncBufReadExt, BufReader};
+use ui::{prelude::*, Tooltip};
+use util::{post_inc, ResultExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+use crate::{tasks_settings::TaskPanelSettings, TaskDefinition};
+
+const TASK_PANEL_KEY: &'static str = "TaskPanel";
+const MAX_OUTPUT_LINES: usize = 2000;
+
+actions!(task_panel, [ToggleFocus]);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TaskStatus {
+    Running,
+    Succeeded,
+    Failed(Option<i32>),
+    Cancelled,
+}
+
+struct TaskRun {
+    id: usize,
+    definition: TaskDefinition,
+    cwd: Option<PathBuf>,
+    output: VecDeque<SharedString>,
+    status: TaskStatus,
+    /// Dropping this task kills the spawned process.
+    _process: Option<Task<()>>,
+}
+
+/// A spawned task process.
+struct TaskProcess {
+    /// The lines the process prints to stdout and stderr.
+    output: BoxStream<'static, Result<String>>,
+    /// Resolves to the exit code of the process once it exits, or to `None` if it's
+    /// terminated by a signal. Dropping it kills the process.
+    exit_code: BoxFuture<'static, Result<Option<i32>>>,
+}
+
+/// Spawns the processes of tasks, so that they can be faked in tests.
+trait TaskRunner: Send + Sync {
+    fn spawn(&self, definition: &TaskDefinition, cwd: Option<PathBuf>) -> Result<TaskProcess>;
+}
+
+/// Runs the command of each task with the user's shell.
+struct ShellTaskRunner;
+
+impl TaskRunner for ShellTaskRunner {
+    fn spawn(&self, definition: &TaskDefinition, cwd: Option<PathBuf>) -> Result<TaskProcess> {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        let mut process = smol::process::Command::new(shell);
+        process
+            .arg("-c")
+            .arg(&definition.command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .envs(&definition.env);
+        if let Some(cwd) = cwd {
+            process.current_dir(cwd);
+        }
+
+        let mut child = process
+            .spawn()
+            .with_context(|| format!("failed to spawn `{}`", definition.command))?;
+        let stdout = BufReader::new(child.stdout.take().context("no stdout")?).lines();
+        let stderr = BufReader::new(child.stderr.take().context("no stderr")?).lines();
+        Ok(TaskProcess {
+            output: futures::stream::select(stdout, stderr)
+                .map(|line| line.map_err(anyhow::Error::from))
+                .boxed(),
+            exit_code: async move { anyhow::Ok(child.status().await?.code()) }.boxed(),
+        })
+    }
+}
+
+/// A panel streaming the output of the tasks spawned with `task::Spawn`.
+pub struct TaskPanel {
+    fs: Arc<dyn Fs>,
+    runner: Arc<dyn TaskRunner>,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    runs: Vec<TaskRun>,
+    active_run_id: Option<usize>,
+    next_run_id: usize,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTaskPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl TaskPanel {
+    pub fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        cx.new_view(|cx: &mut ViewContext<Self>| Self {
+            fs,
+            runner: Arc::new(ShellTaskRunner),
+            width: None,
+            height: None,
+            runs: Vec::new(),
+            active_run_id: None,
+            next_run_id: 0,
+            focus_handle: cx.focus_handle(),
+            _subscriptions: vec![cx.observe_global::<SettingsStore>(|_, cx| cx.notify())],
+        })
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(TASK_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedTaskPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedTaskPanel {
+            width: self.width,
+            height: self.height,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(TASK_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    pub fn spawn_task(
+        &mut self,
+        definition: TaskDefinition,
+        cwd: Option<PathBuf>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let id = post_inc(&mut self.next_run_id);
+        let process = self.run_process(id, &definition, cwd.clone(), cx);
+        self.runs.push(TaskRun {
+            id,
+            definition,
+            cwd,
+            output: VecDeque::new(),
+            status: TaskStatus::Running,
+            _process: Some(process),
+        });
+        self.active_run_id = Some(id);
+        cx.notify();
+    }
+
+    pub(crate) fn rerun_active(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(id) = self.active_run_id {
+            self.rerun(id, cx);
+        }
+    }
+
+    fn rerun(&mut self, id: usize, cx: &mut ViewContext<Self>) {
+        let Some(run) = self.runs.iter().find(|run| run.id == id) else {
+            return;
+        };
+        let process = self.run_process(id, &run.definition, run.cwd.clone(), cx);
+        if let Some(run) = self.runs.iter_mut().find(|run| run.id == id) {
+            run.output.clear();
+            run.status = TaskStatus::Running;
+            run._process = Some(process);
+        }
+        self.active_run_id = Some(id);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, id: usize, cx: &mut ViewContext<Self>) {
+        if let Some(run) = self.runs.iter_mut().find(|run| run.id == id) {
+            if run.status == TaskStatus::Running {
+                run._process.take();
+                run.status = TaskStatus::Cancelled;
+                cx.notify();
+            }
+        }
+    }
+
+    fn remove(&mut self, id: usize, cx: &mut ViewContext<Self>) {
+        self.runs.retain(|run| run.id != id);
+        if self.active_run_id == Some(id) {
+            self.active_run_id = self.runs.last().map(|run| run.id);
+        }
+        cx.notify();
+    }
+
+    fn run_process(
+        &self,
+        id: usize,
+        definition: &TaskDefinition,
+        cwd: Option<PathBuf>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<()> {
+        let process = self.runner.spawn(definition, cwd);
+        cx.spawn(|this, mut cx| async move {
+            let result = async {
+                let TaskProcess {
+                    mut output,
+                    exit_code,
+                } = process?;
+                while let Some(line) = output.next().await {
+                    let line = line?;
+                    this.update(&mut cx, |this, cx| this.push_output(id, line.into(), cx))?;
+                }
+                exit_code.await
+            }
+            .await;
+
+            this.update(&mut cx, |this, cx| {
+                let status = match result {
+                    Ok(Some(0)) => TaskStatus::Succeeded,
+                    Ok(code) => TaskStatus::Failed(code),
+                    Err(error) => {
+                        this.push_output(id, error.to_string().into(), cx);
+                        TaskStatus::Failed(None)
+                    }
+                };
+                if let Some(run) = this.runs.iter_mut().find(|run| run.id == id) {
+                    run.status = status;
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+    }
+
+    #[cfg(test)]
+    fn set_runner(&mut self, runner: Arc<dyn TaskRunner>) {
+        self.runner = runner;
+    }
+
+    fn push_output(&mut self, id: usize, line: SharedString, cx: &mut ViewContext<Self>) {
+        if let Some(run) = self.runs.iter_mut().find(|run| run.id == id) {
+            if run.output.len() == MAX_OUTPUT_LINES {
+                run.output.pop_front();
+            }
+            run.output.push_back(line);
+            cx.notify();
+        }
+    }
+
+    fn render_run_header(&self, run: &TaskRun, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let id = run.id;
+        let (icon, color) = match run.status {
+            TaskStatus::Running => (IconName::ArrowCircle, Color::Muted),
+            TaskStatus::Succeeded => (IconName::Check, Color::Success),
+            TaskStatus::Failed(_) => (IconName::XCircle, Color::Error),
+            TaskStatus::Cancelled => (IconName::Close, Color::Muted),
+        };
+        let status = match run.status {
+            TaskStatus::Running => None,
+            TaskStatus::Succeeded => Some("exited with 0".to_string()),
+            TaskStatus::Failed(Some(code)) => Some(format!("exited with {code}")),
+            TaskStatus::Failed(None) => Some("failed".to_string()),
+            TaskStatus::Cancelled => Some("cancelled".to_string()),
+        };
+
+        h_flex()
+            .id(("task-run", id))
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .when(self.active_run_id == Some(id), |this| {
+                this.bg(cx.theme().colors().ghost_element_selected)
+            })
+            .on_click(cx.listener(move |this, _, cx| {
+                this.active_run_id = Some(id);
+                cx.notify();
+            }))
+            .child(Icon::new(icon).size(IconSize::Small).color(color))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .gap_2()
+                    .overflow_hidden()
+                    .child(Label::new(run.definition.label.clone()))
+                    .children(status.map(|status| {
+                        Label::new(status)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    })),
+            )
+            .child(
+                IconButton::new(("rerun", id), IconName::Update)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Rerun", cx))
+                    .on_click(cx.listener(move |this, _, cx| this.rerun(id, cx))),
+            )
+            .map(|this| {
+                if run.status == TaskStatus::Running {
+                    this.child(
+                        IconButton::new(("cancel", id), IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| Tooltip::text("Cancel", cx))
+                            .on_click(cx.listener(move |this, _, cx| this.cancel(id, cx))),
+                    )
+                } else {
+                    this.child(
+                        IconButton::new(("remove", id), IconName::Delete)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| Tooltip::text("Remove", cx))
+                            .on_click(cx.listener(move |this, _, cx| this.remove(id, cx))),
+                    )
+                }
+            })
+    }
+}
+
+impl Render for TaskPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let active_run = self
+            .active_run_id
+            .and_then(|id| self.runs.iter().find(|run| run.id == id));
+
+        v_flex()
+            .key_context("TaskPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .map(|this| {
+                if self.runs.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            div().flex().w_full().items_center().child(
+                                Label::new("Spawn a task with task: spawn.")
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small),
+                            ),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        v_flex()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border)
+                            .children(
+                                self.runs
+                                    .iter()
+                                    .rev()
+                                    .map(|run| self.render_run_header(run, cx)),
+                            ),
+                    )
+                }
+            })
+            .children(active_run.map(|run| {
+                v_flex()
+                    .id("task-output")
+                    .flex_1()
+                    .p_2()
+                    .overflow_y_scroll()
+                    .children(
+                        run.output
+                            .iter()
+                            .map(|line| Label::new(line.clone()).size(LabelSize::Small)),
+                    )
+            }))
+    }
+}
+
+impl FocusableView for TaskPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TaskPanel {}
+
+impl Panel for TaskPanel {
+    fn persistent_name() -> &'static str {
+        "TaskPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        TaskPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<TaskPanelSettings>(self.fs.clone(), cx, move |settings| {
+            settings.dock = Some(position)
+        });
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = TaskPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        TaskPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::Bolt)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Task Panel")
+    }
+
+    fn icon_label(&self, _: &WindowContext) -> Option<String> {
+        let failed_count = self
+            .runs
+            .iter()
+            .filter(|run| matches!(run.status, TaskStatus::Failed(_)))
+            .count();
+        (failed_count > 0).then(|| failed_count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use collections::HashMap;
+    use futures::channel::{mpsc, oneshot};
+    use gpui::{TestAppContext, VisualTestContext};
+    use parking_lot::Mutex;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use workspace::notifications::NotificationLog;
+
+    use super::*;
+    use crate::{tests::init_test, Rerun, Run};
+
+    #[gpui::test]
+    async fn test_run_task_by_label(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                ".zed": {
+                    "tasks.json": r#"[
+                        {"label": "build", "command": "make"},
+                        {"label": "test", "command": "make test"}
+                    ]"#,
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let (panel, runner) = add_task_panel(&workspace, cx);
+
+        cx.dispatch_action(Run("test".into()));
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(run_labels(panel), ["test"]);
+            assert_eq!(panel.runs[0].cwd, Some(PathBuf::from("/root")));
+        });
+        assert_eq!(runner.commands(), ["make test"]);
+        assert_eq!(runner.cwd(0), Some(PathBuf::from("/root")));
+
+        // An unknown label is reported instead of spawning anything.
+        cx.dispatch_action(Run("deploy".into()));
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| assert_eq!(run_labels(panel), ["test"]));
+        assert_eq!(runner.commands(), ["make test"]);
+        cx.update(|cx| {
+            assert!(cx
+                .global::<NotificationLog>()
+                .entries()
+                .any(|entry| entry.message.contains(r#"no task labeled "deploy""#)));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_task_output_and_status(cx: &mut TestAppContext) {
+        init_test(cx);
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let (panel, runner) = add_task_panel(&workspace, cx);
+
+        panel.update(cx, |panel, cx| {
+            let mut build = task("build", "make");
+            build.env = HashMap::from_iter([("GREETING".into(), "hello".into())]);
+            panel.spawn_task(build, None, cx);
+        });
+        assert_eq!(runner.env(0)["GREETING"], "hello");
+
+        // Output is streamed into the panel while the task is running.
+        runner.print(0, "hello");
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(panel.runs[0].output, ["hello"]);
+            assert_eq!(panel.runs[0].status, TaskStatus::Running);
+        });
+
+        // The exit code is reported once the process exits.
+        runner.print(0, "oops");
+        runner.exit(0, Some(3));
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            let run = &panel.runs[0];
+            assert_eq!(run.output, ["hello", "oops"]);
+            assert_eq!(run.status, TaskStatus::Failed(Some(3)));
+            assert_eq!(panel.icon_label(cx), Some("1".to_string()));
+        });
+
+        // Only the most recent output lines are kept.
+        panel.update(cx, |panel, cx| {
+            panel.spawn_task(task("count", "seq"), None, cx);
+        });
+        for line in 1..=MAX_OUTPUT_LINES + 5 {
+            runner.print(1, &line.to_string());
+        }
+        runner.exit(1, Some(0));
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            let run = &panel.runs[1];
+            assert_eq!(panel.active_run_id, Some(run.id));
+            assert_eq!(run.status, TaskStatus::Succeeded);
+            assert_eq!(run.output.len(), MAX_OUTPUT_LINES);
+            assert_eq!(run.output.front().unwrap(), "6");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_cancel_task(cx: &mut TestAppContext) {
+        init_test(cx);
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let (panel, runner) = add_task_panel(&workspace, cx);
+
+        let id = panel.update(cx, |panel, cx| {
+            panel.spawn_task(task("serve", "serve"), None, cx);
+            panel.runs[0].id
+        });
+        cx.run_until_parked();
+        assert!(!runner.is_killed(0));
+
+        // Cancelling kills the process, which can then be removed.
+        panel.update(cx, |panel, cx| panel.cancel(id, cx));
+        cx.run_until_parked();
+        assert!(runner.is_killed(0));
+        panel.update(cx, |panel, cx| {
+            assert_eq!(panel.runs[0].status, TaskStatus::Cancelled);
+            panel.remove(id, cx);
+            assert!(panel.runs.is_empty());
+            assert_eq!(panel.active_run_id, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_rerun_task(cx: &mut TestAppContext) {
+        init_test(cx);
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let (panel, runner) = add_task_panel(&workspace, cx);
+
+        panel.update(cx, |panel, cx| {
+            panel.spawn_task(task("build", "make"), None, cx);
+            panel.spawn_task(task("test", "make test"), None, cx);
+        });
+        runner.print(0, "building");
+        runner.exit(0, Some(0));
+        runner.print(1, "testing");
+        runner.exit(1, Some(1));
+        cx.run_until_parked();
+
+        // Rerunning replaces the output of the active run instead of adding a run.
+        cx.dispatch_action(Rerun);
+        cx.run_until_parked();
+        assert_eq!(runner.commands(), ["make", "make test", "make test"]);
+        panel.update(cx, |panel, _| {
+            assert_eq!(run_labels(panel), ["build", "test"]);
+            assert!(panel.runs[1].output.is_empty());
+            assert_eq!(panel.runs[1].status, TaskStatus::Running);
+        });
+
+        runner.print(2, "testing again");
+        runner.exit(2, Some(0));
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(panel.runs[1].output, ["testing again"]);
+            assert_eq!(panel.runs[1].status, TaskStatus::Succeeded);
+            assert_eq!(panel.runs[0].output, ["building"]);
+        });
+    }
+
+    /// Records the processes spawned by the panel, whose output and exit are driven
+    /// by the test.
+    #[derive(Default)]
+    struct FakeTaskRunner {
+        processes: Mutex<Vec<FakeProcess>>,
+    }
+
+    struct FakeProcess {
+        definition: TaskDefinition,
+        cwd: Option<PathBuf>,
+        output: Option<mpsc::UnboundedSender<Result<String>>>,
+        exit: Option<oneshot::Sender<Option<i32>>>,
+    }
+
+    impl TaskRunner for FakeTaskRunner {
+        fn spawn(&self, definition: &TaskDefinition, cwd: Option<PathBuf>) -> Result<TaskProcess> {
+            let (output_tx, output_rx) = mpsc::unbounded();
+            let (exit_tx, exit_rx) = oneshot::channel();
+            self.processes.lock().push(FakeProcess {
+                definition: definition.clone(),
+                cwd,
+                output: Some(output_tx),
+                exit: Some(exit_tx),
+            });
+            Ok(TaskProcess {
+                output: output_rx.boxed(),
+                exit_code: async move { anyhow::Ok(exit_rx.await?) }.boxed(),
+            })
+        }
+    }
+
+    impl FakeTaskRunner {
+        fn commands(&self) -> Vec<String> {
+            self.processes
+                .lock()
+                .iter()
+                .map(|process| process.definition.command.clone())
+                .collect()
+        }
+
+        fn cwd(&self, ix: usize) -> Option<PathBuf> {
+            self.processes.lock()[ix].cwd.clone()
+        }
+
+        fn env(&self, ix: usize) -> HashMap<String, String> {
+            self.processes.lock()[ix].definition.env.clone()
+        }
+
+        fn print(&self, ix: usize, line: &str) {
+            self.processes.lock()[ix]
+                .output
+                .as_ref()
+                .expect("process exited")
+                .unbounded_send(Ok(line.to_string()))
+                .unwrap();
+        }
+
+        fn exit(&self, ix: usize, code: Option<i32>) {
+            let mut processes = self.processes.lock();
+            let process = &mut processes[ix];
+            process.output.take();
+            process
+                .exit
+                .take()
+                .expect("process exited")
+                .send(code)
+                .unwrap();
+        }
+
+        /// Whether the panel dropped the process before it exited.
+        fn is_killed(&self, ix: usize) -> bool {
+            self.processes.lock()[ix]
+                .exit
+                .as_ref()
+                .map_or(false, |exit| exit.is_canceled())
+        }
+    }
+
+    fn add_task_panel(
+        workspace: &View<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> (View<TaskPanel>, Arc<FakeTaskRunner>) {
+        let runner = Arc::new(FakeTaskRunner::default());
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = TaskPanel::new(workspace, cx);
+            panel.update(cx, |panel, _| panel.set_runner(runner.clone()));
+            workspace.add_panel(panel.clone(), cx);
+            panel
+        });
+        (panel, runner)
+    }
+
+    fn run_labels(panel: &TaskPanel) -> Vec<&str> {
+        panel
+            .runs
+            .iter()
+            .map(|run| run.definition.label.as_str())
+            .collect()
+    }
+
+    fn task(label: &str, command: &str) -> TaskDefinition {
+        TaskDefinition {
+            label: label.into(),
+            command: command.into(),
+            cwd: None,
+            env: HashMap::default(),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+mod task_panel;
+mod tasks_modal;
+mod tasks_settings;
+
+use anyhow::anyhow;
+use gpui::{actions, impl_actions, AppContext, ViewContext};
+use serde_derive::Deserialize;
+use settings::Settings;
+use workspace::{
+    notifications::NotifyTaskExt,
+    panels::{register_panel, PanelPlacement},
+    Workspace,
+};
+
+pub use task_panel::TaskPanel;
+pub use tasks_settings::{TaskDefinition, TasksSettings};
+
+use tasks_modal::{spawn_in_panel, task_candidates, TaskFiles, TasksModal};
+use tasks_settings::TaskPanelSettings;
+
+actions!(task, [Spawn, Rerun]);
+
+/// Runs the task with the given label, so that it can be bound to a key, e.g.
+/// `"cmd-k b": ["task::Run", "build"]`.
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct Run(pub String);
+
+impl_actions!(task, [Run]);
+
+pub fn init(cx: &mut AppContext) {
+    TasksSettings::register(cx);
+    TaskPanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), TaskPanel::load, cx);
+    cx.set_global(TaskFiles::default());
+
+    cx.observe_new_views(
+        |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
+            workspace.register_action(|workspace, _: &Spawn, cx| {
+                TasksModal::toggle(workspace, cx);
+            });
+            workspace.register_action(run);
+            workspace.register_action(|workspace, _: &Rerun, cx| {
+                if let Some(panel) = workspace.focus_panel::<TaskPanel>(cx) {
+                    panel.update(cx, |panel, cx| panel.rerun_active(cx));
+                }
+            });
+            workspace.register_action(|workspace, _: &task_panel::ToggleFocus, cx| {
+                workspace.toggle_panel_focus::<TaskPanel>(cx);
+            });
+        },
+    )
+    .detach();
+}
+
+fn run(workspace: &mut Workspace, action: &Run, cx: &mut ViewContext<Workspace>) {
+    let label = action.0.clone();
+    let candidates = task_candidates(workspace, cx);
+    cx.spawn(|workspace, mut cx| async move {
+        let (candidates, errors) = candidates.await;
+        workspace.update(&mut cx, |workspace, cx| {
+            for error in &errors {
+                workspace.show_error(error, cx);
+            }
+        })?;
+        let candidate = candidates
+            .into_iter()
+            .find(|candidate| candidate.definition.label == label)
+            .ok_or_else(|| anyhow!("no task labeled {label:?}"))?;
+        workspace.update(&mut cx, |workspace, cx| {
+            spawn_in_panel(workspace, candidate, cx)
+        })
+    })
+    .detach_and_notify_err(cx);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use gpui::TestAppContext;
+    use project::Project;
+    use settings::SettingsStore;
+    use workspace::notifications::NotificationLog;
+
+    pub(crate) fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            cx.set_global(NotificationLog::default());
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            client::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+        });
+    }
+}
@@ -0,0 +1,455 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Global, ParentElement,
+    Render, Styled, Task, View, ViewContext, VisualContext, WeakView,
+};
+use parking_lot::Mutex;
+use picker::{Picker, PickerDelegate};
+use project::Fs;
+use settings::{parse_json_with_comments, Settings};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::{paths::LOCAL_TASKS_RELATIVE_PATH, ResultExt};
+use workspace::{ModalView, Workspace};
+
+use crate::{TaskDefinition, TaskPanel, TasksSettings};
+
+/// A task offered by the picker, along with the directory it runs in.
+#[derive(Clone)]
+pub(crate) struct TaskCandidate {
+    pub(crate) definition: TaskDefinition,
+    pub(crate) cwd: Option<PathBuf>,
+}
+
+/// The tasks parsed from each worktree's `.zed/tasks.json`, along with the file's
+/// modification time, so that it's only parsed again once it changes.
+#[derive(Default)]
+pub(crate) struct TaskFiles(Arc<Mutex<HashMap<PathBuf, (SystemTime, Vec<TaskDefinition>)>>>);
+
+impl Global for TaskFiles {}
+
+pub(crate) struct TasksModal {
+    picker: View<Picker<TasksModalDelegate>>,
+}
+
+impl TasksModal {
+    pub(crate) fn toggle(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let candidates = task_candidates(workspace, cx);
+        cx.spawn(|workspace, mut cx| async move {
+            let (candidates, errors) = candidates.await;
+            workspace.update(&mut cx, |workspace, cx| {
+                for error in &errors {
+                    workspace.show_error(error, cx);
+                }
+                let workspace_handle = workspace.weak_handle();
+                workspace.toggle_modal(cx, move |cx| {
+                    let delegate = TasksModalDelegate::new(
+                        cx.view().downgrade(),
+                        workspace_handle,
+                        candidates,
+                    );
+                    let picker = cx.new_view(|cx| Picker::new(delegate, cx));
+                    Self { picker }
+                });
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+/// Collects the global tasks, which run in the first worktree, followed by the
+/// tasks defined by each local worktree, which run in that worktree's root.
+/// A worktree's tasks come from its `.zed/settings.json` and `.zed/tasks.json`.
+///
+/// Also returns the errors that kept a `.zed/tasks.json` from being read, so that
+/// they can be reported alongside the tasks that did load.
+pub(crate) fn task_candidates(
+    workspace: &Workspace,
+    cx: &mut ViewContext<Workspace>,
+) -> Task<(Vec<TaskCandidate>, Vec<anyhow::Error>)> {
+    let worktree_roots = workspace
+        .visible_worktrees(cx)
+        .filter_map(|worktree| {
+            let worktree = worktree.read(cx);
+            let worktree_id = worktree.id().to_usize();
+            let root = worktree.as_local()?.abs_path().to_path_buf();
+            Some((worktree_id, root))
+        })
+        .collect::<Vec<_>>();
+
+    let global_tasks = TasksSettings::get_global(cx).tasks.clone();
+    let mut candidates = global_tasks
+        .iter()
+        .map(|definition| TaskCandidate {
+            cwd: resolve_cwd(definition, worktree_roots.first().map(|(_, root)| root)),
+            definition: definition.clone(),
+        })
+        .collect::<Vec<_>>();
+    let mut worktree_tasks = Vec::new();
+    for (worktree_id, root) in &worktree_roots {
+        let settings_tasks = TasksSettings::get(Some((*worktree_id, "".as_ref())), cx)
+            .tasks
+            .iter()
+            .filter(|definition| !global_tasks.contains(definition))
+            .cloned()
+            .collect::<Vec<_>>();
+        worktree_tasks.push((root.clone(), settings_tasks));
+    }
+
+    let fs = workspace.app_state().fs.clone();
+    let task_files = cx.global::<TaskFiles>().0.clone();
+    cx.background_executor().spawn(async move {
+        let mut errors = Vec::new();
+        for (root, mut tasks) in worktree_tasks {
+            let path = root.join(*LOCAL_TASKS_RELATIVE_PATH);
+            match load_task_file(fs.as_ref(), &path, &task_files).await {
+                Ok(file_tasks) => tasks.extend(file_tasks),
+                Err(error) => errors.push(error),
+            }
+            candidates.extend(tasks.into_iter().map(|definition| TaskCandidate {
+                cwd: resolve_cwd(&definition, Some(&root)),
+                definition,
+            }));
+        }
+        (candidates, errors)
+    })
+}
+
+/// Returns the tasks defined in the tasks file at `path`, which has none if it
+/// doesn't exist.
+async fn load_task_file(
+    fs: &dyn Fs,
+    path: &Path,
+    task_files: &Mutex<HashMap<PathBuf, (SystemTime, Vec<TaskDefinition>)>>,
+) -> Result<Vec<TaskDefinition>> {
+    let Some(metadata) = fs.metadata(path).await? else {
+        task_files.lock().remove(path);
+        return Ok(Vec::new());
+    };
+    if let Some((mtime, tasks)) = task_files.lock().get(path) {
+        if *mtime == metadata.mtime {
+            return Ok(tasks.clone());
+        }
+    }
+
+    let content = fs.load(path).await?;
+    let tasks = parse_json_with_comments::<Vec<TaskDefinition>>(&content)
+        .with_context(|| format!("invalid tasks in {path:?}"))?;
+    task_files
+        .lock()
+        .insert(path.to_path_buf(), (metadata.mtime, tasks.clone()));
+    Ok(tasks)
+}
+
+fn resolve_cwd(definition: &TaskDefinition, root: Option<&PathBuf>) -> Option<PathBuf> {
+    match (&definition.cwd, root) {
+        (Some(cwd), Some(root)) => Some(root.join(cwd)),
+        (Some(cwd), None) => Some(PathBuf::from(cwd)),
+        (None, root) => root.cloned(),
+    }
+}
+
+/// Runs a task in the task panel, revealing it.
+pub(crate) fn spawn_in_panel(
+    workspace: &mut Workspace,
+    candidate: TaskCandidate,
+    cx: &mut ViewContext<Workspace>,
+) {
+    if let Some(panel) = workspace.focus_panel::<TaskPanel>(cx) {
+        panel.update(cx, |panel, cx| {
+            panel.spawn_task(candidate.definition, candidate.cwd, cx)
+        });
+    }
+}
+
+impl Render for TasksModal {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl FocusableView for TasksModal {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for TasksModal {}
+impl ModalView for TasksModal {}
+
+struct TasksModalDelegate {
+    tasks_modal: WeakView<TasksModal>,
+    workspace: WeakView<Workspace>,
+    candidates: Vec<TaskCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl TasksModalDelegate {
+    fn new(
+        tasks_modal: WeakView<TasksModal>,
+        workspace: WeakView<Workspace>,
+        candidates: Vec<TaskCandidate>,
+    ) -> Self {
+        Self {
+            tasks_modal,
+            workspace,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for TasksModalDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self) -> Arc<str> {
+        "Select a task to spawn...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(id, candidate)| {
+                StringMatchCandidate::new(id, candidate.definition.label.clone())
+            })
+            .collect::<Vec<_>>();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        if let Some(candidate) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.candidates.get(mat.candidate_id))
+        {
+            let candidate = candidate.clone();
+            self.workspace
+                .update(cx, |workspace, cx| spawn_in_panel(workspace, candidate, cx))
+                .log_err();
+        }
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.tasks_modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = &self.matches[ix];
+        let candidate = &self.candidates[mat.candidate_id];
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            mat.string.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(candidate.definition.command.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use workspace::notifications::NotificationLog;
+
+    use super::*;
+    use crate::{tests::init_test, Spawn};
+
+    #[gpui::test]
+    async fn test_task_candidates(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.update_global(|store: &mut SettingsStore, cx| {
+                store
+                    .set_user_settings(
+                        r#"{"tasks": [{"label": "global", "command": "echo global"}]}"#,
+                        cx,
+                    )
+                    .unwrap();
+            })
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                ".zed": {
+                    "settings.json": r#"{"tasks": [{"label": "settings", "command": "make", "cwd": "src"}]}"#,
+                    "tasks.json": r#"[
+                        // Comments are allowed.
+                        {"label": "file", "command": "cargo test", "env": {"RUST_LOG": "debug"}}
+                    ]"#,
+                },
+                "src": {},
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        cx.run_until_parked();
+
+        // Global tasks come first, followed by the worktree's settings and file tasks.
+        let (candidates, errors) = load_candidates(&workspace, cx).await;
+        assert!(errors.is_empty());
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|candidate| (candidate.definition.label.as_str(), candidate.cwd.clone()))
+                .collect::<Vec<_>>(),
+            [
+                ("global", Some(PathBuf::from("/root"))),
+                ("settings", Some(PathBuf::from("/root/src"))),
+                ("file", Some(PathBuf::from("/root"))),
+            ]
+        );
+        assert!(candidates[0].definition.env.is_empty());
+        assert_eq!(
+            candidates[2]
+                .definition
+                .env
+                .get("RUST_LOG")
+                .map(String::as_str),
+            Some("debug")
+        );
+
+        // A malformed tasks file is reported without hiding the other tasks...
+        fs.insert_file("/root/.zed/tasks.json", r#"[{"label": "#.into())
+            .await;
+        let (candidates, errors) = load_candidates(&workspace, cx).await;
+        assert_eq!(labels(&candidates), ["global", "settings"]);
+        assert_eq!(errors.len(), 1);
+        assert!(format!("{:?}", errors[0]).contains("tasks.json"));
+
+        // ...and read again once it changes.
+        fs.insert_file(
+            "/root/.zed/tasks.json",
+            r#"[{"label": "fixed", "command": "true"}]"#.into(),
+        )
+        .await;
+        let (candidates, errors) = load_candidates(&workspace, cx).await;
+        assert_eq!(labels(&candidates), ["global", "settings", "fixed"]);
+        assert!(errors.is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_spawn_reports_malformed_tasks_file(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({ ".zed": { "tasks.json": "[{" } }))
+            .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        cx.run_until_parked();
+
+        cx.dispatch_action(Spawn);
+        cx.run_until_parked();
+        assert!(workspace.update(cx, |workspace, cx| workspace
+            .active_modal::<TasksModal>(cx)
+            .is_some()));
+        cx.update(|cx| {
+            let log = cx.global::<NotificationLog>();
+            assert!(log
+                .entries()
+                .any(|entry| entry.message.contains("invalid tasks")));
+        });
+    }
+
+    async fn load_candidates(
+        workspace: &View<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> (Vec<TaskCandidate>, Vec<anyhow::Error>) {
+        workspace
+            .update(cx, |workspace, cx| task_candidates(workspace, cx))
+            .await
+    }
+
+    fn labels(candidates: &[TaskCandidate]) -> Vec<&str> {
+        candidates
+            .iter()
+            .map(|candidate| candidate.definition.label.as_str())
+            .collect()
+    }
+}
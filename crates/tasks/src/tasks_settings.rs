@@ -0,0 +1,93 @@
+use anyhow;
+use collections::HashMap;
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::Settings;
+use workspace::dock::DockPosition;
+
+/// A shell command that can be spawned with `task::Spawn`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TaskDefinition {
+    /// The name shown in the task picker.
+    pub label: String,
+    /// The command to run, interpreted by the user's shell.
+    pub command: String,
+    /// The directory to run the command in, relative to the worktree root.
+    /// Defaults to the worktree root.
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Environment variables to set for the command, on top of Zed's own.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// The tasks defined in the user's settings and in each worktree's
+/// `.zed/settings.json`. Tasks can also be defined in a worktree's
+/// `.zed/tasks.json`, which is parsed again whenever it has changed since tasks
+/// were last listed.
+#[derive(Debug)]
+pub struct TasksSettings {
+    pub tasks: Vec<TaskDefinition>,
+}
+
+impl Settings for TasksSettings {
+    const KEY: Option<&'static str> = Some("tasks");
+    type FileContent = Vec<TaskDefinition>;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        // Unlike most settings, tasks from each settings file are combined
+        // rather than overridden, so project tasks add to the global ones.
+        let tasks = default_value
+            .iter()
+            .chain(user_values.iter().flat_map(|tasks| tasks.iter()))
+            .cloned()
+            .collect();
+        Ok(Self { tasks })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TaskPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct TaskPanelSettingsContent {
+    /// Whether to show the task panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the task panel.
+    ///
+    /// Default: bottom
+    pub dock: Option<DockPosition>,
+    /// Default width of the task panel in pixels, when docked to the left or right.
+    ///
+    /// Default: 640
+    pub default_width: Option<f32>,
+    /// Default height of the task panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+}
+
+impl Settings for TaskPanelSettings {
+    const KEY: Option<&'static str> = Some("task_panel");
+    type FileContent = TaskPanelSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
@@ -10,7 +10,7 @@ use alacritty_terminal::{
     selection::{Selection, SelectionRange, SelectionType},
     sync::FairMutex,
     term::{
-        cell::Cell,
+        cell::{Cell, Flags},
         search::{Match, RegexIter, RegexSearch},
         Config, RenderableCursor, TermMode,
     },
@@ -867,6 +867,13 @@ impl Terminal {
         &self.last_content
     }
 
+    /// The text currently visible on the terminal's screen, one line per row.
+    pub fn screen_text(&self) -> String {
+        let term = self.term.lock();
+        let content = term.renderable_content();
+        cells_to_text(content.display_iter.map(|ic| (ic.point, ic.cell)))
+    }
+
     //To test:
     //- Activate match on terminal (scrolling and selection)
     //- Editor search snapping behavior
@@ -880,6 +887,15 @@ impl Terminal {
         }
     }
 
+    /// Scrolls so that the given number of scrollback lines are below the viewport.
+    pub fn scroll_to_display_offset(&mut self, display_offset: usize) {
+        let delta = display_offset as i32 - self.last_content.display_offset as i32;
+        if delta != 0 {
+            self.events
+                .push_back(InternalEvent::Scroll(AlacScroll::Delta(delta)));
+        }
+    }
+
     pub fn select_matches(&mut self, matches: Vec<RangeInclusive<AlacPoint>>) {
         let matches_to_select = self
             .matches
@@ -1316,6 +1332,13 @@ impl Terminal {
             .unwrap_or_else(|| "Terminal".to_string())
     }
 
+    /// Whether a process other than the shell is running in the foreground.
+    pub fn has_running_process(&self) -> bool {
+        self.foreground_process_info
+            .as_ref()
+            .map_or(false, |info| info.pid != self.shell_pid)
+    }
+
     pub fn can_navigate_to_selected_word(&self) -> bool {
         self.cmd_pressed && self.hovered_word
     }
@@ -1460,6 +1483,28 @@ pub fn rgba_color(r: u8, g: u8, b: u8) -> Hsla {
     .into()
 }
 
+fn cells_to_text<'a>(cells: impl IntoIterator<Item = (AlacPoint, &'a Cell)>) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current_line = None;
+    for (point, cell) in cells {
+        if current_line != Some(point.line) {
+            current_line = Some(point.line);
+            lines.push(String::new());
+        }
+        if cell.flags.contains(Flags::WIDE_CHAR_SPACER) {
+            continue;
+        }
+        if let Some(line) = lines.last_mut() {
+            line.push(cell.c);
+        }
+    }
+    lines
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use alacritty_terminal::{
@@ -1470,7 +1515,8 @@ mod tests {
     use rand::{distributions::Alphanumeric, rngs::ThreadRng, thread_rng, Rng};
 
     use crate::{
-        content_index_for_mouse, rgb_for_index, IndexedCell, TerminalContent, TerminalSize,
+        cells_to_text, content_index_for_mouse, rgb_for_index, IndexedCell, TerminalContent,
+        TerminalSize,
     };
 
     #[test]
@@ -1558,6 +1604,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cells_to_text() {
+        let size = crate::TerminalSize {
+            cell_width: Pixels::from(10.),
+            line_height: Pixels::from(10.),
+            size: size(Pixels::from(40.), Pixels::from(30.)),
+        };
+        let cells = vec![
+            vec!['l', 's', ' ', ' '],
+            vec![' ', ' ', ' ', ' '],
+            vec!['a', ' ', 'b', ' '],
+        ];
+        let content = convert_cells_to_content(size, &cells);
+
+        assert_eq!(
+            cells_to_text(content.cells.iter().map(|ic| (ic.point, &ic.cell))),
+            "ls\n\na b"
+        );
+    }
+
     fn get_cells(size: TerminalSize, rng: &mut ThreadRng) -> Vec<Vec<char>> {
         let mut cells = Vec::new();
 
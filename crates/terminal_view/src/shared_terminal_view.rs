@@ -0,0 +1,173 @@
+use client::proto::{self, PeerId};
+use gpui::{
+    div, AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, Model, Render,
+    SharedString, Task, View, VisualContext, WindowContext,
+};
+use project::Project;
+use settings::Settings;
+use terminal::terminal_settings::TerminalSettings;
+use theme::ThemeSettings;
+use ui::{h_flex, prelude::*, Icon, IconName, Label};
+use workspace::{
+    item::{FollowEvent, FollowableItem, Item, ItemEvent},
+    Pane, ViewId, Workspace,
+};
+
+/// A read-only copy of a terminal in a leader's dock, showing the text on its screen.
+pub struct SharedTerminalView {
+    remote_id: ViewId,
+    title: String,
+    text: String,
+    focus_handle: FocusHandle,
+}
+
+impl SharedTerminalView {
+    fn new(remote_id: ViewId, title: String, text: String, cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            remote_id,
+            title,
+            text,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl EventEmitter<ItemEvent> for SharedTerminalView {}
+
+impl FocusableView for SharedTerminalView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SharedTerminalView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let terminal_settings = TerminalSettings::get_global(cx);
+        let font_family = terminal_settings.font_family.clone();
+        let font_size = terminal_settings.font_size;
+
+        let theme_settings = ThemeSettings::get_global(cx);
+        let font_family: SharedString = font_family
+            .map(Into::into)
+            .unwrap_or_else(|| theme_settings.buffer_font.family.clone());
+        let buffer_font_size = theme_settings.buffer_font_size(cx);
+        let font_size =
+            font_size.map_or(buffer_font_size, |size| theme::adjusted_font_size(size, cx));
+
+        div()
+            .id("shared-terminal")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .overflow_scroll()
+            .bg(cx.theme().colors().terminal_background)
+            .text_color(cx.theme().colors().terminal_foreground)
+            .font(font_family)
+            .text_size(font_size)
+            .children(
+                self.text
+                    .lines()
+                    .map(|line| div().whitespace_nowrap().child(line.to_string())),
+            )
+    }
+}
+
+impl Item for SharedTerminalView {
+    type Event = ItemEvent;
+
+    fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
+        Some(self.title.clone().into())
+    }
+
+    fn tab_content(&self, _detail: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        h_flex()
+            .gap_2()
+            .child(Icon::new(IconName::Terminal))
+            .child(Label::new(self.title.clone()).color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            }))
+            .into_any()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl FollowableItem for SharedTerminalView {
+    fn remote_id(&self) -> Option<ViewId> {
+        Some(self.remote_id)
+    }
+
+    fn to_state_proto(&self, _: &WindowContext) -> Option<proto::view::Variant> {
+        None
+    }
+
+    fn from_state_proto(
+        _: View<Pane>,
+        _: View<Workspace>,
+        remote_id: ViewId,
+        state: &mut Option<proto::view::Variant>,
+        cx: &mut WindowContext,
+    ) -> Option<Task<anyhow::Result<View<Self>>>> {
+        let Some(proto::view::Variant::Terminal(_)) = state else {
+            return None;
+        };
+        let Some(proto::view::Variant::Terminal(state)) = state.take() else {
+            unreachable!()
+        };
+
+        let view = cx.new_view(|cx| Self::new(remote_id, state.title, state.text, cx));
+        Some(Task::ready(Ok(view)))
+    }
+
+    fn to_follow_event(_: &Self::Event) -> Option<FollowEvent> {
+        None
+    }
+
+    fn add_event_to_update_proto(
+        &self,
+        _: &Self::Event,
+        _: &mut Option<proto::update_view::Variant>,
+        _: &WindowContext,
+    ) -> bool {
+        false
+    }
+
+    fn apply_update_proto(
+        &mut self,
+        _: &Model<Project>,
+        message: proto::update_view::Variant,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        if let proto::update_view::Variant::Terminal(update) = message {
+            if update.title != self.title {
+                self.title = update.title;
+                cx.emit(ItemEvent::UpdateTab);
+            }
+            self.text = update.text;
+            cx.notify();
+        }
+        Task::ready(Ok(()))
+    }
+
+    fn is_project_item(&self, _: &WindowContext) -> bool {
+        true
+    }
+
+    fn set_leader_peer_id(&mut self, _: Option<PeerId>, _: &mut ViewContext<Self>) {}
+}
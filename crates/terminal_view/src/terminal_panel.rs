@@ -1,26 +1,32 @@
-use std::{ops::ControlFlow, path::PathBuf, sync::Arc};
+use std::{mem, ops::ControlFlow, path::PathBuf, sync::Arc};
 
 use crate::TerminalView;
-use db::kvp::KEY_VALUE_STORE;
+use collections::HashMap;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use editor::Editor;
 use gpui::{
-    actions, AppContext, AsyncWindowContext, Entity, EventEmitter, ExternalPaths, FocusHandle,
-    FocusableView, IntoElement, ParentElement, Pixels, Render, Styled, Subscription, Task, View,
-    ViewContext, VisualContext, WeakView, WindowContext,
+    actions, div, AppContext, AsyncWindowContext, Axis, EntityId, EventEmitter, ExternalPaths,
+    FocusHandle, FocusableView, IntoElement, Model, ParentElement, Pixels, Render, Styled,
+    Subscription, Task, View, ViewContext, VisualContext, WeakView, WindowContext,
 };
 use itertools::Itertools;
-use project::{Fs, ProjectEntryId};
+use language::Point;
+use project::{Fs, Project, ProjectEntryId};
 use search::{buffer_search::DivRegistrar, BufferSearchBar};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
 use terminal::terminal_settings::{TerminalDockPosition, TerminalSettings};
-use ui::{h_flex, ButtonCommon, Clickable, IconButton, IconSize, Selectable, Tooltip};
-use util::{ResultExt, TryFutureExt};
+use ui::{
+    h_flex, v_flex, ActiveTheme, ButtonCommon, Clickable, FluentBuilder, IconButton, IconSize,
+    Selectable, Tooltip,
+};
+use util::ResultExt;
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
     item::Item,
     pane,
     ui::IconName,
-    DraggedTab, Pane, Workspace,
+    DraggedTab, Pane, SplitDirection, Workspace,
 };
 
 use anyhow::Result;
@@ -34,6 +40,7 @@ pub fn init(cx: &mut AppContext) {
         |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
             workspace.register_action(TerminalPanel::new_terminal);
             workspace.register_action(TerminalPanel::open_terminal);
+            workspace.register_action(TerminalPanel::send_selection_to_dock);
             workspace.register_action(|workspace, _: &ToggleFocus, cx| {
                 workspace.toggle_panel_focus::<TerminalPanel>(cx);
             });
@@ -42,27 +49,58 @@ pub fn init(cx: &mut AppContext) {
     .detach();
 }
 
+/// A dock panel hosting terminals. Splitting one of its panes places the new pane
+/// next to it. The panes are laid out along a single axis, that of the latest split,
+/// so splitting along the other axis lays out all of them along that one.
 pub struct TerminalPanel {
-    pane: View<Pane>,
+    panes: Vec<View<Pane>>,
+    pane_subscriptions: HashMap<EntityId, [Subscription; 2]>,
+    active_pane: View<Pane>,
+    /// The axis of the latest split, if the panel was ever split.
+    split_axis: Option<Axis>,
     fs: Arc<dyn Fs>,
     workspace: WeakView<Workspace>,
     width: Option<Pixels>,
     height: Option<Pixels>,
-    pending_serialization: Task<Option<()>>,
-    _subscriptions: Vec<Subscription>,
 }
 
 impl TerminalPanel {
     fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let pane = Self::new_pane(workspace.weak_handle(), workspace.project().clone(), cx);
+        let mut this = Self {
+            panes: Vec::new(),
+            pane_subscriptions: HashMap::default(),
+            active_pane: pane.clone(),
+            split_axis: None,
+            fs: workspace.app_state().fs.clone(),
+            workspace: workspace.weak_handle(),
+            width: None,
+            height: None,
+        };
+        this.insert_pane(0, pane, cx);
+        this
+    }
+
+    /// Inserts `pane` at `ix`, subscribing to it for as long as it stays in the panel.
+    fn insert_pane(&mut self, ix: usize, pane: View<Pane>, cx: &mut ViewContext<Self>) {
+        self.pane_subscriptions.insert(
+            pane.entity_id(),
+            [
+                cx.observe(&pane, |_, _, cx| cx.notify()),
+                cx.subscribe(&pane, Self::handle_pane_event),
+            ],
+        );
+        self.panes.insert(ix, pane);
+    }
+
+    fn new_pane(
+        workspace: WeakView<Workspace>,
+        project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> View<Pane> {
         let terminal_panel = cx.view().downgrade();
-        let pane = cx.new_view(|cx| {
-            let mut pane = Pane::new(
-                workspace.weak_handle(),
-                workspace.project().clone(),
-                Default::default(),
-                None,
-                cx,
-            );
+        cx.new_view(|cx| {
+            let mut pane = Pane::new(workspace.clone(), project, Default::default(), None, cx);
             pane.set_can_split(false, cx);
             pane.set_can_navigate(false, cx);
             pane.display_nav_history_buttons(false);
@@ -75,11 +113,21 @@ impl TerminalPanel {
                             .icon_size(IconSize::Small)
                             .on_click(move |_, cx| {
                                 terminal_panel
-                                    .update(cx, |panel, cx| panel.add_terminal(None, cx))
+                                    .update(cx, |panel, cx| {
+                                        panel.add_terminal(None, cx).detach_and_log_err(cx)
+                                    })
                                     .log_err();
                             })
                             .tooltip(|cx| Tooltip::text("New Terminal", cx)),
                     )
+                    .child(
+                        IconButton::new("split", IconName::Split)
+                            .icon_size(IconSize::Small)
+                            .on_click(cx.listener(|pane, _, cx| {
+                                pane.split(SplitDirection::Right, cx);
+                            }))
+                            .tooltip(|cx| Tooltip::text("Split Terminal", cx)),
+                    )
                     .child({
                         let zoomed = pane.is_zoomed();
                         IconButton::new("toggle_zoom", IconName::Maximize)
@@ -96,7 +144,6 @@ impl TerminalPanel {
                     .into_any_element()
             });
 
-            let workspace = workspace.weak_handle();
             pane.set_custom_drop_handle(cx, move |pane, dropped_item, cx| {
                 if let Some(tab) = dropped_item.downcast_ref::<DraggedTab>() {
                     let item = if &tab.pane == cx.view() {
@@ -145,21 +192,7 @@ impl TerminalPanel {
             pane.toolbar()
                 .update(cx, |toolbar, cx| toolbar.add_item(buffer_search_bar, cx));
             pane
-        });
-        let subscriptions = vec![
-            cx.observe(&pane, |_, _, cx| cx.notify()),
-            cx.subscribe(&pane, Self::handle_pane_event),
-        ];
-        let this = Self {
-            pane,
-            fs: workspace.app_state().fs.clone(),
-            workspace: workspace.weak_handle(),
-            pending_serialization: Task::ready(None),
-            width: None,
-            height: None,
-            _subscriptions: subscriptions,
-        };
-        this
+        })
     }
 
     pub async fn load(
@@ -177,14 +210,23 @@ impl TerminalPanel {
             .log_err()
             .flatten();
 
-        let (panel, pane, items) = workspace.update(&mut cx, |workspace, cx| {
+        let (panel, items) = workspace.update(&mut cx, |workspace, cx| {
             let panel = cx.new_view(|cx| TerminalPanel::new(workspace, cx));
             let items = if let Some(serialized_panel) = serialized_panel.as_ref() {
                 panel.update(cx, |panel, cx| {
                     cx.notify();
                     panel.height = serialized_panel.height;
                     panel.width = serialized_panel.width;
-                    panel.pane.update(cx, |_, cx| {
+                    panel.split_axis = serialized_panel.split_axis;
+                    for _ in 1..serialized_panel.pane_item_counts().len() {
+                        let pane = Self::new_pane(
+                            workspace.weak_handle(),
+                            workspace.project().clone(),
+                            cx,
+                        );
+                        panel.insert_pane(panel.panes.len(), pane, cx);
+                    }
+                    panel.active_pane.update(cx, |_, cx| {
                         serialized_panel
                             .items
                             .iter()
@@ -203,51 +245,86 @@ impl TerminalPanel {
             } else {
                 Default::default()
             };
-            let pane = panel.read(cx).pane.clone();
-            (panel, pane, items)
+            (panel, items)
         })?;
 
-        let pane = pane.downgrade();
         let items = futures::future::join_all(items).await;
-        pane.update(&mut cx, |pane, cx| {
-            let active_item_id = serialized_panel
-                .as_ref()
-                .and_then(|panel| panel.active_item_id);
-            let mut active_ix = None;
-            for item in items {
-                if let Some(item) = item.log_err() {
-                    let item_id = item.entity_id().as_u64();
+        if let Some(serialized_panel) = serialized_panel {
+            panel.update(&mut cx, |panel, cx| {
+                panel.restore_items(&serialized_panel, items, cx)
+            })?;
+        }
+
+        Ok(panel)
+    }
+
+    /// Distributes the deserialized terminals among the panes they were in when
+    /// the panel was serialized, dropping panes whose terminals all failed to load.
+    fn restore_items(
+        &mut self,
+        serialized_panel: &SerializedTerminalPanel,
+        items: Vec<Result<View<TerminalView>>>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let mut items = serialized_panel.items.iter().zip(items);
+        for (pane, item_count) in self
+            .panes
+            .clone()
+            .into_iter()
+            .zip(serialized_panel.pane_item_counts())
+        {
+            for (item_id, item) in items.by_ref().take(item_count) {
+                let Some(item) = item.log_err() else {
+                    continue;
+                };
+                let is_active = Some(*item_id) == serialized_panel.active_item_id;
+                pane.update(cx, |pane, cx| {
                     pane.add_item(Box::new(item), false, false, None, cx);
-                    if Some(item_id) == active_item_id {
-                        active_ix = Some(pane.items_len() - 1);
+                    if is_active {
+                        pane.activate_item(pane.items_len() - 1, false, false, cx);
                     }
+                });
+                if is_active {
+                    self.active_pane = pane.clone();
                 }
             }
+        }
 
-            if let Some(active_ix) = active_ix {
-                pane.activate_item(active_ix, false, false, cx)
+        if self.panes.iter().any(|pane| pane.read(cx).items_len() > 0) {
+            let (non_empty_panes, empty_panes): (Vec<_>, Vec<_>) = mem::take(&mut self.panes)
+                .into_iter()
+                .partition(|pane| pane.read(cx).items_len() > 0);
+            for pane in empty_panes {
+                self.pane_subscriptions.remove(&pane.entity_id());
             }
-        })?;
-
-        Ok(panel)
+            self.panes = non_empty_panes;
+            if !self.panes.contains(&self.active_pane) {
+                self.active_pane = self.panes[0].clone();
+            }
+        }
+        cx.notify();
     }
 
     fn handle_pane_event(
         &mut self,
-        _pane: View<Pane>,
+        pane: View<Pane>,
         event: &pane::Event,
         cx: &mut ViewContext<Self>,
     ) {
         match event {
             pane::Event::ActivateItem { .. } => self.serialize(cx),
             pane::Event::RemoveItem { .. } => self.serialize(cx),
-            pane::Event::Remove => cx.emit(PanelEvent::Close),
+            pane::Event::Remove => self.remove_pane(&pane, cx),
+            pane::Event::Split(direction) => self.split_pane(&pane, *direction, cx),
             pane::Event::ZoomIn => cx.emit(PanelEvent::ZoomIn),
             pane::Event::ZoomOut => cx.emit(PanelEvent::ZoomOut),
+            pane::Event::Focus => {
+                self.active_pane = pane;
+                cx.notify();
+            }
 
             pane::Event::AddItem { item } => {
                 if let Some(workspace) = self.workspace.upgrade() {
-                    let pane = self.pane.clone();
                     workspace.update(cx, |workspace, cx| item.added_to_pane(workspace, pane, cx))
                 }
             }
@@ -256,6 +333,51 @@ impl TerminalPanel {
         }
     }
 
+    fn split_pane(
+        &mut self,
+        pane: &View<Pane>,
+        direction: SplitDirection,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+        let new_pane = Self::new_pane(self.workspace.clone(), project, cx);
+
+        let ix = self
+            .panes
+            .iter()
+            .position(|candidate| candidate == pane)
+            .unwrap_or(self.panes.len() - 1);
+        let ix = if direction.increasing() { ix + 1 } else { ix };
+        self.insert_pane(ix, new_pane.clone(), cx);
+        self.split_axis = Some(direction.axis());
+        self.active_pane = new_pane.clone();
+        cx.focus_view(&new_pane);
+        self.add_terminal(None, cx).detach_and_log_err(cx);
+        cx.notify();
+    }
+
+    fn remove_pane(&mut self, pane: &View<Pane>, cx: &mut ViewContext<Self>) {
+        if self.panes.len() == 1 {
+            cx.emit(PanelEvent::Close);
+            return;
+        }
+
+        let Some(ix) = self.panes.iter().position(|candidate| candidate == pane) else {
+            return;
+        };
+        self.panes.remove(ix);
+        self.pane_subscriptions.remove(&pane.entity_id());
+        if &self.active_pane == pane {
+            self.active_pane = self.panes[ix.min(self.panes.len() - 1)].clone();
+            cx.focus_view(&self.active_pane);
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
     pub fn open_terminal(
         workspace: &mut Workspace,
         action: &workspace::OpenTerminal,
@@ -267,6 +389,7 @@ impl TerminalPanel {
 
         this.update(cx, |this, cx| {
             this.add_terminal(Some(action.working_directory.clone()), cx)
+                .detach_and_log_err(cx)
         })
     }
 
@@ -280,14 +403,63 @@ impl TerminalPanel {
             return;
         };
 
-        this.update(cx, |this, cx| this.add_terminal(None, cx))
+        this.update(cx, |this, cx| {
+            this.add_terminal(None, cx).detach_and_log_err(cx)
+        })
+    }
+
+    /// Types the active editor's newest selection, or the line containing the cursor
+    /// if the selection is empty, into the active terminal. The terminal panel is
+    /// opened if needed, but focus stays in the editor.
+    fn send_selection_to_dock(
+        workspace: &mut Workspace,
+        _: &workspace::SendSelectionToDock,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let Some(editor) = workspace
+            .active_item(cx)
+            .and_then(|item| item.act_as::<Editor>(cx))
+        else {
+            return;
+        };
+        let Some(this) = workspace.panel::<Self>(cx) else {
+            return;
+        };
+
+        let text = editor.update(cx, |editor, cx| text_to_send(editor, cx));
+        let active_terminal = this
+            .read(cx)
+            .active_pane
+            .read(cx)
+            .active_item()
+            .and_then(|item| item.downcast::<TerminalView>());
+        let terminal_view = match active_terminal {
+            Some(terminal_view) => Task::ready(Ok(terminal_view)),
+            None => this.update(cx, |this, cx| this.add_terminal(None, cx)),
+        };
+        cx.spawn(|workspace, mut cx| async move {
+            let terminal_view = terminal_view.await?;
+            workspace.update(&mut cx, |workspace, cx| {
+                workspace.open_panel::<Self>(cx);
+                terminal_view.update(cx, |terminal_view, cx| {
+                    terminal_view
+                        .terminal()
+                        .update(cx, |terminal, _| terminal.input(text));
+                });
+            })
+        })
+        .detach_and_log_err(cx);
     }
 
-    fn add_terminal(&mut self, working_directory: Option<PathBuf>, cx: &mut ViewContext<Self>) {
+    fn add_terminal(
+        &mut self,
+        working_directory: Option<PathBuf>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<View<TerminalView>>> {
         let workspace = self.workspace.clone();
         cx.spawn(|this, mut cx| async move {
-            let pane = this.update(&mut cx, |this, _| this.pane.clone())?;
-            workspace.update(&mut cx, |workspace, cx| {
+            let pane = this.update(&mut cx, |this, _| this.active_pane.clone())?;
+            let terminal_view = workspace.update(&mut cx, |workspace, cx| {
                 let working_directory = if let Some(working_directory) = working_directory {
                     Some(working_directory)
                 } else {
@@ -297,63 +469,95 @@ impl TerminalPanel {
                 };
 
                 let window = cx.window_handle();
-                if let Some(terminal) = workspace.project().update(cx, |project, cx| {
-                    project
-                        .create_terminal(working_directory, window, cx)
-                        .log_err()
-                }) {
-                    let terminal = Box::new(cx.new_view(|cx| {
-                        TerminalView::new(
-                            terminal,
-                            workspace.weak_handle(),
-                            workspace.database_id(),
-                            cx,
-                        )
-                    }));
-                    pane.update(cx, |pane, cx| {
-                        let focus = pane.has_focus(cx);
-                        pane.add_item(terminal, true, focus, None, cx);
-                    });
-                }
-            })?;
+                let terminal = workspace.project().update(cx, |project, cx| {
+                    project.create_terminal(working_directory, window, cx)
+                })?;
+                let terminal_view = cx.new_view(|cx| {
+                    TerminalView::new(
+                        terminal,
+                        workspace.weak_handle(),
+                        workspace.database_id(),
+                        cx,
+                    )
+                });
+                pane.update(cx, |pane, cx| {
+                    let focus = pane.has_focus(cx);
+                    pane.add_item(Box::new(terminal_view.clone()), true, focus, None, cx);
+                });
+                anyhow::Ok(terminal_view)
+            })??;
             this.update(&mut cx, |this, cx| this.serialize(cx))?;
-            anyhow::Ok(())
+            Ok(terminal_view)
         })
-        .detach_and_log_err(cx);
+    }
+
+    /// The axis the panes are laid out along. Until the panel is split, that's a row
+    /// when docked at the bottom and a column otherwise.
+    fn layout_axis(&self, cx: &WindowContext) -> Axis {
+        self.split_axis.unwrap_or_else(|| {
+            if self.position(cx) == DockPosition::Bottom {
+                Axis::Horizontal
+            } else {
+                Axis::Vertical
+            }
+        })
+    }
+
+    fn terminal_count(&self, cx: &AppContext) -> usize {
+        self.panes
+            .iter()
+            .map(|pane| pane.read(cx).items_len())
+            .sum()
     }
 
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
         let items = self
-            .pane
-            .read(cx)
-            .items()
+            .panes
+            .iter()
+            .flat_map(|pane| pane.read(cx).items())
             .map(|item| item.item_id().as_u64())
             .collect::<Vec<_>>();
+        let pane_item_counts = self
+            .panes
+            .iter()
+            .map(|pane| pane.read(cx).items_len())
+            .collect::<Vec<_>>();
         let active_item_id = self
-            .pane
+            .active_pane
             .read(cx)
             .active_item()
             .map(|item| item.item_id().as_u64());
-        let height = self.height;
-        let width = self.width;
-        self.pending_serialization = cx.background_executor().spawn(
-            async move {
-                KEY_VALUE_STORE
-                    .write_kvp(
-                        TERMINAL_PANEL_KEY.into(),
-                        serde_json::to_string(&SerializedTerminalPanel {
-                            items,
-                            active_item_id,
-                            height,
-                            width,
-                        })?,
-                    )
-                    .await?;
-                anyhow::Ok(())
-            }
-            .log_err(),
-        );
+        if let Some(serialized) = serde_json::to_string(&SerializedTerminalPanel {
+            items,
+            pane_item_counts,
+            active_item_id,
+            split_axis: self.split_axis,
+            height: self.height,
+            width: self.width,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(TERMINAL_PANEL_KEY, serialized, cx);
+        }
+    }
+}
+
+/// The text [`TerminalPanel::send_selection_to_dock`] types into the terminal, ending
+/// with a newline so that it runs.
+fn text_to_send(editor: &mut Editor, cx: &mut ViewContext<Editor>) -> String {
+    let buffer = editor.buffer().read(cx).snapshot(cx);
+    let selection = editor.selections.newest::<Point>(cx);
+    let range = if selection.is_empty() {
+        let row = selection.head().row;
+        Point::new(row, 0)..Point::new(row, buffer.line_len(row))
+    } else {
+        selection.range()
+    };
+    let mut text = buffer.text_for_range(range).collect::<String>();
+    if !text.ends_with('\n') {
+        text.push('\n');
     }
+    text
 }
 
 fn add_paths_to_terminal(pane: &mut Pane, paths: &[PathBuf], cx: &mut ViewContext<'_, Pane>) {
@@ -379,7 +583,7 @@ impl Render for TerminalPanel {
         let mut registrar = DivRegistrar::new(
             |panel, cx| {
                 panel
-                    .pane
+                    .active_pane
                     .read(cx)
                     .toolbar()
                     .read(cx)
@@ -388,13 +592,33 @@ impl Render for TerminalPanel {
             cx,
         );
         BufferSearchBar::register(&mut registrar);
-        registrar.into_div().size_full().child(self.pane.clone())
+
+        let is_horizontal = self.layout_axis(cx) == Axis::Horizontal;
+        let border_color = cx.theme().colors().border;
+        registrar.into_div().size_full().child(
+            if is_horizontal { h_flex() } else { v_flex() }
+                .size_full()
+                .children(self.panes.iter().enumerate().map(|(ix, pane)| {
+                    div()
+                        .flex_1()
+                        .overflow_hidden()
+                        .map(|this| {
+                            if is_horizontal {
+                                this.h_full().when(ix > 0, |this| this.border_l_1())
+                            } else {
+                                this.w_full().when(ix > 0, |this| this.border_t_1())
+                            }
+                        })
+                        .border_color(border_color)
+                        .child(pane.clone())
+                })),
+        )
     }
 }
 
 impl FocusableView for TerminalPanel {
     fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
-        self.pane.focus_handle(cx)
+        self.active_pane.focus_handle(cx)
     }
 }
 
@@ -442,21 +666,26 @@ impl Panel for TerminalPanel {
     }
 
     fn is_zoomed(&self, cx: &WindowContext) -> bool {
-        self.pane.read(cx).is_zoomed()
+        self.active_pane.read(cx).is_zoomed()
     }
 
     fn set_zoomed(&mut self, zoomed: bool, cx: &mut ViewContext<Self>) {
-        self.pane.update(cx, |pane, cx| pane.set_zoomed(zoomed, cx));
+        self.active_pane
+            .update(cx, |pane, cx| pane.set_zoomed(zoomed, cx));
     }
 
     fn set_active(&mut self, active: bool, cx: &mut ViewContext<Self>) {
-        if active && self.pane.read(cx).items_len() == 0 {
-            self.add_terminal(None, cx)
+        if active && self.terminal_count(cx) == 0 {
+            self.add_terminal(None, cx).detach_and_log_err(cx)
         }
     }
 
+    fn panes(&self) -> Vec<View<Pane>> {
+        self.panes.clone()
+    }
+
     fn icon_label(&self, cx: &WindowContext) -> Option<String> {
-        let count = self.pane.read(cx).items_len();
+        let count = self.terminal_count(cx);
         if count == 0 {
             None
         } else {
@@ -484,7 +713,317 @@ impl Panel for TerminalPanel {
 #[derive(Serialize, Deserialize)]
 struct SerializedTerminalPanel {
     items: Vec<u64>,
+    /// How many of `items` belong to each pane, in order.
+    #[serde(default)]
+    pane_item_counts: Vec<usize>,
     active_item_id: Option<u64>,
+    #[serde(default)]
+    split_axis: Option<Axis>,
     width: Option<Pixels>,
     height: Option<Pixels>,
 }
+
+impl SerializedTerminalPanel {
+    /// Falls back to a single pane for panels serialized before splits, or when
+    /// the counts don't add up.
+    fn pane_item_counts(&self) -> Vec<usize> {
+        if !self.pane_item_counts.is_empty()
+            && self.pane_item_counts.iter().sum::<usize>() == self.items.len()
+        {
+            self.pane_item_counts.clone()
+        } else {
+            vec![self.items.len()]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use workspace::AppState;
+
+    #[gpui::test]
+    async fn test_split_and_remove_panes(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let panel = add_terminal_panel(&workspace, cx);
+        let first_pane = panel.update(cx, |panel, _| panel.active_pane.clone());
+
+        // The new pane goes after the split one, gets a terminal and becomes active.
+        first_pane.update(cx, |pane, cx| pane.split(SplitDirection::Down, cx));
+        cx.run_until_parked();
+        let second_pane = panel.update(cx, |panel, cx| {
+            assert_eq!(panel.panes.len(), 2);
+            assert_eq!(panel.panes[0], first_pane);
+            assert_eq!(panel.active_pane, panel.panes[1]);
+            assert_eq!(panel.active_pane.read(cx).items_len(), 1);
+            assert_eq!(panel.layout_axis(cx), Axis::Vertical);
+            panel.active_pane.clone()
+        });
+
+        // Splitting to the left goes before the split pane, and along the new axis.
+        first_pane.update(cx, |pane, cx| pane.split(SplitDirection::Left, cx));
+        cx.run_until_parked();
+        let third_pane = panel.update(cx, |panel, cx| {
+            assert_eq!(panel.panes.len(), 3);
+            assert_eq!(panel.panes[1], first_pane);
+            assert_eq!(panel.panes[2], second_pane);
+            assert_eq!(panel.active_pane, panel.panes[0]);
+            assert_eq!(panel.layout_axis(cx), Axis::Horizontal);
+            assert_eq!(panel.pane_subscriptions.len(), 3);
+            panel.active_pane.clone()
+        });
+
+        // Removing the active pane activates the one that took its place.
+        panel.update(cx, |panel, cx| {
+            panel.remove_pane(&third_pane, cx);
+            assert_eq!(panel.panes, [first_pane.clone(), second_pane.clone()]);
+            assert_eq!(panel.active_pane, first_pane);
+            assert!(!panel
+                .pane_subscriptions
+                .contains_key(&third_pane.entity_id()));
+
+            panel.remove_pane(&second_pane, cx);
+            assert_eq!(panel.panes, [first_pane.clone()]);
+            assert_eq!(panel.active_pane, first_pane);
+        });
+
+        // The last pane is never removed.
+        panel.update(cx, |panel, cx| {
+            panel.remove_pane(&first_pane, cx);
+            assert_eq!(panel.panes, [first_pane.clone()]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_restore_items_into_panes(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let panel = add_terminal_panel(&workspace, cx);
+
+        let serialized_panel = SerializedTerminalPanel {
+            items: vec![1, 2, 3, 4],
+            pane_item_counts: vec![1, 1, 2],
+            active_item_id: Some(4),
+            split_axis: Some(Axis::Vertical),
+            width: None,
+            height: None,
+        };
+        let terminals = (0..3)
+            .map(|_| new_terminal_view(&workspace, cx))
+            .collect::<Vec<_>>();
+        panel.update(cx, |panel, cx| {
+            for _ in 1..serialized_panel.pane_item_counts().len() {
+                let pane = TerminalPanel::new_pane(
+                    panel.workspace.clone(),
+                    workspace.read(cx).project().clone(),
+                    cx,
+                );
+                panel.insert_pane(panel.panes.len(), pane, cx);
+            }
+            // The terminal of the second pane failed to load.
+            panel.restore_items(
+                &serialized_panel,
+                vec![
+                    Ok(terminals[0].clone()),
+                    Err(anyhow::anyhow!("failed to restore the terminal")),
+                    Ok(terminals[1].clone()),
+                    Ok(terminals[2].clone()),
+                ],
+                cx,
+            );
+        });
+
+        // The pane left empty is dropped, and the active terminal stays active.
+        panel.update(cx, |panel, cx| {
+            let pane_items = panel
+                .panes
+                .iter()
+                .map(|pane| {
+                    pane.read(cx)
+                        .items()
+                        .map(|item| item.item_id())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                pane_items,
+                [
+                    vec![terminals[0].entity_id()],
+                    vec![terminals[1].entity_id(), terminals[2].entity_id()],
+                ]
+            );
+            assert_eq!(panel.pane_subscriptions.len(), 2);
+            assert_eq!(panel.active_pane, panel.panes[1]);
+            assert_eq!(
+                panel
+                    .active_pane
+                    .read(cx)
+                    .active_item()
+                    .map(|item| item.item_id()),
+                Some(terminals[2].entity_id())
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_serialize_and_load_panel(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let panel = add_terminal_panel(&workspace, cx);
+        let first_pane = panel.update(cx, |panel, _| panel.active_pane.clone());
+
+        for _ in 0..2 {
+            panel
+                .update(cx, |panel, cx| panel.add_terminal(None, cx))
+                .await
+                .unwrap();
+        }
+        first_pane.update(cx, |pane, cx| pane.split(SplitDirection::Right, cx));
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            panel.active_pane = first_pane.clone();
+            first_pane.update(cx, |pane, cx| pane.activate_item(0, false, false, cx));
+            panel.serialize(cx);
+        });
+        cx.executor().advance_clock(db::WRITE_DEBOUNCE);
+        cx.run_until_parked();
+
+        let loaded_panel = workspace
+            .update(cx, |_, cx| {
+                cx.spawn(|workspace, cx| TerminalPanel::load(workspace, cx))
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        loaded_panel.update(cx, |panel, cx| {
+            assert_eq!(
+                panel
+                    .panes
+                    .iter()
+                    .map(|pane| pane.read(cx).items_len())
+                    .collect::<Vec<_>>(),
+                [2, 1]
+            );
+            assert_eq!(panel.split_axis, Some(Axis::Horizontal));
+            assert_eq!(panel.active_pane, panel.panes[0]);
+            assert_eq!(panel.active_pane.read(cx).active_item_index(), 0);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_send_selection_to_dock(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let panel = add_terminal_panel(&workspace, cx);
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::multi_line(cx);
+            editor.set_text("one two\nthree\nfour", cx);
+            editor
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.add_item(Box::new(editor.clone()), cx);
+        });
+        editor.update(cx, |_, cx| cx.focus_self());
+
+        // The selection is sent as a line of its own.
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |selections| {
+                selections.select_ranges([Point::new(0, 4)..Point::new(1, 3)])
+            });
+            assert_eq!(text_to_send(editor, cx), "two\nthr\n");
+        });
+
+        // Without a terminal, one is opened in the dock, keeping the focus in the editor.
+        cx.dispatch_action(workspace::SendSelectionToDock);
+        cx.run_until_parked();
+        let terminal = panel.update(cx, |panel, cx| {
+            assert_eq!(panel.terminal_count(cx), 1);
+            panel
+                .active_pane
+                .read(cx)
+                .active_item()
+                .and_then(|item| item.downcast::<TerminalView>())
+                .unwrap()
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.bottom_dock().read(cx).is_open());
+            assert_eq!(
+                workspace.active_item(cx).map(|item| item.item_id()),
+                Some(editor.entity_id())
+            );
+        });
+        assert!(editor.update(cx, |editor, cx| editor.is_focused(cx)));
+
+        // An empty selection sends the line it's on, to the terminal that's already open.
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |selections| {
+                selections.select_ranges([Point::new(2, 1)..Point::new(2, 1)])
+            });
+            assert_eq!(text_to_send(editor, cx), "four\n");
+        });
+        cx.dispatch_action(workspace::SendSelectionToDock);
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(panel.terminal_count(cx), 1);
+            assert_eq!(
+                panel
+                    .active_pane
+                    .read(cx)
+                    .active_item()
+                    .map(|item| item.item_id()),
+                Some(terminal.entity_id())
+            );
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        // Terminals run real shells on background threads.
+        cx.executor().allow_parking();
+        cx.update(|cx| {
+            let app_state = AppState::test(cx);
+            Project::init_settings(cx);
+            language::init(cx);
+            editor::init_settings(cx);
+            crate::init(cx);
+            app_state
+        })
+    }
+
+    fn add_terminal_panel(
+        workspace: &View<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> View<TerminalPanel> {
+        workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TerminalPanel::new(workspace, cx));
+            workspace.add_panel(panel.clone(), cx);
+            panel
+        })
+    }
+
+    fn new_terminal_view(
+        workspace: &View<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> View<TerminalView> {
+        workspace.update(cx, |workspace, cx| {
+            let window = cx.window_handle();
+            let terminal = workspace
+                .project()
+                .update(cx, |project, cx| project.create_terminal(None, window, cx))
+                .unwrap();
+            cx.new_view(|cx| {
+                TerminalView::new(
+                    terminal,
+                    workspace.weak_handle(),
+                    workspace.database_id(),
+                    cx,
+                )
+            })
+        })
+    }
+}
@@ -1,16 +1,19 @@
 mod persistence;
+pub mod shared_terminal_view;
 pub mod terminal_element;
 pub mod terminal_panel;
 
+use client::proto::{self, PeerId};
 use editor::{scroll::Autoscroll, Editor};
 use gpui::{
     div, impl_actions, overlay, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle,
     FocusableView, KeyContext, KeyDownEvent, Keystroke, Model, MouseButton, MouseDownEvent, Pixels,
-    Render, Styled, Subscription, Task, View, VisualContext, WeakView,
+    PromptLevel, Render, Styled, Subscription, Task, View, VisualContext, WeakView,
 };
 use language::Bias;
 use persistence::TERMINAL_DB;
 use project::{search::SearchQuery, LocalWorktree, Project};
+use shared_terminal_view::SharedTerminalView;
 use terminal::{
     alacritty_terminal::{
         index::Point,
@@ -23,12 +26,12 @@ use terminal_element::TerminalElement;
 use ui::{h_flex, prelude::*, ContextMenu, Icon, IconName, Label};
 use util::{paths::PathLikeWithPosition, ResultExt};
 use workspace::{
-    item::{BreadcrumbText, Item, ItemEvent},
+    item::{BreadcrumbText, FollowEvent, FollowableItem, Item, ItemEvent, SerializableItem},
     notifications::NotifyResultExt,
-    register_deserializable_item,
+    register_followable_item, register_serializable_item,
     searchable::{SearchEvent, SearchOptions, SearchableItem, SearchableItemHandle},
-    CloseActiveItem, NewCenterTerminal, OpenVisible, Pane, ToolbarItemLocation, Workspace,
-    WorkspaceId,
+    CloseActiveItem, ItemNavHistory, NewCenterTerminal, OpenVisible, Pane, ToolbarItemLocation,
+    ViewId, Workspace, WorkspaceId, WorkspaceSettings,
 };
 
 use anyhow::Context;
@@ -38,6 +41,7 @@ use settings::Settings;
 use smol::Timer;
 
 use std::{
+    any::Any,
     ops::RangeInclusive,
     path::{Path, PathBuf},
     sync::Arc,
@@ -62,7 +66,9 @@ pub fn init(cx: &mut AppContext) {
     terminal_panel::init(cx);
     terminal::init(cx);
 
-    register_deserializable_item::<TerminalView>(cx);
+    register_serializable_item::<TerminalView>(cx);
+    register_followable_item::<TerminalView>(cx);
+    register_followable_item::<SharedTerminalView>(cx);
 
     cx.observe_new_views(|workspace: &mut Workspace, _| {
         workspace.register_action(TerminalView::deploy);
@@ -76,6 +82,10 @@ pub struct TerminalView {
     workspace: WeakView<Workspace>,
     focus_handle: FocusHandle,
     has_new_content: bool,
+    /// Whether the terminal is on screen, see [`Item::set_visible`].
+    visible: bool,
+    /// Whether output arrived while the terminal was hidden, and hasn't been shown yet.
+    has_hidden_output: bool,
     //Currently using iTerm bell, show bell emoji in tab until input is received
     has_bell: bool,
     context_menu: Option<(View<ContextMenu>, gpui::Point<Pixels>, Subscription)>,
@@ -85,6 +95,7 @@ pub struct TerminalView {
     blink_epoch: usize,
     can_navigate_to_selected_word: bool,
     workspace_id: WorkspaceId,
+    nav_history: Option<ItemNavHistory>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -137,12 +148,23 @@ impl TerminalView {
         cx: &mut ViewContext<Self>,
     ) -> Self {
         let workspace_handle = workspace.clone();
-        cx.observe(&terminal, |_, _, cx| cx.notify()).detach();
+        cx.observe(&terminal, |this, _, cx| {
+            if this.visible {
+                cx.notify();
+            }
+        })
+        .detach();
         cx.subscribe(&terminal, move |this, _, event, cx| match event {
             Event::Wakeup => {
                 if !this.focus_handle.is_focused(cx) {
                     this.has_new_content = true;
                 }
+                if !this.visible {
+                    // Don't redraw the terminal nor invalidate its search matches for
+                    // output nobody can see, but catch up once it gets shown.
+                    this.has_hidden_output = true;
+                    return;
+                }
                 cx.notify();
                 cx.emit(Event::Wakeup);
                 cx.emit(ItemEvent::UpdateTab);
@@ -152,6 +174,7 @@ impl TerminalView {
             Event::Bell => {
                 this.has_bell = true;
                 cx.emit(Event::Wakeup);
+                cx.emit(ItemEvent::UpdateTabIcon);
             }
 
             Event::BlinkChanged => this.blinking_on = !this.blinking_on,
@@ -283,6 +306,8 @@ impl TerminalView {
             terminal,
             workspace: workspace_handle,
             has_new_content: true,
+            visible: true,
+            has_hidden_output: false,
             has_bell: false,
             focus_handle: cx.focus_handle(),
             context_menu: None,
@@ -292,6 +317,7 @@ impl TerminalView {
             blink_epoch: 0,
             can_navigate_to_selected_word: false,
             workspace_id,
+            nav_history: None,
             _subscriptions: vec![focus_in, focus_out],
         }
     }
@@ -311,6 +337,7 @@ impl TerminalView {
     pub fn clear_bel(&mut self, cx: &mut ViewContext<TerminalView>) {
         self.has_bell = false;
         cx.emit(Event::Wakeup);
+        cx.emit(ItemEvent::UpdateTabIcon);
     }
 
     pub fn deploy_context_menu(
@@ -437,6 +464,14 @@ impl TerminalView {
         &self.terminal
     }
 
+    /// Records the current scrollback position, so that going back returns to it.
+    fn push_to_nav_history(&mut self, cx: &mut ViewContext<Self>) {
+        let display_offset = self.terminal.read(cx).last_content().display_offset;
+        if let Some(nav_history) = self.nav_history.as_mut() {
+            nav_history.push(Some(display_offset), cx);
+        }
+    }
+
     fn next_blink_epoch(&mut self) -> usize {
         self.blink_epoch += 1;
         self.blink_epoch
@@ -708,10 +743,50 @@ impl Item for TerminalView {
             .into_any()
     }
 
+    fn tab_icon(&self, _: &WindowContext) -> Option<Icon> {
+        self.has_bell.then(|| {
+            Icon::new(IconName::BellDot)
+                .size(IconSize::XSmall)
+                .color(Color::Warning)
+        })
+    }
+
     fn telemetry_event_text(&self) -> Option<&'static str> {
         None
     }
 
+    fn set_nav_history(&mut self, nav_history: ItemNavHistory, _: &mut ViewContext<Self>) {
+        self.nav_history = Some(nav_history);
+    }
+
+    fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
+        self.push_to_nav_history(cx);
+    }
+
+    fn set_visible(&mut self, visible: bool, cx: &mut ViewContext<Self>) {
+        self.visible = visible;
+        if visible && std::mem::take(&mut self.has_hidden_output) {
+            cx.notify();
+            cx.emit(Event::Wakeup);
+            cx.emit(ItemEvent::UpdateTab);
+            cx.emit(SearchEvent::MatchesInvalidated);
+        }
+    }
+
+    fn navigate(&mut self, data: Box<dyn Any>, cx: &mut ViewContext<Self>) -> bool {
+        let Ok(display_offset) = data.downcast::<usize>() else {
+            return false;
+        };
+        if *display_offset == self.terminal.read(cx).last_content().display_offset {
+            return false;
+        }
+        self.terminal.update(cx, |terminal, _| {
+            terminal.scroll_to_display_offset(*display_offset)
+        });
+        cx.notify();
+        true
+    }
+
     fn clone_on_split(
         &self,
         _workspace_id: WorkspaceId,
@@ -735,10 +810,30 @@ impl Item for TerminalView {
         self.has_bell()
     }
 
+    fn prevent_auto_hide(&self, cx: &AppContext) -> bool {
+        self.terminal().read(cx).has_running_process()
+    }
+
     fn has_conflict(&self, _cx: &AppContext) -> bool {
         false
     }
 
+    fn confirm_close(&mut self, cx: &mut ViewContext<Self>) -> Task<bool> {
+        let terminal = self.terminal().read(cx);
+        if !terminal.has_running_process() {
+            return Task::ready(true);
+        }
+
+        let title = terminal.title(false);
+        let answer = cx.prompt(
+            PromptLevel::Warning,
+            "A process is still running in this terminal. Do you want to terminate it?",
+            Some(&title),
+            &["Terminate", "Cancel"],
+        );
+        cx.spawn(|_, _| async move { answer.await == Ok(0) })
+    }
+
     fn as_searchable(&self, handle: &View<Self>) -> Option<Box<dyn SearchableItemHandle>> {
         Some(Box::new(handle.clone()))
     }
@@ -754,8 +849,92 @@ impl Item for TerminalView {
         }])
     }
 
-    fn serialized_item_kind() -> Option<&'static str> {
-        Some("Terminal")
+    fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
+        cx.background_executor()
+            .spawn(TERMINAL_DB.update_workspace_id(
+                workspace.database_id(),
+                self.workspace_id,
+                cx.entity_id().as_u64(),
+            ))
+            .detach();
+        self.workspace_id = workspace.database_id();
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+/// Terminals are shared with followers as read-only [`SharedTerminalView`]s, and only
+/// when `share_dock_items` is enabled.
+impl FollowableItem for TerminalView {
+    fn remote_id(&self) -> Option<ViewId> {
+        None
+    }
+
+    fn to_state_proto(&self, cx: &WindowContext) -> Option<proto::view::Variant> {
+        if !WorkspaceSettings::get_global(cx).share_dock_items {
+            return None;
+        }
+        let terminal = self.terminal().read(cx);
+        Some(proto::view::Variant::Terminal(proto::view::Terminal {
+            title: terminal.title(true),
+            text: terminal.screen_text(),
+        }))
+    }
+
+    fn from_state_proto(
+        _: View<Pane>,
+        _: View<Workspace>,
+        _: ViewId,
+        _: &mut Option<proto::view::Variant>,
+        _: &mut WindowContext,
+    ) -> Option<Task<anyhow::Result<View<Self>>>> {
+        None
+    }
+
+    fn to_follow_event(_: &Self::Event) -> Option<FollowEvent> {
+        None
+    }
+
+    fn add_event_to_update_proto(
+        &self,
+        event: &Self::Event,
+        update: &mut Option<proto::update_view::Variant>,
+        cx: &WindowContext,
+    ) -> bool {
+        if *event != ItemEvent::UpdateTab || !WorkspaceSettings::get_global(cx).share_dock_items {
+            return false;
+        }
+        let terminal = self.terminal().read(cx);
+        *update = Some(proto::update_view::Variant::Terminal(
+            proto::update_view::Terminal {
+                title: terminal.title(true),
+                text: terminal.screen_text(),
+            },
+        ));
+        true
+    }
+
+    fn apply_update_proto(
+        &mut self,
+        _: &Model<Project>,
+        _: proto::update_view::Variant,
+        _: &mut ViewContext<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_project_item(&self, _: &WindowContext) -> bool {
+        true
+    }
+
+    fn set_leader_peer_id(&mut self, _: Option<PeerId>, _: &mut ViewContext<Self>) {}
+}
+
+impl SerializableItem for TerminalView {
+    fn serialized_item_kind() -> &'static str {
+        "Terminal"
     }
 
     fn deserialize(
@@ -763,6 +942,7 @@ impl Item for TerminalView {
         workspace: WeakView<Workspace>,
         workspace_id: workspace::WorkspaceId,
         item_id: workspace::ItemId,
+        _state: Option<String>,
         cx: &mut ViewContext<Pane>,
     ) -> Task<anyhow::Result<View<Self>>> {
         let window = cx.window_handle();
@@ -793,21 +973,6 @@ impl Item for TerminalView {
             })
         })
     }
-
-    fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
-        cx.background_executor()
-            .spawn(TERMINAL_DB.update_workspace_id(
-                workspace.database_id(),
-                self.workspace_id,
-                cx.entity_id().as_u64(),
-            ))
-            .detach();
-        self.workspace_id = workspace.database_id();
-    }
-
-    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
-        f(*event)
-    }
 }
 
 impl SearchableItem for TerminalView {
@@ -844,6 +1009,7 @@ impl SearchableItem for TerminalView {
 
     /// Focus match at given index into the Vec of matches
     fn activate_match(&mut self, index: usize, _: Vec<Self::Match>, cx: &mut ViewContext<Self>) {
+        self.push_to_nav_history(cx);
         self.terminal()
             .update(cx, |term, _| term.activate_match(index));
         cx.notify();
@@ -1077,6 +1243,59 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_shared_terminal_view(cx: &mut TestAppContext) {
+        let (project, _) = init_test(cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let view_id = ViewId {
+            creator: PeerId { owner_id: 1, id: 1 },
+            id: 2,
+        };
+
+        // Views of other kinds are left for their own builders.
+        let mut state = Some(proto::view::Variant::Editor(Default::default()));
+        let task = workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().clone();
+            SharedTerminalView::from_state_proto(pane, cx.view().clone(), view_id, &mut state, cx)
+        });
+        assert!(task.is_none());
+        assert!(state.is_some());
+
+        let mut state = Some(proto::view::Variant::Terminal(proto::view::Terminal {
+            title: "Terminal".into(),
+            text: "$ cargo build".into(),
+        }));
+        let task = workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().clone();
+            SharedTerminalView::from_state_proto(pane, cx.view().clone(), view_id, &mut state, cx)
+        });
+        assert!(state.is_none());
+        let shared_terminal = task.unwrap().await.unwrap();
+        shared_terminal.update(cx, |shared_terminal, _| {
+            assert_eq!(shared_terminal.remote_id(), Some(view_id));
+            assert_eq!(shared_terminal.title(), "Terminal");
+            assert_eq!(shared_terminal.text(), "$ cargo build");
+        });
+
+        shared_terminal
+            .update(cx, |shared_terminal, cx| {
+                shared_terminal.apply_update_proto(
+                    &project,
+                    proto::update_view::Variant::Terminal(proto::update_view::Terminal {
+                        title: "zed — cargo build".into(),
+                        text: "$ cargo build\n   Compiling zed".into(),
+                    }),
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        shared_terminal.update(cx, |shared_terminal, _| {
+            assert_eq!(shared_terminal.title(), "zed — cargo build");
+            assert_eq!(shared_terminal.text(), "$ cargo build\n   Compiling zed");
+        });
+    }
+
     /// Creates a worktree with 1 file: /root.txt
     pub async fn init_test(cx: &mut TestAppContext) -> (Model<Project>, View<Workspace>) {
         let params = cx.update(AppState::test);
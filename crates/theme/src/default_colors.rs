@@ -54,10 +54,13 @@ impl ThemeColors {
             tab_bar_background: neutral().light().step_2(),
             tab_inactive_background: neutral().light().step_2(),
             tab_active_background: neutral().light().step_1(),
+            tab_active_indicator: blue().light().step_9(),
             search_match_background: neutral().light().step_2(),
             panel_background: neutral().light().step_2(),
             panel_focused_border: blue().light().step_5(),
             pane_focused_border: blue().light().step_5(),
+            resize_handle_hover: blue().light_alpha().step_5(),
+            resize_handle_active: blue().light().step_7(),
             scrollbar_thumb_background: neutral().light_alpha().step_3(),
             scrollbar_thumb_hover_background: neutral().light_alpha().step_4(),
             scrollbar_thumb_border: gpui::transparent_black(),
@@ -148,10 +151,13 @@ impl ThemeColors {
             tab_bar_background: neutral().dark().step_2(),
             tab_inactive_background: neutral().dark().step_2(),
             tab_active_background: neutral().dark().step_1(),
+            tab_active_indicator: blue().dark().step_9(),
             search_match_background: neutral().dark().step_2(),
             panel_background: neutral().dark().step_2(),
             panel_focused_border: blue().dark().step_5(),
             pane_focused_border: blue().dark().step_5(),
+            resize_handle_hover: blue().dark_alpha().step_5(),
+            resize_handle_active: blue().dark().step_7(),
             scrollbar_thumb_background: neutral().dark_alpha().step_3(),
             scrollbar_thumb_hover_background: neutral().dark_alpha().step_4(),
             scrollbar_thumb_border: gpui::transparent_black(),
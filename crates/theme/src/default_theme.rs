@@ -5,8 +5,8 @@ use crate::prelude::*;
 use crate::{
     default_color_scales,
     one_themes::{one_dark, one_family},
-    Appearance, PlayerColors, StatusColors, SyntaxTheme, SystemColors, Theme, ThemeColors,
-    ThemeFamily, ThemeStyles,
+    Appearance, PlayerColors, StatusColors, SyntaxTheme, SystemColors, TabActiveIndicatorStyle,
+    Theme, ThemeColors, ThemeFamily, ThemeStyles,
 };
 
 fn zed_pro_daylight() -> Theme {
@@ -35,6 +35,7 @@ fn zed_pro_daylight() -> Theme {
                 indigo().light().step_9(),
                 iris().light().step_9(),
             ],
+            tab_active_indicator_style: TabActiveIndicatorStyle::default(),
         },
     }
 }
@@ -65,6 +66,7 @@ pub(crate) fn zed_pro_moonlight() -> Theme {
                 indigo().dark().step_9(),
                 iris().dark().step_9(),
             ],
+            tab_active_indicator_style: TabActiveIndicatorStyle::default(),
         },
     }
 }
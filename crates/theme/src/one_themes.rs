@@ -3,8 +3,8 @@ use std::sync::Arc;
 use gpui::{hsla, FontStyle, FontWeight, HighlightStyle};
 
 use crate::{
-    default_color_scales, Appearance, PlayerColors, StatusColors, SyntaxTheme, SystemColors, Theme,
-    ThemeColors, ThemeFamily, ThemeStyles,
+    default_color_scales, Appearance, PlayerColors, StatusColors, SyntaxTheme, SystemColors,
+    TabActiveIndicatorStyle, Theme, ThemeColors, ThemeFamily, ThemeStyles,
 };
 
 // Note: This theme family is not the one you see in Zed at the moment.
@@ -79,6 +79,7 @@ pub(crate) fn one_dark() -> Theme {
                 tab_bar_background: bg,
                 tab_inactive_background: bg,
                 tab_active_background: editor,
+                tab_active_indicator: blue,
                 search_match_background: bg,
 
                 editor_background: editor,
@@ -131,6 +132,8 @@ pub(crate) fn one_dark() -> Theme {
                 panel_background: bg,
                 panel_focused_border: blue,
                 pane_focused_border: blue,
+                resize_handle_hover: hsla(207.8 / 360., 81. / 100., 66. / 100., 0.5),
+                resize_handle_active: blue,
                 scrollbar_thumb_background: gpui::transparent_black(),
                 scrollbar_thumb_hover_background: hsla(225.0 / 360., 11.8 / 100., 26.7 / 100., 1.0),
                 scrollbar_thumb_border: hsla(228. / 360., 8. / 100., 25. / 100., 1.),
@@ -249,6 +252,7 @@ pub(crate) fn one_dark() -> Theme {
                 ],
             }),
             accents: vec![blue, orange, purple, teal],
+            tab_active_indicator_style: TabActiveIndicatorStyle::default(),
         },
     }
 }
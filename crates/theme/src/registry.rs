@@ -184,6 +184,10 @@ impl ThemeRegistry {
                     player: player_colors,
                     syntax: Arc::new(syntax_colors),
                     accents: Vec::new(),
+                    tab_active_indicator_style: user_theme
+                        .style
+                        .tab_active_indicator_style
+                        .unwrap_or_default(),
                 },
             }
         }));
@@ -254,6 +258,9 @@ impl ThemeRegistry {
             let Some(theme_path) = theme_path.log_err() else {
                 continue;
             };
+            if theme_path.extension() != Some("json".as_ref()) {
+                continue;
+            }
 
             self.load_user_theme(&theme_path, fs.clone())
                 .await
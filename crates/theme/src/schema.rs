@@ -9,7 +9,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::{StatusColorsRefinement, ThemeColorsRefinement};
+use crate::{StatusColorsRefinement, TabActiveIndicatorStyle, ThemeColorsRefinement};
 
 pub(crate) fn try_parse_color(color: &str) -> Result<Hsla> {
     let rgba = gpui::Rgba::try_from(color)?;
@@ -65,6 +65,10 @@ pub struct ThemeStyleContent {
     /// The styles for syntax nodes.
     #[serde(default)]
     pub syntax: IndexMap<String, HighlightStyleContent>,
+
+    /// How the active tab is set apart from the other tabs.
+    #[serde(rename = "tab.active_indicator_style", default)]
+    pub tab_active_indicator_style: Option<TabActiveIndicatorStyle>,
 }
 
 impl ThemeStyleContent {
@@ -277,6 +281,10 @@ pub struct ThemeColorsContent {
     #[serde(rename = "tab.active_background")]
     pub tab_active_background: Option<String>,
 
+    /// The color of the bar marking the active tab, when the theme uses one.
+    #[serde(rename = "tab.active_indicator")]
+    pub tab_active_indicator: Option<String>,
+
     #[serde(rename = "search.match_background")]
     pub search_match_background: Option<String>,
 
@@ -289,6 +297,14 @@ pub struct ThemeColorsContent {
     #[serde(rename = "pane.focused_border")]
     pub pane_focused_border: Option<String>,
 
+    /// The color of the handles used to resize docks and panes, when hovered over.
+    #[serde(rename = "resize_handle.hover")]
+    pub resize_handle_hover: Option<String>,
+
+    /// The color of the handles used to resize docks and panes, while being dragged.
+    #[serde(rename = "resize_handle.active")]
+    pub resize_handle_active: Option<String>,
+
     /// The color of the scrollbar thumb.
     #[serde(rename = "scrollbar_thumb.background")]
     pub scrollbar_thumb_background: Option<String>,
@@ -627,6 +643,10 @@ impl ThemeColorsContent {
                 .tab_active_background
                 .as_ref()
                 .and_then(|color| try_parse_color(&color).ok()),
+            tab_active_indicator: self
+                .tab_active_indicator
+                .as_ref()
+                .and_then(|color| try_parse_color(&color).ok()),
             search_match_background: self
                 .search_match_background
                 .as_ref()
@@ -643,6 +663,14 @@ impl ThemeColorsContent {
                 .pane_focused_border
                 .as_ref()
                 .and_then(|color| try_parse_color(&color).ok()),
+            resize_handle_hover: self
+                .resize_handle_hover
+                .as_ref()
+                .and_then(|color| try_parse_color(&color).ok()),
+            resize_handle_active: self
+                .resize_handle_active
+                .as_ref()
+                .and_then(|color| try_parse_color(&color).ok()),
             scrollbar_thumb_background: self
                 .scrollbar_thumb_background
                 .as_ref()
@@ -1,5 +1,7 @@
 use gpui::Hsla;
 use refineable::Refineable;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::{PlayerColors, StatusColors, StatusColorsRefinement, SyntaxTheme, SystemColors};
@@ -115,10 +117,16 @@ pub struct ThemeColors {
     pub tab_bar_background: Hsla,
     pub tab_inactive_background: Hsla,
     pub tab_active_background: Hsla,
+    /// The color of the bar marking the active tab, when the theme uses one.
+    pub tab_active_indicator: Hsla,
     pub search_match_background: Hsla,
     pub panel_background: Hsla,
     pub panel_focused_border: Hsla,
     pub pane_focused_border: Hsla,
+    /// The color of the handles used to resize docks and panes, when hovered over.
+    pub resize_handle_hover: Hsla,
+    /// The color of the handles used to resize docks and panes, while being dragged.
+    pub resize_handle_active: Hsla,
     /// The color of the scrollbar thumb.
     pub scrollbar_thumb_background: Hsla,
     /// The color of the scrollbar thumb when hovered over.
@@ -233,6 +241,20 @@ pub struct ThemeColors {
     pub link_text_hover: Hsla,
 }
 
+/// How the active tab is set apart from the other tabs, in addition to
+/// `tab.active_background`.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TabActiveIndicatorStyle {
+    /// Only the background of the active tab differs.
+    #[default]
+    Background,
+    /// A bar along the bottom edge of the active tab, in `tab.active_indicator`.
+    Underline,
+    /// A bar along the top edge of the active tab, in `tab.active_indicator`.
+    TopBar,
+}
+
 #[derive(Refineable, Clone)]
 pub struct ThemeStyles {
     pub system: SystemColors,
@@ -241,6 +263,9 @@ pub struct ThemeStyles {
     /// Example: Player colors, rainbow brackets and indent guides, etc.
     pub accents: Vec<Hsla>,
 
+    /// How the active tab is set apart from the other tabs.
+    pub tab_active_indicator_style: TabActiveIndicatorStyle,
+
     #[refineable]
     pub colors: ThemeColors,
 
@@ -308,4 +333,22 @@ mod tests {
         assert_eq!(colors.background, Some(gpui::rgb(0xff00ff).into()));
         assert_eq!(colors.text, Some(gpui::rgb(0xff0000).into()));
     }
+
+    #[test]
+    fn deserialize_tab_active_indicator_from_json() {
+        let style: crate::ThemeStyleContent = serde_json::from_value(json!({
+            "tab.active_indicator": "#ff0000",
+            "tab.active_indicator_style": "top_bar"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            style.tab_active_indicator_style,
+            Some(TabActiveIndicatorStyle::TopBar)
+        );
+        assert_eq!(
+            style.theme_colors_refinement().tab_active_indicator,
+            Some(gpui::rgb(0xff0000).into())
+        );
+    }
 }
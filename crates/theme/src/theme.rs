@@ -138,6 +138,12 @@ impl Theme {
         &self.styles.status
     }
 
+    /// Returns how the active tab is set apart from the other tabs.
+    #[inline(always)]
+    pub fn tab_active_indicator_style(&self) -> TabActiveIndicatorStyle {
+        self.styles.tab_active_indicator_style
+    }
+
     /// Returns the color for the syntax node with the given name.
     #[inline(always)]
     pub fn syntax_color(&self, name: &str) -> Hsla {
@@ -60,6 +60,7 @@ impl VsCodeThemeConverter {
                 status: status_colors,
                 players: Vec::new(),
                 syntax: syntax_theme,
+                tab_active_indicator_style: None,
             },
         })
     }
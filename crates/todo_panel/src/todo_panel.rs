@@ -0,0 +1,739 @@
+mod todo_panel_settings;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Result;
+use collections::HashSet;
+use db::kvp::{write_kvp_debounced, KEY_VALUE_STORE};
+use editor::{scroll::Autoscroll, Bias, Editor};
+use gpui::{
+    actions, uniform_list, Action, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, KeyContext, Model, Pixels, Subscription, Task, UniformListScrollHandle, View,
+    ViewContext, VisualContext, WeakView, WindowContext,
+};
+use language::Point;
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
+use project::{Fs, Project, ProjectPath};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use todo_panel_settings::TodoPanelSettings;
+use ui::{prelude::*, ListItem, Tooltip};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    item::ItemHandle,
+    panels::{register_panel, register_status_item, PanelPlacement},
+    StatusItemSide, StatusItemView, Workspace,
+};
+
+const TODO_PANEL_KEY: &'static str = "TodoPanel";
+const SCAN_DEBOUNCE: Duration = Duration::from_millis(200);
+
+actions!(todo_panel, [ToggleFocus]);
+
+pub fn init(cx: &mut AppContext) {
+    TodoPanelSettings::register(cx);
+    register_panel(PanelPlacement::default(), TodoPanel::load, cx);
+    register_status_item(
+        StatusItemSide::Left,
+        0,
+        |workspace, cx| {
+            let panel = workspace.panel::<TodoPanel>(cx)?;
+            Some(cx.new_view(|cx| TodoIndicator::new(panel, cx)))
+        },
+        cx,
+    );
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<TodoPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+/// A tagged comment found in a file.
+#[derive(Clone, Debug, PartialEq)]
+struct Todo {
+    tag: SharedString,
+    text: SharedString,
+    position: Point,
+}
+
+/// A row of the panel: either a file header, or one of the file's comments.
+enum ListEntry {
+    File(ProjectPath),
+    Todo(ProjectPath, usize),
+}
+
+/// A dock panel listing the tagged comments (TODO, FIXME, …) of the project's
+/// files, grouped by file.
+pub struct TodoPanel {
+    project: Model<Project>,
+    workspace: WeakView<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    todos: BTreeMap<ProjectPath, Vec<Todo>>,
+    list_entries: Vec<ListEntry>,
+    selected_index: Option<usize>,
+    list: UniformListScrollHandle,
+    tags: Vec<String>,
+    needs_full_scan: bool,
+    dirty_paths: HashSet<ProjectPath>,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    scan_task: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTodoPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl TodoPanel {
+    fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().clone();
+        let _subscriptions = vec![
+            cx.subscribe(&project, |this, _, event, cx| match event {
+                project::Event::WorktreeAdded => {
+                    this.needs_full_scan = true;
+                    this.schedule_scan(cx);
+                }
+                project::Event::WorktreeRemoved(worktree_id) => {
+                    let worktree_id = *worktree_id;
+                    this.todos
+                        .retain(|project_path, _| project_path.worktree_id != worktree_id);
+                    this.update_list_entries(cx);
+                }
+                project::Event::WorktreeUpdatedEntries(worktree_id, changes) => {
+                    this.dirty_paths
+                        .extend(changes.iter().map(|(path, _, _)| ProjectPath {
+                            worktree_id: *worktree_id,
+                            path: path.clone(),
+                        }));
+                    this.schedule_scan(cx);
+                }
+                _ => {}
+            }),
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                let tags = &TodoPanelSettings::get_global(cx).tags;
+                if *tags != this.tags {
+                    this.tags = tags.clone();
+                    this.needs_full_scan = true;
+                    this.schedule_scan(cx);
+                }
+                cx.notify();
+            }),
+        ];
+
+        let mut this = Self {
+            project,
+            workspace: workspace.weak_handle(),
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            todos: BTreeMap::default(),
+            list_entries: Vec::new(),
+            selected_index: None,
+            list: UniformListScrollHandle::new(),
+            tags: TodoPanelSettings::get_global(cx).tags.clone(),
+            needs_full_scan: true,
+            dirty_paths: HashSet::default(),
+            width: None,
+            height: None,
+            scan_task: Task::ready(None),
+            _subscriptions,
+        };
+        this.schedule_scan(cx);
+        this
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(TODO_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedTodoPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = cx.new_view(|cx| Self::new(workspace, cx));
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width;
+                        panel.height = serialized_panel.height;
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(serialized) = serde_json::to_string(&SerializedTodoPanel {
+            width: self.width,
+            height: self.height,
+        })
+        .log_err()
+        {
+            write_kvp_debounced(TODO_PANEL_KEY, serialized, cx);
+        }
+    }
+
+    /// The number of tagged comments found in the project.
+    pub fn todo_count(&self) -> usize {
+        self.todos.values().map(|todos| todos.len()).sum()
+    }
+
+    /// Scans the files that changed since the last scan, or all of the project's
+    /// files when the worktrees or the tags changed, after a short delay.
+    ///
+    /// A scan that is superseded by a newer one is dropped without being applied,
+    /// so the paths it was scanning stay dirty until the newer scan completes.
+    fn schedule_scan(&mut self, cx: &mut ViewContext<Self>) {
+        self.scan_task = cx.spawn(|this, mut cx| {
+            async move {
+                cx.background_executor().timer(SCAN_DEBOUNCE).await;
+
+                let (full_scan, files, fs, regex) = this.update(&mut cx, |this, cx| {
+                    let files = this.files_to_scan(cx);
+                    (
+                        this.needs_full_scan,
+                        files,
+                        this.fs.clone(),
+                        tags_regex(&this.tags),
+                    )
+                })?;
+
+                let todos = cx
+                    .background_executor()
+                    .spawn(async move {
+                        let mut todos = Vec::new();
+                        if let Some(regex) = regex {
+                            for (project_path, abs_path) in files {
+                                let file_todos = match fs.load(&abs_path).await {
+                                    Ok(text) => find_todos(&text, &regex),
+                                    Err(_) => Vec::new(),
+                                };
+                                todos.push((project_path, file_todos));
+                            }
+                        }
+                        todos
+                    })
+                    .await;
+
+                this.update(&mut cx, |this, cx| {
+                    if full_scan {
+                        this.todos.clear();
+                    } else {
+                        for path in &this.dirty_paths {
+                            this.todos.remove(path);
+                        }
+                    }
+                    this.needs_full_scan = false;
+                    this.dirty_paths.clear();
+                    this.todos.extend(
+                        todos
+                            .into_iter()
+                            .filter(|(_, file_todos)| !file_todos.is_empty()),
+                    );
+                    this.update_list_entries(cx);
+                })
+            }
+            .log_err()
+        });
+    }
+
+    /// The files to scan, along with their absolute paths. Ignored and external
+    /// entries are skipped, as in project search.
+    fn files_to_scan(&self, cx: &AppContext) -> Vec<(ProjectPath, PathBuf)> {
+        let project = self.project.read(cx);
+        let mut files = Vec::new();
+        if self.needs_full_scan {
+            for worktree in project.visible_worktrees(cx) {
+                let worktree = worktree.read(cx);
+                let Some(local_worktree) = worktree.as_local() else {
+                    continue;
+                };
+                let worktree_id = worktree.id();
+                for entry in worktree.files(false, 0) {
+                    if entry.is_external {
+                        continue;
+                    }
+                    if let Some(abs_path) = local_worktree.absolutize(&entry.path).log_err() {
+                        let project_path = ProjectPath {
+                            worktree_id,
+                            path: entry.path.clone(),
+                        };
+                        files.push((project_path, abs_path));
+                    }
+                }
+            }
+        } else {
+            for project_path in &self.dirty_paths {
+                let Some(worktree) = project.worktree_for_id(project_path.worktree_id, cx) else {
+                    continue;
+                };
+                let worktree = worktree.read(cx);
+                let Some(local_worktree) = worktree.as_local() else {
+                    continue;
+                };
+                let Some(entry) = worktree.entry_for_path(&project_path.path) else {
+                    continue;
+                };
+                if entry.is_file() && !entry.is_ignored && !entry.is_external {
+                    if let Some(abs_path) = local_worktree.absolutize(&entry.path).log_err() {
+                        files.push((project_path.clone(), abs_path));
+                    }
+                }
+            }
+        }
+        files
+    }
+
+    fn update_list_entries(&mut self, cx: &mut ViewContext<Self>) {
+        self.list_entries.clear();
+        for (project_path, todos) in &self.todos {
+            self.list_entries
+                .push(ListEntry::File(project_path.clone()));
+            self.list_entries
+                .extend((0..todos.len()).map(|ix| ListEntry::Todo(project_path.clone(), ix)));
+        }
+        self.selected_index = self
+            .selected_index
+            .map(|ix| ix.min(self.list_entries.len().saturating_sub(1)))
+            .filter(|_| !self.list_entries.is_empty());
+        cx.notify();
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix < self.list_entries.len() {
+            self.selected_index = Some(ix);
+            self.list.scroll_to_item(ix);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, cx: &mut ViewContext<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, cx: &mut ViewContext<Self>) {
+        self.select_index(self.list_entries.len().saturating_sub(1), cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_entry(ix, cx);
+        }
+    }
+
+    /// Opens the file of the entry, moving the cursor to the comment when the
+    /// entry is one.
+    fn open_entry(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let (project_path, position) = match self.list_entries.get(ix) {
+            Some(ListEntry::File(project_path)) => (project_path.clone(), None),
+            Some(ListEntry::Todo(project_path, todo_ix)) => (
+                project_path.clone(),
+                self.todos
+                    .get(project_path)
+                    .and_then(|todos| todos.get(*todo_ix))
+                    .map(|todo| todo.position),
+            ),
+            None => return,
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let open_task = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(project_path, None, true, cx)
+        });
+        cx.spawn(|_, mut cx| async move {
+            let item = open_task.await?;
+            if let Some((editor, position)) = item.downcast::<Editor>().zip(position) {
+                editor.update(&mut cx, |editor, cx| {
+                    let snapshot = editor.buffer().read(cx).snapshot(cx);
+                    let point = snapshot.clip_point(position, Bias::Left);
+                    editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+                        s.select_ranges([point..point])
+                    });
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::default();
+        dispatch_context.add("TodoPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let item = ListItem::new(ix)
+            .inset(true)
+            .selected(self.selected_index == Some(ix));
+        let item = match &self.list_entries[ix] {
+            ListEntry::File(project_path) => {
+                let path = &project_path.path;
+                let file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string_lossy().to_string());
+                let directory = path
+                    .parent()
+                    .filter(|parent| *parent != Path::new(""))
+                    .map(|parent| parent.to_string_lossy().to_string());
+                let count = self.todos.get(project_path).map_or(0, |todos| todos.len());
+                item.child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(file_name))
+                        .children(directory.map(|directory| {
+                            Label::new(directory)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                        }))
+                        .child(
+                            Label::new(count.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+            }
+            ListEntry::Todo(project_path, todo_ix) => {
+                let todo = &self.todos[project_path][*todo_ix];
+                let color = if todo.tag.as_ref() == "TODO" {
+                    Color::Info
+                } else {
+                    Color::Warning
+                };
+                item.indent_level(1).child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(todo.tag.clone()).color(color))
+                        .child(Label::new(todo.text.clone()))
+                        .child(
+                            Label::new(format!("{}", todo.position.row + 1))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+            }
+        };
+        item.on_click(cx.listener(move |this, _, cx| {
+            this.selected_index = Some(ix);
+            this.open_entry(ix, cx);
+            cx.notify();
+        }))
+    }
+}
+
+/// A regex matching any of the tags as a whole word, capturing the tag and the
+/// rest of the line.
+fn tags_regex(tags: &[String]) -> Option<Regex> {
+    let tags = tags
+        .iter()
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| regex::escape(tag))
+        .collect::<Vec<_>>();
+    if tags.is_empty() {
+        return None;
+    }
+    Regex::new(&format!(r"\b({})\b(.*)", tags.join("|"))).log_err()
+}
+
+fn find_todos(text: &str, regex: &Regex) -> Vec<Todo> {
+    let mut todos = Vec::new();
+    for (row, line) in text.lines().enumerate() {
+        let Some(captures) = regex.captures(line) else {
+            continue;
+        };
+        let (Some(tag), Some(rest)) = (captures.get(1), captures.get(2)) else {
+            continue;
+        };
+        let text = rest
+            .as_str()
+            .trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+            .trim_end()
+            .trim_end_matches("*/")
+            .trim_end()
+            .to_string();
+        todos.push(Todo {
+            tag: tag.as_str().to_string().into(),
+            text: text.into(),
+            position: Point::new(row as u32, tag.start() as u32),
+        });
+    }
+    todos
+}
+
+impl EventEmitter<PanelEvent> for TodoPanel {}
+
+impl Render for TodoPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("todo-panel")
+            .size_full()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .child(if self.list_entries.is_empty() {
+                h_flex()
+                    .flex_1()
+                    .justify_center()
+                    .child(Label::new("No tagged comments").color(Color::Muted))
+                    .into_any_element()
+            } else {
+                uniform_list(
+                    cx.view().clone(),
+                    "todo-panel-entries",
+                    self.list_entries.len(),
+                    |this, range, cx| {
+                        range
+                            .map(|ix| this.render_entry(ix, cx).into_any_element())
+                            .collect()
+                    },
+                )
+                .flex_1()
+                .track_scroll(self.list.clone())
+                .into_any_element()
+            })
+    }
+}
+
+impl FocusableView for TodoPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for TodoPanel {
+    fn persistent_name() -> &'static str {
+        "TodoPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        TodoPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<TodoPanelSettings>(self.fs.clone(), cx, move |settings| {
+            settings.dock = Some(position)
+        });
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        let settings = TodoPanelSettings::get_global(cx);
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or_else(|| settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or_else(|| settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        match self.position(cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        TodoPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::Check)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("TODO Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+}
+
+/// A status bar item showing the number of tagged comments in the project, which
+/// toggles the TODO panel when clicked.
+pub struct TodoIndicator {
+    panel: View<TodoPanel>,
+    _observe_panel: Subscription,
+}
+
+impl TodoIndicator {
+    pub fn new(panel: View<TodoPanel>, cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            _observe_panel: cx.observe(&panel, |_, _, cx| cx.notify()),
+            panel,
+        }
+    }
+}
+
+impl Render for TodoIndicator {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let count = self.panel.read(cx).todo_count();
+        h_flex().when(count > 0, |this| {
+            this.child(
+                Button::new("todo-count", count.to_string())
+                    .label_size(LabelSize::Small)
+                    .icon(IconName::Check)
+                    .icon_size(IconSize::Small)
+                    .icon_position(IconPosition::Start)
+                    .tooltip(|cx| Tooltip::for_action("Tagged Comments", &ToggleFocus, cx))
+                    .on_click(|_, cx| cx.dispatch_action(ToggleFocus.boxed_clone())),
+            )
+        })
+    }
+}
+
+impl StatusItemView for TodoIndicator {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+
+    #[gpui::test]
+    async fn test_scanning_tagged_comments(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "a.rs": "fn a() {\n    // TODO: handle errors\n}\n",
+                "src": {
+                    "b.rs": "// FIXME(nathan) this is slow\nlet TODOS = 1;\n/* HACK */\n",
+                    "c.rs": "fn c() {}\n",
+                },
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let window = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let workspace = window.root(cx).unwrap();
+        let panel = workspace.update(cx, |workspace, cx| {
+            cx.new_view(|cx| TodoPanel::new(workspace, cx))
+        });
+        cx.executor().advance_clock(SCAN_DEBOUNCE);
+        cx.run_until_parked();
+
+        let todos = |panel: &View<TodoPanel>, cx: &mut VisualTestContext| {
+            panel.update(cx, |panel, _| {
+                panel
+                    .todos
+                    .iter()
+                    .flat_map(|(project_path, todos)| {
+                        todos.iter().map(|todo| {
+                            (
+                                project_path.path.to_string_lossy().to_string(),
+                                todo.position.row,
+                                todo.tag.to_string(),
+                                todo.text.to_string(),
+                            )
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            todos(&panel, cx),
+            [
+                ("a.rs".into(), 1, "TODO".into(), "handle errors".into()),
+                (
+                    "src/b.rs".into(),
+                    0,
+                    "FIXME".into(),
+                    "(nathan) this is slow".into()
+                ),
+                ("src/b.rs".into(), 2, "HACK".into(), "".into()),
+            ]
+        );
+        assert_eq!(panel.read_with(cx, |panel, _| panel.todo_count()), 3);
+
+        // Only the changed file is rescanned.
+        fs.save(
+            Path::new("/root/a.rs"),
+            &"fn a() {}\n".into(),
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        cx.run_until_parked();
+        cx.executor().advance_clock(SCAN_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            todos(&panel, cx),
+            [
+                (
+                    "src/b.rs".into(),
+                    0,
+                    "FIXME".into(),
+                    "(nathan) this is slow".into()
+                ),
+                ("src/b.rs".into(), 2, "HACK".into(), "".into()),
+            ]
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            client::init_settings(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+            editor::init(cx);
+        });
+    }
+}
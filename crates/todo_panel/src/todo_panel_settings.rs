@@ -0,0 +1,54 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug)]
+pub struct TodoPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+    pub tags: Vec<String>,
+}
+
+/// TODO panel configuration.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct TodoPanelSettingsContent {
+    /// Whether to show the TODO panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the TODO panel.
+    ///
+    /// Default: left
+    pub dock: Option<DockPosition>,
+    /// Default width of the TODO panel in pixels, when docked to the left or right.
+    ///
+    /// Default: 280
+    pub default_width: Option<f32>,
+    /// Default height of the TODO panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+    /// The comment tags to look for. They are matched as whole, case-sensitive words.
+    ///
+    /// Default: ["TODO", "FIXME", "HACK"]
+    pub tags: Option<Vec<String>>,
+}
+
+impl settings::Settings for TodoPanelSettings {
+    const KEY: Option<&'static str> = Some("todo_panel");
+    type FileContent = TodoPanelSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _cx: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
@@ -2,6 +2,7 @@ use crate::prelude::*;
 use gpui::{AnyElement, IntoElement, Stateful};
 use smallvec::SmallVec;
 use std::cmp::Ordering;
+use theme::TabActiveIndicatorStyle;
 
 /// The position of a [`Tab`] within a list of tabs.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -115,9 +116,33 @@ impl RenderOnce for Tab {
             ),
         };
 
+        let indicator_style = if self.selected {
+            cx.theme().tab_active_indicator_style()
+        } else {
+            TabActiveIndicatorStyle::Background
+        };
+        let indicator_color = cx.theme().colors().tab_active_indicator;
+
         self.div
             .h(rems(Self::CONTAINER_HEIGHT_IN_REMS))
             .bg(tab_bg)
+            .when(
+                indicator_style != TabActiveIndicatorStyle::Background,
+                |this| {
+                    this.relative().child(
+                        div()
+                            .absolute()
+                            .left_0()
+                            .right_0()
+                            .h(px(2.))
+                            .bg(indicator_color)
+                            .map(|this| match indicator_style {
+                                TabActiveIndicatorStyle::TopBar => this.top_0(),
+                                _ => this.bottom_0(),
+                            }),
+                    )
+                },
+            )
             .border_color(cx.theme().colors().border)
             .map(|this| match self.position {
                 TabPosition::First => {
@@ -0,0 +1,137 @@
+//! Listing the registered actions along with their key bindings, for the parts of the
+//! UI that present actions to the user, such as the command palette, menus or the
+//! keymap editor.
+
+use gpui::{AppContext, KeyBinding, SharedString};
+
+/// The namespaces of the actions defined by this crate. The actions toggling and
+/// resizing docks are part of the `workspace` namespace.
+pub const WORKSPACE_ACTION_NAMESPACES: &[&str] = &["pane", "workspace"];
+
+/// An action that can be built by name, as returned by [`registered_actions`].
+#[derive(Clone, Debug)]
+pub struct RegisteredAction {
+    /// The name the action is registered under, such as `pane::GoBack`.
+    pub name: SharedString,
+    /// The name shown to users, such as `pane: go back`.
+    pub humanized_name: String,
+    /// The bindings for the action in the keymap, in any context and with any data,
+    /// in the order they were added.
+    pub bindings: Vec<KeyBinding>,
+}
+
+/// Returns the registered actions in the given namespaces, or in every namespace when
+/// `namespaces` is empty, sorted by name.
+pub fn registered_actions(namespaces: &[&str], cx: &AppContext) -> Vec<RegisteredAction> {
+    let keymap = cx.key_bindings();
+    let keymap = keymap.borrow();
+    let mut actions = cx
+        .all_action_names()
+        .iter()
+        .filter(|name| {
+            namespaces.is_empty()
+                || name
+                    .split_once("::")
+                    .map_or(false, |(namespace, _)| namespaces.contains(&namespace))
+        })
+        .map(|name| RegisteredAction {
+            name: name.clone(),
+            humanized_name: humanize_action_name(name),
+            bindings: keymap
+                .bindings()
+                .filter(|binding| binding.action().name() == name.as_ref())
+                .cloned()
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    actions.sort_by(|a, b| a.name.as_ref().cmp(b.name.as_ref()));
+    actions
+}
+
+/// Turns an action name such as `editor::GoToDefinition` into `editor: go to definition`.
+pub fn humanize_action_name(name: &str) -> String {
+    let capacity = name.len() + name.chars().filter(|c| c.is_uppercase()).count();
+    let mut result = String::with_capacity(capacity);
+    for char in name.chars() {
+        if char == ':' {
+            if result.ends_with(':') {
+                result.push(' ');
+            } else {
+                result.push(':');
+            }
+        } else if char == '_' {
+            result.push(' ');
+        } else if char.is_uppercase() {
+            if !result.ends_with(' ') {
+                result.push(' ');
+            }
+            result.extend(char.to_lowercase());
+        } else {
+            result.push(char);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pane, CloseWindow};
+    use gpui::TestAppContext;
+
+    #[test]
+    fn test_humanize_action_name() {
+        assert_eq!(
+            humanize_action_name("editor::GoToDefinition"),
+            "editor: go to definition"
+        );
+        assert_eq!(
+            humanize_action_name("editor::Backspace"),
+            "editor: backspace"
+        );
+        assert_eq!(
+            humanize_action_name("go_to_line::Deploy"),
+            "go to line: deploy"
+        );
+    }
+
+    #[gpui::test]
+    fn test_registered_actions(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            cx.bind_keys([
+                KeyBinding::new("cmd-shift-w", CloseWindow, None),
+                KeyBinding::new("ctrl--", pane::GoBack, Some("Pane")),
+                KeyBinding::new("cmd-[", pane::GoBack, Some("Pane")),
+            ]);
+
+            let actions = registered_actions(WORKSPACE_ACTION_NAMESPACES, cx);
+            assert!(actions
+                .windows(2)
+                .all(|pair| pair[0].name.as_ref() < pair[1].name.as_ref()));
+            assert!(actions
+                .iter()
+                .all(|action| action.name.starts_with("pane::")
+                    || action.name.starts_with("workspace::")));
+
+            let go_back = actions
+                .iter()
+                .find(|action| action.name.as_ref() == "pane::GoBack")
+                .unwrap();
+            assert_eq!(go_back.humanized_name, "pane: go back");
+            assert_eq!(
+                go_back
+                    .bindings
+                    .iter()
+                    .map(|binding| binding.keystrokes()[0].to_string())
+                    .collect::<Vec<_>>(),
+                ["^-", "⌘["]
+            );
+
+            let close_window = actions
+                .iter()
+                .find(|action| action.name.as_ref() == "workspace::CloseWindow")
+                .unwrap();
+            assert_eq!(close_window.bindings.len(), 1);
+        });
+    }
+}
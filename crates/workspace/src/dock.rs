@@ -1,10 +1,13 @@
 use crate::persistence::model::DockData;
-use crate::DraggedDock;
-use crate::{status_bar::StatusItemView, Workspace};
+use crate::{floating_panel::FloatingPanel, status_bar::StatusItemView, Pane, Workspace};
+use crate::{DraggedDock, DraggedDockSplit};
+use collections::HashSet;
 use gpui::{
-    div, px, Action, AnchorCorner, AnyView, AppContext, Axis, ClickEvent, Entity, EntityId,
-    EventEmitter, FocusHandle, FocusableView, IntoElement, MouseButton, ParentElement, Render,
-    SharedString, Styled, Subscription, View, ViewContext, VisualContext, WeakView, WindowContext,
+    div, px, relative, size, Action, AnchorCorner, AnyElement, AnyView, AnyWindowHandle,
+    AppContext, Axis, Bounds, ClickEvent, DragMoveEvent, Entity, EntityId, EventEmitter,
+    FocusHandle, FocusableView, IntoElement, MouseButton, ParentElement, Render, SharedString,
+    Styled, Subscription, TitlebarOptions, View, ViewContext, VisualContext, WeakView,
+    WindowBounds, WindowContext, WindowKind, WindowOptions,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -14,6 +17,7 @@ use ui::{h_flex, ContextMenu, IconButton, Tooltip};
 use ui::{prelude::*, right_click_menu};
 
 const RESIZE_HANDLE_SIZE: Pixels = Pixels(6.);
+const MIN_SPLIT_RATIO: f32 = 0.1;
 
 pub enum PanelEvent {
     ZoomIn,
@@ -27,11 +31,17 @@ pub trait Panel: FocusableView + EventEmitter<PanelEvent> {
     fn position(&self, cx: &WindowContext) -> DockPosition;
     fn position_is_valid(&self, position: DockPosition) -> bool;
     fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>);
+    /// The size of the panel along its dock's axis. Each panel keeps its own size,
+    /// so switching between panels in a dock restores the size of the activated one.
     fn size(&self, cx: &WindowContext) -> Pixels;
+    /// Called when the user resizes the dock while this panel is active. Panels are
+    /// expected to persist the size themselves, so it survives restarts.
     fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>);
     fn icon(&self, cx: &WindowContext) -> Option<ui::IconName>;
     fn icon_tooltip(&self, cx: &WindowContext) -> Option<&'static str>;
     fn toggle_action(&self) -> Box<dyn Action>;
+    /// A short badge, such as a count, drawn over the panel's status bar button.
+    /// Panels should notify when it changes so the button is redrawn.
     fn icon_label(&self, _: &WindowContext) -> Option<String> {
         None
     }
@@ -42,7 +52,14 @@ pub trait Panel: FocusableView + EventEmitter<PanelEvent> {
         false
     }
     fn set_zoomed(&mut self, _zoomed: bool, _cx: &mut ViewContext<Self>) {}
+    /// Called with `true` when the panel is shown, and with `false` when it's hidden
+    /// again, either because its dock closed or another panel took its place.
     fn set_active(&mut self, _active: bool, _cx: &mut ViewContext<Self>) {}
+    /// The panes hosted by the panel, if any. Their items are consulted before the
+    /// dock gets hidden automatically, see [`crate::item::Item::prevent_auto_hide`].
+    fn panes(&self) -> Vec<View<Pane>> {
+        Vec::new()
+    }
 }
 
 pub trait PanelHandle: Send + Sync {
@@ -62,6 +79,7 @@ pub trait PanelHandle: Send + Sync {
     fn icon_label(&self, cx: &WindowContext) -> Option<String>;
     fn focus_handle(&self, cx: &AppContext) -> FocusHandle;
     fn to_any(&self) -> AnyView;
+    fn panes(&self, cx: &WindowContext) -> Vec<View<Pane>>;
 }
 
 impl<T> PanelHandle for View<T>
@@ -97,7 +115,10 @@ where
     }
 
     fn set_active(&self, active: bool, cx: &mut WindowContext) {
-        self.update(cx, |this, cx| this.set_active(active, cx))
+        self.update(cx, |this, cx| this.set_active(active, cx));
+        for pane in self.panes(cx) {
+            pane.update(cx, |pane, cx| pane.set_visible(active, cx));
+        }
     }
 
     fn size(&self, cx: &WindowContext) -> Pixels {
@@ -131,6 +152,10 @@ where
     fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
         self.read(cx).focus_handle(cx).clone()
     }
+
+    fn panes(&self, cx: &WindowContext) -> Vec<View<Pane>> {
+        self.read(cx).panes()
+    }
 }
 
 impl From<&dyn PanelHandle> for AnyView {
@@ -144,11 +169,34 @@ pub struct Dock {
     panel_entries: Vec<PanelEntry>,
     is_open: bool,
     active_panel_index: usize,
+    /// The panel shown below the active one, when a side dock is split.
+    split_panel_index: Option<usize>,
+    /// The fraction of the dock's height given to the active panel when split.
+    split_ratio: f32,
+    collapsed_panels: HashSet<EntityId>,
+    /// Panels popped out of the dock into floating windows, to be docked back later.
+    popped_out_entries: Vec<PoppedOutEntry>,
     focus_handle: FocusHandle,
     pub(crate) serialized_dock: Option<DockData>,
     _focus_subscription: Subscription,
 }
 
+/// Emitted by a [`Dock`] when something shown by its [`PanelButtons`] changes, so that status
+/// items can re-render on these rather than on every notification of the dock's panels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DockEvent {
+    /// The dock was opened or closed.
+    VisibilityChanged,
+    /// The active panel, or the panel shown below it, changed.
+    ActivePanelChanged,
+    /// A panel was added to or removed from the dock.
+    PanelsChanged,
+    /// The icon, icon label or tooltip of one of the dock's panels changed.
+    PanelButtonChanged,
+}
+
+impl EventEmitter<DockEvent> for Dock {}
+
 impl FocusableView for Dock {
     fn focus_handle(&self, _: &AppContext) -> FocusHandle {
         self.focus_handle.clone()
@@ -178,17 +226,72 @@ impl DockPosition {
             Self::Bottom => Axis::Vertical,
         }
     }
+
+    /// The side dock across from this one, if this is a side dock.
+    pub fn opposite_side(&self) -> Option<Self> {
+        match self {
+            Self::Left => Some(Self::Right),
+            Self::Right => Some(Self::Left),
+            Self::Bottom => None,
+        }
+    }
 }
 
 struct PanelEntry {
     panel: Arc<dyn PanelHandle>,
+    /// What the panel's status bar button showed when last checked.
+    button: PanelButton,
     _subscriptions: [Subscription; 3],
 }
 
+/// What a panel's button in the status bar shows, compared whenever the panel notifies
+/// so that [`PanelButtons`] only re-render when it changes.
+#[derive(Clone, Debug, PartialEq)]
+struct PanelButton {
+    icon: Option<ui::IconName>,
+    label: Option<String>,
+    tooltip: Option<&'static str>,
+}
+
+impl PanelButton {
+    fn new(panel: &dyn PanelHandle, cx: &WindowContext) -> Self {
+        Self {
+            icon: panel.icon(cx),
+            label: panel.icon_label(cx),
+            tooltip: panel.icon_tooltip(cx),
+        }
+    }
+}
+
+/// A panel popped out of its dock into a floating window.
+struct PoppedOutEntry {
+    entry: PanelEntry,
+    /// The panel's index in the dock when it was popped out, where it's docked back.
+    index: usize,
+    window: AnyWindowHandle,
+}
+
 pub struct PanelButtons {
     dock: View<Dock>,
 }
 
+/// A panel being dragged by its button in the status bar, to move it to another dock.
+#[derive(Clone)]
+pub struct DraggedPanel {
+    panel: Arc<dyn PanelHandle>,
+    icon: IconName,
+}
+
+impl Render for DraggedPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .p_1()
+            .rounded_md()
+            .bg(cx.theme().colors().elevated_surface_background)
+            .child(Icon::new(self.icon).size(IconSize::Small))
+    }
+}
+
 impl Dock {
     pub fn new(position: DockPosition, cx: &mut ViewContext<Workspace>) -> View<Self> {
         let focus_handle = cx.focus_handle();
@@ -203,6 +306,10 @@ impl Dock {
                 position,
                 panel_entries: Default::default(),
                 active_panel_index: 0,
+                split_panel_index: None,
+                split_ratio: 0.5,
+                collapsed_panels: HashSet::default(),
+                popped_out_entries: Vec::new(),
                 is_open: false,
                 focus_handle: focus_handle.clone(),
                 _focus_subscription: focus_subscription,
@@ -260,18 +367,43 @@ impl Dock {
         self.is_open
     }
 
+    /// The dock's panel of the given type, whether it's docked or popped out.
     pub fn panel<T: Panel>(&self) -> Option<View<T>> {
         self.panel_entries
             .iter()
+            .chain(self.popped_out_entries.iter().map(|popped| &popped.entry))
             .find_map(|entry| entry.panel.to_any().clone().downcast().ok())
     }
 
+    /// The index of the docked panel of the given type. A popped out panel has none,
+    /// see [`Dock::popped_out_panel`].
     pub fn panel_index_for_type<T: Panel>(&self) -> Option<usize> {
         self.panel_entries
             .iter()
             .position(|entry| entry.panel.to_any().downcast::<T>().is_ok())
     }
 
+    /// The panel of the given type if it's popped out, along with its floating window.
+    pub fn popped_out_panel<T: Panel>(&self) -> Option<(Arc<dyn PanelHandle>, AnyWindowHandle)> {
+        self.popped_out_entries.iter().find_map(|popped| {
+            popped
+                .entry
+                .panel
+                .to_any()
+                .downcast::<T>()
+                .is_ok()
+                .then(|| (popped.entry.panel.clone(), popped.window))
+        })
+    }
+
+    /// The persistent names of the panels popped out of the dock.
+    pub fn popped_out_panel_names(&self) -> Vec<String> {
+        self.popped_out_entries
+            .iter()
+            .map(|popped| popped.entry.panel.persistent_name().to_string())
+            .collect()
+    }
+
     pub fn panel_index_for_persistent_name(
         &self,
         ui_name: &str,
@@ -292,7 +424,11 @@ impl Dock {
             if let Some(active_panel) = self.panel_entries.get(self.active_panel_index) {
                 active_panel.panel.set_active(open, cx);
             }
+            if let Some(split_panel) = self.split_entry() {
+                split_panel.panel.set_active(open, cx);
+            }
 
+            cx.emit(DockEvent::VisibilityChanged);
             cx.notify();
         }
     }
@@ -326,7 +462,10 @@ impl Dock {
         cx: &mut ViewContext<Self>,
     ) {
         let subscriptions = [
-            cx.observe(&panel, |_, _, cx| cx.notify()),
+            cx.observe(&panel, |this, panel, cx| {
+                this.update_panel_button(panel.entity_id(), cx);
+                cx.notify();
+            }),
             cx.observe_global::<SettingsStore>({
                 let workspace = workspace.clone();
                 let panel = panel.clone();
@@ -334,9 +473,13 @@ impl Dock {
                 move |this, cx| {
                     let new_position = panel.read(cx).position(cx);
                     if new_position == this.position {
+                        // The panel's default size may have changed along with the settings.
+                        this.update_panel_button(panel.entity_id(), cx);
+                        cx.notify();
                         return;
                     }
 
+                    let old_position = this.position;
                     let Ok(new_dock) = workspace.update(cx, |workspace, cx| {
                         if panel.is_zoomed(cx) {
                             workspace.zoomed_position = Some(new_position);
@@ -365,6 +508,15 @@ impl Dock {
                             new_dock.activate_panel(new_dock.panels_len() - 1, cx);
                         }
                     });
+                    workspace
+                        .update(cx, |_, cx| {
+                            cx.emit(crate::Event::DockPositionChanged {
+                                panel: T::persistent_name(),
+                                old_position,
+                                new_position,
+                            })
+                        })
+                        .ok();
                 }
             }),
             cx.subscribe(&panel, move |this, panel, event, cx| match event {
@@ -409,6 +561,11 @@ impl Dock {
                         .map_or(false, |p| p.panel_id() == Entity::entity_id(&panel))
                     {
                         this.set_open(false, cx);
+                    } else if this
+                        .split_panel()
+                        .map_or(false, |p| p.panel_id() == Entity::entity_id(&panel))
+                    {
+                        this.close_split(cx);
                     }
                 }
             }),
@@ -416,12 +573,16 @@ impl Dock {
 
         let name = panel.persistent_name().to_string();
 
+        let button = PanelButton::new(&panel, cx);
         self.panel_entries.push(PanelEntry {
             panel: Arc::new(panel.clone()),
+            button,
             _subscriptions: subscriptions,
         });
         if let Some(serialized) = self.serialized_dock.clone() {
-            if serialized.active_panel == Some(name) {
+            if serialized.popped_out_panels.contains(&name) {
+                self.pop_out_panel(self.panel_entries.len() - 1, cx);
+            } else if serialized.active_panel == Some(name) {
                 self.activate_panel(self.panel_entries.len() - 1, cx);
                 if serialized.visible {
                     self.set_open(true, cx);
@@ -437,6 +598,7 @@ impl Dock {
             self.set_open(true, cx);
         }
 
+        cx.emit(DockEvent::PanelsChanged);
         cx.notify()
     }
 
@@ -446,22 +608,117 @@ impl Dock {
             .iter()
             .position(|entry| entry.panel.panel_id() == Entity::entity_id(panel))
         {
-            if panel_ix == self.active_panel_index {
-                self.active_panel_index = 0;
-                self.set_open(false, cx);
-            } else if panel_ix < self.active_panel_index {
-                self.active_panel_index -= 1;
-            }
-            self.panel_entries.remove(panel_ix);
+            self.take_panel_entry(panel_ix, cx);
+            self.collapsed_panels.remove(&Entity::entity_id(panel));
+            cx.emit(DockEvent::PanelsChanged);
             cx.notify();
         }
     }
 
+    fn take_panel_entry(&mut self, panel_ix: usize, cx: &mut ViewContext<Self>) -> PanelEntry {
+        if panel_ix == self.active_panel_index {
+            self.active_panel_index = 0;
+            self.set_open(false, cx);
+        } else if panel_ix < self.active_panel_index {
+            self.active_panel_index -= 1;
+        }
+        match self.split_panel_index {
+            Some(split_ix) if split_ix == panel_ix => {
+                self.close_split(cx);
+            }
+            Some(split_ix) if split_ix > panel_ix => {
+                self.split_panel_index = Some(split_ix - 1);
+            }
+            _ => {}
+        }
+        let entry = self.panel_entries.remove(panel_ix);
+        if self.split_panel_index == Some(self.active_panel_index) {
+            self.split_panel_index = None;
+        }
+        entry
+    }
+
+    /// Moves the panel at the given index out of the dock, into a window that floats
+    /// above the workspace until the panel is docked back.
+    pub fn pop_out_panel(&mut self, panel_ix: usize, cx: &mut ViewContext<Self>) {
+        if panel_ix >= self.panel_entries.len() {
+            return;
+        }
+        let entry = self.take_panel_entry(panel_ix, cx);
+        let panel = entry.panel.clone();
+
+        let dock = cx.view().clone();
+        let workspace_window = cx.window_handle();
+        let title = panel.icon_tooltip(cx).unwrap_or(panel.persistent_name());
+        let options = WindowOptions {
+            bounds: WindowBounds::Fixed(Bounds {
+                origin: Default::default(),
+                size: size(px(480.), px(640.)).into(),
+            }),
+            titlebar: Some(TitlebarOptions {
+                title: Some(title.into()),
+                ..Default::default()
+            }),
+            center: true,
+            kind: WindowKind::Floating,
+            ..Default::default()
+        };
+        let window = cx.open_window(options, |cx| {
+            cx.new_view(|cx| FloatingPanel::new(panel.clone(), &dock, workspace_window, cx))
+        });
+        panel.set_active(true, cx);
+        self.popped_out_entries.push(PoppedOutEntry {
+            entry,
+            index: panel_ix,
+            window: window.into(),
+        });
+        cx.emit(DockEvent::PanelsChanged);
+        cx.notify();
+    }
+
+    /// Returns a panel popped out with [`Dock::pop_out_panel`] to where it was in the
+    /// dock, and shows it.
+    pub fn dock_popped_out_panel(&mut self, panel_id: EntityId, cx: &mut ViewContext<Self>) {
+        let Some(ix) = self
+            .popped_out_entries
+            .iter()
+            .position(|popped| popped.entry.panel.panel_id() == panel_id)
+        else {
+            return;
+        };
+        let PoppedOutEntry { entry, index, .. } = self.popped_out_entries.remove(ix);
+        entry.panel.set_active(false, cx);
+
+        let panel_ix = index.min(self.panel_entries.len());
+        if !self.panel_entries.is_empty() && panel_ix <= self.active_panel_index {
+            self.active_panel_index += 1;
+        }
+        if let Some(split_ix) = self.split_panel_index.as_mut() {
+            if panel_ix <= *split_ix {
+                *split_ix += 1;
+            }
+        }
+        self.panel_entries.insert(panel_ix, entry);
+        self.activate_panel(panel_ix, cx);
+        self.set_open(true, cx);
+        cx.emit(DockEvent::PanelsChanged);
+        cx.notify();
+    }
+
     pub fn panels_len(&self) -> usize {
         self.panel_entries.len()
     }
 
     pub fn activate_panel(&mut self, panel_ix: usize, cx: &mut ViewContext<Self>) {
+        if self.split_panel_index == Some(panel_ix) {
+            // Activating the panel shown below the active one swaps them.
+            self.split_panel_index = Some(self.active_panel_index);
+            self.active_panel_index = panel_ix;
+            cx.emit(DockEvent::ActivePanelChanged);
+            cx.notify();
+            return;
+        }
+
         if panel_ix != self.active_panel_index {
             if let Some(active_panel) = self.panel_entries.get(self.active_panel_index) {
                 active_panel.panel.set_active(false, cx);
@@ -472,10 +729,92 @@ impl Dock {
                 active_panel.panel.set_active(true, cx);
             }
 
+            cx.emit(DockEvent::ActivePanelChanged);
+            cx.notify();
+        }
+    }
+
+    /// Shows the panel at the given index below the active one. Only the left
+    /// and right docks can be split.
+    pub fn split_with_panel(&mut self, panel_ix: usize, cx: &mut ViewContext<Self>) {
+        if self.position.axis() != Axis::Horizontal
+            || panel_ix == self.active_panel_index
+            || panel_ix >= self.panel_entries.len()
+            || self.split_panel_index == Some(panel_ix)
+        {
+            return;
+        }
+
+        self.close_split(cx);
+        self.split_panel_index = Some(panel_ix);
+        if self.is_open {
+            self.panel_entries[panel_ix].panel.set_active(true, cx);
+        }
+        cx.emit(DockEvent::ActivePanelChanged);
+        cx.notify();
+    }
+
+    pub fn close_split(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(split_ix) = self.split_panel_index.take() {
+            if self.is_open {
+                if let Some(entry) = self.panel_entries.get(split_ix) {
+                    entry.panel.set_active(false, cx);
+                }
+            }
+            cx.emit(DockEvent::ActivePanelChanged);
             cx.notify();
         }
     }
 
+    fn update_panel_button(&mut self, panel_id: EntityId, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self
+            .panel_entries
+            .iter_mut()
+            .chain(
+                self.popped_out_entries
+                    .iter_mut()
+                    .map(|popped| &mut popped.entry),
+            )
+            .find(|entry| entry.panel.panel_id() == panel_id)
+        else {
+            return;
+        };
+        let button = PanelButton::new(entry.panel.as_ref(), cx);
+        if entry.button != button {
+            entry.button = button;
+            cx.emit(DockEvent::PanelButtonChanged);
+        }
+    }
+
+    pub fn split_panel_index(&self) -> Option<usize> {
+        self.split_panel_index
+    }
+
+    pub fn split_panel(&self) -> Option<&Arc<dyn PanelHandle>> {
+        Some(&self.split_entry()?.panel)
+    }
+
+    fn split_entry(&self) -> Option<&PanelEntry> {
+        self.panel_entries.get(self.split_panel_index?)
+    }
+
+    fn set_split_ratio(&mut self, ratio: f32, cx: &mut ViewContext<Self>) {
+        self.split_ratio = ratio.clamp(MIN_SPLIT_RATIO, 1. - MIN_SPLIT_RATIO);
+        cx.notify();
+    }
+
+    /// Collapses a panel of a split dock down to its header, or expands it again.
+    pub fn toggle_panel_collapsed(&mut self, panel_id: EntityId, cx: &mut ViewContext<Self>) {
+        if !self.collapsed_panels.remove(&panel_id) {
+            self.collapsed_panels.insert(panel_id);
+        }
+        cx.notify();
+    }
+
+    pub fn is_panel_collapsed(&self, panel_id: EntityId) -> bool {
+        self.collapsed_panels.contains(&panel_id)
+    }
+
     pub fn visible_panel(&self) -> Option<&Arc<dyn PanelHandle>> {
         let entry = self.visible_entry()?;
         Some(&entry.panel)
@@ -485,6 +824,10 @@ impl Dock {
         Some(&self.panel_entries.get(self.active_panel_index)?.panel)
     }
 
+    pub fn panels(&self) -> impl Iterator<Item = &Arc<dyn PanelHandle>> {
+        self.panel_entries.iter().map(|entry| &entry.panel)
+    }
+
     fn visible_entry(&self) -> Option<&PanelEntry> {
         if self.is_open {
             self.panel_entries.get(self.active_panel_index)
@@ -493,6 +836,16 @@ impl Dock {
         }
     }
 
+    /// Whether an item in one of the visible panels asks for the dock to stay open,
+    /// even where it would otherwise be hidden automatically.
+    pub fn prevents_auto_hide(&self, cx: &WindowContext) -> bool {
+        self.visible_entry()
+            .into_iter()
+            .chain(self.is_open.then(|| self.split_entry()).flatten())
+            .flat_map(|entry| entry.panel.panes(cx))
+            .any(|pane| pane.read(cx).items().any(|item| item.prevent_auto_hide(cx)))
+    }
+
     pub fn zoomed_panel(&self, cx: &WindowContext) -> Option<Arc<dyn PanelHandle>> {
         let entry = self.visible_entry()?;
         if entry.panel.is_zoomed(cx) {
@@ -527,6 +880,22 @@ impl Dock {
         }
     }
 
+    pub(crate) fn can_accept_panel(
+        position: DockPosition,
+        dragged: &DraggedPanel,
+        cx: &WindowContext,
+    ) -> bool {
+        dragged.panel.position(cx) != position && dragged.panel.position_is_valid(position, cx)
+    }
+
+    /// Moves a panel dropped onto this dock here. The new position is saved in
+    /// the panel's settings, and the panel is moved once those are reloaded.
+    pub(crate) fn handle_panel_drop(&mut self, dragged: &DraggedPanel, cx: &mut ViewContext<Self>) {
+        if Self::can_accept_panel(self.position, dragged, cx) {
+            dragged.panel.set_position(self.position, cx);
+        }
+    }
+
     pub fn toggle_action(&self) -> Box<dyn Action> {
         match self.position {
             DockPosition::Left => crate::ToggleLeftDock.boxed_clone(),
@@ -536,14 +905,128 @@ impl Dock {
     }
 }
 
+impl Dock {
+    fn render_split_section(
+        &self,
+        entry: &PanelEntry,
+        is_split_panel: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> Div {
+        let panel_id = entry.panel.panel_id();
+        let is_collapsed = self.is_panel_collapsed(panel_id);
+        let title = entry
+            .panel
+            .icon_tooltip(cx)
+            .unwrap_or(entry.panel.persistent_name());
+
+        let header = h_flex()
+            .flex_none()
+            .px_1()
+            .gap_1()
+            .border_b()
+            .border_color(cx.theme().colors().border)
+            .child(
+                IconButton::new(
+                    ("collapse-panel", panel_id),
+                    if is_collapsed {
+                        IconName::ChevronRight
+                    } else {
+                        IconName::ChevronDown
+                    },
+                )
+                .icon_size(IconSize::Small)
+                .on_click(cx.listener(move |dock, _, cx| {
+                    dock.toggle_panel_collapsed(panel_id, cx);
+                })),
+            )
+            .child(Label::new(title).size(LabelSize::Small))
+            .when(is_split_panel, |this| {
+                this.child(div().flex_1()).child(
+                    IconButton::new("close-split", IconName::Close)
+                        .icon_size(IconSize::Small)
+                        .tooltip(|cx| Tooltip::text("Remove From Split", cx))
+                        .on_click(cx.listener(|dock, _, cx| dock.close_split(cx))),
+                )
+            });
+
+        v_flex()
+            .w_full()
+            .overflow_hidden()
+            .child(header)
+            .when(!is_collapsed, |this| {
+                this.child(
+                    div()
+                        .flex_1()
+                        .w_full()
+                        .overflow_hidden()
+                        .child(entry.panel.to_any().cached()),
+                )
+            })
+    }
+
+    fn render_panels(&self, entry: &PanelEntry, cx: &mut ViewContext<Self>) -> AnyElement {
+        let Some(split_entry) = self.split_entry() else {
+            return entry.panel.to_any().cached().into_any_element();
+        };
+
+        let active_collapsed = self.is_panel_collapsed(entry.panel.panel_id());
+        let split_collapsed = self.is_panel_collapsed(split_entry.panel.panel_id());
+        let active_section = self.render_split_section(entry, false, cx);
+        let split_section = self.render_split_section(split_entry, true, cx);
+        let position = self.position;
+
+        v_flex()
+            .size_full()
+            .on_drag_move(
+                cx.listener(|dock, e: &DragMoveEvent<DraggedDockSplit>, cx| {
+                    if e.drag(cx).0 == dock.position {
+                        let offset = e.event.position.y - e.bounds.top();
+                        dock.set_split_ratio(offset / e.bounds.size.height, cx);
+                    }
+                }),
+            )
+            .child(match (active_collapsed, split_collapsed) {
+                (false, false) => active_section.h(relative(self.split_ratio)),
+                (false, true) => active_section.flex_1(),
+                (true, _) => active_section.flex_none(),
+            })
+            .when(!active_collapsed && !split_collapsed, |this| {
+                this.child(
+                    div()
+                        .id("split-resize-handle")
+                        .flex_none()
+                        .w_full()
+                        .h(RESIZE_HANDLE_SIZE)
+                        .cursor_row_resize()
+                        .hover(|style| style.bg(cx.theme().colors().resize_handle_hover))
+                        .active(|style| style.bg(cx.theme().colors().resize_handle_active))
+                        .on_drag(DraggedDockSplit(position), |split, cx| {
+                            cx.stop_propagation();
+                            cx.new_view(|_| split.clone())
+                        })
+                        .block_mouse(),
+                )
+            })
+            .child(if split_collapsed {
+                split_section.flex_none()
+            } else {
+                split_section.flex_1()
+            })
+            .into_any_element()
+    }
+}
+
 impl Render for Dock {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         if let Some(entry) = self.visible_entry() {
             let size = entry.panel.size(cx);
 
             let position = self.position;
+            let colors = cx.theme().colors();
             let mut handle = div()
                 .id("resize-handle")
+                .hover(|style| style.bg(colors.resize_handle_hover))
+                .active(|style| style.bg(colors.resize_handle_active))
                 .on_drag(DraggedDock(position), |dock, cx| {
                     cx.stop_propagation();
                     cx.new_view(|_| dock.clone())
@@ -591,6 +1074,16 @@ impl Render for Dock {
                 .track_focus(&self.focus_handle)
                 .flex()
                 .bg(cx.theme().colors().panel_background)
+                .drag_over::<DraggedPanel>(move |style, dragged, cx| {
+                    if Self::can_accept_panel(position, dragged, cx) {
+                        style.bg(cx.theme().colors().drop_target_background)
+                    } else {
+                        style
+                    }
+                })
+                .on_drop(cx.listener(|dock, dragged: &DraggedPanel, cx| {
+                    dock.handle_panel_drop(dragged, cx)
+                }))
                 .border_color(cx.theme().colors().border)
                 .overflow_hidden()
                 .map(|this| match self.position().axis() {
@@ -608,7 +1101,7 @@ impl Render for Dock {
                             Axis::Horizontal => this.min_w(size).h_full(),
                             Axis::Vertical => this.min_h(size).w_full(),
                         })
-                        .child(entry.panel.to_any().cached()),
+                        .child(self.render_panels(entry, cx)),
                 )
                 .child(handle)
         } else {
@@ -619,15 +1112,18 @@ impl Render for Dock {
 
 impl PanelButtons {
     pub fn new(dock: View<Dock>, cx: &mut ViewContext<Self>) -> Self {
-        cx.observe(&dock, |_, _, cx| cx.notify()).detach();
+        cx.subscribe(&dock, |_, _, _: &DockEvent, cx| cx.notify())
+            .detach();
         Self { dock }
     }
 }
 
 impl Render for PanelButtons {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let dock_view = self.dock.clone();
         let dock = self.dock.read(cx);
         let active_index = dock.active_panel_index;
+        let split_index = dock.split_panel_index;
         let is_open = dock.is_open;
         let dock_position = dock.position;
 
@@ -645,10 +1141,17 @@ impl Render for PanelButtons {
             .filter_map(|(i, entry)| {
                 let icon = entry.panel.icon(cx)?;
                 let icon_tooltip = entry.panel.icon_tooltip(cx)?;
+                let icon_label = entry.panel.icon_label(cx);
                 let name = entry.panel.persistent_name();
                 let panel = entry.panel.clone();
 
                 let is_active_button = i == active_index && is_open;
+                let is_split_button = split_index == Some(i) && is_open;
+                let dock_view = dock_view.clone();
+                let dragged_panel = DraggedPanel {
+                    panel: panel.clone(),
+                    icon,
+                };
 
                 let (action, tooltip) = if is_active_button {
                     let action = dock.toggle_action();
@@ -687,27 +1190,79 @@ impl Render for PanelButtons {
                                         )
                                     }
                                 }
+                                menu = menu.entry("Pop Out", None, {
+                                    let dock_view = dock_view.clone();
+                                    move |cx| {
+                                        dock_view.update(cx, |dock, cx| dock.pop_out_panel(i, cx));
+                                    }
+                                });
+                                if dock_position.axis() == Axis::Horizontal && i != active_index {
+                                    let dock_view = dock_view.clone();
+                                    menu = if is_split_button {
+                                        menu.entry("Remove From Split", None, move |cx| {
+                                            dock_view.update(cx, |dock, cx| dock.close_split(cx));
+                                        })
+                                    } else {
+                                        menu.entry("Show Below Active Panel", None, move |cx| {
+                                            dock_view.update(cx, |dock, cx| {
+                                                dock.split_with_panel(i, cx);
+                                                dock.set_open(true, cx);
+                                            });
+                                        })
+                                    };
+                                }
                                 menu
                             })
                         })
                         .anchor(menu_anchor)
                         .attach(menu_attach)
                         .trigger(
-                            IconButton::new(name, icon)
-                                .icon_size(IconSize::Small)
-                                .selected(is_active_button)
-                                .on_click({
-                                    let action = action.boxed_clone();
-                                    move |_, cx| cx.dispatch_action(action.boxed_clone())
+                            div()
+                                .id(("panel-button", i))
+                                .relative()
+                                .on_drag(dragged_panel, |dragged_panel, cx| {
+                                    cx.new_view(|_| dragged_panel.clone())
                                 })
-                                .tooltip(move |cx| {
-                                    Tooltip::for_action(tooltip.clone(), &*action, cx)
-                                }),
+                                .child(
+                                    IconButton::new(name, icon)
+                                        .icon_size(IconSize::Small)
+                                        .selected(is_active_button || is_split_button)
+                                        .on_click({
+                                            let action = action.boxed_clone();
+                                            move |_, cx| cx.dispatch_action(action.boxed_clone())
+                                        })
+                                        .tooltip(move |cx| {
+                                            Tooltip::for_action(tooltip.clone(), &*action, cx)
+                                        }),
+                                )
+                                .children(icon_label.map(|label| {
+                                    div()
+                                        .absolute()
+                                        .top_0()
+                                        .right_0()
+                                        .px_0p5()
+                                        .rounded_md()
+                                        .bg(cx.theme().colors().element_selected)
+                                        .child(Label::new(label).size(LabelSize::XSmall))
+                                })),
                         ),
                 )
             });
 
-        h_flex().gap_0p5().children(buttons)
+        h_flex()
+            .gap_0p5()
+            .drag_over::<DraggedPanel>(move |style, dragged, cx| {
+                if Dock::can_accept_panel(dock_position, dragged, cx) {
+                    style.bg(cx.theme().colors().drop_target_background)
+                } else {
+                    style
+                }
+            })
+            .on_drop(cx.listener(|this, dragged: &DraggedPanel, cx| {
+                this.dock
+                    .update(cx, |dock, cx| dock.handle_panel_drop(dragged, cx))
+            }))
+            .children(buttons)
     }
 }
 
@@ -732,6 +1287,10 @@ pub mod test {
         pub active: bool,
         pub focus_handle: FocusHandle,
         pub size: Pixels,
+        pub panes: Vec<View<Pane>>,
+        pub icon: Option<ui::IconName>,
+        pub icon_label: Option<String>,
+        pub icon_tooltip: Option<&'static str>,
     }
     actions!(test, [ToggleTestPanel]);
 
@@ -745,6 +1304,10 @@ pub mod test {
                 active: false,
                 focus_handle: cx.focus_handle(),
                 size: px(300.),
+                panes: Vec::new(),
+                icon: None,
+                icon_label: None,
+                icon_tooltip: None,
             }
         }
     }
@@ -782,11 +1345,15 @@ pub mod test {
         }
 
         fn icon(&self, _: &WindowContext) -> Option<ui::IconName> {
-            None
+            self.icon
         }
 
         fn icon_tooltip(&self, _cx: &WindowContext) -> Option<&'static str> {
-            None
+            self.icon_tooltip
+        }
+
+        fn icon_label(&self, _: &WindowContext) -> Option<String> {
+            self.icon_label.clone()
         }
 
         fn toggle_action(&self) -> Box<dyn Action> {
@@ -804,6 +1371,10 @@ pub mod test {
         fn set_active(&mut self, active: bool, _cx: &mut ViewContext<Self>) {
             self.active = active;
         }
+
+        fn panes(&self) -> Vec<View<Pane>> {
+            self.panes.clone()
+        }
     }
 
     impl FocusableView for TestPanel {
@@ -0,0 +1,105 @@
+use crate::dock::{Dock, PanelHandle};
+use gpui::{
+    AnyWindowHandle, EntityId, FocusHandle, FocusableView, Render, Subscription, View, ViewContext,
+    WeakView, WindowContext,
+};
+use std::sync::Arc;
+use ui::{prelude::*, IconButton, Tooltip};
+
+/// The root view of a window a panel has been popped out into. The window floats
+/// above the workspace until the panel is docked back, which closing it also does.
+pub struct FloatingPanel {
+    panel: Arc<dyn PanelHandle>,
+    dock: WeakView<Dock>,
+    workspace_window: AnyWindowHandle,
+    focus_handle: FocusHandle,
+    _dock_release: Subscription,
+}
+
+impl FloatingPanel {
+    pub(crate) fn new(
+        panel: Arc<dyn PanelHandle>,
+        dock: &View<Dock>,
+        workspace_window: AnyWindowHandle,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        cx.on_window_should_close({
+            let dock = dock.downgrade();
+            let panel_id = panel.panel_id();
+            move |cx| {
+                dock_panel(&dock, workspace_window, panel_id, cx);
+                true
+            }
+        });
+
+        Self {
+            panel,
+            dock: dock.downgrade(),
+            workspace_window,
+            focus_handle: cx.focus_handle(),
+            _dock_release: cx.observe_release(dock, |_, _, cx| cx.remove_window()),
+        }
+    }
+
+    fn dock_back(&mut self, cx: &mut ViewContext<Self>) {
+        dock_panel(&self.dock, self.workspace_window, self.panel.panel_id(), cx);
+        cx.remove_window();
+    }
+}
+
+/// Returns a popped out panel to its dock. The dock is updated within the
+/// workspace's window, which is where it's rendered.
+fn dock_panel(
+    dock: &WeakView<Dock>,
+    workspace_window: AnyWindowHandle,
+    panel_id: EntityId,
+    cx: &mut WindowContext,
+) {
+    let Some(dock) = dock.upgrade() else {
+        return;
+    };
+    workspace_window
+        .update(cx, |_, cx| {
+            dock.update(cx, |dock, cx| dock.dock_popped_out_panel(panel_id, cx))
+        })
+        .ok();
+}
+
+impl FocusableView for FloatingPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for FloatingPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let title = self
+            .panel
+            .icon_tooltip(cx)
+            .unwrap_or(self.panel.persistent_name());
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .text_color(cx.theme().colors().text)
+            .child(
+                h_flex()
+                    .flex_none()
+                    .justify_between()
+                    .h(rems(1.75))
+                    .pl_2()
+                    .pr_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new(title).size(LabelSize::Small))
+                    .child(
+                        IconButton::new("dock-panel", IconName::Minimize)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| Tooltip::text("Dock Panel", cx))
+                            .on_click(cx.listener(|this, _, cx| this.dock_back(cx))),
+                    ),
+            )
+            .child(div().flex_1().overflow_hidden().child(self.panel.to_any()))
+    }
+}
@@ -3,8 +3,8 @@ use crate::{
     persistence::model::ItemId,
     searchable::SearchableItemHandle,
     workspace_settings::{AutosaveSetting, WorkspaceSettings},
-    DelayedDebouncedEditAction, FollowableItemBuilders, ItemNavHistory, ToolbarItemLocation,
-    ViewId, Workspace, WorkspaceId,
+    DelayedDebouncedEditAction, FollowableItemBuilders, ItemConverters, ItemNavHistory,
+    SerializableItemRegistry, ToolbarItemLocation, ViewId, Workspace, WorkspaceId,
 };
 use anyhow::Result;
 use client::{
@@ -12,9 +12,9 @@ use client::{
     Client,
 };
 use gpui::{
-    AnyElement, AnyView, AppContext, Entity, EntityId, EventEmitter, FocusHandle, FocusableView,
-    HighlightStyle, Model, Pixels, Point, SharedString, Task, View, ViewContext, WeakView,
-    WindowContext,
+    div, AnyElement, AnyView, AppContext, Entity, EntityId, EventEmitter, FocusHandle,
+    FocusableView, HighlightStyle, IntoElement, Model, ParentElement, Pixels, Point, SharedString,
+    Styled, Task, View, ViewContext, WeakView, WindowContext,
 };
 use project::{Project, ProjectEntryId, ProjectPath};
 use schemars::JsonSchema;
@@ -33,12 +33,15 @@ use std::{
     },
     time::Duration,
 };
-use theme::Theme;
+use theme::{Theme, ThemeSettings};
+use ui::{v_flex, Icon};
+use util::truncate_and_trailoff;
 
 #[derive(Deserialize)]
 pub struct ItemSettings {
     pub git_status: bool,
     pub close_position: ClosePosition,
+    pub hover_preview: bool,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -63,11 +66,15 @@ pub struct ItemSettingsContent {
     /// Whether to show the Git file status on a tab item.
     ///
     /// Default: true
-    git_status: Option<bool>,
+    pub git_status: Option<bool>,
     /// Position of the close button in a tab.
     ///
     /// Default: right
-    close_position: Option<ClosePosition>,
+    pub close_position: Option<ClosePosition>,
+    /// Whether to show a preview of the item's content when hovering a tab.
+    ///
+    /// Default: false
+    pub hover_preview: Option<bool>,
 }
 
 impl Settings for ItemSettings {
@@ -84,12 +91,50 @@ impl Settings for ItemSettings {
     }
 }
 
+/// Returns the settings that apply to the given item, taking the local settings
+/// (`.zed/settings.json`) of the worktree containing it into account.
+pub fn settings_for_item<'a, T: Settings>(item: &dyn ItemHandle, cx: &'a AppContext) -> &'a T {
+    let project_path = item.project_path(cx);
+    T::get(
+        project_path
+            .as_ref()
+            .map(|path| (path.worktree_id.to_usize(), path.path.as_ref())),
+        cx,
+    )
+}
+
+/// The number of lines shown by [`text_tab_preview`].
+pub const TAB_PREVIEW_MAX_LINES: usize = 12;
+const TAB_PREVIEW_MAX_LINE_LEN: usize = 80;
+
+/// Renders the first lines of `text` in the buffer font, for use as a tab preview.
+pub fn text_tab_preview(text: &str, cx: &WindowContext) -> AnyElement {
+    let buffer_font = ThemeSettings::get_global(cx).buffer_font.family.clone();
+    v_flex()
+        .font(buffer_font)
+        .text_xs()
+        .children(
+            text.lines()
+                .take(TAB_PREVIEW_MAX_LINES)
+                .map(|line| truncate_and_trailoff(line, TAB_PREVIEW_MAX_LINE_LEN))
+                .map(|line| div().h_4().child(line)),
+        )
+        .into_any_element()
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub enum ItemEvent {
     CloseItem,
     UpdateTab,
+    /// Only the item's [`Item::tab_icon`] changed. The pane re-renders, tab bar
+    /// included, but unlike [`ItemEvent::UpdateTab`] it isn't reported as a title
+    /// change and leaves docks kept open by the item alone.
+    UpdateTabIcon,
     UpdateBreadcrumbs,
     Edit,
+    /// The state returned by [`SerializableItem::serialize`] changed, so the
+    /// workspace should be saved again.
+    Serialize,
 }
 
 // TODO: Combine this with existing HighlightedText struct?
@@ -101,11 +146,27 @@ pub struct BreadcrumbText {
 pub trait Item: FocusableView + EventEmitter<Self::Event> {
     type Event;
 
+    /// Called when the pane switches away from this item, and when the item gets
+    /// closed. Items can use it to flush state or to stop work that is only useful
+    /// while they are visible.
     fn deactivated(&mut self, _: &mut ViewContext<Self>) {}
     fn workspace_deactivated(&mut self, _: &mut ViewContext<Self>) {}
+    /// Called with whether the item is on screen when it becomes the active item of its
+    /// pane, when it stops being the active one, and when its pane gets shown or hidden
+    /// along with a dock. Items are assumed to be visible until told otherwise, and can
+    /// use it to suspend work whose only purpose is to update what they display.
+    fn set_visible(&mut self, _visible: bool, _: &mut ViewContext<Self>) {}
+    /// Restores a position previously recorded through the item's [`ItemNavHistory`].
+    /// Returns whether anything changed, so that entries which would leave the item
+    /// as is get skipped when going back or forward.
     fn navigate(&mut self, _: Box<dyn Any>, _: &mut ViewContext<Self>) -> bool {
         false
     }
+    /// The position to return to when the item gets reopened after being closed in a
+    /// previous session. It is handed back to [`Item::navigate`] as a `language::Point`.
+    fn restorable_position(&self, _: &AppContext) -> Option<language::Point> {
+        None
+    }
     fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
         None
     }
@@ -113,6 +174,16 @@ pub trait Item: FocusableView + EventEmitter<Self::Event> {
         None
     }
     fn tab_content(&self, detail: Option<usize>, selected: bool, cx: &WindowContext) -> AnyElement;
+    /// A small readonly rendering of the item's content, shown when hovering its tab
+    /// if `tabs.hover_preview` is enabled. Text items can use [`text_tab_preview`].
+    fn tab_preview(&self, _: &WindowContext) -> Option<AnyElement> {
+        None
+    }
+    /// An icon shown next to the tab's content to reflect transient state, such as
+    /// a save in progress. Emit [`ItemEvent::UpdateTabIcon`] whenever it changes.
+    fn tab_icon(&self, _: &WindowContext) -> Option<Icon> {
+        None
+    }
 
     fn telemetry_event_text(&self) -> Option<&'static str>;
 
@@ -126,6 +197,9 @@ pub trait Item: FocusableView + EventEmitter<Self::Event> {
     fn is_singleton(&self, _cx: &AppContext) -> bool {
         false
     }
+    /// Hands the item the history of the pane it was added to. Items that want
+    /// `GoBack`/`GoForward` to revisit them keep it around and push entries to it,
+    /// which are later passed back to [`Item::navigate`].
     fn set_nav_history(&mut self, _: ItemNavHistory, _: &mut ViewContext<Self>) {}
     fn clone_on_split(
         &self,
@@ -143,6 +217,14 @@ pub trait Item: FocusableView + EventEmitter<Self::Event> {
     fn has_conflict(&self, _: &AppContext) -> bool {
         false
     }
+    /// The remote collaborators currently in the item, shown on its tab.
+    fn present_collaborators(&self, _: &AppContext) -> Vec<PeerId> {
+        Vec::new()
+    }
+    /// Lets the item show its own prompt before being closed. Resolving to `false` keeps it open.
+    fn confirm_close(&mut self, _: &mut ViewContext<Self>) -> Task<bool> {
+        Task::ready(true)
+    }
     fn can_save(&self, _cx: &AppContext) -> bool {
         false
     }
@@ -194,24 +276,18 @@ pub trait Item: FocusableView + EventEmitter<Self::Event> {
 
     fn added_to_workspace(&mut self, _workspace: &mut Workspace, _cx: &mut ViewContext<Self>) {}
 
-    fn serialized_item_kind() -> Option<&'static str> {
-        None
-    }
+    /// Prevents the item from being edited while the workspace is in read-only mode.
+    fn set_read_only(&mut self, _read_only: bool, _cx: &mut ViewContext<Self>) {}
 
-    fn deserialize(
-        _project: Model<Project>,
-        _workspace: WeakView<Workspace>,
-        _workspace_id: WorkspaceId,
-        _item_id: ItemId,
-        _cx: &mut ViewContext<Pane>,
-    ) -> Task<Result<View<Self>>> {
-        unimplemented!(
-            "deserialize() must be implemented if serialized_item_kind() returns Some(_)"
-        )
-    }
     fn show_toolbar(&self) -> bool {
         true
     }
+    /// Keeps the dock hosting this item open where it would otherwise be hidden
+    /// automatically, for instance while a build or a test run is in progress. Emit
+    /// [`ItemEvent::UpdateTab`] once this changes, so the dock can be hidden then.
+    fn prevent_auto_hide(&self, _: &AppContext) -> bool {
+        false
+    }
     fn pixel_position_of_cursor(&self, _: &AppContext) -> Option<Point<Pixels>> {
         None
     }
@@ -227,6 +303,8 @@ pub trait ItemHandle: 'static + Send {
     fn tab_tooltip_text(&self, cx: &AppContext) -> Option<SharedString>;
     fn tab_description(&self, detail: usize, cx: &AppContext) -> Option<SharedString>;
     fn tab_content(&self, detail: Option<usize>, selected: bool, cx: &WindowContext) -> AnyElement;
+    fn tab_icon(&self, cx: &WindowContext) -> Option<Icon>;
+    fn tab_preview(&self, cx: &WindowContext) -> Option<AnyElement>;
     fn telemetry_event_text(&self, cx: &WindowContext) -> Option<&'static str>;
     fn dragged_tab_content(&self, detail: Option<usize>, cx: &WindowContext) -> AnyElement;
     fn project_path(&self, cx: &AppContext) -> Option<ProjectPath>;
@@ -251,12 +329,17 @@ pub trait ItemHandle: 'static + Send {
         cx: &mut ViewContext<Workspace>,
     );
     fn deactivated(&self, cx: &mut WindowContext);
+    fn set_read_only(&self, read_only: bool, cx: &mut WindowContext);
     fn workspace_deactivated(&self, cx: &mut WindowContext);
+    fn set_visible(&self, visible: bool, cx: &mut WindowContext);
     fn navigate(&self, data: Box<dyn Any>, cx: &mut WindowContext) -> bool;
+    fn restorable_position(&self, cx: &AppContext) -> Option<language::Point>;
     fn item_id(&self) -> EntityId;
     fn to_any(&self) -> AnyView;
     fn is_dirty(&self, cx: &AppContext) -> bool;
     fn has_conflict(&self, cx: &AppContext) -> bool;
+    fn present_collaborators(&self, cx: &AppContext) -> Vec<PeerId>;
+    fn confirm_close(&self, cx: &mut WindowContext) -> Task<bool>;
     fn can_save(&self, cx: &AppContext) -> bool;
     fn save(&self, project: Model<Project>, cx: &mut WindowContext) -> Task<Result<()>>;
     fn save_as(
@@ -276,8 +359,12 @@ pub trait ItemHandle: 'static + Send {
     fn to_searchable_item_handle(&self, cx: &AppContext) -> Option<Box<dyn SearchableItemHandle>>;
     fn breadcrumb_location(&self, cx: &AppContext) -> ToolbarItemLocation;
     fn breadcrumbs(&self, theme: &Theme, cx: &AppContext) -> Option<Vec<BreadcrumbText>>;
-    fn serialized_item_kind(&self) -> Option<&'static str>;
+    fn to_serializable_item_handle(
+        &self,
+        cx: &AppContext,
+    ) -> Option<Box<dyn SerializableItemHandle>>;
     fn show_toolbar(&self, cx: &AppContext) -> bool;
+    fn prevent_auto_hide(&self, cx: &AppContext) -> bool;
     fn pixel_position_of_cursor(&self, cx: &AppContext) -> Option<Point<Pixels>>;
 }
 
@@ -328,6 +415,14 @@ impl<T: Item> ItemHandle for View<T> {
         self.read(cx).tab_content(detail, selected, cx)
     }
 
+    fn tab_icon(&self, cx: &WindowContext) -> Option<Icon> {
+        self.read(cx).tab_icon(cx)
+    }
+
+    fn tab_preview(&self, cx: &WindowContext) -> Option<AnyElement> {
+        self.read(cx).tab_preview(cx)
+    }
+
     fn dragged_tab_content(&self, detail: Option<usize>, cx: &WindowContext) -> AnyElement {
         self.read(cx).tab_content(detail, true, cx)
     }
@@ -397,9 +492,15 @@ impl<T: Item> ItemHandle for View<T> {
         self.update(cx, |this, cx| {
             this.set_nav_history(history, cx);
             this.added_to_workspace(workspace, cx);
+            if workspace.is_read_only() {
+                this.set_read_only(true, cx);
+            }
         });
 
-        if let Some(followed_item) = self.to_followable_item_handle(cx) {
+        if let Some(followed_item) = self
+            .to_followable_item_handle(cx)
+            .filter(|_| workspace.shares_pane_with_followers(&pane, cx))
+        {
             if let Some(message) = followed_item.to_state_proto(cx) {
                 workspace.update_followers(
                     followed_item.is_project_item(cx),
@@ -448,7 +549,11 @@ impl<T: Item> ItemHandle for View<T> {
                             workspace.unfollow(&pane, cx);
                         }
 
-                        if item.focus_handle(cx).contains_focused(cx)
+                        // Items in dock panes, such as terminals, keep their followers up
+                        // to date even while the leader works elsewhere.
+                        let is_dock_item = !workspace.panes.contains(&pane);
+                        if workspace.shares_pane_with_followers(&pane, cx)
+                            && (is_dock_item || item.focus_handle(cx).contains_focused(cx))
                             && item.add_event_to_update_proto(
                                 event,
                                 &mut *pending_update.borrow_mut(),
@@ -490,14 +595,22 @@ impl<T: Item> ItemHandle for View<T> {
                         }
 
                         ItemEvent::UpdateTab => {
-                            pane.update(cx, |_, cx| {
+                            pane.update(cx, |pane, cx| {
                                 cx.emit(pane::Event::ChangeItemTitle);
-                                cx.notify();
+                                pane.notify_item_changed(cx);
                             });
+                            workspace.hide_docks_kept_open(cx);
                         }
 
+                        ItemEvent::UpdateTabIcon => {
+                            pane.update(cx, |pane, cx| pane.notify_item_changed(cx));
+                        }
+
+                        ItemEvent::Serialize => workspace.schedule_serialize(cx),
+
                         ItemEvent::Edit => {
-                            let autosave = WorkspaceSettings::get_global(cx).autosave;
+                            let autosave =
+                                settings_for_item::<WorkspaceSettings>(&item, cx).autosave;
                             if let AutosaveSetting::AfterDelay { milliseconds } = autosave {
                                 let delay = Duration::from_millis(milliseconds);
                                 let item = item.clone();
@@ -512,8 +625,10 @@ impl<T: Item> ItemHandle for View<T> {
                 }));
 
             cx.on_blur(&self.focus_handle(cx), move |workspace, cx| {
-                if WorkspaceSettings::get_global(cx).autosave == AutosaveSetting::OnFocusChange {
-                    if let Some(item) = weak_item.upgrade() {
+                if let Some(item) = weak_item.upgrade() {
+                    if settings_for_item::<WorkspaceSettings>(&item, cx).autosave
+                        == AutosaveSetting::OnFocusChange
+                    {
                         Pane::autosave_item(&item, workspace.project.clone(), cx)
                             .detach_and_log_err(cx);
                     }
@@ -538,14 +653,26 @@ impl<T: Item> ItemHandle for View<T> {
         self.update(cx, |this, cx| this.deactivated(cx));
     }
 
+    fn set_read_only(&self, read_only: bool, cx: &mut WindowContext) {
+        self.update(cx, |this, cx| this.set_read_only(read_only, cx));
+    }
+
     fn workspace_deactivated(&self, cx: &mut WindowContext) {
         self.update(cx, |this, cx| this.workspace_deactivated(cx));
     }
 
+    fn set_visible(&self, visible: bool, cx: &mut WindowContext) {
+        self.update(cx, |this, cx| this.set_visible(visible, cx));
+    }
+
     fn navigate(&self, data: Box<dyn Any>, cx: &mut WindowContext) -> bool {
         self.update(cx, |this, cx| this.navigate(data, cx))
     }
 
+    fn restorable_position(&self, cx: &AppContext) -> Option<language::Point> {
+        self.read(cx).restorable_position(cx)
+    }
+
     fn item_id(&self) -> EntityId {
         self.entity_id()
     }
@@ -562,6 +689,14 @@ impl<T: Item> ItemHandle for View<T> {
         self.read(cx).has_conflict(cx)
     }
 
+    fn present_collaborators(&self, cx: &AppContext) -> Vec<PeerId> {
+        self.read(cx).present_collaborators(cx)
+    }
+
+    fn confirm_close(&self, cx: &mut WindowContext) -> Task<bool> {
+        self.update(cx, |item, cx| item.confirm_close(cx))
+    }
+
     fn can_save(&self, cx: &AppContext) -> bool {
         self.read(cx).can_save(cx)
     }
@@ -584,7 +719,12 @@ impl<T: Item> ItemHandle for View<T> {
     }
 
     fn act_as_type<'a>(&'a self, type_id: TypeId, cx: &'a AppContext) -> Option<AnyView> {
-        self.read(cx).act_as_type(type_id, self, cx)
+        self.read(cx).act_as_type(type_id, self, cx).or_else(|| {
+            let converter = cx
+                .try_global::<ItemConverters>()?
+                .get(&(TypeId::of::<T>(), type_id))?;
+            converter(&self.to_any(), cx)
+        })
     }
 
     fn to_followable_item_handle(&self, cx: &AppContext) -> Option<Box<dyn FollowableItemHandle>> {
@@ -613,14 +753,23 @@ impl<T: Item> ItemHandle for View<T> {
         self.read(cx).breadcrumbs(theme, cx)
     }
 
-    fn serialized_item_kind(&self) -> Option<&'static str> {
-        T::serialized_item_kind()
+    fn to_serializable_item_handle(
+        &self,
+        cx: &AppContext,
+    ) -> Option<Box<dyn SerializableItemHandle>> {
+        let registry = cx.try_global::<SerializableItemRegistry>()?;
+        let item = self.to_any();
+        Some(registry.handle_builder(item.entity_type())?(&item))
     }
 
     fn show_toolbar(&self, cx: &AppContext) -> bool {
         self.read(cx).show_toolbar()
     }
 
+    fn prevent_auto_hide(&self, cx: &AppContext) -> bool {
+        self.read(cx).prevent_auto_hide(cx)
+    }
+
     fn pixel_position_of_cursor(&self, cx: &AppContext) -> Option<Point<Pixels>> {
         self.read(cx).pixel_position_of_cursor(cx)
     }
@@ -666,6 +815,46 @@ pub trait ProjectItem: Item {
         Self: Sized;
 }
 
+/// An item that is persisted along with the workspace, so that it can be restored
+/// when the workspace is reopened.
+///
+/// The workspace records the item's kind along with the state returned by
+/// [`SerializableItem::serialize`], and on restore passes that state to the
+/// `deserialize` of the type registered for the kind with
+/// [`crate::register_serializable_item`]. Items that store their state in their own
+/// tables, keyed by workspace and item id, don't need to return any.
+pub trait SerializableItem: Item {
+    fn serialized_item_kind() -> &'static str;
+
+    fn serialize(&self, _cx: &AppContext) -> Option<String> {
+        None
+    }
+
+    fn deserialize(
+        project: Model<Project>,
+        workspace: WeakView<Workspace>,
+        workspace_id: WorkspaceId,
+        item_id: ItemId,
+        state: Option<String>,
+        cx: &mut ViewContext<Pane>,
+    ) -> Task<Result<View<Self>>>;
+}
+
+pub trait SerializableItemHandle: ItemHandle {
+    fn serialized_item_kind(&self) -> &'static str;
+    fn serialize(&self, cx: &AppContext) -> Option<String>;
+}
+
+impl<T: SerializableItem> SerializableItemHandle for View<T> {
+    fn serialized_item_kind(&self) -> &'static str {
+        T::serialized_item_kind()
+    }
+
+    fn serialize(&self, cx: &AppContext) -> Option<String> {
+        self.read(cx).serialize(cx)
+    }
+}
+
 pub enum FollowEvent {
     Unfollow,
 }
@@ -768,7 +957,7 @@ impl<T: FollowableItem> FollowableItemHandle for View<T> {
 
 #[cfg(any(test, feature = "test-support"))]
 pub mod test {
-    use super::{Item, ItemEvent};
+    use super::{Item, ItemEvent, SerializableItem};
     use crate::{ItemId, ItemNavHistory, Pane, Workspace, WorkspaceId};
     use gpui::{
         AnyElement, AppContext, Context as _, EntityId, EventEmitter, FocusableView,
@@ -793,10 +982,16 @@ pub mod test {
         pub is_dirty: bool,
         pub is_singleton: bool,
         pub has_conflict: bool,
+        pub allow_close: bool,
+        pub prevent_auto_hide: bool,
+        pub save_error: Option<String>,
+        pub read_only: bool,
         pub project_items: Vec<Model<TestProjectItem>>,
         pub nav_history: Option<ItemNavHistory>,
         pub tab_descriptions: Option<Vec<&'static str>>,
         pub tab_detail: Cell<Option<usize>>,
+        /// Every value passed to [`Item::set_visible`], oldest first.
+        pub visibility_changes: Vec<bool>,
         focus_handle: gpui::FocusHandle,
     }
 
@@ -845,11 +1040,16 @@ pub mod test {
                 reload_count: 0,
                 is_dirty: false,
                 has_conflict: false,
+                allow_close: true,
+                prevent_auto_hide: false,
+                save_error: None,
+                read_only: false,
                 project_items: Vec::new(),
                 is_singleton: true,
                 nav_history: None,
                 tab_descriptions: None,
                 tab_detail: Default::default(),
+                visibility_changes: Vec::new(),
                 workspace_id: 0,
                 focus_handle: cx.focus_handle(),
             }
@@ -881,6 +1081,16 @@ pub mod test {
             self
         }
 
+        pub fn with_allow_close(mut self, allow_close: bool) -> Self {
+            self.allow_close = allow_close;
+            self
+        }
+
+        pub fn with_save_error(mut self, error: &str) -> Self {
+            self.save_error = Some(error.to_string());
+            self
+        }
+
         pub fn with_project_items(mut self, items: &[Model<TestProjectItem>]) -> Self {
             self.project_items.clear();
             self.project_items.extend(items.iter().cloned());
@@ -973,6 +1183,10 @@ pub mod test {
             self.push_to_nav_history(cx);
         }
 
+        fn set_visible(&mut self, visible: bool, _: &mut ViewContext<Self>) {
+            self.visibility_changes.push(visible);
+        }
+
         fn clone_on_split(
             &self,
             _workspace_id: WorkspaceId,
@@ -990,10 +1204,15 @@ pub mod test {
                 is_dirty: self.is_dirty,
                 is_singleton: self.is_singleton,
                 has_conflict: self.has_conflict,
+                allow_close: self.allow_close,
+                prevent_auto_hide: self.prevent_auto_hide,
+                save_error: self.save_error.clone(),
+                read_only: self.read_only,
                 project_items: self.project_items.clone(),
                 nav_history: None,
                 tab_descriptions: None,
                 tab_detail: Default::default(),
+                visibility_changes: Vec::new(),
                 workspace_id: self.workspace_id,
                 focus_handle: cx.focus_handle(),
             }))
@@ -1003,10 +1222,22 @@ pub mod test {
             self.is_dirty
         }
 
+        fn prevent_auto_hide(&self, _: &AppContext) -> bool {
+            self.prevent_auto_hide
+        }
+
         fn has_conflict(&self, _: &AppContext) -> bool {
             self.has_conflict
         }
 
+        fn set_read_only(&mut self, read_only: bool, _: &mut ViewContext<Self>) {
+            self.read_only = read_only;
+        }
+
+        fn confirm_close(&mut self, _: &mut ViewContext<Self>) -> Task<bool> {
+            Task::ready(self.allow_close)
+        }
+
         fn can_save(&self, cx: &AppContext) -> bool {
             !self.project_items.is_empty()
                 && self
@@ -1020,6 +1251,9 @@ pub mod test {
             _: Model<Project>,
             _: &mut ViewContext<Self>,
         ) -> Task<anyhow::Result<()>> {
+            if let Some(error) = &self.save_error {
+                return Task::ready(Err(anyhow::anyhow!(error.clone())));
+            }
             self.save_count += 1;
             self.is_dirty = false;
             Task::ready(Ok(()))
@@ -1045,9 +1279,11 @@ pub mod test {
             self.is_dirty = false;
             Task::ready(Ok(()))
         }
+    }
 
-        fn serialized_item_kind() -> Option<&'static str> {
-            Some("TestItem")
+    impl SerializableItem for TestItem {
+        fn serialized_item_kind() -> &'static str {
+            "TestItem"
         }
 
         fn deserialize(
@@ -1055,6 +1291,7 @@ pub mod test {
             _workspace: WeakView<Workspace>,
             workspace_id: WorkspaceId,
             _item_id: ItemId,
+            _state: Option<String>,
             cx: &mut ViewContext<Pane>,
         ) -> Task<anyhow::Result<View<Self>>> {
             let view = cx.new_view(|cx| Self::new_deserialized(workspace_id, cx));
@@ -0,0 +1,656 @@
+use std::{io::ErrorKind, sync::Arc};
+
+use anyhow::Result;
+use collections::HashMap;
+use fs::Fs;
+use gpui::{
+    uniform_list, Action, AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView,
+    KeyDownEvent, KeymapVersion, Keystroke, NoAction, Render, SharedString, Unbind,
+    UniformListScrollHandle, View, ViewContext, WeakView, WindowContext,
+};
+use serde_json::{json, Value};
+use settings::KeymapFile;
+use ui::{prelude::*, ButtonLike, Tooltip};
+use util::paths;
+
+use crate::{
+    action_registry::{registered_actions, RegisteredAction},
+    item::{Item, ItemEvent},
+    OpenKeymapEditor, Workspace, WorkspaceId,
+};
+
+/// Lists every registered action with its key bindings, and lets the user record new
+/// keystrokes for an action. Recorded bindings are appended to the user keymap file,
+/// so that they override the default ones without touching anything written there by
+/// hand.
+pub struct KeymapEditor {
+    workspace: WeakView<Workspace>,
+    focus_handle: FocusHandle,
+    recording_focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    actions: Vec<RegisteredAction>,
+    /// The actions bound to each keystroke sequence in each context, keyed by the
+    /// keystrokes and the context predicate in keymap file syntax.
+    actions_by_binding: HashMap<(String, Option<String>), Vec<SharedString>>,
+    keymap_version: KeymapVersion,
+    recording: Option<Recording>,
+}
+
+/// The keystrokes being recorded for an action.
+struct Recording {
+    action_ix: usize,
+    /// The binding the recorded keystrokes replace, if they're not added as a new one.
+    replacing: Option<gpui::KeyBinding>,
+    keystrokes: Vec<Keystroke>,
+}
+
+impl KeymapEditor {
+    /// Activates the keymap editor of the workspace, opening one if needed.
+    pub fn deploy(
+        workspace: &mut Workspace,
+        _: &OpenKeymapEditor,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        if let Some(existing) = workspace.item_of_type::<Self>(cx) {
+            workspace.activate_item(&existing, cx);
+        } else {
+            let keymap_editor = cx.new_view(|cx| Self::new(workspace.weak_handle(), cx));
+            workspace.add_item(Box::new(keymap_editor), cx);
+        }
+    }
+
+    fn new(workspace: WeakView<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        let mut this = Self {
+            workspace,
+            focus_handle: cx.focus_handle(),
+            recording_focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            actions: Vec::new(),
+            actions_by_binding: HashMap::default(),
+            keymap_version: KeymapVersion::default(),
+            recording: None,
+        };
+        this.reload_actions(cx);
+        this
+    }
+
+    /// Re-reads the actions and their bindings if the keymap changed since they were
+    /// last read.
+    fn reload_actions(&mut self, cx: &mut ViewContext<Self>) {
+        let keymap_version = cx.key_bindings().borrow().version();
+        if !self.actions.is_empty() && keymap_version == self.keymap_version {
+            return;
+        }
+
+        self.keymap_version = keymap_version;
+        self.actions = registered_actions(&[], cx)
+            .into_iter()
+            .filter(|action| {
+                action.name.as_ref() != NoAction.name()
+                    && action.name.as_ref() != Unbind::debug_name()
+            })
+            .collect();
+        self.actions_by_binding.clear();
+        for action in &self.actions {
+            for binding in &action.bindings {
+                let actions = self
+                    .actions_by_binding
+                    .entry(binding_key(binding.keystrokes(), binding))
+                    .or_default();
+                if !actions.contains(&action.name) {
+                    actions.push(action.name.clone());
+                }
+            }
+        }
+    }
+
+    /// The other actions bound to `keystrokes` in the context of `binding`, or without
+    /// a context when it's `None`.
+    fn conflicts(
+        &self,
+        action_ix: usize,
+        keystrokes: &[Keystroke],
+        binding: Option<&gpui::KeyBinding>,
+    ) -> Vec<SharedString> {
+        let action_name = &self.actions[action_ix].name;
+        let key = match binding {
+            Some(binding) => binding_key(keystrokes, binding),
+            None => (unparse_keystrokes(keystrokes), None),
+        };
+        self.actions_by_binding
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .filter(|name| *name != action_name)
+            .cloned()
+            .collect()
+    }
+
+    fn start_recording(
+        &mut self,
+        action_ix: usize,
+        replacing: Option<gpui::KeyBinding>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.recording = Some(Recording {
+            action_ix,
+            replacing,
+            keystrokes: Vec::new(),
+        });
+        cx.focus(&self.recording_focus_handle);
+        cx.notify();
+    }
+
+    fn cancel_recording(&mut self, cx: &mut ViewContext<Self>) {
+        self.recording = None;
+        cx.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    /// Records the pressed keystroke. Since the keystrokes aren't matched against the
+    /// keymap while recording, enter and escape are reserved for saving and canceling.
+    fn record_keystroke(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        if self.recording.is_none() {
+            return;
+        }
+        cx.stop_propagation();
+        if event.is_held {
+            return;
+        }
+
+        let keystroke = &event.keystroke;
+        let unmodified = keystroke.modifiers == Default::default();
+        match keystroke.key.as_str() {
+            "enter" if unmodified => self.save_recording(cx),
+            "escape" if unmodified => self.cancel_recording(cx),
+            _ => {
+                if let Some(recording) = self.recording.as_mut() {
+                    recording.keystrokes.push(Keystroke {
+                        ime_key: None,
+                        ..keystroke.clone()
+                    });
+                }
+                cx.notify();
+            }
+        }
+    }
+
+    /// Appends the recorded binding to the user keymap file, in the context of the
+    /// binding it replaces, which is unbound from the action. The binding is also added
+    /// to the keymap right away, rather than once the file is reloaded.
+    fn save_recording(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(recording) = self.recording.take() else {
+            return;
+        };
+        cx.focus(&self.focus_handle);
+        cx.notify();
+        if recording.keystrokes.is_empty() {
+            return;
+        }
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+
+        let action_name = self.actions[recording.action_ix].name.to_string();
+        let new_keystrokes = unparse_keystrokes(&recording.keystrokes);
+        let mut context = None;
+        // The replaced binding's action is written as it was in its keymap file, so
+        // that any data it was bound with carries over to the new keystrokes.
+        let action_value = recording
+            .replacing
+            .as_ref()
+            .and_then(|replacing| KeymapFile::action_value(replacing, cx))
+            .unwrap_or_else(|| Value::from(action_name.clone()));
+        let mut bindings = vec![(new_keystrokes.clone(), action_value)];
+        if let Some(replacing) = &recording.replacing {
+            context = replacing
+                .context_predicate()
+                .map(|predicate| predicate.to_string());
+            let old_keystrokes = unparse_keystrokes(replacing.keystrokes());
+            if old_keystrokes != new_keystrokes {
+                // Binding the old keystrokes to null would disable them for every other
+                // action bound to them in the same context too.
+                bindings.push((old_keystrokes, json!([Unbind::debug_name(), action_name])));
+            }
+        }
+
+        let fs = workspace.read(cx).app_state().fs.clone();
+        cx.spawn(|this, mut cx| async move {
+            let old_text = load_keymap(&fs).await?;
+            let new_text =
+                KeymapFile::append_block_to_text(&old_text, context.as_deref(), &bindings)?;
+            fs.atomic_write(paths::KEYMAP.clone(), new_text).await?;
+
+            let block = KeymapFile::append_block_to_text("", context.as_deref(), &bindings)?;
+            this.update(&mut cx, |this, cx| {
+                KeymapFile::parse(&block)?.add_to_cx(cx)?;
+                this.reload_actions(cx);
+                cx.notify();
+                anyhow::Ok(())
+            })?
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_action(&self, action_ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let action = &self.actions[action_ix];
+        let can_record = cx.build_action(&action.name, None).is_ok();
+        let recording = self
+            .recording
+            .as_ref()
+            .filter(|recording| recording.action_ix == action_ix);
+
+        let bindings = action
+            .bindings
+            .iter()
+            .enumerate()
+            .map(|(binding_ix, binding)| {
+                let conflicts = self.conflicts(action_ix, binding.keystrokes(), Some(binding));
+                let can_rebind = can_record || KeymapFile::action_value(binding, cx).is_some();
+                let replacing = binding.clone();
+                h_flex()
+                    .gap_1()
+                    .child(
+                        ButtonLike::new(ElementId::NamedInteger(action.name.clone(), binding_ix))
+                            .child(ui::KeyBinding::new(binding.clone()))
+                            .disabled(!can_rebind || recording.is_some())
+                            .tooltip(|cx| Tooltip::text("Record new keystrokes", cx))
+                            .on_click(cx.listener(move |this, _, cx| {
+                                this.start_recording(action_ix, Some(replacing.clone()), cx)
+                            })),
+                    )
+                    .when_some(binding.context_predicate(), |this, predicate| {
+                        this.child(
+                            Label::new(predicate.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .when(!conflicts.is_empty(), |this| {
+                        this.child(render_conflicts(
+                            ElementId::NamedInteger(
+                                format!("{}-conflicts", action.name).into(),
+                                binding_ix,
+                            ),
+                            &conflicts,
+                        ))
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        h_flex()
+            .w_full()
+            .h_8()
+            .px_4()
+            .gap_4()
+            .justify_between()
+            .child(Label::new(action.humanized_name.clone()))
+            .child(match recording {
+                Some(recording) => self.render_recording(recording, cx).into_any_element(),
+                None => h_flex()
+                    .gap_2()
+                    .children(bindings)
+                    .child(
+                        IconButton::new(action.name.clone(), IconName::Plus)
+                            .icon_size(IconSize::Small)
+                            .disabled(!can_record || self.recording.is_some())
+                            .tooltip(|cx| Tooltip::text("Add a key binding", cx))
+                            .on_click(cx.listener(move |this, _, cx| {
+                                this.start_recording(action_ix, None, cx)
+                            })),
+                    )
+                    .into_any_element(),
+            })
+            .into_any_element()
+    }
+
+    fn render_recording(&self, recording: &Recording, cx: &mut ViewContext<Self>) -> Div {
+        let keystrokes = if recording.keystrokes.is_empty() {
+            Label::new("Press keys…").color(Color::Muted)
+        } else {
+            Label::new(
+                recording
+                    .keystrokes
+                    .iter()
+                    .map(|keystroke| keystroke.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        };
+        let conflicts = self.conflicts(
+            recording.action_ix,
+            &recording.keystrokes,
+            recording.replacing.as_ref(),
+        );
+
+        h_flex()
+            .gap_2()
+            .child(
+                div()
+                    .id("keymap-editor-recording")
+                    .track_focus(&self.recording_focus_handle)
+                    .block_key_bindings()
+                    .on_key_down(cx.listener(Self::record_keystroke))
+                    .px_2()
+                    .border_1()
+                    .rounded_md()
+                    .border_color(cx.theme().colors().border_focused)
+                    .child(keystrokes),
+            )
+            .when(!conflicts.is_empty(), |this| {
+                this.child(render_conflicts("recording-conflicts", &conflicts))
+            })
+            .child(
+                Button::new("save-recording", "Save")
+                    .disabled(recording.keystrokes.is_empty())
+                    .on_click(cx.listener(|this, _, cx| this.save_recording(cx))),
+            )
+            .child(
+                Button::new("cancel-recording", "Cancel")
+                    .on_click(cx.listener(|this, _, cx| this.cancel_recording(cx))),
+            )
+    }
+}
+
+fn render_conflicts(id: impl Into<ElementId>, conflicts: &[SharedString]) -> impl IntoElement {
+    let tooltip = format!("Also bound to {}", conflicts.join(", "));
+    div()
+        .id(id)
+        .child(
+            Icon::new(IconName::ExclamationTriangle)
+                .size(IconSize::Small)
+                .color(Color::Warning),
+        )
+        .tooltip(move |cx| Tooltip::text(tooltip.clone(), cx))
+}
+
+fn unparse_keystrokes(keystrokes: &[Keystroke]) -> String {
+    keystrokes
+        .iter()
+        .map(|keystroke| keystroke.unparse())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn binding_key(keystrokes: &[Keystroke], binding: &gpui::KeyBinding) -> (String, Option<String>) {
+    (
+        unparse_keystrokes(keystrokes),
+        binding
+            .context_predicate()
+            .map(|predicate| predicate.to_string()),
+    )
+}
+
+async fn load_keymap(fs: &Arc<dyn Fs>) -> Result<String> {
+    match fs.load(&paths::KEYMAP).await {
+        Err(error)
+            if error
+                .downcast_ref::<std::io::Error>()
+                .map_or(false, |error| error.kind() == ErrorKind::NotFound) =>
+        {
+            Ok(String::new())
+        }
+        result => result,
+    }
+}
+
+impl Render for KeymapEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.reload_actions(cx);
+        v_flex()
+            .key_context("KeymapEditor")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .py_2()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                uniform_list(
+                    cx.view().clone(),
+                    "keymap-editor-actions",
+                    self.actions.len(),
+                    |this, range, cx| {
+                        range
+                            .map(|action_ix| this.render_action(action_ix, cx))
+                            .collect()
+                    },
+                )
+                .flex_1()
+                .track_scroll(self.scroll_handle.clone()),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for KeymapEditor {}
+
+impl FocusableView for KeymapEditor {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for KeymapEditor {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        Label::new("Key Bindings")
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("keymap editor")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        Some(cx.new_view(|cx| Self::new(self.workspace.clone(), cx)))
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{item::test::TestItem, pane, tests::init_test};
+    use fs::{FakeFs, Fs as _};
+    use gpui::{KeyBinding, KeyContext, TestAppContext};
+    use project::Project;
+
+    #[gpui::test]
+    async fn test_keymap_editor(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.create_dir(&paths::CONFIG_DIR).await.unwrap();
+        cx.update(|cx| {
+            cx.bind_keys([
+                KeyBinding::new("cmd-[", pane::GoBack, Some("Pane")),
+                KeyBinding::new("cmd-[", pane::GoForward, Some("Pane")),
+            ])
+        });
+
+        let project = Project::test(fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        cx.dispatch_action(OpenKeymapEditor);
+        let keymap_editor = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<KeymapEditor>(cx).unwrap()
+        });
+        let item = cx.new_view(|cx| TestItem::new(cx));
+        workspace.update(cx, |workspace, cx| workspace.add_item(Box::new(item), cx));
+        cx.dispatch_action(OpenKeymapEditor);
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<KeymapEditor>(cx) == Some(keymap_editor.clone()));
+        });
+
+        let go_back_ix = keymap_editor.update(cx, |editor, _| {
+            let go_back_ix = editor
+                .actions
+                .iter()
+                .position(|action| action.name.as_ref() == "pane::GoBack")
+                .unwrap();
+            let binding = &editor.actions[go_back_ix].bindings[0];
+            assert_eq!(
+                editor.conflicts(go_back_ix, binding.keystrokes(), Some(binding)),
+                [SharedString::from("pane::GoForward")]
+            );
+            go_back_ix
+        });
+
+        keymap_editor.update(cx, |editor, cx| {
+            let binding = editor.actions[go_back_ix].bindings[0].clone();
+            editor.start_recording(go_back_ix, Some(binding), cx);
+        });
+        keymap_editor.update(cx, |editor, cx| {
+            for keystroke in ["cmd-k", "b", "enter"] {
+                let event = KeyDownEvent {
+                    keystroke: Keystroke::parse(keystroke).unwrap(),
+                    is_held: false,
+                };
+                editor.record_keystroke(&event, cx);
+            }
+        });
+        cx.run_until_parked();
+
+        let keymap = fs.load(&paths::KEYMAP).await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&keymap).unwrap(),
+            serde_json::json!([{
+                "context": "Pane",
+                "bindings": {
+                    "cmd-k b": "pane::GoBack",
+                    "cmd-[": ["zed::Unbind", "pane::GoBack"],
+                },
+            }])
+        );
+        keymap_editor.update(cx, |editor, cx| {
+            let go_back = &editor.actions[go_back_ix];
+            assert_eq!(go_back.name.as_ref(), "pane::GoBack");
+            assert_eq!(
+                unparse_keystrokes(go_back.bindings.last().unwrap().keystrokes()),
+                "cmd-k b"
+            );
+
+            // Only the replaced binding is disabled, not the other action bound to the
+            // same keystrokes.
+            let keymap = cx.key_bindings();
+            let keymap = keymap.borrow();
+            let pane_context = [KeyContext::parse("Pane").unwrap()];
+            let old_bindings = keymap
+                .bindings()
+                .filter(|binding| unparse_keystrokes(binding.keystrokes()) == "cmd-[")
+                .map(|binding| {
+                    (
+                        binding.action().name(),
+                        keymap.binding_enabled(binding, &pane_context),
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                old_bindings,
+                [("pane::GoBack", false), ("pane::GoForward", true)]
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_rebinding_action_with_data(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.create_dir(&paths::CONFIG_DIR).await.unwrap();
+        cx.update(|cx| {
+            KeymapFile::parse(
+                r#"[
+                    {
+                        "context": "Pane",
+                        "bindings": {
+                            "cmd-1": ["pane::ActivateItem", 0],
+                            "cmd-2": ["pane::ActivateItem", 1]
+                        }
+                    }
+                ]"#,
+            )
+            .unwrap()
+            .add_to_cx(cx)
+            .unwrap();
+        });
+
+        let project = Project::test(fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        cx.dispatch_action(OpenKeymapEditor);
+        let keymap_editor = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<KeymapEditor>(cx).unwrap()
+        });
+
+        keymap_editor.update(cx, |editor, cx| {
+            let activate_item_ix = editor
+                .actions
+                .iter()
+                .position(|action| action.name.as_ref() == "pane::ActivateItem")
+                .unwrap();
+            let binding = editor.actions[activate_item_ix]
+                .bindings
+                .iter()
+                .find(|binding| unparse_keystrokes(binding.keystrokes()) == "cmd-2")
+                .unwrap()
+                .clone();
+            assert_eq!(
+                KeymapFile::action_value(&binding, cx),
+                Some(serde_json::json!(["pane::ActivateItem", 1]))
+            );
+            editor.start_recording(activate_item_ix, Some(binding), cx);
+            for keystroke in ["cmd-k", "2", "enter"] {
+                let event = KeyDownEvent {
+                    keystroke: Keystroke::parse(keystroke).unwrap(),
+                    is_held: false,
+                };
+                editor.record_keystroke(&event, cx);
+            }
+        });
+        cx.run_until_parked();
+
+        let keymap = fs.load(&paths::KEYMAP).await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&keymap).unwrap(),
+            serde_json::json!([{
+                "context": "Pane",
+                "bindings": {
+                    "cmd-k 2": ["pane::ActivateItem", 1],
+                    "cmd-2": ["zed::Unbind", "pane::ActivateItem"],
+                },
+            }])
+        );
+        cx.update(|cx| {
+            let keymap = cx.key_bindings();
+            let keymap = keymap.borrow();
+            let pane_context = [KeyContext::parse("Pane").unwrap()];
+            let new_binding = keymap
+                .bindings()
+                .find(|binding| unparse_keystrokes(binding.keystrokes()) == "cmd-k 2")
+                .unwrap();
+            assert!(new_binding.action().partial_eq(&pane::ActivateItem(1)));
+            assert!(keymap.binding_enabled(new_binding, &pane_context));
+            for binding in keymap.bindings() {
+                let keystrokes = unparse_keystrokes(binding.keystrokes());
+                if keystrokes == "cmd-1" {
+                    assert!(keymap.binding_enabled(binding, &pane_context));
+                } else if keystrokes == "cmd-2" {
+                    assert!(!keymap.binding_enabled(binding, &pane_context));
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,430 @@
+use gpui::{
+    div, prelude::*, px, AnyView, DismissEvent, FocusHandle, ManagedView, Render, Subscription,
+    View, ViewContext, WindowContext,
+};
+use ui::{h_flex, v_flex};
+
+pub trait ModalView: ManagedView {
+    fn on_before_dismiss(&mut self, _: &mut ViewContext<Self>) -> bool {
+        true
+    }
+}
+
+trait ModalViewHandle {
+    fn on_before_dismiss(&mut self, cx: &mut WindowContext) -> bool;
+    fn view(&self) -> AnyView;
+}
+
+impl<V: ModalView> ModalViewHandle for View<V> {
+    fn on_before_dismiss(&mut self, cx: &mut WindowContext) -> bool {
+        self.update(cx, |this, cx| this.on_before_dismiss(cx))
+    }
+
+    fn view(&self) -> AnyView {
+        self.clone().into()
+    }
+}
+
+pub struct ActiveModal {
+    modal: Box<dyn ModalViewHandle>,
+    _subscriptions: [Subscription; 2],
+    previous_focus_handle: Option<FocusHandle>,
+    focus_handle: FocusHandle,
+}
+
+/// Owns the workspace's modals (file finder, command palette, pickers, ...).
+///
+/// Modals are kept in a stack, with the topmost one receiving focus. Escape and
+/// clicks outside of the topmost modal dismiss it, and focus is restored to
+/// whatever was focused before it was shown.
+pub struct ModalManager {
+    modal_stack: Vec<ActiveModal>,
+}
+
+impl ModalManager {
+    pub fn new() -> Self {
+        Self {
+            modal_stack: Vec::new(),
+        }
+    }
+
+    /// Shows a modal of the given type, replacing the topmost modal. If the
+    /// topmost modal is already of this type, it is dismissed instead.
+    pub fn toggle_modal<V, B>(&mut self, cx: &mut ViewContext<Self>, build_view: B)
+    where
+        V: ModalView,
+        B: FnOnce(&mut ViewContext<V>) -> V,
+    {
+        if let Some(active_modal) = self.modal_stack.last() {
+            let is_close = active_modal.modal.view().downcast::<V>().is_ok();
+            let did_close = self.hide_modal(cx);
+            if is_close || !did_close {
+                return;
+            }
+        }
+        let new_modal = cx.new_view(build_view);
+        self.show_modal(new_modal, cx);
+    }
+
+    /// Shows a modal on top of the current ones, e.g. a confirmation opened from
+    /// within a picker. Dismissing it returns focus to the modal underneath.
+    pub fn push_modal<V, B>(&mut self, cx: &mut ViewContext<Self>, build_view: B) -> View<V>
+    where
+        V: ModalView,
+        B: FnOnce(&mut ViewContext<V>) -> V,
+    {
+        let new_modal = cx.new_view(build_view);
+        self.show_modal(new_modal.clone(), cx);
+        new_modal
+    }
+
+    fn show_modal<V>(&mut self, new_modal: View<V>, cx: &mut ViewContext<Self>)
+    where
+        V: ModalView,
+    {
+        let focus_handle = cx.focus_handle();
+        let previous_focus_handle = cx.focused();
+        self.modal_stack.push(ActiveModal {
+            modal: Box::new(new_modal.clone()),
+            _subscriptions: [
+                cx.subscribe(&new_modal, |this, modal, _: &DismissEvent, cx| {
+                    let modal_id = modal.entity_id();
+                    if let Some(ix) = this
+                        .modal_stack
+                        .iter()
+                        .position(|active| active.modal.view().entity_id() == modal_id)
+                    {
+                        this.dismiss_modal_at(ix, cx);
+                    }
+                }),
+                cx.on_focus_out(&focus_handle, {
+                    let focus_handle = focus_handle.clone();
+                    move |this, cx| {
+                        // Focus moves out of a modal when another one is pushed on
+                        // top of it, which must not dismiss the modal underneath.
+                        if this
+                            .modal_stack
+                            .last()
+                            .map_or(false, |active| active.focus_handle == focus_handle)
+                        {
+                            this.hide_modal(cx);
+                        }
+                    }
+                }),
+            ],
+            previous_focus_handle,
+            focus_handle,
+        });
+        cx.focus_view(&new_modal);
+        cx.notify();
+    }
+
+    /// Dismisses the topmost modal, returning whether it agreed to be dismissed.
+    pub fn hide_modal(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        match self.modal_stack.len() {
+            0 => false,
+            len => self.dismiss_modal_at(len - 1, cx),
+        }
+    }
+
+    fn dismiss_modal_at(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> bool {
+        let Some(active_modal) = self.modal_stack.get_mut(ix) else {
+            return false;
+        };
+
+        let dismiss = active_modal.modal.on_before_dismiss(cx);
+        if !dismiss {
+            return false;
+        }
+
+        let active_modal = self.modal_stack.remove(ix);
+        if let Some(modal_above) = self.modal_stack.get_mut(ix) {
+            // The modal that was stacked on top of the dismissed one now returns
+            // focus to wherever the dismissed one would have.
+            modal_above.previous_focus_handle = active_modal.previous_focus_handle;
+        } else if active_modal.focus_handle.contains_focused(cx) {
+            if let Some(previous_focus) = active_modal.previous_focus_handle {
+                previous_focus.focus(cx);
+            }
+        }
+        cx.notify();
+        true
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        self.hide_modal(cx);
+    }
+
+    pub fn active_modal<V>(&self) -> Option<View<V>>
+    where
+        V: 'static,
+    {
+        let active_modal = self.modal_stack.last()?;
+        active_modal.modal.view().downcast::<V>().ok()
+    }
+
+    pub fn has_active_modal(&self) -> bool {
+        !self.modal_stack.is_empty()
+    }
+}
+
+impl Render for ModalManager {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        if self.modal_stack.is_empty() {
+            return div();
+        }
+
+        let top_ix = self.modal_stack.len() - 1;
+        div()
+            .absolute()
+            .size_full()
+            .top_0()
+            .left_0()
+            .z_index(169)
+            .on_action(cx.listener(Self::cancel))
+            .children(
+                self.modal_stack
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, active_modal)| {
+                        div().absolute().size_full().top_0().left_0().child(
+                            v_flex()
+                                .h(px(0.0))
+                                .top_20()
+                                .flex()
+                                .flex_col()
+                                .items_center()
+                                .track_focus(&active_modal.focus_handle)
+                                .child(
+                                    h_flex()
+                                        .when(ix == top_ix, |this| {
+                                            this.on_mouse_down_out(cx.listener(|this, _, cx| {
+                                                this.hide_modal(cx);
+                                            }))
+                                        })
+                                        .child(active_modal.modal.view()),
+                                ),
+                        )
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Workspace;
+    use fs::FakeFs;
+    use gpui::{
+        point, AppContext, EventEmitter, FocusableView, Modifiers, MouseButton, MouseDownEvent,
+        TestAppContext, VisualTestContext,
+    };
+    use project::Project;
+    use settings::SettingsStore;
+    use theme::LoadThemes;
+
+    struct TestModal {
+        focus_handle: FocusHandle,
+        allow_dismiss: bool,
+    }
+
+    struct OtherTestModal {
+        focus_handle: FocusHandle,
+    }
+
+    impl EventEmitter<DismissEvent> for TestModal {}
+    impl EventEmitter<DismissEvent> for OtherTestModal {}
+
+    impl FocusableView for TestModal {
+        fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+            self.focus_handle.clone()
+        }
+    }
+
+    impl FocusableView for OtherTestModal {
+        fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+            self.focus_handle.clone()
+        }
+    }
+
+    impl Render for TestModal {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_10().track_focus(&self.focus_handle)
+        }
+    }
+
+    impl Render for OtherTestModal {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_10().track_focus(&self.focus_handle)
+        }
+    }
+
+    impl ModalView for TestModal {
+        fn on_before_dismiss(&mut self, _: &mut ViewContext<Self>) -> bool {
+            self.allow_dismiss
+        }
+    }
+
+    impl ModalView for OtherTestModal {}
+
+    fn test_modal(allow_dismiss: bool) -> impl FnOnce(&mut ViewContext<TestModal>) -> TestModal {
+        move |cx| TestModal {
+            focus_handle: cx.focus_handle(),
+            allow_dismiss,
+        }
+    }
+
+    fn other_test_modal(cx: &mut ViewContext<OtherTestModal>) -> OtherTestModal {
+        OtherTestModal {
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_toggling_modals(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, test_modal(true));
+            assert!(workspace.active_modal::<TestModal>(cx).is_some());
+
+            // Toggling a modal of another type replaces the open one.
+            workspace.toggle_modal(cx, other_test_modal);
+            assert!(workspace.active_modal::<TestModal>(cx).is_none());
+            assert!(workspace.active_modal::<OtherTestModal>(cx).is_some());
+
+            // Toggling the open modal's type closes it.
+            workspace.toggle_modal(cx, other_test_modal);
+            assert!(!workspace.has_active_modal(cx));
+
+            // A modal that refuses to be dismissed isn't replaced.
+            workspace.toggle_modal(cx, test_modal(false));
+            workspace.toggle_modal(cx, other_test_modal);
+            assert!(workspace.active_modal::<TestModal>(cx).is_some());
+            assert!(workspace.active_modal::<OtherTestModal>(cx).is_none());
+            assert!(!workspace.hide_modal(cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_dismissing_modals(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let pane_is_focused = |cx: &mut VisualTestContext| {
+            cx.update(|cx| cx.refresh());
+            pane.update(cx, |pane, cx| pane.has_focus(cx))
+        };
+        assert!(pane_is_focused(cx));
+
+        // Escape dismisses the modal, returning focus to where it was.
+        let modal = workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, test_modal(true));
+            workspace.active_modal::<TestModal>(cx).unwrap()
+        });
+        cx.update(|cx| cx.refresh());
+        assert!(modal.update(cx, |modal, cx| modal.focus_handle.is_focused(cx)));
+        assert!(!pane_is_focused(cx));
+        cx.dispatch_action(menu::Cancel);
+        assert!(workspace.update(cx, |workspace, cx| !workspace.has_active_modal(cx)));
+        assert!(pane_is_focused(cx));
+
+        // So does clicking outside of it.
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, test_modal(true))
+        });
+        cx.update(|cx| cx.refresh());
+        assert!(!pane_is_focused(cx));
+        cx.simulate_event(MouseDownEvent {
+            button: MouseButton::Left,
+            position: point(px(1.), px(1.)),
+            modifiers: Modifiers::default(),
+            click_count: 1,
+        });
+        assert!(workspace.update(cx, |workspace, cx| !workspace.has_active_modal(cx)));
+        assert!(pane_is_focused(cx));
+
+        // A modal dismissing itself also restores focus.
+        let modal = workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, test_modal(true));
+            workspace.active_modal::<TestModal>(cx).unwrap()
+        });
+        modal.update(cx, |_, cx| cx.emit(DismissEvent));
+        assert!(workspace.update(cx, |workspace, cx| !workspace.has_active_modal(cx)));
+        assert!(pane_is_focused(cx));
+    }
+
+    #[gpui::test]
+    async fn test_nested_modals(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let pane_is_focused = |cx: &mut VisualTestContext| {
+            cx.update(|cx| cx.refresh());
+            pane.update(cx, |pane, cx| pane.has_focus(cx))
+        };
+
+        let parent = workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, test_modal(true));
+            workspace.active_modal::<TestModal>(cx).unwrap()
+        });
+        cx.update(|cx| cx.refresh());
+        let child = workspace.update(cx, |workspace, cx| {
+            workspace.push_modal(cx, other_test_modal)
+        });
+        cx.update(|cx| cx.refresh());
+        assert!(child.update(cx, |child, cx| child.focus_handle.is_focused(cx)));
+
+        // Focus moving to the nested modal doesn't dismiss its parent.
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(
+                workspace.active_modal::<OtherTestModal>(cx),
+                Some(child.clone())
+            );
+        });
+
+        // Dismissing the nested modal returns focus to its parent.
+        cx.dispatch_action(menu::Cancel);
+        cx.update(|cx| cx.refresh());
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(
+                workspace.active_modal::<TestModal>(cx),
+                Some(parent.clone())
+            );
+        });
+        assert!(parent.update(cx, |parent, cx| parent.focus_handle.is_focused(cx)));
+
+        cx.dispatch_action(menu::Cancel);
+        assert!(workspace.update(cx, |workspace, cx| !workspace.has_active_modal(cx)));
+        assert!(pane_is_focused(cx));
+
+        // A parent dismissed from under a nested modal hands its focus target over.
+        let parent = workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, test_modal(true));
+            workspace.active_modal::<TestModal>(cx).unwrap()
+        });
+        cx.update(|cx| cx.refresh());
+        workspace.update(cx, |workspace, cx| {
+            workspace.push_modal(cx, other_test_modal);
+        });
+        cx.update(|cx| cx.refresh());
+        parent.update(cx, |_, cx| cx.emit(DismissEvent));
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<OtherTestModal>(cx).is_some());
+        });
+        cx.dispatch_action(menu::Cancel);
+        assert!(workspace.update(cx, |workspace, cx| !workspace.has_active_modal(cx)));
+        assert!(pane_is_focused(cx));
+    }
+
+    async fn init_test(cx: &mut TestAppContext) -> (View<Workspace>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(LoadThemes::JustBase, cx);
+            crate::init_settings(cx);
+            Project::init_settings(cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        cx.add_window_view(|cx| Workspace::test_new(project, cx))
+    }
+}
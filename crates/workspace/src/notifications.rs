@@ -2,12 +2,22 @@ use crate::{Toast, Workspace};
 use collections::HashMap;
 use gpui::{
     AnyView, AppContext, AsyncWindowContext, DismissEvent, Entity, EntityId, EventEmitter, Global,
-    PromptLevel, Render, Task, View, ViewContext, VisualContext, WindowContext,
+    PromptLevel, Render, SharedString, Task, View, ViewContext, VisualContext, WindowContext,
 };
-use std::{any::TypeId, ops::DerefMut};
+use std::{
+    any::TypeId,
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+    ops::DerefMut,
+};
+use time::OffsetDateTime;
+use util::post_inc;
+
+const MAX_LOGGED_NOTIFICATIONS: usize = 500;
 
 pub fn init(cx: &mut AppContext) {
     cx.set_global(NotificationTracker::new());
+    cx.set_global(NotificationLog::default());
 }
 
 pub trait Notification: EventEmitter<DismissEvent> + Render {}
@@ -63,6 +73,87 @@ impl NotificationTracker {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NotificationSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug)]
+pub struct LoggedNotification {
+    pub id: usize,
+    pub severity: NotificationSeverity,
+    pub message: SharedString,
+    pub timestamp: OffsetDateTime,
+}
+
+/// A history of the notifications shown across all workspaces, kept so that they
+/// can still be reviewed after being dismissed. Only the most recent ones are kept.
+#[derive(Default)]
+pub struct NotificationLog {
+    entries: VecDeque<LoggedNotification>,
+    next_id: usize,
+}
+
+impl Global for NotificationLog {}
+
+impl NotificationLog {
+    /// Returns the logged notifications, oldest first.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &LoggedNotification> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, severity: NotificationSeverity, message: impl Into<SharedString>) {
+        if self.entries.len() == MAX_LOGGED_NOTIFICATIONS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LoggedNotification {
+            id: post_inc(&mut self.next_id),
+            severity,
+            message: message.into(),
+            timestamp: OffsetDateTime::now_utc(),
+        });
+    }
+
+    pub fn remove(&mut self, id: usize) {
+        self.entries.retain(|entry| entry.id != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Derives a notification or toast id from the type `T`, for notifications that are shown
+/// from a single place and would otherwise need a number nobody else happens to use.
+pub fn notification_id<T: 'static>() -> usize {
+    let mut hasher = DefaultHasher::new();
+    TypeId::of::<T>().hash(&mut hasher);
+    hasher.finish() as usize
+}
+
+/// Records a notification in the [`NotificationLog`], for notifications that aren't
+/// shown through [`Workspace::show_error`] or [`Workspace::show_toast`].
+pub fn log_notification(
+    severity: NotificationSeverity,
+    message: impl Into<SharedString>,
+    cx: &mut AppContext,
+) {
+    // The log isn't initialized in some tests.
+    if cx.has_global::<NotificationLog>() {
+        cx.update_global(|log: &mut NotificationLog, _| log.push(severity, message));
+    }
+}
+
 impl Workspace {
     pub fn has_shown_notification_once<V: Notification>(
         &self,
@@ -75,6 +166,16 @@ impl Workspace {
             .unwrap_or(false)
     }
 
+    /// Whether a notification of type `V` with the given id is currently shown.
+    pub fn is_showing_notification<V: Notification>(&self, id: usize) -> bool {
+        let type_id = TypeId::of::<V>();
+        self.notifications
+            .iter()
+            .any(|(existing_type_id, existing_id, _)| {
+                (*existing_type_id, *existing_id) == (type_id, id)
+            })
+    }
+
     pub fn show_notification_once<V: Notification>(
         &mut self,
         id: usize,
@@ -118,6 +219,7 @@ impl Workspace {
     where
         E: std::fmt::Debug,
     {
+        log_notification(NotificationSeverity::Error, format!("{err:?}"), cx);
         self.show_notification(0, cx, |cx| {
             cx.new_view(|_cx| {
                 simple_message_notification::MessageNotification::new(format!("Error: {err:?}"))
@@ -132,6 +234,7 @@ impl Workspace {
     }
 
     pub fn show_toast(&mut self, toast: Toast, cx: &mut ViewContext<Self>) {
+        log_notification(NotificationSeverity::Info, toast.msg.to_string(), cx);
         self.dismiss_notification::<simple_message_notification::MessageNotification>(toast.id, cx);
         self.show_notification(toast.id, cx, |cx| {
             cx.new_view(|_cx| match toast.on_click.as_ref() {
@@ -345,3 +448,92 @@ where
         .detach();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::init_test;
+    use anyhow::anyhow;
+    use fs::FakeFs;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::Project;
+
+    #[gpui::test]
+    async fn test_notification_log(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| cx.set_global(NotificationLog::default()));
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let logged = |cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                cx.global::<NotificationLog>()
+                    .entries()
+                    .map(|entry| (entry.id, entry.severity, entry.message.to_string()))
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Errors, toasts and notifications shown elsewhere are all recorded,
+        // each with its own severity.
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_error(&anyhow!("disk full"), cx);
+            workspace.show_toast(Toast::new(1, "Copied permalink"), cx);
+        });
+        cx.update(|cx| {
+            log_notification(NotificationSeverity::Warning, "Language server exited", cx)
+        });
+        assert_eq!(
+            logged(cx),
+            [
+                (0, NotificationSeverity::Error, "disk full".to_string()),
+                (
+                    1,
+                    NotificationSeverity::Info,
+                    "Copied permalink".to_string()
+                ),
+                (
+                    2,
+                    NotificationSeverity::Warning,
+                    "Language server exited".to_string()
+                ),
+            ]
+        );
+
+        // Dismissing a notification in the workspace keeps it in the log.
+        workspace.update(cx, |workspace, cx| {
+            workspace.dismiss_toast(1, cx);
+            assert!(workspace.notifications.iter().all(|(_, id, _)| *id != 1));
+        });
+        assert_eq!(logged(cx).len(), 3);
+
+        // Dismissing it from the log removes only that entry, and ids aren't reused.
+        cx.update(|cx| cx.update_global(|log: &mut NotificationLog, _| log.remove(1)));
+        cx.update(|cx| log_notification(NotificationSeverity::Info, "Saved", cx));
+        assert_eq!(
+            logged(cx)
+                .into_iter()
+                .map(|(id, _, _)| id)
+                .collect::<Vec<_>>(),
+            [0, 2, 3]
+        );
+
+        cx.update(|cx| cx.update_global(|log: &mut NotificationLog, _| log.clear()));
+        assert!(logged(cx).is_empty());
+    }
+
+    #[test]
+    fn test_notification_log_keeps_most_recent_entries() {
+        let mut log = NotificationLog::default();
+        for ix in 0..MAX_LOGGED_NOTIFICATIONS + 2 {
+            log.push(NotificationSeverity::Info, format!("notification {ix}"));
+        }
+        assert_eq!(log.len(), MAX_LOGGED_NOTIFICATIONS);
+        assert_eq!(log.entries().next().unwrap().message, "notification 2");
+        assert_eq!(
+            log.entries().next_back().unwrap().message,
+            format!("notification {}", MAX_LOGGED_NOTIFICATIONS + 1)
+        );
+    }
+}
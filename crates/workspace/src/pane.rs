@@ -1,17 +1,22 @@
 use crate::{
-    item::{ClosePosition, Item, ItemHandle, ItemSettings, WeakItemHandle},
+    item::{settings_for_item, ClosePosition, Item, ItemHandle, ItemSettings, WeakItemHandle},
+    persistence::model::SerializedClosedItem,
     toolbar::Toolbar,
-    workspace_settings::{AutosaveSetting, WorkspaceSettings},
-    NewCenterTerminal, NewFile, NewSearch, OpenVisible, SplitDirection, ToggleZoom, Workspace,
+    workspace_settings::{
+        ActivePaneIndicatorStyle, AutosaveSetting, MouseBindings, WorkspaceSettings,
+    },
+    NewCenterTerminal, NewFile, NewSearch, SplitDirection, ToggleZoom, UnsavedChangesModal,
+    Workspace,
 };
 use anyhow::Result;
 use collections::{HashMap, HashSet, VecDeque};
 use futures::{stream::FuturesUnordered, StreamExt};
 use gpui::{
-    actions, impl_actions, overlay, prelude::*, Action, AnchorCorner, AnyElement, AppContext,
-    AsyncWindowContext, DismissEvent, Div, DragMoveEvent, EntityId, EventEmitter, ExternalPaths,
-    FocusHandle, FocusableView, Model, MouseButton, NavigationDirection, Pixels, Point,
-    PromptLevel, Render, ScrollHandle, Subscription, Task, View, ViewContext, VisualContext,
+    actions, canvas, fill, impl_actions, overlay, prelude::*, Action, AnchorCorner, AnyElement,
+    AppContext, AsyncWindowContext, DismissEvent, Div, DragMoveEvent, EntityId, EventEmitter,
+    ExternalPaths, ExternalText, FocusHandle, FocusableView, Model, MouseButton, MouseDownEvent,
+    NavigationDirection, Pixels, Point, PromptLevel, Render, ScrollHandle, ScrollWheelEvent,
+    Subscription, SwipeDirection, SwipeEvent, Task, TouchPhase, View, ViewContext, VisualContext,
     WeakView, WindowContext,
 };
 use parking_lot::Mutex;
@@ -32,8 +37,8 @@ use std::{
 use theme::ThemeSettings;
 
 use ui::{
-    prelude::*, right_click_menu, ButtonSize, Color, IconButton, IconButtonShape, IconName,
-    IconSize, Indicator, Label, Tab, TabBar, TabPosition, Tooltip,
+    prelude::*, right_click_menu, tooltip_container, Avatar, ButtonSize, Color, IconButton,
+    IconButtonShape, IconName, IconSize, Indicator, Label, Tab, TabBar, TabPosition, Tooltip,
 };
 use ui::{v_flex, ContextMenu};
 use util::{maybe, truncate_and_remove_front, ResultExt};
@@ -94,6 +99,7 @@ actions!(
         ActivatePrevItem,
         ActivateNextItem,
         ActivateLastItem,
+        AlternateFile,
         CloseInactiveItems,
         CloseCleanItems,
         CloseItemsToTheLeft,
@@ -106,6 +112,12 @@ actions!(
         SplitUp,
         SplitRight,
         SplitDown,
+        MoveToNewWindow,
+        FocusTabBar,
+        SelectPrevTab,
+        SelectNextTab,
+        MoveTabLeft,
+        MoveTabRight,
     ]
 );
 
@@ -117,6 +129,7 @@ pub enum Event {
     Remove,
     RemoveItem { item_id: EntityId },
     Split(SplitDirection),
+    MoveToNewWindow,
     ChangeItemTitle,
     Focus,
     ZoomIn,
@@ -143,6 +156,7 @@ impl fmt::Debug for Event {
                 .debug_struct("Split")
                 .field("direction", direction)
                 .finish(),
+            Event::MoveToNewWindow => f.write_str("MoveToNewWindow"),
             Event::ChangeItemTitle => f.write_str("ChangeItemTitle"),
             Event::Focus => f.write_str("Focus"),
             Event::ZoomIn => f.write_str("ZoomIn"),
@@ -156,6 +170,12 @@ pub struct Pane {
     items: Vec<Box<dyn ItemHandle>>,
     activation_history: Vec<EntityId>,
     zoomed: bool,
+    /// Whether the pane is on screen. Panes hosted by a panel are hidden with its dock,
+    /// and stay suspended while hidden: changes to their items don't re-render them.
+    visible: bool,
+    /// Whether an item changed while the pane was suspended, so it must re-render
+    /// once it's shown again.
+    stale: bool,
     was_focused: bool,
     active_item_index: usize,
     last_focused_view_by_item: HashMap<EntityId, FocusHandle>,
@@ -174,9 +194,20 @@ pub struct Pane {
     render_tab_bar_buttons: Rc<dyn Fn(&mut Pane, &mut ViewContext<Pane>) -> AnyElement>,
     _subscriptions: Vec<Subscription>,
     tab_bar_scroll_handle: ScrollHandle,
+    /// Focused while the tab bar is navigated with the keyboard.
+    tab_bar_focus_handle: FocusHandle,
+    /// The tab under the keyboard cursor while the tab bar is focused.
+    tab_cursor: usize,
+    /// Lines scrolled over the tab bar with shift held that didn't switch tabs yet.
+    tab_bar_scroll_remainder: f32,
     display_nav_history_buttons: bool,
 }
 
+/// A handle to a pane's navigation history on behalf of one of its items.
+///
+/// Entries pushed here carry arbitrary item-specific data, which is handed back
+/// to [`Item::navigate`] when the user goes back or forward to them.
+#[derive(Clone)]
 pub struct ItemNavHistory {
     history: NavHistory,
     item: Arc<dyn WeakItemHandle>,
@@ -191,6 +222,10 @@ struct NavHistoryState {
     forward_stack: VecDeque<NavigationEntry>,
     closed_stack: VecDeque<NavigationEntry>,
     paths_by_item: HashMap<EntityId, (ProjectPath, Option<PathBuf>)>,
+    closed_item_details: HashMap<EntityId, ClosedItemDetails>,
+    /// The timestamp of the latest entry pushed while navigating normally, rather than
+    /// through the history.
+    last_visit_timestamp: Option<usize>,
     pane: WeakView<Pane>,
     next_timestamp: Arc<AtomicUsize>,
 }
@@ -211,6 +246,12 @@ impl Default for NavigationMode {
     }
 }
 
+/// What is remembered about a closed item, beyond its path, to reopen it in a later session.
+struct ClosedItemDetails {
+    kind: Option<Arc<str>>,
+    position: Option<language::Point>,
+}
+
 pub struct NavigationEntry {
     pub item: Arc<dyn WeakItemHandle>,
     pub data: Option<Box<dyn Any + Send>>,
@@ -251,6 +292,8 @@ impl Pane {
             activation_history: Vec::new(),
             was_focused: false,
             zoomed: false,
+            visible: true,
+            stale: false,
             active_item_index: 0,
             last_focused_view_by_item: Default::default(),
             nav_history: NavHistory(Arc::new(Mutex::new(NavHistoryState {
@@ -259,6 +302,8 @@ impl Pane {
                 forward_stack: Default::default(),
                 closed_stack: Default::default(),
                 paths_by_item: Default::default(),
+                closed_item_details: Default::default(),
+                last_visit_timestamp: None,
                 pane: handle.clone(),
                 next_timestamp,
             }))),
@@ -266,6 +311,9 @@ impl Pane {
             new_item_menu: None,
             split_item_menu: None,
             tab_bar_scroll_handle: ScrollHandle::new(),
+            tab_bar_focus_handle: cx.focus_handle(),
+            tab_cursor: 0,
+            tab_bar_scroll_remainder: 0.,
             drag_split_direction: None,
             workspace,
             project,
@@ -307,6 +355,8 @@ impl Pane {
                                         .action("Split Left", SplitLeft.boxed_clone())
                                         .action("Split Up", SplitUp.boxed_clone())
                                         .action("Split Down", SplitDown.boxed_clone())
+                                        .separator()
+                                        .action("Move to New Window", MoveToNewWindow.boxed_clone())
                                 });
                                 cx.subscribe(&menu, |pane, _, _: &DismissEvent, cx| {
                                     pane.focus(cx);
@@ -358,6 +408,10 @@ impl Pane {
         if !self.was_focused {
             self.was_focused = true;
             cx.emit(Event::Focus);
+            let pane = cx.view().clone();
+            self.workspace
+                .update(cx, |workspace, _| workspace.pane_focused(&pane))
+                .ok();
             cx.notify();
         }
 
@@ -378,7 +432,7 @@ impl Pane {
 
                 active_item.focus_handle(cx).focus(cx);
             } else if let Some(focused) = cx.focused() {
-                if !self.context_menu_focused(cx) {
+                if !self.context_menu_focused(cx) && !self.tab_bar_focus_handle.is_focused(cx) {
                     self.last_focused_view_by_item
                         .insert(active_item.item_id(), focused);
                 }
@@ -618,6 +672,33 @@ impl Pane {
         cx.emit(Event::AddItem { item });
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Shows or hides the pane's active item, see [`Item::set_visible`].
+    pub fn set_visible(&mut self, visible: bool, cx: &mut ViewContext<Self>) {
+        if visible != self.visible {
+            self.visible = visible;
+            if let Some(item) = self.active_item() {
+                item.set_visible(visible, cx);
+            }
+            if visible && mem::take(&mut self.stale) {
+                cx.notify();
+            }
+        }
+    }
+
+    /// Re-renders the pane after one of its items changed how its tab is shown. A
+    /// hidden pane is suspended instead, and catches up when it's shown again.
+    pub(crate) fn notify_item_changed(&mut self, cx: &mut ViewContext<Self>) {
+        if self.visible {
+            cx.notify();
+        } else {
+            self.stale = true;
+        }
+    }
+
     pub fn items_len(&self) -> usize {
         self.items.len()
     }
@@ -693,6 +774,9 @@ impl Pane {
             {
                 if let Some(prev_item) = self.items.get(prev_active_item_ix) {
                     prev_item.deactivated(cx);
+                    if prev_active_item_ix != index {
+                        prev_item.set_visible(false, cx);
+                    }
                 }
 
                 cx.emit(Event::ActivateItem {
@@ -701,6 +785,7 @@ impl Pane {
             }
 
             if let Some(newly_active_item) = self.items.get(index) {
+                newly_active_item.set_visible(self.visible, cx);
                 self.activation_history
                     .retain(|&previously_active_item_id| {
                         previously_active_item_id != newly_active_item.item_id()
@@ -730,6 +815,22 @@ impl Pane {
         self.activate_item(index, activate_pane, activate_pane, cx);
     }
 
+    /// Activates the item that was active before the current one, so that repeating
+    /// this switches back and forth between the two most recent items.
+    pub fn activate_alternate_item(&mut self, cx: &mut ViewContext<Self>) {
+        let active_item_id = self.active_item().map(|item| item.item_id());
+        let Some(index) = self
+            .activation_history
+            .iter()
+            .rev()
+            .filter(|&&item_id| Some(item_id) != active_item_id)
+            .find_map(|&item_id| self.items.iter().position(|item| item.item_id() == item_id))
+        else {
+            return;
+        };
+        self.activate_item(index, true, true, cx);
+    }
+
     pub fn activate_next_item(&mut self, activate_pane: bool, cx: &mut ViewContext<Self>) {
         let mut index = self.active_item_index;
         if index + 1 < self.items.len() {
@@ -866,58 +967,49 @@ impl Pane {
         )
     }
 
-    pub(super) fn file_names_for_prompt(
-        items: &mut dyn Iterator<Item = &Box<dyn ItemHandle>>,
-        all_dirty_items: usize,
+    /// Returns the dirty items among `items_to_close` that would need saving, i.e. the
+    /// ones with project items that aren't open anywhere else in the workspace.
+    fn dirty_items_to_save(
+        items_to_close: &[Box<dyn ItemHandle>],
+        workspace: &Workspace,
         cx: &AppContext,
-    ) -> (String, String) {
-        /// Quantity of item paths displayed in prompt prior to cutoff..
-        const FILE_NAMES_CUTOFF_POINT: usize = 10;
-        let mut file_names: Vec<_> = items
-            .filter_map(|item| {
-                item.project_path(cx).and_then(|project_path| {
-                    project_path
-                        .path
-                        .file_name()
-                        .and_then(|name| name.to_str().map(ToOwned::to_owned))
-                })
+    ) -> Vec<Box<dyn ItemHandle>> {
+        let other_project_item_ids = workspace
+            .items(cx)
+            .filter(|item| {
+                !items_to_close
+                    .iter()
+                    .any(|item_to_close| item_to_close.item_id() == item.item_id())
             })
-            .take(FILE_NAMES_CUTOFF_POINT)
-            .collect();
-        let should_display_followup_text =
-            all_dirty_items > FILE_NAMES_CUTOFF_POINT || file_names.len() != all_dirty_items;
-        if should_display_followup_text {
-            let not_shown_files = all_dirty_items - file_names.len();
-            if not_shown_files == 1 {
-                file_names.push(".. 1 file not shown".into());
-            } else {
-                file_names.push(format!(".. {} files not shown", not_shown_files).into());
-            }
-        }
-        (
-            format!(
-                "Do you want to save changes to the following {} files?",
-                all_dirty_items
-            ),
-            file_names.join("\n"),
-        )
+            .flat_map(|item| item.project_item_model_ids(cx))
+            .collect::<HashSet<_>>();
+
+        let mut project_item_ids_to_save = HashSet::default();
+        items_to_close
+            .iter()
+            .filter(|item| {
+                item.is_dirty(cx)
+                    && item
+                        .project_item_model_ids(cx)
+                        .into_iter()
+                        .filter(|id| !other_project_item_ids.contains(id))
+                        .any(|id| project_item_ids_to_save.insert(id))
+            })
+            .map(|item| item.boxed_clone())
+            .collect()
     }
 
     pub fn close_items(
         &mut self,
         cx: &mut ViewContext<Pane>,
-        mut save_intent: SaveIntent,
+        save_intent: SaveIntent,
         should_close: impl Fn(EntityId) -> bool,
     ) -> Task<Result<()>> {
         // Find the items to close.
         let mut items_to_close = Vec::new();
-        let mut dirty_items = Vec::new();
         for item in &self.items {
             if should_close(item.item_id()) {
                 items_to_close.push(item.boxed_clone());
-                if item.is_dirty(cx) {
-                    dirty_items.push(item.boxed_clone());
-                }
             }
         }
 
@@ -929,21 +1021,21 @@ impl Pane {
 
         let workspace = self.workspace.clone();
         cx.spawn(|pane, mut cx| async move {
-            if save_intent == SaveIntent::Close && dirty_items.len() > 1 {
-                let answer = pane.update(&mut cx, |_, cx| {
-                    let (prompt, detail) =
-                        Self::file_names_for_prompt(&mut dirty_items.iter(), dirty_items.len(), cx);
-                    cx.prompt(
-                        PromptLevel::Warning,
-                        &prompt,
-                        Some(&detail),
-                        &["Save all", "Discard all", "Cancel"],
-                    )
+            // When several items need saving, ask about all of them at once
+            // instead of prompting for each item in turn.
+            let mut item_ids_to_save = None;
+            if save_intent == SaveIntent::Close {
+                let dirty_items = workspace.update(&mut cx, |workspace, cx| {
+                    Self::dirty_items_to_save(&items_to_close, workspace, cx)
                 })?;
-                match answer.await {
-                    Ok(0) => save_intent = SaveIntent::SaveAll,
-                    Ok(1) => save_intent = SaveIntent::Skip,
-                    _ => {}
+                if dirty_items.len() > 1 {
+                    let answer = workspace.update(&mut cx, |workspace, cx| {
+                        UnsavedChangesModal::prompt(workspace, &dirty_items, cx)
+                    })?;
+                    match answer.await.ok().flatten() {
+                        Some(item_ids) => item_ids_to_save = Some(item_ids),
+                        None => return Ok(()),
+                    }
                 }
             }
             let mut saved_project_items_ids = HashSet::default();
@@ -979,13 +1071,18 @@ impl Pane {
                     .iter()
                     .any(|id| saved_project_items_ids.insert(*id));
 
+                let item_save_intent = match &item_ids_to_save {
+                    Some(item_ids) if item_ids.contains(&item.item_id()) => SaveIntent::SaveAll,
+                    Some(_) => SaveIntent::Skip,
+                    None => save_intent,
+                };
                 if should_save
                     && !Self::save_item(
                         project.clone(),
                         &pane,
                         item_ix,
                         &*item,
-                        save_intent,
+                        item_save_intent,
                         &mut cx,
                     )
                     .await?
@@ -993,6 +1090,12 @@ impl Pane {
                     break;
                 }
 
+                // Give the item a chance to veto being closed, e.g. because it
+                // is running a process the user may not want to kill.
+                if !pane.update(&mut cx, |_, cx| item.confirm_close(cx))?.await {
+                    break;
+                }
+
                 // Remove the item from the pane.
                 pane.update(&mut cx, |pane, cx| {
                     if let Some(item_ix) = pane
@@ -1074,6 +1177,18 @@ impl Pane {
                 .lock()
                 .paths_by_item
                 .insert(item.item_id(), (path, abs_path));
+
+            let details = ClosedItemDetails {
+                kind: item
+                    .to_serializable_item_handle(cx)
+                    .map(|item| Arc::from(item.serialized_item_kind())),
+                position: item.restorable_position(cx),
+            };
+            self.nav_history
+                .0
+                .lock()
+                .closed_item_details
+                .insert(item.item_id(), details);
         } else {
             self.nav_history
                 .0
@@ -1147,7 +1262,7 @@ impl Pane {
             if save_intent == SaveIntent::Close {
                 let will_autosave = cx.update(|cx| {
                     matches!(
-                        WorkspaceSettings::get_global(cx).autosave,
+                        settings_for_item::<WorkspaceSettings>(&*item, cx).autosave,
                         AutosaveSetting::OnFocusChange | AutosaveSetting::OnWindowChange
                     ) && Self::can_autosave_item(&*item, cx)
                 })?;
@@ -1273,6 +1388,164 @@ impl Pane {
         });
     }
 
+    /// Avatars of the collaborators in the item, outlined in their player color.
+    fn render_tab_collaborators(
+        &self,
+        item: &dyn ItemHandle,
+        cx: &WindowContext,
+    ) -> Option<impl IntoElement> {
+        let peer_ids = item.present_collaborators(cx);
+        if peer_ids.is_empty() {
+            return None;
+        }
+
+        let project = self.project.read(cx);
+        let collaborators = project.collaborators();
+        let user_store = project.user_store().read(cx);
+        let participant_indices = user_store.participant_indices();
+        let avatars = peer_ids
+            .iter()
+            .filter_map(|peer_id| {
+                let collaborator = collaborators.get(peer_id)?;
+                let user = user_store.get_cached_user(collaborator.user_id)?;
+                let avatar = Avatar::new(user.avatar_uri.clone()).size(rems(0.875));
+                Some(match participant_indices.get(&collaborator.user_id) {
+                    Some(participant_index) => avatar.border_color(
+                        cx.theme()
+                            .players()
+                            .color_for_participant(participant_index.0)
+                            .cursor,
+                    ),
+                    None => avatar,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        (!avatars.is_empty()).then(|| h_flex().gap_0p5().children(avatars))
+    }
+
+    /// Moves keyboard focus to the tab bar, with the cursor on the active tab.
+    pub fn focus_tab_bar(&mut self, _: &FocusTabBar, cx: &mut ViewContext<Self>) {
+        if self.items.is_empty() {
+            return;
+        }
+        self.tab_cursor = self.active_item_index;
+        self.tab_bar_focus_handle.focus(cx);
+        cx.notify();
+    }
+
+    fn select_prev_tab(&mut self, _: &SelectPrevTab, cx: &mut ViewContext<Self>) {
+        if !self.items.is_empty() {
+            self.set_tab_cursor(
+                (self.tab_cursor + self.items.len() - 1) % self.items.len(),
+                cx,
+            );
+        }
+    }
+
+    fn select_next_tab(&mut self, _: &SelectNextTab, cx: &mut ViewContext<Self>) {
+        if !self.items.is_empty() {
+            self.set_tab_cursor((self.tab_cursor + 1) % self.items.len(), cx);
+        }
+    }
+
+    fn set_tab_cursor(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        self.tab_cursor = ix;
+        self.tab_bar_scroll_handle.scroll_to_item(ix);
+        cx.notify();
+    }
+
+    fn activate_tab_under_cursor(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        if self.tab_cursor < self.items.len() {
+            self.activate_item(self.tab_cursor, true, true, cx);
+        }
+    }
+
+    fn move_tab_left(&mut self, _: &MoveTabLeft, cx: &mut ViewContext<Self>) {
+        if self.tab_cursor > 0 && self.tab_cursor < self.items.len() {
+            self.swap_tabs(self.tab_cursor, self.tab_cursor - 1, cx);
+        }
+    }
+
+    fn move_tab_right(&mut self, _: &MoveTabRight, cx: &mut ViewContext<Self>) {
+        if self.tab_cursor + 1 < self.items.len() {
+            self.swap_tabs(self.tab_cursor, self.tab_cursor + 1, cx);
+        }
+    }
+
+    /// Horizontal swipes on the tab bar switch to the previous or next tab, as if the
+    /// tabs were pages being swiped through.
+    fn handle_tab_bar_swipe(&mut self, event: &SwipeEvent, cx: &mut ViewContext<Self>) {
+        match event.direction {
+            SwipeDirection::Right => self.activate_prev_item(true, cx),
+            SwipeDirection::Left => self.activate_next_item(true, cx),
+            SwipeDirection::Up | SwipeDirection::Down => return,
+        }
+        cx.stop_propagation();
+    }
+
+    /// Scrolling over the tab bar with shift held switches tabs, one tab per line scrolled.
+    fn handle_tab_bar_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if !event.modifiers.shift {
+            return;
+        }
+        cx.stop_propagation();
+        if matches!(event.touch_phase, TouchPhase::Started) {
+            self.tab_bar_scroll_remainder = 0.;
+        }
+        let line_height = cx.line_height();
+        let delta = event.delta.pixel_delta(line_height);
+        let delta = if delta.x.abs() > delta.y.abs() {
+            delta.x
+        } else {
+            delta.y
+        };
+        self.tab_bar_scroll_remainder += delta / line_height;
+        while self.tab_bar_scroll_remainder >= 1. {
+            self.tab_bar_scroll_remainder -= 1.;
+            self.activate_prev_item(true, cx);
+        }
+        while self.tab_bar_scroll_remainder <= -1. {
+            self.tab_bar_scroll_remainder += 1.;
+            self.activate_next_item(true, cx);
+        }
+    }
+
+    /// Swaps the tab under the cursor with its neighbor at `ix`, keeping the
+    /// cursor and the active item on the tabs they were on.
+    fn swap_tabs(&mut self, cursor: usize, ix: usize, cx: &mut ViewContext<Self>) {
+        self.items.swap(cursor, ix);
+        if self.active_item_index == cursor {
+            self.active_item_index = ix;
+        } else if self.active_item_index == ix {
+            self.active_item_index = cursor;
+        }
+        self.set_tab_cursor(ix, cx);
+    }
+
+    /// Runs the action bound to a mouse button in the `mouse_bindings.tab` setting, after
+    /// activating the clicked tab so that the action applies to it.
+    fn dispatch_tab_mouse_binding(
+        &mut self,
+        ix: usize,
+        event: &MouseDownEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let bindings = &WorkspaceSettings::get_global(cx).mouse_bindings.tab;
+        let Some(action) = MouseBindings::action_for(bindings, event.button, &event.modifiers, cx)
+        else {
+            return;
+        };
+        cx.stop_propagation();
+        self.activate_item(ix, true, false, cx);
+        self.focus(cx);
+        cx.dispatch_action(action);
+    }
+
     fn render_tab(
         &self,
         ix: usize,
@@ -1283,7 +1556,10 @@ impl Pane {
         let is_active = ix == self.active_item_index;
 
         let label = item.tab_content(Some(detail), is_active, cx);
-        let close_side = &ItemSettings::get_global(cx).close_position;
+        let icon = item.tab_icon(cx);
+        let item_settings = settings_for_item::<ItemSettings>(item.as_ref(), cx);
+        let close_side = item_settings.close_position.clone();
+        let hover_preview = item_settings.hover_preview;
 
         let indicator = maybe!({
             let indicator_color = match (item.has_conflict(cx), item.is_dirty(cx)) {
@@ -1316,6 +1592,9 @@ impl Pane {
             .on_click(
                 cx.listener(move |pane: &mut Self, _, cx| pane.activate_item(ix, true, true, cx)),
             )
+            .on_any_mouse_down(
+                cx.listener(move |pane, event, cx| pane.dispatch_tab_mouse_binding(ix, event, cx)),
+            )
             // TODO: This should be a click listener with the middle mouse button instead of a mouse down listener.
             .on_mouse_down(
                 MouseButton::Middle,
@@ -1334,12 +1613,32 @@ impl Pane {
                 },
                 |tab, cx| cx.new_view(|_| tab.clone()),
             )
+            .group("tab")
+            .child(Self::render_drop_caret("tab", cx))
+            .when(
+                ix == self.tab_cursor && self.tab_bar_focus_handle.is_focused(cx),
+                |tab| {
+                    tab.child(
+                        div()
+                            .absolute()
+                            .inset_0()
+                            .border_1()
+                            .border_color(cx.theme().colors().border_focused),
+                    )
+                },
+            )
             .drag_over::<DraggedTab>(|tab, _, cx| {
                 tab.bg(cx.theme().colors().drop_target_background)
             })
             .drag_over::<ProjectEntryId>(|tab, _, cx| {
                 tab.bg(cx.theme().colors().drop_target_background)
             })
+            .drag_over::<ExternalPaths>(|tab, _, cx| {
+                tab.bg(cx.theme().colors().drop_target_background)
+            })
+            .drag_over::<ExternalText>(|tab, _, cx| {
+                tab.bg(cx.theme().colors().drop_target_background)
+            })
             .when_some(self.can_drop_predicate.clone(), |this, p| {
                 this.can_drop(move |a, cx| p(a, cx))
             })
@@ -1349,14 +1648,28 @@ impl Pane {
             }))
             .on_drop(cx.listener(move |this, entry_id: &ProjectEntryId, cx| {
                 this.drag_split_direction = None;
-                this.handle_project_entry_drop(entry_id, cx)
+                this.handle_project_entry_drop(entry_id, Some(ix), cx)
             }))
             .on_drop(cx.listener(move |this, paths, cx| {
                 this.drag_split_direction = None;
-                this.handle_external_paths_drop(paths, cx)
+                this.handle_external_paths_drop(paths, Some(ix), cx)
+            }))
+            .on_drop(cx.listener(move |this, text: &ExternalText, cx| {
+                this.drag_split_direction = None;
+                this.handle_external_text_drop(text, ix, cx)
             }))
-            .when_some(item.tab_tooltip_text(cx), |tab, text| {
-                tab.tooltip(move |cx| Tooltip::text(text.clone(), cx))
+            .map(|tab| {
+                if hover_preview {
+                    let item = item.boxed_clone();
+                    tab.tooltip(move |cx| {
+                        let item = item.boxed_clone();
+                        cx.new_view(|_| TabPreview { item }).into()
+                    })
+                } else if let Some(text) = item.tab_tooltip_text(cx) {
+                    tab.tooltip(move |cx| Tooltip::text(text.clone(), cx))
+                } else {
+                    tab
+                }
             })
             .start_slot::<Indicator>(indicator)
             .end_slot(
@@ -1370,7 +1683,13 @@ impl Pane {
                             .detach_and_log_err(cx);
                     })),
             )
-            .child(label);
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(icon)
+                    .child(label)
+                    .children(self.render_tab_collaborators(item.as_ref(), cx)),
+            );
 
         let single_entry_to_resolve = {
             let item_entries = self.items[ix].project_entry_ids(cx);
@@ -1460,7 +1779,7 @@ impl Pane {
     }
 
     fn render_tab_bar(&mut self, cx: &mut ViewContext<'_, Pane>) -> impl IntoElement {
-        TabBar::new("tab_bar")
+        let tab_bar = TabBar::new("tab_bar")
             .track_scroll(self.tab_bar_scroll_handle.clone())
             .when(self.display_nav_history_buttons, |tab_bar| {
                 tab_bar.start_child(
@@ -1503,10 +1822,13 @@ impl Pane {
             )
             .child(
                 div()
+                    .group("tab_bar_drop_target")
+                    .relative()
                     .min_w_6()
                     // HACK: This empty child is currently necessary to force the drop target to appear
                     // despite us setting a min width above.
                     .child("")
+                    .child(Self::render_drop_caret("tab_bar_drop_target", cx))
                     .h_full()
                     .flex_grow()
                     .drag_over::<DraggedTab>(|bar, _, cx| {
@@ -1515,19 +1837,61 @@ impl Pane {
                     .drag_over::<ProjectEntryId>(|bar, _, cx| {
                         bar.bg(cx.theme().colors().drop_target_background)
                     })
+                    .drag_over::<ExternalPaths>(|bar, _, cx| {
+                        bar.bg(cx.theme().colors().drop_target_background)
+                    })
+                    .drag_over::<ExternalText>(|bar, _, cx| {
+                        bar.bg(cx.theme().colors().drop_target_background)
+                    })
                     .on_drop(cx.listener(move |this, dragged_tab: &DraggedTab, cx| {
                         this.drag_split_direction = None;
                         this.handle_tab_drop(dragged_tab, this.items.len(), cx)
                     }))
                     .on_drop(cx.listener(move |this, entry_id: &ProjectEntryId, cx| {
                         this.drag_split_direction = None;
-                        this.handle_project_entry_drop(entry_id, cx)
+                        this.handle_project_entry_drop(entry_id, Some(this.items.len()), cx)
                     }))
                     .on_drop(cx.listener(move |this, paths, cx| {
                         this.drag_split_direction = None;
-                        this.handle_external_paths_drop(paths, cx)
+                        this.handle_external_paths_drop(paths, Some(this.items.len()), cx)
+                    }))
+                    .on_drop(cx.listener(move |this, text: &ExternalText, cx| {
+                        this.drag_split_direction = None;
+                        this.handle_external_text_drop(text, this.items.len(), cx)
                     })),
-            )
+            );
+
+        div()
+            .key_context("TabBar")
+            .track_focus(&self.tab_bar_focus_handle)
+            .w_full()
+            .on_swipe(cx.listener(Self::handle_tab_bar_swipe))
+            .on_scroll_wheel(cx.listener(Self::handle_tab_bar_scroll_wheel))
+            .on_action(cx.listener(Self::select_prev_tab))
+            .on_action(cx.listener(Self::select_next_tab))
+            .on_action(cx.listener(Self::move_tab_left))
+            .on_action(cx.listener(Self::move_tab_right))
+            .on_action(cx.listener(Self::activate_tab_under_cursor))
+            .on_action(cx.listener(|pane, _: &menu::Cancel, cx| pane.focus_active_item(cx)))
+            .child(tab_bar)
+    }
+
+    /// A caret marking where something dragged over the tab bar will be inserted, at the
+    /// start of the given group's element. It's only visible while dragging over that group.
+    fn render_drop_caret(group: &'static str, cx: &WindowContext) -> Div {
+        div()
+            .absolute()
+            .top_0()
+            .bottom_0()
+            .left_0()
+            .w(px(2.))
+            .z_index(1)
+            .bg(cx.theme().colors().text_accent)
+            .invisible()
+            .group_drag_over::<DraggedTab>(group, |style| style.visible())
+            .group_drag_over::<ProjectEntryId>(group, |style| style.visible())
+            .group_drag_over::<ExternalPaths>(group, |style| style.visible())
+            .group_drag_over::<ExternalText>(group, |style| style.visible())
     }
 
     fn render_menu_overlay(menu: &View<ContextMenu>) -> Div {
@@ -1637,9 +2001,12 @@ impl Pane {
             .log_err();
     }
 
+    /// Opens a file dragged from the project panel in this pane, or in a split of it
+    /// when dropped near an edge, inserting it at `ix` when dropped onto the tab bar.
     fn handle_project_entry_drop(
         &mut self,
         project_entry_id: &ProjectEntryId,
+        ix: Option<usize>,
         cx: &mut ViewContext<'_, Self>,
     ) {
         if let Some(custom_drop_handle) = self.custom_drop_handle.clone() {
@@ -1653,26 +2020,72 @@ impl Pane {
         self.workspace
             .update(cx, |_, cx| {
                 cx.defer(move |workspace, cx| {
-                    if let Some(path) = workspace
-                        .project()
-                        .read(cx)
-                        .path_for_entry(project_entry_id, cx)
-                    {
+                    let project = workspace.project().read(cx);
+                    let path = project.path_for_entry(project_entry_id, cx).filter(|path| {
+                        // Directories can't be opened as items.
+                        project
+                            .entry_for_path(path, cx)
+                            .is_some_and(|entry| entry.is_file())
+                    });
+                    if let Some(path) = path {
                         if let Some(split_direction) = split_direction {
                             to_pane = workspace.split_pane(to_pane, split_direction, cx);
                         }
-                        workspace
-                            .open_path(path, Some(to_pane.downgrade()), true, cx)
-                            .detach_and_log_err(cx);
+                        let open_task =
+                            workspace.open_path(path, Some(to_pane.downgrade()), true, cx);
+                        cx.spawn(|workspace, mut cx| async move {
+                            let item = open_task.await?;
+                            if let Some(ix) = ix {
+                                workspace.update(&mut cx, |workspace, cx| {
+                                    workspace.move_item(
+                                        to_pane.clone(),
+                                        to_pane,
+                                        item.item_id(),
+                                        ix,
+                                        cx,
+                                    );
+                                })?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach_and_log_err(cx);
                     }
                 });
             })
             .log_err();
     }
 
+    /// Creates an untitled item containing text dropped onto the tab bar, inserting it at `ix`.
+    fn handle_external_text_drop(
+        &mut self,
+        text: &ExternalText,
+        ix: usize,
+        cx: &mut ViewContext<'_, Self>,
+    ) {
+        if let Some(custom_drop_handle) = self.custom_drop_handle.clone() {
+            if let ControlFlow::Break(()) = custom_drop_handle(self, text, cx) {
+                return;
+            }
+        }
+        let to_pane = cx.view().clone();
+        let text = text.text().clone();
+        self.workspace
+            .update(cx, |_, cx| {
+                cx.defer(move |workspace, cx| {
+                    workspace
+                        .add_untitled_item(&text, &to_pane, Some(ix), cx)
+                        .log_err();
+                });
+            })
+            .log_err();
+    }
+
+    /// Opens files dropped from outside of Zed in this pane, inserting them at `ix`
+    /// when dropped onto the tab bar. See [`Workspace::open_dropped_paths`].
     fn handle_external_paths_drop(
         &mut self,
         paths: &ExternalPaths,
+        ix: Option<usize>,
         cx: &mut ViewContext<'_, Self>,
     ) {
         if let Some(custom_drop_handle) = self.custom_drop_handle.clone() {
@@ -1703,21 +2116,35 @@ impl Pane {
                         split_direction = None;
                     }
 
-                    if let Some(open_task) = workspace
+                    if let Some((open_task, to_pane)) = workspace
                         .update(&mut cx, |workspace, cx| {
                             if let Some(split_direction) = split_direction {
                                 to_pane = workspace.split_pane(to_pane, split_direction, cx);
                             }
-                            workspace.open_paths(
-                                paths,
-                                OpenVisible::OnlyDirectories,
-                                Some(to_pane.downgrade()),
-                                cx,
-                            )
+                            let open_task =
+                                workspace.open_dropped_paths(paths, Some(to_pane.downgrade()), cx);
+                            (open_task, to_pane)
                         })
                         .ok()
                     {
-                        let _opened_items: Vec<_> = open_task.await;
+                        let opened_items: Vec<_> = open_task.await;
+                        if let Some(ix) = ix {
+                            workspace
+                                .update(&mut cx, |workspace, cx| {
+                                    let opened_items =
+                                        opened_items.into_iter().flatten().filter_map(Result::ok);
+                                    for (offset, item) in opened_items.enumerate() {
+                                        workspace.move_item(
+                                            to_pane.clone(),
+                                            to_pane.clone(),
+                                            item.item_id(),
+                                            ix + offset,
+                                            cx,
+                                        );
+                                    }
+                                })
+                                .ok();
+                        }
                     }
                 })
                 .detach();
@@ -1728,6 +2155,46 @@ impl Pane {
     pub fn display_nav_history_buttons(&mut self, display: bool) {
         self.display_nav_history_buttons = display;
     }
+
+    /// Marks the pane as focused or not, depending on the `active_pane_indicator` setting.
+    /// Neither overlay blocks the mouse, so a dimmed pane can still be clicked to focus it.
+    fn render_active_pane_indicator(&self, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
+        let indicator = WorkspaceSettings::get_global(cx).active_pane_indicator;
+        let intensity = indicator.intensity.clamp(0., 1.);
+        let overlay = match indicator.style {
+            ActivePaneIndicatorStyle::None => return None,
+            ActivePaneIndicatorStyle::Border => {
+                if !self.has_focus(cx) {
+                    return None;
+                }
+                let color = cx.theme().colors().pane_focused_border;
+                div()
+                    .size_full()
+                    .border_2()
+                    .border_color(theme::color_alpha(color, intensity))
+                    .into_any_element()
+            }
+            ActivePaneIndicatorStyle::Dim => {
+                if self.has_focus(cx) {
+                    return None;
+                }
+                let color = theme::color_alpha(cx.theme().colors().editor_background, intensity);
+                canvas(move |bounds, cx| cx.paint_quad(fill(*bounds, color)))
+                    .size_full()
+                    .into_any_element()
+            }
+        };
+        Some(
+            div()
+                .absolute()
+                .top_0()
+                .left_0()
+                .size_full()
+                .z_index(2)
+                .child(overlay)
+                .into_any_element(),
+        )
+    }
 }
 
 impl FocusableView for Pane {
@@ -1741,6 +2208,7 @@ impl Render for Pane {
         v_flex()
             .key_context("Pane")
             .track_focus(&self.focus_handle)
+            .relative()
             .size_full()
             .flex_none()
             .overflow_hidden()
@@ -1750,6 +2218,7 @@ impl Render for Pane {
                 cx.listener(|pane, _: &SplitRight, cx| pane.split(SplitDirection::Right, cx)),
             )
             .on_action(cx.listener(|pane, _: &SplitDown, cx| pane.split(SplitDirection::Down, cx)))
+            .on_action(cx.listener(|_, _: &MoveToNewWindow, cx| cx.emit(Event::MoveToNewWindow)))
             .on_action(cx.listener(|pane, _: &GoBack, cx| pane.navigate_backward(cx)))
             .on_action(cx.listener(|pane, _: &GoForward, cx| pane.navigate_forward(cx)))
             .on_action(cx.listener(Pane::toggle_zoom))
@@ -1765,6 +2234,10 @@ impl Render for Pane {
             .on_action(cx.listener(|pane: &mut Pane, _: &ActivateNextItem, cx| {
                 pane.activate_next_item(true, cx);
             }))
+            .on_action(cx.listener(|pane: &mut Pane, _: &AlternateFile, cx| {
+                pane.activate_alternate_item(cx);
+            }))
+            .on_action(cx.listener(Self::focus_tab_bar))
             .on_action(
                 cx.listener(|pane: &mut Self, action: &CloseActiveItem, cx| {
                     pane.close_active_item(action, cx)
@@ -1864,26 +2337,28 @@ impl Render for Pane {
                                 this.handle_tab_drop(dragged_tab, this.active_item_index(), cx)
                             }))
                             .on_drop(cx.listener(move |this, entry_id, cx| {
-                                this.handle_project_entry_drop(entry_id, cx)
+                                this.handle_project_entry_drop(entry_id, None, cx)
                             }))
                             .on_drop(cx.listener(move |this, paths, cx| {
-                                this.handle_external_paths_drop(paths, cx)
+                                this.handle_external_paths_drop(paths, None, cx)
                             }))
+                            // Highlight the half of the pane that a split would occupy.
                             .map(|div| match self.drag_split_direction {
                                 None => div.top_0().left_0().right_0().bottom_0(),
-                                Some(SplitDirection::Up) => div.top_0().left_0().right_0().h_32(),
+                                Some(SplitDirection::Up) => div.top_0().left_0().right_0().h_1_2(),
                                 Some(SplitDirection::Down) => {
-                                    div.left_0().bottom_0().right_0().h_32()
+                                    div.left_0().bottom_0().right_0().h_1_2()
                                 }
                                 Some(SplitDirection::Left) => {
-                                    div.top_0().left_0().bottom_0().w_32()
+                                    div.top_0().left_0().bottom_0().w_1_2()
                                 }
                                 Some(SplitDirection::Right) => {
-                                    div.top_0().bottom_0().right_0().w_32()
+                                    div.top_0().bottom_0().right_0().w_1_2()
                                 }
                             }),
                     )
             })
+            .children(self.render_active_pane_indicator(cx))
             .on_mouse_down(
                 MouseButton::Navigate(NavigationDirection::Back),
                 cx.listener(|pane, _, cx| {
@@ -1913,7 +2388,41 @@ impl Render for Pane {
     }
 }
 
+/// The tooltip shown when hovering a tab while `tabs.hover_preview` is enabled.
+struct TabPreview {
+    item: Box<dyn ItemHandle>,
+}
+
+impl Render for TabPreview {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let title = self.item.tab_tooltip_text(cx);
+        let preview = self.item.tab_preview(cx);
+        if title.is_none() && preview.is_none() {
+            return div().into_any_element();
+        }
+        tooltip_container(cx, |container, cx| {
+            container
+                .gap_1()
+                .max_w(rems(36.))
+                .children(title)
+                .when_some(preview, |container, preview| {
+                    container.child(
+                        div()
+                            .pt_1()
+                            .border_t_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .overflow_hidden()
+                            .child(preview),
+                    )
+                })
+        })
+        .into_any_element()
+    }
+}
+
 impl ItemNavHistory {
+    /// Records a position in the item. Pushing while navigating places the entry
+    /// on the opposite stack, so that the position being left can be returned to.
     pub fn push<D: 'static + Send + Any>(&mut self, data: Option<D>, cx: &mut WindowContext) {
         self.history.push(data, self.item.clone(), cx);
     }
@@ -1998,12 +2507,14 @@ impl NavHistory {
                 if state.backward_stack.len() >= MAX_NAVIGATION_HISTORY_LEN {
                     state.backward_stack.pop_front();
                 }
+                let timestamp = state.next_timestamp.fetch_add(1, Ordering::SeqCst);
                 state.backward_stack.push_back(NavigationEntry {
                     item,
                     data: data.map(|data| Box::new(data) as Box<dyn Any + Send>),
-                    timestamp: state.next_timestamp.fetch_add(1, Ordering::SeqCst),
+                    timestamp,
                 });
                 state.forward_stack.clear();
+                state.last_visit_timestamp = Some(timestamp);
             }
             NavigationMode::GoingBack => {
                 if state.forward_stack.len() >= MAX_NAVIGATION_HISTORY_LEN {
@@ -2042,6 +2553,7 @@ impl NavHistory {
     pub fn remove_item(&mut self, item_id: EntityId) {
         let mut state = self.0.lock();
         state.paths_by_item.remove(&item_id);
+        state.closed_item_details.remove(&item_id);
         state
             .backward_stack
             .retain(|entry| entry.item.id() != item_id);
@@ -2056,6 +2568,48 @@ impl NavHistory {
     pub fn path_for_item(&self, item_id: EntityId) -> Option<(ProjectPath, Option<PathBuf>)> {
         self.0.lock().paths_by_item.get(&item_id).cloned()
     }
+
+    /// The timestamp of the entry that navigating in the given direction would pop next.
+    pub(crate) fn next_entry_timestamp(&self, mode: NavigationMode) -> Option<usize> {
+        let state = self.0.lock();
+        let entry = match mode {
+            NavigationMode::GoingBack => state.backward_stack.back(),
+            NavigationMode::GoingForward => state.forward_stack.back(),
+            _ => None,
+        };
+        entry.map(|entry| entry.timestamp)
+    }
+
+    pub(crate) fn last_visit_timestamp(&self) -> Option<usize> {
+        self.0.lock().last_visit_timestamp
+    }
+
+    pub fn has_closed_items(&self) -> bool {
+        !self.0.lock().closed_stack.is_empty()
+    }
+
+    /// Returns the closed items that can be reopened from their absolute path, along
+    /// with the timestamp they were closed at, most recently closed first.
+    pub(crate) fn closed_items(&self) -> Vec<(usize, SerializedClosedItem)> {
+        let state = self.0.lock();
+        state
+            .closed_stack
+            .iter()
+            .rev()
+            .filter_map(|entry| {
+                let (_, abs_path) = state.paths_by_item.get(&entry.item.id())?;
+                let details = state.closed_item_details.get(&entry.item.id());
+                Some((
+                    entry.timestamp,
+                    SerializedClosedItem {
+                        kind: details.and_then(|details| details.kind.clone()),
+                        abs_path: abs_path.clone()?,
+                        position: details.and_then(|details| details.position),
+                    },
+                ))
+            })
+            .collect()
+    }
 }
 
 impl NavHistoryState {
@@ -2080,10 +2634,14 @@ fn dirty_message_for(buffer_path: Option<ProjectPath>) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::item::test::{TestItem, TestProjectItem};
-    use gpui::{TestAppContext, VisualTestContext};
+    use crate::item::{
+        test::{TestItem, TestProjectItem},
+        ItemEvent,
+    };
+    use gpui::{point, Modifiers, ScrollDelta, TestAppContext, VisualTestContext};
     use project::FakeFs;
     use settings::SettingsStore;
+    use std::cell::Cell;
     use theme::LoadThemes;
 
     #[gpui::test]
@@ -2354,7 +2912,7 @@ mod tests {
     }
 
     #[gpui::test]
-    async fn test_remove_item_ordering(cx: &mut TestAppContext) {
+    async fn test_alternate_item(cx: &mut TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.executor());
 
@@ -2365,51 +2923,26 @@ mod tests {
         add_labeled_item(&pane, "A", false, cx);
         add_labeled_item(&pane, "B", false, cx);
         add_labeled_item(&pane, "C", false, cx);
-        add_labeled_item(&pane, "D", false, cx);
-        assert_item_labels(&pane, ["A", "B", "C", "D*"], cx);
-
-        pane.update(cx, |pane, cx| pane.activate_item(1, false, false, cx));
-        add_labeled_item(&pane, "1", false, cx);
-        assert_item_labels(&pane, ["A", "B", "1*", "C", "D"], cx);
-
-        pane.update(cx, |pane, cx| {
-            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
-        })
-        .unwrap()
-        .await
-        .unwrap();
-        assert_item_labels(&pane, ["A", "B*", "C", "D"], cx);
-
-        pane.update(cx, |pane, cx| pane.activate_item(3, false, false, cx));
-        assert_item_labels(&pane, ["A", "B", "C", "D*"], cx);
-
-        pane.update(cx, |pane, cx| {
-            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
-        })
-        .unwrap()
-        .await
-        .unwrap();
-        assert_item_labels(&pane, ["A", "B*", "C"], cx);
+        pane.update(cx, |pane, cx| pane.activate_item(0, false, false, cx));
+        assert_item_labels(&pane, ["A*", "B", "C"], cx);
 
-        pane.update(cx, |pane, cx| {
-            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
-        })
-        .unwrap()
-        .await
-        .unwrap();
-        assert_item_labels(&pane, ["A", "C*"], cx);
+        pane.update(cx, |pane, cx| pane.activate_alternate_item(cx));
+        assert_item_labels(&pane, ["A", "B", "C*"], cx);
+        pane.update(cx, |pane, cx| pane.activate_alternate_item(cx));
+        assert_item_labels(&pane, ["A*", "B", "C"], cx);
 
+        // Closing the alternate item falls back to the one active before it.
         pane.update(cx, |pane, cx| {
-            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
+            pane.close_item_by_id(pane.items[2].item_id(), SaveIntent::Skip, cx)
         })
-        .unwrap()
         .await
         .unwrap();
-        assert_item_labels(&pane, ["A*"], cx);
+        pane.update(cx, |pane, cx| pane.activate_alternate_item(cx));
+        assert_item_labels(&pane, ["A", "B*"], cx);
     }
 
     #[gpui::test]
-    async fn test_close_inactive_items(cx: &mut TestAppContext) {
+    async fn test_tab_bar_keyboard_navigation(cx: &mut TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.executor());
 
@@ -2417,10 +2950,159 @@ mod tests {
         let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
         let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
 
-        set_labeled_items(&pane, ["A", "B", "C*", "D", "E"], cx);
-
-        pane.update(cx, |pane, cx| {
-            pane.close_inactive_items(&CloseInactiveItems, cx)
+        add_labeled_item(&pane, "A", false, cx);
+        add_labeled_item(&pane, "B", false, cx);
+        add_labeled_item(&pane, "C", false, cx);
+        assert_item_labels(&pane, ["A", "B", "C*"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.focus_tab_bar(&FocusTabBar, cx);
+            assert_eq!(pane.tab_cursor, 2);
+            pane.select_next_tab(&SelectNextTab, cx);
+            assert_eq!(pane.tab_cursor, 0);
+            pane.select_prev_tab(&SelectPrevTab, cx);
+            pane.select_prev_tab(&SelectPrevTab, cx);
+            assert_eq!(pane.tab_cursor, 1);
+        });
+
+        // Moving a tab keeps the cursor on it, and the active item where it was.
+        pane.update(cx, |pane, cx| pane.move_tab_left(&MoveTabLeft, cx));
+        assert_item_labels(&pane, ["B", "A", "C*"], cx);
+        pane.update(cx, |pane, cx| pane.move_tab_left(&MoveTabLeft, cx));
+        assert_item_labels(&pane, ["B", "A", "C*"], cx);
+        pane.update(cx, |pane, cx| {
+            pane.select_next_tab(&SelectNextTab, cx);
+            pane.select_next_tab(&SelectNextTab, cx);
+            pane.move_tab_left(&MoveTabLeft, cx);
+        });
+        assert_item_labels(&pane, ["B", "C*", "A"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.select_next_tab(&SelectNextTab, cx);
+            pane.activate_tab_under_cursor(&menu::Confirm, cx);
+        });
+        assert_item_labels(&pane, ["B", "C", "A*"], cx);
+    }
+
+    #[gpui::test]
+    async fn test_tab_bar_gestures(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        add_labeled_item(&pane, "A", false, cx);
+        add_labeled_item(&pane, "B", false, cx);
+        add_labeled_item(&pane, "C", false, cx);
+        assert_item_labels(&pane, ["A", "B", "C*"], cx);
+
+        let swipe = |direction| SwipeEvent {
+            direction,
+            ..Default::default()
+        };
+        pane.update(cx, |pane, cx| {
+            pane.handle_tab_bar_swipe(&swipe(SwipeDirection::Right), cx)
+        });
+        assert_item_labels(&pane, ["A", "B*", "C"], cx);
+        pane.update(cx, |pane, cx| {
+            pane.handle_tab_bar_swipe(&swipe(SwipeDirection::Left), cx)
+        });
+        assert_item_labels(&pane, ["A", "B", "C*"], cx);
+
+        // Only scrolling with shift held switches tabs, once per line scrolled.
+        let scroll = |lines, shift| ScrollWheelEvent {
+            delta: ScrollDelta::Lines(point(0., lines)),
+            modifiers: Modifiers {
+                shift,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        pane.update(cx, |pane, cx| {
+            pane.handle_tab_bar_scroll_wheel(&scroll(2., false), cx)
+        });
+        assert_item_labels(&pane, ["A", "B", "C*"], cx);
+        pane.update(cx, |pane, cx| {
+            pane.handle_tab_bar_scroll_wheel(&scroll(0.5, true), cx);
+            pane.handle_tab_bar_scroll_wheel(&scroll(0.5, true), cx);
+        });
+        assert_item_labels(&pane, ["A", "B*", "C"], cx);
+        pane.update(cx, |pane, cx| {
+            pane.handle_tab_bar_scroll_wheel(&scroll(-2., true), cx)
+        });
+        assert_item_labels(&pane, ["A*", "B", "C"], cx);
+    }
+
+    #[gpui::test]
+    async fn test_remove_item_ordering(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        add_labeled_item(&pane, "A", false, cx);
+        add_labeled_item(&pane, "B", false, cx);
+        add_labeled_item(&pane, "C", false, cx);
+        add_labeled_item(&pane, "D", false, cx);
+        assert_item_labels(&pane, ["A", "B", "C", "D*"], cx);
+
+        pane.update(cx, |pane, cx| pane.activate_item(1, false, false, cx));
+        add_labeled_item(&pane, "1", false, cx);
+        assert_item_labels(&pane, ["A", "B", "1*", "C", "D"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["A", "B*", "C", "D"], cx);
+
+        pane.update(cx, |pane, cx| pane.activate_item(3, false, false, cx));
+        assert_item_labels(&pane, ["A", "B", "C", "D*"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["A", "B*", "C"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["A", "C*"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.close_active_item(&CloseActiveItem { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["A*"], cx);
+    }
+
+    #[gpui::test]
+    async fn test_close_inactive_items(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        set_labeled_items(&pane, ["A", "B", "C*", "D", "E"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.close_inactive_items(&CloseInactiveItems, cx)
         })
         .unwrap()
         .await
@@ -2524,12 +3206,471 @@ mod tests {
             })
             .unwrap();
 
+        // None of the items have project items, so there's nothing to save.
         cx.executor().run_until_parked();
-        cx.simulate_prompt_answer(2);
+        assert!(!cx.has_pending_prompt());
         save.await.unwrap();
         assert_item_labels(&pane, [], cx);
     }
 
+    #[gpui::test]
+    async fn test_hidden_pane_is_suspended(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let item = add_labeled_item(&pane, "A", false, cx);
+
+        let notify_count = Rc::new(Cell::new(0));
+        let _subscription = workspace.update(cx, |_, cx| {
+            let notify_count = notify_count.clone();
+            cx.observe(&pane, move |_, _, _| {
+                notify_count.set(notify_count.get() + 1)
+            })
+        });
+
+        // While hidden, tab changes don't re-render the pane...
+        pane.update(cx, |pane, cx| pane.set_visible(false, cx));
+        item.update(cx, |_, cx| cx.emit(ItemEvent::UpdateTab));
+        item.update(cx, |_, cx| cx.emit(ItemEvent::UpdateTabIcon));
+        assert_eq!(notify_count.get(), 0);
+
+        // ...but it catches up once when it's shown again.
+        pane.update(cx, |pane, cx| pane.set_visible(true, cx));
+        assert_eq!(notify_count.get(), 1);
+        pane.update(cx, |pane, cx| pane.set_visible(false, cx));
+        pane.update(cx, |pane, cx| pane.set_visible(true, cx));
+        assert_eq!(notify_count.get(), 1);
+
+        item.update(cx, |_, cx| cx.emit(ItemEvent::UpdateTab));
+        assert_eq!(notify_count.get(), 2);
+    }
+
+    #[gpui::test]
+    async fn test_tab_icon_updates(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let item = add_labeled_item(&pane, "A", false, cx);
+
+        let notify_count = Rc::new(Cell::new(0));
+        let title_change_count = Rc::new(Cell::new(0));
+        let _subscriptions = workspace.update(cx, |_, cx| {
+            let notify_count = notify_count.clone();
+            let title_change_count = title_change_count.clone();
+            [
+                cx.observe(&pane, move |_, _, _| {
+                    notify_count.set(notify_count.get() + 1)
+                }),
+                cx.subscribe(&pane, move |_, _, event: &Event, _| {
+                    if let Event::ChangeItemTitle = event {
+                        title_change_count.set(title_change_count.get() + 1)
+                    }
+                }),
+            ]
+        });
+
+        // A new tab icon re-renders the pane without being reported as a title change.
+        item.update(cx, |_, cx| cx.emit(ItemEvent::UpdateTabIcon));
+        assert_eq!(notify_count.get(), 1);
+        assert_eq!(title_change_count.get(), 0);
+
+        item.update(cx, |_, cx| cx.emit(ItemEvent::UpdateTab));
+        assert_eq!(notify_count.get(), 2);
+        assert_eq!(title_change_count.get(), 1);
+    }
+
+    #[gpui::test]
+    async fn test_close_items_with_unsaved_changes(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let items = (0..3)
+            .map(|ix| {
+                let item = cx.new_view(|cx| {
+                    TestItem::new(cx)
+                        .with_dirty(true)
+                        .with_project_items(&[TestProjectItem::new(ix, &format!("{ix}.txt"), cx)])
+                });
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(Box::new(item.clone()), false, false, None, cx)
+                });
+                item
+            })
+            .collect::<Vec<_>>();
+
+        let close = pane
+            .update(cx, |pane, cx| {
+                pane.close_all_items(&CloseAllItems { save_intent: None }, cx)
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+
+        // A single modal lists every dirty item, all of them marked for saving.
+        assert!(!cx.has_pending_prompt());
+        let modal = workspace
+            .update(cx, |workspace, cx| {
+                workspace.active_modal::<UnsavedChangesModal>(cx)
+            })
+            .unwrap();
+
+        // Discard the changes to the second item and save the others.
+        modal.update(cx, |modal, cx| modal.toggle_item(1, cx));
+        cx.dispatch_action(menu::Confirm);
+        cx.executor().run_until_parked();
+        close.await.unwrap();
+
+        assert_item_labels(&pane, [], cx);
+        cx.read(|cx| {
+            let save_counts = items
+                .iter()
+                .map(|item| item.read(cx).save_count)
+                .collect::<Vec<_>>();
+            assert_eq!(save_counts, [1, 0, 1]);
+        });
+        assert!(workspace.update(cx, |workspace, cx| workspace
+            .active_modal::<UnsavedChangesModal>(cx)
+            .is_none()));
+    }
+
+    #[gpui::test]
+    async fn test_close_requests_while_unsaved_changes_modal_is_open(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let pane_1 = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let pane_2 = workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(pane_1.clone(), SplitDirection::Right, cx)
+        });
+
+        let mut items = Vec::new();
+        for (pane, offset) in [(&pane_1, 0), (&pane_2, 2)] {
+            for ix in offset..offset + 2 {
+                let item = cx.new_view(|cx| {
+                    TestItem::new(cx)
+                        .with_dirty(true)
+                        .with_project_items(&[TestProjectItem::new(ix, &format!("{ix}.txt"), cx)])
+                });
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(Box::new(item.clone()), false, false, None, cx)
+                });
+                items.push(item);
+            }
+        }
+
+        let close_1 = pane_1
+            .update(cx, |pane, cx| {
+                pane.close_all_items(&CloseAllItems { save_intent: None }, cx)
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        let modal_1 = workspace
+            .update(cx, |workspace, cx| {
+                workspace.active_modal::<UnsavedChangesModal>(cx)
+            })
+            .unwrap();
+
+        // The second request's prompt is shown on top of the first one instead of
+        // closing it.
+        let close_2 = pane_2
+            .update(cx, |pane, cx| {
+                pane.close_all_items(&CloseAllItems { save_intent: None }, cx)
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        let modal_2 = workspace
+            .update(cx, |workspace, cx| {
+                workspace.active_modal::<UnsavedChangesModal>(cx)
+            })
+            .unwrap();
+        assert_ne!(modal_1, modal_2);
+        modal_2.update(cx, |modal, _| {
+            assert_eq!(
+                modal.item_labels(),
+                [(None, "2.txt".into()), (None, "3.txt".into())]
+            );
+        });
+
+        cx.dispatch_action(menu::Confirm);
+        cx.executor().run_until_parked();
+        close_2.await.unwrap();
+        assert_item_labels(&pane_2, [], cx);
+
+        // Then the first prompt is answered.
+        assert_eq!(
+            workspace.update(cx, |workspace, cx| {
+                workspace.active_modal::<UnsavedChangesModal>(cx)
+            }),
+            Some(modal_1)
+        );
+        cx.dispatch_action(menu::Cancel);
+        cx.executor().run_until_parked();
+        close_1.await.unwrap();
+        pane_1.update(cx, |pane, _| assert_eq!(pane.items_len(), 2));
+
+        cx.read(|cx| {
+            let save_counts = items
+                .iter()
+                .map(|item| item.read(cx).save_count)
+                .collect::<Vec<_>>();
+            assert_eq!(save_counts, [0, 0, 1, 1]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_toggling_unsaved_items_with_the_keyboard(cx: &mut TestAppContext) {
+        use crate::unsaved_changes_modal::ToggleSelectedItem;
+
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let items = (0..3)
+            .map(|ix| {
+                let item = cx.new_view(|cx| {
+                    TestItem::new(cx)
+                        .with_dirty(true)
+                        .with_project_items(&[TestProjectItem::new(ix, &format!("{ix}.txt"), cx)])
+                });
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(Box::new(item.clone()), false, false, None, cx)
+                });
+                item
+            })
+            .collect::<Vec<_>>();
+
+        let close = pane
+            .update(cx, |pane, cx| {
+                pane.close_all_items(&CloseAllItems { save_intent: None }, cx)
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        let modal = workspace
+            .update(cx, |workspace, cx| {
+                workspace.active_modal::<UnsavedChangesModal>(cx)
+            })
+            .unwrap();
+
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(menu::SelectNext);
+        modal.update(cx, |modal, _| assert_eq!(modal.selected_index(), 2));
+        cx.dispatch_action(menu::SelectPrev);
+        cx.dispatch_action(ToggleSelectedItem);
+        modal.update(cx, |modal, _| {
+            assert_eq!(modal.items_to_save(), [true, false, true]);
+        });
+        cx.dispatch_action(menu::SelectFirst);
+        cx.dispatch_action(ToggleSelectedItem);
+        modal.update(cx, |modal, _| {
+            assert_eq!(modal.selected_index(), 0);
+            assert_eq!(modal.items_to_save(), [false, false, true]);
+        });
+
+        cx.dispatch_action(menu::Confirm);
+        cx.executor().run_until_parked();
+        close.await.unwrap();
+        cx.read(|cx| {
+            let save_counts = items
+                .iter()
+                .map(|item| item.read(cx).save_count)
+                .collect::<Vec<_>>();
+            assert_eq!(save_counts, [0, 0, 1]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_close_items_respects_confirm_close(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        add_labeled_item(&pane, "A", false, cx);
+        pane.update(cx, |pane, cx| {
+            let item = Box::new(
+                cx.new_view(|cx| TestItem::new(cx).with_label("B").with_allow_close(false)),
+            );
+            pane.add_item(item, false, false, None, cx);
+        });
+        add_labeled_item(&pane, "C", false, cx);
+        assert_item_labels(&pane, ["A", "B", "C*"], cx);
+
+        pane.update(cx, |pane, cx| {
+            pane.close_all_items(&CloseAllItems { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["B", "C*"], cx);
+    }
+
+    #[gpui::test]
+    async fn test_dropping_files_onto_tab_bar(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(crate::register_project_item::<TestItem>);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            serde_json::json!({ "1.txt": "", "2.txt": "", "3.txt": "" }),
+        )
+        .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        set_labeled_items(&pane, ["A", "B*", "C"], cx);
+
+        // Opened files are shown by their path, other items by their label.
+        let tabs = |cx: &mut VisualTestContext| {
+            pane.update(cx, |pane, cx| {
+                pane.items()
+                    .map(|item| match item.project_path(cx) {
+                        Some(project_path) => project_path.path.to_string_lossy().into_owned(),
+                        None => item.downcast::<TestItem>().unwrap().read(cx).label.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Files dropped onto a tab are inserted in its place.
+        pane.update(cx, |pane, cx| {
+            let paths = ExternalPaths::new(["/root/1.txt".into(), "/root/2.txt".into()]);
+            pane.handle_external_paths_drop(&paths, Some(1), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(tabs(cx), ["A", "1.txt", "2.txt", "B", "C"]);
+
+        // Files dropped onto the end of the tab bar are appended.
+        pane.update(cx, |pane, cx| {
+            pane.activate_item(0, false, false, cx);
+            let paths = ExternalPaths::new(["/root/3.txt".into()]);
+            pane.handle_external_paths_drop(&paths, Some(pane.items_len()), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(tabs(cx), ["A", "1.txt", "2.txt", "B", "C", "3.txt"]);
+    }
+
+    #[gpui::test]
+    async fn test_dropping_project_entries_onto_tab_bar(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(crate::register_project_item::<TestItem>);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            serde_json::json!({ "1.txt": "", "2.txt": "", "dir": { "3.txt": "" } }),
+        )
+        .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        set_labeled_items(&pane, ["A", "B*", "C"], cx);
+        cx.run_until_parked();
+
+        let entry_id = |path: &str, cx: &mut VisualTestContext| {
+            project.update(cx, |project, cx| {
+                let worktree = project.worktrees().next().unwrap();
+                worktree.read(cx).entry_for_path(path).unwrap().id
+            })
+        };
+        // Opened files are shown by their path, other items by their label.
+        let tabs = |cx: &mut VisualTestContext| {
+            pane.update(cx, |pane, cx| {
+                pane.items()
+                    .map(|item| match item.project_path(cx) {
+                        Some(project_path) => project_path.path.to_string_lossy().into_owned(),
+                        None => item.downcast::<TestItem>().unwrap().read(cx).label.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // An entry dropped onto a tab is opened in its place.
+        let entry = entry_id("1.txt", cx);
+        pane.update(cx, |pane, cx| {
+            pane.handle_project_entry_drop(&entry, Some(0), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(tabs(cx), ["1.txt", "A", "B", "C"]);
+
+        // An entry dropped onto the end of the tab bar is appended.
+        let entry = entry_id("2.txt", cx);
+        pane.update(cx, |pane, cx| {
+            pane.activate_item(0, false, false, cx);
+            pane.handle_project_entry_drop(&entry, Some(pane.items_len()), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(tabs(cx), ["1.txt", "A", "B", "C", "2.txt"]);
+
+        // Dropping a directory doesn't open anything.
+        let entry = entry_id("dir", cx);
+        pane.update(cx, |pane, cx| {
+            pane.handle_project_entry_drop(&entry, Some(1), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(tabs(cx), ["1.txt", "A", "B", "C", "2.txt"]);
+    }
+
+    #[gpui::test]
+    async fn test_dropping_text_onto_tab_bar(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(crate::register_project_item::<TestItem>);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        set_labeled_items(&pane, ["A", "B*", "C"], cx);
+
+        // Untitled items are shown as "untitled", other items by their label.
+        let tabs = |cx: &mut VisualTestContext| {
+            pane.update(cx, |pane, cx| {
+                pane.items()
+                    .map(|item| {
+                        let item = item.downcast::<TestItem>().unwrap().read(cx);
+                        match item.project_items.as_slice() {
+                            [project_item] => {
+                                assert!(project_item.read(cx).project_path.is_none());
+                                "untitled".to_string()
+                            }
+                            _ => item.label.clone(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Text dropped onto a tab is opened in an untitled buffer in its place.
+        pane.update(cx, |pane, cx| {
+            pane.handle_external_text_drop(&ExternalText::new("dropped text"), 1, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(tabs(cx), ["A", "untitled", "B", "C"]);
+        pane.update(cx, |pane, _| assert_eq!(pane.active_item_index(), 1));
+
+        // Text dropped onto the end of the tab bar is appended.
+        pane.update(cx, |pane, cx| {
+            pane.handle_external_text_drop(&ExternalText::new("more text"), pane.items_len(), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(tabs(cx), ["A", "untitled", "B", "C", "untitled"]);
+        pane.update(cx, |pane, _| assert_eq!(pane.active_item_index(), 4));
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
@@ -1,16 +1,16 @@
-use crate::{pane_group::element::pane_axis, AppState, FollowerState, Pane, Workspace};
+use crate::{pane_group::element::pane_axis, AppState, FollowerState, Pane, Unfollow, Workspace};
 use anyhow::{anyhow, Result};
 use call::{ActiveCall, ParticipantLocation};
 use collections::HashMap;
 use gpui::{
-    point, size, AnyView, AnyWeakView, Axis, Bounds, IntoElement, Model, MouseButton, Pixels,
-    Point, View, ViewContext,
+    point, size, AnyView, AnyWeakView, Axis, Bounds, Hsla, IntoElement, Model, MouseButton, Pixels,
+    Point, SharedUri, View, ViewContext,
 };
 use parking_lot::Mutex;
 use project::Project;
 use serde::Deserialize;
 use std::sync::Arc;
-use ui::prelude::*;
+use ui::{prelude::*, Avatar, IconButton, Tab, Tooltip};
 
 pub const HANDLE_HITBOX_SIZE: f32 = 4.0;
 const HORIZONTAL_MIN_SIZE: f32 = 80.;
@@ -190,6 +190,7 @@ impl Member {
                 });
 
                 let mut leader_border = None;
+                let mut leader_chip = None;
                 let mut leader_status_box = None;
                 let mut leader_join_data = None;
                 if let Some(leader) = &leader {
@@ -200,6 +201,13 @@ impl Member {
                         .cursor;
                     leader_color.fade_out(0.3);
                     leader_border = Some(leader_color);
+                    leader_chip = Some(self.render_leader_chip(
+                        pane,
+                        leader.user.avatar_uri.clone(),
+                        leader.user.github_login.clone(),
+                        leader_color,
+                        cx,
+                    ));
 
                     leader_status_box = match leader.location {
                         ParticipantLocation::SharedProject {
@@ -249,6 +257,7 @@ impl Member {
                                 .border_color(color),
                         )
                     })
+                    .children(leader_chip)
                     .when_some(leader_status_box, |this, status_box| {
                         this.child(
                             div()
@@ -296,6 +305,40 @@ impl Member {
         }
     }
 
+    /// Renders a chip naming the leader a pane is following, at the top of the
+    /// pane, from which the pane can stop following them.
+    fn render_leader_chip(
+        &self,
+        pane: &View<Pane>,
+        avatar_uri: SharedUri,
+        github_login: String,
+        color: Hsla,
+        cx: &mut ViewContext<Workspace>,
+    ) -> impl IntoElement {
+        let pane = pane.clone();
+        h_flex()
+            .absolute()
+            .top(rems(Tab::CONTAINER_HEIGHT_IN_REMS))
+            .right_3()
+            .mt_1()
+            .z_index(1)
+            .gap_1()
+            .pl_1()
+            .rounded_md()
+            .elevation_2(cx)
+            .border_color(color)
+            .child(Avatar::new(avatar_uri).size(rems(1.)).border_color(color))
+            .child(Label::new(format!("Following {github_login}")).size(LabelSize::Small))
+            .child(
+                IconButton::new("unfollow", IconName::Close)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::for_action("Unfollow", &Unfollow, cx))
+                    .on_click(cx.listener(move |workspace, _, cx| {
+                        workspace.unfollow(&pane, cx);
+                    })),
+            )
+    }
+
     fn collect_panes<'a>(&'a self, panes: &mut Vec<&'a View<Pane>>) {
         match self {
             Member::Axis(axis) => {
@@ -527,6 +570,15 @@ impl SplitDirection {
         [Self::Up, Self::Down, Self::Left, Self::Right]
     }
 
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+
     pub fn edge(&self, rect: Bounds<Pixels>) -> Pixels {
         match self {
             Self::Up => rect.origin.y,
@@ -710,11 +762,15 @@ mod element {
                 proposed_current_pixel_change -= current_pixel_change;
             }
 
+            // Only re-render the workspace rather than refreshing the whole window, so that
+            // panes whose bounds weren't affected by the new ratios reuse their cached frame.
             workspace
-                .update(cx, |this, cx| this.schedule_serialize(cx))
+                .update(cx, |this, cx| {
+                    this.schedule_serialize(cx);
+                    cx.notify();
+                })
                 .log_err();
             cx.stop_propagation();
-            cx.refresh();
         }
 
         fn push_handle(
@@ -747,7 +803,8 @@ mod element {
                     bounds: handle_bounds,
                     stacking_order: cx.stacking_order().clone(),
                 };
-                if interactive_handle_bounds.visibly_contains(&cx.mouse_position(), cx) {
+                let hovered = interactive_handle_bounds.visibly_contains(&cx.mouse_position(), cx);
+                if hovered {
                     cx.set_cursor_style(match axis {
                         Axis::Vertical => CursorStyle::ResizeUpDown,
                         Axis::Horizontal => CursorStyle::ResizeLeftRight,
@@ -755,7 +812,15 @@ mod element {
                 }
 
                 cx.add_opaque_layer(handle_bounds);
-                cx.paint_quad(gpui::fill(divider_bounds, cx.theme().colors().border));
+                let colors = cx.theme().colors();
+                let (bounds, color) = if *dragged_handle.borrow() == Some(ix) {
+                    (handle_bounds, colors.resize_handle_active)
+                } else if hovered {
+                    (handle_bounds, colors.resize_handle_hover)
+                } else {
+                    (divider_bounds, colors.border)
+                };
+                cx.paint_quad(gpui::fill(bounds, color));
 
                 cx.on_mouse_event({
                     let dragged_handle = dragged_handle.clone();
@@ -768,10 +833,11 @@ mod element {
                                 let mut borrow = flexes.lock();
                                 *borrow = vec![1.; borrow.len()];
                                 workspace
-                                    .update(cx, |this, cx| this.schedule_serialize(cx))
+                                    .update(cx, |this, cx| {
+                                        this.schedule_serialize(cx);
+                                        cx.notify();
+                                    })
                                     .log_err();
-
-                                cx.refresh();
                             }
                             cx.stop_propagation();
                         }
@@ -793,6 +859,9 @@ mod element {
                                 workspace.clone(),
                                 cx,
                             )
+                        } else if phase.bubble() && handle_bounds.contains(&e.position) != hovered {
+                            // Repaint the handle with or without its hover color.
+                            workspace.update(cx, |_, cx| cx.notify()).log_err();
                         }
                     }
                 });
@@ -908,9 +977,10 @@ mod element {
             cx.with_z_index(1, |cx| {
                 cx.on_mouse_event({
                     let state = state.clone();
-                    move |_: &MouseUpEvent, phase, _cx| {
-                        if phase.bubble() {
-                            state.replace(None);
+                    let workspace = self.workspace.clone();
+                    move |_: &MouseUpEvent, phase, cx| {
+                        if phase.bubble() && state.replace(None).is_some() {
+                            workspace.update(cx, |_, cx| cx.notify()).log_err();
                         }
                     }
                 });
@@ -928,3 +998,73 @@ mod element {
         (flexes.iter().copied().sum::<f32>() - flexes.len() as f32).abs() < 0.001
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::init_test;
+    use fs::FakeFs;
+    use gpui::{
+        px, Modifiers, MouseDownEvent, MouseMoveEvent, MouseUpEvent, TestAppContext,
+        VisualTestContext,
+    };
+
+    #[gpui::test]
+    async fn test_resizing_panes_redraws_them(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace.split_pane(pane, SplitDirection::Right, cx);
+        });
+
+        // The bounds of the panes as of the last time they were painted.
+        let pane_bounds = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, _| {
+                let Member::Axis(axis) = &workspace.center.root else {
+                    panic!("expected the center to be split");
+                };
+                let bounding_boxes = axis.bounding_boxes.lock();
+                (bounding_boxes[0].unwrap(), bounding_boxes[1].unwrap())
+            })
+        };
+        let (left, right) = pane_bounds(cx);
+        assert_eq!(left.size.width, right.size.width);
+
+        // Dragging the handle between the panes redraws them with the new ratio.
+        let handle = point(left.origin.x + left.size.width, left.origin.y + px(10.));
+        cx.simulate_event(MouseDownEvent {
+            button: MouseButton::Left,
+            position: handle,
+            modifiers: Modifiers::default(),
+            click_count: 1,
+        });
+        cx.simulate_event(MouseMoveEvent {
+            position: point(handle.x + px(100.), handle.y),
+            pressed_button: Some(MouseButton::Left),
+            modifiers: Modifiers::default(),
+        });
+        cx.simulate_event(MouseUpEvent {
+            button: MouseButton::Left,
+            position: point(handle.x + px(100.), handle.y),
+            modifiers: Modifiers::default(),
+            click_count: 1,
+        });
+        let (resized_left, resized_right) = pane_bounds(cx);
+        assert!((resized_left.size.width - left.size.width - px(100.)).abs() < px(1.));
+        assert!((left.size.width - resized_right.size.width - px(100.)).abs() < px(1.));
+
+        // Double-clicking the handle redraws them with their sizes reset.
+        cx.simulate_event(MouseDownEvent {
+            button: MouseButton::Left,
+            position: point(handle.x + px(100.), handle.y),
+            modifiers: Modifiers::default(),
+            click_count: 2,
+        });
+        let (reset_left, reset_right) = pane_bounds(cx);
+        assert_eq!(reset_left.size.width, left.size.width);
+        assert_eq!(reset_right.size.width, right.size.width);
+    }
+}
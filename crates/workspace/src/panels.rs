@@ -0,0 +1,166 @@
+//! Adding panels to the docks of every workspace from other crates.
+//!
+//! A crate implements [`Panel`] for its view and calls [`register_panel`] from its
+//! `init` function. Each workspace window then loads the panel with
+//! [`load_registered_panels`] once it opens, so neither this crate nor the application
+//! needs to be changed to add a panel. Items in the status bar are registered the same
+//! way, with [`register_status_item`], and are added once the panels have loaded.
+//!
+//! Panels are told about their lifecycle through the [`Panel`] trait:
+//! - [`Panel::set_active`] is called with `true` when the panel is shown, because its
+//!   dock was opened or it became the dock's active or split panel, and with `false`
+//!   when it's hidden again.
+//! - [`Panel::set_size`] is called when the user resizes the panel, and
+//!   [`Panel::set_zoomed`] when it's zoomed in or out.
+//!
+//! Which dock a panel goes in is up to [`Panel::position`], which usually reads the
+//! panel's settings, so that moving the panel persists. The order of registered panels
+//! within their docks is given by their [`PanelPlacement`].
+
+use std::rc::Rc;
+
+use anyhow::{Context as _, Result};
+use futures::future::join_all;
+use gpui::{
+    AppContext, AsyncWindowContext, Global, Task, View, ViewContext, WeakView, WindowContext,
+};
+use util::ResultExt;
+
+pub use crate::dock::{Dock, DockPosition, Panel, PanelEvent, PanelHandle};
+use crate::{StatusItemSide, StatusItemView, Workspace};
+
+/// Where a registered panel is placed among the other panels of its dock.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PanelPlacement {
+    /// Registered panels are added to their docks in ascending order, after the panels
+    /// built into the application. Panels with the same order keep the order they were
+    /// registered in.
+    pub order: i32,
+}
+
+type AddPanel = Box<dyn FnOnce(&mut Workspace, &mut WindowContext)>;
+
+/// A panel added with [`register_panel`].
+pub struct PanelRegistration {
+    /// The [`Panel::persistent_name`] of the panel.
+    pub persistent_name: &'static str,
+    pub placement: PanelPlacement,
+    load: Rc<dyn Fn(WeakView<Workspace>, AsyncWindowContext) -> Task<Result<AddPanel>>>,
+}
+
+#[derive(Default)]
+struct PanelRegistry(Vec<PanelRegistration>);
+
+impl Global for PanelRegistry {}
+
+/// Adds the panel built by `load` to every workspace whose panels are loaded from now
+/// on. `load` is usually the panel's own `load` function, which restores its
+/// serialized state.
+pub fn register_panel<T: Panel>(
+    placement: PanelPlacement,
+    load: impl Fn(WeakView<Workspace>, AsyncWindowContext) -> Task<Result<View<T>>> + 'static,
+    cx: &mut AppContext,
+) {
+    let load = Rc::new(move |workspace, cx: AsyncWindowContext| {
+        let panel = load(workspace, cx.clone());
+        cx.spawn(|_| async move {
+            let panel = panel.await?;
+            anyhow::Ok(
+                Box::new(move |workspace: &mut Workspace, cx: &mut WindowContext| {
+                    workspace.add_panel(panel, cx)
+                }) as AddPanel,
+            )
+        })
+    });
+    cx.default_global::<PanelRegistry>()
+        .0
+        .push(PanelRegistration {
+            persistent_name: T::persistent_name(),
+            placement,
+            load,
+        });
+}
+
+type AddStatusItem = Rc<dyn Fn(&mut Workspace, &mut ViewContext<Workspace>)>;
+
+#[derive(Default)]
+struct StatusItemRegistry(Vec<AddStatusItem>);
+
+impl Global for StatusItemRegistry {}
+
+/// Adds the item built by `build` to the status bar of every workspace whose panels are
+/// loaded from now on, placed among the other items on `side` by `order`. The item is
+/// built after the registered panels are added, so that it can look up its panel with
+/// [`Workspace::panel`]. `build` returns `None` to leave the item out.
+pub fn register_status_item<T: StatusItemView>(
+    side: StatusItemSide,
+    order: i32,
+    build: impl Fn(&mut Workspace, &mut ViewContext<Workspace>) -> Option<View<T>> + 'static,
+    cx: &mut AppContext,
+) {
+    let add = Rc::new(
+        move |workspace: &mut Workspace, cx: &mut ViewContext<Workspace>| {
+            if let Some(item) = build(workspace, cx) {
+                workspace.status_bar().update(cx, |status_bar, cx| {
+                    status_bar.add_item_with_order(side, order, item, cx)
+                });
+            }
+        },
+    );
+    cx.default_global::<StatusItemRegistry>().0.push(add);
+}
+
+/// The panels added with [`register_panel`], in the order they were registered.
+pub fn registered_panels(cx: &AppContext) -> impl Iterator<Item = &PanelRegistration> {
+    cx.try_global::<PanelRegistry>()
+        .into_iter()
+        .flat_map(|registry| &registry.0)
+}
+
+/// Loads every registered panel and adds it to `workspace`, followed by the registered
+/// status items. The panels are loaded concurrently, and one that fails to load is
+/// skipped without affecting the others.
+pub fn load_registered_panels(
+    workspace: WeakView<Workspace>,
+    cx: AsyncWindowContext,
+) -> Task<Result<()>> {
+    cx.spawn(|mut cx| async move {
+        let mut registrations = cx.update(|cx| {
+            registered_panels(cx)
+                .map(|registration| {
+                    (
+                        registration.persistent_name,
+                        registration.placement,
+                        registration.load.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })?;
+        registrations.sort_by_key(|(_, placement, _)| placement.order);
+
+        let panels = join_all(
+            registrations
+                .iter()
+                .map(|(_, _, load)| load(workspace.clone(), cx.clone())),
+        )
+        .await;
+        workspace.update(&mut cx, |workspace, cx| {
+            for ((name, _, _), panel) in registrations.iter().zip(panels) {
+                if let Some(add_panel) = panel
+                    .with_context(|| format!("failed to load the {name} panel"))
+                    .log_err()
+                {
+                    add_panel(workspace, cx);
+                }
+            }
+
+            let status_items = cx
+                .try_global::<StatusItemRegistry>()
+                .map(|registry| registry.0.clone())
+                .unwrap_or_default();
+            for add_status_item in status_items {
+                add_status_item(workspace, cx);
+            }
+        })
+    })
+}
@@ -17,8 +17,8 @@ use uuid::Uuid;
 use crate::WorkspaceId;
 
 use model::{
-    GroupId, PaneId, SerializedItem, SerializedPane, SerializedPaneGroup, SerializedWorkspace,
-    WorkspaceLocation,
+    GroupId, PaneId, SerializedClosedItem, SerializedItem, SerializedPane, SerializedPaneGroup,
+    SerializedWorkspace, WorkspaceLocation,
 };
 
 use self::model::DockStructure;
@@ -185,6 +185,7 @@ define_connection! {
     //     kind: String, // Indicates which view this connects to. This is the key in the item_deserializers global
     //     position: usize, // Position of the item in the parent pane. This is equivalent to panes' position column
     //     active: bool, // Indicates if this item is the active one in the pane
+    //     state: Option<String>, // The item's own serialized state, if any
     // )
     pub static ref DB: WorkspaceDb<()> =
     &[sql!(
@@ -291,6 +292,33 @@ define_connection! {
     // Add pane group flex data
     sql!(
         ALTER TABLE pane_groups ADD COLUMN flexes TEXT;
+    ),
+    // Add item state, for items that don't persist it in their own tables
+    sql!(
+        ALTER TABLE items ADD COLUMN state TEXT;
+    ),
+    // Add recently closed items, so that they can be reopened after a restart
+    sql!(
+        CREATE TABLE closed_items(
+            workspace_id INTEGER NOT NULL,
+            position INTEGER NOT NULL, // 0 is the most recently closed item
+            kind TEXT,
+            abs_path BLOB NOT NULL,
+            cursor_row INTEGER,
+            cursor_column INTEGER,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+            PRIMARY KEY(workspace_id, position)
+        ) STRICT;
+    ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN ui_zoom REAL;
+    ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_popped_out_panels TEXT; // JSON array of panel names
+        ALTER TABLE workspaces ADD COLUMN right_dock_popped_out_panels TEXT; // JSON array of panel names
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_popped_out_panels TEXT; // JSON array of panel names
     )
     ];
 }
@@ -307,12 +335,13 @@ impl WorkspaceDb {
 
         // Note that we re-assign the workspace_id here in case it's empty
         // and we've grabbed the most recent workspace
-        let (workspace_id, workspace_location, bounds, display, docks): (
+        let (workspace_id, workspace_location, bounds, display, docks, ui_zoom): (
             WorkspaceId,
             WorkspaceLocation,
             Option<SerializedWindowsBounds>,
             Option<Uuid>,
             DockStructure,
+            Option<f32>,
         ) = self
             .select_row_bound(sql! {
                 SELECT
@@ -327,14 +356,20 @@ impl WorkspaceDb {
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
+                    left_dock_popped_out_panels,
                     right_dock_visible,
                     right_dock_active_panel,
                     right_dock_zoom,
+                    right_dock_popped_out_panels,
                     bottom_dock_visible,
                     bottom_dock_active_panel,
-                    bottom_dock_zoom
+                    bottom_dock_zoom,
+                    bottom_dock_popped_out_panels,
+                    ui_zoom
                 FROM workspaces
                 WHERE workspace_location = ?
+                ORDER BY timestamp DESC
+                LIMIT 1
             })
             .and_then(|mut prepared_statement| (prepared_statement)(&workspace_location))
             .context("No workspaces found")
@@ -351,12 +386,29 @@ impl WorkspaceDb {
             bounds: bounds.map(|bounds| bounds.0),
             display,
             docks,
+            closed_items: self
+                .get_closed_items(workspace_id)
+                .context("Getting closed items")
+                .log_err()
+                .unwrap_or_default(),
+            ui_zoom,
         })
     }
 
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
+        self.save_workspace_alongside(workspace, Vec::new()).await
+    }
+
+    /// Like [`Self::save_workspace`], but keeps the rows of `siblings`: the
+    /// workspaces of other open windows onto the same project, which share this
+    /// workspace's location.
+    pub(crate) async fn save_workspace_alongside(
+        &self,
+        workspace: SerializedWorkspace,
+        siblings: Vec<WorkspaceId>,
+    ) {
         self.write(move |conn| {
             conn.with_savepoint("update_worktrees", || {
                 // Clear out panes and pane_groups
@@ -365,10 +417,19 @@ impl WorkspaceDb {
                     DELETE FROM panes WHERE workspace_id = ?1;))?(workspace.id)
                 .expect("Clearing old panes");
 
-                conn.exec_bound(sql!(
-                    DELETE FROM workspaces WHERE workspace_location = ? AND workspace_id != ?
-                ))?((&workspace.location, workspace.id.clone()))
-                .context("clearing out old locations")?;
+                let stale_ids = conn.select_bound::<_, WorkspaceId>(sql!(
+                    SELECT workspace_id FROM workspaces
+                    WHERE workspace_location = ? AND workspace_id != ?
+                ))?((&workspace.location, workspace.id))
+                .context("finding old locations")?;
+                for stale_id in stale_ids {
+                    if !siblings.contains(&stale_id) {
+                        conn.exec_bound(sql!(DELETE FROM workspaces WHERE workspace_id = ?))?(
+                            stale_id,
+                        )
+                        .context("clearing out old locations")?;
+                    }
+                }
 
                 // Upsert
                 conn.exec_bound(sql!(
@@ -378,35 +439,51 @@ impl WorkspaceDb {
                         left_dock_visible,
                         left_dock_active_panel,
                         left_dock_zoom,
+                        left_dock_popped_out_panels,
                         right_dock_visible,
                         right_dock_active_panel,
                         right_dock_zoom,
+                        right_dock_popped_out_panels,
                         bottom_dock_visible,
                         bottom_dock_active_panel,
                         bottom_dock_zoom,
+                        bottom_dock_popped_out_panels,
+                        ui_zoom,
                         timestamp
                     )
-                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, CURRENT_TIMESTAMP)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, CURRENT_TIMESTAMP)
                     ON CONFLICT DO
                     UPDATE SET
                         workspace_location = ?2,
                         left_dock_visible = ?3,
                         left_dock_active_panel = ?4,
                         left_dock_zoom = ?5,
-                        right_dock_visible = ?6,
-                        right_dock_active_panel = ?7,
-                        right_dock_zoom = ?8,
-                        bottom_dock_visible = ?9,
-                        bottom_dock_active_panel = ?10,
-                        bottom_dock_zoom = ?11,
+                        left_dock_popped_out_panels = ?6,
+                        right_dock_visible = ?7,
+                        right_dock_active_panel = ?8,
+                        right_dock_zoom = ?9,
+                        right_dock_popped_out_panels = ?10,
+                        bottom_dock_visible = ?11,
+                        bottom_dock_active_panel = ?12,
+                        bottom_dock_zoom = ?13,
+                        bottom_dock_popped_out_panels = ?14,
+                        ui_zoom = ?15,
                         timestamp = CURRENT_TIMESTAMP
-                ))?((workspace.id, &workspace.location, workspace.docks))
+                ))?((
+                    workspace.id,
+                    &workspace.location,
+                    workspace.docks,
+                    workspace.ui_zoom,
+                ))
                 .context("Updating workspace")?;
 
                 // Save center pane group
                 Self::save_pane_group(conn, workspace.id, &workspace.center_group, None)
                     .context("save pane group in save workspace")?;
 
+                Self::save_closed_items(conn, workspace.id, &workspace.closed_items)
+                    .context("save closed items in save workspace")?;
+
                 Ok(())
             })
             .log_err();
@@ -455,6 +532,19 @@ impl WorkspaceDb {
         Ok(result)
     }
 
+    #[cfg(test)]
+    pub(crate) fn workspace_ids_for_roots<P: AsRef<Path>>(
+        &self,
+        worktree_roots: &[P],
+    ) -> Result<Vec<WorkspaceId>> {
+        let workspace_location: WorkspaceLocation = worktree_roots.into();
+        self.select_bound(sql!(
+            SELECT workspace_id FROM workspaces
+            WHERE workspace_location = ?
+            ORDER BY workspace_id
+        ))?(&workspace_location)
+    }
+
     pub async fn last_workspace(&self) -> Result<Option<WorkspaceLocation>> {
         Ok(self
             .recent_workspaces_on_disk()
@@ -623,7 +713,7 @@ impl WorkspaceDb {
 
     fn get_items(&self, pane_id: PaneId) -> Result<Vec<SerializedItem>> {
         Ok(self.select_bound(sql!(
-            SELECT kind, item_id, active FROM items
+            SELECT kind, item_id, active, state FROM items
             WHERE pane_id = ?
                 ORDER BY position
         ))?(pane_id)?)
@@ -636,7 +726,7 @@ impl WorkspaceDb {
         items: &[SerializedItem],
     ) -> Result<()> {
         let mut insert = conn.exec_bound(sql!(
-            INSERT INTO items(workspace_id, pane_id, position, kind, item_id, active) VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO items(workspace_id, pane_id, position, kind, item_id, active, state) VALUES (?, ?, ?, ?, ?, ?, ?)
         )).context("Preparing insertion")?;
         for (position, item) in items.iter().enumerate() {
             insert((workspace_id, pane_id, position, item))?;
@@ -645,6 +735,34 @@ impl WorkspaceDb {
         Ok(())
     }
 
+    fn get_closed_items(&self, workspace_id: WorkspaceId) -> Result<Vec<SerializedClosedItem>> {
+        Ok(self.select_bound(sql!(
+            SELECT kind, abs_path, cursor_row, cursor_column FROM closed_items
+            WHERE workspace_id = ?
+                ORDER BY position
+        ))?(workspace_id)?)
+    }
+
+    fn save_closed_items(
+        conn: &Connection,
+        workspace_id: WorkspaceId,
+        closed_items: &[SerializedClosedItem],
+    ) -> Result<()> {
+        conn.exec_bound(sql!(
+            DELETE FROM closed_items WHERE workspace_id = ?
+        ))?(workspace_id)
+        .context("Clearing old closed items")?;
+
+        let mut insert = conn.exec_bound(sql!(
+            INSERT INTO closed_items(workspace_id, position, kind, abs_path, cursor_row, cursor_column) VALUES (?, ?, ?, ?, ?, ?)
+        )).context("Preparing insertion")?;
+        for (position, closed_item) in closed_items.iter().enumerate() {
+            insert((workspace_id, position, closed_item))?;
+        }
+
+        Ok(())
+    }
+
     query! {
         pub async fn update_timestamp(workspace_id: WorkspaceId) -> Result<()> {
             UPDATE workspaces
@@ -750,6 +868,8 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
         };
 
         let workspace_2 = SerializedWorkspace {
@@ -759,6 +879,8 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -857,6 +979,8 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: Some(1.2),
         };
 
         db.save_workspace(workspace.clone()).await;
@@ -872,6 +996,88 @@ mod tests {
         assert_eq!(workspace, round_trip_workspace.unwrap());
     }
 
+    #[gpui::test]
+    async fn test_closed_items_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_closed_items_serialization").await);
+
+        let mut workspace = SerializedWorkspace {
+            id: 1,
+            location: (["/tmp"]).into(),
+            center_group: Default::default(),
+            bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            closed_items: vec![
+                SerializedClosedItem {
+                    kind: Some("Editor".into()),
+                    abs_path: "/tmp/b.txt".into(),
+                    position: Some(language::Point::new(3, 7)),
+                },
+                SerializedClosedItem {
+                    kind: None,
+                    abs_path: "/tmp/a.txt".into(),
+                    position: None,
+                },
+            ],
+            ui_zoom: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+
+        // Saving again replaces the previously stored items.
+        workspace.closed_items.truncate(1);
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+    }
+
+    #[gpui::test]
+    async fn test_item_state_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_item_state_serialization").await);
+
+        let mut workspace = SerializedWorkspace {
+            id: 1,
+            location: (["/tmp/item_state"]).into(),
+            center_group: SerializedPaneGroup::Pane(SerializedPane::new(
+                vec![
+                    SerializedItem::new("Terminal", 1, false),
+                    SerializedItem::new("ProjectSearchView", 2, true)
+                        .with_state(r#"{"query":"needle"}"#),
+                ],
+                true,
+            )),
+            bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(
+            db.workspace_for_roots(&["/tmp/item_state"]).unwrap(),
+            workspace
+        );
+
+        // Saving again replaces the stored state, including clearing it.
+        workspace.center_group = SerializedPaneGroup::Pane(SerializedPane::new(
+            vec![
+                SerializedItem::new("Terminal", 1, false).with_state("{}"),
+                SerializedItem::new("ProjectSearchView", 2, true),
+            ],
+            true,
+        ));
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(
+            db.workspace_for_roots(&["/tmp/item_state"]).unwrap(),
+            workspace
+        );
+    }
+
     #[gpui::test]
     async fn test_workspace_assignment() {
         env_logger::try_init().ok();
@@ -885,6 +1091,8 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
         };
 
         let mut workspace_2 = SerializedWorkspace {
@@ -894,6 +1102,8 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -930,6 +1140,8 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
         };
 
         db.save_workspace(workspace_3.clone()).await;
@@ -963,9 +1175,38 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
         }
     }
 
+    #[gpui::test]
+    async fn test_saving_alongside_sibling_windows() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_saving_alongside_sibling_windows").await);
+
+        let workspace = |id| SerializedWorkspace {
+            id,
+            location: (["/tmp"]).into(),
+            center_group: Default::default(),
+            bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            closed_items: Vec::new(),
+            ui_zoom: None,
+        };
+
+        db.save_workspace(workspace(1)).await;
+        db.save_workspace_alongside(workspace(2), vec![1]).await;
+        db.save_workspace_alongside(workspace(1), vec![2]).await;
+        assert_eq!(db.workspace_ids_for_roots(&["/tmp"]).unwrap(), vec![1, 2]);
+
+        // Once the other window is gone, its row is collected like any stale location.
+        db.save_workspace(workspace(3)).await;
+        assert_eq!(db.workspace_ids_for_roots(&["/tmp"]).unwrap(), vec![3]);
+    }
+
     #[gpui::test]
     async fn test_simple_split() {
         env_logger::try_init().ok();
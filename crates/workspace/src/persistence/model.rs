@@ -1,12 +1,16 @@
 use super::SerializedAxis;
-use crate::{item::ItemHandle, ItemDeserializers, Member, Pane, PaneAxis, Workspace, WorkspaceId};
+use crate::{
+    item::ItemHandle, Member, Pane, PaneAxis, SerializableItemRegistry, Workspace, WorkspaceId,
+};
 use anyhow::{Context, Result};
 use async_recursion::async_recursion;
 use db::sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::Statement,
 };
+use futures::{stream::FuturesOrdered, StreamExt};
 use gpui::{AsyncWindowContext, Model, Task, View, WeakView, WindowBounds};
+use language::Point;
 use project::Project;
 use std::{
     path::{Path, PathBuf},
@@ -62,6 +66,10 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) bounds: Option<WindowBounds>,
     pub(crate) display: Option<Uuid>,
     pub(crate) docks: DockStructure,
+    /// Most recently closed first.
+    pub(crate) closed_items: Vec<SerializedClosedItem>,
+    /// Scale applied to the UI of the window, on top of the UI font size.
+    pub(crate) ui_zoom: Option<f32>,
 }
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -100,6 +108,8 @@ pub struct DockData {
     pub(crate) visible: bool,
     pub(crate) active_panel: Option<String>,
     pub(crate) zoom: bool,
+    /// The persistent names of the panels popped out into floating windows.
+    pub(crate) popped_out_panels: Vec<String>,
 }
 
 impl Column for DockData {
@@ -107,11 +117,15 @@ impl Column for DockData {
         let (visible, next_index) = Option::<bool>::column(statement, start_index)?;
         let (active_panel, next_index) = Option::<String>::column(statement, next_index)?;
         let (zoom, next_index) = Option::<bool>::column(statement, next_index)?;
+        let (popped_out_panels, next_index) = Option::<String>::column(statement, next_index)?;
         Ok((
             DockData {
                 visible: visible.unwrap_or(false),
                 active_panel,
                 zoom: zoom.unwrap_or(false),
+                popped_out_panels: popped_out_panels
+                    .and_then(|names| serde_json::from_str(&names).log_err())
+                    .unwrap_or_default(),
             },
             next_index,
         ))
@@ -122,7 +136,13 @@ impl Bind for DockData {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
         let next_index = statement.bind(&self.visible, start_index)?;
         let next_index = statement.bind(&self.active_panel, next_index)?;
-        statement.bind(&self.zoom, next_index)
+        let next_index = statement.bind(&self.zoom, next_index)?;
+        let popped_out_panels = if self.popped_out_panels.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&self.popped_out_panels)?)
+        };
+        statement.bind(&popped_out_panels, next_index)
     }
 }
 
@@ -233,13 +253,22 @@ impl SerializedPane {
         workspace: WeakView<Workspace>,
         cx: &mut AsyncWindowContext,
     ) -> Result<Vec<Option<Box<dyn ItemHandle>>>> {
-        let mut item_tasks = Vec::new();
-        let mut active_item_index = None;
-        for (index, item) in self.children.iter().enumerate() {
+        let mut item_tasks = FuturesOrdered::new();
+        for item in &self.children {
             let project = project.clone();
-            item_tasks.push(pane.update(cx, |_, cx| {
-                if let Some(deserializer) = cx.global::<ItemDeserializers>().get(&item.kind) {
-                    deserializer(project, workspace.clone(), workspace_id, item.item_id, cx)
+            item_tasks.push_back(pane.update(cx, |_, cx| {
+                let deserializer = cx
+                    .try_global::<SerializableItemRegistry>()
+                    .and_then(|registry| registry.deserializer(&item.kind));
+                if let Some(deserializer) = deserializer {
+                    deserializer(
+                        project,
+                        workspace.clone(),
+                        workspace_id,
+                        item.item_id,
+                        item.state.clone(),
+                        cx,
+                    )
                 } else {
                     Task::ready(Err(anyhow::anyhow!(
                         "Deserializer does not exist for item kind: {}",
@@ -247,26 +276,36 @@ impl SerializedPane {
                     )))
                 }
             })?);
-            if item.active {
-                active_item_index = Some(index);
-            }
         }
 
+        // Items are added to the pane in their serialized order as each one finishes
+        // loading, rather than once all of them have. Items that fail to load are
+        // skipped, so the active item is tracked by its handle rather than its index.
         let mut items = Vec::new();
-        for item_handle in futures::future::join_all(item_tasks).await {
+        let mut active_item = None;
+        for serialized_item in &self.children {
+            let Some(item_handle) = item_tasks.next().await else {
+                break;
+            };
             let item_handle = item_handle.log_err();
             items.push(item_handle.clone());
 
             if let Some(item_handle) = item_handle {
+                if serialized_item.active {
+                    active_item = Some(item_handle.clone());
+                }
                 pane.update(cx, |pane, cx| {
-                    pane.add_item(item_handle.clone(), true, true, None, cx);
+                    let ix = pane.items_len();
+                    pane.add_item(item_handle, true, true, Some(ix), cx);
                 })?;
             }
         }
 
-        if let Some(active_item_index) = active_item_index {
+        if let Some(active_item) = active_item {
             pane.update(cx, |pane, cx| {
-                pane.activate_item(active_item_index, false, false, cx);
+                if let Some(ix) = pane.index_for_item(active_item.as_ref()) {
+                    pane.activate_item(ix, false, false, cx);
+                }
             })?;
         }
 
@@ -283,6 +322,8 @@ pub struct SerializedItem {
     pub kind: Arc<str>,
     pub item_id: ItemId,
     pub active: bool,
+    /// The state returned by the item's `SerializableItem::serialize`.
+    pub state: Option<String>,
 }
 
 impl SerializedItem {
@@ -291,8 +332,14 @@ impl SerializedItem {
             kind: Arc::from(kind.as_ref()),
             item_id,
             active,
+            state: None,
         }
     }
+
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -302,20 +349,22 @@ impl Default for SerializedItem {
             kind: Arc::from("Terminal"),
             item_id: 100000,
             active: false,
+            state: None,
         }
     }
 }
 
 impl StaticColumnCount for SerializedItem {
     fn column_count() -> usize {
-        3
+        4
     }
 }
 impl Bind for &SerializedItem {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
         let next_index = statement.bind(&self.kind, start_index)?;
         let next_index = statement.bind(&self.item_id, next_index)?;
-        statement.bind(&self.active, next_index)
+        let next_index = statement.bind(&self.active, next_index)?;
+        statement.bind(&self.state, next_index)
     }
 }
 
@@ -324,11 +373,54 @@ impl Column for SerializedItem {
         let (kind, next_index) = Arc::<str>::column(statement, start_index)?;
         let (item_id, next_index) = ItemId::column(statement, next_index)?;
         let (active, next_index) = bool::column(statement, next_index)?;
+        let (state, next_index) = Option::<String>::column(statement, next_index)?;
         Ok((
             SerializedItem {
                 kind,
                 item_id,
                 active,
+                state,
+            },
+            next_index,
+        ))
+    }
+}
+
+/// An item that was closed and can be reopened from disk, even in a later session.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SerializedClosedItem {
+    /// The kind the item was serialized with, if it was a serializable item.
+    pub kind: Option<Arc<str>>,
+    pub abs_path: PathBuf,
+    /// The position within the item to restore, passed to `Item::navigate` on reopen.
+    pub position: Option<Point>,
+}
+
+impl StaticColumnCount for SerializedClosedItem {
+    fn column_count() -> usize {
+        4
+    }
+}
+impl Bind for &SerializedClosedItem {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        let next_index = statement.bind(&self.kind, start_index)?;
+        let next_index = statement.bind(&self.abs_path, next_index)?;
+        let next_index = statement.bind(&self.position.map(|position| position.row), next_index)?;
+        statement.bind(&self.position.map(|position| position.column), next_index)
+    }
+}
+
+impl Column for SerializedClosedItem {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (kind, next_index) = Option::<Arc<str>>::column(statement, start_index)?;
+        let (abs_path, next_index) = PathBuf::column(statement, next_index)?;
+        let (row, next_index) = Option::<u32>::column(statement, next_index)?;
+        let (column, next_index) = Option::<u32>::column(statement, next_index)?;
+        Ok((
+            SerializedClosedItem {
+                kind,
+                abs_path,
+                position: row.zip(column).map(|(row, column)| Point::new(row, column)),
             },
             next_index,
         ))
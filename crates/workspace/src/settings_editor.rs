@@ -0,0 +1,302 @@
+use std::sync::Arc;
+
+use gpui::{
+    AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, Render, SharedString,
+    Subscription, View, ViewContext, WeakView, WindowContext,
+};
+use settings::{Settings, SettingsStore};
+use ui::{prelude::*, Checkbox, Selection};
+
+use crate::{
+    dock::{DockPosition, PanelHandle},
+    item::{ClosePosition, Item, ItemEvent, ItemSettings},
+    workspace_settings::{AutosaveSetting, WorkspaceSettings},
+    OpenSettingsUI, Workspace, WorkspaceId,
+};
+
+const DEFAULT_AUTOSAVE_DELAY_MILLISECONDS: u64 = 1000;
+
+/// A graphical alternative to editing `settings.json` by hand. Each control
+/// writes its value back to the user settings file, and the editor re-renders
+/// whenever the settings change, whatever the source of the change.
+pub struct SettingsEditor {
+    workspace: WeakView<Workspace>,
+    focus_handle: FocusHandle,
+    _settings_subscription: Subscription,
+}
+
+impl SettingsEditor {
+    /// Activates the settings editor of the workspace, opening one if needed.
+    pub fn deploy(workspace: &mut Workspace, _: &OpenSettingsUI, cx: &mut ViewContext<Workspace>) {
+        if let Some(existing) = workspace.item_of_type::<Self>(cx) {
+            workspace.activate_item(&existing, cx);
+        } else {
+            let settings_editor = cx.new_view(|cx| Self::new(workspace.weak_handle(), cx));
+            workspace.add_item(Box::new(settings_editor), cx);
+        }
+    }
+
+    fn new(workspace: WeakView<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            workspace,
+            focus_handle: cx.focus_handle(),
+            _settings_subscription: cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        }
+    }
+
+    fn update_settings<T: Settings>(
+        &self,
+        cx: &mut ViewContext<Self>,
+        update: impl 'static + Send + FnOnce(&mut T::FileContent),
+    ) {
+        if let Some(workspace) = self.workspace.upgrade() {
+            let fs = workspace.read(cx).app_state().fs.clone();
+            settings::update_settings_file::<T>(fs, cx, update);
+        }
+    }
+
+    fn panels(&self, cx: &AppContext) -> Vec<Arc<dyn PanelHandle>> {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return Vec::new();
+        };
+        let workspace = workspace.read(cx);
+        [
+            workspace.left_dock(),
+            workspace.bottom_dock(),
+            workspace.right_dock(),
+        ]
+        .into_iter()
+        .flat_map(|dock| dock.read(cx).panels().cloned().collect::<Vec<_>>())
+        .collect()
+    }
+
+    fn render_docks(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let overlay_side_docks = WorkspaceSettings::get_global(cx).overlay_side_docks;
+        let panels = self
+            .panels(cx)
+            .into_iter()
+            .map(|panel| self.render_panel_position(panel, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        render_section("Docks")
+            .children(panels)
+            .child(render_checkbox(
+                "overlay-side-docks",
+                "Overlay side docks on top of the panes",
+                overlay_side_docks,
+                cx.listener(|this, selection: &Selection, cx| {
+                    let value = *selection == Selection::Selected;
+                    this.update_settings::<WorkspaceSettings>(cx, move |settings| {
+                        settings.overlay_side_docks = Some(value);
+                    });
+                }),
+            ))
+    }
+
+    fn render_panel_position(
+        &self,
+        panel: Arc<dyn PanelHandle>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let name = panel.persistent_name();
+        let label = panel.icon_tooltip(cx).unwrap_or(name);
+        let current_position = panel.position(cx);
+        let buttons = [
+            (DockPosition::Left, "Left"),
+            (DockPosition::Bottom, "Bottom"),
+            (DockPosition::Right, "Right"),
+        ]
+        .into_iter()
+        .filter(|(position, _)| panel.position_is_valid(*position, cx))
+        .map(|(position, position_label)| {
+            let panel = panel.clone();
+            Button::new(
+                SharedString::from(format!("{name}-{position_label}")),
+                position_label,
+            )
+            .selected(position == current_position)
+            .on_click(move |_, cx| panel.set_position(position, cx))
+        })
+        .collect::<Vec<_>>();
+
+        render_row(label).child(h_flex().gap_1().children(buttons))
+    }
+
+    fn render_autosave(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let autosave = WorkspaceSettings::get_global(cx).autosave;
+        let delay = match autosave {
+            AutosaveSetting::AfterDelay { milliseconds } => milliseconds,
+            _ => DEFAULT_AUTOSAVE_DELAY_MILLISECONDS,
+        };
+        let buttons = [
+            (AutosaveSetting::Off, "Off"),
+            (
+                AutosaveSetting::AfterDelay {
+                    milliseconds: delay,
+                },
+                "After Delay",
+            ),
+            (AutosaveSetting::OnFocusChange, "On Focus Change"),
+            (AutosaveSetting::OnWindowChange, "On Window Change"),
+        ]
+        .into_iter()
+        .map(|(setting, label)| {
+            Button::new(label, label)
+                .selected(setting == autosave)
+                .on_click(cx.listener(move |this, _, cx| {
+                    this.update_settings::<WorkspaceSettings>(cx, move |settings| {
+                        settings.autosave = Some(setting);
+                    });
+                }))
+        })
+        .collect::<Vec<_>>();
+
+        render_section("Autosave")
+            .child(render_row("Save edited buffers").child(h_flex().gap_1().children(buttons)))
+    }
+
+    fn render_tabs(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let settings = ItemSettings::get_global(cx);
+        let close_on_right = settings.close_position.right();
+        let git_status = settings.git_status;
+        let hover_preview = settings.hover_preview;
+        let close_position_buttons = [
+            (ClosePosition::Left, "Left"),
+            (ClosePosition::Right, "Right"),
+        ]
+        .into_iter()
+        .map(|(close_position, label)| {
+            Button::new(SharedString::from(format!("close-position-{label}")), label)
+                .selected(close_position.right() == close_on_right)
+                .on_click(cx.listener(move |this, _, cx| {
+                    let close_position = close_position.clone();
+                    this.update_settings::<ItemSettings>(cx, move |settings| {
+                        settings.close_position = Some(close_position);
+                    });
+                }))
+        })
+        .collect::<Vec<_>>();
+
+        render_section("Tabs")
+            .child(
+                render_row("Close button position")
+                    .child(h_flex().gap_1().children(close_position_buttons)),
+            )
+            .child(render_checkbox(
+                "tabs-git-status",
+                "Show the Git status of files",
+                git_status,
+                cx.listener(|this, selection: &Selection, cx| {
+                    let value = *selection == Selection::Selected;
+                    this.update_settings::<ItemSettings>(cx, move |settings| {
+                        settings.git_status = Some(value);
+                    });
+                }),
+            ))
+            .child(render_checkbox(
+                "tabs-hover-preview",
+                "Preview the content of a tab when hovering it",
+                hover_preview,
+                cx.listener(|this, selection: &Selection, cx| {
+                    let value = *selection == Selection::Selected;
+                    this.update_settings::<ItemSettings>(cx, move |settings| {
+                        settings.hover_preview = Some(value);
+                    });
+                }),
+            ))
+    }
+}
+
+fn render_section(title: &'static str) -> Div {
+    v_flex()
+        .gap_2()
+        .child(Headline::new(title).size(HeadlineSize::Small))
+}
+
+fn render_row(label: impl Into<SharedString>) -> Div {
+    h_flex()
+        .justify_between()
+        .gap_4()
+        .child(Label::new(label.into()))
+}
+
+fn render_checkbox(
+    id: &'static str,
+    label: &'static str,
+    checked: bool,
+    on_click: impl 'static + Fn(&Selection, &mut WindowContext) + Send + Sync,
+) -> impl IntoElement {
+    let selection = if checked {
+        Selection::Selected
+    } else {
+        Selection::Unselected
+    };
+    h_flex()
+        .gap_2()
+        .child(Checkbox::new(id, selection).on_click(on_click))
+        .child(Label::new(label))
+}
+
+impl Render for SettingsEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("settings-editor")
+            .key_context("SettingsEditor")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .w(rems(40.))
+                    .mx_auto()
+                    .p_4()
+                    .gap_6()
+                    .child(self.render_docks(cx))
+                    .child(self.render_autosave(cx))
+                    .child(self.render_tabs(cx)),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for SettingsEditor {}
+
+impl FocusableView for SettingsEditor {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for SettingsEditor {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        Label::new("Settings")
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("settings editor")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        Some(cx.new_view(|cx| Self::new(self.workspace.clone(), cx)))
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
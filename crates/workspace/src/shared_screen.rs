@@ -7,12 +7,18 @@ use call::participant::{Frame, RemoteVideoTrack};
 use client::{proto::PeerId, User};
 use futures::StreamExt;
 use gpui::{
-    div, img, AppContext, Element, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
-    ParentElement, Render, SharedString, Styled, Task, View, ViewContext, VisualContext,
-    WindowContext,
+    actions, div, img, px, AppContext, Element, EventEmitter, FocusHandle, FocusableView,
+    InteractiveElement, ParentElement, Render, SharedString, Styled, Task, View, ViewContext,
+    VisualContext, WindowContext,
 };
 use std::sync::{Arc, Weak};
-use ui::{h_flex, prelude::*, Icon, IconName, Label};
+use ui::{h_flex, prelude::*, Icon, IconButton, IconName, Label, Tooltip};
+
+actions!(shared_screen, [ZoomIn, ZoomOut, FitToPane]);
+
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 4.;
+const ZOOM_STEP: f32 = 1.25;
 
 pub enum Event {
     Close,
@@ -26,6 +32,9 @@ pub struct SharedScreen {
     nav_history: Option<ItemNavHistory>,
     _maintain_frame: Task<Result<()>>,
     focus: FocusHandle,
+    /// The scale at which the screen is shown, relative to its actual size, or
+    /// `None` to fit it to the pane.
+    zoom: Option<f32>,
 }
 
 impl SharedScreen {
@@ -54,8 +63,64 @@ impl SharedScreen {
                 Ok(())
             }),
             focus: cx.focus_handle(),
+            zoom: None,
         }
     }
+
+    fn zoom_in(&mut self, _: &ZoomIn, cx: &mut ViewContext<Self>) {
+        self.set_zoom(self.zoom.unwrap_or(1.) * ZOOM_STEP, cx);
+    }
+
+    fn zoom_out(&mut self, _: &ZoomOut, cx: &mut ViewContext<Self>) {
+        self.set_zoom(self.zoom.unwrap_or(1.) / ZOOM_STEP, cx);
+    }
+
+    fn set_zoom(&mut self, zoom: f32, cx: &mut ViewContext<Self>) {
+        self.zoom = Some(zoom.clamp(MIN_ZOOM, MAX_ZOOM));
+        cx.notify();
+    }
+
+    fn fit_to_pane(&mut self, _: &FitToPane, cx: &mut ViewContext<Self>) {
+        self.zoom = None;
+        cx.notify();
+    }
+
+    fn render_zoom_controls(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let label = match self.zoom {
+            Some(zoom) => format!("{}%", (zoom * 100.).round()),
+            None => "Fit".to_string(),
+        };
+        h_flex()
+            .absolute()
+            .top_2()
+            .right_2()
+            .gap_1()
+            .p_1()
+            .rounded_md()
+            .bg(cx.theme().colors().elevated_surface_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                IconButton::new("zoom-out", IconName::Dash)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::for_action("Zoom Out", &ZoomOut, cx))
+                    .on_click(cx.listener(|this, _, cx| this.zoom_out(&ZoomOut, cx))),
+            )
+            .child(Label::new(label).size(LabelSize::Small))
+            .child(
+                IconButton::new("zoom-in", IconName::Plus)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::for_action("Zoom In", &ZoomIn, cx))
+                    .on_click(cx.listener(|this, _, cx| this.zoom_in(&ZoomIn, cx))),
+            )
+            .child(
+                IconButton::new("fit-to-pane", IconName::Maximize)
+                    .icon_size(IconSize::Small)
+                    .selected(self.zoom.is_none())
+                    .tooltip(|cx| Tooltip::for_action("Fit to Pane", &FitToPane, cx))
+                    .on_click(cx.listener(|this, _, cx| this.fit_to_pane(&FitToPane, cx))),
+            )
+    }
 }
 
 impl EventEmitter<Event> for SharedScreen {}
@@ -67,15 +132,36 @@ impl FocusableView for SharedScreen {
 }
 impl Render for SharedScreen {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let scale_factor = cx.scale_factor();
+        let zoom = self.zoom;
+        let screen = self.frame.as_ref().map(|frame| {
+            let image = img(frame.image());
+            match zoom {
+                Some(zoom) => image
+                    .flex_none()
+                    .w(px(frame.width() as f32 / scale_factor * zoom))
+                    .h(px(frame.height() as f32 / scale_factor * zoom)),
+                None => image.size_full(),
+            }
+        });
+
         div()
+            .key_context("SharedScreen")
+            .on_action(cx.listener(Self::zoom_in))
+            .on_action(cx.listener(Self::zoom_out))
+            .on_action(cx.listener(Self::fit_to_pane))
             .bg(cx.theme().colors().editor_background)
             .track_focus(&self.focus)
+            .relative()
             .size_full()
-            .children(
-                self.frame
-                    .as_ref()
-                    .map(|frame| img(frame.image()).size_full()),
+            .child(
+                div()
+                    .id("shared-screen")
+                    .size_full()
+                    .overflow_scroll()
+                    .children(screen),
             )
+            .child(self.render_zoom_controls(cx))
     }
 }
 
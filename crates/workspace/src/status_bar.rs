@@ -1,10 +1,12 @@
-use crate::{ItemHandle, Pane};
+use crate::{ItemHandle, Pane, ToggleReadOnly, Workspace};
 use gpui::{
-    AnyView, IntoElement, ParentElement, Render, Styled, Subscription, View, ViewContext,
-    WindowContext,
+    div, AnyView, Entity, IntoElement, ParentElement, Render, Styled, Subscription, View,
+    ViewContext, WindowContext,
 };
+use itertools::Itertools;
+use settings::{SelectProfile, SettingsStore};
 use std::any::TypeId;
-use ui::{h_flex, prelude::*};
+use ui::{h_flex, prelude::*, Tooltip};
 use util::ResultExt;
 
 pub trait StatusItemView: Render {
@@ -25,9 +27,22 @@ trait StatusItemViewHandle: Send {
     fn item_type(&self) -> TypeId;
 }
 
+/// Which side of the status bar an item is shown on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StatusItemSide {
+    Left,
+    Right,
+}
+
+struct StatusItemEntry {
+    /// Items are kept sorted by this key, from the edge of the bar inwards.
+    order: i32,
+    item: Box<dyn StatusItemViewHandle>,
+}
+
 pub struct StatusBar {
-    left_items: Vec<Box<dyn StatusItemViewHandle>>,
-    right_items: Vec<Box<dyn StatusItemViewHandle>>,
+    left_items: Vec<StatusItemEntry>,
+    right_items: Vec<StatusItemEntry>,
     active_pane: View<Pane>,
     _observe_active_pane: Subscription,
 }
@@ -52,13 +67,16 @@ impl StatusBar {
         h_flex()
             .gap_2()
             .overflow_x_hidden()
-            .children(self.left_items.iter().map(|item| item.to_any()))
+            .children(self.left_items.iter().map(|entry| entry.item.to_any()))
     }
 
     fn render_right_tools(&self, _: &mut ViewContext<Self>) -> impl IntoElement {
-        h_flex()
-            .gap_2()
-            .children(self.right_items.iter().rev().map(|item| item.to_any()))
+        h_flex().gap_2().children(
+            self.right_items
+                .iter()
+                .rev()
+                .map(|entry| entry.item.to_any()),
+        )
     }
 }
 
@@ -78,32 +96,73 @@ impl StatusBar {
     pub fn add_left_item<T>(&mut self, item: View<T>, cx: &mut ViewContext<Self>)
     where
         T: 'static + StatusItemView,
+    {
+        self.add_item_with_order(StatusItemSide::Left, 0, item, cx);
+    }
+
+    /// Adds an item to the given side of the bar, placed among the other items by
+    /// `order`, from the edge of the bar inwards. Items added with [`Self::add_left_item`]
+    /// and [`Self::add_right_item`] have order 0, and items with the same order are shown
+    /// in the order they were added. This way, an item that's added and removed while
+    /// the app runs always shows up in the same place.
+    pub fn add_item_with_order<T>(
+        &mut self,
+        side: StatusItemSide,
+        order: i32,
+        item: View<T>,
+        cx: &mut ViewContext<Self>,
+    ) where
+        T: 'static + StatusItemView,
     {
         let active_pane_item = self.active_pane.read(cx).active_item();
         item.set_active_pane_item(active_pane_item.as_deref(), cx);
 
-        self.left_items.push(Box::new(item));
+        let items = match side {
+            StatusItemSide::Left => &mut self.left_items,
+            StatusItemSide::Right => &mut self.right_items,
+        };
+        let ix = items.partition_point(|entry| entry.order <= order);
+        items.insert(
+            ix,
+            StatusItemEntry {
+                order,
+                item: Box::new(item),
+            },
+        );
         cx.notify();
     }
 
+    /// Removes an item added with any of the `add_*` methods, if it's still in the bar.
+    pub fn remove_item<T: StatusItemView>(&mut self, item: &View<T>, cx: &mut ViewContext<Self>) {
+        let item_id = item.entity_id();
+        let len = self.left_items.len() + self.right_items.len();
+        self.left_items
+            .retain(|entry| entry.item.to_any().entity_id() != item_id);
+        self.right_items
+            .retain(|entry| entry.item.to_any().entity_id() != item_id);
+        if self.left_items.len() + self.right_items.len() != len {
+            cx.notify();
+        }
+    }
+
     pub fn item_of_type<T: StatusItemView>(&self) -> Option<View<T>> {
         self.left_items
             .iter()
             .chain(self.right_items.iter())
-            .find_map(|item| item.to_any().clone().downcast().log_err())
+            .find_map(|entry| entry.item.to_any().clone().downcast().log_err())
     }
 
     pub fn position_of_item<T>(&self) -> Option<usize>
     where
         T: StatusItemView,
     {
-        for (index, item) in self.left_items.iter().enumerate() {
-            if item.item_type() == TypeId::of::<T>() {
+        for (index, entry) in self.left_items.iter().enumerate() {
+            if entry.item.item_type() == TypeId::of::<T>() {
                 return Some(index);
             }
         }
-        for (index, item) in self.right_items.iter().enumerate() {
-            if item.item_type() == TypeId::of::<T>() {
+        for (index, entry) in self.right_items.iter().enumerate() {
+            if entry.item.item_type() == TypeId::of::<T>() {
                 return Some(index + self.left_items.len());
             }
         }
@@ -121,12 +180,21 @@ impl StatusBar {
         let active_pane_item = self.active_pane.read(cx).active_item();
         item.set_active_pane_item(active_pane_item.as_deref(), cx);
 
-        if position < self.left_items.len() {
-            self.left_items.insert(position + 1, Box::new(item))
+        // The item shares the order of the one it's inserted after, so that it stays
+        // next to it when other items are added.
+        let (items, position) = if position < self.left_items.len() {
+            (&mut self.left_items, position)
         } else {
-            self.right_items
-                .insert(position + 1 - self.left_items.len(), Box::new(item))
-        }
+            (&mut self.right_items, position - self.left_items.len())
+        };
+        let order = items[position].order;
+        items.insert(
+            position + 1,
+            StatusItemEntry {
+                order,
+                item: Box::new(item),
+            },
+        );
         cx.notify()
     }
 
@@ -143,11 +211,7 @@ impl StatusBar {
     where
         T: 'static + StatusItemView,
     {
-        let active_pane_item = self.active_pane.read(cx).active_item();
-        item.set_active_pane_item(active_pane_item.as_deref(), cx);
-
-        self.right_items.push(Box::new(item));
-        cx.notify();
+        self.add_item_with_order(StatusItemSide::Right, 0, item, cx);
     }
 
     pub fn set_active_pane(&mut self, active_pane: &View<Pane>, cx: &mut ViewContext<Self>) {
@@ -159,8 +223,10 @@ impl StatusBar {
 
     fn update_active_pane_item(&mut self, cx: &mut ViewContext<Self>) {
         let active_pane_item = self.active_pane.read(cx).active_item();
-        for item in self.left_items.iter().chain(&self.right_items) {
-            item.set_active_pane_item(active_pane_item.as_deref(), cx);
+        for entry in self.left_items.iter().chain(&self.right_items) {
+            entry
+                .item
+                .set_active_pane_item(active_pane_item.as_deref(), cx);
         }
     }
 }
@@ -190,3 +256,232 @@ impl From<&dyn StatusItemViewHandle> for AnyView {
         val.to_any().clone()
     }
 }
+
+/// Shows the keystrokes typed so far towards a multi-stroke key binding,
+/// e.g. `cmd-k` while the rest of `cmd-k cmd-left` has yet to be typed.
+pub struct PendingKeystrokesIndicator {
+    pending_keystrokes: Option<String>,
+    _observe_pending_input: Subscription,
+}
+
+impl PendingKeystrokesIndicator {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            pending_keystrokes: None,
+            _observe_pending_input: cx.observe_pending_input(|this, cx| {
+                this.pending_keystrokes = cx.pending_input_keystrokes().map(|keystrokes| {
+                    keystrokes
+                        .iter()
+                        .map(|keystroke| keystroke.to_string())
+                        .join(" ")
+                });
+                cx.notify();
+            }),
+        }
+    }
+}
+
+impl Render for PendingKeystrokesIndicator {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(keystrokes) = self.pending_keystrokes.as_ref() else {
+            return div().into_any_element();
+        };
+
+        Label::new(format!("{keystrokes} …"))
+            .size(LabelSize::Small)
+            .color(Color::Muted)
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for PendingKeystrokesIndicator {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+}
+
+/// Shows the name of the active settings profile, if any. Clicking it opens
+/// the profile picker.
+pub struct SettingsProfileIndicator {
+    _observe_settings: Subscription,
+}
+
+impl SettingsProfileIndicator {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            _observe_settings: cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        }
+    }
+}
+
+impl Render for SettingsProfileIndicator {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(profile) = cx.global::<SettingsStore>().active_profile() else {
+            return div().into_any_element();
+        };
+
+        Button::new("settings-profile", format!("Profile: {profile}"))
+            .label_size(LabelSize::Small)
+            .tooltip(|cx| Tooltip::for_action("Switch Settings Profile", &SelectProfile, cx))
+            .on_click(|_, cx| cx.dispatch_action(Box::new(SelectProfile)))
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for SettingsProfileIndicator {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+}
+
+/// Shows that the workspace is in read-only mode. Clicking it makes the
+/// workspace editable again.
+pub struct ReadOnlyIndicator {
+    read_only: bool,
+    _subscription: Subscription,
+}
+
+impl ReadOnlyIndicator {
+    pub fn new(workspace: &View<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            read_only: false,
+            _subscription: cx.subscribe(workspace, |this, _, event, cx| {
+                if let crate::Event::ReadOnlyChanged(read_only) = event {
+                    this.read_only = *read_only;
+                    cx.notify();
+                }
+            }),
+        }
+    }
+}
+
+impl Render for ReadOnlyIndicator {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        if !self.read_only {
+            return div().into_any_element();
+        }
+
+        Button::new("read-only-mode", "Read-Only")
+            .icon(IconName::FileLock)
+            .icon_position(IconPosition::Start)
+            .icon_size(IconSize::Small)
+            .label_size(LabelSize::Small)
+            .tooltip(|cx| Tooltip::for_action("Leave Read-Only Mode", &ToggleReadOnly, cx))
+            .on_click(|_, cx| cx.dispatch_action(Box::new(ToggleReadOnly)))
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for ReadOnlyIndicator {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::init_test;
+    use fs::FakeFs;
+    use gpui::{KeyBinding, TestAppContext, VisualContext};
+    use project::Project;
+
+    struct TestStatusItem(&'static str);
+
+    impl Render for TestStatusItem {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().child(self.0)
+        }
+    }
+
+    impl StatusItemView for TestStatusItem {
+        fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+    }
+
+    #[gpui::test]
+    async fn test_status_items_keep_their_order(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let status_bar = workspace.update(cx, |workspace, _| workspace.status_bar().clone());
+
+        let labels = |cx: &mut gpui::VisualTestContext| {
+            status_bar.update(cx, |status_bar, cx| {
+                status_bar
+                    .right_items
+                    .iter()
+                    .filter_map(|entry| entry.item.to_any().downcast::<TestStatusItem>().ok())
+                    .map(|item| item.read(cx).0)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        let language_server = cx.new_view(|_| TestStatusItem("language server"));
+        status_bar.update(cx, |status_bar, cx| {
+            status_bar.add_item_with_order(
+                StatusItemSide::Right,
+                10,
+                cx.new_view(|_| TestStatusItem("cursor")),
+                cx,
+            );
+            status_bar.add_item_with_order(StatusItemSide::Right, 20, language_server.clone(), cx);
+            status_bar.add_item_with_order(
+                StatusItemSide::Right,
+                30,
+                cx.new_view(|_| TestStatusItem("language")),
+                cx,
+            );
+        });
+        assert_eq!(labels(cx), ["cursor", "language server", "language"]);
+
+        status_bar.update(cx, |status_bar, cx| {
+            status_bar.remove_item(&language_server, cx)
+        });
+        assert_eq!(labels(cx), ["cursor", "language"]);
+
+        status_bar.update(cx, |status_bar, cx| {
+            status_bar.add_right_item(cx.new_view(|_| TestStatusItem("unordered")), cx);
+            status_bar.add_item_with_order(StatusItemSide::Right, 20, language_server.clone(), cx);
+        });
+        assert_eq!(
+            labels(cx),
+            ["unordered", "cursor", "language server", "language"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_pending_keystrokes_indicator(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.bind_keys([KeyBinding::new(
+                "ctrl-k ctrl-b",
+                crate::ToggleBottomDock,
+                Some("Workspace"),
+            )])
+        });
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let indicator = cx.new_view(|cx| PendingKeystrokesIndicator::new(cx));
+        workspace.update(cx, |workspace, cx| {
+            workspace.status_bar().update(cx, |status_bar, cx| {
+                status_bar.add_right_item(indicator.clone(), cx)
+            });
+            cx.focus_view(workspace.active_pane());
+        });
+        let pending_keystrokes = |cx: &mut gpui::VisualTestContext| {
+            indicator.read_with(cx, |indicator, _| indicator.pending_keystrokes.clone())
+        };
+        assert_eq!(pending_keystrokes(cx), None);
+
+        // The first keystroke of the chord is shown until the chord is completed.
+        cx.simulate_keystrokes("ctrl-k");
+        assert_eq!(pending_keystrokes(cx).as_deref(), Some("^K"));
+        cx.simulate_keystrokes("ctrl-b");
+        assert_eq!(pending_keystrokes(cx), None);
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.bottom_dock().read(cx).is_open())
+        });
+
+        // Typing a keystroke that doesn't continue the chord cancels it.
+        cx.simulate_keystrokes("ctrl-k");
+        assert_eq!(pending_keystrokes(cx).as_deref(), Some("^K"));
+        cx.simulate_keystrokes("escape");
+        assert_eq!(pending_keystrokes(cx), None);
+    }
+}
@@ -0,0 +1,328 @@
+use collections::HashSet;
+use futures::channel::oneshot;
+use gpui::{
+    actions, AppContext, DismissEvent, EntityId, EventEmitter, FocusHandle, FocusableView, Render,
+    SharedString, ViewContext,
+};
+use menu::{SelectFirst, SelectLast, SelectNext, SelectPrev};
+use ui::{prelude::*, Checkbox, Selection};
+
+use crate::{item::ItemHandle, ModalView, Workspace};
+
+actions!(unsaved_changes_modal, [ToggleSelectedItem]);
+
+/// The ids of the items the user chose to save, or `None` if closing was cancelled.
+pub type UnsavedChangesAnswer = Option<HashSet<EntityId>>;
+
+struct UnsavedItem {
+    item_id: EntityId,
+    group: Option<SharedString>,
+    label: SharedString,
+    detail: Option<SharedString>,
+    save: bool,
+}
+
+/// Lists every dirty item that is about to be closed, so that the user can
+/// decide which ones to save and which ones to discard in a single step.
+pub struct UnsavedChangesModal {
+    items: Vec<UnsavedItem>,
+    selected_ix: usize,
+    quitting: bool,
+    answer: Option<oneshot::Sender<UnsavedChangesAnswer>>,
+    focus_handle: FocusHandle,
+}
+
+impl UnsavedItem {
+    fn new(item: &dyn ItemHandle, group: Option<SharedString>, cx: &AppContext) -> Self {
+        let project_path = item.project_path(cx);
+        let label = project_path
+            .as_ref()
+            .and_then(|path| path.path.file_name())
+            .map(|name| name.to_string_lossy().to_string().into())
+            .or_else(|| item.tab_description(0, cx))
+            .unwrap_or_else(|| "untitled".into());
+        let detail = project_path
+            .as_ref()
+            .and_then(|path| path.path.parent())
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map(|parent| parent.to_string_lossy().to_string().into());
+        Self {
+            item_id: item.item_id(),
+            group,
+            label,
+            detail,
+            save: true,
+        }
+    }
+}
+
+impl UnsavedChangesModal {
+    /// Shows the modal for the given items. The returned receiver is cancelled
+    /// if the modal gets dismissed without an answer.
+    pub fn prompt(
+        workspace: &mut Workspace,
+        items: &[Box<dyn ItemHandle>],
+        cx: &mut ViewContext<Workspace>,
+    ) -> oneshot::Receiver<UnsavedChangesAnswer> {
+        let items = items
+            .iter()
+            .map(|item| UnsavedItem::new(item.as_ref(), None, cx))
+            .collect();
+        Self::show(workspace, items, false, cx)
+    }
+
+    /// Shows the modal for the dirty items of every window before quitting, with
+    /// the items listed under the name of the window they belong to.
+    pub fn prompt_for_quit(
+        workspace: &mut Workspace,
+        groups: &[(SharedString, Vec<Box<dyn ItemHandle>>)],
+        cx: &mut ViewContext<Workspace>,
+    ) -> oneshot::Receiver<UnsavedChangesAnswer> {
+        let items = groups
+            .iter()
+            .flat_map(|(group, items)| {
+                items
+                    .iter()
+                    .map(|item| UnsavedItem::new(item.as_ref(), Some(group.clone()), cx))
+            })
+            .collect();
+        Self::show(workspace, items, true, cx)
+    }
+
+    fn show(
+        workspace: &mut Workspace,
+        items: Vec<UnsavedItem>,
+        quitting: bool,
+        cx: &mut ViewContext<Workspace>,
+    ) -> oneshot::Receiver<UnsavedChangesAnswer> {
+        let (tx, rx) = oneshot::channel();
+        let build = move |cx: &mut ViewContext<Self>| Self {
+            items,
+            selected_ix: 0,
+            quitting,
+            answer: Some(tx),
+            focus_handle: cx.focus_handle(),
+        };
+        // Items can be closed while the modal is open, e.g. by closing another pane.
+        // Toggling would dismiss the open prompt and drop the new one, so the new
+        // prompt is shown on top and answered first.
+        if workspace.active_modal::<Self>(cx).is_some() {
+            workspace.push_modal(cx, build);
+        } else {
+            workspace.toggle_modal(cx, build);
+        }
+        rx
+    }
+
+    /// Flips whether the item at `ix` gets saved or discarded.
+    pub fn toggle_item(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(item) = self.items.get_mut(ix) {
+            item.save = !item.save;
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        if self.selected_ix + 1 < self.items.len() {
+            self.selected_ix += 1;
+            cx.notify();
+        }
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        if self.selected_ix > 0 {
+            self.selected_ix -= 1;
+            cx.notify();
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, cx: &mut ViewContext<Self>) {
+        self.selected_ix = 0;
+        cx.notify();
+    }
+
+    fn select_last(&mut self, _: &SelectLast, cx: &mut ViewContext<Self>) {
+        self.selected_ix = self.items.len().saturating_sub(1);
+        cx.notify();
+    }
+
+    fn toggle_selected_item(&mut self, _: &ToggleSelectedItem, cx: &mut ViewContext<Self>) {
+        self.toggle_item(self.selected_ix, cx);
+    }
+
+    /// The index of the item that `space` toggles.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn selected_index(&self) -> usize {
+        self.selected_ix
+    }
+
+    /// Whether each listed item is marked for saving, in order.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn items_to_save(&self) -> Vec<bool> {
+        self.items.iter().map(|item| item.save).collect()
+    }
+
+    /// The listed items, in order, labelled with the window they belong to when quitting.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn item_labels(&self) -> Vec<(Option<SharedString>, SharedString)> {
+        self.items
+            .iter()
+            .map(|item| (item.group.clone(), item.label.clone()))
+            .collect()
+    }
+
+    fn save_all(&mut self, cx: &mut ViewContext<Self>) {
+        for item in &mut self.items {
+            item.save = true;
+        }
+        self.confirm(&menu::Confirm, cx);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let item_ids = self
+            .items
+            .iter()
+            .filter(|item| item.save)
+            .map(|item| item.item_id)
+            .collect();
+        self.respond(Some(item_ids), cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        self.respond(None, cx);
+    }
+
+    fn respond(&mut self, answer: UnsavedChangesAnswer, cx: &mut ViewContext<Self>) {
+        if let Some(tx) = self.answer.take() {
+            tx.send(answer).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn render_item(&self, ix: usize, item: &UnsavedItem, cx: &mut ViewContext<Self>) -> Div {
+        let selection = if item.save {
+            Selection::Selected
+        } else {
+            Selection::Unselected
+        };
+        h_flex()
+            .gap_2()
+            .px_1()
+            .rounded_md()
+            .when(ix == self.selected_ix, |row| {
+                row.bg(cx.theme().colors().ghost_element_selected)
+            })
+            .child(
+                Checkbox::new(("unsaved-item", ix), selection)
+                    .on_click(cx.listener(move |this, _, cx| this.toggle_item(ix, cx))),
+            )
+            .child(Label::new(item.label.clone()))
+            .children(item.detail.clone().map(|detail| {
+                Label::new(detail)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+    }
+}
+
+impl EventEmitter<DismissEvent> for UnsavedChangesModal {}
+
+impl ModalView for UnsavedChangesModal {}
+
+impl FocusableView for UnsavedChangesModal {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for UnsavedChangesModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let save_count = self.items.iter().filter(|item| item.save).count();
+        let mut items = Vec::new();
+        for (ix, item) in self.items.iter().enumerate() {
+            let previous_group = ix
+                .checked_sub(1)
+                .and_then(|prev_ix| self.items[prev_ix].group.as_ref());
+            if let Some(group) = item
+                .group
+                .as_ref()
+                .filter(|&group| Some(group) != previous_group)
+            {
+                items.push(
+                    Label::new(group.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .into_any_element(),
+                );
+            }
+            items.push(self.render_item(ix, item, cx).into_any_element());
+        }
+
+        v_flex()
+            .key_context("UnsavedChangesModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::toggle_selected_item))
+            .elevation_3(cx)
+            .w(rems(34.))
+            .p_4()
+            .gap_2()
+            .child(Headline::new(format!(
+                "Do you want to save changes to {} items?",
+                self.items.len()
+            )))
+            .child(
+                Label::new("Unchecked items will be closed without saving.")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                v_flex()
+                    .id("unsaved-items")
+                    .max_h(rems(20.))
+                    .overflow_y_scroll()
+                    .gap_1()
+                    .children(items),
+            )
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_1()
+                    .child(
+                        Button::new("cancel", "Cancel")
+                            .style(ButtonStyle::Subtle)
+                            .on_click(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx))),
+                    )
+                    .child(
+                        Button::new(
+                            "save-selected",
+                            if save_count == 0 {
+                                "Discard All"
+                            } else {
+                                "Save Selected"
+                            },
+                        )
+                        .style(ButtonStyle::Subtle)
+                        .on_click(cx.listener(|this, _, cx| this.confirm(&menu::Confirm, cx))),
+                    )
+                    .child(
+                        Button::new(
+                            "save-all",
+                            if self.quitting {
+                                "Save All and Quit"
+                            } else {
+                                "Save All"
+                            },
+                        )
+                        .style(ButtonStyle::Filled)
+                        .color(Color::Accent)
+                        .on_click(cx.listener(|this, _, cx| this.save_all(cx))),
+                    ),
+            )
+    }
+}
@@ -1,14 +1,20 @@
+pub mod action_registry;
 pub mod dock;
+mod floating_panel;
 pub mod item;
-mod modal_layer;
+mod keymap_editor;
+mod modal_manager;
 pub mod notifications;
 pub mod pane;
 pub mod pane_group;
+pub mod panels;
 mod persistence;
 pub mod searchable;
+mod settings_editor;
 pub mod shared_screen;
 mod status_bar;
 mod toolbar;
+mod unsaved_changes_modal;
 mod workspace_settings;
 
 use anyhow::{anyhow, Context as _, Result};
@@ -17,30 +23,38 @@ use client::{
     proto::{self, ErrorCode, PeerId},
     Client, ErrorExt, Status, TypedEnvelope, UserStore,
 };
-use collections::{hash_map, HashMap, HashSet};
+use collections::{hash_map, HashMap, HashSet, VecDeque};
 use derive_more::{Deref, DerefMut};
-use dock::{Dock, DockPosition, Panel, PanelButtons, PanelHandle};
+use dock::{Dock, DockEvent, DockPosition, DraggedPanel, Panel, PanelButtons, PanelHandle};
 use futures::{
     channel::{mpsc, oneshot},
     future::try_join_all,
     Future, FutureExt, StreamExt,
 };
 use gpui::{
-    actions, canvas, div, impl_actions, point, px, size, Action, AnyElement, AnyModel, AnyView,
-    AnyWeakView, AppContext, AsyncAppContext, AsyncWindowContext, Bounds, Context, Div,
-    DragMoveEvent, Element, ElementContext, Entity, EntityId, EventEmitter, FocusHandle,
-    FocusableView, Global, GlobalPixels, InteractiveElement, IntoElement, KeyContext, LayoutId,
-    ManagedView, Model, ModelContext, ParentElement, PathPromptOptions, Pixels, Point, PromptLevel,
-    Render, SharedString, Size, Styled, Subscription, Task, View, ViewContext, VisualContext,
-    WeakView, WindowBounds, WindowContext, WindowHandle, WindowOptions,
+    actions, canvas, div, impl_actions, point, px, register_action_variants, size, Action,
+    ActionVariants, AnyElement, AnyModel, AnyView, AnyWeakView, AnyWindowHandle, AppContext,
+    AsyncAppContext, AsyncWindowContext, Bounds, Context, Div, DragMoveEvent, Element,
+    ElementContext, Entity, EntityId, EventEmitter, ExternalPaths, FocusHandle, FocusableView,
+    Global, GlobalPixels, InteractiveElement, IntoElement, KeyContext, LayoutId, ManagedView,
+    Model, ModelContext, MouseDownEvent, ParentElement, PathPromptOptions, Pixels, Point,
+    PromptLevel, Render, SharedString, Size, Styled, Subscription, SwipeEvent, Task, View,
+    ViewContext, VisualContext, WeakView, WindowBounds, WindowContext, WindowHandle, WindowOptions,
+};
+use item::{
+    settings_for_item, FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings,
+    ProjectItem, SerializableItem, SerializableItemHandle,
 };
-use item::{FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings, ProjectItem};
 use itertools::Itertools;
+pub use keymap_editor::KeymapEditor;
 use language::{LanguageRegistry, Rope};
 use lazy_static::lazy_static;
-pub use modal_layer::*;
+pub use modal_manager::*;
 use node_runtime::NodeRuntime;
-use notifications::{simple_message_notification::MessageNotification, NotificationHandle};
+use notifications::{
+    log_notification, notification_id, simple_message_notification::MessageNotification,
+    NotificationHandle, NotificationSeverity, NotifyTaskExt,
+};
 pub use pane::*;
 pub use pane_group::*;
 use persistence::{model::SerializedWorkspace, SerializedWindowsBounds, DB};
@@ -51,14 +65,18 @@ pub use persistence::{
 use postage::stream::Stream;
 use project::{Project, ProjectEntryId, ProjectPath, Worktree, WorktreeId};
 use serde::Deserialize;
-use settings::Settings;
+use settings::{Settings, SettingsStore, SwitchProfile};
+pub use settings_editor::SettingsEditor;
 use shared_screen::SharedScreen;
 use status_bar::StatusBar;
-pub use status_bar::StatusItemView;
+pub use status_bar::{
+    PendingKeystrokesIndicator, ReadOnlyIndicator, SettingsProfileIndicator, StatusItemSide,
+    StatusItemView,
+};
 use std::{
     any::TypeId,
     borrow::Cow,
-    cmp, env,
+    cmp, env, mem,
     path::{Path, PathBuf},
     sync::Weak,
     sync::{atomic::AtomicUsize, Arc},
@@ -68,15 +86,41 @@ use theme::{ActiveTheme, ThemeSettings};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
 use ui::Label;
+pub use unsaved_changes_modal::{UnsavedChangesAnswer, UnsavedChangesModal};
 use util::ResultExt;
 use uuid::Uuid;
-pub use workspace_settings::{AutosaveSetting, WorkspaceSettings};
+pub use workspace_settings::{
+    ActivePaneIndicator, ActivePaneIndicatorStyle, AutosaveSetting, MouseBindings,
+    WorkspaceSettings,
+};
 
 use crate::persistence::{
-    model::{DockData, DockStructure, SerializedItem, SerializedPane, SerializedPaneGroup},
+    model::{
+        DockData, DockStructure, SerializedClosedItem, SerializedItem, SerializedPane,
+        SerializedPaneGroup,
+    },
     SerializedAxis,
 };
 
+/// How many recently closed items are kept around in the database, to be reopened
+/// after a restart.
+const MAX_PERSISTED_CLOSED_ITEMS: usize = 20;
+
+/// Scale applied to the UI of a window, on top of the UI font size, by
+/// [`ZoomIn`], [`ZoomOut`] and [`ResetZoom`].
+const DEFAULT_UI_ZOOM: f32 = 1.;
+const MIN_UI_ZOOM: f32 = 0.5;
+const MAX_UI_ZOOM: f32 = 3.;
+const UI_ZOOM_STEP: f32 = 0.1;
+
+/// [`SaveAll`] reports its progress in a toast once it saves at least this many items.
+const SAVE_ALL_PROGRESS_THRESHOLD: usize = 10;
+
+/// The toast reporting [`SaveAll`]'s progress, identified with [`notification_id`].
+struct SaveAllProgressToast;
+/// The notification listing the items [`SaveAll`] failed to save.
+struct SaveAllFailureNotification;
+
 lazy_static! {
     static ref ZED_WINDOW_SIZE: Option<Size<GlobalPixels>> = env::var("ZED_WINDOW_SIZE")
         .ok()
@@ -97,6 +141,7 @@ actions!(
         Open,
         NewFile,
         NewWindow,
+        NewWindowForProject,
         CloseWindow,
         CloseInactiveTabsAndPanes,
         AddFolderToProject,
@@ -105,9 +150,17 @@ actions!(
         ReloadActiveItem,
         ActivatePreviousPane,
         ActivateNextPane,
+        FocusNextPanel,
+        FocusPreviousPanel,
+        FocusPreviousPane,
         FollowNextCollaborator,
+        JumpBack,
+        JumpForward,
         NewTerminal,
         NewCenterTerminal,
+        SendSelectionToDock,
+        PasteToDock,
+        NewScratch,
         ToggleTerminalFocus,
         NewSearch,
         Feedback,
@@ -118,6 +171,16 @@ actions!(
         ToggleRightDock,
         ToggleBottomDock,
         CloseAllDocks,
+        MoveFocusedPanelToOppositeSide,
+        ToggleAllPanels,
+        OpenSettingsUI,
+        OpenKeymapEditor,
+        DiffActiveFile,
+        ZoomIn,
+        ZoomOut,
+        ResetZoom,
+        ToggleReadOnly,
+        Reload,
     ]
 );
 
@@ -132,6 +195,11 @@ pub struct ActivatePane(pub usize);
 #[derive(Clone, Deserialize, PartialEq)]
 pub struct ActivatePaneInDirection(pub SplitDirection);
 
+/// Runs several actions in turn, so that they can be bound to a single keystroke.
+/// Each entry is either an action name or a `[name, data]` pair, as in the keymap.
+#[derive(Clone, Deserialize, PartialEq)]
+pub struct RunActionSequence(pub Vec<serde_json::Value>);
+
 #[derive(Clone, Deserialize, PartialEq)]
 pub struct SwapPaneInDirection(pub SplitDirection);
 
@@ -156,6 +224,16 @@ pub struct CloseAllItemsAndPanes {
     pub save_intent: Option<SaveIntent>,
 }
 
+/// Opens the scratch buffer with the given name, creating it if it doesn't exist yet.
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenScratch {
+    pub name: String,
+    /// Whether to open the scratch buffer in a dock pane rather than the center.
+    #[serde(default)]
+    pub in_dock: bool,
+}
+
 impl_actions!(
     workspace,
     [
@@ -163,13 +241,36 @@ impl_actions!(
         ActivatePaneInDirection,
         CloseAllItemsAndPanes,
         NewFileInDirection,
+        OpenScratch,
         OpenTerminal,
+        RunActionSequence,
         Save,
         SaveAll,
         SwapPaneInDirection,
     ]
 );
 
+impl ActionVariants for ActivatePaneInDirection {
+    fn variants() -> Vec<(&'static str, Self)> {
+        SplitDirection::all()
+            .into_iter()
+            .map(|direction| (direction.name(), Self(direction)))
+            .collect()
+    }
+}
+
+impl ActionVariants for SwapPaneInDirection {
+    fn variants() -> Vec<(&'static str, Self)> {
+        SplitDirection::all()
+            .into_iter()
+            .map(|direction| (direction.name(), Self(direction)))
+            .collect()
+    }
+}
+
+register_action_variants!(ActivatePaneInDirection);
+register_action_variants!(SwapPaneInDirection);
+
 #[derive(Deserialize)]
 pub struct Toast {
     id: usize,
@@ -233,6 +334,11 @@ pub fn init(app_state: Arc<AppState>, cx: &mut AppContext) {
 
     cx.on_action(Workspace::close_global);
     cx.on_action(restart);
+    cx.on_action(|action: &SwitchProfile, cx: &mut AppContext| {
+        let profile = action.profile.clone();
+        cx.update_global(|store: &mut SettingsStore, cx| store.set_active_profile(profile, cx))
+            .log_err();
+    });
 
     cx.on_action({
         let app_state = Arc::downgrade(&app_state);
@@ -310,36 +416,78 @@ pub fn register_followable_item<I: FollowableItem>(cx: &mut AppContext) {
     );
 }
 
+type ItemConverter = Box<dyn Fn(&AnyView, &AppContext) -> Option<AnyView>>;
+
 #[derive(Default, Deref, DerefMut)]
-struct ItemDeserializers(
-    HashMap<
-        Arc<str>,
-        fn(
-            Model<Project>,
-            WeakView<Workspace>,
-            WorkspaceId,
-            ItemId,
-            &mut ViewContext<Pane>,
-        ) -> Task<Result<Box<dyn ItemHandle>>>,
-    >,
-);
+struct ItemConverters(HashMap<(TypeId, TypeId), ItemConverter>);
 
-impl Global for ItemDeserializers {}
-
-pub fn register_deserializable_item<I: Item>(cx: &mut AppContext) {
-    if let Some(serialized_item_kind) = I::serialized_item_kind() {
-        let deserializers = cx.default_global::<ItemDeserializers>();
-        deserializers.insert(
-            Arc::from(serialized_item_kind),
-            |project, workspace, workspace_id, item_id, cx| {
-                let task = I::deserialize(project, workspace, workspace_id, item_id, cx);
-                cx.foreground_executor()
-                    .spawn(async { Ok(Box::new(task.await?) as Box<_>) })
-            },
-        );
+impl Global for ItemConverters {}
+
+/// Lets items of type `I` act as views of type `V` through [`ItemHandle::act_as`], on
+/// top of what `I` supports through [`Item::act_as_type`]. This allows a crate to
+/// bridge item types that don't depend on each other.
+pub fn register_item_converter<I: Item, V: Render>(
+    cx: &mut AppContext,
+    convert: impl Fn(&View<I>, &AppContext) -> Option<View<V>> + 'static,
+) {
+    cx.default_global::<ItemConverters>().insert(
+        (TypeId::of::<I>(), TypeId::of::<V>()),
+        Box::new(move |item, cx| {
+            let item = item.clone().downcast::<I>().ok()?;
+            Some(convert(&item, cx)?.into())
+        }),
+    );
+}
+
+type SerializableItemDeserializer = fn(
+    Model<Project>,
+    WeakView<Workspace>,
+    WorkspaceId,
+    ItemId,
+    Option<String>,
+    &mut ViewContext<Pane>,
+) -> Task<Result<Box<dyn ItemHandle>>>;
+
+#[derive(Default)]
+struct SerializableItemRegistry {
+    deserializers_by_kind: HashMap<Arc<str>, SerializableItemDeserializer>,
+    handle_builders_by_type: HashMap<TypeId, fn(&AnyView) -> Box<dyn SerializableItemHandle>>,
+}
+
+impl Global for SerializableItemRegistry {}
+
+impl SerializableItemRegistry {
+    fn deserializer(&self, kind: &str) -> Option<SerializableItemDeserializer> {
+        self.deserializers_by_kind.get(kind).copied()
+    }
+
+    fn handle_builder(
+        &self,
+        type_id: TypeId,
+    ) -> Option<fn(&AnyView) -> Box<dyn SerializableItemHandle>> {
+        self.handle_builders_by_type.get(&type_id).copied()
     }
 }
 
+/// Registers an item type whose views are persisted with the workspace, restoring
+/// items of its [`SerializableItem::serialized_item_kind`] when a workspace is opened.
+pub fn register_serializable_item<I: SerializableItem>(cx: &mut AppContext) {
+    let registry = cx.default_global::<SerializableItemRegistry>();
+    registry.deserializers_by_kind.insert(
+        Arc::from(I::serialized_item_kind()),
+        |project, workspace, workspace_id, item_id, state, cx| {
+            let task = I::deserialize(project, workspace, workspace_id, item_id, state, cx);
+            cx.foreground_executor()
+                .spawn(async { Ok(Box::new(task.await?) as Box<_>) })
+        },
+    );
+    registry
+        .handle_builders_by_type
+        .insert(TypeId::of::<I>(), |this| {
+            Box::new(this.clone().downcast::<I>().unwrap())
+        });
+}
+
 pub struct AppState {
     pub languages: Arc<LanguageRegistry>,
     pub client: Arc<Client>,
@@ -383,7 +531,6 @@ impl AppState {
     #[cfg(any(test, feature = "test-support"))]
     pub fn test(cx: &mut AppContext) -> Arc<Self> {
         use node_runtime::FakeNodeRuntime;
-        use settings::SettingsStore;
 
         if !cx.has_global::<SettingsStore>() {
             let settings_store = SettingsStore::test(cx);
@@ -461,8 +608,38 @@ impl DelayedDebouncedEditAction {
 
 pub enum Event {
     PaneAdded(View<Pane>),
+    ActiveItemChanged,
     ContactRequestedJoin(u64),
     WorkspaceCreated(WeakView<Workspace>),
+    ReadOnlyChanged(bool),
+    /// An item was added to one of the center panes, either newly opened or moved
+    /// there from another pane.
+    ItemOpened {
+        pane: View<Pane>,
+        item: Box<dyn ItemHandle>,
+    },
+    /// An item was removed from one of the center panes.
+    ItemClosed {
+        pane: View<Pane>,
+        item_id: EntityId,
+    },
+    /// `pane` was split, with `new_pane` placed on the side given by `direction`.
+    PaneSplit {
+        pane: View<Pane>,
+        new_pane: View<Pane>,
+        direction: SplitDirection,
+    },
+    /// A panel moved to another dock.
+    DockPositionChanged {
+        panel: &'static str,
+        old_position: DockPosition,
+        new_position: DockPosition,
+    },
+    /// A dock was opened or closed.
+    DockToggled {
+        position: DockPosition,
+        open: bool,
+    },
 }
 
 pub enum OpenVisible {
@@ -477,6 +654,10 @@ pub struct Workspace {
     workspace_actions: Vec<Box<dyn Fn(Div, &mut ViewContext<Self>) -> Div>>,
     zoomed: Option<AnyWeakView>,
     zoomed_position: Option<DockPosition>,
+    docks_hidden_by_toggle: Vec<DockPosition>,
+    /// Docks that would have been hidden automatically, but were kept open because
+    /// one of their items prevented it. They are hidden once none of them does.
+    docks_kept_open: Vec<DockPosition>,
     center: PaneGroup,
     left_dock: View<Dock>,
     bottom_dock: View<Dock>,
@@ -485,9 +666,15 @@ pub struct Workspace {
     panes_by_item: HashMap<EntityId, WeakView<Pane>>,
     active_pane: View<Pane>,
     last_active_center_pane: Option<WeakView<Pane>>,
+    /// The most recently focused panes, including those hosted by docks, most recent first.
+    pane_focus_history: VecDeque<WeakView<Pane>>,
+    read_only: bool,
     last_active_view_id: Option<proto::ViewId>,
+    last_active_panel: Option<String>,
+    /// Whether dock items were shared with followers as of the last settings change.
+    sharing_dock_items: bool,
     status_bar: View<StatusBar>,
-    modal_layer: View<ModalLayer>,
+    modal_manager: View<ModalManager>,
     titlebar_item: Option<AnyView>,
     notifications: Vec<(TypeId, usize, Box<dyn NotificationHandle>)>,
     project: Model<Project>,
@@ -503,11 +690,33 @@ pub struct Workspace {
     _observe_current_user: Task<Result<()>>,
     _schedule_serialize: Option<Task<()>>,
     pane_history_timestamp: Arc<AtomicUsize>,
+    /// Closed items that no pane remembers anymore, either because they were restored
+    /// from a previous session or because their pane was removed. Most recent first.
+    closed_items: VecDeque<SerializedClosedItem>,
+    ui_zoom: f32,
     bounds: Bounds<Pixels>,
 }
 
 impl EventEmitter<Event> for Workspace {}
 
+impl Workspace {
+    /// Subscribes to the events of `workspace` that `filter` maps to a value, such as
+    /// `|event| matches!(event, Event::PaneSplit { .. }).then_some(())`, and calls
+    /// `callback` with that value. Other events are ignored.
+    pub fn subscribe_to_events<V: 'static, T>(
+        workspace: &View<Workspace>,
+        filter: impl Fn(&Event) -> Option<T> + 'static,
+        mut callback: impl FnMut(&mut V, T, &mut ViewContext<V>) + 'static,
+        cx: &mut ViewContext<V>,
+    ) -> Subscription {
+        cx.subscribe(workspace, move |this, _, event, cx| {
+            if let Some(value) = filter(event) {
+                callback(this, value, cx);
+            }
+        })
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ViewId {
     pub creator: PeerId,
@@ -518,6 +727,8 @@ pub struct ViewId {
 struct FollowerState {
     leader_id: PeerId,
     active_view_id: Option<ViewId>,
+    /// The persistent name of the dock panel the leader is focused on, if any.
+    active_panel: Option<String>,
     items_by_leader_view_id: HashMap<ViewId, Box<dyn FollowableItemHandle>>,
 }
 
@@ -648,7 +859,7 @@ impl Workspace {
             status_bar
         });
 
-        let modal_layer = cx.new_view(|_| ModalLayer::new());
+        let modal_manager = cx.new_view(|_| ModalManager::new());
 
         let mut active_call = None;
         if let Some(call) = ActiveCall::try_global(cx) {
@@ -694,6 +905,14 @@ impl Workspace {
                 this.serialize_workspace(cx);
                 cx.notify();
             }),
+            cx.subscribe(&left_dock, Self::handle_dock_event),
+            cx.subscribe(&bottom_dock, Self::handle_dock_event),
+            cx.subscribe(&right_dock, Self::handle_dock_event),
+            // Re-render when settings such as the dock layout or the pane magnification change.
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                this.share_dock_items_if_enabled(cx);
+                cx.notify();
+            }),
             cx.on_release(|this, window, cx| {
                 this.app_state.workspace_store.update(cx, |store, _| {
                     let window = window.downcast::<Self>().unwrap();
@@ -714,9 +933,15 @@ impl Workspace {
             panes_by_item: Default::default(),
             active_pane: center_pane.clone(),
             last_active_center_pane: Some(center_pane.downgrade()),
+            pane_focus_history: VecDeque::from([center_pane.downgrade()]),
+            read_only: false,
             last_active_view_id: None,
+            last_active_panel: None,
+            sharing_dock_items: WorkspaceSettings::get_global(cx).share_dock_items,
             status_bar,
-            modal_layer,
+            modal_manager,
+            docks_hidden_by_toggle: Vec::new(),
+            docks_kept_open: Vec::new(),
             titlebar_item: None,
             notifications: Default::default(),
             left_dock,
@@ -735,6 +960,8 @@ impl Workspace {
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
+            closed_items: VecDeque::new(),
+            ui_zoom: DEFAULT_UI_ZOOM,
             workspace_actions: Default::default(),
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
@@ -762,10 +989,16 @@ impl Workspace {
         );
 
         cx.spawn(|mut cx| async move {
-            let serialized_workspace: Option<SerializedWorkspace> =
-                persistence::DB.workspace_for_roots(abs_paths.as_slice());
-
+            // Reading the pane tree and its items can take a while for large sessions, so
+            // keep it off the main thread.
             let paths_to_open = Arc::new(abs_paths);
+            let serialized_workspace: Option<SerializedWorkspace> = cx
+                .background_executor()
+                .spawn({
+                    let paths_to_open = paths_to_open.clone();
+                    async move { persistence::DB.workspace_for_roots(paths_to_open.as_slice()) }
+                })
+                .await;
 
             // Get project paths for all of the abs_paths
             let mut worktree_roots: HashSet<Arc<Path>> = Default::default();
@@ -808,25 +1041,9 @@ impl Workspace {
                         .as_ref()
                         .and_then(|serialized_workspace| {
                             let serialized_display = serialized_workspace.display?;
-                            let mut bounds = serialized_workspace.bounds?;
-
-                            // Stored bounds are relative to the containing display.
-                            // So convert back to global coordinates if that screen still exists
-                            if let WindowBounds::Fixed(mut window_bounds) = bounds {
-                                let screen = cx
-                                    .update(|cx| {
-                                        cx.displays().into_iter().find(|display| {
-                                            display.uuid().ok() == Some(serialized_display)
-                                        })
-                                    })
-                                    .ok()??;
-                                let screen_bounds = screen.bounds();
-                                window_bounds.origin.x += screen_bounds.origin.x;
-                                window_bounds.origin.y += screen_bounds.origin.y;
-                                bounds = WindowBounds::Fixed(window_bounds);
-                            }
-
-                            Some((bounds, serialized_display))
+                            let bounds = serialized_workspace.bounds?;
+                            cx.update(|cx| restore_window_bounds(bounds, serialized_display, cx))
+                                .ok()?
                         })
                         .unzip()
                 };
@@ -863,6 +1080,106 @@ impl Workspace {
         })
     }
 
+    /// Opens another window onto this workspace's project. The windows share the
+    /// project's worktrees and buffers, but each has its own panes and docks.
+    pub fn new_window_for_project(&mut self, _: &NewWindowForProject, cx: &mut ViewContext<Self>) {
+        self.open_window_for_project(cx).detach_and_log_err(cx);
+    }
+
+    fn open_window_for_project(
+        &self,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<WindowHandle<Workspace>>> {
+        let project = self.project.clone();
+        let app_state = self.app_state.clone();
+        let is_local = project.read(cx).is_local();
+        cx.spawn(|_, mut cx| async move {
+            // Remote projects aren't persisted, so their windows don't need their own ids.
+            let workspace_id = if is_local {
+                DB.next_id().await.unwrap_or(0)
+            } else {
+                0
+            };
+            let window_bounds_override = window_bounds_env_override(&cx);
+            let window = cx.update(|cx| {
+                let options = (app_state.build_window_options)(window_bounds_override, None, cx);
+                cx.open_window(options, |cx| {
+                    cx.new_view(|cx| Workspace::new(workspace_id, project, app_state.clone(), cx))
+                })
+            })?;
+            window.update(&mut cx, |_, cx| cx.activate_window())?;
+            Ok(window)
+        })
+    }
+
+    /// Moves the items of a pane to a new window onto the same project, removing
+    /// the pane from this window once it's empty.
+    ///
+    /// Item views are tied to the workspace they were added to, so rather than being
+    /// moved, the items are reopened from their project paths in the new window.
+    /// That would lose untitled items and the unsaved changes of dirty ones, so the
+    /// pane isn't moved while it has any. Items that fail to reopen stay where they are.
+    fn move_pane_to_new_window(
+        &mut self,
+        pane: View<Pane>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        let (items_to_move, active_path) = {
+            let pane = pane.read(cx);
+            if pane
+                .items()
+                .any(|item| item.project_path(cx).is_none() || item.is_dirty(cx))
+            {
+                return Task::ready(Err(anyhow!(
+                    "save or close the untitled and modified items of the pane before moving it to a new window"
+                )));
+            }
+            let items = pane
+                .items()
+                .filter_map(|item| Some((item.boxed_clone(), item.project_path(cx)?)))
+                .collect::<Vec<_>>();
+            let active_path = pane.active_item().and_then(|item| item.project_path(cx));
+            (items, active_path)
+        };
+        if items_to_move.is_empty() {
+            return Task::ready(Ok(()));
+        }
+
+        let open_window = self.open_window_for_project(cx);
+        cx.spawn(|_, mut cx| async move {
+            let window = open_window.await?;
+            let mut moved_items = Vec::new();
+            for (item, project_path) in items_to_move {
+                let opened = window
+                    .update(&mut cx, |workspace, cx| {
+                        workspace.open_path(project_path, None, false, cx)
+                    })?
+                    .await;
+                if opened.log_err().is_some() {
+                    moved_items.push(item);
+                }
+            }
+            if let Some(active_path) = active_path {
+                window
+                    .update(&mut cx, |workspace, cx| {
+                        workspace.open_path(active_path, None, true, cx)
+                    })?
+                    .await
+                    .log_err();
+            }
+
+            // Removing the last item of the pane also removes the pane.
+            pane.update(&mut cx, |pane, cx| {
+                for item in &moved_items {
+                    if let Some(ix) = pane.index_for_item(item.as_ref()) {
+                        pane.remove_item(ix, false, cx);
+                    }
+                }
+            })?;
+            Ok(())
+        })
+    }
+
     pub fn weak_handle(&self) -> WeakView<Self> {
         self.weak_self.clone()
     }
@@ -899,6 +1216,23 @@ impl Workspace {
         &self.status_bar
     }
 
+    pub fn ui_zoom(&self) -> f32 {
+        self.ui_zoom
+    }
+
+    /// Scales the UI of the window, which is sized in rems, without changing the
+    /// font size of the editors. The zoom level is persisted with the workspace.
+    pub fn set_ui_zoom(&mut self, ui_zoom: f32, cx: &mut ViewContext<Self>) {
+        // Round to the zoom step, so that zooming in and out again ends up where it started.
+        let ui_zoom =
+            ((ui_zoom / UI_ZOOM_STEP).round() * UI_ZOOM_STEP).clamp(MIN_UI_ZOOM, MAX_UI_ZOOM);
+        if ui_zoom != self.ui_zoom {
+            self.ui_zoom = ui_zoom;
+            self.serialize_workspace(cx);
+            cx.notify();
+        }
+    }
+
     pub fn app_state(&self) -> &Arc<AppState> {
         &self.app_state
     }
@@ -1067,12 +1401,90 @@ impl Workspace {
         self.navigate_history(pane, NavigationMode::GoingForward, cx)
     }
 
+    /// Goes back to the previous location visited in any pane, activating that pane and
+    /// reopening the location's item if it has been closed since.
+    pub fn jump_back(&mut self, cx: &mut ViewContext<Workspace>) -> Task<Result<()>> {
+        self.jump(NavigationMode::GoingBack, cx)
+    }
+
+    /// Returns to the location left by [`Self::jump_back`], in whichever pane it was in.
+    pub fn jump_forward(&mut self, cx: &mut ViewContext<Workspace>) -> Task<Result<()>> {
+        self.jump(NavigationMode::GoingForward, cx)
+    }
+
+    /// The workspace's jump list interleaves the navigation histories of all of its panes,
+    /// whose entries are ordered by their shared timestamps.
+    fn jump(&mut self, mode: NavigationMode, cx: &mut ViewContext<Workspace>) -> Task<Result<()>> {
+        let Some((timestamp, pane)) = self
+            .panes
+            .iter()
+            .filter_map(|pane| {
+                let timestamp = pane.read(cx).nav_history().next_entry_timestamp(mode)?;
+                Some((timestamp, pane.downgrade()))
+            })
+            .max_by_key(|(timestamp, _)| *timestamp)
+        else {
+            return Task::ready(Ok(()));
+        };
+
+        // Visiting a location discards the forward history of its own pane only, so any
+        // other pane's forward entries that predate the latest visit are stale.
+        if let NavigationMode::GoingForward = mode {
+            let last_visit = self
+                .panes
+                .iter()
+                .filter_map(|pane| pane.read(cx).nav_history().last_visit_timestamp())
+                .max();
+            if last_visit.is_some_and(|last_visit| last_visit > timestamp) {
+                return Task::ready(Ok(()));
+            }
+        }
+
+        self.navigate_history(pane, mode, cx)
+    }
+
     pub fn reopen_closed_item(&mut self, cx: &mut ViewContext<Workspace>) -> Task<Result<()>> {
-        self.navigate_history(
-            self.active_pane().downgrade(),
-            NavigationMode::ReopeningClosedItem,
-            cx,
-        )
+        if self.active_pane.read(cx).nav_history().has_closed_items()
+            || self.closed_items.is_empty()
+        {
+            return self.navigate_history(
+                self.active_pane().downgrade(),
+                NavigationMode::ReopeningClosedItem,
+                cx,
+            );
+        }
+
+        // The active pane has nothing left to reopen, so fall back to the items
+        // that were closed in other panes or in a previous session.
+        let fs = self.app_state.fs.clone();
+        cx.spawn(|workspace, mut cx| async move {
+            while let Some(closed_item) =
+                workspace.update(&mut cx, |workspace, _| workspace.closed_items.pop_front())?
+            {
+                if !fs.is_file(&closed_item.abs_path).await {
+                    continue;
+                }
+
+                let item = workspace
+                    .update(&mut cx, |workspace, cx| {
+                        workspace.open_abs_path(closed_item.abs_path.clone(), true, cx)
+                    })?
+                    .await?;
+                workspace.update(&mut cx, |workspace, cx| {
+                    let kind = item
+                        .to_serializable_item_handle(cx)
+                        .map(|item| item.serialized_item_kind());
+                    if let Some(position) = closed_item.position {
+                        if closed_item.kind.is_none() || closed_item.kind.as_deref() == kind {
+                            item.navigate(Box::new(position), cx);
+                        }
+                    }
+                    workspace.serialize_workspace(cx);
+                })?;
+                break;
+            }
+            Ok(())
+        })
     }
 
     pub fn client(&self) -> &Client {
@@ -1225,75 +1637,176 @@ impl Workspace {
 
     fn save_all_internal(
         &mut self,
-        mut save_intent: SaveIntent,
+        save_intent: SaveIntent,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<bool>> {
+        self.save_dirty_items(save_intent, None, cx)
+    }
+
+    /// Saves the items the user picked in a prompt covering every window, before
+    /// quitting. Dirty items that weren't picked are left unsaved.
+    pub fn save_items_before_quit(
+        &mut self,
+        item_ids_to_save: HashSet<EntityId>,
         cx: &mut ViewContext<Self>,
     ) -> Task<Result<bool>> {
+        self.save_dirty_items(SaveIntent::Close, Some(item_ids_to_save), cx)
+    }
+
+    /// The items with unsaved changes in every pane, including the ones in docks.
+    pub fn unsaved_items(&self, cx: &WindowContext) -> Vec<Box<dyn ItemHandle>> {
+        self.dirty_items(cx)
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect()
+    }
+
+    fn dirty_items(&self, cx: &WindowContext) -> Vec<(WeakView<Pane>, Box<dyn ItemHandle>)> {
         if self.project.read(cx).is_disconnected() {
-            return Task::ready(Ok(true));
+            return Vec::new();
         }
-        let dirty_items = self
+        let mut project_item_ids = HashSet::default();
+        let panes = self
             .panes
+            .iter()
+            .cloned()
+            .chain(self.dock_panes(cx))
+            .collect::<Vec<_>>();
+        panes
             .iter()
             .flat_map(|pane| {
                 pane.read(cx).items().filter_map(|item| {
-                    if item.is_dirty(cx) {
+                    if item.is_dirty(cx)
+                        && (item.is_singleton(cx) || !item.project_entry_ids(cx).is_empty())
+                    {
                         Some((pane.downgrade(), item.boxed_clone()))
                     } else {
                         None
                     }
                 })
             })
-            .collect::<Vec<_>>();
+            // Don't ask twice about items showing the same buffers.
+            .filter(|(_, item)| {
+                let item_project_item_ids = item.project_item_model_ids(cx);
+                item_project_item_ids.is_empty()
+                    || item_project_item_ids
+                        .into_iter()
+                        .fold(false, |is_new, id| project_item_ids.insert(id) || is_new)
+            })
+            .collect()
+    }
+
+    fn save_dirty_items(
+        &mut self,
+        save_intent: SaveIntent,
+        mut item_ids_to_save: Option<HashSet<EntityId>>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<bool>> {
+        if self.project.read(cx).is_disconnected() {
+            return Task::ready(Ok(true));
+        }
+        let dirty_items = self.dirty_items(cx);
 
         let project = self.project.clone();
         cx.spawn(|workspace, mut cx| async move {
-            // Override save mode and display "Save all files" prompt
-            if save_intent == SaveIntent::Close && dirty_items.len() > 1 {
-                let answer = workspace.update(&mut cx, |_, cx| {
-                    let (prompt, detail) = Pane::file_names_for_prompt(
-                        &mut dirty_items.iter().map(|(_, handle)| handle),
-                        dirty_items.len(),
-                        cx,
-                    );
-                    cx.prompt(
-                        PromptLevel::Warning,
-                        &prompt,
-                        Some(&detail),
-                        &["Save all", "Discard all", "Cancel"],
-                    )
+            // Let the user pick which items to save in a single prompt.
+            if item_ids_to_save.is_none()
+                && save_intent == SaveIntent::Close
+                && dirty_items.len() > 1
+            {
+                let answer = workspace.update(&mut cx, |workspace, cx| {
+                    let items = dirty_items
+                        .iter()
+                        .map(|(_, item)| item.boxed_clone())
+                        .collect::<Vec<_>>();
+                    UnsavedChangesModal::prompt(workspace, &items, cx)
                 })?;
-                match answer.await.log_err() {
-                    Some(0) => save_intent = SaveIntent::SaveAll,
-                    Some(1) => save_intent = SaveIntent::Skip,
-                    _ => {}
+                match answer.await.ok().flatten() {
+                    Some(item_ids) => item_ids_to_save = Some(item_ids),
+                    None => return Ok(false),
                 }
             }
-            for (pane, item) in dirty_items {
-                let (singleton, project_entry_ids) =
-                    cx.update(|cx| (item.is_singleton(cx), item.project_entry_ids(cx)))?;
-                if singleton || !project_entry_ids.is_empty() {
-                    if let Some(ix) =
-                        pane.update(&mut cx, |pane, _| pane.index_for_item(item.as_ref()))?
-                    {
-                        if !Pane::save_item(
-                            project.clone(),
-                            &pane,
-                            ix,
-                            &*item,
-                            save_intent,
-                            &mut cx,
+            let item_count = dirty_items.len();
+            let show_progress = item_count >= SAVE_ALL_PROGRESS_THRESHOLD;
+            let mut failures = Vec::new();
+            for (saved_count, (pane, item)) in dirty_items.into_iter().enumerate() {
+                let item_save_intent = match &item_ids_to_save {
+                    Some(item_ids) if item_ids.contains(&item.item_id()) => SaveIntent::SaveAll,
+                    Some(_) => continue,
+                    None => save_intent,
+                };
+                if show_progress {
+                    workspace.update(&mut cx, |workspace, cx| {
+                        workspace.show_toast(
+                            Toast::new(
+                                notification_id::<SaveAllProgressToast>(),
+                                format!("Saving {} of {item_count} files…", saved_count + 1),
+                            ),
+                            cx,
                         )
-                        .await?
-                        {
-                            return Ok(false);
-                        }
+                    })?;
+                }
+                let Some(ix) =
+                    pane.update(&mut cx, |pane, _| pane.index_for_item(item.as_ref()))?
+                else {
+                    continue;
+                };
+                match Pane::save_item(
+                    project.clone(),
+                    &pane,
+                    ix,
+                    &*item,
+                    item_save_intent,
+                    &mut cx,
+                )
+                .await
+                {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        workspace.update(&mut cx, |workspace, cx| {
+                            workspace.dismiss_toast(notification_id::<SaveAllProgressToast>(), cx)
+                        })?;
+                        return Ok(false);
+                    }
+                    Err(error) => {
+                        let label = cx.update(|cx| save_failure_label(item.as_ref(), cx))?;
+                        failures.push(format!("{label}: {error}"));
                     }
                 }
             }
-            Ok(true)
+
+            workspace.update(&mut cx, |workspace, cx| {
+                workspace.dismiss_toast(notification_id::<SaveAllProgressToast>(), cx);
+                if !failures.is_empty() {
+                    let message = format!(
+                        "Failed to save {} of {item_count} files:\n{}",
+                        failures.len(),
+                        failures.join("\n")
+                    );
+                    log_notification(NotificationSeverity::Error, message.clone(), cx);
+                    workspace.dismiss_notification::<MessageNotification>(
+                        notification_id::<SaveAllFailureNotification>(),
+                        cx,
+                    );
+                    workspace.show_notification(
+                        notification_id::<SaveAllFailureNotification>(),
+                        cx,
+                        |cx| cx.new_view(|_| MessageNotification::new(message)),
+                    );
+                }
+            })?;
+            Ok(failures.is_empty())
         })
     }
 
+    /// The panes hosted by the panels of every dock.
+    fn dock_panes(&self, cx: &WindowContext) -> Vec<View<Pane>> {
+        [&self.left_dock, &self.bottom_dock, &self.right_dock]
+            .into_iter()
+            .flat_map(|dock| dock.read(cx).panels().flat_map(|panel| panel.panes(cx)))
+            .collect()
+    }
+
     pub fn open(&mut self, _: &Open, cx: &mut ViewContext<Self>) {
         self.client()
             .telemetry()
@@ -1350,25 +1863,134 @@ impl Workspace {
         })
     }
 
+    /// Opens paths dropped onto the workspace from outside of Zed. Files open in the
+    /// given pane, while the user chooses whether dropped directories are added to
+    /// the project or opened in a new window.
     #[allow(clippy::type_complexity)]
-    pub fn open_paths(
+    pub fn open_dropped_paths(
         &mut self,
-        mut abs_paths: Vec<PathBuf>,
-        visible: OpenVisible,
+        abs_paths: Vec<PathBuf>,
         pane: Option<WeakView<Pane>>,
         cx: &mut ViewContext<Self>,
     ) -> Task<Vec<Option<Result<Box<dyn ItemHandle>, anyhow::Error>>>> {
-        log::info!("open paths {abs_paths:?}");
-
         let fs = self.app_state.fs.clone();
+        let app_state = self.app_state.clone();
+        cx.spawn(|this, mut cx| async move {
+            let mut directories = Vec::new();
+            let mut files = Vec::new();
+            for abs_path in abs_paths {
+                let is_dir = fs
+                    .metadata(&abs_path)
+                    .await
+                    .log_err()
+                    .flatten()
+                    .map_or(false, |metadata| metadata.is_dir);
+                if is_dir {
+                    directories.push(abs_path);
+                } else {
+                    files.push(abs_path);
+                }
+            }
 
-        // Sort the paths to ensure we add worktrees for parents before their children.
-        abs_paths.sort_unstable();
-        cx.spawn(move |this, mut cx| async move {
-            let mut tasks = Vec::with_capacity(abs_paths.len());
+            if !directories.is_empty() {
+                let message = if directories.len() == 1 {
+                    "Add the dropped folder to this project?"
+                } else {
+                    "Add the dropped folders to this project?"
+                };
+                let answer = this.update(&mut cx, |_, cx| {
+                    cx.prompt(
+                        PromptLevel::Info,
+                        message,
+                        None,
+                        &["Add to Project", "Open in New Window", "Cancel"],
+                    )
+                });
+                let answer = match answer {
+                    Ok(answer) => answer.await.ok(),
+                    Err(_) => None,
+                };
+                match answer {
+                    Some(0) => files.extend(directories),
+                    Some(1) => {
+                        cx.update(|cx| {
+                            open_paths(&directories, &app_state, None, cx).detach_and_log_err(cx)
+                        })
+                        .log_err();
+                    }
+                    _ => {}
+                }
+            }
 
-            for abs_path in &abs_paths {
-                let visible = match visible {
+            if files.is_empty() {
+                return Vec::new();
+            }
+            match this.update(&mut cx, |this, cx| {
+                this.open_paths(files, OpenVisible::OnlyDirectories, pane, cx)
+            }) {
+                Ok(open_task) => open_task.await,
+                Err(_) => Vec::new(),
+            }
+        })
+    }
+
+    /// Creates an untitled buffer containing `text` and adds an item for it to `pane`,
+    /// at `ix` if given, using whichever item is registered for buffers.
+    pub fn add_untitled_item(
+        &mut self,
+        text: &str,
+        pane: &View<Pane>,
+        ix: Option<usize>,
+        cx: &mut ViewContext<Self>,
+    ) -> Result<Box<dyn ItemHandle>> {
+        let project = self.project.clone();
+        let buffer: AnyModel = project
+            .update(cx, |project, cx| project.create_buffer(text, None, cx))?
+            .into();
+        let build_item = cx
+            .default_global::<ProjectItemBuilders>()
+            .get(&buffer.entity_type())
+            .copied()
+            .ok_or_else(|| anyhow!("no item builder for buffers"))?;
+        Ok(pane.update(cx, |pane, cx| {
+            let item = build_item(project, buffer, cx);
+            pane.add_item(item.boxed_clone(), true, true, ix, cx);
+            item
+        }))
+    }
+
+    /// Stashes the clipboard text in a new untitled item in a pane hosted by a dock
+    /// panel, revealing it, so that it doesn't disturb the center layout. Falls back
+    /// to the active pane when no dock panel hosts panes.
+    pub fn paste_to_dock(&mut self, _: &PasteToDock, cx: &mut ViewContext<Self>) {
+        let Some(text) = cx.read_from_clipboard().map(|item| item.text().clone()) else {
+            return;
+        };
+        let pane = self
+            .reveal_dock_pane(cx)
+            .unwrap_or_else(|| self.active_pane.clone());
+        self.add_untitled_item(&text, &pane, None, cx).log_err();
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn open_paths(
+        &mut self,
+        mut abs_paths: Vec<PathBuf>,
+        visible: OpenVisible,
+        pane: Option<WeakView<Pane>>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Vec<Option<Result<Box<dyn ItemHandle>, anyhow::Error>>>> {
+        log::info!("open paths {abs_paths:?}");
+
+        let fs = self.app_state.fs.clone();
+
+        // Sort the paths to ensure we add worktrees for parents before their children.
+        abs_paths.sort_unstable();
+        cx.spawn(move |this, mut cx| async move {
+            let mut tasks = Vec::with_capacity(abs_paths.len());
+
+            for abs_path in &abs_paths {
+                let visible = match visible {
                     OpenVisible::All => Some(true),
                     OpenVisible::None => Some(false),
                     OpenVisible::OnlyFiles => match fs.metadata(abs_path).await.log_err() {
@@ -1622,6 +2244,8 @@ impl Workspace {
             DockPosition::Bottom => &self.bottom_dock,
             DockPosition::Right => &self.right_dock,
         };
+        // Toggling a dock by hand overrides what `toggle_all_panels` would restore.
+        self.docks_hidden_by_toggle.clear();
         let mut focus_center = false;
         let mut reveal_dock = false;
         dock.update(cx, |dock, cx| {
@@ -1668,6 +2292,74 @@ impl Workspace {
         self.serialize_workspace(cx);
     }
 
+    /// Hides every open dock, or reopens the docks hidden by the previous call if
+    /// none are open.
+    pub fn toggle_all_panels(&mut self, cx: &mut ViewContext<Self>) {
+        let docks = [
+            (DockPosition::Left, &self.left_dock),
+            (DockPosition::Bottom, &self.bottom_dock),
+            (DockPosition::Right, &self.right_dock),
+        ];
+        let open_docks = docks
+            .iter()
+            .filter(|(_, dock)| dock.read(cx).is_open())
+            .map(|(position, _)| *position)
+            .collect::<Vec<_>>();
+
+        if open_docks.is_empty() {
+            for (position, dock) in docks {
+                if self.docks_hidden_by_toggle.contains(&position) {
+                    dock.update(cx, |dock, cx| dock.set_open(true, cx));
+                }
+            }
+            self.docks_hidden_by_toggle.clear();
+        } else {
+            let mut focus_center = false;
+            for (_, dock) in docks {
+                dock.update(cx, |dock, cx| {
+                    if dock
+                        .active_panel()
+                        .map_or(false, |panel| panel.focus_handle(cx).contains_focused(cx))
+                    {
+                        focus_center = true;
+                    }
+                    dock.set_open(false, cx);
+                });
+            }
+            self.docks_hidden_by_toggle = open_docks;
+
+            if focus_center {
+                self.active_pane.update(cx, |pane, cx| pane.focus(cx))
+            }
+        }
+
+        cx.notify();
+        self.serialize_workspace(cx);
+    }
+
+    /// Moves the focused panel from one side dock to the other, persisting the
+    /// new position in the panel's settings.
+    pub fn move_focused_panel_to_opposite_side(&mut self, cx: &mut ViewContext<Self>) {
+        for dock in [&self.left_dock, &self.right_dock] {
+            let dock = dock.read(cx);
+            let Some(position) = dock.position().opposite_side() else {
+                continue;
+            };
+            let focused_panel = dock
+                .visible_panel()
+                .into_iter()
+                .chain(dock.split_panel())
+                .find(|panel| panel.focus_handle(cx).contains_focused(cx))
+                .cloned();
+            if let Some(panel) = focused_panel {
+                if panel.position_is_valid(position, cx) {
+                    panel.set_position(position, cx);
+                }
+                return;
+            }
+        }
+    }
+
     /// Transfer focus to the panel of the given type.
     pub fn focus_panel<T: Panel>(&mut self, cx: &mut ViewContext<Self>) -> Option<View<T>> {
         let panel = self.focus_or_unfocus_panel::<T>(cx, |_, _| true)?;
@@ -1689,7 +2381,12 @@ impl Workspace {
         should_focus: impl Fn(&dyn PanelHandle, &mut ViewContext<Dock>) -> bool,
     ) -> Option<Arc<dyn PanelHandle>> {
         for dock in [&self.left_dock, &self.bottom_dock, &self.right_dock] {
+            if let Some((panel, window)) = dock.read(cx).popped_out_panel::<T>() {
+                focus_popped_out_panel(&*panel, window, cx);
+                return Some(panel);
+            }
             if let Some(panel_index) = dock.read(cx).panel_index_for_type::<T>() {
+                self.docks_hidden_by_toggle.clear();
                 let mut focus_center = false;
                 let panel = dock.update(cx, |dock, cx| {
                     dock.activate_panel(panel_index, cx);
@@ -1721,7 +2418,10 @@ impl Workspace {
     /// Open the panel of the given type
     pub fn open_panel<T: Panel>(&mut self, cx: &mut ViewContext<Self>) {
         for dock in [&self.left_dock, &self.bottom_dock, &self.right_dock] {
-            if let Some(panel_index) = dock.read(cx).panel_index_for_type::<T>() {
+            if let Some((panel, window)) = dock.read(cx).popped_out_panel::<T>() {
+                focus_popped_out_panel(&*panel, window, cx);
+            } else if let Some(panel_index) = dock.read(cx).panel_index_for_type::<T>() {
+                self.docks_hidden_by_toggle.clear();
                 dock.update(cx, |dock, cx| {
                     dock.activate_panel(panel_index, cx);
                     dock.set_open(true, cx);
@@ -1754,17 +2454,19 @@ impl Workspace {
 
         // If another dock is zoomed, hide it.
         let mut focus_center = false;
-        for dock in [&self.left_dock, &self.right_dock, &self.bottom_dock] {
-            dock.update(cx, |dock, cx| {
-                if Some(dock.position()) != dock_to_reveal {
-                    if let Some(panel) = dock.active_panel() {
-                        if panel.is_zoomed(cx) {
-                            focus_center |= panel.focus_handle(cx).contains_focused(cx);
-                            dock.set_open(false, cx);
-                        }
+        for dock in [
+            self.left_dock.clone(),
+            self.right_dock.clone(),
+            self.bottom_dock.clone(),
+        ] {
+            let position = dock.read(cx).position();
+            if Some(position) != dock_to_reveal {
+                if let Some(panel) = dock.read(cx).active_panel().cloned() {
+                    if panel.is_zoomed(cx) && self.auto_hide_dock(position, cx) {
+                        focus_center |= panel.focus_handle(cx).contains_focused(cx);
                     }
                 }
-            });
+            }
         }
 
         if focus_center {
@@ -1779,6 +2481,50 @@ impl Workspace {
         cx.notify();
     }
 
+    fn dock_at_position(&self, position: DockPosition) -> &View<Dock> {
+        match position {
+            DockPosition::Left => &self.left_dock,
+            DockPosition::Bottom => &self.bottom_dock,
+            DockPosition::Right => &self.right_dock,
+        }
+    }
+
+    /// Hides a dock as a result of focus moving elsewhere, unless one of its items
+    /// prevents it, in which case it is kept open until none of them does anymore.
+    /// Returns whether the dock was hidden.
+    fn auto_hide_dock(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) -> bool {
+        let dock = self.dock_at_position(position).clone();
+        if !dock.read(cx).is_open() {
+            return false;
+        }
+
+        if dock.read(cx).prevents_auto_hide(cx) {
+            if !self.docks_kept_open.contains(&position) {
+                self.docks_kept_open.push(position);
+            }
+            false
+        } else {
+            dock.update(cx, |dock, cx| dock.set_open(false, cx));
+            true
+        }
+    }
+
+    /// Hides the docks that were kept open by their items, once those items allow
+    /// it, unless the user has moved focus back into them in the meantime.
+    pub(crate) fn hide_docks_kept_open(&mut self, cx: &mut ViewContext<Self>) {
+        for position in mem::take(&mut self.docks_kept_open) {
+            let dock = self.dock_at_position(position).clone();
+            if dock.focus_handle(cx).contains_focused(cx) {
+                continue;
+            }
+            if self.auto_hide_dock(position, cx) && self.zoomed_position == Some(position) {
+                self.zoomed = None;
+                self.zoomed_position = None;
+            }
+        }
+        cx.notify();
+    }
+
     fn add_pane(&mut self, cx: &mut ViewContext<Self>) -> View<Pane> {
         let pane = cx.new_view(|cx| {
             Pane::new(
@@ -1890,6 +2636,19 @@ impl Workspace {
         })
     }
 
+    /// The pane that paths open into when no pane is given: the center pane that was
+    /// active last. Panes belonging to docks are never chosen, so that opening a file
+    /// from a picker while a dock is focused doesn't bury it in the dock; callers that
+    /// mean to open into a dock's pane have to pass it explicitly.
+    fn navigation_target_pane(&self) -> WeakView<Pane> {
+        self.last_active_center_pane.clone().unwrap_or_else(|| {
+            self.panes
+                .first()
+                .expect("There must be an active pane")
+                .downgrade()
+        })
+    }
+
     pub fn open_path(
         &mut self,
         path: impl Into<ProjectPath>,
@@ -1897,14 +2656,7 @@ impl Workspace {
         focus_item: bool,
         cx: &mut WindowContext,
     ) -> Task<Result<Box<dyn ItemHandle>, anyhow::Error>> {
-        let pane = pane.unwrap_or_else(|| {
-            self.last_active_center_pane.clone().unwrap_or_else(|| {
-                self.panes
-                    .first()
-                    .expect("There must be an active pane")
-                    .downgrade()
-            })
-        });
+        let pane = pane.unwrap_or_else(|| self.navigation_target_pane());
 
         let task = self.load_path(path.into(), cx);
         cx.spawn(move |mut cx| async move {
@@ -1915,17 +2667,53 @@ impl Workspace {
         })
     }
 
-    pub fn split_path(
+    /// Opens a path in a pane hosted by a dock panel, such as the terminal panel,
+    /// revealing the dock if it's hidden. When no dock panel hosts panes, the path
+    /// opens in the center like [`Self::open_path`].
+    pub fn open_path_in_dock(
         &mut self,
         path: impl Into<ProjectPath>,
         cx: &mut ViewContext<Self>,
     ) -> Task<Result<Box<dyn ItemHandle>, anyhow::Error>> {
-        let pane = self.last_active_center_pane.clone().unwrap_or_else(|| {
-            self.panes
-                .first()
-                .expect("There must be an active pane")
-                .downgrade()
+        let pane = self.reveal_dock_pane(cx).map(|pane| pane.downgrade());
+        self.open_path(path, pane, true, cx)
+    }
+
+    /// Activates and reveals a dock panel hosting panes, returning the pane that items
+    /// opened into the dock go to. A panel that's already visible is preferred.
+    pub fn reveal_dock_pane(&mut self, cx: &mut ViewContext<Self>) -> Option<View<Pane>> {
+        let mut candidates = Vec::new();
+        for dock in [&self.bottom_dock, &self.left_dock, &self.right_dock] {
+            let dock_ref = dock.read(cx);
+            for (panel_ix, panel) in dock_ref.panels().enumerate() {
+                if let Some(pane) = panel.panes(cx).into_iter().next() {
+                    let is_visible =
+                        dock_ref.is_open() && dock_ref.active_panel_index() == panel_ix;
+                    candidates.push((is_visible, dock.clone(), panel_ix, pane));
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+        let ix = candidates
+            .iter()
+            .position(|(is_visible, ..)| *is_visible)
+            .unwrap_or(0);
+        let (_, dock, panel_ix, pane) = candidates.swap_remove(ix);
+        dock.update(cx, |dock, cx| {
+            dock.activate_panel(panel_ix, cx);
+            dock.set_open(true, cx);
         });
+        Some(pane)
+    }
+
+    pub fn split_path(
+        &mut self,
+        path: impl Into<ProjectPath>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<Box<dyn ItemHandle>, anyhow::Error>> {
+        let pane = self.navigation_target_pane();
 
         if let Member::Pane(center_pane) = &self.center.root {
             if center_pane.read(cx).items_len() == 0 {
@@ -1997,6 +2785,41 @@ impl Workspace {
         item
     }
 
+    /// Like [`Self::open_project_item`], but opens the item in a pane hosted by a dock
+    /// panel, revealing it. See [`Self::open_path_in_dock`].
+    pub fn open_project_item_in_dock<T>(
+        &mut self,
+        project_item: Model<T::Item>,
+        cx: &mut ViewContext<Self>,
+    ) -> View<T>
+    where
+        T: ProjectItem,
+    {
+        use project::Item as _;
+
+        let Some(pane) = self.reveal_dock_pane(cx) else {
+            return self.open_project_item(project_item, cx);
+        };
+        let entry_id = project_item.read(cx).entry_id(cx);
+        if let Some(item) = entry_id
+            .and_then(|entry_id| pane.read(cx).item_for_entry(entry_id, cx))
+            .and_then(|item| item.downcast::<T>())
+        {
+            pane.update(cx, |pane, cx| {
+                if let Some(ix) = pane.index_for_item(&item) {
+                    pane.activate_item(ix, true, true, cx);
+                }
+            });
+            return item;
+        }
+
+        let item = cx.new_view(|cx| T::for_project_item(self.project().clone(), project_item, cx));
+        pane.update(cx, |pane, cx| {
+            pane.add_item(Box::new(item.clone()), true, true, None, cx)
+        });
+        item
+    }
+
     pub fn split_project_item<T>(
         &mut self,
         project_item: Model<T::Item>,
@@ -2052,6 +2875,44 @@ impl Workspace {
         }
     }
 
+    fn run_action_sequence(&mut self, action: &RunActionSequence, cx: &mut ViewContext<Self>) {
+        let actions = action
+            .0
+            .iter()
+            .map(|entry| build_action_from_json(entry, cx))
+            .collect::<Result<Vec<_>>>()
+            .context("invalid action sequence");
+
+        // Don't run any of the actions unless all of them are valid.
+        let Some(actions) = actions.log_err() else {
+            return;
+        };
+        for action in actions {
+            cx.dispatch_action(action);
+        }
+    }
+
+    /// Runs the action bound to a mouse button in the `mouse_bindings.workspace` setting,
+    /// for clicks that the element under the pointer didn't handle.
+    fn dispatch_mouse_binding(&mut self, event: &MouseDownEvent, cx: &mut ViewContext<Self>) {
+        let bindings = &WorkspaceSettings::get_global(cx).mouse_bindings.workspace;
+        if let Some(action) =
+            MouseBindings::action_for(bindings, event.button, &event.modifiers, cx)
+        {
+            cx.stop_propagation();
+            cx.dispatch_action(action);
+        }
+    }
+
+    /// Runs the action bound to a swipe direction in the `mouse_bindings.swipe` setting.
+    fn dispatch_swipe_binding(&mut self, event: &SwipeEvent, cx: &mut ViewContext<Self>) {
+        let bindings = &WorkspaceSettings::get_global(cx).mouse_bindings;
+        if let Some(action) = bindings.action_for_swipe(event.direction, cx) {
+            cx.stop_propagation();
+            cx.dispatch_action(action);
+        }
+    }
+
     pub fn activate_next_pane(&mut self, cx: &mut WindowContext) {
         let panes = self.center.panes();
         if let Some(ix) = panes.iter().position(|pane| **pane == self.active_pane) {
@@ -2070,6 +2931,123 @@ impl Workspace {
         }
     }
 
+    /// Moves focus to the next area of the workspace: the left dock, the center
+    /// panes, the bottom dock and the right dock, in that order.
+    pub fn focus_next_panel(&mut self, cx: &mut WindowContext) {
+        let focus_handles = self.focus_cycle_handles(cx);
+        let next_ix = focus_handles
+            .iter()
+            .position(|handle| handle.contains_focused(cx))
+            .map_or(0, |ix| (ix + 1) % focus_handles.len());
+        if let Some(handle) = focus_handles.get(next_ix) {
+            cx.focus(handle);
+        }
+    }
+
+    pub fn focus_previous_panel(&mut self, cx: &mut WindowContext) {
+        let focus_handles = self.focus_cycle_handles(cx);
+        let prev_ix = focus_handles
+            .iter()
+            .position(|handle| handle.contains_focused(cx))
+            .map_or(0, |ix| {
+                cmp::min(ix.wrapping_sub(1), focus_handles.len() - 1)
+            });
+        if let Some(handle) = focus_handles.get(prev_ix) {
+            cx.focus(handle);
+        }
+    }
+
+    /// Records that a pane gained focus, so that [`FocusPreviousPane`] can return to it.
+    pub(crate) fn pane_focused(&mut self, pane: &View<Pane>) {
+        let pane = pane.downgrade();
+        self.pane_focus_history
+            .retain(|entry| *entry != pane && entry.upgrade().is_some());
+        self.pane_focus_history.push_front(pane);
+        self.pane_focus_history.truncate(2);
+    }
+
+    /// Focuses the pane that was focused before the current one, revealing the dock
+    /// hosting it if needed.
+    pub fn focus_previous_pane(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(pane) = self
+            .pane_focus_history
+            .iter()
+            .skip(1)
+            .find_map(|pane| pane.upgrade())
+        else {
+            return;
+        };
+        for dock in [&self.left_dock, &self.bottom_dock, &self.right_dock] {
+            let panel_ix = dock
+                .read(cx)
+                .panels()
+                .position(|panel| panel.panes(cx).contains(&pane));
+            if let Some(panel_ix) = panel_ix {
+                dock.update(cx, |dock, cx| {
+                    dock.activate_panel(panel_ix, cx);
+                    dock.set_open(true, cx);
+                });
+                break;
+            }
+        }
+        cx.focus_view(&pane);
+    }
+
+    /// Whether the workspace is in read-only mode, where items can't be edited and
+    /// files can't be created, renamed or deleted.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn toggle_read_only(&mut self, _: &ToggleReadOnly, cx: &mut ViewContext<Self>) {
+        self.read_only = !self.read_only;
+        let read_only = self.read_only;
+        let panes = self
+            .panes
+            .iter()
+            .cloned()
+            .chain(self.dock_panes(cx))
+            .collect::<Vec<_>>();
+        for pane in panes {
+            let items = pane
+                .read(cx)
+                .items()
+                .map(|item| item.boxed_clone())
+                .collect::<Vec<_>>();
+            for item in items {
+                item.set_read_only(read_only, cx);
+            }
+        }
+        cx.emit(Event::ReadOnlyChanged(read_only));
+        cx.notify();
+    }
+
+    fn focus_cycle_handles(&self, cx: &WindowContext) -> Vec<FocusHandle> {
+        let dock_handles = |dock: &View<Dock>| -> Vec<FocusHandle> {
+            let dock = dock.read(cx);
+            if dock.is_open() {
+                dock.visible_panel()
+                    .into_iter()
+                    .chain(dock.split_panel())
+                    .map(|panel| panel.focus_handle(cx))
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        };
+
+        let mut handles = dock_handles(&self.left_dock);
+        handles.extend(
+            self.center
+                .panes()
+                .into_iter()
+                .map(|pane| pane.read(cx).focus_handle(cx)),
+        );
+        handles.extend(dock_handles(&self.bottom_dock));
+        handles.extend(dock_handles(&self.right_dock));
+        handles
+    }
+
     pub fn activate_pane_in_direction(
         &mut self,
         direction: SplitDirection,
@@ -2135,9 +3113,15 @@ impl Workspace {
             });
             self.active_item_path_changed(cx);
             self.last_active_center_pane = Some(pane.downgrade());
+            cx.emit(Event::ActiveItemChanged);
         }
 
         self.dismiss_zoomed_items_to_reveal(None, cx);
+        if WorkspaceSettings::get_global(cx).overlay_side_docks {
+            for position in [DockPosition::Left, DockPosition::Right] {
+                self.auto_hide_dock(position, cx);
+            }
+        }
         if pane.read(cx).is_zoomed() {
             self.zoomed = Some(pane.downgrade().into());
         } else {
@@ -2156,10 +3140,19 @@ impl Workspace {
         cx: &mut ViewContext<Self>,
     ) {
         match event {
-            pane::Event::AddItem { item } => item.added_to_pane(self, pane, cx),
+            pane::Event::AddItem { item } => {
+                item.added_to_pane(self, pane.clone(), cx);
+                cx.emit(Event::ItemOpened {
+                    pane,
+                    item: item.boxed_clone(),
+                });
+            }
             pane::Event::Split(direction) => {
                 self.split_and_clone(pane, *direction, cx);
             }
+            pane::Event::MoveToNewWindow => self
+                .move_pane_to_new_window(pane, cx)
+                .detach_and_notify_err(cx),
             pane::Event::Remove => self.remove_pane(pane, cx),
             pane::Event::ActivateItem { local } => {
                 if *local {
@@ -2168,6 +3161,7 @@ impl Workspace {
                 if &pane == self.active_pane() {
                     self.active_item_path_changed(cx);
                     self.update_active_view_for_followers(cx);
+                    cx.emit(Event::ActiveItemChanged);
                 }
             }
             pane::Event::ChangeItemTitle => {
@@ -2178,11 +3172,18 @@ impl Workspace {
             }
             pane::Event::RemoveItem { item_id } => {
                 self.update_window_edited(cx);
+                if pane == self.active_pane {
+                    cx.emit(Event::ActiveItemChanged);
+                }
                 if let hash_map::Entry::Occupied(entry) = self.panes_by_item.entry(*item_id) {
                     if entry.get().entity_id() == pane.entity_id() {
                         entry.remove();
                     }
                 }
+                cx.emit(Event::ItemClosed {
+                    pane,
+                    item_id: *item_id,
+                });
             }
             pane::Event::Focus => {
                 self.handle_pane_focused(pane.clone(), cx);
@@ -2216,13 +3217,26 @@ impl Workspace {
         cx: &mut ViewContext<Self>,
     ) -> View<Pane> {
         let new_pane = self.add_pane(cx);
-        self.center
-            .split(&pane_to_split, &new_pane, split_direction)
-            .unwrap();
-        cx.notify();
+        self.split_center_pane(pane_to_split, new_pane.clone(), split_direction, cx);
         new_pane
     }
 
+    fn split_center_pane(
+        &mut self,
+        pane: View<Pane>,
+        new_pane: View<Pane>,
+        direction: SplitDirection,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.center.split(&pane, &new_pane, direction).unwrap();
+        cx.emit(Event::PaneSplit {
+            pane,
+            new_pane,
+            direction,
+        });
+        cx.notify();
+    }
+
     pub fn split_and_clone(
         &mut self,
         pane: View<Pane>,
@@ -2233,7 +3247,7 @@ impl Workspace {
         let maybe_pane_handle = if let Some(clone) = item.clone_on_split(self.database_id(), cx) {
             let new_pane = self.add_pane(cx);
             new_pane.update(cx, |pane, cx| pane.add_item(clone, true, true, None, cx));
-            self.center.split(&pane, &new_pane, direction).unwrap();
+            self.split_center_pane(pane, new_pane.clone(), direction, cx);
             Some(new_pane)
         } else {
             None
@@ -2259,10 +3273,7 @@ impl Workspace {
 
         let new_pane = self.add_pane(cx);
         self.move_item(from.clone(), new_pane.clone(), item_id_to_move, 0, cx);
-        self.center
-            .split(&pane_to_split, &new_pane, split_direction)
-            .unwrap();
-        cx.notify();
+        self.split_center_pane(pane_to_split, new_pane, split_direction, cx);
     }
 
     pub fn split_pane_with_project_entry(
@@ -2274,9 +3285,7 @@ impl Workspace {
     ) -> Option<Task<Result<()>>> {
         let pane_to_split = pane_to_split.upgrade()?;
         let new_pane = self.add_pane(cx);
-        self.center
-            .split(&pane_to_split, &new_pane, split_direction)
-            .unwrap();
+        self.split_center_pane(pane_to_split, new_pane.clone(), split_direction, cx);
 
         let path = self.project.read(cx).path_for_entry(project_entry, cx)?;
         let task = self.open_path(path, Some(new_pane.downgrade()), true, cx);
@@ -2328,6 +3337,10 @@ impl Workspace {
             for removed_item in pane.read(cx).items() {
                 self.panes_by_item.remove(&removed_item.item_id());
             }
+            for (_, closed_item) in pane.read(cx).nav_history().closed_items().into_iter().rev() {
+                self.closed_items.push_front(closed_item);
+            }
+            self.closed_items.truncate(MAX_PERSISTED_CLOSED_ITEMS);
 
             cx.notify();
         } else {
@@ -2339,6 +3352,20 @@ impl Workspace {
         &self.panes
     }
 
+    /// Returns the navigation history of the pane containing the given item, for
+    /// recording positions in items that weren't handed one when they were added.
+    pub fn nav_history_for_item<T: Item>(
+        &self,
+        item: &View<T>,
+        cx: &AppContext,
+    ) -> Option<ItemNavHistory> {
+        let pane = self
+            .panes
+            .iter()
+            .find(|pane| pane.read(cx).index_for_item(item).is_some())?;
+        Some(pane.read(cx).nav_history_for_item(item))
+    }
+
     pub fn active_pane(&self) -> &View<Pane> {
         &self.active_pane
     }
@@ -2377,6 +3404,7 @@ impl Workspace {
             FollowerState {
                 leader_id,
                 active_view_id: None,
+                active_panel: None,
                 items_by_leader_view_id: Default::default(),
             },
         );
@@ -2594,11 +3622,78 @@ impl Workspace {
         }
     }
 
-    fn render_notifications(&self, _cx: &ViewContext<Self>) -> Option<Div> {
-        if self.notifications.is_empty() {
-            None
+    fn render_overlay_dock(
+        &self,
+        position: DockPosition,
+        overlay_side_docks: bool,
+        cx: &ViewContext<Self>,
+    ) -> Option<Div> {
+        let dock = match position {
+            DockPosition::Left => &self.left_dock,
+            DockPosition::Bottom => return None,
+            DockPosition::Right => &self.right_dock,
+        };
+        if !overlay_side_docks || !dock.read(cx).is_open() || self.zoomed_position == Some(position)
+        {
+            return None;
+        }
+
+        let colors = cx.theme().colors();
+        let div = div()
+            .absolute()
+            .z_index(1)
+            .top_0()
+            .bottom_0()
+            .flex()
+            .overflow_hidden()
+            .bg(colors.panel_background)
+            .border_color(colors.border)
+            .shadow_lg()
+            .child(dock.clone());
+        Some(if position == DockPosition::Left {
+            div.left_0().border_r()
         } else {
-            Some(
+            div.right_0().border_l()
+        })
+    }
+
+    /// While a panel is being dragged, renders drop targets along the edges of the
+    /// workspace for the closed docks it can move to, which aren't rendered otherwise.
+    fn render_dock_drop_targets(&self, cx: &ViewContext<Self>) -> Vec<Div> {
+        let Some(dragged) = cx.active_drag_value::<DraggedPanel>() else {
+            return Vec::new();
+        };
+        let drop_target_background = cx.theme().colors().drop_target_background;
+        [&self.left_dock, &self.bottom_dock, &self.right_dock]
+            .into_iter()
+            .filter_map(|dock| {
+                let position = dock.read(cx).position();
+                if dock.read(cx).is_open() || !Dock::can_accept_panel(position, dragged, cx) {
+                    return None;
+                }
+                let dock = dock.clone();
+                let target = div()
+                    .absolute()
+                    .z_index(1)
+                    .bg(theme::color_alpha(drop_target_background, 0.5))
+                    .drag_over::<DraggedPanel>(move |style, _, _| style.bg(drop_target_background))
+                    .on_drop(cx.listener(move |_, dragged: &DraggedPanel, cx| {
+                        dock.update(cx, |dock, cx| dock.handle_panel_drop(dragged, cx))
+                    }));
+                Some(match position {
+                    DockPosition::Left => target.top_0().bottom_0().left_0().w_16(),
+                    DockPosition::Right => target.top_0().bottom_0().right_0().w_16(),
+                    DockPosition::Bottom => target.left_0().right_0().bottom_0().h_16(),
+                })
+            })
+            .collect()
+    }
+
+    fn render_notifications(&self, _cx: &ViewContext<Self>) -> Option<Div> {
+        if self.notifications.is_empty() {
+            None
+        } else {
+            Some(
                 div()
                     .absolute()
                     .z_index(100)
@@ -2629,21 +3724,23 @@ impl Workspace {
         let client = &self.app_state.client;
         let project_id = self.project.read(cx).remote_id();
 
-        let active_view_id = self.active_item(cx).and_then(|i| {
-            Some(
-                i.to_followable_item_handle(cx)?
-                    .remote_id(client, cx)?
-                    .to_proto(),
-            )
-        });
+        let active_view_id = self
+            .focused_dock_item(cx)
+            .map(|(_, item)| item)
+            .or_else(|| self.active_item(cx)?.to_followable_item_handle(cx))
+            .and_then(|item| Some(item.remote_id(client, cx)?.to_proto()));
 
         cx.notify();
 
+        let mut panes = self.panes.clone();
+        if WorkspaceSettings::get_global(cx).share_dock_items {
+            panes.extend(self.dock_panes(cx));
+        }
+
         self.last_active_view_id = active_view_id.clone();
         proto::FollowResponse {
             active_view_id,
-            views: self
-                .panes()
+            views: panes
                 .iter()
                 .flat_map(|pane| {
                     let leader_id = self.leader_for_pane(pane);
@@ -2707,6 +3804,7 @@ impl Workspace {
                                 } else {
                                     None
                                 };
+                            state.active_panel = update_active_view.panel.clone();
                         }
                     }
                     anyhow::Ok(())
@@ -2823,13 +3921,37 @@ impl Workspace {
                             .remote_id(&self.app_state.client, cx)
                             .map(|id| id.to_proto()),
                         leader_id: self.leader_for_pane(&self.active_pane),
+                        panel: None,
                     };
                 }
             }
         }
 
-        if update.id != self.last_active_view_id {
+        if update.id.is_none() {
+            if let Some((pane, item)) = self.focused_dock_item(cx) {
+                is_project_item = item.is_project_item(cx);
+                update = proto::UpdateActiveView {
+                    id: item
+                        .remote_id(&self.app_state.client, cx)
+                        .map(|id| id.to_proto()),
+                    leader_id: self.leader_for_pane(&pane),
+                    panel: None,
+                };
+            }
+        }
+
+        // Panels can't be followed like items, but followers can at least show the one
+        // the leader is using instead of losing track of them.
+        if update.id.is_none() {
+            if let Some(panel) = self.focused_panel(cx) {
+                is_project_item = true;
+                update.panel = Some(panel.persistent_name().to_string());
+            }
+        }
+
+        if update.id != self.last_active_view_id || update.panel != self.last_active_panel {
             self.last_active_view_id = update.id.clone();
+            self.last_active_panel = update.panel.clone();
             self.update_followers(
                 is_project_item,
                 proto::update_followers::Variant::UpdateActiveView(update),
@@ -2838,6 +3960,79 @@ impl Workspace {
         }
     }
 
+    /// Whether the items in `pane` are shared with followers. Dock panes are only shared
+    /// when `share_dock_items` is enabled.
+    pub(crate) fn shares_pane_with_followers(&self, pane: &View<Pane>, cx: &AppContext) -> bool {
+        self.panes.contains(pane) || WorkspaceSettings::get_global(cx).share_dock_items
+    }
+
+    /// The followable item active in the focused dock pane, if dock items are shared.
+    fn focused_dock_item(
+        &self,
+        cx: &WindowContext,
+    ) -> Option<(View<Pane>, Box<dyn FollowableItemHandle>)> {
+        if !WorkspaceSettings::get_global(cx).share_dock_items {
+            return None;
+        }
+        self.dock_panes(cx).into_iter().find_map(|pane| {
+            if !pane.read(cx).has_focus(cx) {
+                return None;
+            }
+            let item = pane.read(cx).active_item()?.to_followable_item_handle(cx)?;
+            Some((pane, item))
+        })
+    }
+
+    /// Sends the items already in the docks to followers when `share_dock_items` gets
+    /// enabled, since they were skipped when they were added.
+    fn share_dock_items_if_enabled(&mut self, cx: &mut ViewContext<Self>) {
+        let share_dock_items = WorkspaceSettings::get_global(cx).share_dock_items;
+        if mem::replace(&mut self.sharing_dock_items, share_dock_items) || !share_dock_items {
+            return;
+        }
+        for pane in self.dock_panes(cx) {
+            for item in pane.read(cx).items() {
+                let Some(item) = item.to_followable_item_handle(cx) else {
+                    continue;
+                };
+                let Some(variant) = item.to_state_proto(cx) else {
+                    continue;
+                };
+                self.update_followers(
+                    item.is_project_item(cx),
+                    proto::update_followers::Variant::CreateView(proto::View {
+                        id: item
+                            .remote_id(&self.app_state.client, cx)
+                            .map(|id| id.to_proto()),
+                        variant: Some(variant),
+                        leader_id: self.leader_for_pane(&pane),
+                    }),
+                    cx,
+                );
+            }
+        }
+    }
+
+    fn focused_panel(&self, cx: &WindowContext) -> Option<Arc<dyn PanelHandle>> {
+        [&self.left_dock, &self.bottom_dock, &self.right_dock]
+            .into_iter()
+            .filter_map(|dock| dock.read(cx).visible_panel().cloned())
+            .find(|panel| panel.focus_handle(cx).contains_focused(cx))
+    }
+
+    /// Opens the dock panel with the given persistent name, without focusing it.
+    fn reveal_panel(&mut self, name: &str, cx: &mut ViewContext<Self>) {
+        for dock in [&self.left_dock, &self.bottom_dock, &self.right_dock] {
+            if let Some(panel_index) = dock.read(cx).panel_index_for_persistent_name(name, cx) {
+                dock.update(cx, |dock, cx| {
+                    dock.activate_panel(panel_index, cx);
+                    dock.set_open(true, cx);
+                });
+                return;
+            }
+        }
+    }
+
     fn update_followers(
         &self,
         project_only: bool,
@@ -2869,6 +4064,7 @@ impl Workspace {
         let room = call.read(cx).room()?.read(cx);
         let participant = room.remote_participant_for_peer_id(leader_id)?;
         let mut items_to_activate = Vec::new();
+        let mut panel_to_reveal = None;
 
         let leader_in_this_app;
         let leader_in_this_project;
@@ -2900,11 +4096,20 @@ impl Workspace {
                 continue;
             }
 
+            if let (Some(panel), true) = (&state.active_panel, leader_in_this_project) {
+                panel_to_reveal = Some(panel.clone());
+                continue;
+            }
+
             if let Some(shared_screen) = self.shared_screen_for_peer(leader_id, pane, cx) {
                 items_to_activate.push((pane.clone(), Box::new(shared_screen)));
             }
         }
 
+        if let Some(panel) = panel_to_reveal {
+            self.reveal_panel(&panel, cx);
+        }
+
         for (pane, item) in items_to_activate {
             let pane_was_focused = pane.read(cx).has_focus(cx);
             if let Some(index) = pane.update(cx, |pane, _| pane.index_for_item(item.as_ref())) {
@@ -2956,11 +4161,11 @@ impl Workspace {
                     if let Some(item) = pane.active_item() {
                         item.workspace_deactivated(cx);
                     }
-                    if matches!(
-                        WorkspaceSettings::get_global(cx).autosave,
-                        AutosaveSetting::OnWindowChange | AutosaveSetting::OnFocusChange
-                    ) {
-                        for item in pane.items() {
+                    for item in pane.items() {
+                        if matches!(
+                            settings_for_item::<WorkspaceSettings>(item.as_ref(), cx).autosave,
+                            AutosaveSetting::OnWindowChange | AutosaveSetting::OnFocusChange
+                        ) {
                             Pane::autosave_item(item.as_ref(), self.project.clone(), cx)
                                 .detach_and_log_err(cx);
                         }
@@ -2974,6 +4179,21 @@ impl Workspace {
         self.active_call.as_ref().map(|(call, _)| call)
     }
 
+    fn handle_dock_event(
+        &mut self,
+        dock: View<Dock>,
+        event: &DockEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let DockEvent::VisibilityChanged = event {
+            let dock = dock.read(cx);
+            cx.emit(Event::DockToggled {
+                position: dock.position(),
+                open: dock.is_open(),
+            });
+        }
+    }
+
     fn on_active_call_event(
         &mut self,
         _: Model<ActiveCall>,
@@ -3036,15 +4256,13 @@ impl Workspace {
 
     fn schedule_serialize(&mut self, cx: &mut ViewContext<Self>) {
         self._schedule_serialize = Some(cx.spawn(|this, mut cx| async move {
-            cx.background_executor()
-                .timer(Duration::from_millis(100))
-                .await;
+            cx.background_executor().timer(db::WRITE_DEBOUNCE).await;
             this.update(&mut cx, |this, cx| this.serialize_workspace(cx))
                 .log_err();
         }));
     }
 
-    fn serialize_workspace(&self, cx: &mut WindowContext) {
+    fn serialized_workspace(&self, cx: &mut WindowContext) -> Option<SerializedWorkspace> {
         fn serialize_pane_handle(pane_handle: &View<Pane>, cx: &WindowContext) -> SerializedPane {
             let (items, active) = {
                 let pane = pane_handle.read(cx);
@@ -3052,10 +4270,12 @@ impl Workspace {
                 (
                     pane.items()
                         .filter_map(|item_handle| {
+                            let item = item_handle.to_serializable_item_handle(cx)?;
                             Some(SerializedItem {
-                                kind: Arc::from(item_handle.serialized_item_kind()?),
+                                kind: Arc::from(item.serialized_item_kind()),
                                 item_id: item_handle.item_id().as_u64(),
                                 active: Some(item_handle.item_id()) == active_item_id,
+                                state: item.serialize(cx),
                             })
                         })
                         .collect::<Vec<_>>(),
@@ -3100,6 +4320,7 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let left_popped_out_panels = left_dock.popped_out_panel_names();
 
             let right_dock = this.right_dock.read(cx);
             let right_visible = right_dock.is_open();
@@ -3110,6 +4331,7 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let right_popped_out_panels = right_dock.popped_out_panel_names();
 
             let bottom_dock = this.bottom_dock.read(cx);
             let bottom_visible = bottom_dock.is_open();
@@ -3120,49 +4342,127 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let bottom_popped_out_panels = bottom_dock.popped_out_panel_names();
 
             DockStructure {
                 left: DockData {
                     visible: left_visible,
                     active_panel: left_active_panel,
                     zoom: left_dock_zoom,
+                    popped_out_panels: left_popped_out_panels,
                 },
                 right: DockData {
                     visible: right_visible,
                     active_panel: right_active_panel,
                     zoom: right_dock_zoom,
+                    popped_out_panels: right_popped_out_panels,
                 },
                 bottom: DockData {
                     visible: bottom_visible,
                     active_panel: bottom_active_panel,
                     zoom: bottom_dock_zoom,
+                    popped_out_panels: bottom_popped_out_panels,
                 },
             }
         }
 
-        if let Some(location) = self.location(cx) {
-            // Load bearing special case:
-            //  - with_local_workspace() relies on this to not have other stuff open
-            //    when you open your log
-            if !location.paths().is_empty() {
-                let center_group = build_serialized_pane_group(&self.center.root, cx);
-                let docks = build_serialized_docks(self, cx);
-
-                let serialized_workspace = SerializedWorkspace {
-                    id: self.database_id,
-                    location,
-                    center_group,
-                    bounds: Default::default(),
-                    display: Default::default(),
-                    docks,
-                };
+        fn build_serialized_closed_items(
+            this: &Workspace,
+            cx: &WindowContext,
+        ) -> Vec<SerializedClosedItem> {
+            let mut closed_items = this
+                .panes
+                .iter()
+                .flat_map(|pane| pane.read(cx).nav_history().closed_items())
+                .collect::<Vec<_>>();
+            closed_items.sort_by_key(|(timestamp, _)| cmp::Reverse(*timestamp));
 
-                cx.spawn(|_| persistence::DB.save_workspace(serialized_workspace))
-                    .detach();
-            }
+            let mut seen_paths = HashSet::default();
+            closed_items
+                .into_iter()
+                .map(|(_, closed_item)| closed_item)
+                .chain(this.closed_items.iter().cloned())
+                .filter(|closed_item| seen_paths.insert(closed_item.abs_path.clone()))
+                .take(MAX_PERSISTED_CLOSED_ITEMS)
+                .collect()
+        }
+
+        let location = self.location(cx)?;
+        // Load bearing special case:
+        //  - with_local_workspace() relies on this to not have other stuff open
+        //    when you open your log
+        if location.paths().is_empty() {
+            return None;
+        }
+        let center_group = build_serialized_pane_group(&self.center.root, cx);
+        let docks = build_serialized_docks(self, cx);
+        let closed_items = build_serialized_closed_items(self, cx);
+
+        Some(SerializedWorkspace {
+            id: self.database_id,
+            location,
+            center_group,
+            bounds: Default::default(),
+            display: Default::default(),
+            docks,
+            closed_items,
+            ui_zoom: Some(self.ui_zoom),
+        })
+    }
+
+    fn serialize_workspace(&self, cx: &mut WindowContext) {
+        if let Some(serialized_workspace) = self.serialized_workspace(cx) {
+            let siblings = self.sibling_database_ids(cx);
+            cx.spawn(|_| persistence::DB.save_workspace_alongside(serialized_workspace, siblings))
+                .detach();
         }
     }
 
+    /// The database ids of the other windows onto this workspace's project. Their
+    /// rows share this workspace's location, so saving must not collect them.
+    fn sibling_database_ids(&self, cx: &AppContext) -> Vec<WorkspaceId> {
+        cx.windows()
+            .into_iter()
+            .filter_map(|window| window.downcast::<Workspace>()?.read(cx).ok())
+            .filter(|workspace| {
+                workspace.project == self.project && workspace.database_id != self.database_id
+            })
+            .map(|workspace| workspace.database_id)
+            .collect()
+    }
+
+    /// Replaces the workspace in this window with a new one for the same project,
+    /// restoring the layout and items from their serialized state. Unsaved changes
+    /// are saved first, and nothing happens if the user cancels saving them.
+    pub fn reload(&mut self, cx: &mut ViewContext<Self>) -> Task<Result<()>> {
+        let Some(location) = self.location(cx) else {
+            return Task::ready(Err(anyhow!("only local projects can be reloaded")));
+        };
+        let Some(window) = cx.window_handle().downcast::<Self>() else {
+            return Task::ready(Ok(()));
+        };
+        let paths = location.paths().to_vec();
+        let app_state = self.app_state.clone();
+        let save = self.save_all_internal(SaveIntent::Close, cx);
+        cx.spawn(|this, mut cx| async move {
+            if !save.await? {
+                return Ok(());
+            }
+            let (serialized_workspace, siblings) = this.update(&mut cx, |this, cx| {
+                (this.serialized_workspace(cx), this.sibling_database_ids(cx))
+            })?;
+            if let Some(serialized_workspace) = serialized_workspace {
+                persistence::DB
+                    .save_workspace_alongside(serialized_workspace, siblings)
+                    .await;
+            }
+            (*cx)
+                .update(|cx| Workspace::new_local(paths, app_state, Some(window), cx))?
+                .await?;
+            Ok(())
+        })
+    }
+
     pub(crate) fn load_workspace(
         serialized_workspace: SerializedWorkspace,
         paths_to_open: Vec<Option<ProjectPath>>,
@@ -3225,6 +4525,11 @@ impl Workspace {
                     }
                 }
 
+                workspace.closed_items = serialized_workspace.closed_items.into_iter().collect();
+                if let Some(ui_zoom) = serialized_workspace.ui_zoom {
+                    workspace.ui_zoom = ui_zoom.clamp(MIN_UI_ZOOM, MAX_UI_ZOOM);
+                }
+
                 let docks = serialized_workspace.docks;
 
                 let right = docks.right.clone();
@@ -3256,6 +4561,7 @@ impl Workspace {
             .on_action(cx.listener(Self::close_all_items_and_panes))
             .on_action(cx.listener(Self::save_all))
             .on_action(cx.listener(Self::add_folder_to_project))
+            .on_action(cx.listener(Self::new_window_for_project))
             .on_action(cx.listener(Self::follow_next_collaborator))
             .on_action(cx.listener(|workspace, _: &Unfollow, cx| {
                 let pane = workspace.active_pane().clone();
@@ -3277,6 +4583,25 @@ impl Workspace {
             .on_action(
                 cx.listener(|workspace, _: &ActivateNextPane, cx| workspace.activate_next_pane(cx)),
             )
+            .on_action(
+                cx.listener(|workspace, _: &FocusNextPanel, cx| workspace.focus_next_panel(cx)),
+            )
+            .on_action(cx.listener(|workspace, _: &FocusPreviousPanel, cx| {
+                workspace.focus_previous_panel(cx)
+            }))
+            .on_action(
+                cx.listener(|workspace, _: &FocusPreviousPane, cx| {
+                    workspace.focus_previous_pane(cx)
+                }),
+            )
+            .on_action(cx.listener(Self::toggle_read_only))
+            .on_action(cx.listener(Self::paste_to_dock))
+            .on_action(cx.listener(|workspace, _: &JumpBack, cx| {
+                workspace.jump_back(cx).detach_and_log_err(cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &JumpForward, cx| {
+                workspace.jump_forward(cx).detach_and_log_err(cx)
+            }))
             .on_action(
                 cx.listener(|workspace, action: &ActivatePaneInDirection, cx| {
                     workspace.activate_pane_in_direction(action.0, cx)
@@ -3303,9 +4628,31 @@ impl Workspace {
                     workspace.close_all_docks(cx);
                 }),
             )
+            .on_action(
+                cx.listener(|workspace: &mut Workspace, _: &ToggleAllPanels, cx| {
+                    workspace.toggle_all_panels(cx);
+                }),
+            )
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &MoveFocusedPanelToOppositeSide, cx| {
+                    workspace.move_focused_panel_to_opposite_side(cx);
+                },
+            ))
             .on_action(cx.listener(Workspace::open))
             .on_action(cx.listener(Workspace::close_window))
             .on_action(cx.listener(Workspace::activate_pane_at_index))
+            .on_action(cx.listener(Workspace::run_action_sequence))
+            .on_action(cx.listener(SettingsEditor::deploy))
+            .on_action(cx.listener(KeymapEditor::deploy))
+            .on_action(cx.listener(|workspace: &mut Workspace, _: &ZoomIn, cx| {
+                workspace.set_ui_zoom(workspace.ui_zoom + UI_ZOOM_STEP, cx);
+            }))
+            .on_action(cx.listener(|workspace: &mut Workspace, _: &ZoomOut, cx| {
+                workspace.set_ui_zoom(workspace.ui_zoom - UI_ZOOM_STEP, cx);
+            }))
+            .on_action(cx.listener(|workspace: &mut Workspace, _: &ResetZoom, cx| {
+                workspace.set_ui_zoom(DEFAULT_UI_ZOOM, cx);
+            }))
             .on_action(
                 cx.listener(|workspace: &mut Workspace, _: &ReopenClosedItem, cx| {
                     workspace.reopen_closed_item(cx).detach();
@@ -3365,20 +4712,72 @@ impl Workspace {
     }
 
     pub fn has_active_modal(&self, cx: &WindowContext<'_>) -> bool {
-        self.modal_layer.read(cx).has_active_modal()
+        self.modal_manager.read(cx).has_active_modal()
     }
 
     pub fn active_modal<V: ManagedView + 'static>(&mut self, cx: &AppContext) -> Option<View<V>> {
-        self.modal_layer.read(cx).active_modal()
+        self.modal_manager.read(cx).active_modal()
     }
 
     pub fn toggle_modal<V: ModalView, B>(&mut self, cx: &mut WindowContext, build: B)
     where
         B: FnOnce(&mut ViewContext<V>) -> V,
     {
-        self.modal_layer
-            .update(cx, |modal_layer, cx| modal_layer.toggle_modal(cx, build))
+        self.modal_manager.update(cx, |modal_manager, cx| {
+            modal_manager.toggle_modal(cx, build)
+        })
+    }
+
+    pub fn push_modal<V: ModalView, B>(&mut self, cx: &mut WindowContext, build: B) -> View<V>
+    where
+        B: FnOnce(&mut ViewContext<V>) -> V,
+    {
+        self.modal_manager
+            .update(cx, |modal_manager, cx| modal_manager.push_modal(cx, build))
+    }
+
+    pub fn hide_modal(&mut self, cx: &mut WindowContext) -> bool {
+        self.modal_manager
+            .update(cx, |modal_manager, cx| modal_manager.hide_modal(cx))
+    }
+}
+
+/// Converts the bounds of a window, stored relative to the display containing it,
+/// back to global coordinates. When that display is gone, the window moves to the
+/// primary display, keeping its size unless it doesn't fit there.
+fn restore_window_bounds(
+    mut bounds: WindowBounds,
+    display_uuid: Uuid,
+    cx: &AppContext,
+) -> Option<(WindowBounds, Uuid)> {
+    let displays = cx.displays();
+    let saved_display = displays
+        .iter()
+        .find(|display| display.uuid().ok() == Some(display_uuid));
+    let (display, display_uuid) = match saved_display {
+        Some(display) => (display, display_uuid),
+        None => {
+            let primary_display = displays.first()?;
+            (primary_display, primary_display.uuid().ok()?)
+        }
+    };
+
+    if let WindowBounds::Fixed(mut window_bounds) = bounds {
+        let display_bounds = display.bounds();
+        if saved_display.is_none() {
+            window_bounds.origin = Point::default();
+            if window_bounds.size.width > display_bounds.size.width {
+                window_bounds.size.width = display_bounds.size.width;
+            }
+            if window_bounds.size.height > display_bounds.size.height {
+                window_bounds.size.height = display_bounds.size.height;
+            }
+        }
+        window_bounds.origin.x += display_bounds.origin.x;
+        window_bounds.origin.y += display_bounds.origin.y;
+        bounds = WindowBounds::Fixed(window_bounds);
     }
+    Some((bounds, display_uuid))
 }
 
 fn window_bounds_env_override(cx: &AsyncAppContext) -> Option<WindowBounds> {
@@ -3488,6 +4887,28 @@ fn open_items(
     })
 }
 
+/// How an item that couldn't be saved is referred to in the failure notification.
+fn save_failure_label(item: &dyn ItemHandle, cx: &AppContext) -> String {
+    item.project_path(cx)
+        .map(|project_path| project_path.path.to_string_lossy().to_string())
+        .or_else(|| item.tab_description(0, cx).map(|text| text.to_string()))
+        .unwrap_or_else(|| "untitled".to_string())
+}
+
+/// Brings a panel popped out of its dock to the front, focusing it in its window.
+fn focus_popped_out_panel(
+    panel: &dyn PanelHandle,
+    window: AnyWindowHandle,
+    cx: &mut WindowContext,
+) {
+    window
+        .update(cx, |_, cx| {
+            cx.activate_window();
+            panel.focus_handle(cx).focus(cx);
+        })
+        .log_err();
+}
+
 fn notify_if_database_failed(workspace: WindowHandle<Workspace>, cx: &mut AsyncAppContext) {
     const REPORT_ISSUE_URL: &str ="https://github.com/zed-industries/zed/issues/new?assignees=&labels=defect%2Ctriage&template=2_bug_report.yml";
 
@@ -3515,6 +4936,24 @@ impl FocusableView for Workspace {
 #[derive(Clone, Render)]
 struct DraggedDock(DockPosition);
 
+#[derive(Clone, Render)]
+struct DraggedDockSplit(DockPosition);
+
+/// Builds an action given as in the keymap: either its name, or a `[name, data]` pair.
+pub(crate) fn build_action_from_json(
+    entry: &serde_json::Value,
+    cx: &AppContext,
+) -> Result<Box<dyn Action>> {
+    match entry {
+        serde_json::Value::String(name) => cx.build_action(name, None),
+        serde_json::Value::Array(items) => match items.as_slice() {
+            [serde_json::Value::String(name), data] => cx.build_action(name, Some(data.clone())),
+            _ => Err(anyhow!("expected a [name, data] pair, got {entry}")),
+        },
+        _ => Err(anyhow!("expected an action name, got {entry}")),
+    }
+}
+
 impl Render for Workspace {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let mut context = KeyContext::default();
@@ -3530,10 +4969,18 @@ impl Render for Workspace {
 
         let theme = cx.theme().clone();
         let colors = theme.colors();
-        cx.set_rem_size(ui_font_size);
+        cx.set_rem_size(ui_font_size * self.ui_zoom);
+        let overlay_side_docks = WorkspaceSettings::get_global(cx).overlay_side_docks;
 
         self.actions(div(), cx)
             .key_context(context)
+            .on_drop(cx.listener(|workspace, paths: &ExternalPaths, cx| {
+                workspace
+                    .open_dropped_paths(paths.paths().to_vec(), None, cx)
+                    .detach();
+            }))
+            .on_any_mouse_down(cx.listener(Self::dispatch_mouse_binding))
+            .on_swipe(cx.listener(Self::dispatch_swipe_binding))
             .relative()
             .size_full()
             .flex()
@@ -3595,22 +5042,24 @@ impl Render for Workspace {
                             }
                         }),
                     )
-                    .child(self.modal_layer.clone())
+                    .child(self.modal_manager.clone())
                     .child(
                         div()
                             .flex()
                             .flex_row()
                             .h_full()
                             // Left Dock
-                            .children(self.zoomed_position.ne(&Some(DockPosition::Left)).then(
-                                || {
+                            .children(
+                                (!overlay_side_docks
+                                    && self.zoomed_position.ne(&Some(DockPosition::Left)))
+                                .then(|| {
                                     div()
                                         .flex()
                                         .flex_none()
                                         .overflow_hidden()
                                         .child(self.left_dock.clone())
-                                },
-                            ))
+                                }),
+                            )
                             // Panes
                             .child(
                                 div()
@@ -3634,16 +5083,21 @@ impl Render for Workspace {
                                     ),
                             )
                             // Right Dock
-                            .children(self.zoomed_position.ne(&Some(DockPosition::Right)).then(
-                                || {
+                            .children(
+                                (!overlay_side_docks
+                                    && self.zoomed_position.ne(&Some(DockPosition::Right)))
+                                .then(|| {
                                     div()
                                         .flex()
                                         .flex_none()
                                         .overflow_hidden()
                                         .child(self.right_dock.clone())
-                                },
-                            )),
+                                }),
+                            ),
                     )
+                    .children(self.render_overlay_dock(DockPosition::Left, overlay_side_docks, cx))
+                    .children(self.render_overlay_dock(DockPosition::Right, overlay_side_docks, cx))
+                    .children(self.render_dock_drop_targets(cx))
                     .children(self.render_notifications(cx))
                     .children(self.zoomed.as_ref().and_then(|view| {
                         let zoomed_view = view.upgrade()?;
@@ -4401,7 +5855,10 @@ mod tests {
         },
     };
     use fs::FakeFs;
-    use gpui::{px, DismissEvent, TestAppContext, VisualTestContext};
+    use gpui::{
+        point, px, DismissEvent, Modifiers, MouseButton, MouseMoveEvent, MouseUpEvent,
+        NavigationDirection, TestAppContext, VisualTestContext,
+    };
     use project::{Project, ProjectEntryId};
     use serde_json::json;
     use settings::SettingsStore;
@@ -4454,6 +5911,154 @@ mod tests {
         item3.update(cx, |item, _| assert_eq!(item.tab_detail.get(), Some(3)));
     }
 
+    #[gpui::test]
+    async fn test_workspace_events(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let _subscription = workspace.update(cx, |_, cx| {
+            let events = events.clone();
+            Workspace::subscribe_to_events(
+                &cx.view().clone(),
+                |event| match event {
+                    Event::ItemOpened { .. } => Some("item opened".to_string()),
+                    Event::ItemClosed { .. } => Some("item closed".to_string()),
+                    Event::PaneSplit { direction, .. } => Some(format!("split {direction:?}")),
+                    Event::DockPositionChanged {
+                        panel,
+                        old_position,
+                        new_position,
+                    } => Some(format!(
+                        "{panel} moved {old_position:?} to {new_position:?}"
+                    )),
+                    Event::DockToggled { position, open } => {
+                        Some(format!("{position:?} dock open: {open}"))
+                    }
+                    _ => None,
+                },
+                move |_, event, _| events.borrow_mut().push(event),
+                cx,
+            )
+        });
+
+        let item = cx.new_view(|cx| TestItem::new(cx));
+        let pane = workspace.update(cx, |workspace, cx| {
+            workspace.add_item(Box::new(item.clone()), cx);
+            workspace.active_pane().clone()
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(pane.clone(), SplitDirection::Right, cx);
+        });
+        pane.update(cx, |pane, cx| {
+            pane.close_item_by_id(item.entity_id(), SaveIntent::Close, cx)
+        })
+        .await
+        .unwrap();
+
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), cx);
+            workspace
+                .left_dock()
+                .update(cx, |dock, cx| dock.set_open(true, cx));
+            panel
+        });
+        panel.update(cx, |panel, cx| panel.set_position(DockPosition::Right, cx));
+        cx.run_until_parked();
+
+        assert_eq!(
+            *events.borrow(),
+            [
+                "item opened",
+                "split Right",
+                "item closed",
+                "Left dock open: true",
+                "TestPanel moved Left to Right",
+                "Left dock open: false",
+                "Right dock open: true",
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_dragging_panel_onto_closed_dock(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TestPanel {
+                icon: Some(ui::IconName::FileTree),
+                icon_tooltip: Some("Test Panel"),
+                ..TestPanel::new(DockPosition::Right, cx)
+            });
+            workspace.add_panel(panel.clone(), cx);
+            workspace.close_all_docks(cx);
+            panel
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.render_dock_drop_targets(cx).is_empty())
+        });
+
+        // Start dragging the panel by its button in the status bar.
+        let button = cx.debug_bounds("ICON-FileTree").unwrap();
+        cx.simulate_event(MouseDownEvent {
+            position: button.center(),
+            button: MouseButton::Left,
+            modifiers: Modifiers::default(),
+            click_count: 1,
+        });
+        cx.simulate_event(MouseMoveEvent {
+            position: button.center() - point(px(0.), px(50.)),
+            pressed_button: Some(MouseButton::Left),
+            modifiers: Modifiers::default(),
+        });
+        cx.run_until_parked();
+
+        // Drop targets are shown for the closed docks the panel isn't already in.
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.render_dock_drop_targets(cx).len(), 2)
+        });
+
+        // Dropping the panel onto the left edge moves it to the left dock.
+        let position = point(px(10.), button.center().y - px(200.));
+        cx.simulate_event(MouseMoveEvent {
+            position,
+            pressed_button: Some(MouseButton::Left),
+            modifiers: Modifiers::default(),
+        });
+        cx.simulate_event(MouseUpEvent {
+            position,
+            button: MouseButton::Left,
+            modifiers: Modifiers::default(),
+            click_count: 1,
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            panel.read_with(cx, |panel, _| panel.position),
+            DockPosition::Left
+        );
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace
+                .left_dock()
+                .read(cx)
+                .panel::<TestPanel>()
+                .is_some());
+            assert!(workspace
+                .right_dock()
+                .read(cx)
+                .panel::<TestPanel>()
+                .is_none());
+            assert!(workspace.render_dock_drop_targets(cx).is_empty());
+        });
+    }
+
     #[gpui::test]
     async fn test_tracking_active_path(cx: &mut TestAppContext) {
         init_test(cx);
@@ -4559,8 +6164,8 @@ mod tests {
         let task = workspace.update(cx, |w, cx| w.prepare_to_close(false, cx));
         assert!(task.await.unwrap());
 
-        // When there are dirty untitled items, prompt to save each one. If the user
-        // cancels any prompt, then abort.
+        // When there are several dirty items, prompt about all of them at once. If
+        // the user cancels the prompt, then abort.
         let item2 = cx.new_view(|cx| TestItem::new(cx).with_dirty(true));
         let item3 = cx.new_view(|cx| {
             TestItem::new(cx)
@@ -4573,37 +6178,454 @@ mod tests {
         });
         let task = workspace.update(cx, |w, cx| w.prepare_to_close(false, cx));
         cx.executor().run_until_parked();
-        cx.simulate_prompt_answer(2); // cancel save all
-        cx.executor().run_until_parked();
-        cx.simulate_prompt_answer(2); // cancel save all
+        assert!(workspace.update(cx, |w, cx| w
+            .active_modal::<UnsavedChangesModal>(cx)
+            .is_some()));
+        cx.dispatch_action(menu::Cancel);
         cx.executor().run_until_parked();
         assert!(!cx.has_pending_prompt());
+        assert!(workspace.update(cx, |w, cx| w
+            .active_modal::<UnsavedChangesModal>(cx)
+            .is_none()));
         assert!(!task.await.unwrap());
     }
 
     #[gpui::test]
-    async fn test_close_pane_items(cx: &mut TestAppContext) {
+    async fn test_windows_onto_same_project_keep_their_rows(cx: &mut TestAppContext) {
         init_test(cx);
 
         let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/two-windows", json!({ "one.txt": "" }))
+            .await;
+        let project = Project::test(fs, ["/two-windows".as_ref()], cx).await;
 
-        let project = Project::test(fs, None, cx).await;
-        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
-
-        let item1 = cx.new_view(|cx| {
-            TestItem::new(cx)
-                .with_dirty(true)
-                .with_project_items(&[TestProjectItem::new(1, "1.txt", cx)])
-        });
-        let item2 = cx.new_view(|cx| {
-            TestItem::new(cx)
-                .with_dirty(true)
-                .with_conflict(true)
-                .with_project_items(&[TestProjectItem::new(2, "2.txt", cx)])
+        let first_id = DB.next_id().await.unwrap();
+        let first_window = cx.add_window(|cx| {
+            let mut workspace = Workspace::test_new(project.clone(), cx);
+            workspace.database_id = first_id;
+            workspace
         });
-        let item3 = cx.new_view(|cx| {
-            TestItem::new(cx)
-                .with_dirty(true)
+        let second_window = first_window
+            .update(cx, |workspace, cx| workspace.open_window_for_project(cx))
+            .unwrap()
+            .await
+            .unwrap();
+        let second_id = second_window
+            .update(cx, |workspace, _| workspace.database_id())
+            .unwrap();
+        assert_ne!(first_id, second_id);
+
+        for window in [first_window, second_window, first_window] {
+            window
+                .update(cx, |workspace, cx| workspace.serialize_workspace(cx))
+                .unwrap();
+            cx.run_until_parked();
+        }
+        assert_eq!(
+            DB.workspace_ids_for_roots(&["/two-windows"]).unwrap(),
+            vec![first_id, second_id]
+        );
+    }
+
+    #[gpui::test]
+    fn test_restore_window_bounds(cx: &mut TestAppContext) {
+        let saved_bounds = WindowBounds::Fixed(Bounds {
+            origin: Point::new(GlobalPixels::from(100.), GlobalPixels::from(200.)),
+            size: size(GlobalPixels::from(2500.), GlobalPixels::from(800.)),
+        });
+        let display_uuid = cx.update(|cx| cx.displays()[0].uuid().unwrap());
+
+        // On the display it was saved on, the window is restored as it was.
+        let restored = cx.update(|cx| restore_window_bounds(saved_bounds, display_uuid, cx));
+        assert_eq!(restored, Some((saved_bounds, display_uuid)));
+
+        // On the primary display, the window is moved to its origin and shrunk to fit.
+        let restored = cx.update(|cx| restore_window_bounds(saved_bounds, Uuid::new_v4(), cx));
+        assert_eq!(
+            restored,
+            Some((
+                WindowBounds::Fixed(Bounds {
+                    origin: Point::default(),
+                    size: size(GlobalPixels::from(1920.), GlobalPixels::from(800.)),
+                }),
+                display_uuid
+            ))
+        );
+
+        // Maximized and fullscreen windows are left as they are.
+        let restored =
+            cx.update(|cx| restore_window_bounds(WindowBounds::Maximized, Uuid::new_v4(), cx));
+        assert_eq!(restored, Some((WindowBounds::Maximized, display_uuid)));
+    }
+
+    #[gpui::test]
+    async fn test_move_pane_to_new_window(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(register_project_item::<TestItem>);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/move-pane", json!({ "one.txt": "" })).await;
+        let project = Project::test(fs, ["/move-pane".as_ref()], cx).await;
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees().next().unwrap().read(cx).id()
+        });
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+
+        let new_item = |path: &str, cx: &mut VisualTestContext| {
+            let project_path = ProjectPath {
+                worktree_id,
+                path: Path::new(path).into(),
+            };
+            cx.new_view(|cx| {
+                let project_item = cx.new_model(|_| TestProjectItem {
+                    entry_id: None,
+                    project_path: Some(project_path),
+                });
+                TestItem::new(cx).with_project_items(&[project_item])
+            })
+        };
+        let existing = new_item("one.txt", cx);
+        let missing = new_item("missing.txt", cx);
+        workspace.update(cx, |workspace, cx| {
+            workspace.add_item(Box::new(existing.clone()), cx);
+            workspace.add_item(Box::new(missing.clone()), cx);
+        });
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.move_pane_to_new_window(pane.clone(), cx)
+            })
+            .await
+            .unwrap();
+
+        // The item that couldn't be reopened stays in its pane.
+        pane.update(cx, |pane, _| {
+            assert_eq!(pane.items_len(), 1);
+            assert!(pane.index_for_item(&missing).is_some());
+        });
+
+        let this_window = cx.handle();
+        let new_window = cx
+            .windows()
+            .into_iter()
+            .find(|window| *window != this_window)
+            .and_then(|window| window.downcast::<Workspace>())
+            .unwrap();
+        let moved_paths = new_window
+            .update(cx, |workspace, cx| {
+                workspace
+                    .active_pane()
+                    .read(cx)
+                    .items()
+                    .filter_map(|item| item.project_path(cx))
+                    .map(|project_path| project_path.path)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        assert_eq!(moved_paths, [Arc::from(Path::new("one.txt"))]);
+    }
+
+    #[gpui::test]
+    async fn test_move_pane_with_untitled_item_to_new_window(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(register_project_item::<TestItem>);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/move-pane", json!({ "one.txt": "" })).await;
+        let project = Project::test(fs, ["/move-pane".as_ref()], cx).await;
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees().next().unwrap().read(cx).id()
+        });
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+
+        let titled = cx.new_view(|cx| {
+            let project_item = cx.new_model(|_| TestProjectItem {
+                entry_id: None,
+                project_path: Some(ProjectPath {
+                    worktree_id,
+                    path: Path::new("one.txt").into(),
+                }),
+            });
+            TestItem::new(cx).with_project_items(&[project_item])
+        });
+        let untitled = cx.new_view(|cx| TestItem::new(cx).with_dirty(true));
+        let pane = workspace.update(cx, |workspace, cx| {
+            let pane =
+                workspace.split_pane(workspace.active_pane().clone(), SplitDirection::Right, cx);
+            pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(titled.clone()), true, true, None, cx);
+                pane.add_item(Box::new(untitled.clone()), true, true, None, cx);
+            });
+            pane
+        });
+
+        // The untitled item can't be reopened, so nothing is moved.
+        let error = workspace
+            .update(cx, |workspace, cx| {
+                workspace.move_pane_to_new_window(pane.clone(), cx)
+            })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("untitled and modified items"));
+        assert_eq!(cx.windows().len(), 1);
+        pane.update(cx, |pane, _| assert_eq!(pane.items_len(), 2));
+
+        // Once it's gone, the pane is moved and removed from this window.
+        pane.update(cx, |pane, cx| {
+            let ix = pane.index_for_item(&untitled).unwrap();
+            pane.remove_item(ix, false, cx);
+        });
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.move_pane_to_new_window(pane.clone(), cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(cx.windows().len(), 2);
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.panes().len(), 1);
+            assert!(!workspace.panes().contains(&pane));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_restoring_pane_items_as_they_load(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            // Restores a `TestItem` labeled with the part of the state after the colon,
+            // once the number of milliseconds before it has passed.
+            cx.default_global::<SerializableItemRegistry>()
+                .deserializers_by_kind
+                .insert("SlowTestItem".into(), |_, _, workspace_id, _, state, cx| {
+                    let state = state.unwrap_or_default();
+                    let (delay, label) = state.split_once(':').unwrap();
+                    let label = label.to_string();
+                    let timer = cx
+                        .background_executor()
+                        .timer(Duration::from_millis(delay.parse().unwrap()));
+                    cx.spawn(|_, mut cx| async move {
+                        timer.await;
+                        let item = cx.new_view(|cx| {
+                            TestItem::new_deserialized(workspace_id, cx).with_label(&label)
+                        })?;
+                        Ok(Box::new(item) as Box<dyn ItemHandle>)
+                    })
+                });
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let serialized_pane = SerializedPane::new(
+            vec![
+                SerializedItem::new("SlowTestItem", 1, false).with_state("0:one"),
+                SerializedItem::new("SlowTestItem", 2, false).with_state("100:two"),
+                SerializedItem::new("UnknownItem", 3, false),
+                SerializedItem::new("SlowTestItem", 4, true).with_state("0:four"),
+                SerializedItem::new("SlowTestItem", 5, false).with_state("50:five"),
+            ],
+            true,
+        );
+        let restored_items = workspace.update(cx, |workspace, cx| {
+            let project = project.clone();
+            let pane = pane.downgrade();
+            let workspace_id = workspace.database_id();
+            cx.spawn(|workspace, mut cx| async move {
+                serialized_pane
+                    .deserialize_to(&project, &pane, workspace_id, workspace, &mut cx)
+                    .await
+            })
+        });
+        let labels = |cx: &mut VisualTestContext| {
+            pane.update(cx, |pane, cx| {
+                pane.items()
+                    .map(|item| item.downcast::<TestItem>().unwrap().read(cx).label.clone())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Items show up as soon as they and the ones before them have loaded.
+        cx.run_until_parked();
+        assert_eq!(labels(cx), ["one"]);
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+
+        // The item that failed to load is skipped without shifting the active item.
+        assert_eq!(labels(cx), ["one", "two", "four", "five"]);
+        pane.update(cx, |pane, _| assert_eq!(pane.active_item_index(), 2));
+        let restored_items = restored_items.await.unwrap();
+        assert_eq!(
+            restored_items
+                .iter()
+                .map(|item| item.is_some())
+                .collect::<Vec<_>>(),
+            [true, true, false, true, true]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_open_dropped_paths(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(register_project_item::<TestItem>);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({ "a.txt": "" })).await;
+        fs.insert_tree("/other", json!({ "b.txt": "" })).await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let worktree_paths = |cx: &mut VisualTestContext| {
+            project.update(cx, |project, cx| {
+                project
+                    .visible_worktrees(cx)
+                    .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Dropped files are opened without asking.
+        let opened = workspace.update(cx, |workspace, cx| {
+            workspace.open_dropped_paths(vec!["/root/a.txt".into()], None, cx)
+        });
+        cx.run_until_parked();
+        assert!(!cx.has_pending_prompt());
+        assert_eq!(opened.await.len(), 1);
+
+        // Dropped folders are only added to the project once the user agrees.
+        let opened = workspace.update(cx, |workspace, cx| {
+            workspace.open_dropped_paths(vec!["/other".into()], None, cx)
+        });
+        cx.run_until_parked();
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer(2);
+        assert!(opened.await.is_empty());
+        assert_eq!(worktree_paths(cx), [PathBuf::from("/root")]);
+
+        let opened = workspace.update(cx, |workspace, cx| {
+            workspace.open_dropped_paths(vec!["/other".into()], None, cx)
+        });
+        cx.run_until_parked();
+        cx.simulate_prompt_answer(0);
+        assert_eq!(opened.await.len(), 1);
+        assert_eq!(
+            worktree_paths(cx),
+            [PathBuf::from("/root"), PathBuf::from("/other")]
+        );
+        assert_eq!(cx.windows().len(), 1);
+    }
+
+    impl ProjectItem for TestItem {
+        type Item = language::Buffer;
+
+        fn for_project_item(
+            _: Model<Project>,
+            buffer: Model<Self::Item>,
+            cx: &mut ViewContext<Self>,
+        ) -> Self {
+            use project::Item as _;
+
+            let project_item = cx.new_model(|cx| TestProjectItem {
+                entry_id: buffer.read(cx).entry_id(cx),
+                project_path: buffer.read(cx).project_path(cx),
+            });
+            TestItem::new(cx).with_project_items(&[project_item])
+        }
+    }
+
+    #[gpui::test]
+    async fn test_save_all_reports_failures(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let item1 = cx.new_view(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_project_items(&[TestProjectItem::new(1, "1.txt", cx)])
+        });
+        let item2 = cx.new_view(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_save_error("disk full")
+                .with_project_items(&[TestProjectItem::new(2, "2.txt", cx)])
+        });
+        let item3 = cx.new_view(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_project_items(&[TestProjectItem::new(3, "3.txt", cx)])
+        });
+        workspace.update(cx, |w, cx| {
+            w.add_item(Box::new(item1.clone()), cx);
+            w.add_item(Box::new(item2.clone()), cx);
+            w.add_item(Box::new(item3.clone()), cx);
+        });
+
+        // A failing item doesn't prevent the remaining ones from being saved, and
+        // is reported once every item has been attempted.
+        let task = workspace.update(cx, |w, cx| w.save_all_internal(SaveIntent::SaveAll, cx));
+        cx.executor().run_until_parked();
+        assert!(!task.await.unwrap());
+        item1.read_with(cx, |item, _| assert_eq!(item.save_count, 1));
+        item2.read_with(cx, |item, _| assert!(item.is_dirty));
+        item3.read_with(cx, |item, _| assert_eq!(item.save_count, 1));
+        let failure_id = notification_id::<SaveAllFailureNotification>();
+        workspace.read_with(cx, |w, _| {
+            assert!(w.is_showing_notification::<MessageNotification>(failure_id));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_toggle_read_only(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let item1 = cx.new_view(|cx| TestItem::new(cx));
+        workspace.update(cx, |w, cx| w.add_item(Box::new(item1.clone()), cx));
+        cx.dispatch_action(ToggleReadOnly);
+        assert!(workspace.read_with(cx, |w, _| w.is_read_only()));
+        item1.read_with(cx, |item, _| assert!(item.read_only));
+
+        // Items added while the workspace is read-only are read-only too.
+        let item2 = cx.new_view(|cx| TestItem::new(cx));
+        workspace.update(cx, |w, cx| w.add_item(Box::new(item2.clone()), cx));
+        item2.read_with(cx, |item, _| assert!(item.read_only));
+
+        cx.dispatch_action(ToggleReadOnly);
+        assert!(!workspace.read_with(cx, |w, _| w.is_read_only()));
+        item1.read_with(cx, |item, _| assert!(!item.read_only));
+        item2.read_with(cx, |item, _| assert!(!item.read_only));
+    }
+
+    #[gpui::test]
+    async fn test_close_pane_items(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let item1 = cx.new_view(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_project_items(&[TestProjectItem::new(1, "1.txt", cx)])
+        });
+        let item2 = cx.new_view(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_conflict(true)
+                .with_project_items(&[TestProjectItem::new(2, "2.txt", cx)])
+        });
+        let item3 = cx.new_view(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
                 .with_conflict(true)
                 .with_project_items(&[TestProjectItem::new(3, "3.txt", cx)])
         });
@@ -4632,20 +6654,18 @@ mod tests {
         });
         cx.executor().run_until_parked();
 
-        assert!(cx.has_pending_prompt());
-        // Ignore "Save all" prompt
-        cx.simulate_prompt_answer(2);
-        cx.executor().run_until_parked();
-        // There's a prompt to save item 1.
-        pane.update(cx, |pane, _| {
-            assert_eq!(pane.items_len(), 4);
-            assert_eq!(pane.active_item().unwrap().item_id(), item1.item_id());
-        });
-        // Confirm saving item 1.
-        cx.simulate_prompt_answer(0);
+        // The dirty items are listed in a single prompt instead of one prompt each.
+        assert!(!cx.has_pending_prompt());
+        assert!(workspace.update(cx, |w, cx| w
+            .active_modal::<UnsavedChangesModal>(cx)
+            .is_some()));
+        pane.update(cx, |pane, _| assert_eq!(pane.items_len(), 4));
+
+        // Confirm saving all of them.
+        cx.dispatch_action(menu::Confirm);
         cx.executor().run_until_parked();
 
-        // Item 1 is saved. There's a prompt to save item 3.
+        // Item 1 is saved. There's a prompt about the conflict in item 3.
         pane.update(cx, |pane, cx| {
             assert_eq!(item1.read(cx).save_count, 1);
             assert_eq!(item1.read(cx).save_as_count, 0);
@@ -4655,23 +6675,17 @@ mod tests {
         });
         assert!(cx.has_pending_prompt());
 
-        // Cancel saving item 3.
+        // Discard the changes to item 3.
         cx.simulate_prompt_answer(1);
         cx.executor().run_until_parked();
 
-        // Item 3 is reloaded. There's a prompt to save item 4.
+        // Item 3 is reloaded.
         pane.update(cx, |pane, cx| {
             assert_eq!(item3.read(cx).save_count, 0);
             assert_eq!(item3.read(cx).save_as_count, 0);
             assert_eq!(item3.read(cx).reload_count, 1);
             assert_eq!(pane.items_len(), 2);
-            assert_eq!(pane.active_item().unwrap().item_id(), item4.item_id());
         });
-        assert!(cx.has_pending_prompt());
-
-        // Confirm saving item 4.
-        cx.simulate_prompt_answer(0);
-        cx.executor().run_until_parked();
 
         // There's a prompt for a path for item 4.
         cx.simulate_new_path_selection(|_| Some(Default::default()));
@@ -4739,8 +6753,8 @@ mod tests {
         let left_pane = workspace.update(cx, |workspace, cx| {
             let left_pane = workspace.active_pane().clone();
             workspace.add_item(Box::new(item_2_3.clone()), cx);
-            for item in single_entry_items {
-                workspace.add_item(Box::new(item), cx);
+            for item in &single_entry_items {
+                workspace.add_item(Box::new(item.clone()), cx);
             }
             left_pane.update(cx, |pane, cx| {
                 pane.activate_item(2, true, true, cx);
@@ -4759,41 +6773,29 @@ mod tests {
 
         cx.focus_view(&left_pane);
 
-        // When closing all of the items in the left pane, we should be prompted twice:
-        // once for project entry 0, and once for project entry 2. Project entries 1,
-        // 3, and 4 are all still open in the other paten. After those two
-        // prompts, the task should complete.
-
+        // When closing all of the items in the left pane, we should be asked about
+        // project entries 0 and 2 only. Project entries 1, 3, and 4 are all still
+        // open in the other pane.
         let close = left_pane.update(cx, |pane, cx| {
             pane.close_all_items(&CloseAllItems::default(), cx).unwrap()
         });
         cx.executor().run_until_parked();
+        assert!(workspace.update(cx, |w, cx| w
+            .active_modal::<UnsavedChangesModal>(cx)
+            .is_some()));
 
-        // Discard "Save all" prompt
-        cx.simulate_prompt_answer(2);
-
-        cx.executor().run_until_parked();
-        left_pane.update(cx, |pane, cx| {
-            assert_eq!(
-                pane.active_item().unwrap().project_entry_ids(cx).as_slice(),
-                &[ProjectEntryId::from_proto(0)]
-            );
-        });
-        cx.simulate_prompt_answer(0);
-
-        cx.executor().run_until_parked();
-        left_pane.update(cx, |pane, cx| {
-            assert_eq!(
-                pane.active_item().unwrap().project_entry_ids(cx).as_slice(),
-                &[ProjectEntryId::from_proto(2)]
-            );
-        });
-        cx.simulate_prompt_answer(0);
-
+        cx.dispatch_action(menu::Confirm);
         cx.executor().run_until_parked();
+        assert!(!cx.has_pending_prompt());
         close.await.unwrap();
-        left_pane.update(cx, |pane, _| {
+        left_pane.update(cx, |pane, cx| {
             assert_eq!(pane.items_len(), 0);
+            let save_counts = single_entry_items
+                .iter()
+                .map(|item| item.read(cx).save_count)
+                .collect::<Vec<_>>();
+            assert_eq!(save_counts, [1, 0, 1, 0, 0]);
+            assert_eq!(item_2_3.read(cx).save_count, 0);
         });
     }
 
@@ -4969,15 +6971,117 @@ mod tests {
     }
 
     #[gpui::test]
-    async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
+    async fn test_jump_list_across_panes(cx: &mut gpui::TestAppContext) {
         init_test(cx);
+
         let fs = FakeFs::new(cx.executor());
 
         let project = Project::test(fs, [], cx).await;
         let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
 
-        let panel = workspace.update(cx, |workspace, cx| {
-            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Right, cx));
+        let item_1 = cx.new_view(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "1.txt", cx)])
+        });
+        let item_2 = cx.new_view(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(2, "2.txt", cx)])
+        });
+        let (left_pane, right_pane) = workspace.update(cx, |workspace, cx| {
+            let left_pane = workspace.active_pane().clone();
+            workspace.add_item(Box::new(item_1.clone()), cx);
+            let right_pane = workspace.split_pane(left_pane.clone(), SplitDirection::Right, cx);
+            right_pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item_2.clone()), true, true, None, cx)
+            });
+            (left_pane, right_pane)
+        });
+
+        item_1.update(cx, |item, cx| item.set_state("one".to_string(), cx));
+        item_2.update(cx, |item, cx| item.set_state("two".to_string(), cx));
+
+        // Jumping back visits the most recent locations first, whichever pane they're in.
+        workspace
+            .update(cx, |workspace, cx| workspace.jump_back(cx))
+            .await
+            .unwrap();
+        item_2.update(cx, |item, _| assert_eq!(item.state, ""));
+        item_1.update(cx, |item, _| assert_eq!(item.state, "one"));
+
+        workspace
+            .update(cx, |workspace, cx| workspace.jump_back(cx))
+            .await
+            .unwrap();
+        item_1.update(cx, |item, _| assert_eq!(item.state, ""));
+        left_pane.update(cx, |pane, cx| assert!(pane.has_focus(cx)));
+
+        // Jumping forward retraces those steps.
+        workspace
+            .update(cx, |workspace, cx| workspace.jump_forward(cx))
+            .await
+            .unwrap();
+        item_1.update(cx, |item, _| assert_eq!(item.state, "one"));
+        item_2.update(cx, |item, _| assert_eq!(item.state, ""));
+
+        workspace
+            .update(cx, |workspace, cx| workspace.jump_forward(cx))
+            .await
+            .unwrap();
+        item_2.update(cx, |item, _| assert_eq!(item.state, "two"));
+        right_pane.update(cx, |pane, cx| assert!(pane.has_focus(cx)));
+    }
+
+    #[gpui::test]
+    async fn test_pushing_nav_history_for_item(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let item = cx.new_view(|cx| TestItem::new(cx));
+        let pane = workspace.update(cx, |workspace, cx| {
+            workspace.add_item(Box::new(item.clone()), cx);
+            workspace.active_pane().clone()
+        });
+
+        // Items can record positions without having been handed a history.
+        let mut nav_history = workspace
+            .update(cx, |workspace, cx| {
+                workspace.nav_history_for_item(&item, cx)
+            })
+            .unwrap();
+        cx.update(|cx| nav_history.push(Some("one".to_string()), cx));
+        cx.run_until_parked();
+        pane.update(cx, |pane, _| {
+            assert!(pane.can_navigate_backward());
+            assert!(!pane.can_navigate_forward());
+        });
+
+        workspace
+            .update(cx, |workspace, cx| workspace.go_back(pane.downgrade(), cx))
+            .await
+            .unwrap();
+        item.update(cx, |item, _| assert_eq!(item.state, "one"));
+        pane.update(cx, |pane, _| assert!(!pane.can_navigate_backward()));
+
+        // Items that aren't in any pane don't have a history.
+        let detached_item = cx.new_view(|cx| TestItem::new(cx));
+        assert!(workspace
+            .update(cx, |workspace, cx| workspace
+                .nav_history_for_item(&detached_item, cx))
+            .is_none());
+    }
+
+    #[gpui::test]
+    async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Right, cx));
             workspace.add_panel(panel.clone(), cx);
 
             workspace
@@ -5139,6 +7243,31 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_registered_item_converters(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (_workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let item: Box<dyn ItemHandle> = Box::new(cx.new_view(|cx| TestItem::new(cx)));
+        let modal = cx.new_view(TestModal::new);
+        cx.update(|cx| {
+            assert!(item.act_as::<TestItem>(cx).is_some());
+            assert!(item.act_as::<TestModal>(cx).is_none());
+
+            register_item_converter::<TestItem, TestModal>(cx, {
+                let modal = modal.clone();
+                move |_, _| Some(modal.clone())
+            });
+            assert_eq!(
+                item.act_as::<TestModal>(cx).map(|view| view.entity_id()),
+                Some(modal.entity_id())
+            );
+            assert!(item.act_as::<TestItem>(cx).is_some());
+        });
+    }
+
     #[gpui::test]
     async fn test_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -5368,6 +7497,822 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_pop_out_and_dock_back_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({})).await;
+
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let (panel_1, panel_2) = workspace.update(cx, |workspace, cx| {
+            let panel_1 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_2 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), cx);
+            workspace.add_panel(panel_2.clone(), cx);
+            (panel_1, panel_2)
+        });
+        let left_dock = workspace.update(cx, |workspace, _| workspace.left_dock().clone());
+
+        // Pop out both panels, the first one first.
+        left_dock.update(cx, |dock, cx| {
+            dock.pop_out_panel(0, cx);
+            dock.pop_out_panel(0, cx);
+            assert_eq!(dock.panels_len(), 0);
+        });
+
+        // Popped out panels are still found, focused and serialized.
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.panel::<TestPanel>(cx), Some(panel_1.clone()));
+            assert_eq!(
+                workspace.focus_panel::<TestPanel>(cx),
+                Some(panel_1.clone())
+            );
+            let docks = workspace.serialized_workspace(cx).unwrap().docks;
+            assert_eq!(docks.left.popped_out_panels, ["TestPanel", "TestPanel"]);
+        });
+
+        // Docking them back in either order restores their original order.
+        left_dock.update(cx, |dock, cx| {
+            dock.dock_popped_out_panel(panel_2.entity_id(), cx);
+            dock.dock_popped_out_panel(panel_1.entity_id(), cx);
+            assert_eq!(dock.panels_len(), 2);
+            assert!(dock.popped_out_panel_names().is_empty());
+            assert_eq!(dock.active_panel_index(), 0);
+            assert_eq!(dock.active_panel().unwrap().panel_id(), panel_1.entity_id());
+            dock.activate_panel(1, cx);
+            assert_eq!(dock.active_panel().unwrap().panel_id(), panel_2.entity_id());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_registered_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            for (order, size) in [(1, px(200.)), (0, px(100.))] {
+                panels::register_panel(
+                    panels::PanelPlacement { order },
+                    move |_, mut cx| {
+                        Task::ready(cx.new_view(|cx| {
+                            let mut panel = TestPanel::new(DockPosition::Right, cx);
+                            panel.size = size;
+                            panel
+                        }))
+                    },
+                    cx,
+                );
+            }
+            panels::register_panel::<TestPanel>(
+                panels::PanelPlacement::default(),
+                |_, _| Task::ready(Err(anyhow!("failed to load"))),
+                cx,
+            );
+            // Status items are built once the panels they belong to have been added.
+            panels::register_status_item(
+                StatusItemSide::Right,
+                0,
+                |workspace, cx| {
+                    workspace
+                        .panel::<TestPanel>(cx)
+                        .map(|_| cx.new_view(SettingsProfileIndicator::new))
+                },
+                cx,
+            );
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let load_panels = workspace.update(cx, |_, cx| {
+            panels::load_registered_panels(cx.view().downgrade(), cx.to_async())
+        });
+        load_panels.await.unwrap();
+
+        workspace.update(cx, |workspace, cx| {
+            let sizes = workspace
+                .right_dock()
+                .read(cx)
+                .panels()
+                .map(|panel| panel.size(cx))
+                .collect::<Vec<_>>();
+            assert_eq!(sizes, [px(100.), px(200.)]);
+            assert!(workspace
+                .status_bar()
+                .read(cx)
+                .item_of_type::<SettingsProfileIndicator>()
+                .is_some());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_panel_sizes_are_kept_per_panel(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        workspace.update(cx, |workspace, cx| {
+            let panel_1 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), cx);
+            let panel_2 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_2.clone(), cx);
+
+            let left_dock = workspace.left_dock().clone();
+            left_dock.update(cx, |dock, cx| {
+                dock.set_open(true, cx);
+                dock.activate_panel(0, cx);
+                dock.resize_active_panel(Some(px(200.)), cx);
+                dock.activate_panel(1, cx);
+                dock.resize_active_panel(Some(px(450.)), cx);
+            });
+            assert_eq!(panel_1.read(cx).size, px(200.));
+            assert_eq!(panel_2.read(cx).size, px(450.));
+
+            left_dock.update(cx, |dock, cx| dock.activate_panel(0, cx));
+            assert_eq!(left_dock.read(cx).active_panel_size(cx), Some(px(200.)));
+            left_dock.update(cx, |dock, cx| dock.activate_panel(1, cx));
+            assert_eq!(left_dock.read(cx).active_panel_size(cx), Some(px(450.)));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_overlay_side_docks(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), cx);
+            workspace.toggle_dock(DockPosition::Left, cx);
+            panel
+        });
+
+        // Without the setting, focusing the center leaves the dock open.
+        workspace.update(cx, |workspace, cx| {
+            assert!(panel.read(cx).focus_handle(cx).contains_focused(cx));
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.left_dock().read(cx).is_open());
+        });
+
+        cx.update_global(|settings: &mut SettingsStore, cx| {
+            settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.overlay_side_docks = Some(true);
+            })
+        });
+
+        // With the setting, the overlaid dock is hidden once a pane is focused again.
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(panel.read(cx).focus_handle(cx).contains_focused(cx));
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.left_dock().read(cx).is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_items_preventing_dock_auto_hide(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+
+        cx.update_global(|settings: &mut SettingsStore, cx| {
+            settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.overlay_side_docks = Some(true);
+            })
+        });
+
+        let item = cx.new_view(|cx| TestItem::new(cx));
+        workspace.update(cx, |workspace, cx| {
+            let pane = cx.new_view(|cx| {
+                Pane::new(
+                    workspace.weak_handle(),
+                    project.clone(),
+                    Default::default(),
+                    None,
+                    cx,
+                )
+            });
+            pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item.clone()), true, true, None, cx)
+            });
+            item.added_to_pane(workspace, pane.clone(), cx);
+
+            let panel = cx.new_view(|cx| {
+                let mut panel = TestPanel::new(DockPosition::Left, cx);
+                panel.panes.push(pane);
+                panel
+            });
+            workspace.add_panel(panel, cx);
+            workspace.toggle_dock(DockPosition::Left, cx);
+        });
+
+        // While the item prevents it, focusing the center leaves the dock open.
+        item.update(cx, |item, _| item.prevent_auto_hide = true);
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.left_dock().read(cx).is_open());
+        });
+
+        // The dock is hidden once the item allows it.
+        item.update(cx, |item, cx| {
+            item.prevent_auto_hide = false;
+            cx.emit(ItemEvent::UpdateTab);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.left_dock().read(cx).is_open());
+        });
+
+        // Unless the dock was focused again in the meantime.
+        item.update(cx, |item, _| item.prevent_auto_hide = true);
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        item.update(cx, |item, cx| {
+            item.prevent_auto_hide = false;
+            cx.emit(ItemEvent::UpdateTab);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.left_dock().read(cx).is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_items_told_when_shown_or_hidden(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let center_pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let item_1 = cx.new_view(|cx| TestItem::new(cx));
+        let item_2 = cx.new_view(|cx| TestItem::new(cx));
+        let dock_item = cx.new_view(|cx| TestItem::new(cx));
+        workspace.update(cx, |workspace, cx| {
+            workspace.add_item(Box::new(item_1.clone()), cx);
+            workspace.add_item(Box::new(item_2.clone()), cx);
+
+            let dock_pane = cx.new_view(|cx| {
+                Pane::new(
+                    workspace.weak_handle(),
+                    project.clone(),
+                    Default::default(),
+                    None,
+                    cx,
+                )
+            });
+            dock_pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(dock_item.clone()), true, true, None, cx)
+            });
+            let panel = cx.new_view(|cx| {
+                let mut panel = TestPanel::new(DockPosition::Left, cx);
+                panel.panes.push(dock_pane);
+                panel
+            });
+            workspace.add_panel(panel, cx);
+            workspace.toggle_dock(DockPosition::Left, cx);
+        });
+
+        let take_changes = |item: &View<TestItem>, cx: &mut VisualTestContext| {
+            item.update(cx, |item, _| mem::take(&mut item.visibility_changes))
+        };
+        take_changes(&item_1, cx);
+        take_changes(&item_2, cx);
+        take_changes(&dock_item, cx);
+
+        // Switching tabs hides the previously active item and shows the new one.
+        center_pane.update(cx, |pane, cx| pane.activate_item(0, true, true, cx));
+        assert_eq!(take_changes(&item_1, cx), [true]);
+        assert_eq!(take_changes(&item_2, cx), [false]);
+
+        // Closing a dock hides the items of its panels, and opening it shows them again.
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_dock(DockPosition::Left, cx)
+        });
+        assert_eq!(take_changes(&dock_item, cx), [false]);
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_dock(DockPosition::Left, cx)
+        });
+        assert_eq!(take_changes(&dock_item, cx), [true]);
+
+        // Items in the center aren't affected by docks.
+        assert!(take_changes(&item_1, cx).is_empty());
+        assert!(take_changes(&item_2, cx).is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_docks_redrawn_when_settings_change(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let notify_count = Rc::new(RefCell::new(0));
+        let _subscription = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel, cx);
+            workspace.toggle_dock(DockPosition::Left, cx);
+
+            let notify_count = notify_count.clone();
+            cx.observe(workspace.left_dock(), move |_, _, _| {
+                *notify_count.borrow_mut() += 1;
+            })
+        });
+
+        // Panels fall back to sizes from the settings, so their dock is redrawn
+        // even when the panel stays where it is.
+        cx.update_global(|settings: &mut SettingsStore, cx| {
+            settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.active_pane_magnification = Some(1.5);
+            })
+        });
+        assert!(*notify_count.borrow() > 0);
+    }
+
+    #[gpui::test]
+    async fn test_panel_button_changes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let (panel_1, panel_2, _subscription) = workspace.update(cx, |workspace, cx| {
+            let panel_1 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_2 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), cx);
+            workspace.add_panel(panel_2.clone(), cx);
+
+            let events = events.clone();
+            let subscription =
+                cx.subscribe(workspace.left_dock(), move |_, _, event: &DockEvent, _| {
+                    events.borrow_mut().push(*event);
+                });
+            (panel_1, panel_2, subscription)
+        });
+
+        // Notifying without changing what the button shows doesn't affect the buttons.
+        panel_1.update(cx, |_, cx| cx.notify());
+        assert!(events.borrow().is_empty());
+
+        // Only the panel that notified is checked, and each change is reported once.
+        panel_2.update(cx, |panel, cx| {
+            panel.icon_label = Some("3".into());
+            cx.notify();
+        });
+        assert_eq!(events.take(), [DockEvent::PanelButtonChanged]);
+        panel_2.update(cx, |_, cx| cx.notify());
+        assert!(events.borrow().is_empty());
+
+        // Tooltips are tracked too.
+        panel_1.update(cx, |panel, cx| {
+            panel.icon_tooltip = Some("Test Panel");
+            cx.notify();
+        });
+        assert_eq!(events.take(), [DockEvent::PanelButtonChanged]);
+    }
+
+    #[gpui::test]
+    async fn test_local_settings_for_items(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (_workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let item = cx.new_view(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "a.txt", cx)])
+        });
+        let untitled_item = cx.new_view(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new_untitled(cx)])
+        });
+
+        cx.update(|cx| {
+            cx.update_global(|store: &mut SettingsStore, cx| {
+                store
+                    .set_local_settings(
+                        0,
+                        Path::new("").into(),
+                        Some(r#"{ "autosave": "on_focus_change" }"#),
+                        cx,
+                    )
+                    .unwrap();
+            });
+
+            assert_eq!(
+                settings_for_item::<WorkspaceSettings>(&item, cx).autosave,
+                AutosaveSetting::OnFocusChange
+            );
+            assert_eq!(
+                settings_for_item::<WorkspaceSettings>(&untitled_item, cx).autosave,
+                AutosaveSetting::Off
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_ui_zoom(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        cx.dispatch_action(ZoomIn);
+        cx.dispatch_action(ZoomIn);
+        cx.dispatch_action(ZoomOut);
+        workspace.update(cx, |workspace, _| assert_eq!(workspace.ui_zoom(), 1.1));
+
+        cx.dispatch_action(ResetZoom);
+        workspace.update(cx, |workspace, _| assert_eq!(workspace.ui_zoom(), 1.));
+
+        for _ in 0..10 {
+            cx.dispatch_action(ZoomOut);
+        }
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.ui_zoom(), MIN_UI_ZOOM)
+        });
+    }
+
+    #[gpui::test]
+    async fn test_open_settings_ui(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        cx.dispatch_action(OpenSettingsUI);
+        let settings_editor = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<SettingsEditor>(cx).unwrap()
+        });
+
+        let item = cx.new_view(|cx| TestItem::new(cx));
+        workspace.update(cx, |workspace, cx| {
+            workspace.add_item(Box::new(item), cx);
+        });
+
+        // Opening the settings UI again activates the existing editor.
+        cx.dispatch_action(OpenSettingsUI);
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<SettingsEditor>(cx) == Some(settings_editor));
+            assert_eq!(workspace.items_of_type::<SettingsEditor>(cx).count(), 1);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_toggle_all_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        workspace.update(cx, |workspace, cx| {
+            for position in [
+                DockPosition::Left,
+                DockPosition::Bottom,
+                DockPosition::Right,
+            ] {
+                let panel = cx.new_view(|cx| TestPanel::new(position, cx));
+                workspace.add_panel(panel, cx);
+            }
+            workspace.toggle_dock(DockPosition::Left, cx);
+            workspace.toggle_dock(DockPosition::Bottom, cx);
+        });
+
+        let open_docks = |workspace: &Workspace, cx: &WindowContext| {
+            [
+                workspace.left_dock().read(cx).is_open(),
+                workspace.bottom_dock().read(cx).is_open(),
+                workspace.right_dock().read(cx).is_open(),
+            ]
+        };
+
+        cx.dispatch_action(ToggleAllPanels);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(open_docks(workspace, cx), [false, false, false]);
+        });
+
+        // Only the docks that were open before are restored.
+        cx.dispatch_action(ToggleAllPanels);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(open_docks(workspace, cx), [true, true, false]);
+        });
+
+        cx.dispatch_action(ToggleAllPanels);
+        cx.dispatch_action(ToggleAllPanels);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(open_docks(workspace, cx), [true, true, false]);
+        });
+
+        // Docks opened or closed by hand since hiding them aren't brought back.
+        cx.dispatch_action(ToggleAllPanels);
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_dock(DockPosition::Right, cx);
+            workspace.toggle_dock(DockPosition::Right, cx);
+            assert_eq!(open_docks(workspace, cx), [false, false, false]);
+        });
+        cx.dispatch_action(ToggleAllPanels);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(open_docks(workspace, cx), [false, false, false]);
+        });
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_dock(DockPosition::Bottom, cx);
+        });
+        cx.dispatch_action(ToggleAllPanels);
+        workspace.update(cx, |workspace, cx| {
+            workspace.focus_panel::<TestPanel>(cx);
+            assert_eq!(open_docks(workspace, cx), [true, false, false]);
+            workspace.toggle_dock(DockPosition::Left, cx);
+        });
+        cx.dispatch_action(ToggleAllPanels);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(open_docks(workspace, cx), [false, false, false]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_focus_panel_cycling(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let (left_panel, right_panel, pane) = workspace.update(cx, |workspace, cx| {
+            let left_panel = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(left_panel.clone(), cx);
+            let right_panel = cx.new_view(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(right_panel.clone(), cx);
+            workspace.toggle_dock(DockPosition::Left, cx);
+            workspace.toggle_dock(DockPosition::Right, cx);
+
+            let pane = workspace.active_pane().clone();
+            cx.focus_view(&pane);
+            (left_panel, right_panel, pane)
+        });
+
+        cx.dispatch_action(FocusNextPanel);
+        right_panel.update(cx, |panel, cx| assert!(panel.focus_handle.is_focused(cx)));
+
+        // Wraps around to the left dock.
+        cx.dispatch_action(FocusNextPanel);
+        left_panel.update(cx, |panel, cx| assert!(panel.focus_handle.is_focused(cx)));
+
+        cx.dispatch_action(FocusNextPanel);
+        pane.update(cx, |pane, cx| {
+            assert!(pane.focus_handle(cx).contains_focused(cx))
+        });
+
+        cx.dispatch_action(FocusPreviousPanel);
+        left_panel.update(cx, |panel, cx| assert!(panel.focus_handle.is_focused(cx)));
+
+        cx.dispatch_action(FocusPreviousPanel);
+        right_panel.update(cx, |panel, cx| assert!(panel.focus_handle.is_focused(cx)));
+    }
+
+    #[gpui::test]
+    async fn test_focus_previous_pane(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let left_pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        cx.focus_view(&left_pane);
+        let right_pane = workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(left_pane.clone(), SplitDirection::Right, cx)
+        });
+        right_pane.update(cx, |pane, cx| assert!(pane.has_focus(cx)));
+
+        cx.dispatch_action(FocusPreviousPane);
+        left_pane.update(cx, |pane, cx| assert!(pane.has_focus(cx)));
+
+        // Going back again toggles between the two most recently focused panes.
+        cx.dispatch_action(FocusPreviousPane);
+        right_pane.update(cx, |pane, cx| assert!(pane.has_focus(cx)));
+    }
+
+    #[gpui::test]
+    async fn test_move_focused_panel_to_opposite_side(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), cx);
+            workspace.toggle_dock(DockPosition::Left, cx);
+            panel
+        });
+
+        cx.dispatch_action(MoveFocusedPanelToOppositeSide);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(panel.read(cx).position, DockPosition::Right);
+            assert!(workspace.left_dock().read(cx).visible_panel().is_none());
+            assert_eq!(
+                workspace
+                    .right_dock()
+                    .read(cx)
+                    .visible_panel()
+                    .unwrap()
+                    .panel_id(),
+                panel.panel_id()
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_split_dock(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let (panel_1, panel_2, panel_3) = workspace.update(cx, |workspace, cx| {
+            let panel_1 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), cx);
+            let panel_2 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_2.clone(), cx);
+            let panel_3 = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_3.clone(), cx);
+            workspace
+                .left_dock()
+                .update(cx, |dock, cx| dock.set_open(true, cx));
+            (panel_1, panel_2, panel_3)
+        });
+        let left_dock = workspace.update(cx, |workspace, _| workspace.left_dock().clone());
+
+        // Showing a panel below the active one makes both of them active.
+        left_dock.update(cx, |dock, cx| dock.split_with_panel(1, cx));
+        left_dock.update(cx, |dock, cx| {
+            assert_eq!(dock.active_panel_index(), 0);
+            assert_eq!(dock.split_panel_index(), Some(1));
+            assert!(panel_1.read(cx).active);
+            assert!(panel_2.read(cx).active);
+            assert!(!panel_3.read(cx).active);
+        });
+
+        // Activating the split panel swaps it with the active one.
+        left_dock.update(cx, |dock, cx| dock.activate_panel(1, cx));
+        left_dock.update(cx, |dock, _| {
+            assert_eq!(dock.active_panel_index(), 1);
+            assert_eq!(dock.split_panel_index(), Some(0));
+        });
+
+        // Closing the dock deactivates both panels.
+        left_dock.update(cx, |dock, cx| dock.set_open(false, cx));
+        left_dock.update(cx, |_, cx| {
+            assert!(!panel_1.read(cx).active);
+            assert!(!panel_2.read(cx).active);
+        });
+        left_dock.update(cx, |dock, cx| dock.set_open(true, cx));
+
+        // Removing the split panel closes the split.
+        left_dock.update(cx, |dock, cx| dock.remove_panel(&panel_1, cx));
+        left_dock.update(cx, |dock, cx| {
+            assert_eq!(dock.active_panel_index(), 0);
+            assert_eq!(dock.split_panel_index(), None);
+            assert!(panel_2.read(cx).active);
+        });
+
+        // Bottom docks can't be split.
+        let bottom_dock = workspace.update(cx, |workspace, cx| {
+            let panel_4 = cx.new_view(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(panel_4, cx);
+            let panel_5 = cx.new_view(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(panel_5, cx);
+            workspace.bottom_dock().clone()
+        });
+        bottom_dock.update(cx, |dock, cx| {
+            dock.split_with_panel(1, cx);
+            assert_eq!(dock.split_panel_index(), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_run_action_sequence(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        workspace.update(cx, |workspace, cx| {
+            let left_panel = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(left_panel, cx);
+            let right_panel = cx.new_view(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(right_panel, cx);
+        });
+
+        cx.dispatch_action(RunActionSequence(vec![
+            json!("workspace::ToggleLeftDock"),
+            json!("workspace::ToggleRightDock"),
+        ]));
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.left_dock().read(cx).is_open());
+            assert!(workspace.right_dock().read(cx).is_open());
+        });
+
+        // None of the actions run if any of them is invalid.
+        cx.dispatch_action(RunActionSequence(vec![
+            json!("workspace::ToggleLeftDock"),
+            json!("workspace::NoSuchAction"),
+        ]));
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.left_dock().read(cx).is_open());
+            assert!(workspace.right_dock().read(cx).is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_mouse_bindings(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        cx.update(|cx| {
+            let mut bindings = WorkspaceSettings::get_global(cx).mouse_bindings.clone();
+            bindings
+                .workspace
+                .insert("shift-middle".into(), json!(["pane::ActivateItem", 1]));
+            bindings
+                .workspace
+                .insert("forward".into(), serde_json::Value::Null);
+            let action_name = |button, modifiers: Modifiers| {
+                MouseBindings::action_for(&bindings.workspace, button, &modifiers, cx)
+                    .map(|action| action.name().to_string())
+            };
+
+            assert_eq!(
+                action_name(
+                    MouseButton::Navigate(NavigationDirection::Back),
+                    Modifiers::default()
+                )
+                .as_deref(),
+                Some("pane::GoBack")
+            );
+            assert_eq!(
+                action_name(
+                    MouseButton::Navigate(NavigationDirection::Forward),
+                    Modifiers::default()
+                ),
+                None
+            );
+            assert_eq!(
+                action_name(
+                    MouseButton::Middle,
+                    Modifiers {
+                        shift: true,
+                        ..Default::default()
+                    }
+                )
+                .as_deref(),
+                Some("pane::ActivateItem")
+            );
+            assert_eq!(action_name(MouseButton::Middle, Modifiers::default()), None);
+
+            let cmd = Modifiers {
+                command: true,
+                ..Default::default()
+            };
+            assert_eq!(
+                MouseBindings::action_for(&bindings.tab, MouseButton::Left, &cmd, cx)
+                    .map(|action| action.name().to_string())
+                    .as_deref(),
+                Some("pane::SplitRight")
+            );
+        });
+    }
+
     pub fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
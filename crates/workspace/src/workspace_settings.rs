@@ -1,13 +1,23 @@
+use anyhow::Context as _;
+use collections::HashMap;
+use gpui::{Action, AppContext, Modifiers, MouseButton, NavigationDirection, SwipeDirection};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
+use util::ResultExt;
+
+use crate::build_action_from_json;
 
 #[derive(Deserialize)]
 pub struct WorkspaceSettings {
     pub active_pane_magnification: f32,
+    pub active_pane_indicator: ActivePaneIndicator,
+    pub overlay_side_docks: bool,
     pub confirm_quit: bool,
     pub show_call_status_icon: bool,
+    pub share_dock_items: bool,
     pub autosave: AutosaveSetting,
+    pub mouse_bindings: MouseBindings,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +28,13 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: `1.0`
     pub active_pane_magnification: Option<f32>,
+    /// How the focused pane, including panes in docks, stands out from the others.
+    pub active_pane_indicator: Option<ActivePaneIndicator>,
+    /// Whether the left and right docks should float above the panes instead of
+    /// taking up space next to them. Overlaid docks close when a pane is focused.
+    ///
+    /// Default: false
+    pub overlay_side_docks: Option<bool>,
     /// Whether or not to prompt the user to confirm before closing the application.
     ///
     /// Default: false
@@ -26,10 +43,45 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: true
     pub show_call_status_icon: Option<bool>,
+    /// Whether collaborators following you see the items in your docks, such as
+    /// terminals, as read-only copies. Terminals in the center are only shared
+    /// when this is enabled too.
+    ///
+    /// Default: false
+    pub share_dock_items: Option<bool>,
     /// When to automatically save edited buffers.
     ///
     /// Default: off
     pub autosave: Option<AutosaveSetting>,
+    /// Actions run by mouse buttons and modifier-clicks.
+    pub mouse_bindings: Option<MouseBindings>,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ActivePaneIndicator {
+    /// How to mark the focused pane.
+    ///
+    /// Default: none
+    #[serde(default)]
+    pub style: ActivePaneIndicatorStyle,
+    /// How strongly the focused pane is marked, from 0.0 to 1.0. This is the
+    /// opacity of the border, or how far the other panes are faded out.
+    ///
+    /// Default: 0.5
+    #[serde(default)]
+    pub intensity: f32,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivePaneIndicatorStyle {
+    /// Don't mark the focused pane.
+    #[default]
+    None,
+    /// Draw a border around the focused pane, in the theme's focused pane border color.
+    Border,
+    /// Fade out the panes that aren't focused.
+    Dim,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -45,6 +97,87 @@ pub enum AutosaveSetting {
     OnWindowChange,
 }
 
+/// Actions run by mouse buttons, keyed by the button with optional modifiers, such as
+/// `"back"` or `"cmd-left"`. The buttons are `left`, `right`, `middle`, `back` and
+/// `forward`, and the modifiers `ctrl`, `alt`, `shift` and `cmd`. Actions are given
+/// by name or as a `[name, data]` pair, as in the keymap, and `null` removes a binding.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MouseBindings {
+    /// Bindings for clicks anywhere in the workspace that weren't handled by the
+    /// element under the pointer.
+    #[serde(default)]
+    pub workspace: HashMap<String, serde_json::Value>,
+    /// Bindings for clicks on a tab, which is activated before the action runs.
+    #[serde(default)]
+    pub tab: HashMap<String, serde_json::Value>,
+    /// Bindings for trackpad swipes anywhere in the workspace but the tab bar, keyed
+    /// by the direction of the swipe: `left`, `right`, `up` or `down`.
+    #[serde(default)]
+    pub swipe: HashMap<String, serde_json::Value>,
+}
+
+impl MouseBindings {
+    /// Builds the action bound to pressing `button` with `modifiers` held.
+    pub fn action_for(
+        bindings: &HashMap<String, serde_json::Value>,
+        button: MouseButton,
+        modifiers: &Modifiers,
+        cx: &AppContext,
+    ) -> Option<Box<dyn Action>> {
+        let modifiers = Modifiers {
+            function: false,
+            ..*modifiers
+        };
+        let (chord, action) = bindings.iter().find(|(chord, action)| {
+            !action.is_null() && parse_mouse_chord(chord) == Some((modifiers, button))
+        })?;
+        build_action_from_json(action, cx)
+            .with_context(|| format!("invalid mouse binding for {chord:?}"))
+            .log_err()
+    }
+
+    /// Builds the action bound to swiping in `direction`.
+    pub fn action_for_swipe(
+        &self,
+        direction: SwipeDirection,
+        cx: &AppContext,
+    ) -> Option<Box<dyn Action>> {
+        let key = match direction {
+            SwipeDirection::Left => "left",
+            SwipeDirection::Right => "right",
+            SwipeDirection::Up => "up",
+            SwipeDirection::Down => "down",
+        };
+        let action = self.swipe.get(key).filter(|action| !action.is_null())?;
+        build_action_from_json(action, cx)
+            .with_context(|| format!("invalid swipe binding for {key:?}"))
+            .log_err()
+    }
+}
+
+fn parse_mouse_chord(chord: &str) -> Option<(Modifiers, MouseButton)> {
+    let mut components = chord.split('-').collect::<Vec<_>>();
+    let button = match components.pop()? {
+        "left" => MouseButton::Left,
+        "right" => MouseButton::Right,
+        "middle" => MouseButton::Middle,
+        "back" => MouseButton::Navigate(NavigationDirection::Back),
+        "forward" => MouseButton::Navigate(NavigationDirection::Forward),
+        _ => return None,
+    };
+    let mut modifiers = Modifiers::default();
+    for component in components {
+        match component {
+            "ctrl" => modifiers.control = true,
+            "alt" => modifiers.alt = true,
+            "shift" => modifiers.shift = true,
+            "cmd" => modifiers.command = true,
+            _ => return None,
+        }
+    }
+    Some((modifiers, button))
+}
+
 impl Settings for WorkspaceSettings {
     const KEY: Option<&'static str> = None;
 
@@ -15,7 +15,9 @@ pub fn app_menus() -> Vec<Menu<'static>> {
                     name: "Preferences",
                     items: vec![
                         MenuItem::action("Open Settings", super::OpenSettings),
+                        MenuItem::action("Open Settings Editor", workspace::OpenSettingsUI),
                         MenuItem::action("Open Key Bindings", super::OpenKeymap),
+                        MenuItem::action("Open Key Bindings Editor", workspace::OpenKeymapEditor),
                         MenuItem::action("Open Default Settings", super::OpenDefaultSettings),
                         MenuItem::action("Open Default Key Bindings", super::OpenDefaultKeymap),
                         MenuItem::action("Open Local Settings", super::OpenLocalSettings),
@@ -35,6 +37,7 @@ pub fn app_menus() -> Vec<Menu<'static>> {
             items: vec![
                 MenuItem::action("New", workspace::NewFile),
                 MenuItem::action("New Window", workspace::NewWindow),
+                MenuItem::action("New Window for Project", workspace::NewWindowForProject),
                 MenuItem::separator(),
                 MenuItem::action("Open…", workspace::Open),
                 MenuItem::action("Open Recent...", recent_projects::OpenRecent),
@@ -106,6 +109,7 @@ pub fn app_menus() -> Vec<Menu<'static>> {
                 MenuItem::action("Toggle Right Dock", workspace::ToggleRightDock),
                 MenuItem::action("Toggle Bottom Dock", workspace::ToggleBottomDock),
                 MenuItem::action("Close All Docks", workspace::CloseAllDocks),
+                MenuItem::action("Toggle All Panels", workspace::ToggleAllPanels),
                 MenuItem::submenu(Menu {
                     name: "Editor Layout",
                     items: vec![
@@ -164,6 +164,9 @@ fn main() {
         language::init(cx);
         editor::init(cx);
         diagnostics::init(cx);
+        git_panel::init(cx);
+        todo_panel::init(cx);
+        bookmarks::init(cx);
         copilot::init(
             copilot_language_server_id,
             http.clone(),
@@ -243,9 +246,13 @@ fn main() {
         journal::init(app_state.clone(), cx);
         language_selector::init(cx);
         theme_selector::init(cx);
+        settings_profile_selector::init(cx);
         language_tools::init(cx);
         call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
         notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
+        notification_center::init(cx);
+        tasks::init(cx);
+        debugger::init(cx);
         collab_ui::init(&app_state, cx);
         feedback::init(cx);
         welcome::init(cx);
@@ -920,6 +927,8 @@ fn watch_themes(fs: Arc<dyn fs::Fs>, cx: &mut AppContext) {
                 if event.flags.contains(StreamFlags::ITEM_REMOVED) {
                     // Theme was removed, don't need to reload.
                     // We may want to remove the theme from the registry, in this case.
+                } else if event.path.extension() != Some("json".as_ref()) {
+                    // Editors may write backup or swap files next to the themes.
                 } else {
                     if let Some(theme_registry) =
                         cx.update(|cx| ThemeRegistry::global(cx).clone()).log_err()
@@ -9,8 +9,9 @@ use breadcrumbs::Breadcrumbs;
 use collections::VecDeque;
 use editor::{Editor, MultiBuffer};
 use gpui::{
-    actions, point, px, AppContext, Context, FocusableView, PromptLevel, TitlebarOptions, View,
-    ViewContext, VisualContext, WindowBounds, WindowKind, WindowOptions,
+    actions, point, px, AppContext, Context, FocusableView, PromptLevel, SharedString,
+    TitlebarOptions, View, ViewContext, VisualContext, WindowBounds, WindowContext, WindowKind,
+    WindowOptions,
 };
 pub use only_instance::*;
 pub use open_listener::*;
@@ -26,6 +27,7 @@ use search::project_search::ProjectSearchBar;
 use settings::{initial_local_settings_content, KeymapFile, Settings, SettingsStore};
 use std::{borrow::Cow, ops::Deref, path::Path, sync::Arc};
 use terminal_view::terminal_panel::{self, TerminalPanel};
+use theme::ThemeRegistry;
 use util::{
     asset_str,
     paths::{self, LOCAL_SETTINGS_RELATIVE_PATH},
@@ -35,10 +37,13 @@ use uuid::Uuid;
 use welcome::BaseKeymap;
 use workspace::Pane;
 use workspace::{
-    create_and_open_local_file, notifications::simple_message_notification::MessageNotification,
-    open_new, AppState, NewFile, NewWindow, Workspace, WorkspaceSettings,
+    create_and_open_local_file,
+    dock::Panel,
+    notifications::{notification_id, simple_message_notification::MessageNotification},
+    open_new, AppState, NewFile, NewWindow, Reload, Toast, UnsavedChangesModal, Workspace,
+    WorkspaceSettings,
 };
-use zed_actions::{OpenBrowser, OpenSettings, OpenZedUrl, Quit};
+use zed_actions::{OpenBrowser, OpenSettings, OpenZedUrl, Quit, SaveAllAndQuit};
 
 actions!(
     zed,
@@ -70,8 +75,13 @@ pub fn init(cx: &mut AppContext) {
     cx.on_action(|_: &HideOthers, cx| cx.hide_other_apps());
     cx.on_action(|_: &ShowAll, cx| cx.unhide_other_apps());
     cx.on_action(quit);
+    cx.on_action(save_all_and_quit);
 }
 
+/// Lets workspace windows be merged into native window tabs, and split back out
+/// with "Move Tab to New Window".
+const WORKSPACE_TABBING_IDENTIFIER: &str = "zed-workspace";
+
 pub fn build_window_options(
     bounds: Option<WindowBounds>,
     display_uuid: Option<Uuid>,
@@ -97,6 +107,22 @@ pub fn build_window_options(
         kind: WindowKind::Normal,
         is_movable: true,
         display_id: display.map(|display| display.id()),
+        tabbing_identifier: Some(WORKSPACE_TABBING_IDENTIFIER.into()),
+    }
+}
+
+/// Adds a panel built into the application, unless it failed to load, in which case the
+/// other panels are added without it.
+fn add_loaded_panel<T: Panel>(
+    workspace: &mut Workspace,
+    panel: anyhow::Result<View<T>>,
+    cx: &mut WindowContext,
+) {
+    if let Some(panel) = panel
+        .with_context(|| format!("failed to load the {} panel", T::persistent_name()))
+        .log_err()
+    {
+        workspace.add_panel(panel, cx);
     }
 }
 
@@ -125,13 +151,22 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
         let feedback_button =
             cx.new_view(|_| feedback::deploy_feedback_button::DeployFeedbackButton::new(workspace));
         let cursor_position = cx.new_view(|_| editor::items::CursorPosition::new());
+        let pending_keystrokes = cx.new_view(|cx| workspace::PendingKeystrokesIndicator::new(cx));
+        let settings_profile = cx.new_view(|cx| workspace::SettingsProfileIndicator::new(cx));
+        let read_only = cx.new_view(|cx| workspace::ReadOnlyIndicator::new(&workspace_handle, cx));
+        let project_sharing =
+            cx.new_view(|cx| collab_ui::ProjectSharingIndicator::new(workspace, cx));
         workspace.status_bar().update(cx, |status_bar, cx| {
             status_bar.add_left_item(diagnostic_summary, cx);
             status_bar.add_left_item(activity_indicator, cx);
+            status_bar.add_right_item(read_only, cx);
+            status_bar.add_right_item(project_sharing, cx);
+            status_bar.add_right_item(settings_profile, cx);
             status_bar.add_right_item(feedback_button, cx);
             status_bar.add_right_item(copilot, cx);
             status_bar.add_right_item(active_buffer_language, cx);
             status_bar.add_right_item(vim_mode_indicator, cx);
+            status_bar.add_right_item(pending_keystrokes, cx);
             status_bar.add_right_item(cursor_position, cx);
         });
 
@@ -169,29 +204,32 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
                 channels_panel,
                 chat_panel,
                 notification_panel,
-            ) = futures::try_join!(
+            ) = futures::join!(
                 project_panel,
                 terminal_panel,
                 assistant_panel,
                 channels_panel,
                 chat_panel,
                 notification_panel,
-            )?;
+            );
 
             workspace_handle.update(&mut cx, |workspace, cx| {
-                workspace.add_panel(project_panel, cx);
-                workspace.add_panel(terminal_panel, cx);
-                workspace.add_panel(assistant_panel, cx);
-                workspace.add_panel(channels_panel, cx);
-                workspace.add_panel(chat_panel, cx);
-                workspace.add_panel(notification_panel, cx);
+                add_loaded_panel(workspace, project_panel, cx);
+                add_loaded_panel(workspace, terminal_panel, cx);
+                add_loaded_panel(workspace, assistant_panel, cx);
+                add_loaded_panel(workspace, channels_panel, cx);
+                add_loaded_panel(workspace, chat_panel, cx);
+                add_loaded_panel(workspace, notification_panel, cx);
                 cx.focus_self();
-            })
+            })?;
+
+            workspace::panels::load_registered_panels(workspace_handle, cx).await
         })
         .detach();
 
         workspace
             .register_action(about)
+            .register_action(reload)
             .register_action(|_, _: &Minimize, cx| {
                 cx.minimize_window();
             })
@@ -419,6 +457,50 @@ fn quit(_: &Quit, cx: &mut AppContext) {
             }
         }
 
+        // When several items have unsaved changes, ask about all of them at once,
+        // in the active window, rather than prompting window by window.
+        let mut unsaved_groups = Vec::new();
+        for window in &workspace_windows {
+            if let Some((label, items)) = window
+                .update(&mut cx, |workspace, cx| {
+                    (window_label(workspace, cx), workspace.unsaved_items(cx))
+                })
+                .log_err()
+            {
+                if !items.is_empty() {
+                    unsaved_groups.push((*window, label, items));
+                }
+            }
+        }
+        let unsaved_count = unsaved_groups
+            .iter()
+            .map(|(_, _, items)| items.len())
+            .sum::<usize>();
+        if let (true, Some(workspace)) = (unsaved_count > 1, workspace_windows.first().copied()) {
+            let groups = unsaved_groups
+                .iter()
+                .map(|(_, label, items)| (label.clone(), items.clone()))
+                .collect::<Vec<_>>();
+            let answer = workspace.update(&mut cx, |workspace, cx| {
+                UnsavedChangesModal::prompt_for_quit(workspace, &groups, cx)
+            })?;
+            let Some(item_ids) = answer.await.ok().flatten() else {
+                return Ok(());
+            };
+            for (window, _, _) in unsaved_groups {
+                let saved = window
+                    .update(&mut cx, |workspace, cx| {
+                        workspace.save_items_before_quit(item_ids.clone(), cx)
+                    })?
+                    .await?;
+                if !saved {
+                    return Ok(());
+                }
+            }
+            cx.update(|cx| cx.quit())?;
+            return Ok(());
+        }
+
         // If the user cancels any save prompt, then keep the app open.
         for window in workspace_windows {
             if let Some(should_close) = window
@@ -438,6 +520,52 @@ fn quit(_: &Quit, cx: &mut AppContext) {
     .detach_and_log_err(cx);
 }
 
+/// Saves every dirty item in every window without asking which ones to keep,
+/// then quits. Quitting is cancelled if any item fails to save.
+fn save_all_and_quit(_: &SaveAllAndQuit, cx: &mut AppContext) {
+    cx.spawn(|mut cx| async move {
+        let workspace_windows = cx.update(|cx| {
+            cx.windows()
+                .into_iter()
+                .filter_map(|window| window.downcast::<Workspace>())
+                .collect::<Vec<_>>()
+        })?;
+        for window in workspace_windows {
+            let saved = window
+                .update(&mut cx, |workspace, cx| {
+                    let item_ids = workspace
+                        .unsaved_items(cx)
+                        .iter()
+                        .map(|item| item.item_id())
+                        .collect();
+                    workspace.save_items_before_quit(item_ids, cx)
+                })?
+                .await?;
+            if !saved {
+                return Ok(());
+            }
+        }
+        cx.update(|cx| cx.quit())?;
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// How a window is referred to when listing its unsaved items before quitting.
+fn window_label(workspace: &Workspace, cx: &AppContext) -> SharedString {
+    let root_names = workspace
+        .project()
+        .read(cx)
+        .visible_worktrees(cx)
+        .map(|worktree| worktree.read(cx).root_name().to_string())
+        .collect::<Vec<_>>();
+    if root_names.is_empty() {
+        "empty project".into()
+    } else {
+        root_names.join(", ").into()
+    }
+}
+
 fn open_log_file(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
     const MAX_LINES: usize = 1000;
     workspace
@@ -516,25 +644,97 @@ pub fn handle_keymap_file_changes(
                 _ = base_keymap_rx.next() => {}
                 user_keymap_content = user_keymap_file_rx.next() => {
                     if let Some(user_keymap_content) = user_keymap_content {
-                        if let Some(keymap_content) = KeymapFile::parse(&user_keymap_content).log_err() {
-                            user_keymap = keymap_content;
-                        } else {
-                            continue
+                        match KeymapFile::parse(&user_keymap_content) {
+                            Ok(keymap_content) => user_keymap = keymap_content,
+                            Err(error) => {
+                                log::error!("failed to parse user keymap: {error:?}");
+                                cx.update(|cx| notify_of_keymap_error(Some(&error), cx)).ok();
+                                continue;
+                            }
                         }
                     }
                 }
             }
-            cx.update(|cx| reload_keymaps(cx, &user_keymap)).ok();
+            cx.update(|cx| {
+                let result = reload_keymaps(cx, &user_keymap);
+                notify_of_keymap_error(result.as_ref().err(), cx);
+            })
+            .ok();
         }
     })
     .detach();
 }
 
-fn reload_keymaps(cx: &mut AppContext, keymap_content: &KeymapFile) {
+/// Reloads the user themes, settings and keymap from disk, then rebuilds the
+/// window from its serialized state.
+fn reload(workspace: &mut Workspace, _: &Reload, cx: &mut ViewContext<Workspace>) {
+    let fs = workspace.app_state().fs.clone();
+    cx.spawn(|workspace, mut cx| async move {
+        let theme_registry = cx.update(|cx| ThemeRegistry::global(cx).clone())?;
+        theme_registry
+            .load_user_themes(&paths::THEMES_DIR, fs.clone())
+            .await
+            .log_err();
+
+        if let Ok(settings_content) = fs.load(&paths::SETTINGS).await {
+            cx.update_global(|store: &mut SettingsStore, cx| {
+                store.set_user_settings(&settings_content, cx).log_err();
+            })?;
+        }
+
+        if let Ok(keymap_content) = fs.load(&paths::KEYMAP).await {
+            cx.update(|cx| {
+                let result = KeymapFile::parse(&keymap_content)
+                    .and_then(|keymap| reload_keymaps(cx, &keymap));
+                notify_of_keymap_error(result.as_ref().err(), cx);
+            })?;
+        }
+
+        workspace
+            .update(&mut cx, |workspace, cx| workspace.reload(cx))?
+            .await
+    })
+    .detach_and_log_err(cx);
+}
+
+fn reload_keymaps(cx: &mut AppContext, keymap_content: &KeymapFile) -> anyhow::Result<()> {
     cx.clear_key_bindings();
     load_default_keymap(cx);
-    keymap_content.clone().add_to_cx(cx).log_err();
+    let result = keymap_content.clone().add_to_cx(cx);
+    if let Err(error) = &result {
+        log::error!("failed to load user keymap: {error:?}");
+    }
     cx.set_menus(app_menus());
+    result
+}
+
+/// The toast reporting that the user keymap could not be loaded, identified with
+/// [`notification_id`].
+struct KeymapErrorToast;
+
+/// Shows a toast in every workspace when the user keymap could not be loaded,
+/// or dismisses it once the keymap loads successfully again.
+fn notify_of_keymap_error(error: Option<&anyhow::Error>, cx: &mut AppContext) {
+    let toast_id = notification_id::<KeymapErrorToast>();
+    for window in cx.windows() {
+        let Some(workspace) = window.downcast::<Workspace>() else {
+            continue;
+        };
+        workspace
+            .update(cx, |workspace, cx| match error {
+                Some(error) => {
+                    workspace.show_toast(
+                        Toast::new(toast_id, format!("Failed to load your keymap: {error}"))
+                            .on_click("Open Keymap", |cx| {
+                                cx.dispatch_action(Box::new(OpenKeymap));
+                            }),
+                        cx,
+                    )
+                }
+                None => workspace.dismiss_toast(toast_id, cx),
+            })
+            .ok();
+    }
 }
 
 pub fn load_default_keymap(cx: &mut AppContext) {
@@ -738,7 +938,7 @@ mod tests {
         actions, Action, AnyWindowHandle, AppContext, AssetSource, Entity, TestAppContext,
         VisualTestContext, WindowHandle,
     };
-    use language::LanguageRegistry;
+    use language::{LanguageRegistry, Point};
     use project::{project_settings::ProjectSettings, Project, ProjectPath};
     use serde_json::json;
     use settings::{handle_settings_file_changes, watch_config_file, SettingsStore};
@@ -970,6 +1170,250 @@ mod tests {
         assert_eq!(cx.update(|cx| cx.windows().len()), 0);
     }
 
+    #[gpui::test]
+    async fn test_quit_with_unsaved_items_in_several_windows(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/a", json!({"a1.txt": "a1", "a2.txt": "a2"}))
+            .await;
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/b", json!({"b.txt": "b"}))
+            .await;
+        let (window_a, _) = open_and_edit("/a", &["a1.txt", "a2.txt"], &app_state, cx).await;
+        let (window_b, _) = open_and_edit("/b", &["b.txt"], &app_state, cx).await;
+
+        // A single modal in the active window lists the unsaved items of every window.
+        window_b.update(cx, |_, cx| cx.activate_window()).unwrap();
+        cx.update(|cx| quit(&Quit, cx));
+        cx.run_until_parked();
+        assert!(!cx.has_pending_prompt());
+        assert!(window_a
+            .update(cx, |workspace, cx| workspace
+                .active_modal::<UnsavedChangesModal>(cx)
+                .is_none())
+            .unwrap());
+        let modal = window_b
+            .update(cx, |workspace, cx| {
+                workspace.active_modal::<UnsavedChangesModal>(cx)
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            cx.read(|cx| modal.read(cx).item_labels()),
+            [
+                (Some("b".into()), "b.txt".into()),
+                (Some("a".into()), "a1.txt".into()),
+                (Some("a".into()), "a2.txt".into()),
+            ]
+        );
+
+        // Only the items left selected are saved before quitting.
+        window_b
+            .update(cx, |_, cx| {
+                modal.update(cx, |modal, cx| modal.toggle_item(2, cx))
+            })
+            .unwrap();
+        cx.dispatch_action(window_b.into(), menu::Confirm);
+        cx.run_until_parked();
+        assert!(cx.did_quit());
+        assert_eq!(
+            app_state.fs.load(Path::new("/a/a1.txt")).await.unwrap(),
+            "EDITa1"
+        );
+        assert_eq!(
+            app_state.fs.load(Path::new("/a/a2.txt")).await.unwrap(),
+            "a2"
+        );
+        assert_eq!(
+            app_state.fs.load(Path::new("/b/b.txt")).await.unwrap(),
+            "EDITb"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_cancelling_quit_with_unsaved_items(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/a", json!({"a1.txt": "a1", "a2.txt": "a2"}))
+            .await;
+        let (window, editors) = open_and_edit("/a", &["a1.txt", "a2.txt"], &app_state, cx).await;
+
+        cx.update(|cx| quit(&Quit, cx));
+        cx.run_until_parked();
+        assert!(window
+            .update(cx, |workspace, cx| workspace
+                .active_modal::<UnsavedChangesModal>(cx)
+                .is_some())
+            .unwrap());
+
+        // Cancelling keeps the app open without saving anything.
+        cx.dispatch_action(window.into(), menu::Cancel);
+        cx.run_until_parked();
+        assert!(!cx.did_quit());
+        assert!(window
+            .update(cx, |workspace, cx| workspace
+                .active_modal::<UnsavedChangesModal>(cx)
+                .is_none())
+            .unwrap());
+        assert!(cx.read(|cx| editors.iter().all(|editor| editor.read(cx).is_dirty(cx))));
+        assert_eq!(
+            app_state.fs.load(Path::new("/a/a1.txt")).await.unwrap(),
+            "a1"
+        );
+        assert_eq!(
+            app_state.fs.load(Path::new("/a/a2.txt")).await.unwrap(),
+            "a2"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_save_all_and_quit_aborts_on_save_failure(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/root", json!({"a.txt": "a", "dir": {"b.txt": "b"}}))
+            .await;
+        let (_, editors) = open_and_edit("/root", &["a.txt", "dir/b.txt"], &app_state, cx).await;
+
+        // Replace b.txt's directory with a file behind the worktree's back,
+        // so that writing b.txt fails.
+        app_state.fs.as_fake().pause_events();
+        app_state
+            .fs
+            .remove_dir(
+                Path::new("/root/dir"),
+                fs::RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: false,
+                },
+            )
+            .await
+            .unwrap();
+        app_state
+            .fs
+            .as_fake()
+            .insert_file("/root/dir", String::new())
+            .await;
+
+        cx.update(|cx| save_all_and_quit(&SaveAllAndQuit, cx));
+        cx.run_until_parked();
+        assert!(!cx.did_quit());
+        assert_eq!(
+            app_state.fs.load(Path::new("/root/a.txt")).await.unwrap(),
+            "EDITa"
+        );
+        assert!(cx.read(|cx| editors[1].read(cx).is_dirty(cx)));
+    }
+
+    #[gpui::test]
+    async fn test_reload_restores_layout_and_items(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/root", json!({"a.txt": "a", "b.txt": "b", "c.txt": "c"}))
+            .await;
+        let (window, _) = cx
+            .update(|cx| open_paths(&[PathBuf::from("/root")], &app_state, None, cx))
+            .await
+            .unwrap();
+        for file_name in ["a.txt", "b.txt"] {
+            window
+                .update(cx, |workspace, cx| {
+                    workspace.open_abs_path(Path::new("/root").join(file_name), true, cx)
+                })
+                .unwrap()
+                .await
+                .unwrap();
+        }
+        cx.dispatch_action(window.into(), pane::SplitRight);
+        window
+            .update(cx, |workspace, cx| {
+                workspace.open_abs_path(PathBuf::from("/root/c.txt"), true, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        // The open files of every pane, and the index of the active pane.
+        let layout = |cx: &mut TestAppContext| {
+            window
+                .read_with(cx, |workspace, cx| {
+                    let panes = workspace
+                        .panes()
+                        .iter()
+                        .map(|pane| {
+                            let pane = pane.read(cx);
+                            let paths = pane
+                                .items()
+                                .map(|item| item.project_path(cx).unwrap().path)
+                                .collect::<Vec<_>>();
+                            (paths, pane.active_item_index())
+                        })
+                        .collect::<Vec<_>>();
+                    let active_pane = workspace
+                        .panes()
+                        .iter()
+                        .position(|pane| pane == workspace.active_pane());
+                    (panes, active_pane)
+                })
+                .unwrap()
+        };
+        let layout_before_reload = layout(cx);
+        assert_eq!(layout_before_reload.0.len(), 2);
+        let workspace_before_reload = window.update(cx, |_, cx| cx.view().entity_id()).unwrap();
+
+        cx.dispatch_action(window.into(), Reload);
+        cx.run_until_parked();
+
+        // The window hosts a new workspace with the same panes and items.
+        assert_ne!(
+            window.update(cx, |_, cx| cx.view().entity_id()).unwrap(),
+            workspace_before_reload
+        );
+        assert_eq!(layout(cx), layout_before_reload);
+    }
+
+    /// Opens `dir` in a new window and makes an unsaved edit to each of the given files.
+    async fn open_and_edit(
+        dir: &str,
+        file_names: &[&str],
+        app_state: &Arc<AppState>,
+        cx: &mut TestAppContext,
+    ) -> (WindowHandle<Workspace>, Vec<View<Editor>>) {
+        let (window, _) = cx
+            .update(|cx| open_paths(&[PathBuf::from(dir)], app_state, None, cx))
+            .await
+            .unwrap();
+        let mut editors = Vec::new();
+        for file_name in file_names {
+            let editor = window
+                .update(cx, |workspace, cx| {
+                    workspace.open_abs_path(Path::new(dir).join(file_name), true, cx)
+                })
+                .unwrap()
+                .await
+                .unwrap()
+                .downcast::<Editor>()
+                .unwrap();
+            window
+                .update(cx, |_, cx| {
+                    editor.update(cx, |editor, cx| editor.insert("EDIT", cx))
+                })
+                .unwrap();
+            editors.push(editor);
+        }
+        (window, editors)
+    }
+
     #[gpui::test]
     async fn test_new_empty_workspace(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
@@ -2392,6 +2836,106 @@ mod tests {
                 .unwrap()
         }
     }
+
+    #[gpui::test]
+    async fn test_reopening_items_closed_in_removed_panes(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                "/root",
+                json!({
+                    "a": {
+                        "file1": "",
+                        "file2": "one\ntwo\nthree\n",
+                    },
+                }),
+            )
+            .await;
+
+        let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project, cx));
+        let entries = cx.update(|cx| workspace.root(cx).unwrap().file_project_paths(cx));
+        let file1 = entries[0].clone();
+        let file2 = entries[1].clone();
+
+        workspace
+            .update(cx, |w, cx| w.open_path(file1.clone(), None, true, cx))
+            .unwrap()
+            .await
+            .unwrap();
+        let right_pane = workspace
+            .update(cx, |w, cx| {
+                w.split_pane(w.active_pane().clone(), SplitDirection::Right, cx)
+            })
+            .unwrap();
+        let editor = workspace
+            .update(cx, |w, cx| {
+                w.open_path(file2.clone(), Some(right_pane.downgrade()), true, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        workspace
+            .update(cx, |_, cx| {
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(None, cx, |s| {
+                        s.select_ranges([Point::new(1, 2)..Point::new(1, 2)])
+                    })
+                })
+            })
+            .unwrap();
+
+        // Closing the last item of the right pane removes the pane, along with its history.
+        workspace
+            .update(cx, |_, cx| {
+                right_pane.update(cx, |pane, cx| {
+                    pane.close_item_by_id(editor.item_id(), SaveIntent::Close, cx)
+                })
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        workspace
+            .read_with(cx, |workspace, _| assert_eq!(workspace.panes().len(), 1))
+            .unwrap();
+
+        // The item can still be reopened from the remaining pane, at the same position.
+        workspace
+            .update(cx, Workspace::reopen_closed_item)
+            .unwrap()
+            .await
+            .unwrap();
+        workspace
+            .read_with(cx, |workspace, cx| {
+                let item = workspace.active_item(cx).unwrap();
+                assert_eq!(item.project_path(cx), Some(file2.clone()));
+                let editor = item.downcast::<Editor>().unwrap();
+                assert_eq!(
+                    editor.read(cx).selections.newest::<Point>(cx).head(),
+                    Point::new(1, 2)
+                );
+            })
+            .unwrap();
+
+        // Reopening past the last closed item is a no-op.
+        workspace
+            .update(cx, Workspace::reopen_closed_item)
+            .unwrap()
+            .await
+            .unwrap();
+        workspace
+            .read_with(cx, |workspace, cx| {
+                let item = workspace.active_item(cx).unwrap();
+                assert_eq!(item.project_path(cx), Some(file2.clone()));
+            })
+            .unwrap();
+    }
+
     fn init_keymap_test(cx: &mut TestAppContext) -> Arc<AppState> {
         cx.update(|cx| {
             let app_state = AppState::test(cx);
@@ -2672,6 +3216,52 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_keymap_error_toast(cx: &mut gpui::TestAppContext) {
+        let executor = cx.executor();
+        let app_state = init_keymap_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+
+        app_state
+            .fs
+            .save(
+                "/keymap.json".as_ref(),
+                &r#"[{ "bindings": { "backspace": "#.into(),
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        cx.update(|cx| {
+            let keymap_rx = watch_config_file(
+                &executor,
+                app_state.fs.clone(),
+                PathBuf::from("/keymap.json"),
+            );
+            handle_keymap_file_changes(keymap_rx, cx);
+        });
+        executor.run_until_parked();
+
+        let toast_id = notification_id::<KeymapErrorToast>();
+        let is_showing_toast = |cx: &mut TestAppContext| {
+            workspace
+                .read_with(cx, |workspace, _| {
+                    workspace.is_showing_notification::<MessageNotification>(toast_id)
+                })
+                .unwrap()
+        };
+        assert!(is_showing_toast(cx));
+
+        // The toast goes away once the keymap is fixed.
+        app_state
+            .fs
+            .save("/keymap.json".as_ref(), &"[]".into(), Default::default())
+            .await
+            .unwrap();
+        executor.run_until_parked();
+        assert!(!is_showing_toast(cx));
+    }
+
     #[gpui::test]
     fn test_bundled_settings_and_themes(cx: &mut AppContext) {
         cx.text_system()